@@ -5,7 +5,7 @@ mod stop;
 mod test_notifications;
 mod validate_config;
 
-pub use rules::{rules_info_command, rules_list_command, rules_test_command};
+pub use rules::{rules_info_command, rules_list_command, rules_test_command, rules_toggle_command};
 pub use start::start_command;
 pub use status::status_command;
 pub use stop::stop_command;
@@ -1,12 +1,14 @@
-use anyhow::Result;
+use crate::config::AppConfig;
+use anyhow::{Context, Result};
 use console::style;
 use solana_sdk::pubkey::Pubkey;
+use std::path::PathBuf;
 use std::str::FromStr;
 use watchtower_engine::{
-    FailureRateRule, LargeTransactionRule, LiquidityDropRule, OracleDeviationRule, Rule,
-    RuleContext,
+    FailureRateRule, LargeTransactionRule, LiquidityDropRule, OracleDeviationRule,
+    ProgramUpgradeRule, Rule, RuleContext, RuleStateStore,
 };
-use watchtower_subscriber::{EventData, EventType, ProgramEvent};
+use watchtower_subscriber::{EventData, EventType, ProgramEvent, UpgradeKind};
 
 pub async fn rules_list_command() -> Result<()> {
     println!("{}", style("Available Monitoring Rules:").bold());
@@ -33,6 +35,16 @@ pub async fn rules_list_command() -> Result<()> {
             "High Failure Rate Detection",
             "Monitors transaction failure rates",
         ),
+        (
+            "program_upgrade",
+            "Program Upgrade Detection",
+            "Flags program upgrades and upgrade authority changes",
+        ),
+        (
+            "mint_authority",
+            "Mint Authority Monitoring",
+            "Flags mint/freeze authority changes and unexpected minting",
+        ),
     ];
 
     for (name, title, description) in rules {
@@ -58,6 +70,8 @@ pub async fn rules_info_command(rule_name: String) -> Result<()> {
         "large_transaction" => show_large_transaction_info(),
         "oracle_deviation" => show_oracle_deviation_info(),
         "failure_rate" => show_failure_rate_info(),
+        "program_upgrade" => show_program_upgrade_info(),
+        "mint_authority" => show_mint_authority_info(),
         _ => {
             println!(
                 "{} Unknown rule: {}",
@@ -84,6 +98,7 @@ pub async fn rules_test_command(rule_name: String) -> Result<()> {
         "large_transaction" => test_large_transaction_rule().await,
         "oracle_deviation" => test_oracle_deviation_rule().await,
         "failure_rate" => test_failure_rate_rule().await,
+        "program_upgrade" => test_program_upgrade_rule().await,
         _ => {
             println!(
                 "{} Unknown rule: {}",
@@ -95,6 +110,43 @@ pub async fn rules_test_command(rule_name: String) -> Result<()> {
     }
 }
 
+pub async fn rules_toggle_command(
+    config_path: PathBuf,
+    rule_name: String,
+    enabled: bool,
+) -> Result<()> {
+    let config = AppConfig::load_with_overrides(&config_path)
+        .await
+        .with_context(|| format!("Failed to load config from {}", config_path.display()))?;
+
+    let state_path = config.engine.rule_state_path.clone().context(
+        "engine.rule_state_path must be configured to persist rule enable/disable state",
+    )?;
+
+    let mut store = RuleStateStore::open(&state_path)
+        .with_context(|| format!("Failed to open rule state file {}", state_path.display()))?;
+    store
+        .set(&rule_name, enabled)
+        .context("Failed to save rule state")?;
+
+    println!(
+        "{} Rule {} {}",
+        style("✓").green().bold(),
+        style(&rule_name).bold(),
+        if enabled { "enabled" } else { "disabled" }
+    );
+    println!(
+        "{}",
+        style(format!(
+            "State saved to {}; a running instance applies it on restart",
+            state_path.display()
+        ))
+        .dim()
+    );
+
+    Ok(())
+}
+
 fn show_liquidity_drop_info() {
     println!("{}", style("Liquidity Drop Rule").bold().cyan());
     println!("{}", "─".repeat(50));
@@ -135,7 +187,9 @@ fn show_oracle_deviation_info() {
     println!();
     println!("{}", style("Parameters:").bold());
     println!("• threshold_percentage: Price deviation threshold (default: 5%)");
-    println!("• reference_oracle: Reference oracle for comparison");
+    println!(
+        "• reference_oracle: Reference oracle for comparison (Pyth feed when [pyth] is configured)"
+    );
     println!();
     println!("{}", style("Triggers when:").bold());
     println!("Price deviates more than threshold from reference oracle");
@@ -157,6 +211,47 @@ fn show_failure_rate_info() {
     println!("Failure rate exceeds threshold over the time window");
 }
 
+fn show_program_upgrade_info() {
+    println!("{}", style("Program Upgrade Rule").bold().cyan());
+    println!("{}", "─".repeat(50));
+    println!("{}", style("Description:").bold());
+    println!("Raises Critical alerts whenever a monitored program is");
+    println!("upgraded or its upgrade authority changes.");
+    println!();
+    println!("{}", style("Parameters:").bold());
+    println!("None - every upgrade is alerted on");
+    println!();
+    println!("{}", style("Triggers when:").bold());
+    println!("• The program code is upgraded");
+    println!("• The upgrade authority is changed");
+    println!("• The upgrade authority is set to None (program made immutable)");
+}
+
+fn show_mint_authority_info() {
+    println!("{}", style("Mint Authority Rule").bold().cyan());
+    println!("{}", "─".repeat(50));
+    println!("{}", style("Description:").bold());
+    println!("Watches configured SPL token mints for authority changes");
+    println!("and supply inflation, common rug pull indicators.");
+    println!();
+    println!("{}", style("Parameters ([[mint_authority.mints]]):").bold());
+    println!("• mint: Mint address to watch");
+    println!("• label: Display name used in alerts (optional)");
+    println!("• max_mint_amount: Largest expected MintTo in raw units (optional)");
+    println!("• max_supply: Absolute supply cap in raw units (optional)");
+    println!("• max_supply_growth_pct: Largest expected supply growth (optional)");
+    println!("• supply_window_seconds: Supply growth window, on [mint_authority] (default: 3600)");
+    println!();
+    println!("{}", style("Triggers when:").bold());
+    println!("• The mint or freeze authority is changed or revoked (Critical)");
+    println!("• A MintTo exceeds max_mint_amount, or any MintTo if unset (High)");
+    println!("• Total supply exceeds max_supply (Critical)");
+    println!("• Supply grows more than max_supply_growth_pct within the window (High)");
+    println!();
+    println!("Supply limits subscribe to the mint accounts and run as the");
+    println!("supply_inflation rule.");
+}
+
 async fn test_liquidity_drop_rule() -> Result<()> {
     let rule = LiquidityDropRule::new(10.0, 300, 1000000);
 
@@ -290,7 +385,7 @@ async fn test_failure_rate_rule() -> Result<()> {
         )
         .with_slot(12347 + i as u64);
 
-        context.recent_events.push(test_event);
+        context.recent_events.push_back(test_event);
     }
 
     // Create a current transaction event to evaluate
@@ -323,3 +418,37 @@ async fn test_failure_rate_rule() -> Result<()> {
 
     Ok(())
 }
+
+async fn test_program_upgrade_rule() -> Result<()> {
+    let rule = ProgramUpgradeRule::new();
+    let program_id = Pubkey::from_str("11111111111111111111111111111112").unwrap();
+
+    println!("{}", style("Creating test program upgrade event...").dim());
+
+    let test_event = ProgramEvent::new(
+        program_id,
+        "Test Program".to_string(),
+        EventType::ProgramUpgrade,
+        EventData::ProgramUpgrade {
+            program: Some(program_id),
+            kind: UpgradeKind::Upgrade,
+            new_authority: None,
+        },
+    )
+    .with_slot(12345);
+
+    let result = rule.evaluate(&test_event, &RuleContext::default()).await;
+
+    if result.triggered {
+        println!("{} Rule triggered alert:", style("✓").green().bold());
+        println!("  Severity: {:?}", result.severity);
+        if let Some(message) = &result.message {
+            println!("  Message: {}", message);
+        }
+        println!("  Suggested actions: {:?}", result.suggested_actions);
+    } else {
+        println!("{} Rule did not trigger with test data", style("ⓘ").blue());
+    }
+
+    Ok(())
+}
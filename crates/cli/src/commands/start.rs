@@ -5,26 +5,42 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::signal;
 use tracing::{error, info, warn};
-use watchtower_engine::{AlertManager, MetricsCollector, MonitoringEngine};
+use watchtower_engine::{
+    AlertManager, AlertManagerConfig, ControlService, DeliveryLog, MetricsCollector,
+    MonitoringEngine, PythPriceFeed, TvlTracker,
+};
 use watchtower_notifier::NotificationManager;
-use watchtower_subscriber::SolanaWebSocketClient;
+use watchtower_subscriber::{SolanaWebSocketClient, SubscriptionHandle};
 
 pub async fn start_command(
     config_path: PathBuf,
     daemon: bool,
     dashboard_port: Option<u16>,
     metrics_port: u16,
+    dry_run: bool,
 ) -> Result<()> {
     println!("{}", style("Loading configuration...").cyan());
 
     // Load configuration
     let mut config = AppConfig::load_with_overrides(&config_path)
+        .await
         .with_context(|| format!("Failed to load config from {}", config_path.display()))?;
 
     // Override ports from command line if provided
     if let Some(port) = dashboard_port {
         config.dashboard.port = port;
     }
+    if dry_run {
+        config
+            .notifier
+            .capture
+            .get_or_insert_with(Default::default)
+            .dry_run = true;
+        println!(
+            "{}",
+            style("Dry run: notifications will be captured, not sent").yellow()
+        );
+    }
 
     println!("{}", style("✓ Configuration loaded successfully").green());
 
@@ -37,33 +53,105 @@ pub async fn start_command(
     println!("{}", style("Initializing monitoring components...").cyan());
 
     // Create metrics collector
-    let metrics = Arc::new(MetricsCollector::new().context("Failed to create metrics collector")?);
+    let metrics = Arc::new(
+        MetricsCollector::with_config(config.engine.metrics.clone())
+            .context("Failed to create metrics collector")?,
+    );
 
-    // Create alert manager
-    let alert_manager = Arc::new(AlertManager::new());
+    // Restore sliding windows from the previous run
+    if let Some(path) = &config.engine.window_state_path {
+        match metrics.load_windows(path) {
+            Ok(restored) => info!(
+                "Restored {} sliding windows from {}",
+                restored,
+                path.display()
+            ),
+            Err(e) => warn!(
+                "Failed to restore sliding windows from {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    // Create alert manager, restoring the delivery history of the previous run
+    let mut alert_manager = AlertManager::new();
+    if let Some(path) = &config.engine.delivery_history_path {
+        match DeliveryLog::open(path, AlertManagerConfig::default().max_delivery_records) {
+            Ok(deliveries) => alert_manager = alert_manager.with_delivery_log(deliveries),
+            Err(e) => warn!(
+                "Failed to load delivery history from {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    let alert_manager = Arc::new(alert_manager);
+    for sink_config in &config.alert_sinks {
+        let sink = sink_config
+            .build()
+            .with_context(|| format!("Failed to create alert sink {}", sink_config.name))?;
+        alert_manager.add_sink(sink).await;
+    }
 
     // Create monitoring engine
     let engine = Arc::new(MonitoringEngine::new(
         metrics.clone(),
         alert_manager.clone(),
-        config.engine.clone(),
+        config.engine_config(),
     ));
 
     // Create notification manager
     let notification_manager = Arc::new(
         NotificationManager::new(config.notifier.clone())
             .await
-            .context("Failed to create notification manager")?,
+            .context("Failed to create notification manager")?
+            .with_alert_manager(alert_manager.clone())
+            .with_metrics_registry(&metrics.registry())
+            .context("Failed to register notification metrics")?,
     );
+    notification_manager.start_background_tasks();
 
     // Create WebSocket subscriber
-    let mut subscriber = SolanaWebSocketClient::new(config.subscriber.clone())
+    let mut subscriber = SolanaWebSocketClient::new(config.subscriber_config())
         .context("Failed to create WebSocket client")?;
 
     println!("{}", style("✓ Components initialized").green());
 
     // Register built-in rules
-    register_builtin_rules(&engine).await?;
+    register_builtin_rules(&engine, &config).await?;
+
+    // Start Pyth price feed if configured
+    if let Some(pyth_config) = &config.pyth {
+        let price_feed = Arc::new(
+            PythPriceFeed::new(pyth_config.clone(), metrics.clone())
+                .context("Failed to create Pyth price feed")?,
+        );
+        price_feed.start();
+        println!(
+            "{} {} Pyth feed(s)",
+            style("✓ Price feed started for").green(),
+            pyth_config.feeds.len()
+        );
+    }
+
+    // Start TVL tracker if configured
+    if let Some(tvl_config) = &config.tvl {
+        let tvl_tracker = Arc::new(
+            TvlTracker::new(
+                tvl_config.clone(),
+                config.subscriber.rpc_url.to_string(),
+                metrics.clone(),
+            )
+            .context("Failed to create TVL tracker")?,
+        );
+        tvl_tracker.start();
+        println!(
+            "{} {} program(s)",
+            style("✓ TVL tracking started for").green(),
+            tvl_config.programs.len()
+        );
+    }
 
     // Start the monitoring engine
     engine
@@ -90,15 +178,40 @@ pub async fn start_command(
         }
     });
 
+    // Close incidents on channels that track them when alerts are resolved
+    let mut resolution_receiver = alert_manager.subscribe_resolutions();
+    let notification_manager_clone = notification_manager.clone();
+    tokio::spawn(async move {
+        while let Ok(alert) = resolution_receiver.recv().await {
+            if let Err(e) = notification_manager_clone.send_resolution(&alert).await {
+                error!("Failed to send resolution: {}", e);
+            }
+        }
+    });
+
     // Start dashboard if enabled
     if config.dashboard.enabled {
         let dashboard_config = config.dashboard.clone();
+        // Daemon mode may change the working directory
+        let dashboard_config_path =
+            std::fs::canonicalize(&config_path).unwrap_or_else(|_| config_path.clone());
         let engine_clone = engine.clone();
         let alert_manager_clone = alert_manager.clone();
+        let metrics_clone = metrics.clone();
+        let subscriber_handle = subscriber.handle();
+        let notification_manager_clone = notification_manager.clone();
 
         tokio::spawn(async move {
-            if let Err(e) =
-                start_dashboard(dashboard_config, engine_clone, alert_manager_clone).await
+            if let Err(e) = start_dashboard(
+                dashboard_config,
+                dashboard_config_path,
+                engine_clone,
+                alert_manager_clone,
+                metrics_clone,
+                subscriber_handle,
+                notification_manager_clone,
+            )
+            .await
             {
                 error!("Dashboard error: {}", e);
             }
@@ -108,26 +221,56 @@ pub async fn start_command(
             "{} {}",
             style("✓ Dashboard started on").green(),
             style(format!(
-                "http://{}:{}",
-                config.dashboard.host, config.dashboard.port
+                "{}://{}:{}{}",
+                if config.dashboard.tls_cert.is_some() {
+                    "https"
+                } else {
+                    "http"
+                },
+                config.dashboard.host,
+                config.dashboard.port,
+                watchtower_dashboard::normalize_base_path(&config.dashboard.base_path)
             ))
             .bold()
         );
     }
 
-    // Start metrics server
-    let metrics_clone = metrics.clone();
-    tokio::spawn(async move {
-        if let Err(e) = start_metrics_server(metrics_clone, metrics_port).await {
-            error!("Metrics server error: {}", e);
-        }
-    });
+    // Start gRPC control server if enabled
+    if config.grpc.enabled {
+        let grpc_config = config.grpc.clone();
+        let addr = grpc_config
+            .validate()
+            .context("Invalid gRPC configuration")?;
+        let service = ControlService::new(engine.clone());
 
-    println!(
-        "{} {}",
-        style("✓ Metrics server started on").green(),
-        style(format!("http://127.0.0.1:{}/metrics", metrics_port)).bold()
-    );
+        tokio::spawn(async move {
+            if let Err(e) = service.serve(&grpc_config).await {
+                error!("gRPC control server error: {}", e);
+            }
+        });
+
+        println!(
+            "{} {}",
+            style("✓ gRPC control API started on").green(),
+            style(addr).bold()
+        );
+    }
+
+    // Start metrics server; port 0 leaves scraping to the dashboard's /metrics
+    if metrics_port != 0 {
+        let metrics_clone = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(e) = start_metrics_server(metrics_clone, metrics_port).await {
+                error!("Metrics server error: {}", e);
+            }
+        });
+
+        println!(
+            "{} {}",
+            style("✓ Metrics server started on").green(),
+            style(format!("http://127.0.0.1:{}/metrics", metrics_port)).bold()
+        );
+    }
 
     // Main event processing loop
     println!(
@@ -172,13 +315,27 @@ pub async fn start_command(
         .await
         .context("Failed to shutdown notification manager")?;
 
+    // Persist sliding windows for the next run
+    if let Some(path) = &config.engine.window_state_path {
+        match metrics.save_windows(path) {
+            Ok(saved) => info!("Saved {} sliding windows to {}", saved, path.display()),
+            Err(e) => error!(
+                "Failed to save sliding windows to {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
     println!("{}", style("✓ Watchtower stopped").green());
     Ok(())
 }
 
-async fn register_builtin_rules(engine: &MonitoringEngine) -> Result<()> {
+async fn register_builtin_rules(engine: &MonitoringEngine, config: &AppConfig) -> Result<()> {
     use watchtower_engine::{
-        FailureRateRule, LargeTransactionRule, LiquidityDropRule, OracleDeviationRule,
+        ComputeExhaustionRule, Denylist, DenylistLoader, DenylistRule, FailureRateRule,
+        InstructionSpikeRule, LargeTransactionRule, LiquidityDropRule, MintAuthorityRule,
+        OracleDeviationRule, ProgramUpgradeRule, SupplyInflationRule,
     };
 
     // Register built-in rules
@@ -188,15 +345,59 @@ async fn register_builtin_rules(engine: &MonitoringEngine) -> Result<()> {
     engine
         .add_rule(Box::new(LargeTransactionRule::new(1.0, 500000)))
         .await;
+    let oracle_rule = match config.pyth.as_ref().and_then(|p| p.reference_symbol()) {
+        Some(symbol) => OracleDeviationRule::with_pyth_reference(5.0, symbol),
+        None => OracleDeviationRule::new(5.0, "reference_oracle".to_string()),
+    };
+    engine.add_rule(Box::new(oracle_rule)).await;
     engine
-        .add_rule(Box::new(OracleDeviationRule::new(
-            5.0,
-            "reference_oracle".to_string(),
+        .add_rule(Box::new(FailureRateRule::new(
+            25.0,
+            10,
+            config.engine.metrics.failure_rate_window_seconds,
         )))
         .await;
     engine
-        .add_rule(Box::new(FailureRateRule::new(25.0, 10, 300)))
+        .add_rule(Box::new(ComputeExhaustionRule::new(
+            &config.compute_exhaustion,
+        )))
         .await;
+    engine.add_rule(Box::new(ProgramUpgradeRule::new())).await;
+    if let Some(mint_authority) = &config.mint_authority {
+        engine
+            .add_rule(Box::new(MintAuthorityRule::new(mint_authority)))
+            .await;
+        if mint_authority.mints.iter().any(|m| m.tracks_supply()) {
+            engine
+                .add_rule(Box::new(SupplyInflationRule::new(mint_authority)))
+                .await;
+        }
+    }
+    if let Some(instruction_spike) = &config.instruction_spike {
+        engine
+            .add_rule(Box::new(InstructionSpikeRule::new(instruction_spike)))
+            .await;
+    }
+
+    // Register the denylist rule and keep its lists up to date
+    if let Some(denylist_config) = &config.denylist {
+        let denylist = Arc::new(Denylist::new());
+        let loader = Arc::new(
+            DenylistLoader::new(denylist_config.clone(), denylist.clone())
+                .context("Failed to create denylist loader")?,
+        );
+        loader.refresh().await;
+        loader.start();
+
+        engine
+            .add_rule(Box::new(
+                DenylistRule::new(denylist).with_severity(denylist_config.severity),
+            ))
+            .await;
+    }
+
+    // Register user-defined rate-of-change rules, with the changes made at runtime
+    engine.load_declarative_rules(&config.rate_of_change).await;
 
     info!(
         "Registered {} built-in rules",
@@ -207,14 +408,14 @@ async fn register_builtin_rules(engine: &MonitoringEngine) -> Result<()> {
 
 async fn start_dashboard(
     config: crate::config::DashboardConfig,
+    config_path: PathBuf,
     engine: Arc<MonitoringEngine>,
     alert_manager: Arc<AlertManager>,
+    metrics: Arc<MetricsCollector>,
+    subscriber: SubscriptionHandle,
+    notifier: Arc<NotificationManager>,
 ) -> Result<()> {
     use watchtower_dashboard::{DashboardConfig as DashConfig, DashboardServer};
-    use watchtower_engine::MetricsCollector;
-
-    // Create metrics collector for dashboard
-    let metrics = Arc::new(MetricsCollector::new().context("Failed to create metrics collector")?);
 
     // Convert CLI config to dashboard config
     let dashboard_config = DashConfig {
@@ -222,10 +423,23 @@ async fn start_dashboard(
         port: config.port,
         enable_cors: config.enable_cors,
         static_dir: config.static_dir,
+        slack_signing_secret: config.slack_signing_secret,
+        auth: config.auth,
+        tls_cert: config.tls_cert,
+        tls_key: config.tls_key,
+        base_path: config.base_path,
+        metrics_auth: config.metrics_auth,
+        audit_log_path: config.audit_log_path,
+        // Settings changed in the dashboard are saved to the configuration file
+        config_path: Some(config_path.to_string_lossy().into_owned()),
+        limits: config.limits,
+        federation: config.federation,
     };
 
     // Create and start dashboard server
-    let dashboard = DashboardServer::new(dashboard_config, engine, alert_manager, metrics);
+    let dashboard = DashboardServer::new(dashboard_config, engine, alert_manager, metrics)
+        .with_subscriber(subscriber)
+        .with_notifier(notifier);
 
     dashboard
         .start()
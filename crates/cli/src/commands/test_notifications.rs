@@ -14,6 +14,7 @@ pub async fn test_notifications_command(
 
     // Load configuration
     let config = AppConfig::load_with_overrides(&config_path)
+        .await
         .with_context(|| format!("Failed to load config from {}", config_path.display()))?;
 
     println!("{}", style("✓ Configuration loaded").green());
@@ -24,7 +24,7 @@ pub async fn validate_config_command(config_path: PathBuf) -> Result<()> {
     println!("{} File exists", style("✓").green());
 
     // Try to load and parse the configuration
-    let config = match AppConfig::load_with_overrides(&config_path) {
+    let config = match AppConfig::load_with_overrides(&config_path).await {
         Ok(config) => {
             println!("{} TOML syntax is valid", style("✓").green());
             config
@@ -172,6 +172,24 @@ async fn validate_engine_config(config: &AppConfig) -> Result<()> {
         anyhow::bail!("rule_timeout cannot be zero");
     }
 
+    for (rule_name, budget) in &config.engine.rule_budgets {
+        if budget.timeout.is_some_and(|timeout| timeout.is_zero()) {
+            anyhow::bail!("rule_budgets.{}.timeout cannot be zero", rule_name);
+        }
+    }
+
+    config
+        .engine
+        .metrics
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid engine.metrics settings: {}", e))?;
+
+    config
+        .engine
+        .enrichment
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid engine.enrichment settings: {}", e))?;
+
     println!("{} Engine configuration is valid", style("✓").green());
     Ok(())
 }
@@ -1,7 +1,12 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
-use watchtower_engine::EngineConfig;
+use watchtower_dashboard::{AuthConfig, FederationConfig, RequestLimits};
+use watchtower_engine::{
+    AlertSinkConfig, ComputeExhaustionConfig, DenylistConfig, EngineConfig, GrpcConfig,
+    InstructionSpikeConfig, MintAuthorityConfig, PythConfig, RateOfChangeConfig, TvlConfig,
+};
 use watchtower_notifier::NotifierConfig;
 use watchtower_subscriber::SubscriberConfig;
 
@@ -24,6 +29,46 @@ pub struct AppConfig {
     #[serde(default)]
     pub dashboard: DashboardConfig,
 
+    /// gRPC control API configuration
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+
+    /// Alert exports independent of notification filtering
+    #[serde(default)]
+    pub alert_sinks: Vec<AlertSinkConfig>,
+
+    /// Pyth price feed configuration (optional)
+    #[serde(default)]
+    pub pyth: Option<PythConfig>,
+
+    /// TVL computation configuration (optional)
+    #[serde(default)]
+    pub tvl: Option<TvlConfig>,
+
+    /// User-defined rate-of-change rules
+    #[serde(default)]
+    pub rate_of_change: Vec<RateOfChangeConfig>,
+
+    /// Denylisted address lists (optional)
+    #[serde(default)]
+    pub denylist: Option<DenylistConfig>,
+
+    /// Mints watched for authority changes and supply inflation (optional)
+    #[serde(default)]
+    pub mint_authority: Option<MintAuthorityConfig>,
+
+    /// Compute-unit exhaustion detection
+    #[serde(default)]
+    pub compute_exhaustion: ComputeExhaustionConfig,
+
+    /// Instruction call frequency spike detection (optional)
+    #[serde(default)]
+    pub instruction_spike: Option<InstructionSpikeConfig>,
+
+    /// Per-rule parameters passed to rules through their evaluation context
+    #[serde(default)]
+    pub rules: HashMap<String, HashMap<String, serde_json::Value>>,
+
     /// General application settings
     #[serde(default)]
     pub app: AppSettings,
@@ -50,6 +95,42 @@ pub struct DashboardConfig {
 
     /// Static files directory (optional)
     pub static_dir: Option<String>,
+
+    /// Signing secret of the Slack app whose buttons call back into the dashboard
+    #[serde(default)]
+    pub slack_signing_secret: Option<String>,
+
+    /// User authentication (password logins or an SSO proxy)
+    #[serde(default)]
+    pub auth: AuthConfig,
+
+    /// PEM certificate chain for serving HTTPS (optional)
+    #[serde(default)]
+    pub tls_cert: Option<String>,
+
+    /// PEM private key of the certificate (optional)
+    #[serde(default)]
+    pub tls_key: Option<String>,
+
+    /// URL prefix when served under a path of a reverse proxy, e.g. `/watchtower`
+    #[serde(default)]
+    pub base_path: String,
+
+    /// Require authentication for Prometheus scrapes of `/metrics`
+    #[serde(default)]
+    pub metrics_auth: bool,
+
+    /// JSON lines file recording configuration changes made in the dashboard (optional)
+    #[serde(default)]
+    pub audit_log_path: Option<String>,
+
+    /// Per-IP rate limiting, request body size and request timeout
+    #[serde(default)]
+    pub limits: RequestLimits,
+
+    /// Remote watchtower dashboards combined in the fleet view
+    #[serde(default)]
+    pub federation: FederationConfig,
 }
 
 /// General application settings
@@ -89,13 +170,20 @@ impl AppConfig {
         Ok(config)
     }
 
-    /// Load configuration from environment and file
-    pub fn load_with_overrides<P: AsRef<Path>>(path: P) -> Result<Self> {
+    /// Load configuration from environment and file, resolving secret references
+    pub async fn load_with_overrides<P: AsRef<Path>>(path: P) -> Result<Self> {
         let mut config = Self::load_from_file(path)?;
 
         // Override with environment variables
         config.apply_env_overrides();
 
+        // Resolve ${ENV_VAR}, file: and vault: references in secret fields
+        config
+            .notifier
+            .resolve_secrets()
+            .await
+            .context("Failed to resolve notifier secrets")?;
+
         config.validate()?;
         Ok(config)
     }
@@ -117,9 +205,98 @@ impl AppConfig {
             .validate()
             .context("Invalid dashboard configuration")?;
 
+        // Validate gRPC config
+        if self.grpc.enabled {
+            self.grpc.validate().context("Invalid gRPC configuration")?;
+        }
+
+        // Validate alert sinks
+        for sink in &self.alert_sinks {
+            sink.validate()
+                .context("Invalid alert sink configuration")?;
+        }
+
+        // Validate Pyth config
+        if let Some(pyth) = &self.pyth {
+            pyth.validate().context("Invalid Pyth configuration")?;
+        }
+
+        // Validate TVL config
+        if let Some(tvl) = &self.tvl {
+            tvl.validate().context("Invalid TVL configuration")?;
+        }
+
+        // Validate denylist config
+        if let Some(denylist) = &self.denylist {
+            denylist
+                .validate()
+                .context("Invalid denylist configuration")?;
+        }
+
+        // Validate watched mints
+        if let Some(mint_authority) = &self.mint_authority {
+            mint_authority
+                .validate()
+                .context("Invalid mint authority configuration")?;
+        }
+
+        // Validate compute exhaustion detection
+        self.compute_exhaustion
+            .validate()
+            .context("Invalid compute exhaustion configuration")?;
+
+        // Validate instruction spike detection
+        if let Some(instruction_spike) = &self.instruction_spike {
+            instruction_spike
+                .validate()
+                .context("Invalid instruction spike configuration")?;
+        }
+
+        // Validate rate-of-change rules
+        for rule in &self.rate_of_change {
+            rule.validate()
+                .context("Invalid rate-of-change rule configuration")?;
+        }
+
         Ok(())
     }
 
+    /// Subscriber configuration with the accounts needed by rules merged in.
+    pub fn subscriber_config(&self) -> SubscriberConfig {
+        let mut subscriber = self.subscriber.clone();
+        if let Some(mint_authority) = &self.mint_authority {
+            for account in mint_authority.supply_accounts() {
+                if !subscriber.accounts.iter().any(|a| a.id == account.id) {
+                    subscriber.accounts.push(account);
+                }
+            }
+        }
+        subscriber
+    }
+
+    /// Engine configuration with the `[rules.*]` parameters and program tags
+    /// merged in.
+    pub fn engine_config(&self) -> EngineConfig {
+        let mut engine = self.engine.clone();
+        for (rule_name, params) in &self.rules {
+            engine
+                .rule_config
+                .entry(rule_name.clone())
+                .or_default()
+                .extend(params.clone());
+        }
+        for program in &self.subscriber.programs {
+            if !program.tags.is_empty() {
+                engine
+                    .program_tags
+                    .entry(program.name.clone())
+                    .or_default()
+                    .extend(program.tags.iter().cloned());
+            }
+        }
+        engine
+    }
+
     /// Apply environment variable overrides
     fn apply_env_overrides(&mut self) {
         // Override log level
@@ -161,6 +338,11 @@ impl AppConfig {
                 telegram_config.bot_token = token;
             }
         }
+
+        // Override Slack signing secret (sensitive)
+        if let Ok(secret) = std::env::var("WATCHTOWER_SLACK_SIGNING_SECRET") {
+            self.dashboard.slack_signing_secret = Some(secret);
+        }
     }
 
     /// Create a default configuration for testing
@@ -174,6 +356,7 @@ impl AppConfig {
                 max_reconnect_attempts: 3,
                 reconnect_delay_seconds: 5,
                 programs: vec![],
+                accounts: vec![],
                 filters: Default::default(),
             },
             engine: EngineConfig::default(),
@@ -182,10 +365,40 @@ impl AppConfig {
                 telegram: None,
                 slack: None,
                 discord: None,
+                mattermost: None,
+                pagerduty: None,
+                grafana_oncall: None,
+                pushover: None,
+                ntfy: None,
+                sns: None,
+                kafka: None,
+                nats: None,
+                sms: None,
+                signal: None,
+                capture: None,
                 rate_limiting: Default::default(),
+                http: Default::default(),
                 global: Default::default(),
+                retry: Default::default(),
+                routing: Default::default(),
+                program_routes: Default::default(),
+                escalation: Default::default(),
+                quiet_hours: Default::default(),
+                digest: Default::default(),
+                health: Default::default(),
+                templates: Default::default(),
             },
             dashboard: DashboardConfig::default(),
+            grpc: GrpcConfig::default(),
+            alert_sinks: Vec::new(),
+            pyth: None,
+            tvl: None,
+            rate_of_change: Vec::new(),
+            denylist: None,
+            mint_authority: None,
+            compute_exhaustion: ComputeExhaustionConfig::default(),
+            instruction_spike: None,
+            rules: HashMap::new(),
             app: AppSettings::default(),
         }
     }
@@ -201,6 +414,23 @@ impl DashboardConfig {
             anyhow::bail!("Dashboard host cannot be empty");
         }
 
+        if self
+            .base_path
+            .contains(|c: char| c.is_whitespace() || matches!(c, '?' | '#' | ':' | '*'))
+        {
+            anyhow::bail!("Invalid dashboard base path: {}", self.base_path);
+        }
+
+        if self.tls_cert.is_some() != self.tls_key.is_some() {
+            anyhow::bail!("Dashboard tls_cert and tls_key must be set together");
+        }
+
+        if self.enabled {
+            self.auth.validate()?;
+            self.limits.validate()?;
+            self.federation.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -213,6 +443,15 @@ impl Default for DashboardConfig {
             host: default_host(),
             enable_cors: default_true(),
             static_dir: None,
+            slack_signing_secret: None,
+            auth: AuthConfig::default(),
+            tls_cert: None,
+            tls_key: None,
+            base_path: String::new(),
+            metrics_auth: false,
+            audit_log_path: None,
+            limits: RequestLimits::default(),
+            federation: FederationConfig::default(),
         }
     }
 }
@@ -275,6 +514,9 @@ mod tests {
 
             [app]
             log_level = "debug"
+
+            [rules.large_transaction]
+            amount_threshold = 500000
         "#;
 
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -284,6 +526,10 @@ mod tests {
         assert_eq!(config.dashboard.port, 3000);
         assert_eq!(config.app.log_level, "debug");
         assert_eq!(config.subscriber.programs.len(), 1);
+
+        let engine_config = config.engine_config();
+        let rule_config = engine_config.rule_config_for("large_transaction", "SPL Token");
+        assert_eq!(rule_config["amount_threshold"], serde_json::json!(500000));
     }
 
     #[test]
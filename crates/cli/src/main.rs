@@ -44,14 +44,18 @@ enum Commands {
         #[arg(long)]
         dashboard_port: Option<u16>,
 
-        /// Prometheus metrics port
+        /// Prometheus metrics port (0 to serve metrics only from the dashboard's /metrics)
         #[arg(long, default_value = "9090")]
         metrics_port: u16,
+
+        /// Capture notifications instead of sending them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Test notification channels
     TestNotifications {
-        /// Test specific channel (email, telegram, slack, discord)
+        /// Test specific channel (email, telegram, slack, discord, mattermost, pagerduty, grafana_oncall, pushover, ntfy, sns, kafka, nats, sms, signal)
         #[arg(short = 't', long)]
         channel: Option<String>,
     },
@@ -80,6 +84,10 @@ enum RuleAction {
     Info { rule_name: String },
     /// Test rule with sample data
     Test { rule_name: String },
+    /// Enable a rule
+    Enable { rule_name: String },
+    /// Disable a rule
+    Disable { rule_name: String },
 }
 
 #[tokio::main]
@@ -105,8 +113,9 @@ async fn main() -> Result<()> {
             daemon,
             dashboard_port,
             metrics_port,
+            dry_run,
         } => {
-            start_command(config_path, daemon, dashboard_port, metrics_port).await?;
+            start_command(config_path, daemon, dashboard_port, metrics_port, dry_run).await?;
         }
         Commands::TestNotifications { channel } => {
             test_notifications_command(config_path, channel).await?;
@@ -124,6 +133,12 @@ async fn main() -> Result<()> {
             RuleAction::Test { rule_name } => {
                 rules_test_command(rule_name).await?;
             }
+            RuleAction::Enable { rule_name } => {
+                rules_toggle_command(config_path, rule_name, true).await?;
+            }
+            RuleAction::Disable { rule_name } => {
+                rules_toggle_command(config_path, rule_name, false).await?;
+            }
         },
         Commands::Status => {
             status_command().await?;
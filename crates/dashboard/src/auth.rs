@@ -0,0 +1,759 @@
+//! Dashboard authentication: password logins with session cookies, or users
+//! asserted by an SSO reverse proxy, plus bearer tokens for API automation.
+
+use crate::{
+    app_path, handlers::accepts_html, templates::LoginTemplate, ApiResponse, AppState,
+    DashboardError, DashboardResult,
+};
+use argon2::{password_hash::PasswordHash, Argon2, PasswordVerifier};
+use askama::Template;
+use axum::{
+    extract::{ConnectInfo, Extension, Query, Request, State},
+    http::{header, HeaderMap, HeaderName, Method, StatusCode},
+    middleware::Next,
+    response::{Html, IntoResponse, Json, Redirect, Response},
+    Form,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Cookie holding the session token.
+pub const SESSION_COOKIE: &str = "watchtower_session";
+
+/// How dashboard users are authenticated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMode {
+    /// No authentication; the dashboard is open to anyone who can reach it
+    #[default]
+    None,
+    /// Username/password login against the configured users
+    Password,
+    /// Users authenticated by a reverse proxy, taken from a request header
+    Proxy,
+}
+
+/// Dashboard authentication configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Authentication mode (none, password or proxy)
+    #[serde(default)]
+    pub mode: AuthMode,
+
+    /// Users allowed to log in with a password
+    #[serde(default)]
+    pub users: Vec<DashboardUser>,
+
+    /// Session lifetime in seconds
+    #[serde(default = "default_session_ttl_seconds")]
+    pub session_ttl_seconds: u64,
+
+    /// Only send the session cookie over HTTPS
+    #[serde(default)]
+    pub secure_cookie: bool,
+
+    /// Header carrying the username in proxy mode
+    #[serde(default = "default_proxy_user_header")]
+    pub proxy_user_header: String,
+
+    /// Addresses of the proxies whose `proxy_user_header` is trusted; the
+    /// header is ignored on connections from anywhere else
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+
+    /// Bearer tokens for the JSON API and WebSocket (password and proxy modes)
+    #[serde(default)]
+    pub api_tokens: Vec<ApiToken>,
+}
+
+/// A user allowed to log in to the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardUser {
+    pub username: String,
+
+    /// Argon2 hash of the password in PHC format (`$argon2id$v=19$...`)
+    pub password_hash: String,
+}
+
+/// Named bearer token used by automation instead of a UI login.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    /// Name identifying the token's user in logs and audit trails
+    pub name: String,
+
+    /// Token value sent as `Authorization: Bearer <token>`
+    pub token: String,
+
+    /// Granted scopes
+    #[serde(default = "default_api_token_scopes")]
+    pub scopes: Vec<ApiScope>,
+}
+
+/// Access granted to an API token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// GET requests and the WebSocket
+    Read,
+    /// Requests changing state (POST, PUT, DELETE)
+    Write,
+}
+
+/// Authenticated user of a request, available to handlers as an extension.
+#[derive(Debug, Clone)]
+pub struct CurrentUser(pub String);
+
+/// Logged-in session.
+#[derive(Debug, Clone)]
+struct Session {
+    username: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Users and sessions of the dashboard.
+#[derive(Debug)]
+pub struct Authenticator {
+    config: AuthConfig,
+    sessions: RwLock<HashMap<String, Session>>,
+    cookie_path: String,
+}
+
+impl AuthConfig {
+    /// Validate the users and proxy header of the selected mode.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self.mode {
+            AuthMode::None => {
+                // Without authentication every request is let through, so
+                // tokens would give a false sense of protection
+                if !self.api_tokens.is_empty() {
+                    anyhow::bail!("API tokens need the password or proxy authentication mode");
+                }
+            }
+            AuthMode::Password => {
+                if self.users.is_empty() {
+                    anyhow::bail!("Password authentication needs at least one dashboard user");
+                }
+                for user in &self.users {
+                    if user.username.is_empty() {
+                        anyhow::bail!("Dashboard usernames cannot be empty");
+                    }
+                    PasswordHash::new(&user.password_hash).map_err(|e| {
+                        anyhow::anyhow!("Invalid password hash for user {}: {}", user.username, e)
+                    })?;
+                }
+            }
+            AuthMode::Proxy => {
+                HeaderName::from_bytes(self.proxy_user_header.as_bytes()).map_err(|_| {
+                    anyhow::anyhow!("Invalid proxy user header: {}", self.proxy_user_header)
+                })?;
+                if self.trusted_proxies.is_empty() {
+                    anyhow::bail!(
+                        "Proxy authentication needs the addresses of the trusted proxies"
+                    );
+                }
+            }
+        }
+
+        if self.session_ttl_seconds == 0 {
+            anyhow::bail!("Dashboard session lifetime cannot be 0");
+        }
+
+        let mut names = HashSet::new();
+        for token in &self.api_tokens {
+            if token.name.is_empty() || !names.insert(token.name.as_str()) {
+                anyhow::bail!("API token names must be unique and non-empty");
+            }
+            if token.token.len() < MIN_API_TOKEN_LENGTH {
+                anyhow::bail!(
+                    "API token {} must be at least {} characters",
+                    token.name,
+                    MIN_API_TOKEN_LENGTH
+                );
+            }
+            if token.scopes.is_empty() {
+                anyhow::bail!("API token {} has no scopes", token.name);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            mode: AuthMode::default(),
+            users: Vec::new(),
+            session_ttl_seconds: default_session_ttl_seconds(),
+            secure_cookie: false,
+            proxy_user_header: default_proxy_user_header(),
+            trusted_proxies: Vec::new(),
+            api_tokens: Vec::new(),
+        }
+    }
+}
+
+impl ApiScope {
+    /// Scope needed for a request method.
+    pub fn required_for(method: &Method) -> Self {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            ApiScope::Read
+        } else {
+            ApiScope::Write
+        }
+    }
+}
+
+impl Authenticator {
+    /// Create an authenticator without sessions.
+    pub fn new(config: AuthConfig) -> Self {
+        Self {
+            config,
+            sessions: RwLock::new(HashMap::new()),
+            cookie_path: "/".to_string(),
+        }
+    }
+
+    /// Scope the session cookie to the dashboard's URL prefix.
+    pub fn with_base_path(mut self, base_path: &str) -> Self {
+        self.cookie_path = app_path(base_path, "/");
+        self
+    }
+
+    /// Authentication mode.
+    pub fn mode(&self) -> AuthMode {
+        self.config.mode
+    }
+
+    /// Check a username and password against the configured users.
+    pub fn verify_password(&self, username: &str, password: &str) -> bool {
+        let Some(user) = self.config.users.iter().find(|u| u.username == username) else {
+            return false;
+        };
+        let Ok(hash) = PasswordHash::new(&user.password_hash) else {
+            return false;
+        };
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()
+    }
+
+    /// Start a session for a user, returning its token.
+    pub async fn create_session(&self, username: &str) -> String {
+        let token = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        let now = Utc::now();
+
+        let mut sessions = self.sessions.write().await;
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(
+            token.clone(),
+            Session {
+                username: username.to_string(),
+                expires_at: now + Duration::seconds(self.config.session_ttl_seconds as i64),
+            },
+        );
+
+        token
+    }
+
+    /// User of an unexpired session.
+    pub async fn session_user(&self, token: &str) -> Option<String> {
+        self.sessions
+            .read()
+            .await
+            .get(token)
+            .filter(|session| session.expires_at > Utc::now())
+            .map(|session| session.username.clone())
+    }
+
+    /// API token with the given value.
+    pub fn api_token(&self, token: &str) -> Option<&ApiToken> {
+        // Compare digests so the comparison time does not reveal the token
+        let digest = Sha256::digest(token.as_bytes());
+        self.config
+            .api_tokens
+            .iter()
+            .find(|api_token| Sha256::digest(api_token.token.as_bytes()) == digest)
+    }
+
+    /// End a session.
+    pub async fn end_session(&self, token: &str) {
+        self.sessions.write().await.remove(token);
+    }
+
+    /// User of a request, from its session cookie or the proxy header. The
+    /// proxy header is only taken from connections of a trusted proxy.
+    pub async fn request_user(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> Option<String> {
+        match self.config.mode {
+            AuthMode::None => None,
+            AuthMode::Password => match session_token(headers) {
+                Some(token) => self.session_user(token).await,
+                None => None,
+            },
+            AuthMode::Proxy
+                if !peer.is_some_and(|peer| self.config.trusted_proxies.contains(&peer)) =>
+            {
+                None
+            }
+            AuthMode::Proxy => headers
+                .get(self.config.proxy_user_header.as_str())
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|user| !user.is_empty())
+                .map(String::from),
+        }
+    }
+
+    /// `Set-Cookie` value of a session.
+    fn session_cookie(&self, token: &str) -> String {
+        self.cookie(token, self.config.session_ttl_seconds)
+    }
+
+    /// `Set-Cookie` value removing the session cookie.
+    fn cleared_cookie(&self) -> String {
+        self.cookie("", 0)
+    }
+
+    fn cookie(&self, value: &str, max_age: u64) -> String {
+        let mut cookie = format!(
+            "{}={}; Path={}; Max-Age={}; HttpOnly; SameSite=Lax",
+            SESSION_COOKIE, value, self.cookie_path, max_age
+        );
+        if self.config.secure_cookie {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+}
+
+/// Session token from the `Cookie` header.
+fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, token)| token)
+        .filter(|token| !token.is_empty())
+}
+
+/// Bearer token of a JSON API, WebSocket or metrics request. Browsers cannot set
+/// headers on WebSocket connections, so `/ws` also accepts an `access_token`
+/// parameter.
+fn request_api_token(request: &Request) -> Option<String> {
+    let path = request.uri().path();
+    if !path.starts_with("/api/") && path != "/ws" && path != "/metrics" {
+        return None;
+    }
+
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+
+    bearer.or_else(|| {
+        (path == "/ws")
+            .then(|| request.uri().query())
+            .flatten()
+            .and_then(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .find(|(key, _)| key == "access_token")
+                    .map(|(_, token)| token.into_owned())
+            })
+    })
+}
+
+/// Paths reachable without logging in.
+fn is_public_path(path: &str) -> bool {
+    // Slack callbacks are authenticated by their request signature
+    matches!(path, "/login" | "/health" | "/api/slack/actions") || path.starts_with("/static/")
+}
+
+/// Scope an API token needs for a request.
+fn required_scope(method: &Method, path: &str) -> ApiScope {
+    // Grafana posts its queries, but they only read data
+    if path.starts_with("/api/grafana") {
+        ApiScope::Read
+    } else {
+        ApiScope::required_for(method)
+    }
+}
+
+/// Redirect target after logging in, limited to local paths.
+fn safe_redirect_target(next: Option<&str>) -> &str {
+    match next {
+        Some(next) if next.starts_with('/') && !next.starts_with("//") && !next.contains('\\') => {
+            next
+        }
+        _ => "/",
+    }
+}
+
+/// Middleware rejecting unauthenticated requests to pages and API routes.
+pub async fn require_auth(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let mode = state.auth.mode();
+    let path = request.uri().path();
+    let scrape = path == "/metrics" && !accepts_html(request.headers());
+    if mode == AuthMode::None || is_public_path(path) || (scrape && state.public_metrics) {
+        return next.run(request).await;
+    }
+
+    if let Some(token) = request_api_token(&request) {
+        let required = required_scope(request.method(), path);
+        let (status, error) = match state.auth.api_token(&token) {
+            Some(api_token) if api_token.scopes.contains(&required) => {
+                request
+                    .extensions_mut()
+                    .insert(CurrentUser(format!("token:{}", api_token.name)));
+                return next.run(request).await;
+            }
+            Some(api_token) => {
+                warn!(
+                    "API token {} lacks the {:?} scope for {} {}",
+                    api_token.name,
+                    required,
+                    request.method(),
+                    request.uri().path()
+                );
+                (StatusCode::FORBIDDEN, "API token lacks the required scope")
+            }
+            None => (StatusCode::UNAUTHORIZED, "Invalid API token"),
+        };
+        return (status, Json(ApiResponse::<()>::error(error))).into_response();
+    }
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if let Some(user) = state.auth.request_user(request.headers(), peer).await {
+        request.extensions_mut().insert(CurrentUser(user));
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path();
+    let page = !path.starts_with("/api/") && path != "/ws" && !scrape;
+    if mode == AuthMode::Password && page {
+        let target = request
+            .uri()
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or("/");
+        let next: String = url::form_urlencoded::byte_serialize(target.as_bytes()).collect();
+        return Redirect::to(&format!("{}/login?next={}", state.base_path, next)).into_response();
+    }
+
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ApiResponse::<()>::error("Authentication required")),
+    )
+        .into_response()
+}
+
+/// Query parameters of the login page
+#[derive(Debug, Deserialize)]
+pub struct LoginQuery {
+    pub next: Option<String>,
+}
+
+/// Submitted login form
+#[derive(Debug, Deserialize)]
+pub struct LoginForm {
+    pub username: String,
+    pub password: String,
+    pub next: Option<String>,
+}
+
+/// Session information returned by the API
+#[derive(Debug, Serialize)]
+pub struct SessionInfo {
+    pub mode: AuthMode,
+    pub username: Option<String>,
+}
+
+/// Login page
+pub async fn login_page(
+    State(state): State<AppState>,
+    Query(query): Query<LoginQuery>,
+) -> DashboardResult<Response> {
+    if state.auth.mode() != AuthMode::Password {
+        return Ok(Redirect::to(&app_path(&state.base_path, "/")).into_response());
+    }
+
+    render_login(
+        &state.base_path,
+        query.next.as_deref(),
+        None,
+        StatusCode::OK,
+    )
+}
+
+/// Login form submission
+pub async fn login(
+    State(state): State<AppState>,
+    Form(form): Form<LoginForm>,
+) -> DashboardResult<Response> {
+    if state.auth.mode() != AuthMode::Password {
+        return Ok(Redirect::to(&app_path(&state.base_path, "/")).into_response());
+    }
+
+    // Argon2 verification is deliberately slow; keep it off the async workers
+    let auth = state.auth.clone();
+    let username = form.username.clone();
+    let valid =
+        tokio::task::spawn_blocking(move || auth.verify_password(&username, &form.password))
+            .await
+            .map_err(|e| DashboardError::Internal(e.into()))?;
+
+    if !valid {
+        warn!("Failed dashboard login for user {}", form.username);
+        return render_login(
+            &state.base_path,
+            form.next.as_deref(),
+            Some("Invalid username or password"),
+            StatusCode::UNAUTHORIZED,
+        );
+    }
+
+    let token = state.auth.create_session(&form.username).await;
+    info!("Dashboard user {} logged in", form.username);
+
+    Ok((
+        [(header::SET_COOKIE, state.auth.session_cookie(&token))],
+        Redirect::to(&app_path(
+            &state.base_path,
+            safe_redirect_target(form.next.as_deref()),
+        )),
+    )
+        .into_response())
+}
+
+/// Logout, ending the session
+pub async fn logout(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(token) = session_token(&headers) {
+        state.auth.end_session(token).await;
+    }
+
+    (
+        [(header::SET_COOKIE, state.auth.cleared_cookie())],
+        Redirect::to(&format!("{}/login", state.base_path)),
+    )
+        .into_response()
+}
+
+/// API: Current user
+pub async fn api_session(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+) -> Json<ApiResponse<SessionInfo>> {
+    Json(ApiResponse::success(SessionInfo {
+        mode: state.auth.mode(),
+        username: user.map(|Extension(CurrentUser(name))| name),
+    }))
+}
+
+fn render_login(
+    base_path: &str,
+    next: Option<&str>,
+    error: Option<&str>,
+    status: StatusCode,
+) -> DashboardResult<Response> {
+    let template = LoginTemplate {
+        title: "Login".to_string(),
+        base_path: base_path.to_string(),
+        next: safe_redirect_target(next).to_string(),
+        error: error.unwrap_or_default().to_string(),
+    };
+
+    let html = template.render().map_err(DashboardError::Template)?;
+    Ok((status, Html(html)).into_response())
+}
+
+/// Shortest accepted API token.
+const MIN_API_TOKEN_LENGTH: usize = 16;
+
+// Default value functions
+fn default_session_ttl_seconds() -> u64 {
+    // 12 hours
+    43200
+}
+
+fn default_proxy_user_header() -> String {
+    "X-Forwarded-User".to_string()
+}
+
+fn default_api_token_scopes() -> Vec<ApiScope> {
+    vec![ApiScope::Read]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argon2::password_hash::{PasswordHasher, SaltString};
+
+    fn password_config() -> AuthConfig {
+        let salt = SaltString::from_b64("d2F0Y2h0b3dlcnNhbHQ").unwrap();
+        let hash = Argon2::default()
+            .hash_password(b"correct horse", &salt)
+            .unwrap()
+            .to_string();
+
+        AuthConfig {
+            mode: AuthMode::Password,
+            users: vec![DashboardUser {
+                username: "admin".to_string(),
+                password_hash: hash,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_password_login_and_sessions() {
+        let config = password_config();
+        assert!(config.validate().is_ok());
+
+        let auth = Authenticator::new(config);
+        assert!(auth.verify_password("admin", "correct horse"));
+        assert!(!auth.verify_password("admin", "wrong"));
+        assert!(!auth.verify_password("root", "correct horse"));
+
+        let token = auth.create_session("admin").await;
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            format!("theme=dark; {}={}", SESSION_COOKIE, token)
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            auth.request_user(&headers, None).await.as_deref(),
+            Some("admin")
+        );
+
+        auth.end_session(&token).await;
+        assert!(auth.request_user(&headers, None).await.is_none());
+
+        let invalid = AuthConfig {
+            users: Vec::new(),
+            ..password_config()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_user_and_redirects() {
+        let proxy: IpAddr = "10.0.0.5".parse().unwrap();
+        let config = AuthConfig {
+            mode: AuthMode::Proxy,
+            trusted_proxies: vec![proxy],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let auth = Authenticator::new(config.clone());
+
+        let mut headers = HeaderMap::new();
+        assert!(auth.request_user(&headers, Some(proxy)).await.is_none());
+        headers.insert("x-forwarded-user", "alice@example.com".parse().unwrap());
+        assert_eq!(
+            auth.request_user(&headers, Some(proxy)).await.as_deref(),
+            Some("alice@example.com")
+        );
+        // Clients reaching the dashboard directly cannot assert a user
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        assert!(auth.request_user(&headers, Some(client)).await.is_none());
+        assert!(auth.request_user(&headers, None).await.is_none());
+
+        let untrusted = AuthConfig {
+            trusted_proxies: Vec::new(),
+            ..config
+        };
+        assert!(untrusted.validate().is_err());
+
+        assert!(is_public_path("/static/app.js"));
+        assert!(!is_public_path("/api/alerts"));
+
+        // Only local paths are followed after logging in
+        assert_eq!(
+            safe_redirect_target(Some("/alerts?page=2")),
+            "/alerts?page=2"
+        );
+        assert_eq!(safe_redirect_target(Some("//evil.example")), "/");
+        assert_eq!(safe_redirect_target(Some("https://evil.example")), "/");
+        assert_eq!(safe_redirect_target(None), "/");
+    }
+
+    #[test]
+    fn test_api_tokens_and_scopes() {
+        let config = AuthConfig {
+            mode: AuthMode::Password,
+            api_tokens: vec![ApiToken {
+                name: "grafana".to_string(),
+                token: "s3cr3t-read-only-token".to_string(),
+                scopes: vec![ApiScope::Read],
+            }],
+            ..password_config()
+        };
+        assert!(config.validate().is_ok());
+
+        let auth = Authenticator::new(config.clone());
+        let token = auth.api_token("s3cr3t-read-only-token").unwrap();
+        assert_eq!(token.name, "grafana");
+        assert!(token.scopes.contains(&ApiScope::required_for(&Method::GET)));
+        assert!(!token
+            .scopes
+            .contains(&ApiScope::required_for(&Method::POST)));
+        assert!(token
+            .scopes
+            .contains(&required_scope(&Method::POST, "/api/grafana/query")));
+        assert!(auth.api_token("guess").is_none());
+
+        let request = Request::builder()
+            .uri("/ws?access_token=s3cr3t-read-only-token")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert_eq!(
+            request_api_token(&request).as_deref(),
+            Some("s3cr3t-read-only-token")
+        );
+        // Query tokens are only accepted on the WebSocket
+        let request = Request::builder()
+            .uri("/api/alerts?access_token=s3cr3t-read-only-token")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert!(request_api_token(&request).is_none());
+
+        let short = AuthConfig {
+            api_tokens: vec![ApiToken {
+                name: "ci".to_string(),
+                token: "short".to_string(),
+                scopes: vec![ApiScope::Write],
+            }],
+            ..config.clone()
+        };
+        assert!(short.validate().is_err());
+
+        let open = AuthConfig {
+            mode: AuthMode::None,
+            ..config
+        };
+        assert!(open.validate().is_err());
+    }
+}
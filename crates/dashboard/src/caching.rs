@@ -0,0 +1,168 @@
+//! HTTP caching: ETags and Cache-Control for static assets and JSON API
+//! responses, answering conditional requests with 304 Not Modified.
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+
+/// Cache-Control of static assets. Asset URLs carry no version, so caches
+/// revalidate them after a few minutes instead of keeping them for long.
+pub const STATIC_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// Cache-Control of API responses: per user, always revalidated.
+const API_CACHE_CONTROL: &str = "private, no-cache";
+
+/// Largest API response hashed for an ETag
+const MAX_ETAG_BODY_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Weak ETag of a body; weak because compression may re-encode it.
+fn etag(body: &[u8]) -> String {
+    digest_etag(&Sha256::digest(body))
+}
+
+/// Weak ETag of a SHA-256 digest of the body.
+fn digest_etag(digest: &[u8]) -> String {
+    let hex: String = digest
+        .iter()
+        .take(16)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("W/\"{}\"", hex)
+}
+
+/// Whether `If-None-Match` of a request matches `etag`.
+fn not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    let weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || weak(tag) == weak(etag))
+}
+
+/// Response to a request for a static asset, given the SHA-256 digest of its body.
+pub fn static_response(
+    headers: &HeaderMap,
+    content_type: &str,
+    body: Cow<'static, [u8]>,
+    digest: &[u8],
+) -> Response {
+    let etag = digest_etag(digest);
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, STATIC_CACHE_CONTROL.to_string()),
+    ];
+
+    if not_modified(headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+    (
+        cache_headers,
+        [(header::CONTENT_TYPE, content_type.to_string())],
+        body,
+    )
+        .into_response()
+}
+
+/// Middleware adding ETags to JSON API responses and answering matching
+/// conditional `GET`s with 304.
+///
+/// Streams and downloads are passed through untouched.
+pub async fn conditional_get(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET || !request.uri().path().starts_with("/api/") {
+        return next.run(request).await;
+    }
+
+    let request_headers = request.headers().clone();
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let small = response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size <= MAX_ETAG_BODY_BYTES);
+    if response.status() != StatusCode::OK
+        || !is_json
+        || !small
+        || response.headers().contains_key(header::CONTENT_DISPOSITION)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let etag = etag(&bytes);
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(header::ETAG, value);
+    }
+    parts.headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(API_CACHE_CONTROL),
+    );
+
+    if not_modified(&request_headers, &etag) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        return Response::from_parts(parts, Body::empty());
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_response_conditional() {
+        let asset = |body: &'static str| {
+            let digest = Sha256::digest(body.as_bytes());
+            (Cow::Borrowed(body.as_bytes()), digest)
+        };
+
+        let (body, digest) = asset("body {}");
+        let response = static_response(&HeaderMap::new(), "text/css", body, &digest);
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(etag, super::etag(b"body {}"));
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            STATIC_CACHE_CONTROL
+        );
+
+        // Strong and weak forms of the tag both match, among others
+        let mut headers = HeaderMap::new();
+        let strong = etag.trim_start_matches("W/");
+        headers.insert(
+            header::IF_NONE_MATCH,
+            format!("\"other\", {}", strong).parse().unwrap(),
+        );
+        let (body, digest) = asset("body {}");
+        let response = static_response(&headers, "text/css", body, &digest);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // Changed content no longer matches
+        let (body, digest) = asset("body { margin: 0 }");
+        let response = static_response(&headers, "text/css", body, &digest);
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,686 @@
+//! Applying dashboard settings to the running pipeline.
+//!
+//! Settings updated through `/api/config` are validated, applied to the
+//! engine, alert manager, notifier and subscriber, and saved to the
+//! watchtower configuration file. A failed step rolls back the steps before
+//! it, so the running pipeline, the file and `/api/config` never disagree.
+//!
+//! The dashboard settings (event rate limit, alert retention, real-time
+//! alerts and enabled notification channels) are saved to the
+//! `[dashboard.settings]` table and applied over the rest of the file at
+//! startup. Of the subscriber's settings only the monitored programs can be
+//! changed while running, replacing the file's `[[programs]]`; updates of its
+//! other settings, which need a new connection, are rejected. The file is
+//! replaced through a temporary file, keeping its other contents and comments.
+
+use crate::{AppState, DashboardState, NotificationChannel};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use thiserror::Error;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, Value};
+use tracing::{info, warn};
+use watchtower_notifier::NotificationManager;
+use watchtower_subscriber::{ProgramConfig, SubscriptionHandle};
+
+/// Largest accepted `max_events_per_minute`
+pub const MAX_EVENTS_PER_MINUTE: u32 = 100_000;
+
+/// Largest accepted `alert_retention_days`
+pub const MAX_ALERT_RETENTION_DAYS: u32 = 365;
+
+/// Errors that stop a settings update; nothing of the update stays applied.
+#[derive(Error, Debug)]
+pub enum ConfigApplyError {
+    #[error("Invalid configuration: {0}")]
+    Invalid(String),
+
+    #[error("Failed to apply configuration: {0}")]
+    Apply(String),
+
+    #[error("Failed to save configuration: {0}")]
+    Persist(String),
+}
+
+pub type ConfigApplyResult<T> = Result<T, ConfigApplyError>;
+
+/// Subscriber settings of an update.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SubscriberUpdate {
+    /// Programs to monitor, replacing the current ones
+    pub programs: Option<Vec<ProgramConfig>>,
+
+    /// Any other subscriber setting, which cannot be changed while running
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Check settings before anything is applied.
+///
+/// Channel names must be channels of the notifier, when one is running.
+pub fn validate_settings(
+    settings: &DashboardState,
+    notifier: Option<&NotificationManager>,
+) -> ConfigApplyResult<()> {
+    let monitoring = &settings.monitoring_settings;
+    if !(1..=MAX_EVENTS_PER_MINUTE).contains(&monitoring.max_events_per_minute) {
+        return Err(ConfigApplyError::Invalid(format!(
+            "max_events_per_minute must be between 1 and {}",
+            MAX_EVENTS_PER_MINUTE
+        )));
+    }
+    if !(1..=MAX_ALERT_RETENTION_DAYS).contains(&monitoring.alert_retention_days) {
+        return Err(ConfigApplyError::Invalid(format!(
+            "alert_retention_days must be between 1 and {}",
+            MAX_ALERT_RETENTION_DAYS
+        )));
+    }
+
+    let mut names = std::collections::HashSet::new();
+    for channel in &settings.notification_channels {
+        if !names.insert(channel.name.as_str()) {
+            return Err(ConfigApplyError::Invalid(format!(
+                "Duplicate notification channel: {}",
+                channel.name
+            )));
+        }
+    }
+
+    if let Some(notifier) = notifier {
+        let known = notifier.channel_names();
+        if let Some(unknown) = settings
+            .notification_channels
+            .iter()
+            .find(|channel| !known.contains(&channel.name))
+        {
+            return Err(ConfigApplyError::Invalid(format!(
+                "Unknown notification channel: {} (configured: {})",
+                unknown.name,
+                known.join(", ")
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check subscriber settings before anything is applied, returning the
+/// programs to monitor.
+///
+/// Settings other than the programs only take effect on a new connection and
+/// are rejected rather than saved for the next start.
+pub fn validate_subscriber_update(
+    update: &SubscriberUpdate,
+    subscriber: Option<&SubscriptionHandle>,
+) -> ConfigApplyResult<Option<Vec<ProgramConfig>>> {
+    if !update.other.is_empty() {
+        let mut keys: Vec<&str> = update.other.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        return Err(ConfigApplyError::Invalid(format!(
+            "Subscriber settings cannot be changed while running: {} (only programs can; \
+             change the others in the configuration file and restart)",
+            keys.join(", ")
+        )));
+    }
+
+    let Some(programs) = &update.programs else {
+        return Ok(None);
+    };
+    if subscriber.is_none() {
+        return Err(ConfigApplyError::Invalid(
+            "Programs cannot be changed without a running subscriber".to_string(),
+        ));
+    }
+
+    let mut ids = std::collections::HashSet::new();
+    for program in programs {
+        program
+            .validate()
+            .map_err(|e| ConfigApplyError::Invalid(e.to_string()))?;
+        if !ids.insert(program.id) {
+            return Err(ConfigApplyError::Invalid(format!(
+                "Duplicate program: {}",
+                program.id
+            )));
+        }
+    }
+
+    Ok(Some(programs.clone()))
+}
+
+/// Programs in the form shown by `/api/config` and the audit log, with
+/// their IDs in base58.
+pub fn programs_value(programs: &[ProgramConfig]) -> serde_json::Value {
+    programs
+        .iter()
+        .map(|program| {
+            let mut value = serde_json::to_value(program).unwrap_or_default();
+            value["id"] = program.id.to_string().into();
+            value
+        })
+        .collect()
+}
+
+/// Configurations of the programs the subscriber monitors.
+pub fn monitored_programs(subscriber: &SubscriptionHandle) -> Vec<ProgramConfig> {
+    subscriber
+        .programs()
+        .into_iter()
+        .map(|program| program.config)
+        .collect()
+}
+
+/// Values in effect before an update, restored when a step of it fails.
+#[derive(Debug)]
+pub struct Rollback {
+    max_events_per_minute: Option<u32>,
+    history_retention_days: Option<u32>,
+    real_time_alerts: bool,
+    /// Whether each notifier channel was enabled
+    channels: Vec<(String, bool)>,
+    /// Monitored programs, `None` without a subscriber
+    programs: Option<Vec<ProgramConfig>>,
+    /// Previous contents of the configuration file, `None` if there was none
+    config_file: Option<String>,
+}
+
+impl Rollback {
+    /// Capture the values an update may change.
+    pub fn capture(state: &AppState) -> ConfigApplyResult<Self> {
+        let config_file = match &state.config_path {
+            Some(path) if path.exists() => Some(
+                std::fs::read_to_string(path)
+                    .map_err(|e| ConfigApplyError::Persist(e.to_string()))?,
+            ),
+            _ => None,
+        };
+
+        Ok(Self {
+            max_events_per_minute: state.engine.max_events_per_minute(),
+            history_retention_days: state.alert_manager.history_retention_days(),
+            real_time_alerts: state.real_time_alerts.load(Ordering::Relaxed),
+            channels: state
+                .notifier
+                .as_ref()
+                .map(|notifier| {
+                    notifier
+                        .channel_names()
+                        .into_iter()
+                        .map(|name| {
+                            let enabled = notifier.is_channel_enabled(&name);
+                            (name, enabled)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            programs: state.subscriber.as_ref().map(monitored_programs),
+            config_file,
+        })
+    }
+
+    /// Keep the update. History beyond a shorter retention is only dropped
+    /// now, as it could not be put back by [`restore`](Self::restore).
+    pub async fn commit(self, state: &AppState) {
+        state.alert_manager.prune_history().await;
+    }
+
+    /// Put the captured values back.
+    pub async fn restore(&self, state: &AppState) {
+        warn!("Rolling back configuration update");
+        state
+            .engine
+            .set_max_events_per_minute(self.max_events_per_minute);
+        state
+            .alert_manager
+            .set_history_retention_days(self.history_retention_days)
+            .await;
+        state
+            .real_time_alerts
+            .store(self.real_time_alerts, Ordering::Relaxed);
+
+        if let Some(notifier) = &state.notifier {
+            for (name, enabled) in &self.channels {
+                if let Err(e) = notifier.set_channel_enabled(name, *enabled) {
+                    warn!("Failed to restore notification channel {}: {}", name, e);
+                }
+            }
+        }
+
+        if let (Some(subscriber), Some(programs)) = (&state.subscriber, &self.programs) {
+            if let Err(e) = set_programs(subscriber, programs) {
+                warn!("Failed to restore monitored programs: {}", e);
+            }
+        }
+
+        if let Some(path) = &state.config_path {
+            let restored = match &self.config_file {
+                Some(contents) => write_atomic(path, contents),
+                None if path.exists() => std::fs::remove_file(path),
+                None => Ok(()),
+            };
+            if let Err(e) = restored {
+                warn!(
+                    "Failed to restore configuration file {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Validate `updated`, apply what differs from `current` and the programs,
+/// when given, to the running pipeline and save them.
+///
+/// Returns what restores the previous settings if a later step, such as
+/// recording the update, fails, and is committed otherwise.
+pub async fn apply_update(
+    state: &AppState,
+    current: &DashboardState,
+    updated: &DashboardState,
+    programs: Option<&[ProgramConfig]>,
+) -> ConfigApplyResult<Rollback> {
+    validate_settings(updated, state.notifier.as_deref())?;
+    let rollback = Rollback::capture(state)?;
+
+    let applied = async {
+        apply_live(state, updated, Some(current)).await?;
+        if let (Some(subscriber), Some(programs)) = (&state.subscriber, programs) {
+            set_programs(subscriber, programs)?;
+        }
+        match &state.config_path {
+            Some(path) => save_settings(path, updated, programs),
+            None => Ok(()),
+        }
+    }
+    .await;
+    if let Err(e) = applied {
+        rollback.restore(state).await;
+        return Err(e);
+    }
+
+    Ok(rollback)
+}
+
+/// Set each setting on the component it controls.
+///
+/// Sections equal in `current` are skipped, so toggling a channel does not
+/// impose the displayed monitoring defaults on the engine.
+async fn apply_live(
+    state: &AppState,
+    settings: &DashboardState,
+    current: Option<&DashboardState>,
+) -> ConfigApplyResult<()> {
+    let monitoring = &settings.monitoring_settings;
+    if current.map_or(true, |current| current.monitoring_settings != *monitoring) {
+        state
+            .engine
+            .set_max_events_per_minute(Some(monitoring.max_events_per_minute));
+        state
+            .alert_manager
+            .set_history_retention_days(Some(monitoring.alert_retention_days))
+            .await;
+        state
+            .real_time_alerts
+            .store(monitoring.enable_real_time_alerts, Ordering::Relaxed);
+    }
+
+    if let Some(notifier) = &state.notifier {
+        for channel in &settings.notification_channels {
+            notifier
+                .set_channel_enabled(&channel.name, channel.enabled)
+                .map_err(|e| ConfigApplyError::Apply(e.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Make the subscriber monitor exactly `programs`; programs whose
+/// configuration changed are subscribed again.
+fn set_programs(
+    subscriber: &SubscriptionHandle,
+    programs: &[ProgramConfig],
+) -> ConfigApplyResult<()> {
+    let apply_error =
+        |e: watchtower_subscriber::SubscriberError| ConfigApplyError::Apply(e.to_string());
+    let current = monitored_programs(subscriber);
+    let current_value = programs_value(&current);
+    let updated_value = programs_value(programs);
+    let listed = |value: &serde_json::Value, list: &serde_json::Value| {
+        list.as_array().is_some_and(|list| list.contains(value))
+    };
+
+    for (program, value) in current
+        .iter()
+        .zip(current_value.as_array().into_iter().flatten())
+    {
+        if !listed(value, &updated_value) {
+            subscriber
+                .unsubscribe_program(&program.id)
+                .map_err(apply_error)?;
+        }
+    }
+    for (program, value) in programs
+        .iter()
+        .zip(updated_value.as_array().into_iter().flatten())
+    {
+        if !listed(value, &current_value) {
+            subscriber
+                .subscribe_program(program.clone())
+                .map_err(apply_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Dashboard settings of the configuration file, if it has them.
+pub fn load_settings(path: &Path) -> ConfigApplyResult<Option<DashboardState>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| ConfigApplyError::Persist(e.to_string()))?;
+    let config: toml::Table =
+        toml::from_str(&contents).map_err(|e| ConfigApplyError::Invalid(e.to_string()))?;
+    let Some(settings) = config
+        .get("dashboard")
+        .and_then(|dashboard| dashboard.get("settings"))
+    else {
+        return Ok(None);
+    };
+
+    settings
+        .clone()
+        .try_into()
+        .map(Some)
+        .map_err(|e| ConfigApplyError::Invalid(format!("[dashboard.settings]: {}", e)))
+}
+
+/// Save settings, and programs when given, to the configuration file at
+/// `path`, keeping the rest of it.
+pub fn save_settings(
+    path: &Path,
+    settings: &DashboardState,
+    programs: Option<&[ProgramConfig]>,
+) -> ConfigApplyResult<()> {
+    let persist_error =
+        |e: &dyn std::fmt::Display| ConfigApplyError::Persist(format!("{}: {}", path.display(), e));
+
+    let contents = if path.exists() {
+        std::fs::read_to_string(path).map_err(|e| persist_error(&e))?
+    } else {
+        String::new()
+    };
+    let mut document: DocumentMut = contents.parse().map_err(|e| persist_error(&e))?;
+
+    let dashboard = document
+        .entry("dashboard")
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| persist_error(&"dashboard is not a table"))?;
+    dashboard.insert(
+        "settings",
+        Item::Table(toml_table(settings).map_err(|e| persist_error(&e))?),
+    );
+
+    if let Some(programs) = programs {
+        let mut tables = ArrayOfTables::new();
+        for program in programs {
+            let mut table = toml_table(program).map_err(|e| persist_error(&e))?;
+            table.insert("id", toml_edit::value(program.id.to_string()));
+            tables.push(table);
+        }
+        document.insert("programs", Item::ArrayOfTables(tables));
+    }
+
+    write_atomic(path, &document.to_string()).map_err(|e| persist_error(&e))
+}
+
+/// `value` as a TOML table, with nested tables in sections of their own.
+fn toml_table(value: &impl Serialize) -> Result<Table, toml_edit::ser::Error> {
+    let mut table = toml_edit::ser::to_document(value)?.into_table();
+    expand_tables(&mut table);
+    // Without values of its own, only the headers of its sections are written
+    table.set_implicit(true);
+    Ok(table)
+}
+
+/// Turn the inline tables the serializer produces into standard tables and
+/// arrays of tables, the way the configuration file is written by hand.
+fn expand_tables(table: &mut Table) {
+    for (_, item) in table.iter_mut() {
+        *item = match std::mem::take(item) {
+            Item::Value(Value::InlineTable(inline)) => {
+                let mut nested = inline.into_table();
+                expand_tables(&mut nested);
+                nested.set_implicit(true);
+                Item::Table(nested)
+            }
+            Item::Value(Value::Array(array))
+                if !array.is_empty() && array.iter().all(Value::is_inline_table) =>
+            {
+                let mut tables = ArrayOfTables::new();
+                for value in array {
+                    if let Value::InlineTable(inline) = value {
+                        let mut nested = inline.into_table();
+                        expand_tables(&mut nested);
+                        tables.push(nested);
+                    }
+                }
+                Item::ArrayOfTables(tables)
+            }
+            other => other,
+        };
+    }
+}
+
+/// Replace the file at `path` with `contents` only once fully written, so a
+/// crash never leaves it truncated. Permissions of the file are kept, as it
+/// may hold secrets.
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, contents)?;
+    if let Ok(metadata) = std::fs::metadata(path) {
+        std::fs::set_permissions(&tmp, metadata.permissions())?;
+    }
+    std::fs::rename(&tmp, path)
+}
+
+/// Bring the running pipeline in line with the dashboard settings at startup.
+///
+/// Settings saved by a previous run are validated and applied. Without them,
+/// the channel list shows the notifier's channels and nothing is applied
+/// until the first update.
+pub async fn load_startup_settings(state: &AppState) {
+    let saved = match &state.config_path {
+        Some(path) => load_settings(path).unwrap_or_else(|e| {
+            warn!("Failed to load settings from {}: {}", path.display(), e);
+            None
+        }),
+        None => None,
+    };
+
+    let mut dashboard_state = state.dashboard_state.write().await;
+    if let Some(saved) = saved {
+        match validate_settings(&saved, state.notifier.as_deref()) {
+            Ok(()) => match Rollback::capture(state) {
+                Ok(rollback) => match apply_live(state, &saved, None).await {
+                    Ok(()) => {
+                        rollback.commit(state).await;
+                        info!("Applied saved dashboard settings");
+                        *dashboard_state = with_channel_status(saved);
+                        return;
+                    }
+                    Err(e) => {
+                        warn!("Ignoring saved dashboard settings: {}", e);
+                        rollback.restore(state).await;
+                    }
+                },
+                Err(e) => warn!("Ignoring saved dashboard settings: {}", e),
+            },
+            Err(e) => warn!("Ignoring saved dashboard settings: {}", e),
+        }
+    }
+
+    if let Some(notifier) = &state.notifier {
+        dashboard_state.notification_channels = notifier_channels(notifier);
+    }
+}
+
+/// Channels of the notifier with their runtime state.
+fn notifier_channels(notifier: &NotificationManager) -> Vec<NotificationChannel> {
+    notifier
+        .channel_names()
+        .into_iter()
+        .map(|name| {
+            let enabled = notifier.is_channel_enabled(&name);
+            NotificationChannel {
+                name,
+                enabled,
+                status: channel_status(enabled).to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Settings with each channel's status matching whether it is enabled.
+pub fn with_channel_status(mut settings: DashboardState) -> DashboardState {
+    for channel in &mut settings.notification_channels {
+        channel.status = channel_status(channel.enabled).to_string();
+    }
+    settings
+}
+
+fn channel_status(enabled: bool) -> &'static str {
+    if enabled {
+        "Active"
+    } else {
+        "Disabled"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_settings() {
+        let settings = DashboardState::default();
+        assert!(validate_settings(&settings, None).is_ok());
+
+        let mut too_many = settings.clone();
+        too_many.monitoring_settings.max_events_per_minute = MAX_EVENTS_PER_MINUTE + 1;
+        assert!(matches!(
+            validate_settings(&too_many, None),
+            Err(ConfigApplyError::Invalid(_))
+        ));
+
+        let mut no_retention = settings.clone();
+        no_retention.monitoring_settings.alert_retention_days = 0;
+        assert!(validate_settings(&no_retention, None).is_err());
+
+        let mut duplicate = settings.clone();
+        duplicate
+            .notification_channels
+            .push(settings.notification_channels[0].clone());
+        assert!(validate_settings(&duplicate, None).is_err());
+    }
+
+    fn program(id: &str, name: &str) -> ProgramConfig {
+        serde_json::from_value(serde_json::json!({ "id": id, "name": name })).unwrap()
+    }
+
+    #[test]
+    fn test_validate_subscriber_update() {
+        let update: SubscriberUpdate = serde_json::from_value(serde_json::json!({
+            "ws_url": "wss://example.com",
+            "rpc_url": "https://example.com",
+        }))
+        .unwrap();
+        let error = validate_subscriber_update(&update, None).unwrap_err();
+        assert!(matches!(error, ConfigApplyError::Invalid(_)));
+        assert!(error.to_string().contains("rpc_url, ws_url"));
+
+        let programs = SubscriberUpdate {
+            programs: Some(vec![program(
+                "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+                "Orca",
+            )]),
+            ..Default::default()
+        };
+        assert!(validate_subscriber_update(&programs, None).is_err());
+        assert!(
+            validate_subscriber_update(&SubscriberUpdate::default(), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_settings_saved_to_config_file() {
+        let path = std::env::temp_dir()
+            .join(format!("watchtower-settings-{}", uuid::Uuid::new_v4()))
+            .join("watchtower.toml");
+        assert!(load_settings(&path).unwrap().is_none());
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "# Mainnet monitoring\n\
+             rpc_url = \"https://api.mainnet-beta.solana.com\"\n\n\
+             [[programs]]\n\
+             id = \"whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc\"\n\
+             name = \"Orca\"\n\n\
+             [dashboard]\n\
+             port = 8081 # behind the proxy\n",
+        )
+        .unwrap();
+        assert!(load_settings(&path).unwrap().is_none());
+
+        let mut settings = DashboardState::default();
+        settings.monitoring_settings.alert_retention_days = 7;
+        settings.notification_channels[0].enabled = false;
+        let programs = [program(
+            "So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo",
+            "Solend",
+        )];
+        save_settings(&path, &settings, Some(&programs)).unwrap();
+
+        let loaded = load_settings(&path).unwrap().unwrap();
+        assert_eq!(loaded.monitoring_settings.alert_retention_days, 7);
+        assert!(!loaded.notification_channels[0].enabled);
+        assert!(!path.with_extension("toml.tmp").exists());
+
+        // The rest of the file, comments included, is kept
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("# Mainnet monitoring\n"));
+        assert!(contents.contains("port = 8081 # behind the proxy"));
+
+        #[derive(Deserialize)]
+        struct Saved {
+            rpc_url: String,
+            programs: Vec<ProgramConfig>,
+        }
+        let saved: Saved = toml::from_str(&contents).unwrap();
+        assert_eq!(saved.rpc_url, "https://api.mainnet-beta.solana.com");
+        assert_eq!(saved.programs.len(), 1);
+        assert_eq!(saved.programs[0].id, programs[0].id);
+        assert_eq!(saved.programs[0].name, "Solend");
+
+        let status = with_channel_status(loaded);
+        assert_eq!(status.notification_channels[0].status, "Disabled");
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! Live program events for watching raw activity while debugging rules.
+
+use crate::{
+    broadcast_to_websockets, templates::EventsTemplate, ApiResponse, AppState, DashboardError,
+    DashboardResult, Topic, WebSocketConnection, WebSocketMessage,
+};
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    response::{Html, Json},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{broadcast::error::RecvError, RwLock};
+use tracing::warn;
+use watchtower_engine::MonitoringEngine;
+use watchtower_subscriber::ProgramEvent;
+
+/// Events returned when no limit is given
+const DEFAULT_EVENT_LIMIT: usize = 100;
+
+/// Largest number of events returned at once
+const MAX_EVENT_LIMIT: usize = 1000;
+
+/// Query parameters filtering events
+#[derive(Debug, Default, Deserialize)]
+pub struct EventQuery {
+    /// Program name or ID
+    pub program: Option<String>,
+    /// Event type, e.g. `transaction` or `log_entry`
+    pub event_type: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl EventQuery {
+    /// Whether an event passes the filters.
+    pub fn matches(&self, event: &ProgramEvent) -> bool {
+        let program = self.program.as_deref().filter(|p| !p.is_empty());
+        let event_type = self.event_type.as_deref().filter(|t| !t.is_empty());
+
+        program.map_or(true, |program| {
+            event.program_name == program || event.program_id.to_string() == program
+        }) && event_type.map_or(true, |event_type| event.event_type.as_str() == event_type)
+    }
+
+    /// Number of events to return.
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_EVENT_LIMIT)
+            .clamp(1, MAX_EVENT_LIMIT)
+    }
+}
+
+/// Program event as shown on the events page and streamed to WebSocket clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventNotification {
+    pub id: String,
+    pub program_id: String,
+    pub program_name: String,
+    pub event_type: String,
+    pub slot: u64,
+    pub signature: Option<String>,
+    pub timestamp: String,
+    /// Event payload as JSON
+    pub data: serde_json::Value,
+}
+
+impl From<&ProgramEvent> for EventNotification {
+    fn from(event: &ProgramEvent) -> Self {
+        Self {
+            id: event.id.clone(),
+            program_id: event.program_id.to_string(),
+            program_name: event.program_name.clone(),
+            event_type: event.event_type.as_str().to_string(),
+            slot: event.slot,
+            signature: event.signature.as_ref().map(ToString::to_string),
+            timestamp: event
+                .timestamp
+                .format("%Y-%m-%d %H:%M:%S%.3f UTC")
+                .to_string(),
+            data: serde_json::to_value(&event.data).unwrap_or_default(),
+        }
+    }
+}
+
+/// Events page
+pub async fn events_page(
+    State(state): State<AppState>,
+    Query(query): Query<EventQuery>,
+) -> DashboardResult<Html<String>> {
+    let template = EventsTemplate {
+        title: "Events".to_string(),
+        base_path: state.base_path.to_string(),
+        events: recent_events(&state, &query),
+        program: query.program.unwrap_or_default(),
+        event_type: query.event_type.unwrap_or_default(),
+    };
+
+    let html = template.render().map_err(DashboardError::Template)?;
+    Ok(Html(html))
+}
+
+/// API: Recent events, newest first
+pub async fn api_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventQuery>,
+) -> Json<ApiResponse<Vec<EventNotification>>> {
+    Json(ApiResponse::success(recent_events(&state, &query)))
+}
+
+fn recent_events(state: &AppState, query: &EventQuery) -> Vec<EventNotification> {
+    state
+        .engine
+        .recent_events(query.limit(), |event| query.matches(event))
+        .iter()
+        .map(EventNotification::from)
+        .collect()
+}
+
+/// Background task streaming processed events to WebSocket clients subscribed
+/// to the `events` topic
+pub async fn event_broadcast_task(
+    engine: Arc<MonitoringEngine>,
+    ws_connections: Arc<RwLock<HashMap<String, WebSocketConnection>>>,
+) {
+    let mut event_receiver = engine.subscribe_to_events();
+
+    loop {
+        let event = match event_receiver.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Event stream lagged, skipped {} events", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        // Skip building the message while nobody watches events
+        let watched = ws_connections
+            .read()
+            .await
+            .values()
+            .any(|connection| connection.subscription.topics.contains(&Topic::Events));
+        if watched {
+            let message = WebSocketMessage::Event {
+                data: EventNotification::from(&event),
+            };
+            broadcast_to_websockets(message, &ws_connections).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_subscriber::{EventData, EventType};
+
+    #[test]
+    fn test_event_query_matches() {
+        let event = ProgramEvent::new(
+            "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+                .parse()
+                .unwrap(),
+            "Orca".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: "swap".to_string(),
+                level: None,
+                instruction_index: None,
+            },
+        );
+
+        assert!(EventQuery::default().matches(&event));
+        let by_id = EventQuery {
+            program: Some(event.program_id.to_string()),
+            event_type: Some("log_entry".to_string()),
+            ..Default::default()
+        };
+        assert!(by_id.matches(&event));
+        let other_type = EventQuery {
+            program: Some("Orca".to_string()),
+            event_type: Some("transaction".to_string()),
+            ..Default::default()
+        };
+        assert!(!other_type.matches(&event));
+
+        assert_eq!(EventQuery::default().limit(), DEFAULT_EVENT_LIMIT);
+        let huge = EventQuery {
+            limit: Some(1_000_000),
+            ..Default::default()
+        };
+        assert_eq!(huge.limit(), MAX_EVENT_LIMIT);
+    }
+}
@@ -0,0 +1,174 @@
+//! Alert history export as CSV or JSON for compliance and postmortems.
+
+use crate::{AlertQuery, ApiResponse, AppState};
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use futures::stream;
+use serde::Deserialize;
+use std::convert::Infallible;
+use watchtower_engine::Alert;
+
+/// CSV columns, in order.
+const CSV_HEADER: &str = "id,timestamp,severity,rule_name,program_id,program_name,message,confidence,acknowledged,resolved,tags\n";
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Query parameters of an export besides the alert filters
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// API: Export matching alerts, oldest first, as a CSV or JSON download
+pub async fn api_export_alerts(
+    State(state): State<AppState>,
+    Query(query): Query<AlertQuery>,
+    Query(export): Query<ExportQuery>,
+) -> Response {
+    let filter = match query.filter() {
+        Ok(filter) => filter,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response()
+        }
+    };
+
+    let mut alerts = state
+        .alert_manager
+        .search_alerts(&filter, 0, usize::MAX)
+        .await
+        .alerts;
+    alerts.reverse();
+
+    // Rows are serialized as the body is sent rather than buffered up front
+    let (content_type, extension, body) = match export.format {
+        ExportFormat::Csv => {
+            let rows = alerts.into_iter().map(|alert| csv_row(&alert));
+            let chunks = std::iter::once(CSV_HEADER.to_string()).chain(rows);
+            (
+                "text/csv; charset=utf-8",
+                "csv",
+                Body::from_stream(stream::iter(chunks.map(Ok::<_, Infallible>))),
+            )
+        }
+        ExportFormat::Json => {
+            let count = alerts.len();
+            let items = alerts.into_iter().enumerate().map(move |(i, alert)| {
+                let separator = if i + 1 < count { "," } else { "" };
+                format!(
+                    "{}{}\n",
+                    serde_json::to_string(&alert).unwrap_or_else(|_| "null".to_string()),
+                    separator
+                )
+            });
+            let chunks = std::iter::once("[\n".to_string())
+                .chain(items)
+                .chain(std::iter::once("]\n".to_string()));
+            (
+                "application/json",
+                "json",
+                Body::from_stream(stream::iter(chunks.map(Ok::<_, Infallible>))),
+            )
+        }
+    };
+
+    let filename = format!(
+        "watchtower-alerts-{}.{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+        extension
+    );
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// CSV line of an alert.
+fn csv_row(alert: &Alert) -> String {
+    let fields = [
+        alert.id.clone(),
+        alert.timestamp.to_rfc3339(),
+        alert.severity.as_str().to_string(),
+        alert.rule_name.clone(),
+        alert.program_id.to_string(),
+        alert.program_name.clone(),
+        alert.message.clone(),
+        alert.confidence.to_string(),
+        alert.acknowledged.to_string(),
+        alert.resolved.to_string(),
+        alert.tags.join(";"),
+    ];
+
+    let mut row = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+/// Quote a CSV field when it contains separators, quotes or line breaks.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use watchtower_engine::AlertSeverity;
+
+    #[test]
+    fn test_csv_rows_are_escaped() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Transfer of 1,000,000 USDC\nfrom a new wallet".to_string(),
+            severity: AlertSeverity::High,
+            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+                .parse()
+                .unwrap(),
+            program_name: "Token Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: true,
+            resolved: false,
+            tags: vec!["defi".to_string(), "treasury".to_string()],
+        };
+
+        let row = csv_row(&alert);
+        assert!(row.starts_with("alert-1,"));
+        assert!(row.contains(",\"Transfer of 1,000,000 USDC\nfrom a new wallet\","));
+        assert!(row.ends_with(",0.9,true,false,defi;treasury\n"));
+    }
+}
@@ -0,0 +1,480 @@
+//! Fleet view: status and open alerts of this instance combined with those
+//! of remote watchtower instances, e.g. one watcher per cluster or region.
+//!
+//! Remote instances are queried through their JSON API on each request, with
+//! an API token when they require authentication. An unreachable instance is
+//! shown with its error instead of failing the whole view.
+
+use crate::{
+    system_status, templates::FleetTemplate, AlertInfo, ApiResponse, AppState, DashboardError,
+    DashboardResult, SystemStatus,
+};
+use askama::Template;
+use axum::{
+    extract::State,
+    response::{Html, Json},
+};
+use futures::future::join_all;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use watchtower_engine::{AlertFilter, AlertSearch};
+
+/// Largest `alerts_per_instance`, the page size limit of `/api/alerts`
+const MAX_ALERTS_PER_INSTANCE: usize = 500;
+
+/// Remote instances shown in the fleet view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FederationConfig {
+    /// Name of this instance in the fleet view
+    #[serde(default = "default_local_name")]
+    pub local_name: String,
+
+    /// Remote watchtower dashboards
+    #[serde(default)]
+    pub instances: Vec<RemoteInstance>,
+
+    /// Seconds to wait for a remote instance before showing it as unreachable
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+
+    /// Newest open alerts fetched from each instance
+    #[serde(default = "default_alerts_per_instance")]
+    pub alerts_per_instance: usize,
+}
+
+/// A remote watchtower dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteInstance {
+    /// Name shown in the fleet view, e.g. the cluster or region
+    pub name: String,
+
+    /// Base URL of the dashboard, including its `base_path`
+    pub url: String,
+
+    /// API token with the `read` scope, when the instance requires authentication
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl Default for FederationConfig {
+    fn default() -> Self {
+        Self {
+            local_name: default_local_name(),
+            instances: Vec::new(),
+            timeout_seconds: default_timeout_seconds(),
+            alerts_per_instance: default_alerts_per_instance(),
+        }
+    }
+}
+
+impl FederationConfig {
+    /// Check the remote instances are usable.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.local_name.trim().is_empty() {
+            anyhow::bail!("Federation local_name cannot be empty");
+        }
+        if self.timeout_seconds == 0 {
+            anyhow::bail!("Federation timeout cannot be 0");
+        }
+        if !(1..=MAX_ALERTS_PER_INSTANCE).contains(&self.alerts_per_instance) {
+            anyhow::bail!(
+                "Federation alerts_per_instance must be between 1 and {}",
+                MAX_ALERTS_PER_INSTANCE
+            );
+        }
+
+        let mut names = HashSet::from([self.local_name.as_str()]);
+        for instance in &self.instances {
+            if instance.name.trim().is_empty() {
+                anyhow::bail!("Federated instance name cannot be empty");
+            }
+            if !names.insert(instance.name.as_str()) {
+                anyhow::bail!("Duplicate federated instance name: {}", instance.name);
+            }
+            match url::Url::parse(&instance.url) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                _ => anyhow::bail!(
+                    "Invalid URL of federated instance {}: {}",
+                    instance.name,
+                    instance.url
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Status of an instance in the fleet view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceView {
+    pub name: String,
+    /// Dashboard URL, absent for this instance
+    pub url: Option<String>,
+    pub reachable: bool,
+    /// Why the instance could not be queried
+    pub error: Option<String>,
+    /// Milliseconds the instance took to answer, absent for this instance
+    pub latency_ms: Option<u64>,
+    pub status: Option<SystemStatus>,
+    /// Open alerts fetched from the instance
+    pub open_alerts: usize,
+}
+
+/// Open alert of an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetAlert {
+    pub instance: String,
+    #[serde(flatten)]
+    pub alert: AlertInfo,
+}
+
+/// Sums over the reachable instances
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FleetTotals {
+    pub instances: usize,
+    pub reachable: usize,
+    pub alert_count: usize,
+    pub open_alerts: usize,
+    pub active_rules: usize,
+    pub events_processed: u64,
+}
+
+/// Combined view of the fleet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetView {
+    pub totals: FleetTotals,
+    /// This instance first, then the remote ones in configured order
+    pub instances: Vec<InstanceView>,
+    /// Open alerts of all instances, newest first
+    pub alerts: Vec<FleetAlert>,
+}
+
+/// What an instance answered, or why it did not.
+#[derive(Debug)]
+struct InstanceReport {
+    name: String,
+    url: Option<String>,
+    latency: Option<Duration>,
+    result: Result<(SystemStatus, Vec<AlertInfo>), String>,
+}
+
+/// `ApiResponse` as received from a remote instance
+#[derive(Debug, Deserialize)]
+struct RemoteResponse<T> {
+    success: bool,
+    data: Option<T>,
+    error: Option<String>,
+}
+
+/// Client querying the remote instances of the fleet.
+#[derive(Debug, Clone)]
+pub struct Federation {
+    config: FederationConfig,
+    client: reqwest::Client,
+}
+
+impl Federation {
+    pub fn new(config: FederationConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .unwrap_or_else(|e| {
+                warn!("Failed to build federation HTTP client: {}", e);
+                reqwest::Client::new()
+            });
+        Self { config, client }
+    }
+
+    /// Configuration of the fleet.
+    pub fn config(&self) -> &FederationConfig {
+        &self.config
+    }
+
+    /// Status and open alerts of a remote instance.
+    async fn query(&self, instance: &RemoteInstance) -> InstanceReport {
+        let started = Instant::now();
+        let base = instance.url.trim_end_matches('/');
+        let limit = self.config.alerts_per_instance.to_string();
+
+        let status_url = format!("{}/api/status", base);
+        let alerts_url = format!("{}/api/alerts", base);
+        let alerts_query = [("resolved", "false"), ("limit", limit.as_str())];
+
+        let status = self.get::<SystemStatus>(instance, &status_url, &[]);
+        let alerts = self.get::<Vec<AlertInfo>>(instance, &alerts_url, &alerts_query);
+        let result = match futures::join!(status, alerts) {
+            (Ok(status), Ok(alerts)) => Ok((status, alerts)),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("Federated instance {} is unreachable: {}", instance.name, e);
+                Err(e)
+            }
+        };
+
+        InstanceReport {
+            name: instance.name.clone(),
+            url: Some(instance.url.clone()),
+            latency: Some(started.elapsed()),
+            result,
+        }
+    }
+
+    /// Data of a JSON API endpoint of a remote instance.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        instance: &RemoteInstance,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, String> {
+        let mut request = self.client.get(url).query(query);
+        if let Some(token) = &instance.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())?;
+        let body: RemoteResponse<T> = response.json().await.map_err(|e| e.to_string())?;
+        match body {
+            RemoteResponse {
+                success: true,
+                data: Some(data),
+                ..
+            } => Ok(data),
+            RemoteResponse { error, .. } => {
+                Err(error.unwrap_or_else(|| "Empty response".to_string()))
+            }
+        }
+    }
+}
+
+/// Status and open alerts of this instance.
+async fn local_report(state: &AppState, name: &str, limit: usize) -> InstanceReport {
+    let filter = AlertFilter {
+        resolved: Some(false),
+        ..Default::default()
+    };
+    let search = AlertSearch {
+        limit,
+        ..Default::default()
+    };
+    let page = state.alert_manager.query_alerts(&filter, &search).await;
+
+    InstanceReport {
+        name: name.to_string(),
+        url: None,
+        latency: None,
+        result: Ok((
+            system_status(state).await,
+            page.alerts.iter().map(AlertInfo::from).collect(),
+        )),
+    }
+}
+
+/// Combine the reports of the instances into the fleet view.
+fn fleet_view(reports: Vec<InstanceReport>) -> FleetView {
+    let mut totals = FleetTotals {
+        instances: reports.len(),
+        ..Default::default()
+    };
+    let mut instances = Vec::with_capacity(reports.len());
+    let mut alerts = Vec::new();
+
+    for report in reports {
+        let latency_ms = report.latency.map(|latency| latency.as_millis() as u64);
+        match report.result {
+            Ok((status, open)) => {
+                totals.reachable += 1;
+                totals.alert_count += status.alert_count;
+                totals.open_alerts += open.len();
+                totals.active_rules += status.active_rules;
+                totals.events_processed += status.performance.events_processed;
+
+                instances.push(InstanceView {
+                    name: report.name.clone(),
+                    url: report.url,
+                    reachable: true,
+                    error: None,
+                    latency_ms,
+                    status: Some(status),
+                    open_alerts: open.len(),
+                });
+                alerts.extend(open.into_iter().map(|alert| FleetAlert {
+                    instance: report.name.clone(),
+                    alert,
+                }));
+            }
+            Err(error) => instances.push(InstanceView {
+                name: report.name,
+                url: report.url,
+                reachable: false,
+                error: Some(error),
+                latency_ms,
+                status: None,
+                open_alerts: 0,
+            }),
+        }
+    }
+
+    // Timestamps are formatted UTC, so they order as strings
+    alerts.sort_by(|a, b| b.alert.timestamp.cmp(&a.alert.timestamp));
+
+    FleetView {
+        totals,
+        instances,
+        alerts,
+    }
+}
+
+/// Query this instance and all remote ones concurrently.
+pub async fn collect_fleet(state: &AppState) -> FleetView {
+    let federation = &state.federation;
+    let config = federation.config();
+
+    let local = local_report(state, &config.local_name, config.alerts_per_instance);
+    let remotes = join_all(
+        config
+            .instances
+            .iter()
+            .map(|instance| federation.query(instance)),
+    );
+    let (local, remotes) = futures::join!(local, remotes);
+
+    fleet_view(std::iter::once(local).chain(remotes).collect())
+}
+
+/// Fleet page
+pub async fn fleet_page(State(state): State<AppState>) -> DashboardResult<Html<String>> {
+    let template = FleetTemplate {
+        title: "Fleet".to_string(),
+        base_path: state.base_path.to_string(),
+        fleet: collect_fleet(&state).await,
+    };
+
+    let html = template.render().map_err(DashboardError::Template)?;
+    Ok(Html(html))
+}
+
+/// API: Status and open alerts of this and the federated instances
+pub async fn api_fleet(State(state): State<AppState>) -> Json<ApiResponse<FleetView>> {
+    Json(ApiResponse::success(collect_fleet(&state).await))
+}
+
+fn default_local_name() -> String {
+    "local".to_string()
+}
+
+fn default_timeout_seconds() -> u64 {
+    5
+}
+
+fn default_alerts_per_instance() -> usize {
+    50
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PerformanceInfo;
+
+    fn status(alert_count: usize, events_processed: u64) -> SystemStatus {
+        SystemStatus {
+            engine_status: "Running".to_string(),
+            alert_count,
+            active_rules: 3,
+            uptime_seconds: 60,
+            memory_usage_mb: 0,
+            connected_websockets: 0,
+            performance: PerformanceInfo {
+                events_processed,
+                avg_event_processing_ms: 0.0,
+                avg_rule_evaluation_ms: 0.0,
+                current_events_per_second: 0.0,
+                peak_events_per_second: 0.0,
+            },
+        }
+    }
+
+    fn alert(id: &str, timestamp: &str) -> AlertInfo {
+        AlertInfo {
+            id: id.to_string(),
+            severity: "high".to_string(),
+            message: "Large transfer".to_string(),
+            program_id: "11111111111111111111111111111111".to_string(),
+            timestamp: timestamp.to_string(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fleet_view() {
+        let reports = vec![
+            InstanceReport {
+                name: "us-east".to_string(),
+                url: None,
+                latency: None,
+                result: Ok((status(4, 100), vec![alert("a", "2024-05-01 10:00:00")])),
+            },
+            InstanceReport {
+                name: "eu-west".to_string(),
+                url: Some("https://eu.example.com".to_string()),
+                latency: Some(Duration::from_millis(42)),
+                result: Ok((
+                    status(2, 50),
+                    vec![
+                        alert("b", "2024-05-01 11:00:00"),
+                        alert("c", "2024-05-01 09:00:00"),
+                    ],
+                )),
+            },
+            InstanceReport {
+                name: "ap-south".to_string(),
+                url: Some("https://ap.example.com".to_string()),
+                latency: Some(Duration::from_secs(5)),
+                result: Err("operation timed out".to_string()),
+            },
+        ];
+
+        let view = fleet_view(reports);
+        assert_eq!(view.totals.instances, 3);
+        assert_eq!(view.totals.reachable, 2);
+        assert_eq!(view.totals.alert_count, 6);
+        assert_eq!(view.totals.open_alerts, 3);
+        assert_eq!(view.totals.events_processed, 150);
+
+        let ids: Vec<_> = view.alerts.iter().map(|a| a.alert.id.as_str()).collect();
+        assert_eq!(ids, ["b", "a", "c"]);
+        assert_eq!(view.alerts[0].instance, "eu-west");
+
+        let unreachable = &view.instances[2];
+        assert!(!unreachable.reachable);
+        assert_eq!(unreachable.error.as_deref(), Some("operation timed out"));
+        assert_eq!(view.instances[1].latency_ms, Some(42));
+    }
+
+    #[test]
+    fn test_federation_config_validate() {
+        let mut config = FederationConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.instances.push(RemoteInstance {
+            name: "eu-west".to_string(),
+            url: "https://eu.example.com/watchtower".to_string(),
+            token: Some("token".to_string()),
+        });
+        assert!(config.validate().is_ok());
+
+        let mut duplicate = config.clone();
+        duplicate.instances[0].name = "local".to_string();
+        assert!(duplicate.validate().is_err());
+
+        let mut bad_url = config.clone();
+        bad_url.instances[0].url = "eu.example.com".to_string();
+        assert!(bad_url.validate().is_err());
+    }
+}
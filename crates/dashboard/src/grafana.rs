@@ -0,0 +1,329 @@
+//! Grafana SimpleJSON datasource endpoints, so Grafana dashboards can chart
+//! alert counts and windowed metrics.
+//!
+//! The datasource URL is `<dashboard>/api/grafana`; Grafana checks it with a
+//! `GET` and then posts to `/search` and `/query` below it. The JSON API
+//! (Infinity) datasource can read the same `/query` responses.
+
+use crate::{ApiResponse, AppState};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use watchtower_engine::{Alert, AlertFilter, AlertSeverity, TimeRange};
+
+/// Target counting alerts, optionally suffixed with `:<severity>`
+const ALERTS_TARGET: &str = "alerts";
+
+/// Prefix of targets reading a sliding window
+const WINDOW_PREFIX: &str = "window:";
+
+/// Bucket width used when Grafana sends no interval
+const DEFAULT_INTERVAL_MS: i64 = 60_000;
+
+/// Largest number of buckets in an alert count series
+const MAX_BUCKETS: usize = 10_000;
+
+const SEVERITIES: [AlertSeverity; 5] = [
+    AlertSeverity::Info,
+    AlertSeverity::Low,
+    AlertSeverity::Medium,
+    AlertSeverity::High,
+    AlertSeverity::Critical,
+];
+
+/// Body of a search request
+#[derive(Debug, Default, Deserialize)]
+pub struct GrafanaSearch {
+    /// Text the returned target names contain
+    #[serde(default)]
+    pub target: String,
+}
+
+/// Body of a query request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrafanaQuery {
+    pub range: GrafanaRange,
+    /// Width of alert count buckets
+    pub interval_ms: Option<i64>,
+    /// Largest number of points Grafana wants per series
+    pub max_data_points: Option<usize>,
+    pub targets: Vec<GrafanaTarget>,
+}
+
+/// Time range of a query
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct GrafanaRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// Series requested by a query
+#[derive(Debug, Deserialize)]
+pub struct GrafanaTarget {
+    pub target: String,
+    /// Targets hidden in the Grafana editor are skipped
+    #[serde(default)]
+    pub hide: bool,
+}
+
+/// Time series in the SimpleJSON response shape
+#[derive(Debug, Serialize)]
+pub struct GrafanaSeries {
+    pub target: String,
+    /// `[value, unix milliseconds]` pairs, oldest first
+    pub datapoints: Vec<(f64, i64)>,
+}
+
+/// Data a target reads.
+#[derive(Debug, PartialEq)]
+enum Series {
+    /// Alert counts, optionally of one severity
+    Alerts(Option<AlertSeverity>),
+    /// Points of a sliding window
+    Window(String),
+}
+
+impl Series {
+    fn parse(target: &str) -> Option<Self> {
+        if target == ALERTS_TARGET {
+            return Some(Series::Alerts(None));
+        }
+        if let Some(severity) = target
+            .strip_prefix(ALERTS_TARGET)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            return AlertSeverity::parse(severity).map(|severity| Series::Alerts(Some(severity)));
+        }
+        target
+            .strip_prefix(WINDOW_PREFIX)
+            .filter(|name| !name.is_empty())
+            .map(|name| Series::Window(name.to_string()))
+    }
+}
+
+impl GrafanaQuery {
+    /// Start of the first alert count bucket, bucket width and bucket count.
+    fn buckets(&self) -> (DateTime<Utc>, Duration, usize) {
+        let span = (self.range.to - self.range.from).num_milliseconds().max(1);
+        let max_buckets = self
+            .max_data_points
+            .unwrap_or(MAX_BUCKETS)
+            .clamp(1, MAX_BUCKETS) as i64;
+
+        // Widen the buckets when the interval would exceed the point budget
+        let interval = self
+            .interval_ms
+            .unwrap_or(DEFAULT_INTERVAL_MS)
+            .max(1)
+            .max((span + max_buckets - 1) / max_buckets);
+        let count = (span + interval - 1) / interval;
+
+        (
+            self.range.from,
+            Duration::milliseconds(interval),
+            count as usize,
+        )
+    }
+
+    /// Alerts raised in each bucket of the query range.
+    fn alert_counts(&self, alerts: &[Alert], severity: Option<AlertSeverity>) -> Vec<(f64, i64)> {
+        let (start, interval, count) = self.buckets();
+        let interval_ms = interval.num_milliseconds();
+        let mut counts = vec![0u64; count];
+
+        for alert in alerts {
+            if severity.is_some_and(|severity| alert.severity != severity) {
+                continue;
+            }
+            let offset = (alert.timestamp - start).num_milliseconds();
+            if offset >= 0 {
+                if let Some(bucket) = counts.get_mut((offset / interval_ms) as usize) {
+                    *bucket += 1;
+                }
+            }
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let timestamp = start + interval * i as i32;
+                (count as f64, timestamp.timestamp_millis())
+            })
+            .collect()
+    }
+
+    /// Window points inside the query range.
+    fn window_points(&self, points: &[(DateTime<Utc>, f64)]) -> Vec<(f64, i64)> {
+        points
+            .iter()
+            .filter(|(timestamp, _)| *timestamp >= self.range.from && *timestamp <= self.range.to)
+            .map(|(timestamp, value)| (*value, timestamp.timestamp_millis()))
+            .collect()
+    }
+}
+
+/// API: Grafana datasource connection test
+pub async fn api_grafana() -> StatusCode {
+    StatusCode::OK
+}
+
+/// API: Grafana target names containing the searched text
+pub async fn api_grafana_search(
+    State(state): State<AppState>,
+    Json(search): Json<GrafanaSearch>,
+) -> Json<Vec<String>> {
+    let mut windows = state.metrics.window_names();
+    windows.sort();
+
+    let targets = std::iter::once(ALERTS_TARGET.to_string())
+        .chain(
+            SEVERITIES
+                .iter()
+                .map(|severity| format!("{}:{}", ALERTS_TARGET, severity.as_str())),
+        )
+        .chain(
+            windows
+                .into_iter()
+                .map(|name| format!("{}{}", WINDOW_PREFIX, name)),
+        )
+        .filter(|target| target.contains(&search.target))
+        .collect();
+
+    Json(targets)
+}
+
+/// API: Time series of the queried Grafana targets
+pub async fn api_grafana_query(
+    State(state): State<AppState>,
+    Json(query): Json<GrafanaQuery>,
+) -> Response {
+    if query.range.to < query.range.from {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                "Query range ends before it starts".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let mut series = Vec::new();
+    for target in query.targets.iter().filter(|target| !target.hide) {
+        match Series::parse(&target.target) {
+            Some(kind) => series.push((target.target.clone(), kind)),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::<()>::error(format!(
+                        "Unknown target: {}",
+                        target.target
+                    ))),
+                )
+                    .into_response()
+            }
+        }
+    }
+
+    // Alerts of the range are fetched once for all alert count targets
+    let alerts = if series
+        .iter()
+        .any(|(_, kind)| matches!(kind, Series::Alerts(_)))
+    {
+        let filter = AlertFilter {
+            time_range: Some(TimeRange {
+                start: query.range.from,
+                end: query.range.to,
+            }),
+            ..Default::default()
+        };
+        state
+            .alert_manager
+            .search_alerts(&filter, 0, usize::MAX)
+            .await
+            .alerts
+    } else {
+        Vec::new()
+    };
+
+    let response: Vec<GrafanaSeries> = series
+        .into_iter()
+        .map(|(target, kind)| {
+            let datapoints = match kind {
+                Series::Alerts(severity) => query.alert_counts(&alerts, severity),
+                Series::Window(name) => state
+                    .metrics
+                    .window_points(&name)
+                    .map(|points| query.window_points(&points))
+                    .unwrap_or_default(),
+            };
+            GrafanaSeries { target, datapoints }
+        })
+        .collect();
+
+    Json(response).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(interval_ms: Option<i64>, max_data_points: Option<usize>) -> GrafanaQuery {
+        let from = "2024-01-01T00:00:00Z".parse().unwrap();
+        GrafanaQuery {
+            range: GrafanaRange {
+                from,
+                to: from + Duration::minutes(10),
+            },
+            interval_ms,
+            max_data_points,
+            targets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_series_parse() {
+        assert_eq!(Series::parse("alerts"), Some(Series::Alerts(None)));
+        assert_eq!(
+            Series::parse("alerts:critical"),
+            Some(Series::Alerts(Some(AlertSeverity::Critical)))
+        );
+        assert_eq!(
+            Series::parse("window:Orca_tvl"),
+            Some(Series::Window("Orca_tvl".to_string()))
+        );
+        assert_eq!(Series::parse("alerts:urgent"), None);
+        assert_eq!(Series::parse("window:"), None);
+        assert_eq!(Series::parse("tvl"), None);
+    }
+
+    #[test]
+    fn test_query_buckets() {
+        let (_, interval, count) = query(Some(60_000), None).buckets();
+        assert_eq!(interval, Duration::minutes(1));
+        assert_eq!(count, 10);
+
+        // Too many buckets for the point budget widen the interval
+        let (_, interval, count) = query(Some(1_000), Some(5)).buckets();
+        assert_eq!(interval, Duration::minutes(2));
+        assert_eq!(count, 5);
+
+        let query = query(Some(60_000), None);
+        let points = vec![
+            (query.range.from - Duration::minutes(1), 1.0),
+            (query.range.from + Duration::seconds(30), 2.0),
+        ];
+        assert_eq!(
+            query.window_points(&points),
+            vec![(
+                2.0,
+                (query.range.from + Duration::seconds(30)).timestamp_millis()
+            )]
+        );
+    }
+}
@@ -1,17 +1,32 @@
 use crate::{
+    auth::CurrentUser,
+    caching::static_response,
+    config_apply::{
+        apply_update, monitored_programs, programs_value, validate_subscriber_update,
+        with_channel_status, SubscriberUpdate,
+    },
     templates::{AlertsTemplate, IndexTemplate, MetricsTemplate, RulesTemplate, SettingsTemplate},
-    websocket::handle_websocket,
-    ApiResponse, AppState, DashboardError, DashboardResult, PaginationInfo, PaginationQuery,
+    websocket::{handle_websocket, send_alert_acknowledged},
+    AlertQuery, ApiResponse, AppState, DashboardError, DashboardResult, PaginationInfo,
+    PaginationQuery,
 };
 use askama::Template;
 use axum::{
-    extract::{Path, Query, State, WebSocketUpgrade},
-    http::{header, StatusCode},
+    extract::{Extension, Path, Query, State, WebSocketUpgrade},
+    http::{header, HeaderMap, StatusCode, Uri},
     response::{Html, IntoResponse, Json, Response},
 };
+use prometheus::{Encoder, TextEncoder};
+use rust_embed::RustEmbed;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use tracing::{info, warn};
+use watchtower_engine::{
+    config_changes, Alert, AlertAuditEntry, AlertCursor, AlertError, AlertFeedback,
+    ConfigAuditEntry, ConfigAuditQuery, DeadLetter, DeliveryQuery, DeliveryRecord, EngineError,
+    RateOfChangeConfig, RuleNote, RuleResult, RuleStatus, SIGNATURE_KEY,
+};
+use watchtower_subscriber::{ProgramConfig, ProgramEvent};
 
 // Helper function to format duration
 fn format_duration(duration: chrono::Duration) -> String {
@@ -45,9 +60,11 @@ pub async fn index(State(state): State<AppState>) -> DashboardResult<Html<String
 
     let uptime_duration = chrono::Utc::now() - engine_state.start_time;
     let uptime_formatted = format_duration(uptime_duration);
+    let performance = &engine_state.performance;
 
     let template = IndexTemplate {
         title: "Solana Watchtower Dashboard".to_string(),
+        base_path: state.base_path.to_string(),
         engine_status: if engine_state.running {
             "Running".to_string()
         } else {
@@ -56,6 +73,11 @@ pub async fn index(State(state): State<AppState>) -> DashboardResult<Html<String
         alert_count: alert_stats.total_alerts as usize,
         active_rules,
         uptime: uptime_formatted,
+        events_per_second: format!("{:.2}", performance.current_events_per_second),
+        avg_processing_ms: format!(
+            "{:.2} ms",
+            performance.avg_event_processing_time.as_secs_f64() * 1000.0
+        ),
     };
 
     let html = template.render().map_err(DashboardError::Template)?;
@@ -65,41 +87,23 @@ pub async fn index(State(state): State<AppState>) -> DashboardResult<Html<String
 /// Alerts management page
 pub async fn alerts_page(
     State(state): State<AppState>,
-    Query(query): Query<PaginationQuery>,
+    Query(query): Query<AlertQuery>,
 ) -> DashboardResult<Html<String>> {
-    let page = query.page.unwrap_or(1);
-    let limit = query.limit.unwrap_or(20);
-
-    let all_alerts = state.alert_manager.list_alerts(None).await;
-    let total_alerts = all_alerts.len();
-
-    // Simple pagination
-    let start = ((page - 1) * limit) as usize;
-    let end = (start + limit as usize).min(total_alerts);
-    let alerts = if start < total_alerts {
-        all_alerts[start..end].to_vec()
-    } else {
-        Vec::new()
-    };
+    let filter = query.filter().map_err(DashboardError::Http)?;
+    let search = query.search().map_err(DashboardError::Http)?;
+    let (page, limit) = query.page_and_limit();
+    let result = state.alert_manager.query_alerts(&filter, &search).await;
 
     let template = AlertsTemplate {
         title: "Alerts".to_string(),
-        alerts: alerts
-            .into_iter()
-            .map(|alert| AlertInfo {
-                id: alert.id.clone(),
-                severity: alert.severity.as_str().to_string(),
-                message: alert.message.clone(),
-                program_id: alert.program_id.to_string(),
-                timestamp: alert.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-                resolved: alert.resolved,
-            })
-            .collect(),
+        base_path: state.base_path.to_string(),
+        alerts: result.alerts.iter().map(AlertInfo::from).collect(),
         pagination: PaginationInfo {
             page,
             limit,
-            total: total_alerts as u32,
-            pages: ((total_alerts as f64) / (limit as f64)).ceil() as u32,
+            total: result.total as u32,
+            pages: ((result.total as f64) / (limit as f64)).ceil() as u32,
+            next_cursor: result.next_cursor.as_ref().map(AlertCursor::encode),
         },
     };
 
@@ -107,12 +111,37 @@ pub async fn alerts_page(
     Ok(Html(html))
 }
 
+/// Metrics page for browsers, Prometheus exposition for scrapers
+pub async fn metrics(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if accepts_html(&headers) {
+        return metrics_page(State(state)).await.into_response();
+    }
+
+    let encoder = TextEncoder::new();
+    match encoder.encode_to_string(&state.metrics.registry().gather()) {
+        Ok(body) => (
+            [(header::CONTENT_TYPE, encoder.format_type().to_string())],
+            body,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Whether a request comes from a browser asking for a page.
+pub(crate) fn accepts_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
+
 /// Metrics overview page
 pub async fn metrics_page(State(state): State<AppState>) -> DashboardResult<Html<String>> {
     let metrics_snapshot = state.metrics.snapshot();
 
     // Convert metrics to display format
-    let metric_items: Vec<MetricItem> = metrics_snapshot
+    let mut metric_items: Vec<MetricItem> = metrics_snapshot
         .values
         .into_iter()
         .map(|(name, value)| MetricItem {
@@ -120,9 +149,11 @@ pub async fn metrics_page(State(state): State<AppState>) -> DashboardResult<Html
             value: value.to_string(),
         })
         .collect();
+    metric_items.sort_by(|a, b| a.name.cmp(&b.name));
 
     let template = MetricsTemplate {
         title: "System Metrics".to_string(),
+        base_path: state.base_path.to_string(),
         metrics: metric_items,
     };
 
@@ -132,20 +163,11 @@ pub async fn metrics_page(State(state): State<AppState>) -> DashboardResult<Html
 
 /// Rules management page
 pub async fn rules_page(State(state): State<AppState>) -> DashboardResult<Html<String>> {
-    let rule_names = state.engine.list_rules().await;
-
-    let rule_items: Vec<RuleInfo> = rule_names
-        .into_iter()
-        .map(|name| RuleInfo {
-            name: name.clone(),
-            description: format!("Rule: {}", name),
-            enabled: true,
-            trigger_count: 0,
-        })
-        .collect();
+    let rule_items = rule_infos(&state, state.engine.rule_statuses().await).await;
 
     let template = RulesTemplate {
         title: "Monitoring Rules".to_string(),
+        base_path: state.base_path.to_string(),
         rules: rule_items,
     };
 
@@ -159,6 +181,7 @@ pub async fn settings_page(State(state): State<AppState>) -> DashboardResult<Htm
 
     let template = SettingsTemplate {
         title: "Settings".to_string(),
+        base_path: state.base_path.to_string(),
         notification_channels: dashboard_state.notification_channels.clone(),
     };
 
@@ -168,11 +191,18 @@ pub async fn settings_page(State(state): State<AppState>) -> DashboardResult<Htm
 
 /// API: System status
 pub async fn api_status(State(state): State<AppState>) -> Json<ApiResponse<SystemStatus>> {
+    Json(ApiResponse::success(system_status(&state).await))
+}
+
+/// Engine, alert and performance status of this instance.
+pub async fn system_status(state: &AppState) -> SystemStatus {
     let engine_state = state.engine.state().await;
+    let engine_stats = state.engine.statistics().await;
     let alert_stats = state.alert_manager.statistics().await;
     let active_rules = state.engine.list_rules().await.len();
+    let performance = &engine_stats.performance;
 
-    let status = SystemStatus {
+    SystemStatus {
         engine_status: if engine_state.running {
             "Running".to_string()
         } else {
@@ -180,51 +210,39 @@ pub async fn api_status(State(state): State<AppState>) -> Json<ApiResponse<Syste
         },
         alert_count: alert_stats.total_alerts as usize,
         active_rules,
-        uptime_seconds: 8100, // TODO: Calculate actual uptime
-        memory_usage_mb: 256, // TODO: Get actual memory usage
+        uptime_seconds: engine_stats.uptime.as_secs(),
+        memory_usage_mb: performance.memory_usage_bytes.unwrap_or(0) / (1024 * 1024),
         connected_websockets: state.ws_connections.read().await.len(),
-    };
-
-    Json(ApiResponse::success(status))
+        performance: PerformanceInfo {
+            events_processed: engine_stats.events_processed,
+            avg_event_processing_ms: performance.avg_event_processing_time.as_secs_f64() * 1000.0,
+            avg_rule_evaluation_ms: performance.avg_rule_evaluation_time.as_secs_f64() * 1000.0,
+            current_events_per_second: performance.current_events_per_second,
+            peak_events_per_second: performance.peak_events_per_second,
+        },
+    }
 }
 
-/// API: Get alerts with pagination
+/// API: Search alerts with sorting and page or cursor pagination
 pub async fn api_alerts(
     State(state): State<AppState>,
-    Query(query): Query<PaginationQuery>,
+    Query(query): Query<AlertQuery>,
 ) -> Json<ApiResponse<Vec<AlertInfo>>> {
-    let page = query.page.unwrap_or(1);
-    let limit = query.limit.unwrap_or(20);
-
-    let all_alerts = state.alert_manager.list_alerts(None).await;
-    let total_alerts = all_alerts.len();
-
-    // Simple pagination
-    let start = ((page - 1) * limit) as usize;
-    let end = (start + limit as usize).min(total_alerts);
-    let alerts = if start < total_alerts {
-        all_alerts[start..end].to_vec()
-    } else {
-        Vec::new()
+    let (filter, search) = match (query.filter(), query.search()) {
+        (Ok(filter), Ok(search)) => (filter, search),
+        (Err(e), _) | (_, Err(e)) => return Json(ApiResponse::error(e)),
     };
+    let (page, limit) = query.page_and_limit();
+    let result = state.alert_manager.query_alerts(&filter, &search).await;
 
-    let alert_infos: Vec<AlertInfo> = alerts
-        .into_iter()
-        .map(|alert| AlertInfo {
-            id: alert.id.clone(),
-            severity: alert.severity.as_str().to_string(),
-            message: alert.message.clone(),
-            program_id: alert.program_id.to_string(),
-            timestamp: alert.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-            resolved: alert.resolved,
-        })
-        .collect();
+    let alert_infos: Vec<AlertInfo> = result.alerts.iter().map(AlertInfo::from).collect();
 
     let pagination = PaginationInfo {
         page,
         limit,
-        total: total_alerts as u32,
-        pages: ((total_alerts as f64) / (limit as f64)).ceil() as u32,
+        total: result.total as u32,
+        pages: ((result.total as f64) / (limit as f64)).ceil() as u32,
+        next_cursor: result.next_cursor.as_ref().map(AlertCursor::encode),
     };
 
     Json(ApiResponse::success_with_pagination(
@@ -233,19 +251,47 @@ pub async fn api_alerts(
     ))
 }
 
-/// API: Get specific alert details
+/// API: Get specific alert details, with the event that triggered it and
+/// its transaction as fetched from the RPC node
 pub async fn api_alert_detail(
     State(state): State<AppState>,
     Path(alert_id): Path<String>,
 ) -> Json<ApiResponse<AlertDetail>> {
-    match state.alert_manager.get_alert(&alert_id) {
+    // Resolved alerts are kept in history
+    let alert = match state.alert_manager.get_alert(&alert_id) {
+        Some(alert) => Some(alert),
+        None => state
+            .alert_manager
+            .history(None)
+            .await
+            .into_iter()
+            .find(|alert| alert.id == alert_id),
+    };
+
+    match alert {
         Some(alert) => {
+            // Events leave history with age, so older alerts may have none
+            let event = alert.event_id.as_deref().and_then(|event_id| {
+                state.engine.find_event(
+                    &alert.program_id.to_string(),
+                    &alert.program_name,
+                    event_id,
+                )
+            });
+            let (transaction, transaction_error) =
+                match alert_transaction(&state, &alert, event.as_ref()).await {
+                    Some(Ok(transaction)) => (Some(transaction), None),
+                    Some(Err(e)) => (None, Some(e)),
+                    None => (None, None),
+                };
+
             let detail = AlertDetail {
                 id: alert.id.clone(),
                 severity: alert.severity.as_str().to_string(),
                 message: alert.message.clone(),
                 program_id: alert.program_id.to_string(),
                 timestamp: alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                acknowledged: alert.acknowledged,
                 resolved: alert.resolved,
                 metadata: alert
                     .metadata
@@ -253,6 +299,13 @@ pub async fn api_alert_detail(
                     .map(|(k, v)| (k.clone(), v.to_string()))
                     .collect(),
                 rule_name: alert.rule_name.clone(),
+                tags: alert.tags.clone(),
+                audit_trail: state.alert_manager.audit_trail(&alert.id),
+                deliveries: state.alert_manager.delivery_history(&alert.id),
+                event_id: alert.event_id.clone(),
+                event,
+                transaction,
+                transaction_error,
             };
             Json(ApiResponse::success(detail))
         }
@@ -260,12 +313,177 @@ pub async fn api_alert_detail(
     }
 }
 
+/// Transaction of an alert, from the signature of its event or its metadata.
+///
+/// `None` without a signature or without a subscriber to fetch it through.
+async fn alert_transaction(
+    state: &AppState,
+    alert: &Alert,
+    event: Option<&ProgramEvent>,
+) -> Option<Result<serde_json::Value, String>> {
+    let subscriber = state.subscriber.as_ref()?;
+    let signature = match event.and_then(|event| event.signature) {
+        Some(signature) => signature,
+        None => {
+            let signature = alert.metadata.get(SIGNATURE_KEY)?.as_str()?;
+            match signature.parse() {
+                Ok(signature) => signature,
+                Err(_) => return Some(Err(format!("Invalid signature: {}", signature))),
+            }
+        }
+    };
+
+    Some(subscriber.fetch_transaction(&signature).await.map_err(|e| {
+        warn!("Failed to fetch transaction {}: {}", signature, e);
+        e.to_string()
+    }))
+}
+
+/// API: Acknowledge an alert
+pub async fn api_acknowledge_alert(
+    State(state): State<AppState>,
+    Path(alert_id): Path<String>,
+    user: Option<Extension<CurrentUser>>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    match acknowledge(&state, &alert_id, operator(user)).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Alert acknowledged".to_string())),
+        ),
+        Err(e) => alert_error(e),
+    }
+}
+
+/// API: Resolve an alert, optionally recording whether it was a true or false positive
+pub async fn api_resolve_alert(
+    State(state): State<AppState>,
+    Path(alert_id): Path<String>,
+    user: Option<Extension<CurrentUser>>,
+    request: Option<Json<ResolveAlertRequest>>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let feedback = request.and_then(|Json(request)| request.feedback);
+    match resolve(&state, &alert_id, operator(user), feedback).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse::success("Alert resolved".to_string())),
+        ),
+        Err(e) => alert_error(e),
+    }
+}
+
+/// Response of a failed alert change.
+fn alert_error(error: AlertError) -> (StatusCode, Json<ApiResponse<String>>) {
+    let status = match error {
+        AlertError::NotFound { .. } => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ApiResponse::error(error.to_string())))
+}
+
+/// API: Acknowledge several alerts
+pub async fn api_acknowledge_alerts(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Json(request): Json<BulkAlertRequest>,
+) -> Json<ApiResponse<BulkAlertResult>> {
+    let operator = operator(user);
+    let mut result = BulkAlertResult::default();
+    for alert_id in request.ids {
+        match acknowledge(&state, &alert_id, operator.clone()).await {
+            Ok(()) => result.updated.push(alert_id),
+            Err(e) => {
+                result.failed.insert(alert_id, e.to_string());
+            }
+        }
+    }
+
+    Json(ApiResponse::success(result))
+}
+
+/// API: Resolve several alerts
+pub async fn api_resolve_alerts(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Json(request): Json<BulkAlertRequest>,
+) -> Json<ApiResponse<BulkAlertResult>> {
+    let operator = operator(user);
+    let mut result = BulkAlertResult::default();
+    for alert_id in request.ids {
+        match resolve(&state, &alert_id, operator.clone(), request.feedback).await {
+            Ok(()) => result.updated.push(alert_id),
+            Err(e) => {
+                result.failed.insert(alert_id, e.to_string());
+            }
+        }
+    }
+
+    Json(ApiResponse::success(result))
+}
+
+/// Operator recorded in audit trails for a request.
+pub(crate) fn operator(user: Option<Extension<CurrentUser>>) -> Option<String> {
+    user.map(|Extension(CurrentUser(name))| format!("dashboard:{}", name))
+}
+
+/// Acknowledge an alert and announce it to WebSocket clients.
+pub(crate) async fn acknowledge(
+    state: &AppState,
+    alert_id: &str,
+    operator: Option<String>,
+) -> Result<(), AlertError> {
+    state
+        .alert_manager
+        .acknowledge_alert_by(alert_id, operator.as_deref())
+        .await?;
+
+    info!("Alert {} acknowledged", alert_id);
+    send_alert_acknowledged(alert_id, operator, &state.ws_connections).await;
+    Ok(())
+}
+
+/// Resolve an alert, which is announced to WebSocket clients once done.
+pub(crate) async fn resolve(
+    state: &AppState,
+    alert_id: &str,
+    operator: Option<String>,
+    feedback: Option<AlertFeedback>,
+) -> Result<(), AlertError> {
+    // The resolution reaches WebSocket clients through the alert broadcast task
+    state
+        .alert_manager
+        .resolve_alert_with_feedback(alert_id, operator.as_deref(), feedback)
+        .await?;
+
+    info!("Alert {} resolved", alert_id);
+    Ok(())
+}
+
+/// API: Notification delivery attempts for an alert
+pub async fn api_alert_deliveries(
+    State(state): State<AppState>,
+    Path(alert_id): Path<String>,
+) -> Json<ApiResponse<Vec<DeliveryRecord>>> {
+    Json(ApiResponse::success(
+        state.alert_manager.delivery_history(&alert_id),
+    ))
+}
+
+/// API: Query notification delivery attempts, newest first
+pub async fn api_deliveries(
+    State(state): State<AppState>,
+    Query(query): Query<DeliveryQuery>,
+) -> Json<ApiResponse<Vec<DeliveryRecord>>> {
+    Json(ApiResponse::success(
+        state.alert_manager.deliveries().query(&query),
+    ))
+}
+
 /// API: Get metrics in JSON format
 pub async fn api_metrics(State(state): State<AppState>) -> Json<ApiResponse<MetricsData>> {
     let metrics_snapshot = state.metrics.snapshot();
 
     let metrics_data = MetricsData {
-        raw_prometheus: "# Prometheus metrics placeholder".to_string(),
+        raw_prometheus: state.metrics.export(),
         parsed_metrics: metrics_snapshot.values,
         timestamp: chrono::Utc::now().timestamp(),
     };
@@ -274,20 +492,46 @@ pub async fn api_metrics(State(state): State<AppState>) -> Json<ApiResponse<Metr
 }
 
 /// API: Get rules information
-pub async fn api_rules(State(state): State<AppState>) -> Json<ApiResponse<Vec<RuleInfo>>> {
-    let rule_names = state.engine.list_rules().await;
-
-    let rule_infos: Vec<RuleInfo> = rule_names
+pub async fn api_rules(
+    State(state): State<AppState>,
+    Query(query): Query<PaginationQuery>,
+) -> Json<ApiResponse<Vec<RuleInfo>>> {
+    let statuses = state
+        .engine
+        .rule_statuses()
+        .await
         .into_iter()
-        .map(|name| RuleInfo {
-            name: name.clone(),
-            description: format!("Rule: {}", name),
-            enabled: true,
-            trigger_count: 0,
+        .filter(|status| {
+            query
+                .tag
+                .as_ref()
+                .map_or(true, |tag| status.tags.contains(tag))
         })
         .collect();
 
-    Json(ApiResponse::success(rule_infos))
+    Json(ApiResponse::success(rule_infos(&state, statuses).await))
+}
+
+/// Rule information with the configuration of declarative rules.
+async fn rule_infos(state: &AppState, statuses: Vec<RuleStatus>) -> Vec<RuleInfo> {
+    let mut definitions: HashMap<String, RateOfChangeConfig> = state
+        .engine
+        .declarative_rules()
+        .await
+        .into_iter()
+        .map(|config| (config.name.clone(), config))
+        .collect();
+
+    statuses
+        .into_iter()
+        .map(|status| {
+            let definition = definitions.remove(&status.name);
+            RuleInfo {
+                definition,
+                ..RuleInfo::from(status)
+            }
+        })
+        .collect()
 }
 
 /// API: Get specific rule details
@@ -295,37 +539,346 @@ pub async fn api_rule_detail(
     State(state): State<AppState>,
     Path(rule_name): Path<String>,
 ) -> Json<ApiResponse<RuleDetail>> {
-    let rule_names = state.engine.list_rules().await;
-
-    if rule_names.contains(&rule_name) {
-        let detail = RuleDetail {
-            name: rule_name.clone(),
-            description: format!("Rule: {}", rule_name),
-            enabled: true,
-            trigger_count: 0,
-            last_triggered: None,
-            configuration: HashMap::new(),
-        };
-        Json(ApiResponse::success(detail))
+    let status = state
+        .engine
+        .rule_statuses()
+        .await
+        .into_iter()
+        .find(|status| status.name == rule_name);
+
+    match status {
+        Some(status) => {
+            let definition = state.engine.declarative_rule(&status.name).await;
+            let detail = RuleDetail {
+                name: status.name,
+                description: status.description,
+                enabled: status.enabled,
+                trigger_count: status.stats.triggers,
+                last_triggered: status.stats.last_triggered.map(|t| t.to_rfc3339()),
+                evaluations: status.stats.evaluations,
+                timeouts: status.stats.timeouts,
+                avg_evaluation_ms: status.stats.avg_evaluation_time.as_secs_f64() * 1000.0,
+                false_positives: status.stats.false_positives,
+                true_positives: status.stats.true_positives,
+                precision: status.stats.precision(),
+                notes: status.stats.notes,
+                configuration: HashMap::new(),
+                definition,
+            };
+            Json(ApiResponse::success(detail))
+        }
+        None => Json(ApiResponse::error("Rule not found")),
+    }
+}
+
+/// API: Create a declarative rule
+pub async fn api_create_rule(
+    State(state): State<AppState>,
+    Json(config): Json<RateOfChangeConfig>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let name = config.name.clone();
+    match state.engine.create_rule(config).await {
+        Ok(()) => {
+            info!("Rule {} created from the dashboard", name);
+            (
+                StatusCode::CREATED,
+                Json(ApiResponse::success(format!("Rule {} created", name))),
+            )
+        }
+        Err(e) => rule_error(e),
+    }
+}
+
+/// API: Replace the configuration of a declarative rule
+pub async fn api_update_rule(
+    State(state): State<AppState>,
+    Path(rule_name): Path<String>,
+    Json(config): Json<RateOfChangeConfig>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    match state.engine.update_rule(&rule_name, config).await {
+        Ok(()) => {
+            info!("Rule {} updated from the dashboard", rule_name);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(format!("Rule {} updated", rule_name))),
+            )
+        }
+        Err(e) => rule_error(e),
+    }
+}
+
+/// API: Remove a declarative rule
+pub async fn api_delete_rule(
+    State(state): State<AppState>,
+    Path(rule_name): Path<String>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    match state.engine.delete_rule(&rule_name).await {
+        Ok(()) => {
+            info!("Rule {} deleted from the dashboard", rule_name);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(format!("Rule {} deleted", rule_name))),
+            )
+        }
+        Err(e) => rule_error(e),
+    }
+}
+
+/// API: Enable a rule at runtime
+pub async fn api_enable_rule(
+    State(state): State<AppState>,
+    Path(rule_name): Path<String>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    set_rule_enabled(&state, &rule_name, true).await
+}
+
+/// API: Disable a rule at runtime
+pub async fn api_disable_rule(
+    State(state): State<AppState>,
+    Path(rule_name): Path<String>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    set_rule_enabled(&state, &rule_name, false).await
+}
+
+async fn set_rule_enabled(
+    state: &AppState,
+    rule_name: &str,
+    enabled: bool,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let action = if enabled { "enabled" } else { "disabled" };
+    match state.engine.set_rule_enabled(rule_name, enabled).await {
+        Ok(()) => {
+            info!("Rule {} {} from the dashboard", rule_name, action);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(format!(
+                    "Rule {} {}",
+                    rule_name, action
+                ))),
+            )
+        }
+        Err(e) => rule_error(e),
+    }
+}
+
+/// API: Evaluate a rule against a sample event without raising alerts
+pub async fn api_test_rule(
+    State(state): State<AppState>,
+    Path(rule_name): Path<String>,
+    Json(event): Json<ProgramEvent>,
+) -> (StatusCode, Json<ApiResponse<RuleResult>>) {
+    match state.engine.test_rule(&rule_name, &event).await {
+        Ok(result) => (StatusCode::OK, Json(ApiResponse::success(result))),
+        Err(e @ EngineError::RuleNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(e.to_string())),
+        ),
+        Err(e) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse::error(e.to_string())),
+        ),
+    }
+}
+
+/// Response of a failed rule change.
+fn rule_error(error: EngineError) -> (StatusCode, Json<ApiResponse<String>>) {
+    let status = match error {
+        EngineError::RuleNotFound(_) => StatusCode::NOT_FOUND,
+        EngineError::RuleExists(_) | EngineError::RuleNotDeclarative(_) => StatusCode::CONFLICT,
+        EngineError::InvalidRule(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ApiResponse::error(error.to_string())))
+}
+
+/// API: Report a false positive for a rule
+pub async fn api_rule_false_positive(
+    State(state): State<AppState>,
+    Path(rule_name): Path<String>,
+    Json(request): Json<FalsePositiveRequest>,
+) -> Json<ApiResponse<String>> {
+    match state
+        .engine
+        .report_false_positive(&rule_name, request.note)
+        .await
+    {
+        Ok(()) => {
+            info!("False positive reported for rule {}", rule_name);
+            Json(ApiResponse::success("False positive recorded".to_string()))
+        }
+        Err(e) => Json(ApiResponse::error(e.to_string())),
+    }
+}
+
+/// API: List failed rule evaluations and alert dispatches
+pub async fn api_dead_letters(State(state): State<AppState>) -> Json<ApiResponse<Vec<DeadLetter>>> {
+    Json(ApiResponse::success(state.engine.dead_letters().list()))
+}
+
+/// API: Retry a dead letter
+pub async fn api_retry_dead_letter(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Json<ApiResponse<String>> {
+    match state.engine.retry_dead_letter(id).await {
+        Ok(result) => Json(ApiResponse::success(format!(
+            "Retried: {} rules evaluated, {} alerts generated",
+            result.rules_evaluated, result.alerts_generated
+        ))),
+        Err(e) => Json(ApiResponse::error(e.to_string())),
+    }
+}
+
+/// API: Discard a dead letter
+pub async fn api_discard_dead_letter(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Json<ApiResponse<String>> {
+    if state.engine.dead_letters().remove(id) {
+        info!("Discarded dead letter {}", id);
+        Json(ApiResponse::success("Dead letter discarded".to_string()))
     } else {
-        Json(ApiResponse::error("Rule not found"))
+        Json(ApiResponse::error("Dead letter not found"))
     }
 }
 
 /// API: Get monitored programs
-pub async fn api_programs(State(_state): State<AppState>) -> Json<ApiResponse<Vec<ProgramInfo>>> {
-    // TODO: Implement once get_monitored_programs is available
-    let program_infos: Vec<ProgramInfo> = vec![ProgramInfo {
-        id: "11111111111111111111111111111112".to_string(),
-        name: "System Program".to_string(),
-        events_processed: 1000,
-        alerts_generated: 5,
-        last_activity: Some("2024-01-15 10:30:00 UTC".to_string()),
-    }];
+pub async fn api_programs(State(state): State<AppState>) -> Json<ApiResponse<Vec<ProgramInfo>>> {
+    let Some(subscriber) = &state.subscriber else {
+        return Json(ApiResponse::error(PROGRAMS_UNAVAILABLE));
+    };
+
+    let alerts_by_program = state.alert_manager.statistics().await.by_program;
+    let program_infos = subscriber
+        .programs()
+        .into_iter()
+        .map(|program| ProgramInfo {
+            id: program.config.id.to_string(),
+            alerts_generated: alerts_by_program
+                .get(&program.config.name)
+                .copied()
+                .unwrap_or(0),
+            name: program.config.name,
+            events_processed: program.events,
+            last_activity: program
+                .last_event
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string()),
+            tags: program.config.tags,
+        })
+        .collect();
 
     Json(ApiResponse::success(program_infos))
 }
 
+/// API: Start monitoring a program
+pub async fn api_add_program(
+    State(state): State<AppState>,
+    Json(program): Json<ProgramConfig>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let Some(subscriber) = &state.subscriber else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::error(PROGRAMS_UNAVAILABLE)),
+        );
+    };
+
+    let message = format!("Monitoring {} ({})", program.name, program.id);
+    match subscriber.subscribe_program(program) {
+        Ok(()) => (StatusCode::CREATED, Json(ApiResponse::success(message))),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e.to_string())),
+        ),
+    }
+}
+
+/// API: Stop monitoring a program
+pub async fn api_remove_program(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let Some(subscriber) = &state.subscriber else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::error(PROGRAMS_UNAVAILABLE)),
+        );
+    };
+    let Ok(program_id) = program_id.parse() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(format!(
+                "Invalid program ID: {}",
+                program_id
+            ))),
+        );
+    };
+
+    match subscriber.unsubscribe_program(&program_id) {
+        Ok(program) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(format!(
+                "Stopped monitoring {} ({})",
+                program.name, program.id
+            ))),
+        ),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(e.to_string())),
+        ),
+    }
+}
+
+/// Error of the program endpoints when the dashboard runs without a subscriber.
+const PROGRAMS_UNAVAILABLE: &str = "Program management is not available without a subscriber";
+
+/// API: Send a test notification through every channel, or only `channel`
+pub async fn api_test_notifications(
+    State(state): State<AppState>,
+    request: Option<Json<NotificationTestRequest>>,
+) -> (StatusCode, Json<ApiResponse<Vec<ChannelTestResult>>>) {
+    let Some(notifier) = &state.notifier else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::error(
+                "Notification testing is not available without a notifier",
+            )),
+        );
+    };
+
+    let results = match request.and_then(|Json(request)| request.channel) {
+        Some(channel) => match notifier.test_channel(&channel).await {
+            Some(result) => vec![(channel, result)],
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(format!(
+                        "Notification channel not found: {}",
+                        channel
+                    ))),
+                )
+            }
+        },
+        None => notifier.test_channels().await.into_iter().collect(),
+    };
+
+    let mut results: Vec<ChannelTestResult> = results
+        .into_iter()
+        .map(|(channel, result)| ChannelTestResult {
+            channel,
+            success: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+        })
+        .collect();
+    results.sort_by(|a, b| a.channel.cmp(&b.channel));
+
+    info!(
+        "Tested {} notification channel(s), {} failed",
+        results.len(),
+        results.iter().filter(|result| !result.success).count()
+    );
+    (StatusCode::OK, Json(ApiResponse::success(results)))
+}
+
 /// API: Get configuration
 pub async fn api_config(State(state): State<AppState>) -> Json<ApiResponse<ConfigInfo>> {
     let dashboard_state = state.dashboard_state.read().await;
@@ -341,21 +894,69 @@ pub async fn api_config(State(state): State<AppState>) -> Json<ApiResponse<Confi
 /// API: Update configuration
 pub async fn api_update_config(
     State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(config): Json<ConfigUpdateRequest>,
 ) -> Json<ApiResponse<String>> {
     info!("Configuration update requested: {:?}", config);
 
     let mut dashboard_state = state.dashboard_state.write().await;
+    let mut updated = dashboard_state.clone();
 
     // Update notification channels if provided
     if let Some(channels) = config.notification_channels {
-        dashboard_state.notification_channels = channels;
+        updated.notification_channels = channels;
     }
 
     // Update monitoring settings if provided
     if let Some(settings) = config.monitoring_settings {
-        dashboard_state.monitoring_settings = settings;
+        updated.monitoring_settings = settings;
     }
+    let updated = with_channel_status(updated);
+
+    let programs = match validate_subscriber_update(
+        &config.subscriber.unwrap_or_default(),
+        state.subscriber.as_ref(),
+    ) {
+        Ok(programs) => programs,
+        Err(e) => {
+            warn!("Configuration update rejected: {}", e);
+            return Json(ApiResponse::error(e.to_string()));
+        }
+    };
+
+    let mut current_value = serde_json::to_value(&*dashboard_state).unwrap_or_default();
+    let mut updated_value = serde_json::to_value(&updated).unwrap_or_default();
+    if let (Some(subscriber), Some(programs)) = (&state.subscriber, &programs) {
+        current_value["programs"] = programs_value(&monitored_programs(subscriber));
+        updated_value["programs"] = programs_value(programs);
+    }
+    let changes = config_changes(&current_value, &updated_value);
+    if changes.is_empty() {
+        return Json(ApiResponse::success("Configuration unchanged".to_string()));
+    }
+
+    // Validated, applied to the running pipeline and saved as a whole
+    let rollback = match apply_update(&state, &dashboard_state, &updated, programs.as_deref()).await
+    {
+        Ok(rollback) => rollback,
+        Err(e) => {
+            warn!("Configuration update rejected: {}", e);
+            return Json(ApiResponse::error(e.to_string()));
+        }
+    };
+
+    // The update is only kept once recorded, so no change escapes the audit log
+    let actor = operator(user).unwrap_or_else(|| "dashboard".to_string());
+    if let Err(e) = state.config_audit.record(&actor, changes) {
+        warn!("Failed to record configuration change: {}", e);
+        rollback.restore(&state).await;
+        return Json(ApiResponse::error(format!(
+            "Failed to record configuration change: {}",
+            e
+        )));
+    }
+    rollback.commit(&state).await;
+    *dashboard_state = updated;
 
     info!("Configuration updated successfully");
     Json(ApiResponse::success(
@@ -363,6 +964,14 @@ pub async fn api_update_config(
     ))
 }
 
+/// API: Configuration changes, newest first
+pub async fn api_audit(
+    State(state): State<AppState>,
+    Query(query): Query<ConfigAuditQuery>,
+) -> Json<ApiResponse<Vec<ConfigAuditEntry>>> {
+    Json(ApiResponse::success(state.config_audit.query(&query)))
+}
+
 /// WebSocket handler
 pub async fn websocket_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
     ws.on_upgrade(|socket| handle_websocket(socket, state))
@@ -377,27 +986,28 @@ pub async fn health_check() -> Json<ApiResponse<HealthStatus>> {
     Json(ApiResponse::success(status))
 }
 
-/// Serve static files (embedded or from filesystem)
-pub async fn serve_static(Path(file_path): Path<String>) -> Result<Response, StatusCode> {
-    // For demo purposes, return a simple CSS file
-    if file_path.ends_with(".css") {
-        let css_content = include_str!("../static/style.css");
-        Ok(([(header::CONTENT_TYPE, "text/css")], css_content).into_response())
-    } else if file_path.ends_with(".js") {
-        let js_content = include_str!("../static/app.js");
-        Ok((
-            [(header::CONTENT_TYPE, "application/javascript")],
-            js_content,
-        )
-            .into_response())
-    } else {
-        Err(StatusCode::NOT_FOUND)
-    }
+/// Static assets bundled into the binary from the `static` directory
+#[derive(RustEmbed)]
+#[folder = "static/"]
+struct StaticAssets;
+
+/// Serve an embedded static asset, at the path below `/static`
+pub async fn serve_static(uri: Uri, headers: HeaderMap) -> Result<Response, StatusCode> {
+    let file_path = uri.path().trim_start_matches('/');
+    let asset = StaticAssets::get(file_path).ok_or(StatusCode::NOT_FOUND)?;
+    let content_type = mime_guess::from_path(file_path).first_or_octet_stream();
+
+    Ok(static_response(
+        &headers,
+        content_type.as_ref(),
+        asset.data,
+        &asset.metadata.sha256_hash(),
+    ))
 }
 
 // Data structures for API responses
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemStatus {
     pub engine_status: String,
     pub alert_count: usize,
@@ -405,16 +1015,43 @@ pub struct SystemStatus {
     pub uptime_seconds: u64,
     pub memory_usage_mb: u64,
     pub connected_websockets: usize,
+    pub performance: PerformanceInfo,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceInfo {
+    pub events_processed: u64,
+    pub avg_event_processing_ms: f64,
+    pub avg_rule_evaluation_ms: f64,
+    pub current_events_per_second: f64,
+    pub peak_events_per_second: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertInfo {
     pub id: String,
     pub severity: String,
     pub message: String,
     pub program_id: String,
     pub timestamp: String,
+    pub acknowledged: bool,
     pub resolved: bool,
+    pub tags: Vec<String>,
+}
+
+impl From<&Alert> for AlertInfo {
+    fn from(alert: &Alert) -> Self {
+        Self {
+            id: alert.id.clone(),
+            severity: alert.severity.as_str().to_string(),
+            message: alert.message.clone(),
+            program_id: alert.program_id.to_string(),
+            timestamp: alert.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            acknowledged: alert.acknowledged,
+            resolved: alert.resolved,
+            tags: alert.tags.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -424,9 +1061,20 @@ pub struct AlertDetail {
     pub message: String,
     pub program_id: String,
     pub timestamp: String,
+    pub acknowledged: bool,
     pub resolved: bool,
     pub metadata: HashMap<String, String>,
     pub rule_name: String,
+    pub tags: Vec<String>,
+    pub audit_trail: Vec<AlertAuditEntry>,
+    pub deliveries: Vec<DeliveryRecord>,
+    pub event_id: Option<String>,
+    /// Event that triggered the alert, while it is still held in history
+    pub event: Option<ProgramEvent>,
+    /// Transaction of the event as returned by `getTransaction`
+    pub transaction: Option<serde_json::Value>,
+    /// Why the transaction could not be fetched
+    pub transaction_error: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -448,6 +1096,28 @@ pub struct RuleInfo {
     pub description: String,
     pub enabled: bool,
     pub trigger_count: u64,
+    pub last_triggered: Option<String>,
+    pub avg_evaluation_ms: f64,
+    pub precision: Option<f64>,
+    pub tags: Vec<String>,
+    /// Configuration of a declarative rule, which can be edited and deleted
+    pub definition: Option<RateOfChangeConfig>,
+}
+
+impl From<RuleStatus> for RuleInfo {
+    fn from(status: RuleStatus) -> Self {
+        Self {
+            name: status.name,
+            description: status.description,
+            enabled: status.enabled,
+            trigger_count: status.stats.triggers,
+            last_triggered: status.stats.last_triggered.map(|t| t.to_rfc3339()),
+            avg_evaluation_ms: status.stats.avg_evaluation_time.as_secs_f64() * 1000.0,
+            precision: status.stats.precision(),
+            tags: status.tags,
+            definition: None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -457,7 +1127,53 @@ pub struct RuleDetail {
     pub enabled: bool,
     pub trigger_count: u64,
     pub last_triggered: Option<String>,
+    pub evaluations: u64,
+    pub timeouts: u64,
+    pub avg_evaluation_ms: f64,
+    pub false_positives: u64,
+    pub true_positives: u64,
+    pub precision: Option<f64>,
+    pub notes: Vec<RuleNote>,
     pub configuration: HashMap<String, String>,
+    pub definition: Option<RateOfChangeConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveAlertRequest {
+    pub feedback: Option<AlertFeedback>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkAlertRequest {
+    pub ids: Vec<String>,
+    /// Verdict recorded for every resolved alert
+    #[serde(default)]
+    pub feedback: Option<AlertFeedback>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BulkAlertResult {
+    pub updated: Vec<String>,
+    /// Errors per alert ID
+    pub failed: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FalsePositiveRequest {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationTestRequest {
+    /// Channel to test instead of all of them
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChannelTestResult {
+    pub channel: String,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -467,6 +1183,7 @@ pub struct ProgramInfo {
     pub events_processed: u64,
     pub alerts_generated: u64,
     pub last_activity: Option<String>,
+    pub tags: Vec<String>,
 }
 
 // Re-export types from lib.rs for convenience
@@ -479,9 +1196,12 @@ pub struct ConfigInfo {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ConfigUpdateRequest {
     pub notification_channels: Option<Vec<NotificationChannel>>,
     pub monitoring_settings: Option<MonitoringSettings>,
+    /// Only `programs` can be changed while running
+    pub subscriber: Option<SubscriberUpdate>,
 }
 
 #[derive(Debug, Serialize)]
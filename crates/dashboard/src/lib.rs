@@ -1,26 +1,67 @@
 use anyhow::Result;
 use axum::{
-    http::StatusCode,
-    response::{IntoResponse, Json},
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    http::{header, HeaderValue, StatusCode},
+    middleware,
+    response::{IntoResponse, Json, Redirect},
+    routing::{delete, get, post},
     Router,
 };
+use axum_server::tls_rustls::RustlsConfig;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+};
 use tokio::net::TcpListener;
 use tokio::sync::RwLock;
+use tower::Layer;
 use tower_http::{
-    cors::CorsLayer,
-    services::{ServeDir, ServeFile},
+    compression::CompressionLayer, cors::CorsLayer, services::ServeDir,
+    set_header::SetResponseHeaderLayer, timeout::TimeoutLayer,
 };
-use tracing::info;
-use watchtower_engine::{AlertManager, MetricsCollector, MonitoringEngine};
-
+use tracing::{info, warn};
+use watchtower_engine::{
+    AlertCursor, AlertFilter, AlertManager, AlertSearch, AlertSeverity, AlertSort, ConfigAuditLog,
+    MetricsCollector, MonitoringEngine, TimeRange,
+};
+use watchtower_notifier::NotificationManager;
+use watchtower_subscriber::SubscriptionHandle;
+
+mod auth;
+mod caching;
+mod config_apply;
+mod diagnostics;
+mod events;
+mod export;
+mod federation;
+mod grafana;
 mod handlers;
+mod limits;
+mod openapi;
+mod silences;
+mod slack;
+mod sse;
 mod templates;
 mod websocket;
 
+pub use auth::*;
+pub use caching::*;
+pub use config_apply::*;
+pub use diagnostics::*;
+pub use events::*;
+pub use export::*;
+pub use federation::*;
+pub use grafana::*;
 pub use handlers::*;
+pub use limits::*;
+pub use openapi::*;
+pub use silences::*;
+pub use slack::*;
+pub use sse::*;
 pub use templates::*;
 pub use websocket::*;
 
@@ -31,8 +72,33 @@ pub struct DashboardConfig {
     pub port: u16,
     pub enable_cors: bool,
     pub static_dir: Option<String>,
+    /// Signing secret verifying Slack interactivity callbacks
+    pub slack_signing_secret: Option<String>,
+    /// User authentication
+    pub auth: AuthConfig,
+    /// PEM certificate chain; with `tls_key` the dashboard serves HTTPS
+    pub tls_cert: Option<String>,
+    /// PEM private key of `tls_cert`
+    pub tls_key: Option<String>,
+    /// URL prefix when served under a path of a reverse proxy, e.g. `/watchtower`
+    pub base_path: String,
+    /// Require authentication for Prometheus scrapes of `/metrics`
+    pub metrics_auth: bool,
+    /// JSON lines file recording configuration changes; kept in memory when unset
+    pub audit_log_path: Option<String>,
+    /// Watchtower configuration file settings changed through `/api/config`
+    /// are saved to and loaded from at startup; changes last until restart
+    /// when unset
+    pub config_path: Option<String>,
+    /// Rate limiting, body size and timeout limits of requests
+    pub limits: RequestLimits,
+    /// Remote watchtower instances combined in the fleet view
+    pub federation: FederationConfig,
 }
 
+/// Configuration changes kept in memory for `/api/audit`
+const CONFIG_AUDIT_CAPACITY: usize = 1000;
+
 impl Default for DashboardConfig {
     fn default() -> Self {
         Self {
@@ -40,12 +106,22 @@ impl Default for DashboardConfig {
             port: 8080,
             enable_cors: true,
             static_dir: None,
+            slack_signing_secret: None,
+            auth: AuthConfig::default(),
+            tls_cert: None,
+            tls_key: None,
+            base_path: String::new(),
+            metrics_auth: false,
+            audit_log_path: None,
+            config_path: None,
+            limits: RequestLimits::default(),
+            federation: FederationConfig::default(),
         }
     }
 }
 
 /// Notification channel configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NotificationChannel {
     pub name: String,
     pub enabled: bool,
@@ -53,7 +129,7 @@ pub struct NotificationChannel {
 }
 
 /// Monitoring settings configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MonitoringSettings {
     pub max_events_per_minute: u32,
     pub alert_retention_days: u32,
@@ -104,6 +180,26 @@ pub struct AppState {
     pub metrics: Arc<MetricsCollector>,
     pub ws_connections: Arc<RwLock<HashMap<String, WebSocketConnection>>>,
     pub dashboard_state: Arc<RwLock<DashboardState>>,
+    pub slack_signing_secret: Option<Arc<str>>,
+    pub auth: Arc<Authenticator>,
+    /// URL prefix of the dashboard, empty when served at the root
+    pub base_path: Arc<str>,
+    /// Monitored programs of the running subscriber
+    pub subscriber: Option<SubscriptionHandle>,
+    /// Notification channels tested through `/api/notifications/test`
+    pub notifier: Option<Arc<NotificationManager>>,
+    /// Whether Prometheus scrapes of `/metrics` need no authentication
+    pub public_metrics: bool,
+    /// Changes made through `/api/config`
+    pub config_audit: Arc<ConfigAuditLog>,
+    /// Configuration file the settings of `/api/config` are saved to
+    pub config_path: Option<PathBuf>,
+    /// Whether new alerts are pushed to WebSocket and SSE clients
+    pub real_time_alerts: Arc<AtomicBool>,
+    /// Requests per client IP
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Remote instances of the fleet view
+    pub federation: Arc<Federation>,
 }
 
 /// Dashboard server
@@ -120,17 +216,56 @@ impl DashboardServer {
         alert_manager: Arc<AlertManager>,
         metrics: Arc<MetricsCollector>,
     ) -> Self {
+        // Session cookies never need to travel over plain HTTP when serving HTTPS
+        let mut auth = config.auth.clone();
+        if config.tls_cert.is_some() {
+            auth.secure_cookie = true;
+        }
+
+        let base_path = normalize_base_path(&config.base_path);
+
+        let config_audit = match &config.audit_log_path {
+            Some(path) => ConfigAuditLog::open(path, CONFIG_AUDIT_CAPACITY).unwrap_or_else(|e| {
+                warn!("Failed to load config audit log from {}: {}", path, e);
+                ConfigAuditLog::in_memory(CONFIG_AUDIT_CAPACITY)
+            }),
+            None => ConfigAuditLog::in_memory(CONFIG_AUDIT_CAPACITY),
+        };
+
         let state = AppState {
             engine,
             alert_manager,
             metrics,
             ws_connections: Arc::new(RwLock::new(HashMap::new())),
             dashboard_state: Arc::new(RwLock::new(DashboardState::default())),
+            slack_signing_secret: config.slack_signing_secret.as_deref().map(Arc::from),
+            auth: Arc::new(Authenticator::new(auth).with_base_path(&base_path)),
+            base_path: Arc::from(base_path),
+            subscriber: None,
+            notifier: None,
+            public_metrics: !config.metrics_auth,
+            config_audit: Arc::new(config_audit),
+            config_path: config.config_path.as_ref().map(PathBuf::from),
+            real_time_alerts: Arc::new(AtomicBool::new(true)),
+            rate_limiter: Arc::new(RateLimiter::new(config.limits.clone())),
+            federation: Arc::new(Federation::new(config.federation.clone())),
         };
 
         Self { config, state }
     }
 
+    /// Manage the monitored programs of a running subscriber through `/api/programs`
+    pub fn with_subscriber(mut self, subscriber: SubscriptionHandle) -> Self {
+        self.state.subscriber = Some(subscriber);
+        self
+    }
+
+    /// Test the notification channels of a running notifier through `/api/notifications/test`
+    pub fn with_notifier(mut self, notifier: Arc<NotificationManager>) -> Self {
+        self.state.notifier = Some(notifier);
+        self
+    }
+
     /// Start the dashboard server
     pub async fn start(self) -> Result<()> {
         let app = self.create_router();
@@ -139,12 +274,34 @@ impl DashboardServer {
             .parse()
             .map_err(|e| anyhow::anyhow!("Invalid address: {}", e))?;
 
+        let tls = match (&self.config.tls_cert, &self.config.tls_key) {
+            (Some(cert), Some(key)) => {
+                Some(RustlsConfig::from_pem_file(cert, key).await.map_err(|e| {
+                    anyhow::anyhow!("Failed to load TLS certificate {}: {}", cert, e)
+                })?)
+            }
+            (None, None) => None,
+            _ => anyhow::bail!("Both tls_cert and tls_key are needed to serve HTTPS"),
+        };
+
         let listener = TcpListener::bind(&addr).await?;
 
         info!(
-            "Dashboard server starting on http://{}:{}",
-            self.config.host, self.config.port
+            "Dashboard server starting on {}://{}:{}{}",
+            if tls.is_some() { "https" } else { "http" },
+            self.config.host,
+            self.config.port,
+            self.state.base_path
         );
+        if self.config.auth.mode == AuthMode::None && !addr.ip().is_loopback() {
+            warn!(
+                "Dashboard authentication is disabled; anyone reaching {} can use it",
+                addr
+            );
+        }
+
+        // Apply the settings saved through /api/config by a previous run
+        config_apply::load_startup_settings(&self.state).await;
 
         // Start WebSocket heartbeat task
         let ws_connections = self.state.ws_connections.clone();
@@ -155,11 +312,32 @@ impl DashboardServer {
         // Start alert broadcasting task
         let alert_manager = self.state.alert_manager.clone();
         let ws_connections = self.state.ws_connections.clone();
+        let real_time_alerts = self.state.real_time_alerts.clone();
         tokio::spawn(async move {
-            alert_broadcast_task(alert_manager, ws_connections).await;
+            alert_broadcast_task(alert_manager, ws_connections, real_time_alerts).await;
         });
 
-        axum::serve(listener, app).await?;
+        // Start event streaming task
+        let engine = self.state.engine.clone();
+        let ws_connections = self.state.ws_connections.clone();
+        tokio::spawn(async move {
+            event_broadcast_task(engine, ws_connections).await;
+        });
+
+        match tls {
+            Some(tls) => {
+                axum_server::from_tcp_rustls(listener.into_std()?, tls)
+                    .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                    .await?
+            }
+            None => {
+                axum::serve(
+                    listener,
+                    app.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .await?
+            }
+        }
 
         Ok(())
     }
@@ -170,23 +348,105 @@ impl DashboardServer {
             // Main pages
             .route("/", get(handlers::index))
             .route("/alerts", get(handlers::alerts_page))
-            .route("/metrics", get(handlers::metrics_page))
+            .route("/metrics", get(handlers::metrics))
             .route("/rules", get(handlers::rules_page))
+            .route("/events", get(events::events_page))
+            .route("/silences", get(silences::silences_page))
+            .route("/fleet", get(federation::fleet_page))
+            .route("/diagnostics", get(diagnostics::diagnostics_page))
             .route("/settings", get(handlers::settings_page))
+            // Authentication
+            .route("/login", get(auth::login_page).post(auth::login))
+            .route("/logout", post(auth::logout))
+            .route("/api/session", get(auth::api_session))
             // API endpoints
+            .route("/api/openapi.json", get(openapi::api_openapi))
+            .route("/api/docs", get(openapi::api_docs_page))
             .route("/api/status", get(handlers::api_status))
+            .route("/api/fleet", get(federation::api_fleet))
+            .route("/api/diagnostics", get(diagnostics::api_diagnostics))
             .route("/api/alerts", get(handlers::api_alerts))
+            .route("/api/alerts/export", get(export::api_export_alerts))
+            .route("/api/alerts/ack", post(handlers::api_acknowledge_alerts))
+            .route("/api/alerts/resolve", post(handlers::api_resolve_alerts))
             .route("/api/alerts/:id", get(handlers::api_alert_detail))
+            .route("/api/alerts/:id/ack", post(handlers::api_acknowledge_alert))
+            .route("/api/alerts/:id/resolve", post(handlers::api_resolve_alert))
+            .route(
+                "/api/alerts/:id/deliveries",
+                get(handlers::api_alert_deliveries),
+            )
+            .route("/api/deliveries", get(handlers::api_deliveries))
             .route("/api/metrics", get(handlers::api_metrics))
-            .route("/api/rules", get(handlers::api_rules))
-            .route("/api/rules/:name", get(handlers::api_rule_detail))
-            .route("/api/programs", get(handlers::api_programs))
+            .route("/api/events", get(events::api_events))
+            .route("/api/grafana", get(grafana::api_grafana))
+            .route("/api/grafana/search", post(grafana::api_grafana_search))
+            .route("/api/grafana/query", post(grafana::api_grafana_query))
+            .route(
+                "/api/rules",
+                get(handlers::api_rules).post(handlers::api_create_rule),
+            )
+            .route(
+                "/api/rules/:name",
+                get(handlers::api_rule_detail)
+                    .put(handlers::api_update_rule)
+                    .delete(handlers::api_delete_rule),
+            )
+            .route("/api/rules/:name/enable", post(handlers::api_enable_rule))
+            .route("/api/rules/:name/disable", post(handlers::api_disable_rule))
+            .route("/api/rules/:name/test", post(handlers::api_test_rule))
+            .route(
+                "/api/rules/:name/false_positive",
+                post(handlers::api_rule_false_positive),
+            )
+            .route(
+                "/api/silences",
+                get(silences::api_silences).post(silences::api_create_silence),
+            )
+            .route("/api/silences/:id", delete(silences::api_expire_silence))
+            .route("/api/dead_letters", get(handlers::api_dead_letters))
+            .route(
+                "/api/dead_letters/:id",
+                delete(handlers::api_discard_dead_letter),
+            )
+            .route(
+                "/api/dead_letters/:id/retry",
+                post(handlers::api_retry_dead_letter),
+            )
+            .route("/api/slack/actions", post(slack::api_slack_actions))
+            .route(
+                "/api/programs",
+                get(handlers::api_programs).post(handlers::api_add_program),
+            )
+            .route("/api/programs/:id", delete(handlers::api_remove_program))
+            .route(
+                "/api/notifications/test",
+                post(handlers::api_test_notifications),
+            )
             .route("/api/config", get(handlers::api_config))
             .route("/api/config", post(handlers::api_update_config))
+            .route("/api/audit", get(handlers::api_audit))
             // WebSocket endpoint
             .route("/ws", get(handlers::websocket_handler))
+            // Server-Sent Events fallback of the WebSocket stream
+            .route("/sse", get(sse::sse_handler))
             // Health check
             .route("/health", get(handlers::health_check))
+            // ETags of API responses, checked once the request is authorized
+            .layer(middleware::from_fn(caching::conditional_get))
+            // Authentication of everything above except the public routes
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                auth::require_auth,
+            ))
+            // Limits apply before authentication, so floods of
+            // unauthenticated requests are turned away too
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                limits::rate_limit,
+            ))
+            .layer(DefaultBodyLimit::max(self.config.limits.max_body_bytes))
+            .layer(TimeoutLayer::new(self.config.limits.request_timeout()))
             // State
             .with_state(self.state.clone());
 
@@ -197,20 +457,63 @@ impl DashboardServer {
 
         // Add static file serving
         if let Some(static_dir) = &self.config.static_dir {
+            // Files in the directory override the embedded assets, which
+            // serve everything it lacks. ServeDir answers If-Modified-Since itself
+            let cache_control = SetResponseHeaderLayer::if_not_present(
+                header::CACHE_CONTROL,
+                HeaderValue::from_static(caching::STATIC_CACHE_CONTROL),
+            );
             app = app.nest_service(
                 "/static",
-                ServeDir::new(static_dir)
-                    .fallback(ServeFile::new(format!("{}/index.html", static_dir))),
+                cache_control.layer(
+                    ServeDir::new(static_dir)
+                        .precompressed_gzip()
+                        .precompressed_br()
+                        .fallback(get(handlers::serve_static)),
+                ),
             );
         } else {
             // Serve embedded static files
-            app = app.route("/static/*file", get(handlers::serve_static));
+            app = app.nest_service("/static", get(handlers::serve_static));
+        }
+
+        // Compress pages, assets and API responses for clients accepting
+        // gzip or brotli; event streams are left uncompressed
+        app = app.layer(CompressionLayer::new());
+
+        // Serve everything under the URL prefix of the reverse proxy
+        let base_path = self.state.base_path.to_string();
+        if !base_path.is_empty() {
+            let root = base_path.clone();
+            app = Router::new().nest(&base_path, app).route(
+                &format!("{}/", base_path),
+                get(|| async move { Redirect::permanent(&root) }),
+            );
         }
 
         app
     }
 }
 
+/// Normalize a URL prefix to `/prefix` without a trailing slash, or empty.
+pub fn normalize_base_path(base_path: &str) -> String {
+    let trimmed = base_path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+/// URL of a dashboard path under the prefix; the root is the prefix itself.
+pub(crate) fn app_path(base_path: &str, path: &str) -> String {
+    if path == "/" && !base_path.is_empty() {
+        base_path.to_string()
+    } else {
+        format!("{}{}", base_path, path)
+    }
+}
+
 /// Query parameters for pagination
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -218,6 +521,8 @@ pub struct PaginationQuery {
     pub limit: Option<u32>,
     pub sort: Option<String>,
     pub filter: Option<String>,
+    /// Restrict results to items carrying this team/project tag
+    pub tag: Option<String>,
 }
 
 impl Default for PaginationQuery {
@@ -227,10 +532,123 @@ impl Default for PaginationQuery {
             limit: Some(20),
             sort: None,
             filter: None,
+            tag: None,
+        }
+    }
+}
+
+/// Query parameters searching alerts
+#[derive(Debug, Default, Deserialize)]
+pub struct AlertQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    /// Comma-separated severities, e.g. `high,critical`
+    pub severity: Option<String>,
+    /// Program ID
+    pub program: Option<String>,
+    /// Rule name
+    pub rule: Option<String>,
+    pub acknowledged: Option<bool>,
+    pub resolved: Option<bool>,
+    /// Start of the time range (RFC 3339, inclusive)
+    #[serde(alias = "from")]
+    pub since: Option<DateTime<Utc>>,
+    /// End of the time range (RFC 3339, exclusive)
+    #[serde(alias = "to")]
+    pub until: Option<DateTime<Utc>>,
+    /// Free-text search in messages, rules, program names and IDs
+    pub q: Option<String>,
+    /// Restrict results to alerts carrying this team/project tag
+    pub tag: Option<String>,
+    /// Sort field with optional direction, e.g. `severity:desc`; newest first by default
+    pub sort: Option<String>,
+    /// `next_cursor` of the previous page; replaces `page` and `sort`
+    pub cursor: Option<String>,
+}
+
+impl AlertQuery {
+    /// Alert manager filter of the query.
+    pub fn filter(&self) -> Result<AlertFilter, String> {
+        let severities = match &self.severity {
+            Some(severity) => Some(
+                severity
+                    .split(',')
+                    .map(|s| {
+                        AlertSeverity::parse(s.trim())
+                            .ok_or_else(|| format!("Invalid severity: {}", s))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
+        let program_ids = match &self.program {
+            Some(program) => Some(vec![program
+                .parse()
+                .map_err(|_| format!("Invalid program ID: {}", program))?]),
+            None => None,
+        };
+        let time_range = match (self.since, self.until) {
+            (None, None) => None,
+            (since, until) => Some(TimeRange {
+                start: since.unwrap_or(DateTime::<Utc>::MIN_UTC),
+                end: until.unwrap_or(DateTime::<Utc>::MAX_UTC),
+            }),
+        };
+
+        Ok(AlertFilter {
+            severities,
+            rule_names: self.rule.clone().map(|rule| vec![rule]),
+            program_ids,
+            acknowledged: self.acknowledged,
+            resolved: self.resolved,
+            time_range,
+            tags: self.tag.clone().map(|tag| vec![tag]),
+            text: self.q.clone().filter(|q| !q.trim().is_empty()),
+            ..Default::default()
+        })
+    }
+
+    /// Page number (from 1) and page size.
+    pub fn page_and_limit(&self) -> (u32, u32) {
+        (
+            self.page.unwrap_or(1).max(1),
+            self.limit.unwrap_or(20).clamp(1, MAX_PAGE_SIZE),
+        )
+    }
+
+    /// Alert manager order and page of the query.
+    pub fn search(&self) -> Result<AlertSearch, String> {
+        let (page, limit) = self.page_and_limit();
+        let sort = match &self.sort {
+            Some(sort) => {
+                AlertSort::parse(sort).ok_or_else(|| format!("Invalid sort: {}", sort))?
+            }
+            None => AlertSort::default(),
+        };
+
+        match &self.cursor {
+            Some(cursor) => Ok(AlertSearch {
+                sort,
+                after: Some(
+                    AlertCursor::decode(cursor)
+                        .ok_or_else(|| format!("Invalid cursor: {}", cursor))?,
+                ),
+                offset: 0,
+                limit: limit as usize,
+            }),
+            None => Ok(AlertSearch {
+                sort,
+                after: None,
+                offset: ((page - 1) * limit) as usize,
+                limit: limit as usize,
+            }),
         }
     }
 }
 
+/// Largest page of alerts returned at once
+const MAX_PAGE_SIZE: u32 = 500;
+
 /// Standard API response format
 #[derive(Debug, Serialize)]
 pub struct ApiResponse<T> {
@@ -246,6 +664,8 @@ pub struct PaginationInfo {
     pub limit: u32,
     pub total: u32,
     pub pages: u32,
+    /// Cursor of the next page, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
@@ -341,6 +761,67 @@ mod tests {
         assert!(config.static_dir.is_none());
     }
 
+    #[test]
+    fn test_base_path() {
+        assert_eq!(normalize_base_path(""), "");
+        assert_eq!(normalize_base_path("/"), "");
+        assert_eq!(normalize_base_path("watchtower/"), "/watchtower");
+        assert_eq!(normalize_base_path("/ops/watchtower"), "/ops/watchtower");
+
+        assert_eq!(app_path("", "/"), "/");
+        assert_eq!(app_path("/watchtower", "/"), "/watchtower");
+        assert_eq!(app_path("/watchtower", "/alerts"), "/watchtower/alerts");
+    }
+
+    #[test]
+    fn test_alert_query_filter() {
+        let query = AlertQuery {
+            severity: Some("high, Critical".to_string()),
+            program: Some("11111111111111111111111111111111".to_string()),
+            since: Some("2024-01-15T00:00:00Z".parse().unwrap()),
+            q: Some("oracle".to_string()),
+            limit: Some(10_000),
+            ..Default::default()
+        };
+        let filter = query.filter().unwrap();
+        assert_eq!(
+            filter.severities,
+            Some(vec![AlertSeverity::High, AlertSeverity::Critical])
+        );
+        assert_eq!(filter.program_ids.unwrap().len(), 1);
+        assert_eq!(filter.time_range.unwrap().end, DateTime::<Utc>::MAX_UTC);
+        assert_eq!(filter.text.as_deref(), Some("oracle"));
+        assert_eq!(query.page_and_limit(), (1, MAX_PAGE_SIZE));
+
+        let invalid = AlertQuery {
+            severity: Some("urgent".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.filter().is_err());
+
+        let sorted = AlertQuery {
+            page: Some(3),
+            limit: Some(10),
+            sort: Some("severity:asc".to_string()),
+            ..Default::default()
+        };
+        let search = sorted.search().unwrap();
+        assert_eq!(search.offset, 20);
+        assert!(!search.sort.descending);
+        assert!(AlertQuery {
+            sort: Some("message".to_string()),
+            ..Default::default()
+        }
+        .search()
+        .is_err());
+        assert!(AlertQuery {
+            cursor: Some("not a cursor".to_string()),
+            ..Default::default()
+        }
+        .search()
+        .is_err());
+    }
+
     #[test]
     fn test_api_response_success() {
         let response = ApiResponse::success("test data");
@@ -356,4 +837,71 @@ mod tests {
         assert!(response.data.is_none());
         assert_eq!(response.error, Some("test error".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_serve_embedded_static() {
+        let response = serve_static(
+            axum::http::Uri::from_static("/style.css"),
+            axum::http::HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/css");
+        assert!(response.headers().contains_key(header::ETAG));
+
+        let response = serve_static(
+            axum::http::Uri::from_static("/app.js"),
+            axum::http::HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+        assert!(response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .ends_with("/javascript"));
+
+        let missing = serve_static(
+            axum::http::Uri::from_static("/../Cargo.toml"),
+            axum::http::HeaderMap::new(),
+        )
+        .await;
+        assert_eq!(missing.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_serve_tls() {
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let tls = RustlsConfig::from_pem_file(
+            fixtures.join("localhost.crt"),
+            fixtures.join("localhost.key"),
+        )
+        .await
+        .unwrap();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = axum_server::Handle::new();
+        let app = Router::new().route("/", get(|| async { "ok" }));
+        let server = tokio::spawn(
+            axum_server::from_tcp_rustls(listener, tls)
+                .handle(handle.clone())
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>()),
+        );
+
+        let body = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap()
+            .get(format!("https://{}/", addr))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+
+        handle.shutdown();
+        server.await.unwrap().unwrap();
+    }
 }
@@ -0,0 +1,374 @@
+//! Request limits protecting an exposed dashboard: per-IP rate limiting,
+//! request body size caps and request timeouts.
+
+use crate::{ApiResponse, AppState};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Clients tracked before idle ones are forgotten
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Least time between sweeps for idle clients when the map is full
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Request limits of the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestLimits {
+    /// Requests per minute accepted from one client IP; 0 disables rate limiting
+    #[serde(default = "default_requests_per_minute")]
+    pub requests_per_minute: u32,
+
+    /// Requests a client may send at once before the rate applies
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+
+    /// Largest accepted request body in bytes
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    /// Seconds a request may take before it is answered with 408; WebSocket
+    /// and SSE streams are not limited once opened
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+
+    /// Take the client IP from the rightmost `X-Forwarded-For` entry, the one
+    /// appended by the proxy; only enable behind a single reverse proxy
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: default_requests_per_minute(),
+            burst: default_burst(),
+            max_body_bytes: default_max_body_bytes(),
+            request_timeout_seconds: default_request_timeout_seconds(),
+            trust_forwarded_for: false,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Check the limits are usable.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.requests_per_minute > 0 && self.burst == 0 {
+            anyhow::bail!("Dashboard rate limit burst cannot be 0");
+        }
+        if self.max_body_bytes == 0 {
+            anyhow::bail!("Dashboard max_body_bytes cannot be 0");
+        }
+        if self.request_timeout_seconds == 0 {
+            anyhow::bail!("Dashboard request timeout cannot be 0");
+        }
+        Ok(())
+    }
+
+    /// Timeout of a request.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_seconds)
+    }
+}
+
+/// Requests a client may still send, refilled at the configured rate.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn full(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    /// Tokens at `now`, counting the refill since the last request.
+    fn tokens_at(&self, now: Instant, per_second: f64) -> f64 {
+        self.tokens + now.duration_since(self.updated).as_secs_f64() * per_second
+    }
+}
+
+/// Buckets of the tracked clients.
+#[derive(Debug)]
+struct Buckets {
+    clients: HashMap<IpAddr, Bucket>,
+
+    /// Shared by clients arriving while the map is full of active ones
+    overflow: Option<Bucket>,
+
+    /// Last sweep for idle clients
+    swept: Option<Instant>,
+}
+
+/// Token bucket rate limiter keyed by client IP.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limits: RequestLimits,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: RequestLimits) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(Buckets {
+                clients: HashMap::new(),
+                overflow: None,
+                swept: None,
+            }),
+        }
+    }
+
+    /// Limits the limiter enforces.
+    pub fn limits(&self) -> &RequestLimits {
+        &self.limits
+    }
+
+    /// Take a request of `client` at `now`, or return how long it must wait.
+    pub fn check(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        if self.limits.requests_per_minute == 0 {
+            return Ok(());
+        }
+
+        let per_second = self.limits.requests_per_minute as f64 / 60.0;
+        let capacity = self.limits.burst as f64;
+        let mut guard = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let buckets = &mut *guard;
+
+        // Clients whose bucket refilled completely are indistinguishable from
+        // new ones. Sweeping scans every client, so it runs at most once per
+        // interval however many new clients arrive.
+        let full = buckets.clients.len() >= MAX_TRACKED_CLIENTS;
+        if full
+            && !buckets.clients.contains_key(&client)
+            && buckets
+                .swept
+                .map_or(true, |swept| now.duration_since(swept) >= SWEEP_INTERVAL)
+        {
+            buckets
+                .clients
+                .retain(|_, bucket| bucket.tokens_at(now, per_second) < capacity);
+            buckets.swept = Some(now);
+        }
+
+        // While still full, new clients share one bucket rather than growing the map
+        let bucket = if buckets.clients.len() < MAX_TRACKED_CLIENTS {
+            buckets
+                .clients
+                .entry(client)
+                .or_insert_with(|| Bucket::full(capacity, now))
+        } else {
+            match buckets.clients.get_mut(&client) {
+                Some(bucket) => bucket,
+                None => {
+                    if buckets.overflow.is_none() {
+                        warn!(
+                            "Rate limiting {} active clients; new clients share one limit",
+                            MAX_TRACKED_CLIENTS
+                        );
+                    }
+                    buckets
+                        .overflow
+                        .get_or_insert_with(|| Bucket::full(capacity, now))
+                }
+            }
+        };
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Client IP of a request, from `X-Forwarded-For` when trusted. Entries left
+/// of the last one are supplied by the client and cannot be trusted.
+fn client_ip(
+    headers: &HeaderMap,
+    peer: Option<SocketAddr>,
+    trust_forwarded_for: bool,
+) -> Option<IpAddr> {
+    let forwarded = trust_forwarded_for
+        .then(|| {
+            headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .next_back()?
+                .to_str()
+                .ok()
+        })
+        .flatten()
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|last| last.trim().parse().ok());
+    forwarded.or(peer.map(|peer| peer.ip()))
+}
+
+/// Middleware answering 429 to clients over their request rate.
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let limiter = &state.rate_limiter;
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let Some(client) = client_ip(
+        request.headers(),
+        peer,
+        limiter.limits().trust_forwarded_for,
+    ) else {
+        return next.run(request).await;
+    };
+
+    match limiter.check(client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            warn!(
+                "Rate limited {} {} from {}",
+                request.method(),
+                request.uri().path(),
+                client
+            );
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(
+                    header::RETRY_AFTER,
+                    retry_after.as_secs().max(1).to_string(),
+                )],
+                Json(ApiResponse::<()>::error("Too many requests".to_string())),
+            )
+                .into_response()
+        }
+    }
+}
+
+fn default_requests_per_minute() -> u32 {
+    600
+}
+
+fn default_burst() -> u32 {
+    60
+}
+
+fn default_max_body_bytes() -> usize {
+    // 1 MiB
+    1024 * 1024
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    30
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_bucket() {
+        let limiter = RateLimiter::new(RequestLimits {
+            requests_per_minute: 60,
+            burst: 2,
+            ..Default::default()
+        });
+        let start = Instant::now();
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let other: IpAddr = "203.0.113.8".parse().unwrap();
+
+        assert!(limiter.check(client, start).is_ok());
+        assert!(limiter.check(client, start).is_ok());
+        let retry_after = limiter.check(client, start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        // Other clients have their own bucket, and tokens refill over time
+        assert!(limiter.check(other, start).is_ok());
+        assert!(limiter
+            .check(client, start + Duration::from_secs(1))
+            .is_ok());
+
+        let unlimited = RateLimiter::new(RequestLimits {
+            requests_per_minute: 0,
+            ..Default::default()
+        });
+        for _ in 0..1000 {
+            assert!(unlimited.check(client, start).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_full_map() {
+        let limiter = RateLimiter::new(RequestLimits {
+            requests_per_minute: 60,
+            burst: 2,
+            ..Default::default()
+        });
+        let start = Instant::now();
+        for index in 0..MAX_TRACKED_CLIENTS as u32 {
+            let client = IpAddr::from(std::net::Ipv4Addr::from(0x0a00_0000 + index));
+            assert!(limiter.check(client, start).is_ok());
+        }
+        let tracked = || limiter.buckets.lock().unwrap().clients.len();
+        let swept = || limiter.buckets.lock().unwrap().swept;
+
+        // Every client is active, so new ones share a bucket instead of being added
+        let new_clients: Vec<IpAddr> = (1..=3)
+            .map(|i| format!("203.0.113.{}", i).parse().unwrap())
+            .collect();
+        assert!(limiter.check(new_clients[0], start).is_ok());
+        assert!(limiter.check(new_clients[1], start).is_ok());
+        assert!(limiter.check(new_clients[2], start).is_err());
+        assert_eq!(tracked(), MAX_TRACKED_CLIENTS);
+        assert_eq!(swept(), Some(start));
+
+        // No further sweep within the interval
+        let soon = start + Duration::from_millis(500);
+        assert!(limiter.check(new_clients[2], soon).is_err());
+        assert_eq!(swept(), Some(start));
+
+        // Once their buckets refilled, idle clients are forgotten
+        let later = start + Duration::from_secs(2);
+        assert!(limiter.check(new_clients[2], later).is_ok());
+        assert_eq!(swept(), Some(later));
+        assert_eq!(tracked(), 1);
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let peer: SocketAddr = "10.0.0.2:41000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        // A spoofed entry sent by the client, then the address the proxy saw
+        headers.insert(
+            "x-forwarded-for",
+            "203.0.113.7, 198.51.100.4".parse().unwrap(),
+        );
+
+        assert_eq!(client_ip(&headers, Some(peer), false), Some(peer.ip()));
+        assert_eq!(
+            client_ip(&headers, Some(peer), true),
+            Some("198.51.100.4".parse().unwrap())
+        );
+
+        headers.append("x-forwarded-for", "192.0.2.10".parse().unwrap());
+        assert_eq!(
+            client_ip(&headers, Some(peer), true),
+            Some("192.0.2.10".parse().unwrap())
+        );
+        assert_eq!(client_ip(&HeaderMap::new(), None, true), None);
+        assert!(RequestLimits::default().validate().is_ok());
+    }
+}
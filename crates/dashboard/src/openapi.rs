@@ -0,0 +1,789 @@
+//! OpenAPI description of the JSON API and its Swagger UI page.
+//!
+//! Operations are listed next to each other in [`OPERATIONS`] so a route added
+//! to the router is documented by adding one entry here.
+
+use crate::{templates::ApiDocsTemplate, AppState, DashboardError, DashboardResult};
+use askama::Template;
+use axum::{
+    extract::State,
+    response::{Html, Json},
+};
+use serde_json::{json, Map, Value};
+
+/// A documented API operation.
+struct Operation {
+    method: &'static str,
+    /// Path in OpenAPI form, e.g. `/api/alerts/{id}`
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    /// Query parameters and their descriptions
+    query: &'static [(&'static str, &'static str)],
+    /// Schema of the JSON request body in `components.schemas`
+    body: Option<&'static str>,
+}
+
+/// Filters shared by the alert search and export
+const ALERT_FILTERS: &[(&str, &str)] = &[
+    (
+        "severity",
+        "Comma-separated severities, e.g. `high,critical`",
+    ),
+    ("program", "Program ID"),
+    ("rule", "Rule name"),
+    (
+        "acknowledged",
+        "Only acknowledged (`true`) or unacknowledged alerts",
+    ),
+    ("resolved", "Only resolved (`true`) or active alerts"),
+    ("since", "Start of the time range (RFC 3339, inclusive)"),
+    ("until", "End of the time range (RFC 3339, exclusive)"),
+    (
+        "q",
+        "Free-text search in messages, rules, program names and IDs",
+    ),
+    ("tag", "Team/project tag"),
+];
+
+const OPERATIONS: &[Operation] = &[
+    Operation {
+        method: "get",
+        path: "/api/session",
+        tag: "Authentication",
+        summary: "Current user and authentication mode",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/status",
+        tag: "System",
+        summary: "Engine status and performance",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/fleet",
+        tag: "System",
+        summary: "Status and open alerts of this and the federated watchtower instances",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/diagnostics",
+        tag: "System",
+        summary: "Subscriber connection, queue depths, channel circuits and storage health",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/alerts",
+        tag: "Alerts",
+        summary: "Search alerts, newest first unless sorted otherwise",
+        query: &[
+            ("page", "Page number, from 1"),
+            ("limit", "Page size, at most 500"),
+            (
+                "sort",
+                "`timestamp`, `severity`, `confidence`, `rule` or `program`, optionally with `:asc` or `:desc`",
+            ),
+            (
+                "cursor",
+                "`pagination.next_cursor` of the previous page; stable while alerts change, replaces `page` and `sort`",
+            ),
+        ],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/alerts/export",
+        tag: "Alerts",
+        summary: "Export matching alerts as a CSV or JSON download",
+        query: &[("format", "`json` (default) or `csv`")],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/alerts/ack",
+        tag: "Alerts",
+        summary: "Acknowledge several alerts",
+        query: &[],
+        body: Some("BulkAlertRequest"),
+    },
+    Operation {
+        method: "post",
+        path: "/api/alerts/resolve",
+        tag: "Alerts",
+        summary: "Resolve several alerts",
+        query: &[],
+        body: Some("BulkAlertRequest"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/alerts/{id}",
+        tag: "Alerts",
+        summary: "Alert details, audit trail, triggering event and its raw transaction",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/alerts/{id}/ack",
+        tag: "Alerts",
+        summary: "Acknowledge an alert",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/alerts/{id}/resolve",
+        tag: "Alerts",
+        summary: "Resolve an alert, optionally recording a verdict",
+        query: &[],
+        body: Some("ResolveAlertRequest"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/alerts/{id}/deliveries",
+        tag: "Alerts",
+        summary: "Notification delivery attempts of an alert",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/deliveries",
+        tag: "Notifications",
+        summary: "Notification delivery attempts",
+        query: &[
+            ("alert_id", "Alert ID"),
+            ("channel", "Notification channel"),
+            ("outcome", "Delivery outcome"),
+            ("since", "Only attempts at or after this time (RFC 3339)"),
+            ("limit", "Maximum number of records"),
+        ],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/metrics",
+        tag: "System",
+        summary: "Metrics snapshot and Prometheus export",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/events",
+        tag: "Events",
+        summary: "Recent program events, newest first",
+        query: &[
+            ("program", "Program name or ID"),
+            (
+                "event_type",
+                "Event type, e.g. `transaction` or `log_entry`",
+            ),
+            ("limit", "Maximum number of events, at most 1000"),
+        ],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/grafana",
+        tag: "Grafana",
+        summary: "Grafana SimpleJSON datasource connection test",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/grafana/search",
+        tag: "Grafana",
+        summary: "Grafana target names: `alerts`, `alerts:<severity>` and `window:<metric>`",
+        query: &[],
+        body: Some("GrafanaSearchRequest"),
+    },
+    Operation {
+        method: "post",
+        path: "/api/grafana/query",
+        tag: "Grafana",
+        summary: "Time series of Grafana targets as `[value, unix ms]` datapoints",
+        query: &[],
+        body: Some("GrafanaQueryRequest"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/rules",
+        tag: "Rules",
+        summary: "Registered rules",
+        query: &[
+            ("page", "Page number, from 1"),
+            ("limit", "Page size"),
+            ("tag", "Team/project tag"),
+        ],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/rules",
+        tag: "Rules",
+        summary: "Create a rate-of-change rule",
+        query: &[],
+        body: Some("RateOfChangeConfig"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/rules/{name}",
+        tag: "Rules",
+        summary: "Rule details, statistics and definition",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "put",
+        path: "/api/rules/{name}",
+        tag: "Rules",
+        summary: "Replace the definition of a rate-of-change rule",
+        query: &[],
+        body: Some("RateOfChangeConfig"),
+    },
+    Operation {
+        method: "delete",
+        path: "/api/rules/{name}",
+        tag: "Rules",
+        summary: "Delete a rate-of-change rule",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/rules/{name}/enable",
+        tag: "Rules",
+        summary: "Enable a rule",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/rules/{name}/disable",
+        tag: "Rules",
+        summary: "Disable a rule",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/rules/{name}/test",
+        tag: "Rules",
+        summary: "Evaluate a rule against a sample event without alerting",
+        query: &[],
+        body: Some("ProgramEvent"),
+    },
+    Operation {
+        method: "post",
+        path: "/api/rules/{name}/false_positive",
+        tag: "Rules",
+        summary: "Report a false positive of a rule",
+        query: &[],
+        body: Some("FalsePositiveRequest"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/silences",
+        tag: "Silences",
+        summary: "Active silences",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/silences",
+        tag: "Silences",
+        summary: "Silence matching alerts for a while",
+        query: &[],
+        body: Some("CreateSilenceRequest"),
+    },
+    Operation {
+        method: "delete",
+        path: "/api/silences/{id}",
+        tag: "Silences",
+        summary: "Expire a silence now",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/dead_letters",
+        tag: "Dead letters",
+        summary: "Failed rule evaluations and alert dispatches",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "delete",
+        path: "/api/dead_letters/{id}",
+        tag: "Dead letters",
+        summary: "Discard a dead letter",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/dead_letters/{id}/retry",
+        tag: "Dead letters",
+        summary: "Retry a dead letter",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "get",
+        path: "/api/programs",
+        tag: "Programs",
+        summary: "Monitored programs and their activity",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/programs",
+        tag: "Programs",
+        summary: "Start monitoring a program",
+        query: &[],
+        body: Some("ProgramConfig"),
+    },
+    Operation {
+        method: "delete",
+        path: "/api/programs/{id}",
+        tag: "Programs",
+        summary: "Stop monitoring a program",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/notifications/test",
+        tag: "Notifications",
+        summary: "Send a test notification through one or all channels",
+        query: &[],
+        body: Some("NotificationTestRequest"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/config",
+        tag: "System",
+        summary: "Dashboard configuration",
+        query: &[],
+        body: None,
+    },
+    Operation {
+        method: "post",
+        path: "/api/config",
+        tag: "System",
+        summary: "Validate, apply and save dashboard settings; nothing is applied if a step fails",
+        query: &[],
+        body: Some("ConfigUpdateRequest"),
+    },
+    Operation {
+        method: "get",
+        path: "/api/audit",
+        tag: "System",
+        summary: "Configuration changes with who made them and before/after values, newest first",
+        query: &[
+            ("actor", "Only changes by this user, e.g. `dashboard:alice`"),
+            ("field", "Only changes to this field or fields below it"),
+            ("since", "Only changes at or after this time (RFC 3339)"),
+            ("limit", "Maximum number of entries"),
+        ],
+        body: None,
+    },
+];
+
+/// OpenAPI document of the JSON API served under `base_path`.
+pub fn openapi_document(base_path: &str) -> Value {
+    let mut paths = Map::new();
+    for operation in OPERATIONS {
+        let item = paths
+            .entry(operation.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("path items are objects");
+        item.insert(operation.method.to_string(), operation_object(operation));
+    }
+
+    for path in ["/api/alerts", "/api/alerts/export"] {
+        if let Some(parameters) = paths[path]["get"]["parameters"].as_array_mut() {
+            parameters.extend(
+                ALERT_FILTERS
+                    .iter()
+                    .map(|(name, description)| query_parameter(name, description)),
+            );
+        }
+    }
+
+    // The export is a file download instead of the response envelope
+    if let Some(export) = paths.get_mut("/api/alerts/export") {
+        export["get"]["responses"]["200"] = json!({
+            "description": "Alerts, oldest first",
+            "content": {
+                "text/csv": { "schema": { "type": "string" } },
+                "application/json": {
+                    "schema": { "type": "array", "items": { "type": "object" } }
+                }
+            }
+        });
+    }
+
+    // Grafana expects bare arrays instead of the response envelope
+    for path in ["/api/grafana/search", "/api/grafana/query"] {
+        if let Some(grafana) = paths.get_mut(path) {
+            grafana["post"]["responses"]["200"] = json!({
+                "description": "Target names or time series",
+                "content": {
+                    "application/json": { "schema": { "type": "array", "items": {} } }
+                }
+            });
+        }
+    }
+
+    let server = if base_path.is_empty() { "/" } else { base_path };
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Solana Watchtower API",
+            "description": "JSON API of the Solana Watchtower dashboard. Responses are wrapped in \
+                an envelope with `success`, `data`, `error` and, for paged lists, `pagination`. \
+                Live updates are available over the `/ws` WebSocket and the `/sse` \
+                Server-Sent Events stream.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [{ "url": server }],
+        "security": [{ "bearerAuth": [] }, { "sessionCookie": [] }],
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer" },
+                "sessionCookie": {
+                    "type": "apiKey",
+                    "in": "cookie",
+                    "name": crate::auth::SESSION_COOKIE,
+                }
+            },
+            "schemas": schemas(),
+        }
+    })
+}
+
+fn operation_object(operation: &Operation) -> Value {
+    let mut parameters: Vec<Value> = path_parameters(operation.path)
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })
+        })
+        .collect();
+    parameters.extend(
+        operation
+            .query
+            .iter()
+            .map(|(name, description)| query_parameter(name, description)),
+    );
+
+    let mut object = json!({
+        "tags": [operation.tag],
+        "summary": operation.summary,
+        "parameters": parameters,
+        "responses": {
+            "200": {
+                "description": "Successful response",
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/ApiResponse" } }
+                }
+            },
+            "default": {
+                "description": "Error response with `success: false` and an `error` message",
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/ApiResponse" } }
+                }
+            }
+        }
+    });
+    if let Some(body) = operation.body {
+        object["requestBody"] = json!({
+            "required": true,
+            "content": {
+                "application/json": {
+                    "schema": { "$ref": format!("#/components/schemas/{}", body) }
+                }
+            }
+        });
+    }
+    object
+}
+
+fn query_parameter(name: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": { "type": "string" },
+    })
+}
+
+/// Names of the `{parameters}` of a path.
+fn path_parameters(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+}
+
+/// Schemas of the response envelope and request bodies.
+fn schemas() -> Value {
+    let severity = json!({
+        "type": "string",
+        "enum": ["Info", "Low", "Medium", "High", "Critical"],
+    });
+    let feedback = json!({
+        "type": "string",
+        "enum": ["true_positive", "false_positive"],
+    });
+
+    json!({
+        "ApiResponse": {
+            "type": "object",
+            "properties": {
+                "success": { "type": "boolean" },
+                "data": { "description": "Response payload, absent on errors" },
+                "error": { "type": "string", "nullable": true },
+                "pagination": {
+                    "type": "object",
+                    "nullable": true,
+                    "properties": {
+                        "page": { "type": "integer" },
+                        "limit": { "type": "integer" },
+                        "total": { "type": "integer" },
+                        "pages": { "type": "integer" },
+                    }
+                }
+            },
+            "required": ["success"],
+        },
+        "BulkAlertRequest": {
+            "type": "object",
+            "properties": {
+                "ids": { "type": "array", "items": { "type": "string" } },
+                "feedback": feedback,
+            },
+            "required": ["ids"],
+        },
+        "ResolveAlertRequest": {
+            "type": "object",
+            "properties": { "feedback": feedback },
+        },
+        "FalsePositiveRequest": {
+            "type": "object",
+            "properties": { "note": { "type": "string" } },
+        },
+        "NotificationTestRequest": {
+            "type": "object",
+            "properties": {
+                "channel": {
+                    "type": "string",
+                    "description": "Channel to test instead of all of them",
+                }
+            },
+        },
+        "RateOfChangeConfig": {
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "metric": {
+                    "type": "string",
+                    "description": "Metric key; `{program}` is replaced with the event's program name",
+                },
+                "threshold_pct": { "type": "number" },
+                "window_seconds": { "type": "integer" },
+                "direction": { "type": "string", "enum": ["increase", "decrease", "either"] },
+                "severity": severity,
+                "priority": { "type": "integer" },
+            },
+            "required": ["name", "metric", "threshold_pct", "window_seconds"],
+        },
+        "GrafanaSearchRequest": {
+            "type": "object",
+            "properties": {
+                "target": { "type": "string", "description": "Text the target names contain" },
+            },
+        },
+        "GrafanaQueryRequest": {
+            "type": "object",
+            "properties": {
+                "range": {
+                    "type": "object",
+                    "properties": {
+                        "from": { "type": "string", "format": "date-time" },
+                        "to": { "type": "string", "format": "date-time" },
+                    },
+                    "required": ["from", "to"],
+                },
+                "intervalMs": { "type": "integer", "description": "Width of alert count buckets" },
+                "maxDataPoints": { "type": "integer" },
+                "targets": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string" },
+                            "refId": { "type": "string" },
+                            "hide": { "type": "boolean" },
+                        },
+                        "required": ["target"],
+                    }
+                },
+            },
+            "required": ["range", "targets"],
+        },
+        "CreateSilenceRequest": {
+            "type": "object",
+            "description": "Alerts matching every field set are suppressed; at least one of program, rule and severity is needed",
+            "properties": {
+                "program": { "type": "string", "description": "Program name" },
+                "rule": { "type": "string" },
+                "severity": severity,
+                "duration_seconds": { "type": "integer" },
+                "comment": { "type": "string" },
+            },
+            "required": ["duration_seconds"],
+        },
+        "ProgramConfig": {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "description": "Program public key" },
+                "name": { "type": "string" },
+                "monitor_accounts": { "type": "boolean", "default": true },
+                "monitor_transactions": { "type": "boolean", "default": true },
+                "monitor_logs": { "type": "boolean", "default": true },
+                "instruction_filters": { "type": "array", "items": { "type": "string" } },
+                "tags": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["id", "name"],
+        },
+        "ProgramEvent": {
+            "type": "object",
+            "description": "Program event as produced by the subscriber; public keys and signatures are byte arrays",
+            "properties": {
+                "id": { "type": "string" },
+                "program_id": { "type": "array", "items": { "type": "integer" } },
+                "program_name": { "type": "string" },
+                "event_type": {
+                    "type": "object",
+                    "properties": { "type": { "type": "string", "example": "Transaction" } },
+                },
+                "timestamp": { "type": "string", "format": "date-time" },
+                "slot": { "type": "integer" },
+                "block_time": { "type": "integer", "nullable": true },
+                "signature": { "nullable": true },
+                "data": {
+                    "type": "object",
+                    "properties": { "data_type": { "type": "string", "example": "LogEntry" } },
+                },
+                "metadata": { "type": "object" },
+            },
+            "required": ["id", "program_id", "program_name", "event_type", "timestamp", "slot", "data", "metadata"],
+        },
+        "ConfigUpdateRequest": {
+            "type": "object",
+            "properties": {
+                "notification_channels": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "enabled": { "type": "boolean" },
+                            "status": { "type": "string" },
+                        }
+                    }
+                },
+                "monitoring_settings": {
+                    "type": "object",
+                    "properties": {
+                        "max_events_per_minute": { "type": "integer", "minimum": 1, "maximum": 100000 },
+                        "alert_retention_days": { "type": "integer", "minimum": 1, "maximum": 365 },
+                        "enable_real_time_alerts": { "type": "boolean" },
+                    }
+                },
+                "subscriber": {
+                    "type": "object",
+                    "description": "Only the monitored programs can be changed while running; other subscriber settings are rejected",
+                    "properties": {
+                        "programs": {
+                            "type": "array",
+                            "description": "Programs to monitor, replacing the current ones",
+                            "items": { "$ref": "#/components/schemas/ProgramConfig" },
+                        }
+                    }
+                }
+            },
+            "additionalProperties": false,
+        },
+    })
+}
+
+/// API: OpenAPI document of the JSON API
+pub async fn api_openapi(State(state): State<AppState>) -> Json<Value> {
+    Json(openapi_document(&state.base_path))
+}
+
+/// API documentation page
+pub async fn api_docs_page(State(state): State<AppState>) -> DashboardResult<Html<String>> {
+    let template = ApiDocsTemplate {
+        title: "API Documentation".to_string(),
+        base_path: state.base_path.to_string(),
+    };
+
+    let html = template.render().map_err(DashboardError::Template)?;
+    Ok(Html(html))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_document() {
+        let document = openapi_document("/watchtower");
+        assert_eq!(document["servers"][0]["url"], "/watchtower");
+
+        let alert = &document["paths"]["/api/alerts/{id}/resolve"]["post"];
+        assert_eq!(alert["parameters"][0]["name"], "id");
+        assert_eq!(alert["parameters"][0]["in"], "path");
+
+        let rules = &document["paths"]["/api/rules"];
+        assert!(rules["get"].is_object() && rules["post"].is_object());
+        assert!(document["paths"]["/api/alerts/export"]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|parameter| parameter["name"] == "severity"));
+
+        // Every request body refers to a defined schema
+        for operation in OPERATIONS {
+            assert!(operation.path.starts_with("/api/"));
+            if let Some(body) = operation.body {
+                assert!(
+                    document["components"]["schemas"][body].is_object(),
+                    "{} has no schema",
+                    body
+                );
+            }
+        }
+    }
+}
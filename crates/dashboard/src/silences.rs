@@ -0,0 +1,126 @@
+//! Silence management: suppressing alerts of a program, rule or severity for a while.
+
+use crate::{
+    auth::CurrentUser, handlers::operator, templates::SilencesTemplate, ApiResponse, AppState,
+    DashboardError, DashboardResult,
+};
+use askama::Template;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::{Html, Json},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::info;
+use watchtower_engine::{Silence, SilenceScope};
+
+/// Silence creation request.
+#[derive(Debug, Deserialize)]
+pub struct CreateSilenceRequest {
+    #[serde(flatten)]
+    pub scope: SilenceScope,
+    pub duration_seconds: u64,
+    pub comment: Option<String>,
+}
+
+/// Silence as shown on the silences page.
+#[derive(Debug, Serialize)]
+pub struct SilenceInfo {
+    pub id: String,
+    pub program: String,
+    pub rule: String,
+    pub severity: String,
+    pub ends_at: String,
+    pub comment: String,
+    pub created_by: String,
+    pub suppressed: u64,
+}
+
+impl From<Silence> for SilenceInfo {
+    fn from(silence: Silence) -> Self {
+        let any = || "any".to_string();
+        Self {
+            id: silence.id,
+            program: silence.scope.program.unwrap_or_else(any),
+            rule: silence.scope.rule.unwrap_or_else(any),
+            severity: silence
+                .scope
+                .severity
+                .map_or_else(any, |severity| severity.as_str().to_string()),
+            ends_at: silence.ends_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            comment: silence.comment.unwrap_or_default(),
+            created_by: silence.created_by.unwrap_or_default(),
+            suppressed: silence.suppressed,
+        }
+    }
+}
+
+/// Silences page
+pub async fn silences_page(State(state): State<AppState>) -> DashboardResult<Html<String>> {
+    let template = SilencesTemplate {
+        title: "Silences".to_string(),
+        base_path: state.base_path.to_string(),
+        silences: state
+            .engine
+            .silences()
+            .list()
+            .into_iter()
+            .map(SilenceInfo::from)
+            .collect(),
+    };
+
+    let html = template.render().map_err(DashboardError::Template)?;
+    Ok(Html(html))
+}
+
+/// API: List active silences
+pub async fn api_silences(State(state): State<AppState>) -> Json<ApiResponse<Vec<Silence>>> {
+    Json(ApiResponse::success(state.engine.silences().list()))
+}
+
+/// API: Silence matching alerts for a while
+pub async fn api_create_silence(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Json(request): Json<CreateSilenceRequest>,
+) -> (StatusCode, Json<ApiResponse<Silence>>) {
+    // Empty form fields mean "any"
+    let scope = SilenceScope {
+        program: request.scope.program.filter(|p| !p.trim().is_empty()),
+        rule: request.scope.rule.filter(|r| !r.trim().is_empty()),
+        severity: request.scope.severity,
+    };
+
+    match state.engine.silences().create(
+        scope,
+        Duration::from_secs(request.duration_seconds),
+        request.comment,
+        operator(user),
+    ) {
+        Ok(silence) => (StatusCode::CREATED, Json(ApiResponse::success(silence))),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e.to_string())),
+        ),
+    }
+}
+
+/// API: Expire a silence now
+pub async fn api_expire_silence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    if state.engine.silences().expire(&id) {
+        info!("Silence {} expired from the dashboard", id);
+        (
+            StatusCode::OK,
+            Json(ApiResponse::success("Silence expired".to_string())),
+        )
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Silence not found")),
+        )
+    }
+}
@@ -0,0 +1,369 @@
+//! Slack interactivity callbacks for the Acknowledge/Resolve buttons.
+
+use crate::{
+    handlers::{acknowledge, resolve},
+    AppState,
+};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::Sha256;
+use tracing::{debug, info, warn};
+
+/// Maximum age of a signed Slack request before it is rejected as a replay.
+const SLACK_REQUEST_MAX_AGE_SECONDS: i64 = 300;
+
+/// Interaction payload sent by Slack when a button is clicked.
+#[derive(Debug, Deserialize)]
+pub struct SlackInteraction {
+    /// User who clicked
+    pub user: SlackUser,
+
+    /// Clicked buttons
+    #[serde(default)]
+    pub actions: Vec<SlackAction>,
+
+    /// URL for replying to the message
+    pub response_url: Option<String>,
+}
+
+/// Slack user who triggered an interaction.
+#[derive(Debug, Deserialize)]
+pub struct SlackUser {
+    pub id: String,
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+/// A clicked button.
+#[derive(Debug, Deserialize)]
+pub struct SlackAction {
+    pub action_id: String,
+    /// Alert ID carried by the button
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// API: Slack interactivity callback acknowledging or resolving alerts
+pub async fn api_slack_actions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let Some(secret) = &state.slack_signing_secret else {
+        return (
+            StatusCode::NOT_FOUND,
+            "Slack interactivity is not configured",
+        )
+            .into_response();
+    };
+
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let (Some(timestamp), Some(signature)) = (
+        header("x-slack-request-timestamp"),
+        header("x-slack-signature"),
+    ) else {
+        return (StatusCode::UNAUTHORIZED, "Missing Slack signature").into_response();
+    };
+
+    if !verify_slack_signature(
+        secret,
+        timestamp,
+        &body,
+        signature,
+        chrono::Utc::now().timestamp(),
+    ) {
+        warn!("Rejected Slack callback with an invalid signature");
+        return (StatusCode::UNAUTHORIZED, "Invalid Slack signature").into_response();
+    }
+
+    let interaction = url::form_urlencoded::parse(body.as_bytes())
+        .find(|(key, _)| key == "payload")
+        .and_then(|(_, payload)| serde_json::from_str::<SlackInteraction>(&payload).ok());
+    let Some(interaction) = interaction else {
+        return (StatusCode::BAD_REQUEST, "Invalid Slack payload").into_response();
+    };
+
+    let operator = format!(
+        "slack:{}",
+        interaction
+            .user
+            .username
+            .as_deref()
+            .unwrap_or(&interaction.user.id)
+    );
+
+    for action in &interaction.actions {
+        let Some(alert_id) = &action.value else {
+            continue;
+        };
+
+        // Changed as from the dashboard, so WebSocket clients hear of
+        // successful acknowledgements and resolutions alike
+        let (result, verb) = match action.action_id.as_str() {
+            "acknowledge" => (
+                acknowledge(&state, alert_id, Some(operator.clone())).await,
+                "acknowledged",
+            ),
+            "resolve" => (
+                resolve(&state, alert_id, Some(operator.clone()), None).await,
+                "resolved",
+            ),
+            other => {
+                debug!("Ignoring unknown Slack action {}", other);
+                continue;
+            }
+        };
+
+        let reply = match result {
+            Ok(()) => {
+                info!("Alert {} {} by {}", alert_id, verb, operator);
+                format!(
+                    "Alert `{}` {} by <@{}>",
+                    alert_id, verb, interaction.user.id
+                )
+            }
+            Err(e) => format!("Could not update alert `{}`: {}", alert_id, e),
+        };
+
+        if let Some(response_url) = interaction.response_url.clone() {
+            // Slack expects the callback to return within three seconds
+            tokio::spawn(async move {
+                let message = json!({
+                    "response_type": "in_channel",
+                    "replace_original": false,
+                    "text": reply,
+                });
+                if let Err(e) = reqwest::Client::new()
+                    .post(&response_url)
+                    .json(&message)
+                    .send()
+                    .await
+                {
+                    warn!("Failed to reply to Slack action: {}", e);
+                }
+            });
+        }
+    }
+
+    StatusCode::OK.into_response()
+}
+
+/// Verify a Slack request signature (`v0=` HMAC-SHA256 of `v0:{timestamp}:{body}`),
+/// rejecting requests older than five minutes.
+pub fn verify_slack_signature(
+    secret: &str,
+    timestamp: &str,
+    body: &str,
+    signature: &str,
+    now: i64,
+) -> bool {
+    let Ok(sent_at) = timestamp.parse::<i64>() else {
+        return false;
+    };
+    if (now - sent_at).abs() > SLACK_REQUEST_MAX_AGE_SECONDS {
+        return false;
+    }
+
+    let Some(expected) = signature.strip_prefix("v0=").and_then(decode_hex) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    mac.verify_slice(&expected).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::{
+        alert_broadcast_task, Subscription, WebSocketConnection, WebSocketMessage,
+    };
+    use crate::{DashboardConfig, DashboardServer};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use watchtower_engine::{
+        Alert, AlertManager, AlertSeverity, EngineConfig, MetricsCollector, MonitoringEngine,
+    };
+
+    const SECRET: &str = "slack-signing-secret";
+
+    /// Dashboard state accepting Slack callbacks, with one open alert.
+    async fn slack_state() -> AppState {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = Arc::new(MonitoringEngine::new(
+            metrics.clone(),
+            alert_manager.clone(),
+            EngineConfig::default(),
+        ));
+        let config = DashboardConfig {
+            slack_signing_secret: Some(SECRET.to_string()),
+            ..DashboardConfig::default()
+        };
+        let state = DashboardServer::new(config, engine, alert_manager, metrics).state;
+
+        state
+            .alert_manager
+            .send_alert(Alert {
+                id: "alert-1".to_string(),
+                rule_name: "large_transaction".to_string(),
+                message: "Large transaction detected".to_string(),
+                severity: AlertSeverity::High,
+                program_id: Default::default(),
+                program_name: "Orca".to_string(),
+                event_id: None,
+                metadata: Default::default(),
+                confidence: 0.9,
+                suggested_actions: Vec::new(),
+                timestamp: chrono::Utc::now(),
+                acknowledged: false,
+                resolved: false,
+                tags: Vec::new(),
+            })
+            .await
+            .unwrap();
+        state
+    }
+
+    /// A signed callback for clicking `action_id` on an alert.
+    fn callback(action_id: &str, alert_id: &str) -> (HeaderMap, String) {
+        let payload = json!({
+            "user": { "id": "U024BE7LH", "username": "oncall" },
+            "actions": [{ "action_id": action_id, "value": alert_id }],
+        });
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("payload", &payload.to_string())
+            .finish();
+
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+        let signature: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-slack-request-timestamp", timestamp.parse().unwrap());
+        headers.insert(
+            "x-slack-signature",
+            format!("v0={}", signature).parse().unwrap(),
+        );
+        (headers, body)
+    }
+
+    #[tokio::test]
+    async fn test_slack_actions_reach_websocket_clients() {
+        let state = slack_state().await;
+        tokio::spawn(alert_broadcast_task(
+            state.alert_manager.clone(),
+            state.ws_connections.clone(),
+            state.real_time_alerts.clone(),
+        ));
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        state.ws_connections.write().await.insert(
+            "client".to_string(),
+            WebSocketConnection {
+                id: "client".to_string(),
+                sender,
+                last_ping: std::time::Instant::now(),
+                subscription: Subscription::default(),
+            },
+        );
+        // Let the broadcast task subscribe to resolutions
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut next_update = || {
+            let message = receiver.try_recv().expect("alert update");
+            match message {
+                WebSocketMessage::AlertUpdate { data } => data,
+                other => panic!("unexpected message: {:?}", other),
+            }
+        };
+
+        // Failed actions announce nothing
+        for action in ["acknowledge", "resolve"] {
+            let (headers, body) = callback(action, "missing-alert");
+            let response = api_slack_actions(State(state.clone()), headers, body).await;
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let (headers, body) = callback("acknowledge", "alert-1");
+        api_slack_actions(State(state.clone()), headers, body).await;
+        let update = next_update();
+        assert_eq!(update.id, "alert-1");
+        assert!(update.acknowledged && !update.resolved);
+        assert_eq!(update.by.as_deref(), Some("slack:oncall"));
+
+        let (headers, body) = callback("resolve", "alert-1");
+        api_slack_actions(State(state.clone()), headers, body).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let update = next_update();
+        assert_eq!(update.id, "alert-1");
+        assert!(update.resolved);
+        assert_eq!(update.by.as_deref(), Some("slack:oncall"));
+
+        // Each change is announced once
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_verify_slack_signature() {
+        // Example from Slack's request verification guide
+        let secret = "8f742231b10e8888abcd99yyyzzz85a5";
+        let timestamp = "1531420618";
+        let body = "token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+        let signature = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+
+        assert!(verify_slack_signature(
+            secret, timestamp, body, signature, 1531420618
+        ));
+
+        // Tampered bodies, wrong secrets and replays are rejected
+        assert!(!verify_slack_signature(
+            secret,
+            timestamp,
+            &body.replace("roadrunner", "coyote"),
+            signature,
+            1531420618
+        ));
+        assert!(!verify_slack_signature(
+            "other-secret",
+            timestamp,
+            body,
+            signature,
+            1531420618
+        ));
+        assert!(!verify_slack_signature(
+            secret,
+            timestamp,
+            body,
+            signature,
+            1531420618 + 600
+        ));
+        assert!(!verify_slack_signature(
+            secret, timestamp, body, "v0=zz", 1531420618
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+//! Server-Sent Events fallback for clients whose proxies block WebSockets.
+//!
+//! SSE clients are registered alongside WebSocket connections, so they receive
+//! the same broadcasts filtered by the same subscription.
+
+use crate::{ApiResponse, AppState, Subscription, Topic, WebSocketConnection, WebSocketMessage};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+};
+use futures::stream;
+use serde::Deserialize;
+use std::convert::Infallible;
+use tracing::info;
+use uuid::Uuid;
+use watchtower_engine::AlertSeverity;
+
+/// Query parameters selecting the messages of an SSE stream
+#[derive(Debug, Default, Deserialize)]
+pub struct SseQuery {
+    /// Comma-separated topics, e.g. `alerts,metrics`
+    pub topics: Option<String>,
+    /// Program name or ID alerts and events are limited to
+    pub program: Option<String>,
+    /// Lowest severity of alerts delivered
+    pub min_severity: Option<String>,
+    /// Event type events are limited to
+    pub event_type: Option<String>,
+}
+
+impl SseQuery {
+    /// Subscription of the stream; without topics the WebSocket defaults apply.
+    pub fn subscription(&self) -> Result<Subscription, String> {
+        let mut subscription = Subscription::default();
+        if let Some(topics) = &self.topics {
+            subscription.topics = topics
+                .split(',')
+                .map(|topic| {
+                    Topic::parse(topic.trim()).ok_or_else(|| format!("Invalid topic: {}", topic))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(min_severity) = &self.min_severity {
+            subscription.min_severity = Some(
+                AlertSeverity::parse(min_severity)
+                    .ok_or_else(|| format!("Invalid severity: {}", min_severity))?,
+            );
+        }
+        subscription.program = self.program.clone();
+        subscription.event_type = self.event_type.clone();
+        Ok(subscription)
+    }
+}
+
+/// Stream of the messages WebSocket clients receive, as Server-Sent Events
+pub async fn sse_handler(State(state): State<AppState>, Query(query): Query<SseQuery>) -> Response {
+    let subscription = match query.subscription() {
+        Ok(subscription) => subscription,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, Json(ApiResponse::<()>::error(e))).into_response()
+        }
+    };
+
+    let connection_id = Uuid::new_v4().to_string();
+    info!("New SSE connection: {}", connection_id);
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<WebSocketMessage>();
+    state.ws_connections.write().await.insert(
+        connection_id.clone(),
+        WebSocketConnection {
+            id: connection_id.clone(),
+            sender: tx,
+            last_ping: std::time::Instant::now(),
+            subscription,
+        },
+    );
+
+    // The connection is dropped from the map by the next broadcast after the
+    // client disconnects and this stream, with its receiver, is dropped
+    let ws_connections = state.ws_connections.clone();
+    let events = stream::unfold(rx, move |mut rx| {
+        let ws_connections = ws_connections.clone();
+        let connection_id = connection_id.clone();
+        async move {
+            let message = rx.recv().await?;
+            let event = match message {
+                // SSE clients cannot answer heartbeats, so a delivered ping
+                // counts as one
+                WebSocketMessage::Ping => {
+                    if let Some(connection) = ws_connections.write().await.get_mut(&connection_id) {
+                        connection.last_ping = std::time::Instant::now();
+                    }
+                    Event::default().comment("ping")
+                }
+                message => Event::default()
+                    .json_data(&message)
+                    .unwrap_or_else(|_| Event::default().comment("unserializable message")),
+            };
+            Some((Ok::<_, Infallible>(event), rx))
+        }
+    });
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_query_subscription() {
+        let default = SseQuery::default().subscription().unwrap();
+        assert_eq!(default.topics, Subscription::default().topics);
+
+        let query = SseQuery {
+            topics: Some("alerts, events".to_string()),
+            min_severity: Some("high".to_string()),
+            ..Default::default()
+        };
+        let subscription = query.subscription().unwrap();
+        assert_eq!(subscription.topics.len(), 2);
+        assert!(subscription.topics.contains(&Topic::Events));
+        assert_eq!(subscription.min_severity, Some(AlertSeverity::High));
+
+        let invalid = SseQuery {
+            topics: Some("alerts,trades".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.subscription().is_err());
+    }
+}
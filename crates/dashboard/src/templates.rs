@@ -1,4 +1,8 @@
+use crate::diagnostics::Diagnostics;
+use crate::events::EventNotification;
+use crate::federation::FleetView;
 use crate::handlers::{AlertInfo, MetricItem, NotificationChannel, RuleInfo};
+use crate::silences::SilenceInfo;
 use crate::PaginationInfo;
 use askama::Template;
 
@@ -7,6 +11,7 @@ use askama::Template;
 #[template(path = "base.html")]
 pub struct BaseTemplate {
     pub title: String,
+    pub base_path: String,
 }
 
 /// Dashboard index page template
@@ -14,10 +19,13 @@ pub struct BaseTemplate {
 #[template(path = "index.html")]
 pub struct IndexTemplate {
     pub title: String,
+    pub base_path: String,
     pub engine_status: String,
     pub alert_count: usize,
     pub active_rules: usize,
     pub uptime: String,
+    pub events_per_second: String,
+    pub avg_processing_ms: String,
 }
 
 /// Alerts page template
@@ -25,6 +33,7 @@ pub struct IndexTemplate {
 #[template(path = "alerts.html")]
 pub struct AlertsTemplate {
     pub title: String,
+    pub base_path: String,
     pub alerts: Vec<AlertInfo>,
     pub pagination: PaginationInfo,
 }
@@ -34,6 +43,7 @@ pub struct AlertsTemplate {
 #[template(path = "metrics.html")]
 pub struct MetricsTemplate {
     pub title: String,
+    pub base_path: String,
     pub metrics: Vec<MetricItem>,
 }
 
@@ -42,13 +52,75 @@ pub struct MetricsTemplate {
 #[template(path = "rules.html")]
 pub struct RulesTemplate {
     pub title: String,
+    pub base_path: String,
     pub rules: Vec<RuleInfo>,
 }
 
+/// Events page template
+#[derive(Template)]
+#[template(path = "events.html")]
+pub struct EventsTemplate {
+    pub title: String,
+    pub base_path: String,
+    pub events: Vec<EventNotification>,
+    /// Program filter
+    pub program: String,
+    /// Event type filter
+    pub event_type: String,
+}
+
+/// Silences page template
+#[derive(Template)]
+#[template(path = "silences.html")]
+pub struct SilencesTemplate {
+    pub title: String,
+    pub base_path: String,
+    pub silences: Vec<SilenceInfo>,
+}
+
+/// Diagnostics page template
+#[derive(Template)]
+#[template(path = "diagnostics.html")]
+pub struct DiagnosticsTemplate {
+    pub title: String,
+    pub base_path: String,
+    pub diagnostics: Diagnostics,
+}
+
+/// Fleet page template
+#[derive(Template)]
+#[template(path = "fleet.html")]
+pub struct FleetTemplate {
+    pub title: String,
+    pub base_path: String,
+    pub fleet: FleetView,
+}
+
 /// Settings page template
 #[derive(Template)]
 #[template(path = "settings.html")]
 pub struct SettingsTemplate {
     pub title: String,
+    pub base_path: String,
     pub notification_channels: Vec<NotificationChannel>,
 }
+
+/// API documentation page template
+#[derive(Template)]
+#[template(path = "api_docs.html")]
+pub struct ApiDocsTemplate {
+    pub title: String,
+    pub base_path: String,
+}
+
+/// Login page template
+#[derive(Template)]
+#[template(path = "login.html")]
+pub struct LoginTemplate {
+    pub title: String,
+    pub base_path: String,
+    /// Page opened after logging in
+    pub next: String,
+    /// Error of a failed login, empty otherwise
+    pub error: String,
+}
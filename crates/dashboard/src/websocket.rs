@@ -1,12 +1,19 @@
-use crate::AppState;
+use crate::{AppState, EventNotification};
 use axum::extract::ws::{Message, WebSocket};
 use futures::{sink::SinkExt, stream::StreamExt};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 use uuid::Uuid;
-use watchtower_engine::AlertManager;
+use watchtower_engine::{AlertManager, AlertSeverity, AlertTransition};
 
 /// WebSocket connection info
 #[derive(Debug, Clone)]
@@ -14,6 +21,8 @@ pub struct WebSocketConnection {
     pub id: String,
     pub sender: tokio::sync::mpsc::UnboundedSender<WebSocketMessage>,
     pub last_ping: std::time::Instant,
+    /// Topics and filters of messages delivered to the client
+    pub subscription: Subscription,
 }
 
 /// WebSocket message types
@@ -23,21 +32,136 @@ pub enum WebSocketMessage {
     Ping,
     Pong,
     Alert { data: AlertNotification },
+    AlertUpdate { data: AlertStateUpdate },
     Status { data: StatusUpdate },
     Metrics { data: MetricsUpdate },
+    Event { data: EventNotification },
+    Subscribe { data: Subscription },
+    Unsubscribe { topics: Vec<Topic> },
     Error { message: String },
 }
 
+/// Kinds of messages a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Topic {
+    /// New alerts and their acknowledgement or resolution
+    Alerts,
+    /// Metrics updates
+    Metrics,
+    /// Processed program events
+    Events,
+    /// Engine status updates
+    Status,
+}
+
+impl Topic {
+    /// Parse a topic name, e.g. `alerts`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "alerts" => Some(Topic::Alerts),
+            "metrics" => Some(Topic::Metrics),
+            "events" => Some(Topic::Events),
+            "status" => Some(Topic::Status),
+            _ => None,
+        }
+    }
+}
+
+/// Topics and filters of the messages a client receives.
+///
+/// Clients that never subscribe receive alerts, metrics and status; the high
+/// volume `events` topic has to be selected explicitly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub topics: HashSet<Topic>,
+    /// Program name or ID alerts and events are limited to
+    #[serde(default)]
+    pub program: Option<String>,
+    /// Lowest severity of alerts delivered
+    #[serde(default)]
+    pub min_severity: Option<AlertSeverity>,
+    /// Event type events are limited to, e.g. `transaction`
+    #[serde(default)]
+    pub event_type: Option<String>,
+}
+
+impl Default for Subscription {
+    fn default() -> Self {
+        Self {
+            topics: HashSet::from([Topic::Alerts, Topic::Metrics, Topic::Status]),
+            program: None,
+            min_severity: None,
+            event_type: None,
+        }
+    }
+}
+
+impl Subscription {
+    /// Whether a message is delivered to the client.
+    pub fn accepts(&self, message: &WebSocketMessage) -> bool {
+        match message {
+            WebSocketMessage::Alert { data } => {
+                self.topics.contains(&Topic::Alerts)
+                    && self.matches_program(&data.program_id, &data.program_name)
+                    && self.min_severity.map_or(true, |min_severity| {
+                        AlertSeverity::parse(&data.severity)
+                            .map_or(true, |severity| severity >= min_severity)
+                    })
+            }
+            // Updates are small and ignored by clients not showing the alert
+            WebSocketMessage::AlertUpdate { .. } => self.topics.contains(&Topic::Alerts),
+            WebSocketMessage::Status { .. } => self.topics.contains(&Topic::Status),
+            WebSocketMessage::Metrics { .. } => self.topics.contains(&Topic::Metrics),
+            WebSocketMessage::Event { data } => {
+                self.topics.contains(&Topic::Events)
+                    && self.matches_program(&data.program_id, &data.program_name)
+                    && self
+                        .event_type
+                        .as_deref()
+                        .filter(|event_type| !event_type.is_empty())
+                        .map_or(true, |event_type| data.event_type == event_type)
+            }
+            WebSocketMessage::Ping
+            | WebSocketMessage::Pong
+            | WebSocketMessage::Subscribe { .. }
+            | WebSocketMessage::Unsubscribe { .. }
+            | WebSocketMessage::Error { .. } => true,
+        }
+    }
+
+    fn matches_program(&self, program_id: &str, program_name: &str) -> bool {
+        self.program
+            .as_deref()
+            .filter(|program| !program.is_empty())
+            .map_or(true, |program| {
+                program == program_id || program == program_name
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertNotification {
     pub id: String,
     pub severity: String,
     pub message: String,
     pub program_id: String,
+    #[serde(default)]
+    pub program_name: String,
     pub timestamp: String,
     pub rule_name: String,
 }
 
+/// Acknowledgement or resolution of an alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertStateUpdate {
+    pub id: String,
+    pub acknowledged: bool,
+    pub resolved: bool,
+    /// Operator who changed the alert, if known
+    pub by: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusUpdate {
     pub engine_status: String,
@@ -65,6 +189,7 @@ pub async fn handle_websocket(socket: WebSocket, state: AppState) {
         id: connection_id.clone(),
         sender: tx,
         last_ping: std::time::Instant::now(),
+        subscription: Subscription::default(),
     };
 
     state
@@ -157,6 +282,18 @@ async fn handle_websocket_message(
                 connection.last_ping = std::time::Instant::now();
             }
         }
+        WebSocketMessage::Subscribe { data } => {
+            if let Some(connection) = ws_connections.write().await.get_mut(connection_id) {
+                connection.subscription = data;
+            }
+        }
+        WebSocketMessage::Unsubscribe { topics } => {
+            if let Some(connection) = ws_connections.write().await.get_mut(connection_id) {
+                for topic in topics {
+                    connection.subscription.topics.remove(&topic);
+                }
+            }
+        }
         _ => {
             warn!("Unexpected message type from client: {:?}", message);
         }
@@ -165,7 +302,7 @@ async fn handle_websocket_message(
     Ok(())
 }
 
-/// Broadcast message to all connected WebSocket clients
+/// Broadcast message to the connected WebSocket clients subscribed to it
 pub async fn broadcast_to_websockets(
     message: WebSocketMessage,
     ws_connections: &Arc<RwLock<HashMap<String, WebSocketConnection>>>,
@@ -174,6 +311,9 @@ pub async fn broadcast_to_websockets(
     let mut failed_connections = Vec::new();
 
     for (connection_id, connection) in connections.iter() {
+        if !connection.subscription.accepts(&message) {
+            continue;
+        }
         if connection.sender.send(message.clone()).is_err() {
             failed_connections.push(connection_id.clone());
         }
@@ -225,28 +365,82 @@ pub async fn websocket_heartbeat_task(
     }
 }
 
-/// Background task to broadcast alerts to WebSocket clients
+/// Background task to broadcast new and resolved alerts to WebSocket clients
 pub async fn alert_broadcast_task(
     alert_manager: Arc<AlertManager>,
     ws_connections: Arc<RwLock<HashMap<String, WebSocketConnection>>>,
+    real_time_alerts: Arc<AtomicBool>,
 ) {
     let mut alert_receiver = alert_manager.subscribe();
+    // Resolutions from the API, Slack and auto-resolution all arrive here
+    let mut resolution_receiver = alert_manager.subscribe_resolutions();
 
-    while let Ok(alert) = alert_receiver.recv().await {
-        let notification = AlertNotification {
-            id: alert.id.clone(),
-            severity: alert.severity.as_str().to_string(),
-            message: alert.message.clone(),
-            program_id: alert.program_id.to_string(),
-            timestamp: alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
-            rule_name: alert.rule_name.clone(),
+    loop {
+        let message = tokio::select! {
+            Ok(alert) = alert_receiver.recv() => WebSocketMessage::Alert {
+                data: AlertNotification {
+                    id: alert.id.clone(),
+                    severity: alert.severity.as_str().to_string(),
+                    message: alert.message.clone(),
+                    program_id: alert.program_id.to_string(),
+                    program_name: alert.program_name.clone(),
+                    timestamp: alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                    rule_name: alert.rule_name.clone(),
+                },
+            },
+            Ok(alert) = resolution_receiver.recv() => WebSocketMessage::AlertUpdate {
+                data: AlertStateUpdate {
+                    by: resolved_by(&alert_manager, &alert.id),
+                    id: alert.id,
+                    acknowledged: alert.acknowledged,
+                    resolved: true,
+                },
+            },
+            else => break,
         };
 
-        let message = WebSocketMessage::Alert { data: notification };
+        // New alerts are only pushed while real-time alerts are enabled;
+        // resolutions still keep open alert lists current
+        if matches!(message, WebSocketMessage::Alert { .. })
+            && !real_time_alerts.load(Ordering::Relaxed)
+        {
+            continue;
+        }
+
         broadcast_to_websockets(message, &ws_connections).await;
     }
 }
 
+/// Operator recorded as resolving an alert in its audit trail.
+fn resolved_by(alert_manager: &AlertManager, alert_id: &str) -> Option<String> {
+    alert_manager
+        .audit_trail(alert_id)
+        .into_iter()
+        .rev()
+        .find_map(|entry| match entry.transition {
+            AlertTransition::Resolved { by, .. } => Some(by),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// Send an alert acknowledgement to WebSocket clients
+pub async fn send_alert_acknowledged(
+    alert_id: &str,
+    by: Option<String>,
+    ws_connections: &Arc<RwLock<HashMap<String, WebSocketConnection>>>,
+) {
+    let message = WebSocketMessage::AlertUpdate {
+        data: AlertStateUpdate {
+            id: alert_id.to_string(),
+            acknowledged: true,
+            resolved: false,
+            by,
+        },
+    };
+    broadcast_to_websockets(message, ws_connections).await;
+}
+
 /// Send status updates to WebSocket clients
 pub async fn send_status_update(
     status: StatusUpdate,
@@ -264,3 +458,46 @@ pub async fn send_metrics_update(
     let message = WebSocketMessage::Metrics { data: metrics };
     broadcast_to_websockets(message, ws_connections).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(severity: &str, program_name: &str) -> WebSocketMessage {
+        WebSocketMessage::Alert {
+            data: AlertNotification {
+                id: "alert-1".to_string(),
+                severity: severity.to_string(),
+                message: "Large transaction detected".to_string(),
+                program_id: "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(),
+                program_name: program_name.to_string(),
+                timestamp: "2024-01-15 12:00:00 UTC".to_string(),
+                rule_name: "large_transaction".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_subscription_filters() {
+        let metrics = WebSocketMessage::Metrics {
+            data: MetricsUpdate {
+                timestamp: 0,
+                metrics: HashMap::new(),
+            },
+        };
+
+        let default = Subscription::default();
+        assert!(default.accepts(&alert("low", "Orca")));
+        assert!(default.accepts(&metrics));
+
+        let subscription: Subscription = serde_json::from_str(
+            r#"{"topics": ["alerts", "events"], "program": "Orca", "min_severity": "High"}"#,
+        )
+        .unwrap();
+        assert!(subscription.accepts(&alert("critical", "Orca")));
+        assert!(!subscription.accepts(&alert("medium", "Orca")));
+        assert!(!subscription.accepts(&alert("critical", "Solend")));
+        assert!(!subscription.accepts(&metrics));
+        assert!(subscription.accepts(&WebSocketMessage::Ping));
+    }
+}
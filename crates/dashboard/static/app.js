@@ -1,8 +1,17 @@
 // Solana Watchtower Dashboard JavaScript
 
+// URL prefix when served behind a reverse proxy, e.g. "/watchtower"
+const BASE_PATH = document.body.dataset.basePath || '';
+
+function websocketUrl() {
+    const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+    return `${protocol}//${window.location.host}${BASE_PATH}/ws`;
+}
+
 class WatchtowerDashboard {
     constructor() {
         this.websocket = null;
+        this.eventSource = null;
         this.reconnectAttempts = 0;
         this.maxReconnectAttempts = 5;
         this.reconnectDelay = 1000;
@@ -16,18 +25,18 @@ class WatchtowerDashboard {
         this.connectWebSocket();
         this.setupEventListeners();
         this.updateConnectionStatus();
+        this.loadSession();
         
         // Auto-refresh data every 30 seconds if WebSocket is not available
         setInterval(() => {
-            if (!this.websocket || this.websocket.readyState !== WebSocket.OPEN) {
+            if (!this.isConnected()) {
                 this.refreshPageData();
             }
         }, 30000);
     }
 
     connectWebSocket() {
-        const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
-        const wsUrl = `${protocol}//${window.location.host}/ws`;
+        const wsUrl = websocketUrl();
         
         try {
             this.websocket = new WebSocket(wsUrl);
@@ -74,9 +83,38 @@ class WatchtowerDashboard {
                 this.connectWebSocket();
             }, delay);
         } else {
-            console.error('Max reconnection attempts reached');
+            // Proxies blocking WebSockets usually let Server-Sent Events through
+            console.error('Max reconnection attempts reached, falling back to Server-Sent Events');
+            this.connectEventSource();
+        }
+    }
+
+    connectEventSource() {
+        if (!window.EventSource) {
             this.showNotification('Connection lost. Please refresh the page.', 'error');
+            return;
         }
+
+        this.eventSource = new EventSource(`${BASE_PATH}/sse`);
+
+        this.eventSource.onopen = () => {
+            console.log('Server-Sent Events connected');
+            this.updateConnectionStatus(true);
+        };
+
+        this.eventSource.onmessage = (event) => {
+            this.handleWebSocketMessage(event);
+        };
+
+        // EventSource reconnects by itself
+        this.eventSource.onerror = () => {
+            this.updateConnectionStatus(false);
+        };
+    }
+
+    isConnected() {
+        return (this.websocket && this.websocket.readyState === WebSocket.OPEN) ||
+            (this.eventSource && this.eventSource.readyState === EventSource.OPEN);
     }
 
     handleWebSocketMessage(event) {
@@ -92,6 +130,10 @@ class WatchtowerDashboard {
                 case 'Alert':
                     this.handleNewAlert(message.data);
                     break;
+
+                case 'AlertUpdate':
+                    // Applied by the alerts page's own connection
+                    break;
                     
                 case 'Status':
                     this.handleStatusUpdate(message.data);
@@ -152,7 +194,7 @@ class WatchtowerDashboard {
         this.updateAlertBadge();
         
         // If on alerts page, add to list
-        if (window.location.pathname === '/alerts') {
+        if (window.location.pathname === `${BASE_PATH}/alerts`) {
             this.addAlertToList(alertData);
         }
     }
@@ -250,9 +292,24 @@ class WatchtowerDashboard {
         this.fetchSystemStatus();
     }
 
+    async loadSession() {
+        try {
+            const response = await fetch(`${BASE_PATH}/api/session`);
+            const data = await response.json();
+
+            // Only password logins can be ended from the dashboard
+            if (data.success && data.data.mode === 'password' && data.data.username) {
+                document.getElementById('session-username').textContent = data.data.username;
+                document.getElementById('session-user').hidden = false;
+            }
+        } catch (error) {
+            console.error('Failed to load session:', error);
+        }
+    }
+
     async fetchSystemStatus() {
         try {
-            const response = await fetch('/api/status');
+            const response = await fetch(`${BASE_PATH}/api/status`);
             const data = await response.json();
             
             if (data.success) {
@@ -271,7 +328,9 @@ class WatchtowerDashboard {
             'active-rules': statusData.active_rules,
             'uptime': this.formatUptime(statusData.uptime_seconds),
             'memory-usage': `${statusData.memory_usage_mb} MB`,
-            'websocket-connections': statusData.connected_websockets
+            'websocket-connections': statusData.connected_websockets,
+            'events-per-second': statusData.performance.current_events_per_second.toFixed(2),
+            'avg-processing-time': `${statusData.performance.avg_event_processing_ms.toFixed(2)} ms`
         };
         
         Object.entries(elements).forEach(([id, value]) => {
@@ -0,0 +1,84 @@
+//! Compares per-event and batched event processing throughput.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use watchtower_engine::{
+    AlertManager, EngineConfig, LargeTransactionRule, MetricsCollector, MonitoringEngine,
+};
+use watchtower_subscriber::{EventData, EventType, ProgramEvent};
+
+const PROGRAMS: usize = 4;
+
+fn new_engine(runtime: &tokio::runtime::Runtime) -> MonitoringEngine {
+    runtime.block_on(async {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let engine = MonitoringEngine::new(
+            metrics,
+            Arc::new(AlertManager::new()),
+            EngineConfig::default(),
+        );
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, u64::MAX)))
+            .await;
+        engine.start().await.unwrap();
+        engine
+    })
+}
+
+fn events(programs: &[Pubkey], count: usize) -> Vec<ProgramEvent> {
+    (0..count)
+        .map(|i| {
+            let program = i % programs.len();
+            ProgramEvent::new(
+                programs[program],
+                format!("Program {}", program),
+                EventType::TokenTransfer,
+                EventData::TokenTransfer {
+                    from: Pubkey::new_unique(),
+                    to: Pubkey::new_unique(),
+                    amount: 1_000,
+                    mint: Pubkey::new_unique(),
+                    decimals: 6,
+                },
+            )
+        })
+        .collect()
+}
+
+fn bench_process_events(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let programs: Vec<Pubkey> = (0..PROGRAMS).map(|_| Pubkey::new_unique()).collect();
+    let mut group = c.benchmark_group("process_events");
+
+    for size in [100usize, 1_000] {
+        group.throughput(Throughput::Elements(size as u64));
+
+        let engine = &new_engine(&runtime);
+        group.bench_with_input(BenchmarkId::new("per_event", size), &size, |b, &size| {
+            b.to_async(&runtime).iter_batched(
+                || events(&programs, size),
+                |events| async move {
+                    for event in events {
+                        engine.process_event(event).await.unwrap();
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            );
+        });
+
+        let engine = &new_engine(&runtime);
+        group.bench_with_input(BenchmarkId::new("batched", size), &size, |b, &size| {
+            b.to_async(&runtime).iter_batched(
+                || events(&programs, size),
+                |events| async move { engine.process_events(events).await.unwrap() },
+                criterion::BatchSize::SmallInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_process_events);
+criterion_main!(benches);
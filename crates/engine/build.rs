@@ -0,0 +1,13 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored protoc so builds don't need a system install
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+
+    tonic_build::configure()
+        .build_client(true)
+        .compile_protos(&["proto/watchtower.proto"], &["proto"])?;
+
+    println!("cargo:rerun-if-changed=proto/watchtower.proto");
+    Ok(())
+}
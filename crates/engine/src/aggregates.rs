@@ -0,0 +1,328 @@
+//! Per-address activity aggregation shared by all rules.
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::time::Duration;
+use watchtower_subscriber::{EventData, ProgramEvent};
+
+/// Lamports per SOL, used to express account balance changes in SOL.
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Metadata keys checked for the signer of a transaction event.
+pub(crate) const SIGNER_METADATA_KEYS: [&str; 2] = ["signer", "fee_payer"];
+
+/// Configuration for per-address aggregation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationConfig {
+    /// Longest window that can be queried, in seconds
+    #[serde(default = "default_window")]
+    pub window_seconds: u64,
+
+    /// Maximum number of addresses tracked at once
+    #[serde(default = "default_max_addresses")]
+    pub max_addresses: usize,
+}
+
+/// Aggregated activity of one address over a window.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AddressStats {
+    /// Volume sent (token units for transfers, SOL for balance changes)
+    pub volume_sent: f64,
+
+    /// Volume received (token units for transfers, SOL for balance changes)
+    pub volume_received: f64,
+
+    /// Number of events involving the address
+    pub tx_count: u64,
+
+    /// Number of failed events involving the address
+    pub failure_count: u64,
+}
+
+/// A single observation for an address.
+#[derive(Debug, Clone)]
+struct ActivityEntry {
+    timestamp: DateTime<Utc>,
+    sent: f64,
+    received: f64,
+    failed: bool,
+}
+
+/// Activity history of a single address.
+#[derive(Debug, Default)]
+struct AddressActivity {
+    entries: VecDeque<ActivityEntry>,
+    last_seen: Option<DateTime<Utc>>,
+}
+
+/// Aggregates volume, transaction and failure counts per wallet address.
+///
+/// Fed by the engine for every processed event and exposed to rules through
+/// `RuleContext::addresses`, so rules don't each rebuild this from history.
+#[derive(Debug)]
+pub struct AddressAggregator {
+    /// Activity per address
+    addresses: DashMap<Pubkey, AddressActivity>,
+
+    /// Longest window retained
+    window: Duration,
+
+    /// Maximum number of addresses tracked
+    max_addresses: usize,
+}
+
+impl AddressAggregator {
+    /// Create an aggregator from configuration.
+    pub fn new(config: &AggregationConfig) -> Self {
+        Self {
+            addresses: DashMap::new(),
+            window: Duration::from_secs(config.window_seconds),
+            max_addresses: config.max_addresses.max(1),
+        }
+    }
+
+    /// Record the address activity contained in an event.
+    pub fn record(&self, event: &ProgramEvent) {
+        let mut observations: HashMap<Pubkey, ActivityEntry> = HashMap::new();
+        let mut observe = |address: Pubkey, sent: f64, received: f64, failed: bool| {
+            let entry = observations.entry(address).or_insert(ActivityEntry {
+                timestamp: event.timestamp,
+                sent: 0.0,
+                received: 0.0,
+                failed: false,
+            });
+            entry.sent += sent;
+            entry.received += received;
+            entry.failed |= failed;
+        };
+
+        match &event.data {
+            EventData::TokenTransfer {
+                from,
+                to,
+                amount,
+                decimals,
+                ..
+            } => {
+                let amount = *amount as f64 / 10f64.powi(*decimals as i32);
+                observe(*from, amount, 0.0, false);
+                observe(*to, 0.0, amount, false);
+            }
+            EventData::AccountChange {
+                account,
+                balance_before: Some(before),
+                balance_after: Some(after),
+                ..
+            } => {
+                let delta = (*after as f64 - *before as f64) / LAMPORTS_PER_SOL;
+                if delta < 0.0 {
+                    observe(*account, -delta, 0.0, false);
+                } else {
+                    observe(*account, 0.0, delta, false);
+                }
+            }
+            EventData::Instruction {
+                accounts, success, ..
+            } => {
+                for account in accounts {
+                    observe(*account, 0.0, 0.0, !success);
+                }
+            }
+            EventData::Transaction { success, .. } => {
+                for key in SIGNER_METADATA_KEYS {
+                    if let Some(signer) = event
+                        .metadata
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| Pubkey::from_str(s).ok())
+                    {
+                        observe(signer, 0.0, 0.0, !success);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if observations.is_empty() {
+            return;
+        }
+
+        let cutoff = event.timestamp - chrono::Duration::from_std(self.window).unwrap_or_default();
+        for (address, entry) in observations {
+            let mut activity = self.addresses.entry(address).or_default();
+            activity.last_seen = Some(
+                activity
+                    .last_seen
+                    .map_or(entry.timestamp, |seen| seen.max(entry.timestamp)),
+            );
+            activity.entries.push_back(entry);
+            while activity
+                .entries
+                .front()
+                .is_some_and(|e| e.timestamp < cutoff)
+            {
+                activity.entries.pop_front();
+            }
+        }
+
+        self.enforce_limit();
+    }
+
+    /// Aggregated activity of `address` over the last `window`.
+    ///
+    /// Windows longer than the configured retention are capped to it.
+    pub fn stats(&self, address: &Pubkey, window: Duration) -> AddressStats {
+        self.addresses
+            .get(address)
+            .map(|activity| activity.stats_since(self.cutoff(window)))
+            .unwrap_or_default()
+    }
+
+    /// Addresses with the highest total volume over the last `window`.
+    pub fn top_by_volume(&self, window: Duration, limit: usize) -> Vec<(Pubkey, AddressStats)> {
+        let cutoff = self.cutoff(window);
+        let mut ranked: Vec<(Pubkey, AddressStats)> = self
+            .addresses
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().stats_since(cutoff)))
+            .filter(|(_, stats)| stats.tx_count > 0)
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            let volume = |s: &AddressStats| s.volume_sent + s.volume_received;
+            volume(&b.1).total_cmp(&volume(&a.1))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+
+    /// Number of addresses currently tracked.
+    pub fn tracked_addresses(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Start of a query window, capped to the configured retention.
+    fn cutoff(&self, window: Duration) -> DateTime<Utc> {
+        Utc::now() - chrono::Duration::from_std(window.min(self.window)).unwrap_or_default()
+    }
+
+    /// Drop the least recently seen addresses once over the limit.
+    fn enforce_limit(&self) {
+        if self.addresses.len() <= self.max_addresses {
+            return;
+        }
+
+        // Evict a tenth of the limit at a time so the scan is amortized
+        let target = self.max_addresses - self.max_addresses / 10;
+        let mut by_age: Vec<(Pubkey, Option<DateTime<Utc>>)> = self
+            .addresses
+            .iter()
+            .map(|entry| (*entry.key(), entry.last_seen))
+            .collect();
+        by_age.sort_by_key(|(_, last_seen)| *last_seen);
+
+        let excess = by_age.len().saturating_sub(target);
+        for (address, _) in by_age.into_iter().take(excess) {
+            self.addresses.remove(&address);
+        }
+    }
+}
+
+impl AddressActivity {
+    /// Sum the entries observed at or after `cutoff`.
+    fn stats_since(&self, cutoff: DateTime<Utc>) -> AddressStats {
+        self.entries.iter().filter(|e| e.timestamp >= cutoff).fold(
+            AddressStats::default(),
+            |mut stats, e| {
+                stats.volume_sent += e.sent;
+                stats.volume_received += e.received;
+                stats.tx_count += 1;
+                stats.failure_count += e.failed as u64;
+                stats
+            },
+        )
+    }
+}
+
+impl Default for AddressAggregator {
+    fn default() -> Self {
+        Self::new(&AggregationConfig::default())
+    }
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: default_window(),
+            max_addresses: default_max_addresses(),
+        }
+    }
+}
+
+// Default value functions
+fn default_window() -> u64 {
+    3600 // 1 hour
+}
+
+fn default_max_addresses() -> usize {
+    100_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_subscriber::EventType;
+
+    fn transfer(from: Pubkey, to: Pubkey, amount: u64) -> ProgramEvent {
+        ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Token".to_string(),
+            EventType::TokenTransfer,
+            EventData::TokenTransfer {
+                from,
+                to,
+                amount,
+                mint: Pubkey::new_unique(),
+                decimals: 6,
+            },
+        )
+    }
+
+    #[test]
+    fn test_transfer_volumes() {
+        let aggregator = AddressAggregator::default();
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        aggregator.record(&transfer(alice, bob, 5_000_000));
+        aggregator.record(&transfer(alice, bob, 1_000_000));
+        aggregator.record(&transfer(bob, alice, 2_000_000));
+
+        let window = Duration::from_secs(60);
+        let alice_stats = aggregator.stats(&alice, window);
+        assert_eq!(alice_stats.volume_sent, 6.0);
+        assert_eq!(alice_stats.volume_received, 2.0);
+        assert_eq!(alice_stats.tx_count, 3);
+
+        let top = aggregator.top_by_volume(window, 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].1.volume_sent + top[0].1.volume_received, 8.0);
+    }
+
+    #[test]
+    fn test_address_limit() {
+        let aggregator = AddressAggregator::new(&AggregationConfig {
+            window_seconds: 3600,
+            max_addresses: 10,
+        });
+
+        for _ in 0..20 {
+            aggregator.record(&transfer(Pubkey::new_unique(), Pubkey::new_unique(), 1));
+        }
+
+        assert!(aggregator.tracked_addresses() <= 10);
+    }
+}
@@ -1,11 +1,16 @@
 //! Alert management system for Solana monitoring.
 
+use crate::calibration::AlertFeedback;
+use crate::deliveries::{DeliveryLog, DeliveryOutcome, DeliveryRecord};
 use crate::rules::AlertSeverity;
+use crate::sinks::AlertSink;
+use base64::Engine as _;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::{broadcast, RwLock};
@@ -53,6 +58,10 @@ pub struct Alert {
 
     /// Whether the alert has been resolved
     pub resolved: bool,
+
+    /// Team/project tags inherited from the program and rule
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Alert manager that handles alert storage, filtering, and notifications.
@@ -66,11 +75,72 @@ pub struct AlertManager {
     /// Alert broadcasters
     alert_sender: broadcast::Sender<Alert>,
 
+    /// Broadcasts alerts as they are resolved
+    resolution_sender: broadcast::Sender<Alert>,
+
     /// Configuration
     config: AlertManagerConfig,
 
     /// Alert statistics
     stats: Arc<RwLock<AlertStatistics>>,
+
+    /// Recent firing times per alert fingerprint
+    occurrences: Arc<DashMap<String, VecDeque<DateTime<Utc>>>>,
+
+    /// State transitions per alert ID
+    audit: Arc<DashMap<String, Vec<AlertAuditEntry>>>,
+
+    /// Exports receiving every alert regardless of notification filters
+    sinks: Arc<RwLock<Vec<Arc<dyn AlertSink>>>>,
+
+    /// Notification delivery attempts
+    deliveries: Arc<DeliveryLog>,
+
+    /// Days resolved alerts stay in history, adjustable at runtime
+    history_retention_days: Arc<std::sync::Mutex<Option<u32>>>,
+}
+
+/// A state transition in an alert's lifecycle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertTransition {
+    /// Alert was created
+    Created { severity: AlertSeverity },
+
+    /// Alert was delivered on a notification channel
+    Notified { channel: String },
+
+    /// Delivery on a notification channel failed
+    NotificationFailed { channel: String, error: String },
+
+    /// Alert was acknowledged
+    Acknowledged { by: Option<String> },
+
+    /// Alert severity was escalated after recurring
+    Escalated {
+        from: AlertSeverity,
+        to: AlertSeverity,
+        occurrences: usize,
+    },
+
+    /// Alert was resolved, either by an operator or automatically
+    Resolved {
+        by: Option<String>,
+        automatic: bool,
+        /// Operator verdict on whether the alert was real
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        feedback: Option<AlertFeedback>,
+    },
+}
+
+/// A timestamped entry in an alert's audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertAuditEntry {
+    /// When the transition happened
+    pub timestamp: DateTime<Utc>,
+
+    /// The transition
+    pub transition: AlertTransition,
 }
 
 /// Configuration for alert manager.
@@ -90,6 +160,18 @@ pub struct AlertManagerConfig {
 
     /// Deduplication window (in seconds)
     pub deduplication_window_seconds: u64,
+
+    /// Whether to escalate the severity of recurring alerts
+    pub enable_escalation: bool,
+
+    /// Number of firings within the escalation window that triggers escalation
+    pub escalation_threshold: usize,
+
+    /// Escalation window (in seconds)
+    pub escalation_window_seconds: u64,
+
+    /// Maximum number of notification delivery records to keep
+    pub max_delivery_records: usize,
 }
 
 /// Alert filtering criteria.
@@ -115,6 +197,263 @@ pub struct AlertFilter {
 
     /// Filter by confidence threshold
     pub min_confidence: Option<f64>,
+
+    /// Filter by tags (matches alerts carrying any of them)
+    pub tags: Option<Vec<String>>,
+
+    /// Case-insensitive text searched in the message, rule, program name and ID
+    pub text: Option<String>,
+}
+
+impl AlertFilter {
+    /// Whether an alert matches every criterion of the filter.
+    pub fn matches(&self, alert: &Alert) -> bool {
+        // Filter by severity
+        if let Some(severities) = &self.severities {
+            if !severities.contains(&alert.severity) {
+                return false;
+            }
+        }
+
+        // Filter by rule names
+        if let Some(rule_names) = &self.rule_names {
+            if !rule_names.contains(&alert.rule_name) {
+                return false;
+            }
+        }
+
+        // Filter by program IDs
+        if let Some(program_ids) = &self.program_ids {
+            if !program_ids.contains(&alert.program_id) {
+                return false;
+            }
+        }
+
+        // Filter by acknowledged status
+        if let Some(acknowledged) = self.acknowledged {
+            if alert.acknowledged != acknowledged {
+                return false;
+            }
+        }
+
+        // Filter by resolved status
+        if let Some(resolved) = self.resolved {
+            if alert.resolved != resolved {
+                return false;
+            }
+        }
+
+        // Filter by time range
+        if let Some(time_range) = &self.time_range {
+            if alert.timestamp < time_range.start || alert.timestamp >= time_range.end {
+                return false;
+            }
+        }
+
+        // Filter by confidence threshold
+        if let Some(min_confidence) = self.min_confidence {
+            if alert.confidence < min_confidence {
+                return false;
+            }
+        }
+
+        // Filter by tags
+        if let Some(tags) = &self.tags {
+            if !alert.tags.iter().any(|tag| tags.contains(tag)) {
+                return false;
+            }
+        }
+
+        // Search the text
+        if let Some(text) = &self.text {
+            let text = text.to_lowercase();
+            let found = [
+                alert.message.as_str(),
+                alert.rule_name.as_str(),
+                alert.program_name.as_str(),
+                alert.id.as_str(),
+            ]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&text));
+            if !found {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// A page of alerts matching a search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertPage {
+    /// Matching alerts on the page, in the order of the search
+    pub alerts: Vec<Alert>,
+
+    /// Number of matching alerts across all pages
+    pub total: usize,
+
+    /// Cursor of the next page, absent on the last page
+    pub next_cursor: Option<AlertCursor>,
+}
+
+/// Field alerts are ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSortField {
+    #[default]
+    Timestamp,
+    Severity,
+    Confidence,
+    /// Rule name
+    Rule,
+    /// Program name
+    Program,
+}
+
+/// Order of alert search results.
+///
+/// Alerts with equal values are ordered by timestamp and then ID, so the
+/// order is stable across requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertSort {
+    pub field: AlertSortField,
+    pub descending: bool,
+}
+
+impl Default for AlertSort {
+    /// Newest first
+    fn default() -> Self {
+        Self {
+            field: AlertSortField::Timestamp,
+            descending: true,
+        }
+    }
+}
+
+impl AlertSort {
+    /// Parse `<field>` or `<field>:<asc|desc>`.
+    ///
+    /// Timestamps, severities and confidences default to descending, rule and
+    /// program names to ascending.
+    pub fn parse(sort: &str) -> Option<Self> {
+        let (field, direction) = match sort.split_once(':') {
+            Some((field, direction)) => (field, Some(direction)),
+            None => (sort, None),
+        };
+        let field = match field.trim().to_lowercase().as_str() {
+            "timestamp" | "time" => AlertSortField::Timestamp,
+            "severity" => AlertSortField::Severity,
+            "confidence" => AlertSortField::Confidence,
+            "rule" | "rule_name" => AlertSortField::Rule,
+            "program" | "program_name" => AlertSortField::Program,
+            _ => return None,
+        };
+        let descending = match direction.map(|d| d.trim().to_lowercase()) {
+            Some(direction) if direction == "asc" => false,
+            Some(direction) if direction == "desc" => true,
+            Some(_) => return None,
+            None => !matches!(field, AlertSortField::Rule | AlertSortField::Program),
+        };
+        Some(Self { field, descending })
+    }
+
+    /// Position of an alert in this order.
+    fn key(&self, alert: &Alert) -> AlertCursor {
+        let value = match self.field {
+            AlertSortField::Timestamp => SortValue::Timestamp,
+            AlertSortField::Severity => SortValue::Severity(alert.severity),
+            AlertSortField::Confidence => SortValue::Confidence(alert.confidence),
+            AlertSortField::Rule => SortValue::Text(alert.rule_name.clone()),
+            AlertSortField::Program => SortValue::Text(alert.program_name.clone()),
+        };
+        AlertCursor {
+            sort: *self,
+            value,
+            timestamp: alert.timestamp,
+            id: alert.id.clone(),
+        }
+    }
+
+    fn compare(&self, a: &AlertCursor, b: &AlertCursor) -> Ordering {
+        let ordering = a
+            .value
+            .compare(&b.value)
+            .then_with(|| a.timestamp.cmp(&b.timestamp))
+            .then_with(|| a.id.cmp(&b.id));
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Value of the sort field of an alert.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortValue {
+    /// Sorting by timestamp needs no value besides the alert's own
+    Timestamp,
+    Severity(AlertSeverity),
+    Confidence(f64),
+    Text(String),
+}
+
+impl SortValue {
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortValue::Severity(a), SortValue::Severity(b)) => a.cmp(b),
+            (SortValue::Confidence(a), SortValue::Confidence(b)) => a.total_cmp(b),
+            (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// Position after the last alert of a page, where the next page starts.
+///
+/// Unlike offsets, cursors do not skip or repeat alerts when alerts are
+/// raised or resolved between requests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertCursor {
+    /// Order the cursor belongs to
+    sort: AlertSort,
+    value: SortValue,
+    timestamp: DateTime<Utc>,
+    id: String,
+}
+
+impl AlertCursor {
+    /// Opaque, URL-safe form of the cursor.
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Cursor of an encoded string, if valid.
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let json = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Order the cursor belongs to.
+    pub fn sort(&self) -> AlertSort {
+        self.sort
+    }
+}
+
+/// Order and page of an alert search.
+#[derive(Debug, Clone, Default)]
+pub struct AlertSearch {
+    pub sort: AlertSort,
+    /// Only alerts after this cursor; its order replaces `sort`
+    pub after: Option<AlertCursor>,
+    /// Matching alerts skipped, after the cursor if any
+    pub offset: usize,
+    pub limit: usize,
 }
 
 /// Time range for filtering alerts.
@@ -170,6 +509,13 @@ pub enum AlertError {
 
 pub type AlertResult<T> = Result<T, AlertError>;
 
+impl Alert {
+    /// Fingerprint identifying recurrences of the same condition.
+    pub fn fingerprint(&self) -> String {
+        format!("{}:{}", self.rule_name, self.program_id)
+    }
+}
+
 impl Default for AlertManager {
     fn default() -> Self {
         Self::new()
@@ -185,24 +531,83 @@ impl AlertManager {
     /// Create a new alert manager with custom configuration.
     pub fn with_config(config: AlertManagerConfig) -> Self {
         let (alert_sender, _) = broadcast::channel(1000);
+        let (resolution_sender, _) = broadcast::channel(1000);
 
         Self {
             alerts: Arc::new(DashMap::new()),
             history: Arc::new(RwLock::new(Vec::new())),
             alert_sender,
-            config,
+            resolution_sender,
             stats: Arc::new(RwLock::new(AlertStatistics::default())),
+            occurrences: Arc::new(DashMap::new()),
+            audit: Arc::new(DashMap::new()),
+            sinks: Arc::new(RwLock::new(Vec::new())),
+            deliveries: Arc::new(DeliveryLog::in_memory(config.max_delivery_records)),
+            history_retention_days: Arc::new(std::sync::Mutex::new(None)),
+            config,
         }
     }
 
+    /// Keep delivery records in `deliveries`, e.g. a log persisted to disk.
+    pub fn with_delivery_log(mut self, deliveries: DeliveryLog) -> Self {
+        self.deliveries = Arc::new(deliveries);
+        self
+    }
+
+    /// Add a sink that receives every new and escalated alert.
+    pub async fn add_sink(&self, sink: Arc<dyn AlertSink>) {
+        info!("Adding alert sink: {}", sink.name());
+        self.sinks.write().await.push(sink);
+    }
+
+    /// Write an alert to all sinks in the background.
+    async fn export(&self, alert: &Alert) {
+        let sinks = self.sinks.read().await.clone();
+        if sinks.is_empty() {
+            return;
+        }
+
+        let alert = alert.clone();
+        tokio::spawn(async move {
+            for sink in sinks {
+                if let Err(e) = sink.write(&alert).await {
+                    warn!(
+                        "Failed to write alert {} to sink {}: {}",
+                        alert.id,
+                        sink.name(),
+                        e
+                    );
+                }
+            }
+        });
+    }
+
     /// Send an alert through the system.
     pub async fn send_alert(&self, mut alert: Alert) -> AlertResult<()> {
+        let occurrences = self.record_occurrence(&alert);
+        let escalate =
+            self.config.enable_escalation && occurrences >= self.config.escalation_threshold;
+
         // Check for deduplication
         if self.config.enable_deduplication {
             if let Some(existing_alert) = self.find_duplicate(&alert).await {
                 debug!("Deduplicated alert for rule {}", alert.rule_name);
                 // Update the existing alert's timestamp
                 self.update_alert_timestamp(&existing_alert.id).await?;
+
+                // Re-broadcast the existing alert once it escalates
+                if escalate {
+                    if let Some(escalated) = self.escalate_existing(&existing_alert.id, occurrences)
+                    {
+                        self.export(&escalated).await;
+                        if let Err(e) = self.alert_sender.send(escalated) {
+                            warn!(
+                                "Failed to broadcast escalated alert {}: {}",
+                                existing_alert.id, e
+                            );
+                        }
+                    }
+                }
                 return Ok(());
             }
         }
@@ -212,13 +617,29 @@ impl AlertManager {
             alert.id = Uuid::new_v4().to_string();
         }
 
+        // Start the audit trail
+        self.audit.insert(
+            alert.id.clone(),
+            vec![AlertAuditEntry {
+                timestamp: Utc::now(),
+                transition: AlertTransition::Created {
+                    severity: alert.severity,
+                },
+            }],
+        );
+
+        if escalate {
+            self.escalate(&mut alert, occurrences);
+        }
+
         // Add to active alerts
         self.alerts.insert(alert.id.clone(), alert.clone());
 
         // Update statistics
         self.update_statistics(&alert).await;
 
-        // Broadcast alert
+        // Export and broadcast alert
+        self.export(&alert).await;
         if let Err(e) = self.alert_sender.send(alert.clone()) {
             warn!("Failed to broadcast alert {}: {}", alert.id, e);
         }
@@ -254,8 +675,27 @@ impl AlertManager {
 
     /// Acknowledge an alert.
     pub async fn acknowledge_alert(&self, alert_id: &str) -> AlertResult<()> {
+        self.acknowledge_alert_by(alert_id, None).await
+    }
+
+    /// Acknowledge an alert on behalf of an operator.
+    pub async fn acknowledge_alert_by(
+        &self,
+        alert_id: &str,
+        acknowledged_by: Option<&str>,
+    ) -> AlertResult<()> {
         if let Some(mut alert_entry) = self.alerts.get_mut(alert_id) {
-            alert_entry.acknowledged = true;
+            // Repeated acknowledgements change nothing
+            if std::mem::replace(&mut alert_entry.acknowledged, true) {
+                return Ok(());
+            }
+            drop(alert_entry);
+            self.record_transition(
+                alert_id,
+                AlertTransition::Acknowledged {
+                    by: acknowledged_by.map(str::to_string),
+                },
+            );
 
             // Update statistics
             let mut stats = self.stats.write().await;
@@ -272,17 +712,115 @@ impl AlertManager {
 
     /// Resolve an alert.
     pub async fn resolve_alert(&self, alert_id: &str) -> AlertResult<()> {
+        self.resolve_alert_by(alert_id, None).await
+    }
+
+    /// Resolve an alert on behalf of an operator.
+    pub async fn resolve_alert_by(
+        &self,
+        alert_id: &str,
+        resolved_by: Option<&str>,
+    ) -> AlertResult<()> {
+        self.resolve_alert_with_feedback(alert_id, resolved_by, None)
+            .await
+    }
+
+    /// Resolve an alert on behalf of an operator, recording whether it was
+    /// a true or false positive in its audit trail.
+    pub async fn resolve_alert_with_feedback(
+        &self,
+        alert_id: &str,
+        resolved_by: Option<&str>,
+        feedback: Option<AlertFeedback>,
+    ) -> AlertResult<()> {
+        self.resolve_with(
+            alert_id,
+            AlertTransition::Resolved {
+                by: resolved_by.map(str::to_string),
+                automatic: false,
+                feedback,
+            },
+        )
+        .await
+    }
+
+    /// Record a state transition in an alert's audit trail.
+    ///
+    /// Transitions for alerts the manager does not track (e.g. deduplicated
+    /// ones) are ignored.
+    pub fn record_transition(&self, alert_id: &str, transition: AlertTransition) {
+        if let Some(mut trail) = self.audit.get_mut(alert_id) {
+            trail.push(AlertAuditEntry {
+                timestamp: Utc::now(),
+                transition,
+            });
+        }
+    }
+
+    /// Record a notification delivery attempt, also noting it in the alert's
+    /// audit trail.
+    pub fn record_delivery(
+        &self,
+        alert_id: &str,
+        channel: &str,
+        outcome: DeliveryOutcome,
+        provider_message_id: Option<String>,
+        error: Option<String>,
+    ) {
+        match (outcome, &error) {
+            (DeliveryOutcome::Delivered, _) => self.record_transition(
+                alert_id,
+                AlertTransition::Notified {
+                    channel: channel.to_string(),
+                },
+            ),
+            (DeliveryOutcome::Failed, Some(error)) => self.record_transition(
+                alert_id,
+                AlertTransition::NotificationFailed {
+                    channel: channel.to_string(),
+                    error: error.clone(),
+                },
+            ),
+            _ => {}
+        }
+
+        self.deliveries
+            .record(alert_id, channel, outcome, provider_message_id, error);
+    }
+
+    /// Notification delivery history.
+    pub fn deliveries(&self) -> &DeliveryLog {
+        &self.deliveries
+    }
+
+    /// Delivery attempts for an alert, oldest first.
+    pub fn delivery_history(&self, alert_id: &str) -> Vec<DeliveryRecord> {
+        self.deliveries.for_alert(alert_id)
+    }
+
+    /// Get the audit trail of an active or historical alert.
+    pub fn audit_trail(&self, alert_id: &str) -> Vec<AlertAuditEntry> {
+        self.audit
+            .get(alert_id)
+            .map(|trail| trail.clone())
+            .unwrap_or_default()
+    }
+
+    /// Move an alert to history, recording how it was resolved.
+    async fn resolve_with(&self, alert_id: &str, transition: AlertTransition) -> AlertResult<()> {
         if let Some(alert) = self.alerts.remove(alert_id) {
             let mut resolved_alert = alert.1;
             resolved_alert.resolved = true;
+            self.record_transition(alert_id, transition);
 
             // Add to history
             let mut history = self.history.write().await;
             history.push(resolved_alert.clone());
 
-            // Limit history size
+            // Limit history size, dropping the audit trail with the alert
             if history.len() > self.config.max_history_size {
-                history.remove(0);
+                let evicted = history.remove(0);
+                self.audit.remove(&evicted.id);
             }
 
             // Update statistics
@@ -297,6 +835,12 @@ impl AlertManager {
                 stats.avg_resolution_time_seconds = Some(resolution_time);
             }
 
+            drop(stats);
+            drop(history);
+
+            // Receivers are optional, e.g. notification channels with incidents
+            let _ = self.resolution_sender.send(resolved_alert);
+
             info!("Alert resolved: {}", alert_id);
             Ok(())
         } else {
@@ -311,6 +855,11 @@ impl AlertManager {
         self.alert_sender.subscribe()
     }
 
+    /// Subscribe to alerts as they are resolved, manually or automatically.
+    pub fn subscribe_resolutions(&self) -> broadcast::Receiver<Alert> {
+        self.resolution_sender.subscribe()
+    }
+
     /// Get alert statistics.
     pub async fn statistics(&self) -> AlertStatistics {
         self.stats.read().await.clone()
@@ -327,9 +876,143 @@ impl AlertManager {
         }
     }
 
+    /// Search active and resolved alerts, newest first.
+    pub async fn search_alerts(
+        &self,
+        filter: &AlertFilter,
+        offset: usize,
+        limit: usize,
+    ) -> AlertPage {
+        let search = AlertSearch {
+            offset,
+            limit,
+            ..Default::default()
+        };
+        self.query_alerts(filter, &search).await
+    }
+
+    /// Search active and resolved alerts in the order and page of `search`.
+    ///
+    /// Resolved alerts are only searched when the filter does not restrict
+    /// results to unresolved ones. Only the sort keys of matches are
+    /// collected; alerts are copied for the requested page alone.
+    pub async fn query_alerts(&self, filter: &AlertFilter, search: &AlertSearch) -> AlertPage {
+        let sort = search
+            .after
+            .as_ref()
+            .map_or(search.sort, |cursor| cursor.sort);
+
+        // Active alerts are looked up by ID, history by position
+        let history = self.history.read().await;
+        let mut keys: Vec<(AlertCursor, Option<usize>)> = Vec::new();
+        if filter.resolved != Some(true) {
+            keys.extend(
+                self.alerts
+                    .iter()
+                    .filter(|entry| filter.matches(entry.value()))
+                    .map(|entry| (sort.key(entry.value()), None)),
+            );
+        }
+        if filter.resolved != Some(false) {
+            keys.extend(
+                history
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, alert)| filter.matches(alert))
+                    .map(|(index, alert)| (sort.key(alert), Some(index))),
+            );
+        }
+        let total = keys.len();
+
+        if let Some(after) = &search.after {
+            keys.retain(|(key, _)| sort.compare(key, after) == Ordering::Greater);
+        }
+
+        // Only the alerts up to the end of the page need ordering
+        let end = search.offset.saturating_add(search.limit);
+        let more = keys.len() > end;
+        if more {
+            keys.select_nth_unstable_by(end, |a, b| sort.compare(&a.0, &b.0));
+            keys.truncate(end);
+        }
+        keys.sort_by(|a, b| sort.compare(&a.0, &b.0));
+
+        let page: Vec<(AlertCursor, Option<usize>)> =
+            keys.into_iter().skip(search.offset).collect();
+        let next_cursor = if more {
+            page.last().map(|(key, _)| key.clone())
+        } else {
+            None
+        };
+        let alerts = page
+            .into_iter()
+            .filter_map(|(key, index)| match index {
+                Some(index) => history.get(index).cloned(),
+                // Skip alerts resolved since their key was taken
+                None => self.alerts.get(&key.id).map(|alert| alert.clone()),
+            })
+            .collect();
+
+        AlertPage {
+            alerts,
+            total,
+            next_cursor,
+        }
+    }
+
+    /// Drop resolved alerts older than `days` from history during cleanup or
+    /// the next [`prune_history`](Self::prune_history), so a retention change
+    /// can still be reverted without losing history. `None` keeps history up
+    /// to `max_history_size` only.
+    pub async fn set_history_retention_days(&self, days: Option<u32>) {
+        *self
+            .history_retention_days
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = days;
+        info!(
+            "Alert history retention set to {}",
+            days.map_or("unlimited".to_string(), |days| format!("{} days", days))
+        );
+    }
+
+    /// Days resolved alerts stay in history, if limited.
+    pub fn history_retention_days(&self) -> Option<u32> {
+        *self
+            .history_retention_days
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Drop history older than the retention, with its audit trails.
+    pub async fn prune_history(&self) {
+        let Some(days) = self.history_retention_days() else {
+            return;
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+
+        let mut history = self.history.write().await;
+        let before = history.len();
+        history.retain(|alert| {
+            let keep = alert.timestamp >= cutoff;
+            if !keep {
+                self.audit.remove(&alert.id);
+            }
+            keep
+        });
+
+        if history.len() < before {
+            debug!(
+                "Pruned {} alerts older than {} days from history",
+                before - history.len(),
+                days
+            );
+        }
+    }
+
     /// Clear all alerts and history.
     pub async fn clear_all(&self) {
         self.alerts.clear();
+        self.audit.clear();
         self.history.write().await.clear();
 
         // Reset statistics
@@ -358,6 +1041,76 @@ impl AlertManager {
         None
     }
 
+    /// Record a firing of the alert's fingerprint, returning the number of
+    /// firings within the escalation window.
+    fn record_occurrence(&self, alert: &Alert) -> usize {
+        let now = Utc::now();
+        let window_start =
+            now - chrono::Duration::seconds(self.config.escalation_window_seconds as i64);
+
+        let mut times = self.occurrences.entry(alert.fingerprint()).or_default();
+        times.push_back(now);
+        while times.front().is_some_and(|t| *t < window_start) {
+            times.pop_front();
+        }
+        times.len()
+    }
+
+    /// Escalate an alert one severity level and annotate it.
+    ///
+    /// Returns false if the alert was already escalated or is critical.
+    fn escalate(&self, alert: &mut Alert, occurrences: usize) -> bool {
+        if alert.metadata.contains_key("escalated_from")
+            || alert.severity == AlertSeverity::Critical
+        {
+            return false;
+        }
+
+        let original = alert.severity;
+        alert.severity = original.escalated();
+        alert.metadata.insert(
+            "escalated_from".to_string(),
+            serde_json::json!(original.as_str()),
+        );
+        alert
+            .metadata
+            .insert("occurrences".to_string(), serde_json::json!(occurrences));
+        alert.message = format!(
+            "{} (escalated from {}: fired {} times in {}s)",
+            alert.message,
+            original.as_str(),
+            occurrences,
+            self.config.escalation_window_seconds
+        );
+
+        self.record_transition(
+            &alert.id,
+            AlertTransition::Escalated {
+                from: original,
+                to: alert.severity,
+                occurrences,
+            },
+        );
+
+        info!(
+            "Escalated alert {} from {} to {}",
+            alert.id,
+            original.as_str(),
+            alert.severity.as_str()
+        );
+        true
+    }
+
+    /// Escalate an active alert in place, returning it if it changed.
+    fn escalate_existing(&self, alert_id: &str, occurrences: usize) -> Option<Alert> {
+        let mut alert_entry = self.alerts.get_mut(alert_id)?;
+        if self.escalate(&mut alert_entry, occurrences) {
+            Some(alert_entry.clone())
+        } else {
+            None
+        }
+    }
+
     /// Update the timestamp of an existing alert.
     async fn update_alert_timestamp(&self, alert_id: &str) -> AlertResult<()> {
         if let Some(mut alert_entry) = self.alerts.get_mut(alert_id) {
@@ -394,58 +1147,7 @@ impl AlertManager {
     fn apply_filter(&self, alerts: Vec<Alert>, filter: &AlertFilter) -> Vec<Alert> {
         alerts
             .into_iter()
-            .filter(|alert| {
-                // Filter by severity
-                if let Some(severities) = &filter.severities {
-                    if !severities.contains(&alert.severity) {
-                        return false;
-                    }
-                }
-
-                // Filter by rule names
-                if let Some(rule_names) = &filter.rule_names {
-                    if !rule_names.contains(&alert.rule_name) {
-                        return false;
-                    }
-                }
-
-                // Filter by program IDs
-                if let Some(program_ids) = &filter.program_ids {
-                    if !program_ids.contains(&alert.program_id) {
-                        return false;
-                    }
-                }
-
-                // Filter by acknowledged status
-                if let Some(acknowledged) = filter.acknowledged {
-                    if alert.acknowledged != acknowledged {
-                        return false;
-                    }
-                }
-
-                // Filter by resolved status
-                if let Some(resolved) = filter.resolved {
-                    if alert.resolved != resolved {
-                        return false;
-                    }
-                }
-
-                // Filter by time range
-                if let Some(time_range) = &filter.time_range {
-                    if alert.timestamp < time_range.start || alert.timestamp >= time_range.end {
-                        return false;
-                    }
-                }
-
-                // Filter by confidence threshold
-                if let Some(min_confidence) = filter.min_confidence {
-                    if alert.confidence < min_confidence {
-                        return false;
-                    }
-                }
-
-                true
-            })
+            .filter(|alert| filter.matches(alert))
             .collect()
     }
 
@@ -463,7 +1165,12 @@ impl AlertManager {
         }
 
         for alert_id in alerts_to_resolve {
-            if let Err(e) = self.resolve_alert(&alert_id).await {
+            let transition = AlertTransition::Resolved {
+                by: None,
+                automatic: true,
+                feedback: None,
+            };
+            if let Err(e) = self.resolve_with(&alert_id, transition).await {
                 error!("Failed to auto-resolve alert {}: {}", alert_id, e);
             }
         }
@@ -477,15 +1184,29 @@ impl AlertManager {
                 .map(|entry| (entry.timestamp, entry.id.clone()))
                 .collect();
 
+            #[allow(clippy::unnecessary_sort_by)]
             oldest_alerts.sort_by(|a, b| a.0.cmp(&b.0));
 
             let excess = self.alerts.len() - self.config.max_active_alerts;
             for (_, alert_id) in oldest_alerts.into_iter().take(excess) {
-                if let Err(e) = self.resolve_alert(&alert_id).await {
+                let transition = AlertTransition::Resolved {
+                    by: None,
+                    automatic: true,
+                    feedback: None,
+                };
+                if let Err(e) = self.resolve_with(&alert_id, transition).await {
                     error!("Failed to remove old alert {}: {}", alert_id, e);
                 }
             }
         }
+
+        // Forget fingerprints that have not fired within the escalation window
+        let window_start =
+            Utc::now() - chrono::Duration::seconds(self.config.escalation_window_seconds as i64);
+        self.occurrences
+            .retain(|_, times| times.back().is_some_and(|t| *t >= window_start));
+
+        self.prune_history().await;
     }
 }
 
@@ -497,6 +1218,10 @@ impl Default for AlertManagerConfig {
             auto_resolve_age_seconds: 86400, // 24 hours
             enable_deduplication: true,
             deduplication_window_seconds: 300, // 5 minutes
+            enable_escalation: true,
+            escalation_threshold: 5,
+            escalation_window_seconds: 600, // 10 minutes
+            max_delivery_records: 10000,
         }
     }
 }
@@ -532,6 +1257,7 @@ mod tests {
             timestamp: Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
         manager.send_alert(alert.clone()).await.unwrap();
@@ -562,10 +1288,12 @@ mod tests {
             timestamp: Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
         manager.send_alert(alert).await.unwrap();
         manager.acknowledge_alert("test-alert").await.unwrap();
+        manager.acknowledge_alert("test-alert").await.unwrap();
 
         let retrieved = manager.get_alert("test-alert").unwrap();
         assert!(retrieved.acknowledged);
@@ -592,14 +1320,21 @@ mod tests {
             timestamp: Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
         manager.send_alert(alert).await.unwrap();
+        let mut resolutions = manager.subscribe_resolutions();
         manager.resolve_alert("test-alert").await.unwrap();
 
         // Alert should no longer be active
         assert!(manager.get_alert("test-alert").is_none());
 
+        // Resolution is broadcast
+        let resolved = resolutions.try_recv().unwrap();
+        assert_eq!(resolved.id, "test-alert");
+        assert!(resolved.resolved);
+
         // But should be in history
         let history = manager.history(None).await;
         assert_eq!(history.len(), 1);
@@ -608,4 +1343,320 @@ mod tests {
         let stats = manager.statistics().await;
         assert_eq!(stats.resolved_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_history_retention() {
+        let manager = AlertManager::new();
+        let alert = |id: &str, age_days: i64| Alert {
+            id: id.to_string(),
+            rule_name: format!("rule_{}", id),
+            message: "Test alert message".to_string(),
+            severity: AlertSeverity::Low,
+            program_id: Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now() - chrono::Duration::days(age_days),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        for (id, age_days) in [("old", 10), ("recent", 1)] {
+            manager.send_alert(alert(id, age_days)).await.unwrap();
+            let _ = manager.resolve_alert(id).await;
+        }
+        assert_eq!(manager.history(None).await.len(), 2);
+
+        manager.set_history_retention_days(Some(7)).await;
+        assert_eq!(manager.history_retention_days(), Some(7));
+        assert_eq!(manager.history(None).await.len(), 2);
+
+        manager.prune_history().await;
+        let history = manager.history(None).await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, "recent");
+        assert!(manager.audit_trail("old").is_empty());
+        assert!(!manager.audit_trail("recent").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recurring_alert_escalates() {
+        let manager = AlertManager::new();
+        let mut receiver = manager.subscribe();
+        let program_id = Pubkey::new_unique();
+
+        for i in 0..5 {
+            let alert = Alert {
+                id: format!("alert-{}", i),
+                rule_name: "test_rule".to_string(),
+                message: "Test alert message".to_string(),
+                severity: AlertSeverity::Medium,
+                program_id,
+                program_name: "Test Program".to_string(),
+                event_id: None,
+                metadata: HashMap::new(),
+                confidence: 0.8,
+                suggested_actions: Vec::new(),
+                timestamp: Utc::now(),
+                acknowledged: false,
+                resolved: false,
+                tags: Vec::new(),
+            };
+            manager.send_alert(alert).await.unwrap();
+        }
+
+        // The first firing is broadcast, the fifth re-broadcasts it escalated
+        let first = receiver.recv().await.unwrap();
+        assert_eq!(first.severity, AlertSeverity::Medium);
+        let escalated = receiver.recv().await.unwrap();
+        assert_eq!(escalated.id, "alert-0");
+        assert_eq!(escalated.severity, AlertSeverity::High);
+        assert_eq!(escalated.metadata["escalated_from"], "medium");
+        assert!(receiver.try_recv().is_err());
+
+        let active = manager.get_alert("alert-0").unwrap();
+        assert_eq!(active.severity, AlertSeverity::High);
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail() {
+        let manager = AlertManager::new();
+
+        let alert = Alert {
+            id: "test-alert".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test alert message".to_string(),
+            severity: AlertSeverity::High,
+            program_id: Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        manager.send_alert(alert).await.unwrap();
+        manager.record_transition(
+            "test-alert",
+            AlertTransition::Notified {
+                channel: "slack".to_string(),
+            },
+        );
+        manager
+            .acknowledge_alert_by("test-alert", Some("alice"))
+            .await
+            .unwrap();
+        manager.resolve_alert("test-alert").await.unwrap();
+
+        // Unknown alerts are not tracked
+        manager.record_transition("unknown", AlertTransition::Acknowledged { by: None });
+        assert!(manager.audit_trail("unknown").is_empty());
+
+        let transitions: Vec<AlertTransition> = manager
+            .audit_trail("test-alert")
+            .into_iter()
+            .map(|entry| entry.transition)
+            .collect();
+        assert_eq!(
+            transitions,
+            vec![
+                AlertTransition::Created {
+                    severity: AlertSeverity::High
+                },
+                AlertTransition::Notified {
+                    channel: "slack".to_string()
+                },
+                AlertTransition::Acknowledged {
+                    by: Some("alice".to_string())
+                },
+                AlertTransition::Resolved {
+                    by: None,
+                    automatic: false,
+                    feedback: None
+                },
+            ]
+        );
+    }
+
+    struct ChannelSink(tokio::sync::mpsc::UnboundedSender<Alert>);
+
+    #[async_trait::async_trait]
+    impl AlertSink for ChannelSink {
+        fn name(&self) -> &str {
+            "channel"
+        }
+
+        async fn write(&self, alert: &Alert) -> crate::sinks::SinkResult<()> {
+            let _ = self.0.send(alert.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alerts_are_exported_to_sinks() {
+        let manager = AlertManager::new();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        manager.add_sink(Arc::new(ChannelSink(sender))).await;
+
+        let alert = Alert {
+            id: "test-alert".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test alert message".to_string(),
+            severity: AlertSeverity::Low,
+            program_id: Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        manager.send_alert(alert).await.unwrap();
+
+        let exported = tokio::time::timeout(std::time::Duration::from_secs(1), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(exported.id, "test-alert");
+    }
+
+    #[tokio::test]
+    async fn test_search_alerts() {
+        let manager = AlertManager::new();
+        let oracle_program = Pubkey::new_unique();
+
+        for (id, rule_name, severity, minutes_ago) in [
+            ("a1", "oracle_deviation", AlertSeverity::Critical, 30),
+            ("a2", "large_transaction", AlertSeverity::High, 20),
+            ("a3", "large_transaction", AlertSeverity::Low, 10),
+        ] {
+            let alert = Alert {
+                id: id.to_string(),
+                rule_name: rule_name.to_string(),
+                message: format!("{} fired", rule_name),
+                severity,
+                program_id: if id == "a1" {
+                    oracle_program
+                } else {
+                    Pubkey::new_unique()
+                },
+                program_name: "Test Program".to_string(),
+                event_id: None,
+                metadata: HashMap::new(),
+                confidence: 0.9,
+                suggested_actions: Vec::new(),
+                timestamp: Utc::now() - chrono::Duration::minutes(minutes_ago),
+                acknowledged: false,
+                resolved: false,
+                tags: Vec::new(),
+            };
+            manager.send_alert(alert).await.unwrap();
+        }
+        manager.resolve_alert("a2").await.unwrap();
+
+        // Active and resolved alerts, newest first
+        let page = manager.search_alerts(&AlertFilter::default(), 0, 2).await;
+        assert_eq!(page.total, 3);
+        assert_eq!(
+            page.alerts
+                .iter()
+                .map(|a| a.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a3", "a2"]
+        );
+
+        let filter = AlertFilter {
+            text: Some("LARGE".to_string()),
+            resolved: Some(true),
+            ..Default::default()
+        };
+        let page = manager.search_alerts(&filter, 0, 10).await;
+        assert_eq!(page.total, 1);
+        assert_eq!(page.alerts[0].id, "a2");
+
+        let filter = AlertFilter {
+            program_ids: Some(vec![oracle_program]),
+            severities: Some(vec![AlertSeverity::Critical]),
+            time_range: Some(TimeRange {
+                start: Utc::now() - chrono::Duration::hours(1),
+                end: Utc::now(),
+            }),
+            ..Default::default()
+        };
+        let page = manager.search_alerts(&filter, 0, 10).await;
+        assert_eq!(page.total, 1);
+        assert_eq!(page.alerts[0].id, "a1");
+    }
+
+    #[tokio::test]
+    async fn test_query_alerts_sort_and_cursor() {
+        let manager = AlertManager::new();
+        let timestamp = Utc::now();
+
+        for (id, severity) in [
+            ("a1", AlertSeverity::High),
+            ("a2", AlertSeverity::Low),
+            ("a3", AlertSeverity::High),
+            ("a4", AlertSeverity::Critical),
+        ] {
+            let alert = Alert {
+                id: id.to_string(),
+                rule_name: format!("rule_{}", id),
+                message: "Test alert message".to_string(),
+                severity,
+                program_id: Pubkey::new_unique(),
+                program_name: "Test Program".to_string(),
+                event_id: None,
+                metadata: HashMap::new(),
+                confidence: 0.9,
+                suggested_actions: Vec::new(),
+                timestamp,
+                acknowledged: false,
+                resolved: false,
+                tags: Vec::new(),
+            };
+            manager.send_alert(alert).await.unwrap();
+        }
+        manager.resolve_alert("a3").await.unwrap();
+
+        assert_eq!(
+            AlertSort::parse("severity:asc").map(|s| s.descending),
+            Some(false)
+        );
+        assert_eq!(AlertSort::parse("rule").map(|s| s.descending), Some(false));
+        assert!(AlertSort::parse("severity:up").is_none());
+        assert!(AlertSort::parse("message").is_none());
+
+        // Equal severities and timestamps are ordered by ID
+        let ids = |page: &AlertPage| page.alerts.iter().map(|a| a.id.clone()).collect::<Vec<_>>();
+        let mut search = AlertSearch {
+            sort: AlertSort::parse("severity").unwrap(),
+            limit: 2,
+            ..Default::default()
+        };
+        let first = manager.query_alerts(&AlertFilter::default(), &search).await;
+        assert_eq!(first.total, 4);
+        assert_eq!(ids(&first), vec!["a4", "a3"]);
+
+        // A cursor keeps its place when an earlier alert is resolved meanwhile
+        manager.resolve_alert("a4").await.unwrap();
+        let cursor = first.next_cursor.unwrap();
+        search.after = AlertCursor::decode(&cursor.encode());
+        assert_eq!(search.after.as_ref(), Some(&cursor));
+        let second = manager.query_alerts(&AlertFilter::default(), &search).await;
+        assert_eq!(ids(&second), vec!["a1", "a2"]);
+        assert!(second.next_cursor.is_none());
+
+        assert!(AlertCursor::decode("not a cursor").is_none());
+    }
 }
@@ -0,0 +1,552 @@
+//! Rules for program upgrades and authority changes.
+
+use crate::rules::{AlertSeverity, Rule, RuleContext, RuleError, RuleResult};
+use crate::tvl::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use watchtower_subscriber::{AccountConfig, EventData, ProgramEvent, UpgradeKind};
+
+/// Upgradeable BPF loader program ID.
+const BPF_LOADER_UPGRADEABLE_ID: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+/// `UpgradeableLoaderInstruction::Upgrade` discriminant.
+const LOADER_UPGRADE: u32 = 3;
+
+/// `UpgradeableLoaderInstruction::SetAuthority` discriminant.
+const LOADER_SET_AUTHORITY: u32 = 4;
+
+/// `UpgradeableLoaderInstruction::SetAuthorityChecked` discriminant.
+const LOADER_SET_AUTHORITY_CHECKED: u32 = 7;
+
+/// `TokenInstruction::SetAuthority` discriminant.
+const TOKEN_SET_AUTHORITY: u8 = 6;
+
+/// `TokenInstruction::MintTo` discriminant.
+const TOKEN_MINT_TO: u8 = 7;
+
+/// `TokenInstruction::MintToChecked` discriminant.
+const TOKEN_MINT_TO_CHECKED: u8 = 14;
+
+/// Rule that raises a Critical alert on every program upgrade or upgrade authority change.
+///
+/// Consumes `ProgramUpgrade` events from the subscriber and also decodes raw
+/// upgradeable loader instructions when the loader itself is monitored.
+#[derive(Debug, Clone, Default)]
+pub struct ProgramUpgradeRule;
+
+impl ProgramUpgradeRule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Rule for ProgramUpgradeRule {
+    fn name(&self) -> &str {
+        "program_upgrade"
+    }
+
+    fn description(&self) -> &str {
+        "Detects program upgrades and upgrade authority changes"
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        AlertSeverity::Critical
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        let Some((program, kind, new_authority)) = upgrade_change(event) else {
+            return result;
+        };
+
+        let program_label = program
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| event.program_name.clone());
+
+        result.triggered = true;
+        result.confidence = 1.0;
+        result.message = Some(match kind {
+            UpgradeKind::Upgrade => format!("Program {} was upgraded", program_label),
+            UpgradeKind::AuthorityChange => format!(
+                "Upgrade authority of {} changed to {}",
+                program_label,
+                new_authority.map(|a| a.to_string()).unwrap_or_default()
+            ),
+            UpgradeKind::AuthorityRevoked => format!(
+                "Upgrade authority of {} was set to None; the program is now immutable",
+                program_label
+            ),
+        });
+
+        result.metadata.insert(
+            "change".to_string(),
+            serde_json::to_value(kind).unwrap_or_default(),
+        );
+        if let Some(program) = program {
+            result
+                .metadata
+                .insert("program".to_string(), program.to_string().into());
+        }
+        if let Some(authority) = new_authority {
+            result
+                .metadata
+                .insert("new_authority".to_string(), authority.to_string().into());
+        }
+
+        let actions: &[&str] = match kind {
+            UpgradeKind::Upgrade => &[
+                "Verify the upgrade was planned and signed by the expected authority",
+                "Diff the deployed bytecode against the audited release",
+                "Pause integrations until the new code is verified",
+            ],
+            UpgradeKind::AuthorityChange => &[
+                "Confirm the new upgrade authority belongs to the team or multisig",
+                "Treat an unexpected authority change as a key compromise",
+            ],
+            UpgradeKind::AuthorityRevoked => &[
+                "Confirm making the program immutable was intended",
+                "Plan migrations for any pending fixes, as the program can no longer be upgraded",
+            ],
+        };
+        result
+            .suggested_actions
+            .extend(actions.iter().map(|a| a.to_string()));
+
+        result
+    }
+}
+
+/// Extract an upgrade change from a subscriber event or a raw loader instruction.
+fn upgrade_change(event: &ProgramEvent) -> Option<(Option<Pubkey>, UpgradeKind, Option<Pubkey>)> {
+    match &event.data {
+        EventData::ProgramUpgrade {
+            program,
+            kind,
+            new_authority,
+        } => Some((*program, *kind, *new_authority)),
+        EventData::Instruction { data, accounts, .. }
+            if Pubkey::from_str(BPF_LOADER_UPGRADEABLE_ID).ok() == Some(event.program_id) =>
+        {
+            let discriminant = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+            match discriminant {
+                LOADER_UPGRADE => Some((accounts.get(1).copied(), UpgradeKind::Upgrade, None)),
+                LOADER_SET_AUTHORITY | LOADER_SET_AUTHORITY_CHECKED => match accounts.get(2) {
+                    Some(authority) => Some((None, UpgradeKind::AuthorityChange, Some(*authority))),
+                    None => Some((None, UpgradeKind::AuthorityRevoked, None)),
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Configuration for a `MintAuthorityRule` and `SupplyInflationRule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintAuthorityConfig {
+    /// Mints to watch
+    pub mints: Vec<WatchedMint>,
+
+    /// Window over which supply growth is measured, in seconds
+    #[serde(default = "default_supply_window")]
+    pub supply_window_seconds: u64,
+}
+
+/// A mint watched for authority changes and supply inflation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedMint {
+    /// Mint address
+    pub mint: String,
+
+    /// Display name (e.g. the token symbol)
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Largest expected single `MintTo` in raw units; any mint alerts when unset
+    #[serde(default)]
+    pub max_mint_amount: Option<u64>,
+
+    /// Absolute supply cap in raw units
+    #[serde(default)]
+    pub max_supply: Option<u64>,
+
+    /// Largest expected supply growth within the supply window, in percent
+    #[serde(default)]
+    pub max_supply_growth_pct: Option<f64>,
+}
+
+impl WatchedMint {
+    /// Whether the mint's supply is tracked via account subscriptions.
+    pub fn tracks_supply(&self) -> bool {
+        self.max_supply.is_some() || self.max_supply_growth_pct.is_some()
+    }
+}
+
+impl MintAuthorityConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> Result<(), RuleError> {
+        for watched in &self.mints {
+            Pubkey::from_str(&watched.mint).map_err(|_| {
+                RuleError::Configuration(format!("Invalid mint address: {}", watched.mint))
+            })?;
+
+            if watched.max_supply_growth_pct.is_some_and(|pct| pct <= 0.0) {
+                return Err(RuleError::Configuration(format!(
+                    "Supply growth limit of {} must be greater than 0",
+                    watched.mint
+                )));
+            }
+        }
+
+        if self.supply_window_seconds == 0 {
+            return Err(RuleError::Configuration(
+                "Supply window must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Accounts the subscriber must watch to track supply of the configured mints.
+    pub fn supply_accounts(&self) -> Vec<AccountConfig> {
+        self.mints
+            .iter()
+            .filter(|watched| watched.tracks_supply())
+            .filter_map(|watched| {
+                let id = Pubkey::from_str(&watched.mint).ok()?;
+                Some(AccountConfig {
+                    id,
+                    name: watched
+                        .label
+                        .clone()
+                        .unwrap_or_else(|| watched.mint.clone()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Token authority decoded from a `SetAuthority` instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenAuthorityType {
+    MintTokens,
+    FreezeAccount,
+}
+
+/// Change to a watched mint decoded from an SPL Token instruction.
+#[derive(Debug, Clone, PartialEq)]
+enum MintChange {
+    Authority {
+        authority_type: TokenAuthorityType,
+        new_authority: Option<Pubkey>,
+    },
+    MintTo {
+        amount: u64,
+    },
+}
+
+/// Rule that detects mint/freeze authority changes and unexpected `MintTo`
+/// supply inflation on configured mints.
+#[derive(Debug)]
+pub struct MintAuthorityRule {
+    /// Watched mints keyed by address
+    mints: HashMap<Pubkey, WatchedMint>,
+}
+
+impl MintAuthorityRule {
+    /// Create a rule from its configuration, skipping invalid mint addresses.
+    pub fn new(config: &MintAuthorityConfig) -> Self {
+        let mints = config
+            .mints
+            .iter()
+            .filter_map(|watched| {
+                Pubkey::from_str(&watched.mint)
+                    .ok()
+                    .map(|mint| (mint, watched.clone()))
+            })
+            .collect();
+
+        Self { mints }
+    }
+}
+
+#[async_trait]
+impl Rule for MintAuthorityRule {
+    fn name(&self) -> &str {
+        "mint_authority"
+    }
+
+    fn description(&self) -> &str {
+        "Detects mint/freeze authority changes and unexpected supply inflation"
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        AlertSeverity::Critical
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        let Some((mint, change)) = mint_change(event) else {
+            return result;
+        };
+        let Some(watched) = self.mints.get(&mint) else {
+            return result;
+        };
+
+        let label = watched.label.clone().unwrap_or_else(|| mint.to_string());
+        result
+            .metadata
+            .insert("mint".to_string(), mint.to_string().into());
+
+        match change {
+            MintChange::Authority {
+                authority_type,
+                new_authority,
+            } => {
+                let authority_name = match authority_type {
+                    TokenAuthorityType::MintTokens => "Mint",
+                    TokenAuthorityType::FreezeAccount => "Freeze",
+                };
+
+                result.triggered = true;
+                result.confidence = 1.0;
+                result.message = Some(match new_authority {
+                    Some(authority) => format!(
+                        "{} authority of {} changed to {}",
+                        authority_name, label, authority
+                    ),
+                    None => format!("{} authority of {} was revoked", authority_name, label),
+                });
+                result.metadata.insert(
+                    "authority_type".to_string(),
+                    serde_json::to_value(authority_type).unwrap_or_default(),
+                );
+                if let Some(authority) = new_authority {
+                    result
+                        .metadata
+                        .insert("new_authority".to_string(), authority.to_string().into());
+                }
+                result
+                    .suggested_actions
+                    .push("Confirm the authority change with the token issuer".to_string());
+                result.suggested_actions.push(
+                    "Treat an unknown new authority as a potential rug pull and warn users"
+                        .to_string(),
+                );
+            }
+            MintChange::MintTo { amount } => {
+                if watched.max_mint_amount.is_some_and(|max| amount <= max) {
+                    return result;
+                }
+
+                result.triggered = true;
+                result.severity = AlertSeverity::High;
+                result.message = Some(match watched.max_mint_amount {
+                    Some(max) => format!(
+                        "Unexpected mint of {} {} (max expected: {})",
+                        amount, label, max
+                    ),
+                    None => format!("Unexpected mint of {} {}", amount, label),
+                });
+                result.confidence = match watched.max_mint_amount {
+                    Some(max) if max > 0 => (amount as f64 / max as f64 / 10.0).clamp(0.5, 1.0),
+                    _ => 0.9,
+                };
+                result.metadata.insert("amount".to_string(), amount.into());
+                result
+                    .suggested_actions
+                    .push("Verify the mint was authorized by the issuer".to_string());
+                result
+                    .suggested_actions
+                    .push("Check for newly minted tokens being sold into pools".to_string());
+            }
+        }
+
+        result
+    }
+}
+
+/// Decode a mint change from an SPL Token instruction event.
+fn mint_change(event: &ProgramEvent) -> Option<(Pubkey, MintChange)> {
+    let EventData::Instruction { data, accounts, .. } = &event.data else {
+        return None;
+    };
+
+    let program_id = event.program_id.to_string();
+    if program_id != TOKEN_PROGRAM_ID && program_id != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+
+    let mint = *accounts.first()?;
+    let change = match *data.first()? {
+        TOKEN_SET_AUTHORITY => {
+            let authority_type = match *data.get(1)? {
+                0 => TokenAuthorityType::MintTokens,
+                1 => TokenAuthorityType::FreezeAccount,
+                _ => return None, // Account owner/close authorities are not mint-level
+            };
+            let new_authority = match *data.get(2)? {
+                0 => None,
+                _ => Some(Pubkey::try_from(data.get(3..35)?).ok()?),
+            };
+            MintChange::Authority {
+                authority_type,
+                new_authority,
+            }
+        }
+        TOKEN_MINT_TO | TOKEN_MINT_TO_CHECKED => MintChange::MintTo {
+            amount: u64::from_le_bytes(data.get(1..9)?.try_into().ok()?),
+        },
+        _ => return None,
+    };
+
+    Some((mint, change))
+}
+
+impl Default for MintAuthorityConfig {
+    fn default() -> Self {
+        Self {
+            mints: Vec::new(),
+            supply_window_seconds: default_supply_window(),
+        }
+    }
+}
+
+// Default value functions
+fn default_supply_window() -> u64 {
+    3600 // 1 hour
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_subscriber::EventType;
+
+    #[tokio::test]
+    async fn test_upgrade_event_triggers_critical() {
+        let program = Pubkey::new_unique();
+        let event = ProgramEvent::new(
+            program,
+            "Test Program".to_string(),
+            EventType::ProgramUpgrade,
+            EventData::ProgramUpgrade {
+                program: Some(program),
+                kind: UpgradeKind::AuthorityRevoked,
+                new_authority: None,
+            },
+        );
+
+        let result = ProgramUpgradeRule::new()
+            .evaluate(&event, &RuleContext::default())
+            .await;
+        assert!(result.triggered);
+        assert_eq!(result.severity, AlertSeverity::Critical);
+        assert_eq!(result.metadata["change"], "authority_revoked");
+        assert!(!result.suggested_actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_loader_upgrade_instruction() {
+        let program = Pubkey::new_unique();
+        let event = ProgramEvent::new(
+            Pubkey::from_str(BPF_LOADER_UPGRADEABLE_ID).unwrap(),
+            "BPF Loader".to_string(),
+            EventType::Instruction,
+            EventData::Instruction {
+                index: 0,
+                data: LOADER_UPGRADE.to_le_bytes().to_vec(),
+                accounts: vec![Pubkey::new_unique(), program, Pubkey::new_unique()],
+                success: true,
+            },
+        );
+
+        let result = ProgramUpgradeRule::new()
+            .evaluate(&event, &RuleContext::default())
+            .await;
+        assert!(result.triggered);
+        assert_eq!(result.metadata["program"], program.to_string());
+    }
+
+    fn token_instruction(mint: Pubkey, data: Vec<u8>) -> ProgramEvent {
+        ProgramEvent::new(
+            Pubkey::from_str(TOKEN_PROGRAM_ID).unwrap(),
+            "SPL Token".to_string(),
+            EventType::Instruction,
+            EventData::Instruction {
+                index: 0,
+                data,
+                accounts: vec![mint, Pubkey::new_unique()],
+                success: true,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_mint_authority_rule() {
+        let mint = Pubkey::new_unique();
+        let rule = MintAuthorityRule::new(&MintAuthorityConfig {
+            mints: vec![WatchedMint {
+                mint: mint.to_string(),
+                label: Some("TEST".to_string()),
+                max_mint_amount: Some(1_000),
+                max_supply: None,
+                max_supply_growth_pct: None,
+            }],
+            ..Default::default()
+        });
+        let context = RuleContext::default();
+
+        // Freeze authority handed to a new key
+        let new_authority = Pubkey::new_unique();
+        let mut data = vec![TOKEN_SET_AUTHORITY, 1, 1];
+        data.extend_from_slice(new_authority.as_ref());
+        let result = rule
+            .evaluate(&token_instruction(mint, data), &context)
+            .await;
+        assert!(result.triggered);
+        assert_eq!(result.severity, AlertSeverity::Critical);
+        assert_eq!(result.metadata["authority_type"], "freeze_account");
+        assert_eq!(result.metadata["new_authority"], new_authority.to_string());
+
+        // Mints within the expected size are fine, larger ones alert
+        let mint_to = |amount: u64| {
+            let mut data = vec![TOKEN_MINT_TO];
+            data.extend_from_slice(&amount.to_le_bytes());
+            token_instruction(mint, data)
+        };
+        assert!(!rule.evaluate(&mint_to(500), &context).await.triggered);
+        let result = rule.evaluate(&mint_to(5_000), &context).await;
+        assert!(result.triggered);
+        assert_eq!(result.severity, AlertSeverity::High);
+
+        // Unwatched mints are ignored
+        let other = token_instruction(Pubkey::new_unique(), vec![TOKEN_SET_AUTHORITY, 0, 0]);
+        assert!(!rule.evaluate(&other, &context).await.triggered);
+    }
+}
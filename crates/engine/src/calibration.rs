@@ -0,0 +1,172 @@
+//! Confidence calibration from operator feedback.
+
+use crate::engine::RuleStats;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for confidence calibration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationConfig {
+    /// Whether alert confidence is adjusted by rule precision
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Feedback count at which precision and the rule's own confidence
+    /// weigh equally; higher values make calibration more conservative
+    #[serde(default = "default_prior_weight")]
+    pub prior_weight: f64,
+}
+
+/// Operator verdict on an alert, recorded when it is resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertFeedback {
+    /// The alert flagged a real issue
+    TruePositive,
+
+    /// The alert was noise
+    FalsePositive,
+}
+
+/// Historical precision of a rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulePrecision {
+    /// Rule name
+    pub rule_name: String,
+
+    /// Alerts confirmed as real issues
+    pub true_positives: u64,
+
+    /// Alerts reported as noise
+    pub false_positives: u64,
+
+    /// Share of reviewed alerts that were real, if any were reviewed
+    pub precision: Option<f64>,
+
+    /// Weight of the precision in calibrated confidence (0.0 to 1.0)
+    pub calibration_weight: f64,
+}
+
+/// Adjusts rule confidence towards each rule's observed precision.
+///
+/// Calibrated confidence blends the rule's own score with its precision,
+/// weighting precision by `n / (n + prior_weight)` for `n` reviewed alerts,
+/// so rules without feedback keep their raw confidence.
+#[derive(Debug, Clone)]
+pub struct ConfidenceCalibrator {
+    config: CalibrationConfig,
+}
+
+impl ConfidenceCalibrator {
+    pub fn new(config: CalibrationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Calibrate a rule's raw confidence against its feedback statistics.
+    pub fn calibrate(&self, raw_confidence: f64, stats: &RuleStats) -> f64 {
+        if !self.config.enabled {
+            return raw_confidence;
+        }
+
+        match stats.precision() {
+            Some(precision) => {
+                let weight = self.weight(stats);
+                ((1.0 - weight) * raw_confidence + weight * precision).clamp(0.0, 1.0)
+            }
+            None => raw_confidence,
+        }
+    }
+
+    /// Precision statistics for a rule.
+    pub fn precision_stats(&self, rule_name: &str, stats: &RuleStats) -> RulePrecision {
+        RulePrecision {
+            rule_name: rule_name.to_string(),
+            true_positives: stats.true_positives,
+            false_positives: stats.false_positives,
+            precision: stats.precision(),
+            calibration_weight: if self.config.enabled {
+                self.weight(stats)
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Weight of the precision given the amount of feedback.
+    fn weight(&self, stats: &RuleStats) -> f64 {
+        let reviewed = (stats.true_positives + stats.false_positives) as f64;
+        if reviewed == 0.0 {
+            return 0.0;
+        }
+        reviewed / (reviewed + self.config.prior_weight.max(0.0))
+    }
+}
+
+impl RuleStats {
+    /// Share of reviewed triggers that were true positives, if any were reviewed.
+    pub fn precision(&self) -> Option<f64> {
+        let reviewed = self.true_positives + self.false_positives;
+        (reviewed > 0).then(|| self.true_positives as f64 / reviewed as f64)
+    }
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            prior_weight: default_prior_weight(),
+        }
+    }
+}
+
+// Default value functions
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_prior_weight() -> f64 {
+    10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_follows_precision() {
+        let calibrator = ConfidenceCalibrator::new(CalibrationConfig::default());
+
+        // No feedback keeps the rule's own confidence
+        let stats = RuleStats::default();
+        assert_eq!(calibrator.calibrate(0.9, &stats), 0.9);
+        assert_eq!(calibrator.precision_stats("rule", &stats).precision, None);
+
+        // Mostly false positives pull confidence down, more so with more feedback
+        let noisy = RuleStats {
+            true_positives: 1,
+            false_positives: 9,
+            ..Default::default()
+        };
+        let calibrated = calibrator.calibrate(0.9, &noisy);
+        assert!((calibrated - 0.5).abs() < 1e-9);
+
+        let very_noisy = RuleStats {
+            true_positives: 10,
+            false_positives: 90,
+            ..Default::default()
+        };
+        assert!(calibrator.calibrate(0.9, &very_noisy) < calibrated);
+
+        // Reliable rules gain confidence
+        let reliable = RuleStats {
+            true_positives: 10,
+            ..Default::default()
+        };
+        assert!(calibrator.calibrate(0.6, &reliable) > 0.6);
+
+        let disabled = ConfidenceCalibrator::new(CalibrationConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        assert_eq!(disabled.calibrate(0.9, &noisy), 0.9);
+    }
+}
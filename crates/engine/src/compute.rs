@@ -0,0 +1,328 @@
+//! Compute-unit exhaustion detection.
+
+use crate::rules::{AlertSeverity, Rule, RuleContext, RuleError, RuleResult};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use watchtower_subscriber::{EventData, ProgramEvent};
+
+/// Event metadata key carrying a transaction error.
+pub const ERROR_METADATA_KEY: &str = "error";
+
+/// Event metadata key carrying a transaction's compute-unit limit.
+pub const COMPUTE_UNIT_LIMIT_METADATA_KEY: &str = "compute_unit_limit";
+
+/// Messages that mark a transaction as having run out of compute units.
+const EXHAUSTION_MARKERS: [&str; 3] = [
+    "exceeded CUs meter",
+    "Computational budget exceeded",
+    "ComputationalBudgetExceeded",
+];
+
+/// Configuration for a `ComputeExhaustionRule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComputeExhaustionConfig {
+    /// Window in which exhaustion signals are counted, in seconds
+    #[serde(default = "default_window")]
+    pub window_seconds: u64,
+
+    /// Signals within the window that trigger an alert
+    #[serde(default = "default_min_occurrences")]
+    pub min_occurrences: usize,
+
+    /// Consumption, as a percentage of the limit, counted as approaching it
+    #[serde(default = "default_near_limit_pct")]
+    pub near_limit_pct: f64,
+
+    /// Severity of generated alerts
+    #[serde(default = "default_severity")]
+    pub severity: AlertSeverity,
+}
+
+impl ComputeExhaustionConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> Result<(), RuleError> {
+        if self.window_seconds == 0 || self.min_occurrences == 0 {
+            return Err(RuleError::Configuration(
+                "Compute exhaustion window and occurrences must be greater than 0".to_string(),
+            ));
+        }
+
+        if !(0.0..=100.0).contains(&self.near_limit_pct) {
+            return Err(RuleError::Configuration(format!(
+                "Compute exhaustion near_limit_pct must be between 0 and 100, got {}",
+                self.near_limit_pct
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// A compute budget signal extracted from one event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComputeSignal {
+    /// Execution failed after running out of compute units
+    Exhausted,
+    /// Execution consumed this percentage of its limit
+    Usage(f64),
+}
+
+/// Rule that triggers when a program repeatedly runs out of, or comes close
+/// to, its compute budget.
+///
+/// Signals come from `consumed X of Y compute units` and out-of-compute log
+/// lines, and from transaction events carrying an `error` or a
+/// `compute_unit_limit` in their metadata.
+#[derive(Debug, Clone)]
+pub struct ComputeExhaustionRule {
+    config: ComputeExhaustionConfig,
+}
+
+impl ComputeExhaustionRule {
+    pub fn new(config: &ComputeExhaustionConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    /// Whether a signal counts towards the pattern.
+    fn counts(&self, signal: ComputeSignal) -> bool {
+        match signal {
+            ComputeSignal::Exhausted => true,
+            ComputeSignal::Usage(pct) => pct >= self.config.near_limit_pct,
+        }
+    }
+}
+
+#[async_trait]
+impl Rule for ComputeExhaustionRule {
+    fn name(&self) -> &str {
+        "compute_exhaustion"
+    }
+
+    fn description(&self) -> &str {
+        "Detects programs repeatedly exhausting or approaching their compute budget"
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        self.config.severity
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        // Only evaluate when the current event is itself a signal
+        if !compute_signal(event).is_some_and(|signal| self.counts(signal)) {
+            return result;
+        }
+
+        let window_start =
+            event.timestamp - chrono::Duration::seconds(self.config.window_seconds as i64);
+        let mut exhausted = 0;
+        let mut near_limit = 0;
+        let mut max_usage_pct: f64 = 0.0;
+        let signals = context
+            .recent_events
+            .iter()
+            .filter(|e| e.timestamp >= window_start && e.id != event.id)
+            .chain(std::iter::once(event))
+            .filter_map(compute_signal)
+            .filter(|signal| self.counts(*signal));
+        for signal in signals {
+            match signal {
+                ComputeSignal::Exhausted => exhausted += 1,
+                ComputeSignal::Usage(pct) => {
+                    near_limit += 1;
+                    max_usage_pct = max_usage_pct.max(pct);
+                }
+            }
+        }
+
+        let total = exhausted + near_limit;
+        if total >= self.config.min_occurrences {
+            result.triggered = true;
+            result.message = Some(format!(
+                "{} compute budget signals in {}s: {} out-of-compute failures, {} near the limit",
+                total, self.config.window_seconds, exhausted, near_limit
+            ));
+            // Outright failures are stronger evidence than near misses
+            result.confidence = 0.6 + 0.4 * exhausted as f64 / total as f64;
+            result
+                .metadata
+                .insert("exhausted_count".to_string(), exhausted.into());
+            result
+                .metadata
+                .insert("near_limit_count".to_string(), near_limit.into());
+            if near_limit > 0 {
+                result
+                    .metadata
+                    .insert("max_usage_pct".to_string(), max_usage_pct.into());
+            }
+            result
+                .suggested_actions
+                .push("Check for congestion or griefing transactions".to_string());
+            result
+                .suggested_actions
+                .push("Review recent changes to compute-heavy instructions".to_string());
+        }
+
+        result
+    }
+}
+
+/// Extract a compute budget signal from an event.
+fn compute_signal(event: &ProgramEvent) -> Option<ComputeSignal> {
+    match &event.data {
+        EventData::LogEntry { message, .. } => {
+            if EXHAUSTION_MARKERS.iter().any(|m| message.contains(m)) {
+                return Some(ComputeSignal::Exhausted);
+            }
+            parse_consumed_log(message).map(|(used, limit)| usage(used, limit))
+        }
+        EventData::Transaction {
+            success,
+            compute_units,
+            ..
+        } => {
+            let error = event
+                .metadata
+                .get(ERROR_METADATA_KEY)
+                .map(|e| e.to_string())
+                .unwrap_or_default();
+            if !success && EXHAUSTION_MARKERS.iter().any(|m| error.contains(m)) {
+                return Some(ComputeSignal::Exhausted);
+            }
+            let limit = event
+                .metadata
+                .get(COMPUTE_UNIT_LIMIT_METADATA_KEY)
+                .and_then(|v| v.as_u64())?;
+            Some(usage((*compute_units)?, limit))
+        }
+        _ => None,
+    }
+}
+
+/// Signal for `used` of `limit` compute units consumed.
+fn usage(used: u64, limit: u64) -> ComputeSignal {
+    if limit == 0 {
+        return ComputeSignal::Usage(0.0);
+    }
+    ComputeSignal::Usage(used as f64 / limit as f64 * 100.0)
+}
+
+/// Parse `Program <id> consumed <used> of <limit> compute units`.
+fn parse_consumed_log(message: &str) -> Option<(u64, u64)> {
+    let (_, rest) = message.split_once(" consumed ")?;
+    let mut parts = rest.split_whitespace();
+    let used = parts.next()?.parse().ok()?;
+    if parts.next()? != "of" {
+        return None;
+    }
+    let limit = parts.next()?.parse().ok()?;
+    Some((used, limit))
+}
+
+impl Default for ComputeExhaustionConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: default_window(),
+            min_occurrences: default_min_occurrences(),
+            near_limit_pct: default_near_limit_pct(),
+            severity: default_severity(),
+        }
+    }
+}
+
+// Default value functions
+fn default_window() -> u64 {
+    300 // 5 minutes
+}
+
+fn default_min_occurrences() -> usize {
+    5
+}
+
+fn default_near_limit_pct() -> f64 {
+    95.0
+}
+
+fn default_severity() -> AlertSeverity {
+    AlertSeverity::High
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use watchtower_subscriber::EventType;
+
+    fn log(message: &str) -> ProgramEvent {
+        ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: message.to_string(),
+                level: None,
+                instruction_index: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_compute_signals() {
+        assert_eq!(
+            compute_signal(&log(
+                "Program 11111111111111111111111111111111 consumed 199000 of 200000 compute units"
+            )),
+            Some(ComputeSignal::Usage(99.5))
+        );
+        assert_eq!(
+            compute_signal(&log(
+                "Program 11111111111111111111111111111111 failed: exceeded CUs meter at BPF instruction"
+            )),
+            Some(ComputeSignal::Exhausted)
+        );
+        assert_eq!(compute_signal(&log("Program log: hello")), None);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_exhaustion_triggers() {
+        let rule = ComputeExhaustionRule::new(&ComputeExhaustionConfig {
+            min_occurrences: 3,
+            ..Default::default()
+        });
+
+        let history = vec![
+            log("Program 11111111111111111111111111111111 consumed 199500 of 200000 compute units"),
+            log("Program 11111111111111111111111111111111 consumed 50000 of 200000 compute units"),
+            log("Program 11111111111111111111111111111111 failed: exceeded CUs meter at BPF instruction"),
+        ];
+        let context = RuleContext {
+            recent_events: history.into(),
+            ..Default::default()
+        };
+
+        let benign = log("Program log: Instruction: Swap");
+        assert!(!rule.evaluate(&benign, &context).await.triggered);
+
+        let event = log(
+            "Program 11111111111111111111111111111111 failed: exceeded CUs meter at BPF instruction",
+        );
+        let result = rule.evaluate(&event, &context).await;
+        assert!(result.triggered);
+        assert_eq!(result.metadata["exhausted_count"], 2);
+        assert_eq!(result.metadata["near_limit_count"], 1);
+    }
+}
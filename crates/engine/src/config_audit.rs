@@ -0,0 +1,320 @@
+//! Audit log of configuration changes, optionally persisted to disk.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, VecDeque};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// A changed configuration value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// Dotted path of the value, e.g. `monitoring_settings.alert_retention_days`
+    pub field: String,
+
+    /// Value before the change, `null` if it was added
+    pub before: Value,
+
+    /// Value after the change, `null` if it was removed
+    pub after: Value,
+}
+
+/// A recorded configuration update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigAuditEntry {
+    /// Log-assigned identifier
+    pub id: u64,
+
+    /// When the update was applied
+    pub timestamp: DateTime<Utc>,
+
+    /// Who made the update
+    pub actor: String,
+
+    /// Values changed by the update
+    pub changes: Vec<ConfigChange>,
+}
+
+/// Criteria for querying audit entries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigAuditQuery {
+    /// Only updates by this actor
+    pub actor: Option<String>,
+
+    /// Only updates changing this field or a field below it
+    pub field: Option<String>,
+
+    /// Only updates at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Maximum number of entries returned
+    pub limit: Option<usize>,
+}
+
+/// Errors that can occur while loading or persisting audit entries.
+#[derive(Error, Debug)]
+pub enum ConfigAuditError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type ConfigAuditResult<T> = Result<T, ConfigAuditError>;
+
+/// Log of configuration updates.
+///
+/// The most recent entries are kept in memory for queries. When backed by a
+/// file, every entry is appended as a JSON line and the file is never
+/// compacted, so it holds the complete history.
+#[derive(Debug)]
+pub struct ConfigAuditLog {
+    /// File the entries are appended to
+    path: Option<PathBuf>,
+
+    /// Recent entries in arrival order
+    entries: Mutex<VecDeque<ConfigAuditEntry>>,
+
+    /// Maximum number of entries kept in memory
+    capacity: usize,
+
+    /// Next entry identifier
+    next_id: AtomicU64,
+}
+
+impl ConfigAuditLog {
+    /// Create an in-memory log that is not persisted.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self {
+            path: None,
+            entries: Mutex::new(VecDeque::new()),
+            capacity,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Open a log backed by `path`, loading the most recent entries of a previous run.
+    pub fn open(path: impl Into<PathBuf>, capacity: usize) -> ConfigAuditResult<Self> {
+        let path = path.into();
+        let mut entries = VecDeque::new();
+        let mut next_id = 1;
+
+        if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                match serde_json::from_str::<ConfigAuditEntry>(line) {
+                    Ok(entry) => {
+                        next_id = next_id.max(entry.id + 1);
+                        if entries.len() >= capacity.max(1) {
+                            entries.pop_front();
+                        }
+                        entries.push_back(entry);
+                    }
+                    Err(e) => warn!("Skipping malformed config audit entry: {}", e),
+                }
+            }
+        }
+
+        debug!(
+            "Loaded {} config audit entries from {}",
+            entries.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path: Some(path),
+            entries: Mutex::new(entries),
+            capacity,
+            next_id: AtomicU64::new(next_id),
+        })
+    }
+
+    /// Path of the backing file, if persisted.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Record an update, returning the new entry's identifier.
+    pub fn record(&self, actor: &str, changes: Vec<ConfigChange>) -> ConfigAuditResult<u64> {
+        let entry = ConfigAuditEntry {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            timestamp: Utc::now(),
+            actor: actor.to_string(),
+            changes,
+        };
+        let id = entry.id;
+
+        let mut entries = self.lock();
+        self.persist(&entry)?;
+        while entries.len() >= self.capacity.max(1) {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+
+        Ok(id)
+    }
+
+    /// Entries matching `query`, newest first.
+    pub fn query(&self, query: &ConfigAuditQuery) -> Vec<ConfigAuditEntry> {
+        self.lock()
+            .iter()
+            .rev()
+            .filter(|entry| {
+                query.actor.as_ref().map_or(true, |a| &entry.actor == a)
+                    && query.field.as_ref().map_or(true, |field| {
+                        entry.changes.iter().any(|change| {
+                            change.field == *field
+                                || change.field.starts_with(&format!("{}.", field))
+                        })
+                    })
+                    && query.since.map_or(true, |since| entry.timestamp >= since)
+            })
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of entries held in memory.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no entries are held in memory.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Append an entry to the backing file.
+    fn persist(&self, entry: &ConfigAuditEntry) -> ConfigAuditResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ConfigAuditEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Values that differ between two configurations.
+///
+/// Objects are compared key by key; arrays and other values are compared as a whole.
+pub fn config_changes(before: &Value, after: &Value) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    collect_changes("", before, after, &mut changes);
+    changes
+}
+
+fn collect_changes(field: &str, before: &Value, after: &Value, changes: &mut Vec<ConfigChange>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+            for key in keys {
+                let path = if field.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", field, key)
+                };
+                collect_changes(
+                    &path,
+                    before.get(key).unwrap_or(&Value::Null),
+                    after.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        _ if before != after => changes.push(ConfigChange {
+            field: field.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_changes() {
+        let before = json!({
+            "monitoring_settings": { "alert_retention_days": 30, "enable_real_time_alerts": true },
+            "notification_channels": [{ "name": "slack", "enabled": true }],
+        });
+        let after = json!({
+            "monitoring_settings": { "alert_retention_days": 7, "enable_real_time_alerts": true },
+            "notification_channels": [{ "name": "slack", "enabled": false }],
+        });
+
+        let changes = config_changes(&before, &after);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "monitoring_settings.alert_retention_days");
+        assert_eq!(changes[0].before, json!(30));
+        assert_eq!(changes[0].after, json!(7));
+        assert_eq!(changes[1].field, "notification_channels");
+        assert!(config_changes(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_config_audit_log_query_and_reopen() {
+        let dir = std::env::temp_dir().join(format!(
+            "watchtower-config-audit-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let path = dir.join("config_audit.jsonl");
+        let change = |field: &str| ConfigChange {
+            field: field.to_string(),
+            before: json!(1),
+            after: json!(2),
+        };
+
+        let log = ConfigAuditLog::open(&path, 2).unwrap();
+        log.record("dashboard:alice", vec![change("monitoring_settings.a")])
+            .unwrap();
+        log.record("dashboard:bob", vec![change("notification_channels")])
+            .unwrap();
+        log.record("dashboard:alice", vec![change("monitoring_settings.b")])
+            .unwrap();
+
+        // Memory keeps the newest entries while the file keeps all of them
+        assert_eq!(log.len(), 2);
+        let by_alice = log.query(&ConfigAuditQuery {
+            actor: Some("dashboard:alice".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_alice.len(), 1);
+        assert_eq!(by_alice[0].id, 3);
+        let settings = log.query(&ConfigAuditQuery {
+            field: Some("monitoring_settings".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(settings.len(), 1);
+
+        let reopened = ConfigAuditLog::open(&path, 10).unwrap();
+        assert_eq!(reopened.len(), 3);
+        let id = reopened
+            .record("token:ci", vec![change("notification_channels")])
+            .unwrap();
+        assert_eq!(id, 4);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,294 @@
+//! Bounded dead-letter queue for failed rule evaluations and alert dispatches.
+
+use crate::alerts::Alert;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::warn;
+use watchtower_subscriber::ProgramEvent;
+
+/// Configuration for the dead-letter queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterConfig {
+    /// Whether failures are captured
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Maximum number of entries kept; the oldest entry is dropped when full
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+}
+
+/// Why an event ended up in the dead-letter queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadLetterReason {
+    /// Rule evaluation exceeded its timeout
+    RuleTimeout,
+
+    /// Rule evaluation panicked
+    RulePanic,
+
+    /// The rule triggered but the alert could not be dispatched
+    AlertDispatch,
+}
+
+impl DeadLetterReason {
+    /// Reason as a metric label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeadLetterReason::RuleTimeout => "rule_timeout",
+            DeadLetterReason::RulePanic => "rule_panic",
+            DeadLetterReason::AlertDispatch => "alert_dispatch",
+        }
+    }
+}
+
+/// A failed rule evaluation or alert dispatch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Queue-assigned identifier
+    pub id: u64,
+
+    /// Failure category
+    pub reason: DeadLetterReason,
+
+    /// Rule that failed or produced the alert
+    pub rule_name: String,
+
+    /// Event being processed
+    pub event: ProgramEvent,
+
+    /// Alert that could not be dispatched, for `AlertDispatch` failures
+    pub alert: Option<Alert>,
+
+    /// Last error message
+    pub error: String,
+
+    /// Number of failed attempts, including retries
+    pub attempts: u32,
+
+    /// When the first attempt failed
+    pub first_failed_at: DateTime<Utc>,
+
+    /// When the latest attempt failed
+    pub last_failed_at: DateTime<Utc>,
+}
+
+/// Bounded queue of failures kept for inspection and retry.
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    /// Entries in arrival order
+    entries: Mutex<VecDeque<DeadLetter>>,
+
+    /// Maximum number of entries
+    capacity: usize,
+
+    /// Next entry identifier
+    next_id: AtomicU64,
+
+    /// Entries dropped because the queue was full
+    dropped: AtomicU64,
+}
+
+impl DeadLetterQueue {
+    /// Create an empty queue holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            capacity,
+            next_id: AtomicU64::new(1),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Capture a failure, returning the new entry's identifier.
+    pub fn push(
+        &self,
+        reason: DeadLetterReason,
+        rule_name: &str,
+        event: ProgramEvent,
+        alert: Option<Alert>,
+        error: String,
+    ) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let now = Utc::now();
+        self.insert(DeadLetter {
+            id,
+            reason,
+            rule_name: rule_name.to_string(),
+            event,
+            alert,
+            error,
+            attempts: 1,
+            first_failed_at: now,
+            last_failed_at: now,
+        });
+        id
+    }
+
+    /// Put back an entry whose retry failed, keeping its identifier.
+    pub fn requeue(&self, mut entry: DeadLetter, error: String) {
+        entry.attempts += 1;
+        entry.error = error;
+        entry.last_failed_at = Utc::now();
+        self.insert(entry);
+    }
+
+    /// Remove and return an entry, e.g. to retry it.
+    pub fn take(&self, id: u64) -> Option<DeadLetter> {
+        let mut entries = self.lock();
+        let position = entries.iter().position(|entry| entry.id == id)?;
+        entries.remove(position)
+    }
+
+    /// Get a copy of an entry.
+    pub fn get(&self, id: u64) -> Option<DeadLetter> {
+        self.lock().iter().find(|entry| entry.id == id).cloned()
+    }
+
+    /// All entries, oldest first.
+    pub fn list(&self) -> Vec<DeadLetter> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Discard an entry, returning whether it existed.
+    pub fn remove(&self, id: u64) -> bool {
+        self.take(id).is_some()
+    }
+
+    /// Discard all entries, returning how many were removed.
+    pub fn clear(&self) -> usize {
+        let mut entries = self.lock();
+        let count = entries.len();
+        entries.clear();
+        count
+    }
+
+    /// Number of entries held.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Entries dropped because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Entry counts per rule, to spot systematically broken rules.
+    pub fn failures_by_rule(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for entry in self.lock().iter() {
+            *counts.entry(entry.rule_name.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    fn insert(&self, entry: DeadLetter) {
+        if self.capacity == 0 {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let mut entries = self.lock();
+        while entries.len() >= self.capacity {
+            if let Some(oldest) = entries.pop_front() {
+                warn!(
+                    "Dead-letter queue full, dropping entry {} for rule {}",
+                    oldest.id, oldest.rule_name
+                );
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        entries.push_back(entry);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<DeadLetter>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            capacity: default_capacity(),
+        }
+    }
+}
+
+// Default value functions
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_capacity() -> usize {
+    1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use watchtower_subscriber::{EventData, EventType};
+
+    fn event() -> ProgramEvent {
+        ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: "test".to_string(),
+                level: None,
+                instruction_index: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_bounded_queue_and_requeue() {
+        let queue = DeadLetterQueue::new(2);
+        let first = queue.push(
+            DeadLetterReason::RuleTimeout,
+            "slow",
+            event(),
+            None,
+            "timeout".to_string(),
+        );
+        queue.push(
+            DeadLetterReason::RulePanic,
+            "broken",
+            event(),
+            None,
+            "panic".to_string(),
+        );
+        let third = queue.push(
+            DeadLetterReason::RulePanic,
+            "broken",
+            event(),
+            None,
+            "panic".to_string(),
+        );
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 1);
+        assert!(queue.get(first).is_none());
+        assert_eq!(queue.failures_by_rule()["broken"], 2);
+
+        let entry = queue.take(third).unwrap();
+        queue.requeue(entry, "panic again".to_string());
+        let entry = queue.get(third).unwrap();
+        assert_eq!(entry.attempts, 2);
+        assert_eq!(entry.error, "panic again");
+
+        assert_eq!(queue.clear(), 2);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+//! History of notification delivery attempts, optionally persisted to disk.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Outcome of a notification delivery attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryOutcome {
+    /// The provider accepted the notification
+    Delivered,
+
+    /// Delivery failed, possibly after retries
+    Failed,
+
+    /// The notification was dropped by a rate limit
+    RateLimited,
+}
+
+impl DeliveryOutcome {
+    /// Outcome as a query parameter or metric label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryOutcome::Delivered => "delivered",
+            DeliveryOutcome::Failed => "failed",
+            DeliveryOutcome::RateLimited => "rate_limited",
+        }
+    }
+}
+
+/// A single notification delivery attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    /// Log-assigned identifier
+    pub id: u64,
+
+    /// When the attempt finished
+    pub timestamp: DateTime<Utc>,
+
+    /// Alert the notification was for
+    pub alert_id: String,
+
+    /// Channel the notification was sent through
+    pub channel: String,
+
+    /// Outcome of the attempt
+    pub outcome: DeliveryOutcome,
+
+    /// Message identifier returned by the provider, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_message_id: Option<String>,
+
+    /// Error message for failed attempts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Criteria for querying delivery records.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeliveryQuery {
+    /// Only attempts for this alert
+    pub alert_id: Option<String>,
+
+    /// Only attempts on this channel
+    pub channel: Option<String>,
+
+    /// Only attempts with this outcome
+    pub outcome: Option<DeliveryOutcome>,
+
+    /// Only attempts at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Maximum number of records returned
+    pub limit: Option<usize>,
+}
+
+/// Errors that can occur while loading or persisting delivery records.
+#[derive(Error, Debug)]
+pub enum DeliveryLogError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type DeliveryLogResult<T> = Result<T, DeliveryLogError>;
+
+/// Bounded log of delivery attempts.
+///
+/// When backed by a file, records are appended as JSON lines; the file is
+/// rewritten with only the retained records once it grows to twice the
+/// capacity.
+#[derive(Debug)]
+pub struct DeliveryLog {
+    /// File the records are appended to
+    path: Option<PathBuf>,
+
+    /// Records in arrival order
+    records: Mutex<VecDeque<DeliveryRecord>>,
+
+    /// Maximum number of records kept
+    capacity: usize,
+
+    /// Next record identifier
+    next_id: AtomicU64,
+
+    /// Lines in the backing file
+    persisted: AtomicU64,
+}
+
+impl DeliveryLog {
+    /// Create an in-memory log that is not persisted.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self {
+            path: None,
+            records: Mutex::new(VecDeque::new()),
+            capacity,
+            next_id: AtomicU64::new(1),
+            persisted: AtomicU64::new(0),
+        }
+    }
+
+    /// Open a log backed by `path`, loading the most recent records of a previous run.
+    pub fn open(path: impl Into<PathBuf>, capacity: usize) -> DeliveryLogResult<Self> {
+        let path = path.into();
+        let mut records = VecDeque::new();
+        let mut lines = 0;
+
+        if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                lines += 1;
+                match serde_json::from_str::<DeliveryRecord>(line) {
+                    Ok(record) => {
+                        if records.len() >= capacity.max(1) {
+                            records.pop_front();
+                        }
+                        records.push_back(record);
+                    }
+                    Err(e) => warn!("Skipping malformed delivery record: {}", e),
+                }
+            }
+        }
+
+        debug!(
+            "Loaded {} delivery records from {}",
+            records.len(),
+            path.display()
+        );
+
+        let next_id = records.iter().map(|record| record.id).max().unwrap_or(0) + 1;
+        Ok(Self {
+            path: Some(path),
+            records: Mutex::new(records),
+            capacity,
+            next_id: AtomicU64::new(next_id),
+            persisted: AtomicU64::new(lines),
+        })
+    }
+
+    /// Path of the backing file, if persisted.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Record a delivery attempt, returning the new record's identifier.
+    pub fn record(
+        &self,
+        alert_id: &str,
+        channel: &str,
+        outcome: DeliveryOutcome,
+        provider_message_id: Option<String>,
+        error: Option<String>,
+    ) -> u64 {
+        let record = DeliveryRecord {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            timestamp: Utc::now(),
+            alert_id: alert_id.to_string(),
+            channel: channel.to_string(),
+            outcome,
+            provider_message_id,
+            error,
+        };
+        let id = record.id;
+
+        let mut records = self.lock();
+        while records.len() >= self.capacity.max(1) {
+            records.pop_front();
+        }
+        records.push_back(record);
+
+        if let Err(e) = self.persist(&records) {
+            warn!("Failed to persist delivery record {}: {}", id, e);
+        }
+
+        id
+    }
+
+    /// Records matching `query`, newest first.
+    pub fn query(&self, query: &DeliveryQuery) -> Vec<DeliveryRecord> {
+        self.lock()
+            .iter()
+            .rev()
+            .filter(|record| {
+                query
+                    .alert_id
+                    .as_ref()
+                    .map_or(true, |id| &record.alert_id == id)
+                    && query
+                        .channel
+                        .as_ref()
+                        .map_or(true, |c| &record.channel == c)
+                    && query.outcome.map_or(true, |o| record.outcome == o)
+                    && query.since.map_or(true, |since| record.timestamp >= since)
+            })
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    /// Delivery attempts for an alert, oldest first.
+    pub fn for_alert(&self, alert_id: &str) -> Vec<DeliveryRecord> {
+        self.lock()
+            .iter()
+            .filter(|record| record.alert_id == alert_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of records held.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Append the newest record to the backing file, compacting it when it
+    /// has grown to twice the capacity.
+    fn persist(&self, records: &VecDeque<DeliveryRecord>) -> DeliveryLogResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if self.persisted.load(Ordering::Relaxed) + 1 >= 2 * self.capacity.max(1) as u64 {
+            let mut contents = String::new();
+            for record in records {
+                contents.push_str(&serde_json::to_string(record)?);
+                contents.push('\n');
+            }
+
+            // Write to a temporary file first so a crash never leaves a truncated log
+            let tmp_path = path.with_extension("tmp");
+            std::fs::write(&tmp_path, contents)?;
+            std::fs::rename(&tmp_path, path)?;
+            self.persisted
+                .store(records.len() as u64, Ordering::Relaxed);
+        } else if let Some(record) = records.back() {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+            self.persisted.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<DeliveryRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_log_query_and_reopen() {
+        let dir = std::env::temp_dir().join(format!(
+            "watchtower-deliveries-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let path = dir.join("deliveries.jsonl");
+
+        let log = DeliveryLog::open(&path, 2).unwrap();
+        log.record("a", "slack", DeliveryOutcome::Delivered, None, None);
+        log.record(
+            "a",
+            "pagerduty",
+            DeliveryOutcome::Failed,
+            None,
+            Some("503".to_string()),
+        );
+        log.record(
+            "a",
+            "pagerduty",
+            DeliveryOutcome::Delivered,
+            Some("dedup-key".to_string()),
+            None,
+        );
+
+        // The oldest record is dropped once full
+        let records = log.for_alert("a");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].outcome, DeliveryOutcome::Failed);
+
+        let delivered = log.query(&DeliveryQuery {
+            channel: Some("pagerduty".to_string()),
+            outcome: Some(DeliveryOutcome::Delivered),
+            ..Default::default()
+        });
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(
+            delivered[0].provider_message_id.as_deref(),
+            Some("dedup-key")
+        );
+
+        // The file is compacted and reloads the retained records
+        let reopened = DeliveryLog::open(&path, 2).unwrap();
+        let ids: Vec<u64> = reopened
+            .query(&DeliveryQuery::default())
+            .iter()
+            .map(|record| record.id)
+            .collect();
+        assert_eq!(ids, vec![3, 2]);
+        assert_eq!(
+            reopened.record("b", "email", DeliveryOutcome::RateLimited, None, None),
+            4
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
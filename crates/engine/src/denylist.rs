@@ -0,0 +1,558 @@
+//! Denylisted (e.g. sanctioned) address lists and the rule that flags them.
+
+use crate::aggregates::SIGNER_METADATA_KEYS;
+use crate::rules::{AlertSeverity, Rule, RuleContext, RuleResult};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+use watchtower_subscriber::{EventData, ProgramEvent};
+
+/// Configuration for denylisted address lists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DenylistConfig {
+    /// Lists to load
+    pub lists: Vec<DenylistSource>,
+
+    /// How often remote lists are re-fetched, in seconds
+    #[serde(default = "default_refresh_interval")]
+    pub refresh_interval_seconds: u64,
+
+    /// How often local files are checked for changes, in seconds
+    #[serde(default = "default_file_poll_interval")]
+    pub file_poll_interval_seconds: u64,
+
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_request_timeout")]
+    pub request_timeout_seconds: u64,
+
+    /// Severity of generated alerts
+    #[serde(default = "default_severity")]
+    pub severity: AlertSeverity,
+}
+
+/// A single address list, read from a local file or fetched from a URL.
+///
+/// Lists hold one base58 address per line (anything after the address is
+/// ignored, `#` starts a comment) or a JSON array of addresses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DenylistSource {
+    /// List name reported in alert metadata
+    pub name: String,
+
+    /// Local file path
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+
+    /// Remote URL
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Errors that can occur while loading denylists.
+#[derive(Error, Debug)]
+pub enum DenylistError {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid list {list}: {reason}")]
+    InvalidList { list: String, reason: String },
+
+    #[error("Denylist configuration error: {0}")]
+    Configuration(String),
+}
+
+pub type DenylistResult<T> = Result<T, DenylistError>;
+
+/// Loaded address lists shared between the loader and the rule.
+#[derive(Debug, Default)]
+pub struct Denylist {
+    /// Addresses per list name
+    lists: RwLock<HashMap<String, HashSet<Pubkey>>>,
+}
+
+impl Denylist {
+    /// Create an empty denylist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the contents of a list.
+    pub fn set_list(&self, name: &str, addresses: HashSet<Pubkey>) {
+        self.lists
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), addresses);
+    }
+
+    /// Names of the lists containing `address`.
+    pub fn lookup(&self, address: &Pubkey) -> Vec<String> {
+        let lists = self.lists.read().unwrap_or_else(|e| e.into_inner());
+        let mut matched: Vec<String> = lists
+            .iter()
+            .filter(|(_, addresses)| addresses.contains(address))
+            .map(|(name, _)| name.clone())
+            .collect();
+        matched.sort();
+        matched
+    }
+
+    /// Number of addresses per list.
+    pub fn list_sizes(&self) -> HashMap<String, usize> {
+        self.lists
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, addresses)| (name.clone(), addresses.len()))
+            .collect()
+    }
+}
+
+/// Keeps a `Denylist` up to date, reloading changed files and re-fetching URLs.
+pub struct DenylistLoader {
+    config: DenylistConfig,
+    client: reqwest::Client,
+    denylist: Arc<Denylist>,
+    /// Last seen modification time and size per file list
+    file_versions: Mutex<HashMap<String, (SystemTime, u64)>>,
+    /// Last fetch time per URL list
+    last_fetch: Mutex<HashMap<String, Instant>>,
+}
+
+impl DenylistLoader {
+    /// Create a loader that populates `denylist`.
+    pub fn new(config: DenylistConfig, denylist: Arc<Denylist>) -> DenylistResult<Self> {
+        config.validate()?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .build()?;
+
+        Ok(Self {
+            config,
+            client,
+            denylist,
+            file_versions: Mutex::new(HashMap::new()),
+            last_fetch: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Reload lists whose file changed or whose URL is due for a refresh.
+    ///
+    /// A list that fails to load keeps its previous contents. Returns the
+    /// number of lists reloaded.
+    pub async fn refresh(&self) -> usize {
+        let mut reloaded = 0;
+
+        for source in &self.config.lists {
+            let result = match (&source.path, &source.url) {
+                (Some(path), _) => self.reload_file(source, path),
+                (None, Some(url)) => self.reload_url(source, url).await,
+                (None, None) => Ok(false),
+            };
+
+            match result {
+                Ok(true) => reloaded += 1,
+                Ok(false) => {}
+                Err(e) => warn!("Failed to load denylist {}: {}", source.name, e),
+            }
+        }
+
+        if reloaded > 0 {
+            debug!("Reloaded {} denylists", reloaded);
+        }
+        reloaded
+    }
+
+    /// Start reloading lists in the background.
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        info!(
+            "Starting denylist loader for {} lists",
+            self.config.lists.len()
+        );
+
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.config.file_poll_interval_seconds));
+
+            loop {
+                interval.tick().await;
+                self.refresh().await;
+            }
+        })
+    }
+
+    fn reload_file(&self, source: &DenylistSource, path: &Path) -> DenylistResult<bool> {
+        let metadata = std::fs::metadata(path)?;
+        let version = (metadata.modified()?, metadata.len());
+        let mut versions = self.file_versions.lock().unwrap_or_else(|e| e.into_inner());
+        if versions.get(&source.name) == Some(&version) {
+            return Ok(false);
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let addresses = parse_address_list(&source.name, &contents)?;
+        info!(
+            "Loaded {} addresses from denylist {}",
+            addresses.len(),
+            source.name
+        );
+        self.denylist.set_list(&source.name, addresses);
+        versions.insert(source.name.clone(), version);
+        Ok(true)
+    }
+
+    async fn reload_url(&self, source: &DenylistSource, url: &str) -> DenylistResult<bool> {
+        let refresh_interval = Duration::from_secs(self.config.refresh_interval_seconds);
+        let due = self
+            .last_fetch
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&source.name)
+            .map_or(true, |fetched| fetched.elapsed() >= refresh_interval);
+        if !due {
+            return Ok(false);
+        }
+
+        // Count failed fetches too so an unreachable list isn't retried every poll
+        self.last_fetch
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(source.name.clone(), Instant::now());
+
+        let contents = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let addresses = parse_address_list(&source.name, &contents)?;
+        info!(
+            "Fetched {} addresses for denylist {}",
+            addresses.len(),
+            source.name
+        );
+        self.denylist.set_list(&source.name, addresses);
+        Ok(true)
+    }
+}
+
+/// Parse a list of addresses (one per line or a JSON array).
+pub fn parse_address_list(name: &str, contents: &str) -> DenylistResult<HashSet<Pubkey>> {
+    let invalid = |reason: String| DenylistError::InvalidList {
+        list: name.to_string(),
+        reason,
+    };
+
+    if contents.trim_start().starts_with('[') {
+        let entries: Vec<String> =
+            serde_json::from_str(contents).map_err(|e| invalid(e.to_string()))?;
+        return entries
+            .iter()
+            .map(|entry| {
+                Pubkey::from_str(entry.trim())
+                    .map_err(|_| invalid(format!("invalid address {}", entry)))
+            })
+            .collect();
+    }
+
+    let mut addresses = HashSet::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        let Some(entry) = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .find(|token| !token.is_empty())
+        else {
+            continue;
+        };
+
+        match Pubkey::from_str(entry) {
+            Ok(address) => {
+                addresses.insert(address);
+            }
+            Err(_) => warn!(
+                "Skipping invalid address on line {} of denylist {}: {}",
+                line_number + 1,
+                name,
+                entry
+            ),
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// Rule that flags any event involving a denylisted address.
+#[derive(Debug)]
+pub struct DenylistRule {
+    /// Loaded address lists
+    denylist: Arc<Denylist>,
+    /// Severity of generated alerts
+    severity: AlertSeverity,
+}
+
+impl DenylistRule {
+    pub fn new(denylist: Arc<Denylist>) -> Self {
+        Self {
+            denylist,
+            severity: default_severity(),
+        }
+    }
+
+    /// Set the severity of generated alerts.
+    pub fn with_severity(mut self, severity: AlertSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+#[async_trait]
+impl Rule for DenylistRule {
+    fn name(&self) -> &str {
+        "denylist"
+    }
+
+    fn description(&self) -> &str {
+        "Flags events involving denylisted addresses"
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        self.severity
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        let matches: BTreeMap<String, Vec<String>> = involved_addresses(event)
+            .into_iter()
+            .filter_map(|address| {
+                let lists = self.denylist.lookup(&address);
+                (!lists.is_empty()).then(|| (address.to_string(), lists))
+            })
+            .collect();
+
+        if matches.is_empty() {
+            return result;
+        }
+
+        let mut lists: Vec<&String> = matches.values().flatten().collect();
+        lists.sort();
+        lists.dedup();
+
+        result.triggered = true;
+        result.message = Some(format!(
+            "Event involves {} denylisted address(es): {} (lists: {})",
+            matches.len(),
+            matches.keys().cloned().collect::<Vec<_>>().join(", "),
+            lists
+                .iter()
+                .map(|l| l.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        result.confidence = 1.0;
+        result.metadata.insert(
+            "matched_addresses".to_string(),
+            serde_json::to_value(&matches).unwrap_or_default(),
+        );
+        result.metadata.insert(
+            "matched_lists".to_string(),
+            serde_json::to_value(&lists).unwrap_or_default(),
+        );
+        result
+            .suggested_actions
+            .push("Review the transaction and counterparties for compliance".to_string());
+        result
+            .suggested_actions
+            .push("Consider blocking or freezing affected funds".to_string());
+
+        result
+    }
+}
+
+/// Addresses taking part in an event, excluding the monitored program itself.
+fn involved_addresses(event: &ProgramEvent) -> Vec<Pubkey> {
+    let mut addresses = match &event.data {
+        EventData::TokenTransfer { from, to, .. } => vec![*from, *to],
+        EventData::AccountChange { account, .. } => vec![*account],
+        EventData::Instruction { accounts, .. } => accounts.clone(),
+        _ => Vec::new(),
+    };
+
+    addresses.extend(SIGNER_METADATA_KEYS.iter().filter_map(|key| {
+        event
+            .metadata
+            .get(*key)
+            .and_then(|v| v.as_str())
+            .and_then(|s| Pubkey::from_str(s).ok())
+    }));
+
+    addresses.sort();
+    addresses.dedup();
+    addresses
+}
+
+impl DenylistConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> DenylistResult<()> {
+        if self.lists.is_empty() {
+            return Err(DenylistError::Configuration(
+                "At least one denylist must be configured".to_string(),
+            ));
+        }
+
+        if self.refresh_interval_seconds == 0 || self.file_poll_interval_seconds == 0 {
+            return Err(DenylistError::Configuration(
+                "Refresh intervals must be greater than 0".to_string(),
+            ));
+        }
+
+        let mut names = HashSet::new();
+        for source in &self.lists {
+            if !names.insert(source.name.as_str()) {
+                return Err(DenylistError::Configuration(format!(
+                    "Duplicate denylist name: {}",
+                    source.name
+                )));
+            }
+
+            if source.path.is_some() == source.url.is_some() {
+                return Err(DenylistError::Configuration(format!(
+                    "Denylist {} must set exactly one of path or url",
+                    source.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Default value functions
+fn default_refresh_interval() -> u64 {
+    3600
+}
+
+fn default_file_poll_interval() -> u64 {
+    5
+}
+
+fn default_request_timeout() -> u64 {
+    10
+}
+
+fn default_severity() -> AlertSeverity {
+    AlertSeverity::Critical
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_subscriber::EventType;
+
+    #[test]
+    fn test_parse_address_list() {
+        let listed = Pubkey::new_unique();
+        let contents = format!(
+            "# OFAC SDN\n{} sanctioned entity\n\nnot-an-address\n",
+            listed
+        );
+
+        let addresses = parse_address_list("ofac", &contents).unwrap();
+        assert_eq!(addresses.len(), 1);
+        assert!(addresses.contains(&listed));
+
+        let json = format!("[\"{}\"]", listed);
+        assert!(parse_address_list("ofac", &json).unwrap().contains(&listed));
+    }
+
+    #[tokio::test]
+    async fn test_denylist_rule_reports_matching_list() {
+        let listed = Pubkey::new_unique();
+        let denylist = Arc::new(Denylist::new());
+        denylist.set_list("ofac", HashSet::from([listed]));
+        let rule = DenylistRule::new(denylist);
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Token".to_string(),
+            EventType::TokenTransfer,
+            EventData::TokenTransfer {
+                from: Pubkey::new_unique(),
+                to: listed,
+                amount: 1,
+                mint: Pubkey::new_unique(),
+                decimals: 0,
+            },
+        );
+
+        let result = rule.evaluate(&event, &RuleContext::default()).await;
+        assert!(result.triggered);
+        assert_eq!(result.severity, AlertSeverity::Critical);
+        assert_eq!(
+            result.metadata["matched_lists"],
+            serde_json::json!(["ofac"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_list_hot_reload() {
+        let dir =
+            std::env::temp_dir().join(format!("watchtower-denylist-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("list.txt");
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        std::fs::write(&path, first.to_string()).unwrap();
+
+        let denylist = Arc::new(Denylist::new());
+        let loader = DenylistLoader::new(
+            DenylistConfig {
+                lists: vec![DenylistSource {
+                    name: "local".to_string(),
+                    path: Some(path.clone()),
+                    url: None,
+                }],
+                refresh_interval_seconds: default_refresh_interval(),
+                file_poll_interval_seconds: default_file_poll_interval(),
+                request_timeout_seconds: default_request_timeout(),
+                severity: default_severity(),
+            },
+            denylist.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(loader.refresh().await, 1);
+        assert_eq!(loader.refresh().await, 0);
+        assert_eq!(denylist.lookup(&first), vec!["local".to_string()]);
+
+        // Change the size too, in case the modification time has coarse granularity
+        std::fs::write(&path, format!("{}\n# updated\n", second)).unwrap();
+
+        assert_eq!(loader.refresh().await, 1);
+        assert!(denylist.lookup(&first).is_empty());
+        assert_eq!(denylist.lookup(&second), vec!["local".to_string()]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,15 +1,27 @@
 //! Core monitoring engine that coordinates event processing, rule evaluation, and alerting.
 
 use crate::{
+    aggregates::{AddressAggregator, AggregationConfig},
     alerts::{Alert, AlertManager},
-    metrics::{MetricsCollector, MetricsSnapshot},
-    rules::{Rule, RuleContext, RuleResult},
+    calibration::{AlertFeedback, CalibrationConfig, ConfidenceCalibrator, RulePrecision},
+    dead_letter::{DeadLetterConfig, DeadLetterQueue, DeadLetterReason},
+    enrichment::{AlertEnricher, EnrichmentConfig, EnrichmentPipeline},
+    history::{EventHistory, HistoryUsage},
+    metrics::{MetricsCollector, MetricsConfig, MetricsSnapshot},
+    rule_state::RuleStateStore,
+    rules::{RateOfChangeConfig, RateOfChangeRule, Rule, RuleContext, RuleResult},
+    scheduler::{EvaluationPermit, EvaluationScheduler},
+    silences::SilenceList,
 };
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::{broadcast, RwLock};
@@ -18,8 +30,26 @@ use watchtower_subscriber::ProgramEvent;
 
 /// Core monitoring engine that processes events and evaluates rules.
 pub struct MonitoringEngine {
-    /// Registered rules
-    rules: Arc<RwLock<Vec<Box<dyn Rule>>>>,
+    /// Registered rules, ordered by descending priority
+    rules: Arc<RwLock<Vec<Arc<dyn Rule>>>>,
+
+    /// Limits concurrent rule evaluations across all events, by rule priority
+    evaluation_scheduler: EvaluationScheduler,
+
+    /// Runtime enable/disable overrides and rule definitions
+    rule_states: Arc<RwLock<RuleStateStore>>,
+
+    /// Configuration of registered declarative rules, keyed by rule name
+    declarative_rules: Arc<RwLock<BTreeMap<String, RateOfChangeConfig>>>,
+
+    /// Per-rule evaluation statistics
+    rule_stats: Arc<DashMap<String, RuleStats>>,
+
+    /// Decaying throughput and latency estimates
+    performance: Arc<std::sync::Mutex<PerformanceTracker>>,
+
+    /// Cap on events processed per minute, adjustable at runtime
+    event_rate_limit: Arc<std::sync::Mutex<EventRateLimit>>,
 
     /// Metrics collector
     metrics: Arc<MetricsCollector>,
@@ -28,7 +58,25 @@ pub struct MonitoringEngine {
     alert_manager: Arc<AlertManager>,
 
     /// Event history for rule context
-    event_history: Arc<DashMap<String, Vec<ProgramEvent>>>,
+    event_history: Arc<EventHistory>,
+
+    /// Per-address activity aggregates for rule context
+    addresses: Arc<AddressAggregator>,
+
+    /// Enrichers applied to alerts before dispatch
+    enrichment: Arc<RwLock<EnrichmentPipeline>>,
+
+    /// Programs whose rule evaluation is paused, keyed by program name
+    paused_programs: Arc<DashMap<String, ProgramPause>>,
+
+    /// Failed rule evaluations and alert dispatches kept for retry
+    dead_letters: Arc<DeadLetterQueue>,
+
+    /// Silences suppressing matching alerts
+    silences: Arc<SilenceList>,
+
+    /// Adjusts alert confidence by rule precision
+    calibrator: ConfidenceCalibrator,
 
     /// Engine configuration
     config: EngineConfig,
@@ -36,6 +84,9 @@ pub struct MonitoringEngine {
     /// Event sender for alerts
     alert_sender: broadcast::Sender<Alert>,
 
+    /// Sender of every processed program event
+    event_sender: broadcast::Sender<ProgramEvent>,
+
     /// Engine state
     state: Arc<RwLock<EngineState>>,
 }
@@ -49,17 +100,172 @@ pub struct EngineConfig {
     /// Maximum age of events to keep in history
     pub max_history_age: Duration,
 
+    /// Global memory budget for event history across all programs, in bytes
+    #[serde(default)]
+    pub max_history_bytes: Option<usize>,
+
+    /// File used to persist sliding windows across restarts
+    #[serde(default)]
+    pub window_state_path: Option<PathBuf>,
+
     /// Interval for metrics snapshots
     pub metrics_interval: Duration,
 
     /// Maximum concurrent rule evaluations
     pub max_concurrent_evaluations: usize,
 
-    /// Rule evaluation timeout
+    /// Rule evaluation timeout. Enforced even on rules that never yield; an
+    /// overrunning rule keeps its blocking thread until it returns, but its
+    /// result is discarded.
     pub rule_timeout: Duration,
 
     /// Whether to enable detailed logging
     pub debug_logging: bool,
+
+    /// Per-rule resource budgets keyed by rule name
+    #[serde(default)]
+    pub rule_budgets: HashMap<String, RuleBudget>,
+
+    /// File used to persist runtime rule enable/disable state
+    #[serde(default)]
+    pub rule_state_path: Option<PathBuf>,
+
+    /// File used to persist the notification delivery history
+    #[serde(default)]
+    pub delivery_history_path: Option<PathBuf>,
+
+    /// Operator-tunable rule parameters keyed by rule name.
+    ///
+    /// A `programs` table inside a rule's parameters holds per-program
+    /// overrides keyed by program name.
+    #[serde(default)]
+    pub rule_config: HashMap<String, HashMap<String, serde_json::Value>>,
+
+    /// Histogram buckets and sliding window sizes
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// Per-address aggregation settings
+    #[serde(default)]
+    pub aggregation: AggregationConfig,
+
+    /// Explorer links, token symbols and USD values attached to alerts
+    #[serde(default)]
+    pub enrichment: EnrichmentConfig,
+
+    /// Team/project tags keyed by program name
+    #[serde(default)]
+    pub program_tags: HashMap<String, Vec<String>>,
+
+    /// Team/project tags keyed by rule name
+    #[serde(default)]
+    pub rule_tags: HashMap<String, Vec<String>>,
+
+    /// Capture of failed rule evaluations and alert dispatches
+    #[serde(default)]
+    pub dead_letters: DeadLetterConfig,
+
+    /// Confidence calibration from true/false positive feedback
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+}
+
+/// Registration and enablement state of a rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleStatus {
+    /// Rule name
+    pub name: String,
+
+    /// Rule description
+    pub description: String,
+
+    /// Alert severity
+    pub severity: crate::rules::AlertSeverity,
+
+    /// Evaluation priority
+    pub priority: u8,
+
+    /// Whether the rule is currently evaluated
+    pub enabled: bool,
+
+    /// Team/project tags
+    pub tags: Vec<String>,
+
+    /// Evaluation statistics
+    pub stats: RuleStats,
+}
+
+/// Evaluation statistics for a single rule.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleStats {
+    /// Number of completed evaluations
+    pub evaluations: u64,
+
+    /// Number of evaluations that triggered
+    pub triggers: u64,
+
+    /// Number of evaluations that timed out
+    pub timeouts: u64,
+
+    /// Time of the most recent trigger
+    pub last_triggered: Option<DateTime<Utc>>,
+
+    /// Average evaluation time
+    pub avg_evaluation_time: Duration,
+
+    /// Number of triggers reported as false positives
+    pub false_positives: u64,
+
+    /// Number of triggers confirmed as real issues
+    #[serde(default)]
+    pub true_positives: u64,
+
+    /// Operator notes attached to false-positive reports
+    pub notes: Vec<RuleNote>,
+}
+
+/// Note recorded against a rule, e.g. when reporting a false positive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleNote {
+    /// When the note was recorded
+    pub timestamp: DateTime<Utc>,
+
+    /// Note text
+    pub note: String,
+}
+
+/// A pause of rule evaluation for one program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramPause {
+    /// Paused program name
+    pub program_name: String,
+
+    /// When the pause started
+    pub paused_at: DateTime<Utc>,
+
+    /// When evaluation resumes automatically, if ever
+    pub until: Option<DateTime<Utc>>,
+
+    /// Operator-supplied reason
+    pub reason: Option<String>,
+}
+
+/// Key of the per-program override table inside a rule's parameters.
+const PROGRAM_OVERRIDES_KEY: &str = "programs";
+
+/// Maximum number of notes kept per rule.
+const MAX_RULE_NOTES: usize = 100;
+
+/// Resource limits for a single rule, overriding the engine defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleBudget {
+    /// Evaluation timeout for this rule
+    #[serde(default)]
+    pub timeout: Option<Duration>,
+
+    /// Maximum number of history events passed to this rule
+    #[serde(default)]
+    pub max_history_events: Option<usize>,
 }
 
 /// Current state of the monitoring engine.
@@ -106,6 +312,59 @@ pub struct PerformanceStats {
     pub memory_usage_bytes: Option<u64>,
 }
 
+/// Weight of the newest sample in the latency moving averages.
+const LATENCY_SMOOTHING: f64 = 0.1;
+
+/// Time constant of the events-per-second moving average.
+const RATE_TIME_CONSTANT: Duration = Duration::from_secs(60);
+
+/// Minimum interval between events-per-second samples.
+const RATE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks exponentially decaying processing latency and throughput.
+#[derive(Debug)]
+struct PerformanceTracker {
+    /// Moving average of event processing time in seconds
+    avg_event_time: Option<f64>,
+
+    /// Moving average of rule evaluation time in seconds
+    avg_rule_time: Option<f64>,
+
+    /// Moving average of events per second
+    events_per_second: f64,
+
+    /// Highest observed events per second
+    peak_events_per_second: f64,
+
+    /// Start of the current rate sample
+    sample_start: Instant,
+
+    /// Events counted in the current rate sample
+    sample_events: u64,
+}
+
+/// Length of the window events per minute are counted in.
+const EVENT_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Caps the events processed in each one-minute window.
+#[derive(Debug)]
+struct EventRateLimit {
+    /// Events admitted per window, unlimited when absent
+    max_per_minute: Option<u32>,
+
+    /// Start of the current window
+    window_start: Instant,
+
+    /// Events admitted in the current window
+    admitted: u32,
+
+    /// Events dropped in the current window
+    dropped: u64,
+
+    /// Events dropped since the engine was created
+    dropped_total: u64,
+}
+
 /// Result of event processing.
 #[derive(Debug, Clone)]
 pub struct ProcessingResult {
@@ -131,6 +390,30 @@ pub enum EngineError {
     #[error("Rule evaluation timeout: {rule}")]
     RuleTimeout { rule: String },
 
+    #[error("Rule evaluation panicked: {rule}")]
+    RulePanic { rule: String },
+
+    #[error("Dead letter not found: {0}")]
+    DeadLetterNotFound(u64),
+
+    #[error("Alert not found: {0}")]
+    AlertNotFound(String),
+
+    #[error("Rule not found: {0}")]
+    RuleNotFound(String),
+
+    #[error("Rule already registered: {0}")]
+    RuleExists(String),
+
+    #[error("Rule is not declarative: {0}")]
+    RuleNotDeclarative(String),
+
+    #[error("Invalid rule: {0}")]
+    InvalidRule(String),
+
+    #[error("Failed to persist rule state: {0}")]
+    RuleState(#[from] crate::rule_state::RuleStateError),
+
     #[error("Failed to process event: {0}")]
     EventProcessing(String),
 
@@ -154,14 +437,41 @@ impl MonitoringEngine {
         config: EngineConfig,
     ) -> Self {
         let (alert_sender, _) = broadcast::channel(1000);
+        let (event_sender, _) = broadcast::channel(1000);
+
+        let rule_states = match &config.rule_state_path {
+            Some(path) => RuleStateStore::open(path).unwrap_or_else(|e| {
+                warn!("Failed to load rule state from {}: {}", path.display(), e);
+                RuleStateStore::in_memory()
+            }),
+            None => RuleStateStore::in_memory(),
+        };
+        let enrichment = EnrichmentPipeline::from_config(&config.enrichment, metrics.clone());
 
         Self {
             rules: Arc::new(RwLock::new(Vec::new())),
+            evaluation_scheduler: EvaluationScheduler::new(config.max_concurrent_evaluations),
+            rule_states: Arc::new(RwLock::new(rule_states)),
+            declarative_rules: Arc::new(RwLock::new(BTreeMap::new())),
+            rule_stats: Arc::new(DashMap::new()),
+            performance: Arc::new(std::sync::Mutex::new(PerformanceTracker::new())),
+            event_rate_limit: Arc::new(std::sync::Mutex::new(EventRateLimit::new(None))),
             metrics,
             alert_manager,
-            event_history: Arc::new(DashMap::new()),
+            event_history: Arc::new(EventHistory::new(
+                config.max_history_events,
+                config.max_history_age,
+                config.max_history_bytes,
+            )),
+            addresses: Arc::new(AddressAggregator::new(&config.aggregation)),
+            enrichment: Arc::new(RwLock::new(enrichment)),
+            paused_programs: Arc::new(DashMap::new()),
+            dead_letters: Arc::new(DeadLetterQueue::new(config.dead_letters.capacity)),
+            silences: Arc::new(SilenceList::new()),
+            calibrator: ConfidenceCalibrator::new(config.calibration.clone()),
             config,
             alert_sender,
+            event_sender,
             state: Arc::new(RwLock::new(EngineState {
                 running: false,
                 start_time: Utc::now(),
@@ -175,10 +485,18 @@ impl MonitoringEngine {
     }
 
     /// Add a rule to the engine.
+    ///
+    /// Rules are kept ordered by priority; rules with equal priority keep
+    /// their registration order.
     pub async fn add_rule(&self, rule: Box<dyn Rule>) {
-        let mut rules = self.rules.write().await;
-        info!("Adding rule: {}", rule.name());
-        rules.push(rule);
+        insert_rule(&mut *self.rules.write().await, rule);
+    }
+
+    /// Add an alert enricher, run after the built-in enrichers.
+    pub async fn add_enricher(&self, enricher: Arc<dyn AlertEnricher>) {
+        let mut enrichment = self.enrichment.write().await;
+        info!("Adding alert enricher: {}", enricher.name());
+        enrichment.add(enricher);
     }
 
     /// Remove a rule from the engine.
@@ -187,6 +505,7 @@ impl MonitoringEngine {
         let initial_len = rules.len();
         rules.retain(|rule| rule.name() != rule_name);
         let removed = rules.len() != initial_len;
+        self.declarative_rules.write().await.remove(rule_name);
 
         if removed {
             info!("Removed rule: {}", rule_name);
@@ -195,12 +514,340 @@ impl MonitoringEngine {
         removed
     }
 
-    /// Get all registered rules.
+    /// Register the declarative rules of the configuration file together
+    /// with those created at runtime.
+    ///
+    /// Runtime definitions replace configuration entries of the same name,
+    /// and rules removed at runtime are not registered again.
+    pub async fn load_declarative_rules(&self, configs: &[RateOfChangeConfig]) {
+        let rule_states = self.rule_states.read().await;
+        let mut merged = BTreeMap::new();
+        for config in configs {
+            if !rule_states.is_deleted(&config.name) {
+                merged.insert(config.name.clone(), config.clone());
+            }
+        }
+        for config in rule_states.definitions() {
+            merged.insert(config.name.clone(), config.clone());
+        }
+        drop(rule_states);
+
+        for config in merged.into_values() {
+            self.register_declarative_rule(config).await;
+        }
+    }
+
+    /// Configuration of the registered declarative rules.
+    pub async fn declarative_rules(&self) -> Vec<RateOfChangeConfig> {
+        self.declarative_rules
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Configuration of a registered declarative rule.
+    pub async fn declarative_rule(&self, rule_name: &str) -> Option<RateOfChangeConfig> {
+        self.declarative_rules.read().await.get(rule_name).cloned()
+    }
+
+    /// Create a declarative rule at runtime, persisting its definition.
+    pub async fn create_rule(&self, config: RateOfChangeConfig) -> EngineResult<()> {
+        config
+            .validate()
+            .map_err(|e| EngineError::InvalidRule(e.to_string()))?;
+
+        // Checked and registered under one lock, so concurrent creations of
+        // the same name cannot both succeed
+        let mut rules = self.rules.write().await;
+        if rules.iter().any(|rule| rule.name() == config.name) {
+            return Err(EngineError::RuleExists(config.name));
+        }
+
+        self.rule_states.write().await.define(config.clone())?;
+        insert_rule(&mut rules, Box::new(RateOfChangeRule::from_config(&config)));
+        self.declarative_rules
+            .write()
+            .await
+            .insert(config.name.clone(), config);
+        Ok(())
+    }
+
+    /// Replace the configuration of a declarative rule at runtime,
+    /// persisting its definition. The rule keeps its name and enabled state.
+    pub async fn update_rule(
+        &self,
+        rule_name: &str,
+        config: RateOfChangeConfig,
+    ) -> EngineResult<()> {
+        if config.name != rule_name {
+            return Err(EngineError::InvalidRule(format!(
+                "Rule {} cannot be renamed to {}",
+                rule_name, config.name
+            )));
+        }
+        config
+            .validate()
+            .map_err(|e| EngineError::InvalidRule(e.to_string()))?;
+        self.ensure_declarative(rule_name).await?;
+
+        self.rule_states.write().await.define(config.clone())?;
+        self.remove_rule(rule_name).await;
+        self.register_declarative_rule(config).await;
+        Ok(())
+    }
+
+    /// Remove a declarative rule at runtime, persisting the removal so a
+    /// rule of the configuration file is not registered again on restart.
+    pub async fn delete_rule(&self, rule_name: &str) -> EngineResult<()> {
+        self.ensure_declarative(rule_name).await?;
+
+        self.rule_states.write().await.delete(rule_name)?;
+        self.remove_rule(rule_name).await;
+        Ok(())
+    }
+
+    /// Fail unless `rule_name` is a registered declarative rule.
+    async fn ensure_declarative(&self, rule_name: &str) -> EngineResult<()> {
+        if self.declarative_rules.read().await.contains_key(rule_name) {
+            Ok(())
+        } else if self.list_rules().await.iter().any(|name| name == rule_name) {
+            Err(EngineError::RuleNotDeclarative(rule_name.to_string()))
+        } else {
+            Err(EngineError::RuleNotFound(rule_name.to_string()))
+        }
+    }
+
+    async fn register_declarative_rule(&self, config: RateOfChangeConfig) {
+        self.add_rule(Box::new(RateOfChangeRule::from_config(&config)))
+            .await;
+        self.declarative_rules
+            .write()
+            .await
+            .insert(config.name.clone(), config);
+    }
+
+    /// Get all registered rules in evaluation order.
     pub async fn list_rules(&self) -> Vec<String> {
         let rules = self.rules.read().await;
         rules.iter().map(|rule| rule.name().to_string()).collect()
     }
 
+    /// Get the registration and enablement state of all rules in evaluation order.
+    pub async fn rule_statuses(&self) -> Vec<RuleStatus> {
+        let rules = self.rules.read().await;
+        let rule_states = self.rule_states.read().await;
+
+        rules
+            .iter()
+            .map(|rule| RuleStatus {
+                name: rule.name().to_string(),
+                description: rule.description().to_string(),
+                severity: rule.severity(),
+                priority: rule.priority(),
+                enabled: rule_states.get(rule.name()).unwrap_or(rule.is_enabled()),
+                tags: self
+                    .config
+                    .rule_tags
+                    .get(rule.name())
+                    .cloned()
+                    .unwrap_or_default(),
+                stats: self.rule_stats(rule.name()).unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Get evaluation statistics for a rule.
+    pub fn rule_stats(&self, rule_name: &str) -> Option<RuleStats> {
+        self.rule_stats.get(rule_name).map(|stats| stats.clone())
+    }
+
+    /// Record that a rule's trigger was a false positive, with an optional note.
+    pub async fn report_false_positive(
+        &self,
+        rule_name: &str,
+        note: Option<String>,
+    ) -> EngineResult<()> {
+        self.record_feedback(rule_name, AlertFeedback::FalsePositive, note)
+            .await
+    }
+
+    /// Resolve an alert, recording the operator's verdict against its rule
+    /// so future confidence is calibrated by the rule's precision.
+    pub async fn resolve_alert(
+        &self,
+        alert_id: &str,
+        resolved_by: Option<&str>,
+        feedback: Option<AlertFeedback>,
+    ) -> EngineResult<()> {
+        let alert = self
+            .alert_manager
+            .get_alert(alert_id)
+            .ok_or_else(|| EngineError::AlertNotFound(alert_id.to_string()))?;
+
+        self.alert_manager
+            .resolve_alert_with_feedback(alert_id, resolved_by, feedback)
+            .await
+            .map_err(|_| EngineError::AlertNotFound(alert_id.to_string()))?;
+
+        if let Some(feedback) = feedback {
+            // The rule may have been removed since the alert fired
+            if let Err(e) = self.record_feedback(&alert.rule_name, feedback, None).await {
+                warn!("Failed to record feedback for alert {}: {}", alert_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Historical precision of every registered rule.
+    pub async fn rule_precision(&self) -> Vec<RulePrecision> {
+        self.list_rules()
+            .await
+            .iter()
+            .map(|name| {
+                let stats = self.rule_stats(name).unwrap_or_default();
+                self.calibrator.precision_stats(name, &stats)
+            })
+            .collect()
+    }
+
+    /// Record a true/false positive verdict for a rule, with an optional note.
+    pub async fn record_feedback(
+        &self,
+        rule_name: &str,
+        feedback: AlertFeedback,
+        note: Option<String>,
+    ) -> EngineResult<()> {
+        if !self.list_rules().await.iter().any(|name| name == rule_name) {
+            return Err(EngineError::RuleNotFound(rule_name.to_string()));
+        }
+
+        let mut stats = self.rule_stats.entry(rule_name.to_string()).or_default();
+        match feedback {
+            AlertFeedback::TruePositive => stats.true_positives += 1,
+            AlertFeedback::FalsePositive => stats.false_positives += 1,
+        }
+
+        if let Some(note) = note.filter(|n| !n.trim().is_empty()) {
+            stats.notes.push(RuleNote {
+                timestamp: Utc::now(),
+                note,
+            });
+            if stats.notes.len() > MAX_RULE_NOTES {
+                stats.notes.remove(0);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a rule is currently enabled, or `None` if it is not registered.
+    pub async fn is_rule_enabled(&self, rule_name: &str) -> Option<bool> {
+        let rules = self.rules.read().await;
+        let rule = rules.iter().find(|rule| rule.name() == rule_name)?;
+        let rule_states = self.rule_states.read().await;
+
+        Some(rule_states.get(rule_name).unwrap_or(rule.is_enabled()))
+    }
+
+    /// Enable or disable a rule at runtime, persisting the change.
+    pub async fn set_rule_enabled(&self, rule_name: &str, enabled: bool) -> EngineResult<()> {
+        if !self.list_rules().await.iter().any(|name| name == rule_name) {
+            return Err(EngineError::RuleNotFound(rule_name.to_string()));
+        }
+
+        self.rule_states.write().await.set(rule_name, enabled)?;
+        info!(
+            "Rule {} {}",
+            rule_name,
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        Ok(())
+    }
+
+    /// Enable a rule at runtime.
+    pub async fn enable_rule(&self, rule_name: &str) -> EngineResult<()> {
+        self.set_rule_enabled(rule_name, true).await
+    }
+
+    /// Disable a rule at runtime.
+    pub async fn disable_rule(&self, rule_name: &str) -> EngineResult<()> {
+        self.set_rule_enabled(rule_name, false).await
+    }
+
+    /// Silences suppressing matching alerts.
+    pub fn silences(&self) -> Arc<SilenceList> {
+        self.silences.clone()
+    }
+
+    /// Pause rule evaluation for a program.
+    ///
+    /// Events from the program are still recorded in history and metrics.
+    /// With a `duration` the pause lifts automatically once it elapses.
+    pub fn pause_program(
+        &self,
+        program_name: &str,
+        duration: Option<Duration>,
+        reason: Option<String>,
+    ) {
+        let paused_at = Utc::now();
+        let until = duration
+            .and_then(|d| chrono::Duration::from_std(d).ok())
+            .map(|d| paused_at + d);
+
+        info!(
+            "Paused rule evaluation for program {}{}",
+            program_name,
+            until.map_or(String::new(), |t| format!(" until {}", t))
+        );
+        self.paused_programs.insert(
+            program_name.to_string(),
+            ProgramPause {
+                program_name: program_name.to_string(),
+                paused_at,
+                until,
+                reason,
+            },
+        );
+    }
+
+    /// Resume rule evaluation for a program, returning whether it was paused.
+    pub fn resume_program(&self, program_name: &str) -> bool {
+        let resumed = self.paused_programs.remove(program_name).is_some();
+        if resumed {
+            info!("Resumed rule evaluation for program {}", program_name);
+        }
+        resumed
+    }
+
+    /// Whether rule evaluation is currently paused for a program.
+    pub fn is_program_paused(&self, program_name: &str) -> bool {
+        let now = Utc::now();
+        let expired = match self.paused_programs.get(program_name) {
+            None => return false,
+            Some(pause) => pause.until.is_some_and(|until| until <= now),
+        };
+
+        if expired {
+            self.resume_program(program_name);
+        }
+        !expired
+    }
+
+    /// Currently active program pauses.
+    pub fn paused_programs(&self) -> Vec<ProgramPause> {
+        let now = Utc::now();
+        self.paused_programs
+            .retain(|_, pause| pause.until.map_or(true, |until| until > now));
+        self.paused_programs
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
     /// Start the monitoring engine.
     pub async fn start(&self) -> EngineResult<()> {
         let mut state = self.state.write().await;
@@ -238,27 +885,37 @@ impl MonitoringEngine {
             errors: Vec::new(),
         };
 
-        // Check if engine is running
-        {
-            let state = self.state.read().await;
-            if !state.running {
-                return Err(EngineError::NotRunning);
-            }
+        self.ensure_running().await?;
+
+        if self.admit_events(1) == 0 {
+            debug!(
+                "Dropping event from {}: events per minute limit reached",
+                event.program_name
+            );
+            return Ok(result);
         }
 
-        // Record event metrics
-        self.metrics
-            .record_event(&event.program_name, event.event_type.as_str());
+        // Record event metrics and add event to history
+        self.record_event(&event).await;
 
-        // Add event to history
-        self.add_to_history(event.clone()).await;
+        // Paused programs keep history and metrics but skip rule evaluation
+        if self.is_program_paused(&event.program_name) {
+            if self.config.debug_logging {
+                debug!(
+                    "Skipping rule evaluation for paused program {}",
+                    event.program_name
+                );
+            }
+            result.duration = start_time.elapsed();
+            self.record_processing(&result, &[], 1).await;
+            return Ok(result);
+        }
 
         // Create rule context
         let context = self.create_rule_context(&event).await;
 
-        // Evaluate rules
-        let rules = self.rules.read().await;
-        let enabled_rules: Vec<_> = rules.iter().filter(|rule| rule.is_enabled()).collect();
+        // Evaluate rules in priority order
+        let enabled_rules = self.enabled_rules().await;
 
         if self.config.debug_logging {
             debug!(
@@ -268,117 +925,56 @@ impl MonitoringEngine {
             );
         }
 
-        // Process rules concurrently with timeout
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(
-            self.config.max_concurrent_evaluations,
-        ));
+        // Every rule queues for a slot at once, so when evaluations are
+        // saturated higher priority rules start first, across events too.
         let mut rule_tasks = Vec::new();
 
-        for rule in &enabled_rules {
-            let permit = semaphore.clone().acquire_owned().await.unwrap();
+        for rule in enabled_rules {
+            let scheduler = self.evaluation_scheduler.clone();
             let rule_name = rule.name().to_string();
-            let _event_clone = event.clone();
-            let _context_clone = context.clone();
+            let event_clone = event.clone();
+            let context_clone = self.context_for_rule(&context, &rule_name, &event.program_name);
             let metrics_clone = self.metrics.clone();
-            let rule_timeout = self.config.rule_timeout;
+            let rule_stats = self.rule_stats.clone();
+            let rule_timeout = self.config.rule_timeout_for(&rule_name);
 
-            // Create a simple struct to hold rule evaluation result without the rule itself
             let task = tokio::spawn(async move {
-                let _permit = permit; // Keep permit alive
-                let rule_start = Instant::now();
-
-                // Since we can't move the rule into the async block due to lifetime issues,
-                // we'll need to evaluate it synchronously here and just handle the result
-                let rule_result = match tokio::time::timeout(rule_timeout, async {
-                    // In practice, you'd want to restructure this to avoid the lifetime issue
-                    // For now, we'll return a placeholder
-                    Ok(crate::rules::RuleResult {
-                        rule_name: rule_name.clone(),
-                        triggered: false,
-                        message: None,
-                        severity: crate::rules::AlertSeverity::Info,
-                        metadata: std::collections::HashMap::new(),
-                        confidence: 0.0,
-                        suggested_actions: Vec::new(),
-                        timestamp: chrono::Utc::now(),
-                    })
-                })
+                let permit = scheduler.acquire(rule.priority()).await;
+                evaluate_rule(
+                    permit,
+                    rule,
+                    event_clone,
+                    context_clone,
+                    rule_timeout,
+                    &metrics_clone,
+                    &rule_stats,
+                )
                 .await
-                {
-                    Ok(result) => result,
-                    Err(_) => {
-                        error!("Rule evaluation timeout: {}", rule_name);
-                        return Err(EngineError::RuleTimeout {
-                            rule: rule_name.clone(),
-                        });
-                    }
-                };
-
-                let duration = rule_start.elapsed();
-
-                match rule_result {
-                    Ok(rule_result) => {
-                        metrics_clone.record_rule_evaluation(
-                            &rule_name,
-                            duration,
-                            rule_result.triggered,
-                        );
-                        Ok((rule_name.clone(), rule_result))
-                    }
-                    Err(e) => Err(e),
-                }
+                .map(|(rule_result, duration, _)| (rule_result, duration))
             });
 
-            rule_tasks.push(task);
+            rule_tasks.push((rule_name, task));
         }
 
-        drop(rules);
-
-        // Wait for all rule evaluations to complete
-        for task in rule_tasks {
-            match task.await {
-                Ok(Ok((rule_name, rule_result))) => {
+        // Wait for all rule evaluations to complete, dispatching alerts in priority order
+        let mut rule_durations = Vec::with_capacity(rule_tasks.len());
+        for (rule_name, task) in rule_tasks {
+            match join_evaluation(&rule_name, task.await) {
+                Ok((rule_result, rule_duration)) => {
                     result.rules_evaluated += 1;
-
-                    if rule_result.triggered {
-                        let severity_str = rule_result.severity.as_str().to_string();
-                        // Generate alert
-                        match self.generate_alert(rule_result, &event).await {
-                            Ok(_) => {
-                                result.alerts_generated += 1;
-                                self.metrics.record_alert(&rule_name, &severity_str);
-                            }
-                            Err(e) => {
-                                result.errors.push(format!(
-                                    "Alert generation failed for rule {}: {}",
-                                    rule_name, e
-                                ));
-                            }
-                        }
-                    }
-                }
-                Ok(Err(e)) => {
-                    result.errors.push(e.to_string());
+                    rule_durations.push(rule_duration);
+                    self.dispatch_result(&rule_name, rule_result, &event, &mut result)
+                        .await;
                 }
                 Err(e) => {
-                    result.errors.push(format!("Rule task failed: {}", e));
+                    self.dead_letter_rule(&rule_name, &event, &e);
+                    result.errors.push(e.to_string());
                 }
             }
         }
 
-        // Update state
-        {
-            let mut state = self.state.write().await;
-            state.events_processed += 1;
-            state.rules_evaluated += result.rules_evaluated as u64;
-            state.alerts_generated += result.alerts_generated as u64;
-        }
-
         result.duration = start_time.elapsed();
-
-        // Record processing latency
-        self.metrics
-            .record_event_processing_time(result.duration.as_secs_f64());
+        self.record_processing(&result, &rule_durations, 1).await;
 
         if self.config.debug_logging {
             debug!(
@@ -390,51 +986,530 @@ impl MonitoringEngine {
         Ok(result)
     }
 
-    /// Add event to history for rule context.
-    async fn add_to_history(&self, event: ProgramEvent) {
-        let program_key = format!("{}_{}", event.program_id, event.program_name);
+    /// Process a batch of events, returning totals for the whole batch.
+    ///
+    /// Events are grouped by program. Each event is added to history and its
+    /// rule context built before the next one is added, so `recent_events`
+    /// never holds events that arrived later. Every enabled rule then
+    /// evaluates the group's events in order within a single task.
+    pub async fn process_events(
+        &self,
+        events: Vec<ProgramEvent>,
+    ) -> EngineResult<ProcessingResult> {
+        let start_time = Instant::now();
+        let mut result = ProcessingResult {
+            rules_evaluated: 0,
+            alerts_generated: 0,
+            duration: Duration::default(),
+            errors: Vec::new(),
+        };
 
-        let mut entry = self.event_history.entry(program_key).or_default();
-        entry.push(event);
+        self.ensure_running().await?;
 
-        // Trim history to configured limits
-        let cutoff_time =
-            Utc::now() - chrono::Duration::from_std(self.config.max_history_age).unwrap();
-        entry.retain(|e| e.timestamp >= cutoff_time);
+        let mut events = events;
+        let admitted = self.admit_events(events.len());
+        if admitted < events.len() {
+            debug!(
+                "Dropping {} events: events per minute limit reached",
+                events.len() - admitted
+            );
+            events.truncate(admitted);
+        }
 
-        if entry.len() > self.config.max_history_events {
-            let excess = entry.len() - self.config.max_history_events;
-            entry.drain(0..excess);
+        let event_count = events.len();
+        if event_count == 0 {
+            return Ok(result);
         }
-    }
 
-    /// Create rule context for evaluation.
-    async fn create_rule_context(&self, event: &ProgramEvent) -> RuleContext {
-        let program_key = format!("{}_{}", event.program_id, event.program_name);
+        // Group events by program, keeping arrival order within each group
+        let mut group_index: HashMap<String, usize> = HashMap::new();
+        let mut groups: Vec<Vec<ProgramEvent>> = Vec::new();
+        for event in events {
+            let key = format!("{}_{}", event.program_id, event.program_name);
+            let index = *group_index.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(event);
+        }
 
-        let recent_events = self
-            .event_history
-            .get(&program_key)
-            .map(|entry| entry.clone())
-            .unwrap_or_default();
+        let enabled_rules = self.enabled_rules().await;
+        let mut rule_durations = Vec::new();
 
-        let metrics_snapshot = self.metrics.snapshot();
+        for group in groups {
+            let program_name = group[0].program_name.clone();
+            let paused = self.is_program_paused(&program_name);
 
-        RuleContext {
-            recent_events,
-            metrics: metrics_snapshot.values,
-            config: HashMap::new(), // Could be populated from configuration
-            timestamp: Utc::now(),
-        }
-    }
+            // History snapshots are cheap to take, one per event
+            let mut contexts = Vec::with_capacity(group.len());
+            for event in &group {
+                self.record_event(event).await;
+                if !paused {
+                    contexts.push(self.create_rule_context(event).await);
+                }
+            }
+
+            if paused {
+                continue;
+            }
+
+            let group = Arc::new(group);
+            let mut rule_tasks = Vec::with_capacity(enabled_rules.len());
+
+            for rule in &enabled_rules {
+                let scheduler = self.evaluation_scheduler.clone();
+                let rule = rule.clone();
+                let rule_name = rule.name().to_string();
+                let group = group.clone();
+                let contexts: Vec<RuleContext> = contexts
+                    .iter()
+                    .map(|context| self.context_for_rule(context, &rule_name, &program_name))
+                    .collect();
+                let metrics = self.metrics.clone();
+                let rule_stats = self.rule_stats.clone();
+                let rule_timeout = self.config.rule_timeout_for(&rule_name);
+
+                let task = tokio::spawn(async move {
+                    // One slot for the whole group, taken again after a
+                    // timeout left it with the abandoned evaluation
+                    let mut permit = None;
+                    let mut outcomes = Vec::with_capacity(group.len());
+                    for (index, (event, context)) in group.iter().zip(contexts).enumerate() {
+                        let held = match permit.take() {
+                            Some(held) => held,
+                            None => scheduler.acquire(rule.priority()).await,
+                        };
+                        let outcome = evaluate_rule(
+                            held,
+                            rule.clone(),
+                            event.clone(),
+                            context,
+                            rule_timeout,
+                            &metrics,
+                            &rule_stats,
+                        )
+                        .await
+                        .map(|(rule_result, duration, returned)| {
+                            permit = Some(returned);
+                            (rule_result, duration)
+                        });
+                        outcomes.push((index, outcome));
+                    }
+                    outcomes
+                });
+
+                rule_tasks.push((rule_name, task));
+            }
+
+            // Dispatch alerts in rule priority order, then event order
+            for (rule_name, task) in rule_tasks {
+                match task.await {
+                    Ok(outcomes) => {
+                        for (index, outcome) in outcomes {
+                            match outcome {
+                                Ok((rule_result, rule_duration)) => {
+                                    result.rules_evaluated += 1;
+                                    rule_durations.push(rule_duration);
+                                    self.dispatch_result(
+                                        &rule_name,
+                                        rule_result,
+                                        &group[index],
+                                        &mut result,
+                                    )
+                                    .await;
+                                }
+                                Err(e) => {
+                                    self.dead_letter_rule(&rule_name, &group[index], &e);
+                                    result.errors.push(e.to_string());
+                                }
+                            }
+                        }
+                    }
+                    Err(join_error) => {
+                        // A panic loses the whole group for this rule
+                        let e = join_evaluation(&rule_name, Err(join_error)).unwrap_err();
+                        for event in group.iter() {
+                            self.dead_letter_rule(&rule_name, event, &e);
+                        }
+                        result.errors.push(e.to_string());
+                    }
+                }
+            }
+        }
+
+        result.duration = start_time.elapsed();
+        self.record_processing(&result, &rule_durations, event_count)
+            .await;
+
+        if self.config.debug_logging {
+            debug!(
+                "Processed batch of {} events in {:?}: {} rules evaluated, {} alerts generated",
+                event_count, result.duration, result.rules_evaluated, result.alerts_generated
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Fail unless the engine is running.
+    async fn ensure_running(&self) -> EngineResult<()> {
+        if self.state.read().await.running {
+            Ok(())
+        } else {
+            Err(EngineError::NotRunning)
+        }
+    }
+
+    /// Record event metrics and add the event to history.
+    async fn record_event(&self, event: &ProgramEvent) {
+        self.metrics
+            .record_event(&event.program_name, event.event_type.as_str());
+        if event.is_transaction() {
+            if let Some(success) = event.is_successful() {
+                self.metrics.record_outcome(&event.program_name, success);
+            }
+        }
+
+        // Events are only cloned for the stream while someone is watching
+        if self.event_sender.receiver_count() > 0 {
+            let _ = self.event_sender.send(event.clone());
+        }
+
+        self.add_to_history(event.clone()).await;
+    }
+
+    /// Enabled rules in priority order.
+    async fn enabled_rules(&self) -> Vec<Arc<dyn Rule>> {
+        let rules = self.rules.read().await;
+        let rule_states = self.rule_states.read().await;
+        rules
+            .iter()
+            .filter(|rule| rule_states.get(rule.name()).unwrap_or(rule.is_enabled()))
+            .cloned()
+            .collect()
+    }
+
+    /// Copy of `context` with a rule's parameters and history budget applied.
+    fn context_for_rule(
+        &self,
+        context: &RuleContext,
+        rule_name: &str,
+        program_name: &str,
+    ) -> RuleContext {
+        let mut context = context.clone();
+        context.config = self.config.rule_config_for(rule_name, program_name);
+
+        if let Some(max_events) = self.config.max_history_events_for(rule_name) {
+            let excess = context.recent_events.len().saturating_sub(max_events);
+            if excess > 0 {
+                context.recent_events = context.recent_events.skip(excess);
+            }
+        }
+
+        context
+    }
 
-    /// Generate an alert from a rule result.
-    async fn generate_alert(
+    /// Generate an alert for a triggered rule result and update totals.
+    async fn dispatch_result(
         &self,
+        rule_name: &str,
         rule_result: RuleResult,
         event: &ProgramEvent,
-    ) -> EngineResult<()> {
-        let alert = Alert {
+        result: &mut ProcessingResult,
+    ) {
+        if !rule_result.triggered {
+            return;
+        }
+
+        let severity_str = rule_result.severity.as_str().to_string();
+        let alert = self.build_alert(rule_result, event).await;
+        if let Some(silence_id) = self.silences.suppress(&alert) {
+            debug!(
+                "Alert from rule {} for {} suppressed by silence {}",
+                rule_name, alert.program_name, silence_id
+            );
+            return;
+        }
+
+        match self.deliver_alert(alert.clone()).await {
+            Ok(_) => {
+                result.alerts_generated += 1;
+                self.metrics.record_alert(rule_name, &severity_str);
+            }
+            Err(e) => {
+                self.dead_letter(
+                    DeadLetterReason::AlertDispatch,
+                    rule_name,
+                    event,
+                    Some(alert),
+                    e.to_string(),
+                );
+                result.errors.push(format!(
+                    "Alert generation failed for rule {}: {}",
+                    rule_name, e
+                ));
+            }
+        }
+    }
+
+    /// Capture a failed rule evaluation in the dead-letter queue.
+    fn dead_letter_rule(&self, rule_name: &str, event: &ProgramEvent, error: &EngineError) {
+        let reason = match error {
+            EngineError::RuleTimeout { .. } => DeadLetterReason::RuleTimeout,
+            EngineError::RulePanic { .. } => DeadLetterReason::RulePanic,
+            _ => return,
+        };
+        self.dead_letter(reason, rule_name, event, None, error.to_string());
+    }
+
+    /// Capture a failure in the dead-letter queue, if enabled.
+    fn dead_letter(
+        &self,
+        reason: DeadLetterReason,
+        rule_name: &str,
+        event: &ProgramEvent,
+        alert: Option<Alert>,
+        error: String,
+    ) {
+        if !self.config.dead_letters.enabled {
+            return;
+        }
+
+        let id = self
+            .dead_letters
+            .push(reason, rule_name, event.clone(), alert, error);
+        self.metrics.record_dead_letter(rule_name, reason.as_str());
+        debug!(
+            "Captured dead letter {} for rule {} ({})",
+            id,
+            rule_name,
+            reason.as_str()
+        );
+    }
+
+    /// Dead-letter queue of failed rule evaluations and alert dispatches.
+    pub fn dead_letters(&self) -> Arc<DeadLetterQueue> {
+        self.dead_letters.clone()
+    }
+
+    /// Retry a dead letter.
+    ///
+    /// Alert dispatch failures re-send the captured alert; rule failures
+    /// re-evaluate the rule against the captured event with a fresh context.
+    /// The entry is removed on success and put back if the retry fails.
+    pub async fn retry_dead_letter(&self, id: u64) -> EngineResult<ProcessingResult> {
+        let entry = self
+            .dead_letters
+            .take(id)
+            .ok_or(EngineError::DeadLetterNotFound(id))?;
+
+        let start_time = Instant::now();
+        let mut result = ProcessingResult {
+            rules_evaluated: 0,
+            alerts_generated: 0,
+            duration: Duration::default(),
+            errors: Vec::new(),
+        };
+
+        let outcome = match &entry.alert {
+            Some(alert) => {
+                let severity = alert.severity;
+                self.deliver_alert(alert.clone()).await.map(|_| {
+                    result.alerts_generated += 1;
+                    self.metrics
+                        .record_alert(&entry.rule_name, severity.as_str());
+                })
+            }
+            None => match self.reevaluate(&entry.rule_name, &entry.event).await {
+                Ok((rule_result, _)) => {
+                    result.rules_evaluated += 1;
+                    self.dispatch_result(&entry.rule_name, rule_result, &entry.event, &mut result)
+                        .await;
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            },
+        };
+
+        result.duration = start_time.elapsed();
+        match outcome {
+            Ok(()) => {
+                info!("Retried dead letter {} for rule {}", id, entry.rule_name);
+                Ok(result)
+            }
+            Err(e) => {
+                self.dead_letters.requeue(entry, e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Evaluate a rule against a sample event without generating alerts or
+    /// recording evaluation statistics.
+    ///
+    /// The rule sees the current history and metrics of the event's program.
+    /// Rules keeping state of their own are evaluated as a fresh instance, so
+    /// the sample never enters the state of the live rule.
+    pub async fn test_rule(
+        &self,
+        rule_name: &str,
+        event: &ProgramEvent,
+    ) -> EngineResult<RuleResult> {
+        let rule = self
+            .rules
+            .read()
+            .await
+            .iter()
+            .find(|rule| rule.name() == rule_name)
+            .cloned()
+            .ok_or_else(|| EngineError::RuleNotFound(rule_name.to_string()))?;
+        let rule = rule.fresh_instance().map(Arc::from).unwrap_or(rule);
+
+        let context = self.create_rule_context(event).await;
+        let context = self.context_for_rule(&context, rule_name, &event.program_name);
+        let event = event.clone();
+        let rule_timeout = self.config.rule_timeout_for(rule_name);
+        let scheduler = self.evaluation_scheduler.clone();
+
+        let task = tokio::spawn(async move {
+            let rule_name = rule.name().to_string();
+            let permit = scheduler.acquire(rule.priority()).await;
+            run_evaluation(permit, rule, event, context, rule_timeout)
+                .await
+                .map(|(result, busy, _)| (result, busy))
+                .ok_or(EngineError::RuleTimeout { rule: rule_name })
+        });
+        join_evaluation(rule_name, task.await).map(|(result, _)| result)
+    }
+
+    /// Evaluate a single rule against an event outside the normal pipeline.
+    async fn reevaluate(
+        &self,
+        rule_name: &str,
+        event: &ProgramEvent,
+    ) -> EngineResult<(RuleResult, Duration)> {
+        let rule = self
+            .rules
+            .read()
+            .await
+            .iter()
+            .find(|rule| rule.name() == rule_name)
+            .cloned()
+            .ok_or_else(|| EngineError::RuleNotFound(rule_name.to_string()))?;
+
+        let context = self.create_rule_context(event).await;
+        let context = self.context_for_rule(&context, rule_name, &event.program_name);
+        let event = event.clone();
+        let metrics = self.metrics.clone();
+        let rule_stats = self.rule_stats.clone();
+        let rule_timeout = self.config.rule_timeout_for(rule_name);
+        let scheduler = self.evaluation_scheduler.clone();
+
+        let task = tokio::spawn(async move {
+            let permit = scheduler.acquire(rule.priority()).await;
+            evaluate_rule(
+                permit,
+                rule,
+                event,
+                context,
+                rule_timeout,
+                &metrics,
+                &rule_stats,
+            )
+            .await
+            .map(|(rule_result, duration, _)| (rule_result, duration))
+        });
+        join_evaluation(rule_name, task.await)
+    }
+
+    /// Update engine state and latency metrics after processing `events`
+    /// events, spreading the measured duration evenly across them.
+    async fn record_processing(
+        &self,
+        result: &ProcessingResult,
+        rule_durations: &[Duration],
+        events: usize,
+    ) {
+        let events = events.max(1);
+        let per_event = result.duration / events as u32;
+
+        let performance = {
+            let mut tracker = self.performance.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            tracker.record_event(per_event, rule_durations, now);
+            for _ in 1..events {
+                tracker.record_event(per_event, &[], now);
+            }
+            tracker.stats(now)
+        };
+
+        {
+            let mut state = self.state.write().await;
+            state.events_processed += events as u64;
+            state.rules_evaluated += result.rules_evaluated as u64;
+            state.alerts_generated += result.alerts_generated as u64;
+            state.performance = performance;
+        }
+
+        // Record processing latency
+        for _ in 0..events {
+            self.metrics
+                .record_event_processing_time(per_event.as_secs_f64());
+        }
+    }
+
+    /// Add event to history for rule context.
+    async fn add_to_history(&self, event: ProgramEvent) {
+        let program_key = format!("{}_{}", event.program_id, event.program_name);
+
+        self.addresses.record(&event);
+
+        let evicted = self.event_history.push(&program_key, event);
+        if evicted > 0 {
+            debug!("Evicted {} events to stay within history budget", evicted);
+            self.metrics.record_history_evictions(evicted as u64);
+        }
+
+        let usage = self.event_history.usage();
+        self.metrics
+            .update_history_usage(usage.programs, usage.events, usage.bytes);
+    }
+
+    /// Create rule context for evaluation.
+    async fn create_rule_context(&self, event: &ProgramEvent) -> RuleContext {
+        let program_key = format!("{}_{}", event.program_id, event.program_name);
+
+        let recent_events = self.event_history.get_shared(&program_key);
+
+        RuleContext {
+            recent_events,
+            metrics: Arc::new(self.metrics.rule_values()),
+            config: HashMap::new(), // Filled per rule from `EngineConfig::rule_config`
+            timestamp: Utc::now(),
+            addresses: self.addresses.clone(),
+        }
+    }
+
+    /// Per-address activity aggregates.
+    pub fn addresses(&self) -> Arc<AddressAggregator> {
+        self.addresses.clone()
+    }
+
+    /// Build an enriched alert from a rule result.
+    async fn build_alert(&self, rule_result: RuleResult, event: &ProgramEvent) -> Alert {
+        let tags = self
+            .config
+            .tags_for(&rule_result.rule_name, &event.program_name);
+
+        // Adjust the rule's confidence by its historical precision
+        let mut metadata = rule_result.metadata;
+        let stats = self.rule_stats(&rule_result.rule_name).unwrap_or_default();
+        let confidence = self.calibrator.calibrate(rule_result.confidence, &stats);
+        if confidence != rule_result.confidence {
+            metadata.insert("raw_confidence".to_string(), rule_result.confidence.into());
+        }
+
+        let mut alert = Alert {
             id: uuid::Uuid::new_v4().to_string(),
             rule_name: rule_result.rule_name,
             message: rule_result
@@ -444,14 +1519,22 @@ impl MonitoringEngine {
             program_id: event.program_id,
             program_name: event.program_name.clone(),
             event_id: Some(event.id.clone()),
-            metadata: rule_result.metadata,
-            confidence: rule_result.confidence,
+            metadata,
+            confidence,
             suggested_actions: rule_result.suggested_actions,
             timestamp: rule_result.timestamp,
             acknowledged: false,
             resolved: false,
+            tags,
         };
 
+        // Attach links, symbols and values before dispatch
+        self.enrichment.read().await.enrich(&mut alert, event);
+        alert
+    }
+
+    /// Send an alert through the manager and broadcast it to subscribers.
+    async fn deliver_alert(&self, alert: Alert) -> EngineResult<()> {
         // Send alert through manager
         self.alert_manager
             .send_alert(alert.clone())
@@ -468,7 +1551,17 @@ impl MonitoringEngine {
 
     /// Get current engine state.
     pub async fn state(&self) -> EngineState {
-        self.state.read().await.clone()
+        let mut state = self.state.read().await.clone();
+        state.performance = self.performance_stats();
+        state
+    }
+
+    /// Get current performance statistics.
+    pub fn performance_stats(&self) -> PerformanceStats {
+        self.performance
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .stats(Instant::now())
     }
 
     /// Get metrics snapshot.
@@ -481,6 +1574,90 @@ impl MonitoringEngine {
         self.alert_sender.subscribe()
     }
 
+    /// Subscribe to processed program events.
+    pub fn subscribe_to_events(&self) -> broadcast::Receiver<ProgramEvent> {
+        self.event_sender.subscribe()
+    }
+
+    /// Limit events processed per minute; events beyond it are dropped
+    /// before reaching history or rules. `None` removes the limit.
+    pub fn set_max_events_per_minute(&self, max_per_minute: Option<u32>) {
+        let mut limit = self
+            .event_rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        limit.max_per_minute = max_per_minute;
+        info!(
+            "Events per minute limit set to {}",
+            max_per_minute.map_or("unlimited".to_string(), |max| max.to_string())
+        );
+    }
+
+    /// Current limit on events processed per minute.
+    pub fn max_events_per_minute(&self) -> Option<u32> {
+        self.event_rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .max_per_minute
+    }
+
+    /// How many of `count` events fit in the current minute's limit.
+    fn admit_events(&self, count: usize) -> usize {
+        let admitted = self
+            .event_rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .admit(count, Instant::now());
+        if admitted < count {
+            self.metrics
+                .record_dropped_events((count - admitted) as u64);
+        }
+        admitted
+    }
+
+    /// Events dropped by the events per minute limit since the engine started.
+    pub fn events_dropped(&self) -> u64 {
+        self.event_rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .dropped_total
+    }
+
+    /// Messages waiting in the engine's queues.
+    pub fn queue_depths(&self) -> QueueDepths {
+        QueueDepths {
+            events: self.event_sender.len(),
+            alerts: self.alert_sender.len(),
+            dead_letters: self.dead_letters.len(),
+        }
+    }
+
+    /// File rule overrides and definitions are persisted to, if any.
+    pub async fn rule_state_path(&self) -> Option<PathBuf> {
+        self.rule_states.read().await.path().map(PathBuf::from)
+    }
+
+    /// Most recent events in history across all programs matching `filter`,
+    /// newest first.
+    pub fn recent_events(
+        &self,
+        limit: usize,
+        filter: impl Fn(&ProgramEvent) -> bool,
+    ) -> Vec<ProgramEvent> {
+        self.event_history.latest(limit, filter)
+    }
+
+    /// Event of a program still held in history, e.g. the one that triggered an alert.
+    pub fn find_event(
+        &self,
+        program_id: &str,
+        program_name: &str,
+        event_id: &str,
+    ) -> Option<ProgramEvent> {
+        let program_key = format!("{}_{}", program_id, program_name);
+        self.event_history.find(&program_key, event_id)
+    }
+
     /// Get event history for a program.
     pub async fn get_event_history(
         &self,
@@ -488,15 +1665,18 @@ impl MonitoringEngine {
         program_name: &str,
     ) -> Vec<ProgramEvent> {
         let program_key = format!("{}_{}", program_id, program_name);
-        self.event_history
-            .get(&program_key)
-            .map(|entry| entry.clone())
-            .unwrap_or_default()
+        self.event_history.get(&program_key)
+    }
+
+    /// Get current event history usage.
+    pub fn history_usage(&self) -> HistoryUsage {
+        self.event_history.usage()
     }
 
     /// Clear event history.
     pub async fn clear_history(&self) {
         self.event_history.clear();
+        self.metrics.update_history_usage(0, 0, 0);
         info!("Cleared event history");
     }
 
@@ -508,11 +1688,13 @@ impl MonitoringEngine {
         EngineStatistics {
             uptime: uptime.to_std().unwrap_or_default(),
             events_processed: state.events_processed,
+            events_dropped: self.events_dropped(),
             rules_evaluated: state.rules_evaluated,
             alerts_generated: state.alerts_generated,
             rules_registered: self.rules.read().await.len(),
-            programs_monitored: self.event_history.len(),
-            performance: state.performance.clone(),
+            programs_monitored: self.event_history.program_count(),
+            dead_letters: self.dead_letters.len(),
+            performance: self.performance_stats(),
         }
     }
 }
@@ -526,6 +1708,9 @@ pub struct EngineStatistics {
     /// Total events processed
     pub events_processed: u64,
 
+    /// Events dropped by the events per minute limit
+    pub events_dropped: u64,
+
     /// Total rules evaluated
     pub rules_evaluated: u64,
 
@@ -538,77 +1723,739 @@ pub struct EngineStatistics {
     /// Number of programs being monitored
     pub programs_monitored: usize,
 
+    /// Failures waiting in the dead-letter queue
+    pub dead_letters: usize,
+
     /// Performance statistics
     pub performance: PerformanceStats,
 }
 
-impl Default for EngineConfig {
-    fn default() -> Self {
+/// Messages waiting in the engine's queues.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct QueueDepths {
+    /// Processed events not yet taken by their slowest subscriber
+    pub events: usize,
+
+    /// Alerts not yet taken by their slowest subscriber
+    pub alerts: usize,
+
+    /// Failures waiting in the dead-letter queue
+    pub dead_letters: usize,
+}
+
+impl PerformanceTracker {
+    fn new() -> Self {
         Self {
-            max_history_events: 1000,
-            max_history_age: Duration::from_secs(3600), // 1 hour
-            metrics_interval: Duration::from_secs(60),  // 1 minute
-            max_concurrent_evaluations: 100,
-            rule_timeout: Duration::from_secs(30),
-            debug_logging: false,
+            avg_event_time: None,
+            avg_rule_time: None,
+            events_per_second: 0.0,
+            peak_events_per_second: 0.0,
+            sample_start: Instant::now(),
+            sample_events: 0,
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{alerts::AlertManager, metrics::MetricsCollector, rules::LargeTransactionRule};
-    use solana_sdk::pubkey::Pubkey;
-    use watchtower_subscriber::{EventData, EventType, ProgramEvent};
+    /// Record a processed event and the durations of its rule evaluations.
+    fn record_event(&mut self, duration: Duration, rule_durations: &[Duration], now: Instant) {
+        self.avg_event_time = Some(smooth(self.avg_event_time, duration.as_secs_f64()));
+        for rule_duration in rule_durations {
+            self.avg_rule_time = Some(smooth(self.avg_rule_time, rule_duration.as_secs_f64()));
+        }
 
-    #[tokio::test]
-    async fn test_engine_creation() {
-        let metrics = Arc::new(MetricsCollector::new().unwrap());
-        let alert_manager = Arc::new(AlertManager::new());
-        let config = EngineConfig::default();
+        let elapsed = now.saturating_duration_since(self.sample_start);
+        if elapsed >= RATE_SAMPLE_INTERVAL {
+            self.events_per_second = self.decayed_rate(elapsed);
+            self.peak_events_per_second = self.peak_events_per_second.max(self.events_per_second);
+            self.sample_start = now;
+            self.sample_events = 0;
+        }
 
-        let engine = MonitoringEngine::new(metrics, alert_manager, config);
-        assert!(!engine.state().await.running);
+        self.sample_events += 1;
     }
 
-    #[tokio::test]
-    async fn test_rule_management() {
-        let metrics = Arc::new(MetricsCollector::new().unwrap());
-        let alert_manager = Arc::new(AlertManager::new());
-        let config = EngineConfig::default();
+    /// Events-per-second average including the in-progress sample.
+    fn decayed_rate(&self, elapsed: Duration) -> f64 {
+        let observed = self.sample_events as f64 / elapsed.as_secs_f64();
+        let alpha = 1.0 - (-elapsed.as_secs_f64() / RATE_TIME_CONSTANT.as_secs_f64()).exp();
+        self.events_per_second + alpha * (observed - self.events_per_second)
+    }
 
-        let engine = MonitoringEngine::new(metrics, alert_manager, config);
+    /// Current statistics; the rate decays towards zero while idle.
+    fn stats(&self, now: Instant) -> PerformanceStats {
+        let elapsed = now.saturating_duration_since(self.sample_start);
+        let current_events_per_second = if elapsed >= RATE_SAMPLE_INTERVAL {
+            self.decayed_rate(elapsed)
+        } else {
+            self.events_per_second
+        };
 
-        // Add rule
-        let rule = Box::new(LargeTransactionRule::new(1.0, 1000000));
-        engine.add_rule(rule).await;
+        PerformanceStats {
+            avg_event_processing_time: Duration::from_secs_f64(
+                self.avg_event_time.unwrap_or_default(),
+            ),
+            avg_rule_evaluation_time: Duration::from_secs_f64(
+                self.avg_rule_time.unwrap_or_default(),
+            ),
+            peak_events_per_second: self.peak_events_per_second.max(current_events_per_second),
+            current_events_per_second,
+            memory_usage_bytes: resident_memory_bytes(),
+        }
+    }
+}
 
-        let rules = engine.list_rules().await;
-        assert_eq!(rules.len(), 1);
-        assert_eq!(rules[0], "large_transaction");
+impl EventRateLimit {
+    fn new(max_per_minute: Option<u32>) -> Self {
+        Self {
+            max_per_minute,
+            window_start: Instant::now(),
+            admitted: 0,
+            dropped: 0,
+            dropped_total: 0,
+        }
+    }
 
-        // Remove rule
-        let removed = engine.remove_rule("large_transaction").await;
-        assert!(removed);
+    /// Admit up to `count` events at `now`, returning how many were admitted.
+    ///
+    /// Drops are logged when a window starts dropping and again, with their
+    /// count, once it has closed, rather than once per event.
+    fn admit(&mut self, count: usize, now: Instant) -> usize {
+        if now.duration_since(self.window_start) >= EVENT_RATE_WINDOW {
+            if self.dropped > 0 {
+                warn!(
+                    "Dropped {} events over the events per minute limit in the last window",
+                    self.dropped
+                );
+            }
+            self.window_start = now;
+            self.admitted = 0;
+            self.dropped = 0;
+        }
 
-        let rules = engine.list_rules().await;
-        assert_eq!(rules.len(), 0);
+        let Some(max) = self.max_per_minute else {
+            return count;
+        };
+        let admitted = count.min(max.saturating_sub(self.admitted) as usize);
+        self.admitted += admitted as u32;
+
+        let dropped = (count - admitted) as u64;
+        if dropped > 0 && self.dropped == 0 {
+            warn!(
+                "Events per minute limit of {} reached, dropping events until the window resets",
+                max
+            );
+        }
+        self.dropped += dropped;
+        self.dropped_total += dropped;
+        admitted
     }
+}
 
-    #[tokio::test]
-    async fn test_event_processing() {
-        let metrics = Arc::new(MetricsCollector::new().unwrap());
-        let alert_manager = Arc::new(AlertManager::new());
-        let config = EngineConfig::default();
+/// Exponential moving average update.
+fn smooth(average: Option<f64>, sample: f64) -> f64 {
+    match average {
+        Some(avg) => avg + LATENCY_SMOOTHING * (sample - avg),
+        None => sample,
+    }
+}
 
-        let engine = MonitoringEngine::new(metrics, alert_manager, config);
-        engine.start().await.unwrap();
+/// Resident memory of the current process, where the platform exposes it.
+fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
 
-        let event = ProgramEvent::new(
-            Pubkey::new_unique(),
-            "Test Program".to_string(),
-            EventType::TokenTransfer,
+impl RuleStats {
+    /// Record a completed evaluation, with the trigger time if it triggered.
+    pub fn record_evaluation(&mut self, duration: Duration, triggered_at: Option<DateTime<Utc>>) {
+        self.evaluations += 1;
+
+        // Running mean avoids keeping every sample
+        let avg = self.avg_evaluation_time.as_secs_f64();
+        let updated = avg + (duration.as_secs_f64() - avg) / self.evaluations as f64;
+        self.avg_evaluation_time = Duration::from_secs_f64(updated.max(0.0));
+
+        if let Some(timestamp) = triggered_at {
+            self.triggers += 1;
+            self.last_triggered = Some(timestamp);
+        }
+    }
+}
+
+impl EngineConfig {
+    /// Evaluation timeout for a rule, falling back to the global timeout.
+    pub fn rule_timeout_for(&self, rule_name: &str) -> Duration {
+        self.rule_budgets
+            .get(rule_name)
+            .and_then(|budget| budget.timeout)
+            .unwrap_or(self.rule_timeout)
+    }
+
+    /// Parameters passed to a rule when evaluating an event from `program_name`.
+    pub fn rule_config_for(
+        &self,
+        rule_name: &str,
+        program_name: &str,
+    ) -> HashMap<String, serde_json::Value> {
+        let Some(rule_config) = self.rule_config.get(rule_name) else {
+            return HashMap::new();
+        };
+
+        let mut config: HashMap<_, _> = rule_config
+            .iter()
+            .filter(|(key, _)| key.as_str() != PROGRAM_OVERRIDES_KEY)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        if let Some(overrides) = rule_config
+            .get(PROGRAM_OVERRIDES_KEY)
+            .and_then(|programs| programs.get(program_name))
+            .and_then(|overrides| overrides.as_object())
+        {
+            config.extend(overrides.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        config
+    }
+
+    /// Tags of an alert raised by `rule_name` for `program_name`.
+    ///
+    /// Program and rule tags are merged, sorted and deduplicated.
+    pub fn tags_for(&self, rule_name: &str, program_name: &str) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .program_tags
+            .get(program_name)
+            .into_iter()
+            .chain(self.rule_tags.get(rule_name))
+            .flatten()
+            .cloned()
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// History size limit for a rule, if one is configured.
+    pub fn max_history_events_for(&self, rule_name: &str) -> Option<usize> {
+        self.rule_budgets
+            .get(rule_name)
+            .and_then(|budget| budget.max_history_events)
+    }
+}
+
+/// Insert a rule after the rules of its priority and higher.
+fn insert_rule(rules: &mut Vec<Arc<dyn Rule>>, rule: Box<dyn Rule>) {
+    info!(
+        "Adding rule: {} (priority {})",
+        rule.name(),
+        rule.priority()
+    );
+
+    let position = rules
+        .iter()
+        .position(|existing| existing.priority() < rule.priority())
+        .unwrap_or(rules.len());
+    rules.insert(position, Arc::from(rule));
+}
+
+/// Evaluate one rule against one event in the slot of `permit`, recording
+/// timing and statistics.
+///
+/// The permit is handed back with the result. After a timeout it stays with
+/// the abandoned evaluation.
+async fn evaluate_rule(
+    permit: EvaluationPermit,
+    rule: Arc<dyn Rule>,
+    event: ProgramEvent,
+    context: RuleContext,
+    rule_timeout: Duration,
+    metrics: &MetricsCollector,
+    rule_stats: &DashMap<String, RuleStats>,
+) -> EngineResult<(RuleResult, Duration, EvaluationPermit)> {
+    let rule_name = rule.name().to_string();
+    let rule_start = Instant::now();
+
+    let (rule_result, busy, permit) =
+        match run_evaluation(permit, rule, event, context, rule_timeout).await {
+            Some(result) => result,
+            None => {
+                error!("Rule evaluation timeout: {}", rule_name);
+                metrics.record_rule_timeout(&rule_name);
+                rule_stats.entry(rule_name.clone()).or_default().timeouts += 1;
+                return Err(EngineError::RuleTimeout { rule: rule_name });
+            }
+        };
+
+    let duration = rule_start.elapsed();
+    metrics.record_rule_cpu_time(&rule_name, busy);
+    metrics.record_rule_evaluation(&rule_name, duration, rule_result.triggered);
+    rule_stats
+        .entry(rule_name)
+        .or_default()
+        .record_evaluation(duration, rule_result.triggered.then(Utc::now));
+    Ok((rule_result, duration, permit))
+}
+
+/// Run a rule's evaluation on the blocking pool, giving up after `rule_timeout`.
+///
+/// A tokio timeout only fires when the future it wraps yields, so a rule stuck
+/// computing on a runtime worker would never time out and would stall every
+/// task on that worker. On its own thread the evaluation can be abandoned on
+/// time; it keeps that thread, and the permit, until it returns, so abandoned
+/// evaluations still count against `max_concurrent_evaluations` and cannot
+/// exhaust the blocking pool. Returns the result, the time spent polling and
+/// the permit, or `None` on timeout.
+async fn run_evaluation(
+    permit: EvaluationPermit,
+    rule: Arc<dyn Rule>,
+    event: ProgramEvent,
+    context: RuleContext,
+    rule_timeout: Duration,
+) -> Option<(RuleResult, Duration, EvaluationPermit)> {
+    let runtime = tokio::runtime::Handle::current();
+    let evaluation = tokio::task::spawn_blocking(move || {
+        let (result, busy) = runtime.block_on(BusyTimed::new(rule.evaluate(&event, &context)));
+        (result, busy, permit)
+    });
+
+    match tokio::time::timeout(rule_timeout, evaluation).await {
+        Ok(Ok(result)) => Some(result),
+        // Re-raise a panic so the spawning task reports it as `RulePanic`
+        Ok(Err(e)) => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => None,
+    }
+}
+
+/// Flatten a spawned rule evaluation, turning a panic into `RulePanic`.
+fn join_evaluation(
+    rule_name: &str,
+    joined: Result<EngineResult<(RuleResult, Duration)>, tokio::task::JoinError>,
+) -> EngineResult<(RuleResult, Duration)> {
+    match joined {
+        Ok(outcome) => outcome,
+        Err(e) if e.is_panic() => {
+            error!("Rule evaluation panicked: {}", rule_name);
+            Err(EngineError::RulePanic {
+                rule: rule_name.to_string(),
+            })
+        }
+        Err(e) => Err(EngineError::Internal(format!("Rule task failed: {}", e))),
+    }
+}
+
+/// Future wrapper that measures time spent inside `poll`.
+///
+/// This approximates the CPU time of an async rule evaluation without
+/// counting time spent waiting on I/O or timers.
+struct BusyTimed<F> {
+    inner: Pin<Box<F>>,
+    busy: Duration,
+}
+
+impl<F: Future> BusyTimed<F> {
+    fn new(inner: F) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            busy: Duration::default(),
+        }
+    }
+}
+
+impl<F: Future> Future for BusyTimed<F> {
+    type Output = (F::Output, Duration);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let started = Instant::now();
+        let poll = self.inner.as_mut().poll(cx);
+        self.busy += started.elapsed();
+
+        poll.map(|output| (output, self.busy))
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            max_history_events: 1000,
+            max_history_age: Duration::from_secs(3600), // 1 hour
+            max_history_bytes: None,
+            window_state_path: None,
+            metrics_interval: Duration::from_secs(60), // 1 minute
+            max_concurrent_evaluations: 100,
+            rule_timeout: Duration::from_secs(30),
+            debug_logging: false,
+            rule_budgets: HashMap::new(),
+            rule_state_path: None,
+            delivery_history_path: None,
+            rule_config: HashMap::new(),
+            metrics: MetricsConfig::default(),
+            aggregation: AggregationConfig::default(),
+            enrichment: EnrichmentConfig::default(),
+            program_tags: HashMap::new(),
+            rule_tags: HashMap::new(),
+            dead_letters: DeadLetterConfig::default(),
+            calibration: CalibrationConfig::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alerts::AlertManager, metrics::MetricsCollector, rules::LargeTransactionRule};
+    use solana_sdk::pubkey::Pubkey;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+    use watchtower_subscriber::{EventData, EventType, ProgramEvent};
+
+    #[tokio::test]
+    async fn test_engine_creation() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let config = EngineConfig::default();
+
+        let engine = MonitoringEngine::new(metrics, alert_manager, config);
+        assert!(!engine.state().await.running);
+    }
+
+    #[tokio::test]
+    async fn test_rule_management() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let config = EngineConfig::default();
+
+        let engine = MonitoringEngine::new(metrics, alert_manager, config);
+
+        // Add rule
+        let rule = Box::new(LargeTransactionRule::new(1.0, 1000000));
+        engine.add_rule(rule).await;
+
+        let rules = engine.list_rules().await;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0], "large_transaction");
+
+        // Remove rule
+        let removed = engine.remove_rule("large_transaction").await;
+        assert!(removed);
+
+        let rules = engine.list_rules().await;
+        assert_eq!(rules.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_declarative_rule_crud() {
+        use crate::rules::ChangeDirection;
+
+        let dir =
+            std::env::temp_dir().join(format!("watchtower-rule-crud-{}", uuid::Uuid::new_v4()));
+        let config = EngineConfig {
+            rule_state_path: Some(dir.join("rules.json")),
+            ..Default::default()
+        };
+        let rule = |name: &str, threshold_pct: f64| RateOfChangeConfig {
+            name: name.to_string(),
+            metric: "{program}.tvl".to_string(),
+            threshold_pct,
+            window_seconds: 300,
+            direction: ChangeDirection::Decrease,
+            severity: crate::rules::AlertSeverity::High,
+            priority: None,
+        };
+
+        let engine = MonitoringEngine::new(
+            Arc::new(MetricsCollector::new().unwrap()),
+            Arc::new(AlertManager::new()),
+            config.clone(),
+        );
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+        engine
+            .load_declarative_rules(&[rule("tvl_drop", 20.0), rule("volume_drop", 50.0)])
+            .await;
+        assert_eq!(engine.list_rules().await.len(), 3);
+
+        // Validation, name conflicts and non-declarative rules are rejected
+        assert!(matches!(
+            engine.create_rule(rule("bad", 0.0)).await,
+            Err(EngineError::InvalidRule(_))
+        ));
+        assert!(matches!(
+            engine.create_rule(rule("tvl_drop", 10.0)).await,
+            Err(EngineError::RuleExists(_))
+        ));
+
+        // Of concurrent creations of one name, only one succeeds
+        let (first, second) = tokio::join!(
+            engine.create_rule(rule("fees_drop", 10.0)),
+            engine.create_rule(rule("fees_drop", 20.0))
+        );
+        assert!(first.is_ok() != second.is_ok());
+        assert_eq!(
+            engine
+                .list_rules()
+                .await
+                .iter()
+                .filter(|name| *name == "fees_drop")
+                .count(),
+            1
+        );
+        assert!(engine.delete_rule("fees_drop").await.is_ok());
+        assert!(matches!(
+            engine
+                .update_rule("large_transaction", rule("large_transaction", 10.0))
+                .await,
+            Err(EngineError::RuleNotDeclarative(_))
+        ));
+        assert!(matches!(
+            engine
+                .update_rule("tvl_drop", rule("tvl_crash", 10.0))
+                .await,
+            Err(EngineError::InvalidRule(_))
+        ));
+
+        engine
+            .create_rule(rule("supply_spike", 30.0))
+            .await
+            .unwrap();
+        engine.set_rule_enabled("tvl_drop", false).await.unwrap();
+        engine
+            .update_rule("tvl_drop", rule("tvl_drop", 10.0))
+            .await
+            .unwrap();
+        assert_eq!(engine.is_rule_enabled("tvl_drop").await, Some(false));
+        engine.delete_rule("volume_drop").await.unwrap();
+
+        // A restarted engine sees the runtime changes over the config file
+        let restarted = MonitoringEngine::new(
+            Arc::new(MetricsCollector::new().unwrap()),
+            Arc::new(AlertManager::new()),
+            config,
+        );
+        restarted
+            .load_declarative_rules(&[rule("tvl_drop", 20.0), rule("volume_drop", 50.0)])
+            .await;
+        let names: Vec<_> = restarted
+            .declarative_rules()
+            .await
+            .into_iter()
+            .map(|rule| (rule.name, rule.threshold_pct))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("supply_spike".to_string(), 30.0),
+                ("tvl_drop".to_string(), 10.0)
+            ]
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rules_ordered_by_priority() {
+        use crate::rules::{
+            AlertSeverity, ChangeDirection, FailureRateRule, OracleDeviationRule, RateOfChangeRule,
+        };
+
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+
+        engine
+            .add_rule(Box::new(FailureRateRule::new(25.0, 10, 300)))
+            .await;
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+        engine
+            .add_rule(Box::new(OracleDeviationRule::new(5.0, "ref".to_string())))
+            .await;
+        engine
+            .add_rule(Box::new(
+                RateOfChangeRule::new("analytics", "m", 10.0, 60, ChangeDirection::Either)
+                    .with_severity(AlertSeverity::Critical)
+                    .with_priority(Some(0)),
+            ))
+            .await;
+
+        let rules = engine.list_rules().await;
+        assert_eq!(
+            rules,
+            vec![
+                "oracle_deviation",
+                "high_failure_rate",
+                "large_transaction",
+                "analytics"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_runtime_rule_toggle() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+        assert_eq!(
+            engine.is_rule_enabled("large_transaction").await,
+            Some(true)
+        );
+
+        engine.disable_rule("large_transaction").await.unwrap();
+        assert_eq!(
+            engine.is_rule_enabled("large_transaction").await,
+            Some(false)
+        );
+        assert!(!engine.rule_statuses().await[0].enabled);
+
+        engine.enable_rule("large_transaction").await.unwrap();
+        assert_eq!(
+            engine.is_rule_enabled("large_transaction").await,
+            Some(true)
+        );
+
+        assert!(matches!(
+            engine.disable_rule("missing").await,
+            Err(EngineError::RuleNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_rule_stats_recording() {
+        let mut stats = RuleStats::default();
+        stats.record_evaluation(Duration::from_millis(10), None);
+        stats.record_evaluation(Duration::from_millis(30), Some(Utc::now()));
+
+        assert_eq!(stats.evaluations, 2);
+        assert_eq!(stats.triggers, 1);
+        assert!(stats.last_triggered.is_some());
+        assert_eq!(stats.avg_evaluation_time, Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_rule_config_for_program() {
+        let mut config = EngineConfig::default();
+        config.rule_config.insert(
+            "liquidity_drop".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "threshold_pct": 10.0,
+                "window_sec": 300,
+                "programs": { "Orca": { "threshold_pct": 5.0 } }
+            }))
+            .unwrap(),
+        );
+
+        let orca = config.rule_config_for("liquidity_drop", "Orca");
+        assert_eq!(orca["threshold_pct"], serde_json::json!(5.0));
+        assert_eq!(orca["window_sec"], serde_json::json!(300));
+        assert!(!orca.contains_key("programs"));
+
+        let other = config.rule_config_for("liquidity_drop", "Raydium");
+        assert_eq!(other["threshold_pct"], serde_json::json!(10.0));
+
+        assert!(config.rule_config_for("unknown", "Orca").is_empty());
+    }
+
+    #[test]
+    fn test_tags_for_merges_program_and_rule_tags() {
+        let mut config = EngineConfig::default();
+        config.program_tags.insert(
+            "Solend".to_string(),
+            vec!["team:lending".to_string(), "tier:1".to_string()],
+        );
+        config.rule_tags.insert(
+            "denylist".to_string(),
+            vec!["team:security".to_string(), "tier:1".to_string()],
+        );
+
+        assert_eq!(
+            config.tags_for("denylist", "Solend"),
+            vec!["team:lending", "team:security", "tier:1"]
+        );
+        assert_eq!(config.tags_for("liquidity_drop", "Solend").len(), 2);
+        assert!(config.tags_for("liquidity_drop", "Orca").is_empty());
+    }
+
+    #[test]
+    fn test_performance_tracker() {
+        let start = Instant::now();
+        let mut tracker = PerformanceTracker::new();
+        tracker.sample_start = start;
+
+        // 10 events per second for 5 seconds
+        for i in 0..50u64 {
+            tracker.record_event(
+                Duration::from_millis(4),
+                &[Duration::from_millis(1)],
+                start + Duration::from_millis(i * 100),
+            );
+        }
+
+        let stats = tracker.stats(start + Duration::from_millis(5000));
+        assert_eq!(stats.avg_event_processing_time, Duration::from_millis(4));
+        assert_eq!(stats.avg_rule_evaluation_time, Duration::from_millis(1));
+        assert!(stats.current_events_per_second > 0.0);
+        assert!(stats.peak_events_per_second >= stats.current_events_per_second);
+
+        // Rate decays while idle
+        let idle = tracker.stats(start + Duration::from_secs(600));
+        assert!(idle.current_events_per_second < stats.current_events_per_second);
+    }
+
+    #[test]
+    fn test_event_rate_limit() {
+        let start = Instant::now();
+        let mut limit = EventRateLimit::new(Some(5));
+        limit.window_start = start;
+
+        assert_eq!(limit.admit(3, start), 3);
+        assert_eq!(limit.admit(3, start + Duration::from_secs(10)), 2);
+        assert_eq!(limit.admit(1, start + Duration::from_secs(59)), 0);
+
+        // A new window admits events again
+        assert_eq!(limit.admit(4, start + Duration::from_secs(60)), 4);
+
+        limit.max_per_minute = None;
+        assert_eq!(limit.admit(100, start + Duration::from_secs(61)), 100);
+    }
+
+    #[test]
+    fn test_rule_budget_overrides() {
+        let mut config = EngineConfig::default();
+        config.rule_budgets.insert(
+            "expensive".to_string(),
+            RuleBudget {
+                timeout: Some(Duration::from_millis(500)),
+                max_history_events: Some(50),
+            },
+        );
+
+        assert_eq!(
+            config.rule_timeout_for("expensive"),
+            Duration::from_millis(500)
+        );
+        assert_eq!(config.max_history_events_for("expensive"), Some(50));
+        assert_eq!(config.rule_timeout_for("other"), config.rule_timeout);
+        assert_eq!(config.max_history_events_for("other"), None);
+    }
+
+    #[tokio::test]
+    async fn test_event_processing() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let config = EngineConfig::default();
+
+        let engine = MonitoringEngine::new(metrics, alert_manager, config);
+        engine.start().await.unwrap();
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::TokenTransfer,
             EventData::TokenTransfer {
                 from: Pubkey::new_unique(),
                 to: Pubkey::new_unique(),
@@ -624,4 +2471,608 @@ mod tests {
         let stats = engine.statistics().await;
         assert_eq!(stats.events_processed, 1);
     }
+
+    #[tokio::test]
+    async fn test_rule_dry_run() {
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(
+            Arc::new(MetricsCollector::new().unwrap()),
+            alert_manager.clone(),
+            EngineConfig::default(),
+        );
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::TokenTransfer,
+            EventData::TokenTransfer {
+                from: Pubkey::new_unique(),
+                to: Pubkey::new_unique(),
+                amount: 5000000,
+                mint: Pubkey::new_unique(),
+                decimals: 6,
+            },
+        );
+
+        let result = engine.test_rule("large_transaction", &event).await.unwrap();
+        assert!(result.triggered);
+        assert!(alert_manager.list_alerts(None).await.is_empty());
+        assert_eq!(
+            engine
+                .rule_stats("large_transaction")
+                .unwrap_or_default()
+                .evaluations,
+            0
+        );
+
+        assert!(matches!(
+            engine.test_rule("missing", &event).await,
+            Err(EngineError::RuleNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_silenced_alerts_not_dispatched() {
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(
+            Arc::new(MetricsCollector::new().unwrap()),
+            alert_manager.clone(),
+            EngineConfig::default(),
+        );
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+        engine.start().await.unwrap();
+
+        let silence = engine
+            .silences()
+            .create(
+                crate::silences::SilenceScope {
+                    program: Some("Test Program".to_string()),
+                    ..Default::default()
+                },
+                Duration::from_secs(600),
+                None,
+                None,
+            )
+            .unwrap();
+
+        let transfer = || {
+            ProgramEvent::new(
+                Pubkey::new_unique(),
+                "Test Program".to_string(),
+                EventType::TokenTransfer,
+                EventData::TokenTransfer {
+                    from: Pubkey::new_unique(),
+                    to: Pubkey::new_unique(),
+                    amount: 5000000,
+                    mint: Pubkey::new_unique(),
+                    decimals: 6,
+                },
+            )
+        };
+
+        let result = engine.process_event(transfer()).await.unwrap();
+        assert_eq!(result.alerts_generated, 0);
+        assert_eq!(engine.silences().list()[0].suppressed, 1);
+
+        engine.silences().expire(&silence.id);
+        let result = engine.process_event(transfer()).await.unwrap();
+        assert_eq!(result.alerts_generated, 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_event_processing() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+        engine.start().await.unwrap();
+
+        let (orca, solend) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let log_event = |program_id: Pubkey, name: &str| {
+            ProgramEvent::new(
+                program_id,
+                name.to_string(),
+                EventType::LogEntry,
+                EventData::LogEntry {
+                    message: "swap".to_string(),
+                    level: None,
+                    instruction_index: None,
+                },
+            )
+        };
+        let events = vec![
+            log_event(orca, "Orca"),
+            log_event(solend, "Solend"),
+            log_event(orca, "Orca"),
+        ];
+
+        let result = engine.process_events(events).await.unwrap();
+        assert_eq!(result.rules_evaluated, 3);
+        assert!(result.errors.is_empty());
+        assert_eq!(
+            engine
+                .get_event_history(&orca.to_string(), "Orca")
+                .await
+                .len(),
+            2
+        );
+
+        let stats = engine.statistics().await;
+        assert_eq!(stats.events_processed, 3);
+        assert_eq!(stats.programs_monitored, 2);
+
+        // Events beyond the per-minute limit are dropped
+        engine.set_max_events_per_minute(Some(2));
+        let events = vec![
+            log_event(orca, "Orca"),
+            log_event(orca, "Orca"),
+            log_event(solend, "Solend"),
+        ];
+        let result = engine.process_events(events).await.unwrap();
+        assert_eq!(result.rules_evaluated, 2);
+        let stats = engine.statistics().await;
+        assert_eq!(stats.events_processed, 5);
+        assert_eq!(stats.events_dropped, 1);
+        assert_eq!(
+            engine
+                .metrics_snapshot()
+                .values
+                .get("watchtower_events_dropped_total"),
+            Some(&1.0)
+        );
+    }
+
+    /// Records, per evaluated event, whether history ends at that event.
+    struct HistorySnapshotRule {
+        seen: Arc<std::sync::Mutex<Vec<(usize, bool)>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Rule for HistorySnapshotRule {
+        fn name(&self) -> &str {
+            "history_snapshot"
+        }
+
+        fn description(&self) -> &str {
+            "Records the history each event is evaluated with"
+        }
+
+        fn severity(&self) -> crate::rules::AlertSeverity {
+            crate::rules::AlertSeverity::Low
+        }
+
+        async fn evaluate(&self, event: &ProgramEvent, context: &RuleContext) -> RuleResult {
+            let ends_at_event =
+                context.recent_events.back().map(|last| &last.id) == Some(&event.id);
+            self.seen
+                .lock()
+                .unwrap()
+                .push((context.recent_events.len(), ends_at_event));
+            RuleResult {
+                rule_name: self.name().to_string(),
+                triggered: false,
+                message: None,
+                severity: self.severity(),
+                metadata: HashMap::new(),
+                confidence: 0.0,
+                suggested_actions: Vec::new(),
+                timestamp: Utc::now(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_events_see_only_earlier_history() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        engine
+            .add_rule(Box::new(HistorySnapshotRule { seen: seen.clone() }))
+            .await;
+        engine.start().await.unwrap();
+
+        let program_id = Pubkey::new_unique();
+        let events = (0..3)
+            .map(|_| {
+                ProgramEvent::new(
+                    program_id,
+                    "Orca".to_string(),
+                    EventType::LogEntry,
+                    EventData::LogEntry {
+                        message: "swap".to_string(),
+                        level: None,
+                        instruction_index: None,
+                    },
+                )
+            })
+            .collect();
+
+        engine.process_events(events).await.unwrap();
+        assert_eq!(*seen.lock().unwrap(), [(1, true), (2, true), (3, true)]);
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_and_recent_events() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+        engine.start().await.unwrap();
+        let mut stream = engine.subscribe_to_events();
+
+        let log_event = |name: &str| {
+            ProgramEvent::new(
+                Pubkey::new_unique(),
+                name.to_string(),
+                EventType::LogEntry,
+                EventData::LogEntry {
+                    message: "swap".to_string(),
+                    level: None,
+                    instruction_index: None,
+                },
+            )
+        };
+        let orca = log_event("Orca");
+        engine.process_event(orca.clone()).await.unwrap();
+        engine.process_event(log_event("Solend")).await.unwrap();
+
+        assert_eq!(engine.queue_depths().events, 2);
+        assert_eq!(stream.recv().await.unwrap().id, orca.id);
+        assert_eq!(stream.recv().await.unwrap().program_name, "Solend");
+
+        assert_eq!(engine.recent_events(10, |_| true).len(), 2);
+        assert_eq!(engine.recent_events(1, |_| true)[0].program_name, "Solend");
+        let orca_events = engine.recent_events(10, |event| event.program_name == "Orca");
+        assert_eq!(orca_events.len(), 1);
+        assert_eq!(orca_events[0].id, orca.id);
+    }
+
+    #[tokio::test]
+    async fn test_paused_program_skips_rules() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+        engine
+            .add_rule(Box::new(LargeTransactionRule::new(1.0, 1000000)))
+            .await;
+        engine.start().await.unwrap();
+
+        let program_id = Pubkey::new_unique();
+        let event = ProgramEvent::new(
+            program_id,
+            "Test Program".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: "migration".to_string(),
+                level: None,
+                instruction_index: None,
+            },
+        );
+
+        engine.pause_program("Test Program", None, Some("migration".to_string()));
+        assert!(engine.is_program_paused("Test Program"));
+        assert_eq!(engine.paused_programs().len(), 1);
+
+        let result = engine.process_event(event.clone()).await.unwrap();
+        assert_eq!(result.rules_evaluated, 0);
+        assert_eq!(
+            engine
+                .get_event_history(&program_id.to_string(), "Test Program")
+                .await
+                .len(),
+            1
+        );
+
+        assert!(engine.resume_program("Test Program"));
+        let result = engine.process_event(event).await.unwrap();
+        assert_eq!(result.rules_evaluated, 1);
+
+        // Timed pauses lift on their own
+        engine.pause_program("Test Program", Some(Duration::ZERO), None);
+        assert!(!engine.is_program_paused("Test Program"));
+        assert!(engine.paused_programs().is_empty());
+    }
+
+    struct PanickingRule;
+
+    #[async_trait::async_trait]
+    impl Rule for PanickingRule {
+        fn name(&self) -> &str {
+            "panicking"
+        }
+
+        fn description(&self) -> &str {
+            "Always panics"
+        }
+
+        fn severity(&self) -> crate::rules::AlertSeverity {
+            crate::rules::AlertSeverity::Low
+        }
+
+        async fn evaluate(&self, _event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+            panic!("broken rule");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_rules_are_dead_lettered() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager, EngineConfig::default());
+        engine.add_rule(Box::new(PanickingRule)).await;
+        engine.start().await.unwrap();
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: "test".to_string(),
+                level: None,
+                instruction_index: None,
+            },
+        );
+
+        let result = engine.process_event(event).await.unwrap();
+        assert_eq!(result.errors.len(), 1);
+
+        let dead_letters = engine.dead_letters().list();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].reason, DeadLetterReason::RulePanic);
+        assert_eq!(dead_letters[0].rule_name, "panicking");
+
+        // A failed retry puts the entry back
+        let id = dead_letters[0].id;
+        assert!(matches!(
+            engine.retry_dead_letter(id).await,
+            Err(EngineError::RulePanic { .. })
+        ));
+        assert_eq!(engine.dead_letters().get(id).unwrap().attempts, 2);
+
+        assert!(engine.dead_letters().remove(id));
+        assert!(matches!(
+            engine.retry_dead_letter(id).await,
+            Err(EngineError::DeadLetterNotFound(_))
+        ));
+    }
+
+    /// Blocks its thread without ever yielding.
+    struct SpinningRule;
+
+    #[async_trait::async_trait]
+    impl Rule for SpinningRule {
+        fn name(&self) -> &str {
+            "spinning"
+        }
+
+        fn description(&self) -> &str {
+            "Computes for a second without yielding"
+        }
+
+        fn severity(&self) -> crate::rules::AlertSeverity {
+            crate::rules::AlertSeverity::Low
+        }
+
+        async fn evaluate(&self, _event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+            std::thread::sleep(Duration::from_secs(1));
+            RuleResult {
+                rule_name: self.name().to_string(),
+                triggered: false,
+                message: None,
+                severity: self.severity(),
+                metadata: HashMap::new(),
+                confidence: 0.0,
+                suggested_actions: Vec::new(),
+                timestamp: Utc::now(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_preempts_rules_that_never_yield() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let config = EngineConfig {
+            rule_timeout: Duration::from_millis(50),
+            ..EngineConfig::default()
+        };
+        let engine = MonitoringEngine::new(metrics, alert_manager, config);
+        engine.add_rule(Box::new(SpinningRule)).await;
+        engine.start().await.unwrap();
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: "test".to_string(),
+                level: None,
+                instruction_index: None,
+            },
+        );
+
+        let started = Instant::now();
+        let result = engine.process_event(event).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            engine.dead_letters().list()[0].reason,
+            DeadLetterReason::RuleTimeout
+        );
+    }
+
+    /// Blocks its thread until released, counting how many run at once.
+    struct StuckRule {
+        released: Arc<AtomicBool>,
+        running: Arc<AtomicUsize>,
+        max_running: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Rule for StuckRule {
+        fn name(&self) -> &str {
+            "stuck"
+        }
+
+        fn description(&self) -> &str {
+            "Computes without yielding until released"
+        }
+
+        fn severity(&self) -> crate::rules::AlertSeverity {
+            crate::rules::AlertSeverity::Low
+        }
+
+        async fn evaluate(&self, _event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+            let running = self.running.fetch_add(1, AtomicOrdering::SeqCst) + 1;
+            self.max_running.fetch_max(running, AtomicOrdering::SeqCst);
+            while !self.released.load(AtomicOrdering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            self.running.fetch_sub(1, AtomicOrdering::SeqCst);
+
+            RuleResult {
+                rule_name: self.name().to_string(),
+                triggered: false,
+                message: None,
+                severity: self.severity(),
+                metadata: HashMap::new(),
+                confidence: 0.0,
+                suggested_actions: Vec::new(),
+                timestamp: Utc::now(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_rules_keep_their_slots() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let config = EngineConfig {
+            rule_timeout: Duration::from_millis(20),
+            max_concurrent_evaluations: 2,
+            ..EngineConfig::default()
+        };
+        let engine = Arc::new(MonitoringEngine::new(metrics, alert_manager, config));
+        let released = Arc::new(AtomicBool::new(false));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        engine
+            .add_rule(Box::new(StuckRule {
+                released: released.clone(),
+                running: Arc::new(AtomicUsize::new(0)),
+                max_running: max_running.clone(),
+            }))
+            .await;
+        engine.start().await.unwrap();
+
+        let tasks: Vec<_> = (0..5)
+            .map(|_| {
+                let engine = engine.clone();
+                let event = ProgramEvent::new(
+                    Pubkey::new_unique(),
+                    "Test Program".to_string(),
+                    EventType::LogEntry,
+                    EventData::LogEntry {
+                        message: "test".to_string(),
+                        level: None,
+                        instruction_index: None,
+                    },
+                );
+                tokio::spawn(async move { engine.process_event(event).await })
+            })
+            .collect();
+
+        // Timed out evaluations still hold their slots, so the rest wait
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(max_running.load(AtomicOrdering::SeqCst), 2);
+        assert_eq!(engine.evaluation_scheduler.waiting(), 3);
+
+        released.store(true, AtomicOrdering::SeqCst);
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        assert_eq!(max_running.load(AtomicOrdering::SeqCst), 2);
+    }
+
+    struct AlwaysTriggersRule;
+
+    #[async_trait::async_trait]
+    impl Rule for AlwaysTriggersRule {
+        fn name(&self) -> &str {
+            "always"
+        }
+
+        fn description(&self) -> &str {
+            "Triggers on every event"
+        }
+
+        fn severity(&self) -> crate::rules::AlertSeverity {
+            crate::rules::AlertSeverity::Low
+        }
+
+        async fn evaluate(&self, _event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+            RuleResult {
+                rule_name: self.name().to_string(),
+                triggered: true,
+                message: Some("Triggered".to_string()),
+                severity: self.severity(),
+                metadata: HashMap::new(),
+                confidence: 0.8,
+                suggested_actions: Vec::new(),
+                timestamp: Utc::now(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_false_positive_feedback_calibrates_confidence() {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let alert_manager = Arc::new(AlertManager::new());
+        let engine = MonitoringEngine::new(metrics, alert_manager.clone(), EngineConfig::default());
+        engine.add_rule(Box::new(AlwaysTriggersRule)).await;
+        engine.start().await.unwrap();
+
+        let program_id = Pubkey::new_unique();
+        let event = || {
+            ProgramEvent::new(
+                program_id,
+                "Test Program".to_string(),
+                EventType::LogEntry,
+                EventData::LogEntry {
+                    message: "test".to_string(),
+                    level: None,
+                    instruction_index: None,
+                },
+            )
+        };
+
+        for _ in 0..3 {
+            engine.process_event(event()).await.unwrap();
+            let alert = alert_manager.list_alerts(None).await.pop().unwrap();
+            engine
+                .resolve_alert(&alert.id, Some("alice"), Some(AlertFeedback::FalsePositive))
+                .await
+                .unwrap();
+        }
+
+        let precision = engine.rule_precision().await;
+        assert_eq!(precision[0].false_positives, 3);
+        assert_eq!(precision[0].precision, Some(0.0));
+
+        engine.process_event(event()).await.unwrap();
+        let alert = alert_manager.list_alerts(None).await.pop().unwrap();
+        assert!(alert.confidence < 0.8);
+        assert_eq!(alert.metadata["raw_confidence"], 0.8);
+
+        assert!(matches!(
+            engine.resolve_alert("unknown", None, None).await,
+            Err(EngineError::AlertNotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,539 @@
+//! Alert enrichment applied between rule evaluation and alert dispatch.
+
+use crate::alerts::Alert;
+use crate::metrics::MetricsCollector;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use thiserror::Error;
+use watchtower_subscriber::{EventData, ProgramEvent};
+
+/// Lamports per SOL, used to value account balance changes.
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Metadata key holding explorer links.
+pub const EXPLORER_LINKS_KEY: &str = "explorer_links";
+
+/// Metadata key holding the signature of the triggering transaction.
+pub const SIGNATURE_KEY: &str = "signature";
+
+/// Metadata key holding the account involved in the triggering event.
+pub const ACCOUNT_KEY: &str = "account";
+
+/// Metadata key holding the token symbol of the triggering event.
+pub const TOKEN_SYMBOL_KEY: &str = "token_symbol";
+
+/// Metadata key holding the token amount in UI units.
+pub const TOKEN_AMOUNT_KEY: &str = "token_amount";
+
+/// Metadata key holding the USD value of the triggering event.
+pub const USD_VALUE_KEY: &str = "usd_value";
+
+/// Adds context to an alert before it is dispatched.
+pub trait AlertEnricher: Send + Sync {
+    /// Enricher name, used in logs.
+    fn name(&self) -> &str;
+
+    /// Attach additional information to `alert`, typically in its metadata.
+    fn enrich(&self, alert: &mut Alert, event: &ProgramEvent);
+}
+
+/// Block explorers that alert links can point to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Explorer {
+    Solscan,
+    SolanaFm,
+    Xray,
+}
+
+/// Configuration for alert enrichment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichmentConfig {
+    /// Explorers to link to
+    #[serde(default = "default_explorers")]
+    pub explorers: Vec<Explorer>,
+
+    /// Cluster used in explorer links (mainnet-beta, devnet, testnet)
+    #[serde(default = "default_cluster")]
+    pub cluster: String,
+
+    /// Known token mints and the symbols used for price lookups
+    #[serde(default)]
+    pub tokens: Vec<TokenInfo>,
+}
+
+/// A known token mint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenInfo {
+    /// Mint address
+    pub mint: String,
+
+    /// Token symbol (must match the price feed symbol)
+    pub symbol: String,
+}
+
+/// Errors that can occur when configuring enrichment.
+#[derive(Error, Debug)]
+pub enum EnrichmentError {
+    #[error("Enrichment configuration error: {0}")]
+    Configuration(String),
+}
+
+pub type EnrichmentResult<T> = Result<T, EnrichmentError>;
+
+impl EnrichmentConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> EnrichmentResult<()> {
+        if !matches!(self.cluster.as_str(), "mainnet-beta" | "devnet" | "testnet") {
+            return Err(EnrichmentError::Configuration(format!(
+                "Unknown cluster: {}",
+                self.cluster
+            )));
+        }
+
+        for token in &self.tokens {
+            Pubkey::from_str(&token.mint).map_err(|_| {
+                EnrichmentError::Configuration(format!("Invalid mint address: {}", token.mint))
+            })?;
+            if token.symbol.is_empty() {
+                return Err(EnrichmentError::Configuration(format!(
+                    "Token {} has an empty symbol",
+                    token.mint
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Explorer {
+    /// Key used for this explorer in the links metadata.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Explorer::Solscan => "solscan",
+            Explorer::SolanaFm => "solanafm",
+            Explorer::Xray => "xray",
+        }
+    }
+
+    /// Link to an account page.
+    pub fn account_url(&self, address: &str, cluster: &str) -> String {
+        match self {
+            Explorer::Solscan => format!(
+                "https://solscan.io/account/{}{}",
+                address,
+                solscan_cluster(cluster)
+            ),
+            Explorer::SolanaFm => format!(
+                "https://solana.fm/address/{}{}",
+                address,
+                solanafm_cluster(cluster)
+            ),
+            Explorer::Xray => format!(
+                "https://xray.helius.xyz/account/{}{}",
+                address,
+                xray_cluster(cluster)
+            ),
+        }
+    }
+
+    /// Link to a transaction page.
+    pub fn transaction_url(&self, signature: &str, cluster: &str) -> String {
+        match self {
+            Explorer::Solscan => format!(
+                "https://solscan.io/tx/{}{}",
+                signature,
+                solscan_cluster(cluster)
+            ),
+            Explorer::SolanaFm => format!(
+                "https://solana.fm/tx/{}{}",
+                signature,
+                solanafm_cluster(cluster)
+            ),
+            Explorer::Xray => format!(
+                "https://xray.helius.xyz/tx/{}{}",
+                signature,
+                xray_cluster(cluster)
+            ),
+        }
+    }
+}
+
+fn solscan_cluster(cluster: &str) -> String {
+    match cluster {
+        "mainnet-beta" => String::new(),
+        other => format!("?cluster={}", other),
+    }
+}
+
+fn solanafm_cluster(cluster: &str) -> String {
+    match cluster {
+        "mainnet-beta" => String::new(),
+        "devnet" => "?cluster=devnet-alpha".to_string(),
+        other => format!("?cluster={}", other),
+    }
+}
+
+fn xray_cluster(cluster: &str) -> String {
+    match cluster {
+        "mainnet-beta" => String::new(),
+        other => format!("?network={}", other),
+    }
+}
+
+/// Records the transaction signature and account behind an alert, so
+/// notifications can link to them.
+#[derive(Debug, Clone, Default)]
+pub struct EventReferenceEnricher;
+
+impl AlertEnricher for EventReferenceEnricher {
+    fn name(&self) -> &str {
+        "event_references"
+    }
+
+    fn enrich(&self, alert: &mut Alert, event: &ProgramEvent) {
+        let signature = event.transaction_signature().or(event.signature.as_ref());
+        let account = match &event.data {
+            EventData::AccountChange { account, .. } => Some(account),
+            EventData::TokenTransfer { from, .. } => Some(from),
+            _ => None,
+        };
+
+        // Rules may report them themselves
+        if let Some(signature) = signature {
+            alert
+                .metadata
+                .entry(SIGNATURE_KEY.to_string())
+                .or_insert_with(|| json!(signature.to_string()));
+        }
+        if let Some(account) = account {
+            alert
+                .metadata
+                .entry(ACCOUNT_KEY.to_string())
+                .or_insert_with(|| json!(account.to_string()));
+        }
+    }
+}
+
+/// Adds explorer links for the program and transaction behind an alert.
+#[derive(Debug, Clone)]
+pub struct ExplorerLinkEnricher {
+    explorers: Vec<Explorer>,
+    cluster: String,
+}
+
+impl ExplorerLinkEnricher {
+    /// Create an enricher linking to `explorers` on `cluster`.
+    pub fn new(explorers: Vec<Explorer>, cluster: impl Into<String>) -> Self {
+        Self {
+            explorers,
+            cluster: cluster.into(),
+        }
+    }
+}
+
+impl AlertEnricher for ExplorerLinkEnricher {
+    fn name(&self) -> &str {
+        "explorer_links"
+    }
+
+    fn enrich(&self, alert: &mut Alert, event: &ProgramEvent) {
+        let signature = event
+            .transaction_signature()
+            .or(event.signature.as_ref())
+            .map(|s| s.to_string());
+
+        let mut links = serde_json::Map::new();
+        for explorer in &self.explorers {
+            let mut entry = serde_json::Map::new();
+            entry.insert(
+                "program".to_string(),
+                json!(explorer.account_url(&alert.program_id.to_string(), &self.cluster)),
+            );
+            if let Some(signature) = &signature {
+                entry.insert(
+                    "transaction".to_string(),
+                    json!(explorer.transaction_url(signature, &self.cluster)),
+                );
+            }
+            links.insert(explorer.as_str().to_string(), entry.into());
+        }
+
+        if !links.is_empty() {
+            alert
+                .metadata
+                .insert(EXPLORER_LINKS_KEY.to_string(), links.into());
+        }
+    }
+}
+
+/// Resolves token mints in the triggering event to symbols and UI amounts.
+#[derive(Debug, Clone, Default)]
+pub struct TokenSymbolEnricher {
+    symbols: HashMap<Pubkey, String>,
+}
+
+impl TokenSymbolEnricher {
+    /// Create an enricher from known tokens, skipping invalid mints.
+    pub fn new(tokens: &[TokenInfo]) -> Self {
+        let symbols = tokens
+            .iter()
+            .filter_map(|token| {
+                Pubkey::from_str(&token.mint)
+                    .ok()
+                    .map(|mint| (mint, token.symbol.clone()))
+            })
+            .collect();
+        Self { symbols }
+    }
+}
+
+impl AlertEnricher for TokenSymbolEnricher {
+    fn name(&self) -> &str {
+        "token_symbols"
+    }
+
+    fn enrich(&self, alert: &mut Alert, event: &ProgramEvent) {
+        match &event.data {
+            EventData::TokenTransfer {
+                mint,
+                amount,
+                decimals,
+                ..
+            } => {
+                if let Some(symbol) = self.symbols.get(mint) {
+                    alert
+                        .metadata
+                        .insert(TOKEN_SYMBOL_KEY.to_string(), json!(symbol));
+                }
+                let ui_amount = *amount as f64 / 10f64.powi(*decimals as i32);
+                alert
+                    .metadata
+                    .insert(TOKEN_AMOUNT_KEY.to_string(), json!(ui_amount));
+            }
+            EventData::AccountChange {
+                balance_before: Some(before),
+                balance_after: Some(after),
+                ..
+            } => {
+                let delta = (*after as f64 - *before as f64).abs() / LAMPORTS_PER_SOL;
+                alert
+                    .metadata
+                    .insert(TOKEN_SYMBOL_KEY.to_string(), json!("SOL"));
+                alert
+                    .metadata
+                    .insert(TOKEN_AMOUNT_KEY.to_string(), json!(delta));
+            }
+            _ => {
+                // Rules may report the mint themselves
+                let symbol = alert
+                    .metadata
+                    .get("mint")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| Pubkey::from_str(s).ok())
+                    .and_then(|mint| self.symbols.get(&mint));
+                if let Some(symbol) = symbol {
+                    alert
+                        .metadata
+                        .insert(TOKEN_SYMBOL_KEY.to_string(), json!(symbol));
+                }
+            }
+        }
+    }
+}
+
+/// Values the token amount of an alert in USD using the price subsystem.
+///
+/// Runs after `TokenSymbolEnricher`, reading the symbol and amount it set.
+pub struct UsdValueEnricher {
+    metrics: Arc<MetricsCollector>,
+}
+
+impl UsdValueEnricher {
+    /// Create an enricher reading prices from `metrics`.
+    pub fn new(metrics: Arc<MetricsCollector>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl AlertEnricher for UsdValueEnricher {
+    fn name(&self) -> &str {
+        "usd_value"
+    }
+
+    fn enrich(&self, alert: &mut Alert, _event: &ProgramEvent) {
+        let symbol = alert
+            .metadata
+            .get(TOKEN_SYMBOL_KEY)
+            .and_then(|v| v.as_str());
+        let amount = alert
+            .metadata
+            .get(TOKEN_AMOUNT_KEY)
+            .and_then(|v| v.as_f64());
+
+        if let (Some(symbol), Some(amount)) = (symbol, amount) {
+            if let Some(price) = self.metrics.token_price(symbol) {
+                alert
+                    .metadata
+                    .insert(USD_VALUE_KEY.to_string(), json!(amount * price));
+            }
+        }
+    }
+}
+
+/// Ordered list of enrichers applied to every alert.
+#[derive(Default)]
+pub struct EnrichmentPipeline {
+    enrichers: Vec<Arc<dyn AlertEnricher>>,
+}
+
+impl EnrichmentPipeline {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the built-in pipeline from configuration.
+    pub fn from_config(config: &EnrichmentConfig, metrics: Arc<MetricsCollector>) -> Self {
+        let mut pipeline = Self::new();
+        pipeline.add(Arc::new(EventReferenceEnricher));
+        if !config.explorers.is_empty() {
+            pipeline.add(Arc::new(ExplorerLinkEnricher::new(
+                config.explorers.clone(),
+                config.cluster.clone(),
+            )));
+        }
+        pipeline.add(Arc::new(TokenSymbolEnricher::new(&config.tokens)));
+        pipeline.add(Arc::new(UsdValueEnricher::new(metrics)));
+        pipeline
+    }
+
+    /// Append an enricher; enrichers run in insertion order.
+    pub fn add(&mut self, enricher: Arc<dyn AlertEnricher>) {
+        self.enrichers.push(enricher);
+    }
+
+    /// Names of the registered enrichers.
+    pub fn names(&self) -> Vec<String> {
+        self.enrichers
+            .iter()
+            .map(|e| e.name().to_string())
+            .collect()
+    }
+
+    /// Run all enrichers on an alert.
+    pub fn enrich(&self, alert: &mut Alert, event: &ProgramEvent) {
+        for enricher in &self.enrichers {
+            enricher.enrich(alert, event);
+        }
+    }
+}
+
+impl Default for EnrichmentConfig {
+    fn default() -> Self {
+        Self {
+            explorers: default_explorers(),
+            cluster: default_cluster(),
+            tokens: Vec::new(),
+        }
+    }
+}
+
+// Default value functions
+fn default_explorers() -> Vec<Explorer> {
+    vec![Explorer::Solscan, Explorer::SolanaFm]
+}
+
+fn default_cluster() -> String {
+    "mainnet-beta".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::AlertSeverity;
+    use chrono::Utc;
+    use watchtower_subscriber::EventType;
+
+    fn alert_for(event: &ProgramEvent) -> Alert {
+        Alert {
+            id: "test-alert".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test alert message".to_string(),
+            severity: AlertSeverity::High,
+            program_id: event.program_id,
+            program_name: event.program_name.clone(),
+            event_id: Some(event.id.clone()),
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_adds_links_symbol_and_usd_value() {
+        let mint = Pubkey::new_unique();
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        metrics.update_token_price("USDC", 1.0);
+
+        let config = EnrichmentConfig {
+            tokens: vec![TokenInfo {
+                mint: mint.to_string(),
+                symbol: "USDC".to_string(),
+            }],
+            ..EnrichmentConfig::default()
+        };
+        config.validate().unwrap();
+        let pipeline = EnrichmentPipeline::from_config(&config, metrics);
+
+        let from = Pubkey::new_unique();
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Token".to_string(),
+            EventType::TokenTransfer,
+            EventData::TokenTransfer {
+                from,
+                to: Pubkey::new_unique(),
+                amount: 2_500_000,
+                mint,
+                decimals: 6,
+            },
+        );
+        let mut alert = alert_for(&event);
+        pipeline.enrich(&mut alert, &event);
+
+        assert_eq!(alert.metadata[TOKEN_SYMBOL_KEY], "USDC");
+        assert_eq!(alert.metadata[TOKEN_AMOUNT_KEY], 2.5);
+        assert_eq!(alert.metadata[USD_VALUE_KEY], 2.5);
+        assert_eq!(alert.metadata[ACCOUNT_KEY], from.to_string());
+        assert_eq!(
+            alert.metadata[EXPLORER_LINKS_KEY]["solscan"]["program"],
+            format!("https://solscan.io/account/{}", event.program_id)
+        );
+    }
+
+    #[test]
+    fn test_devnet_links() {
+        assert_eq!(
+            Explorer::SolanaFm.transaction_url("sig", "devnet"),
+            "https://solana.fm/tx/sig?cluster=devnet-alpha"
+        );
+        assert_eq!(
+            Explorer::Solscan.transaction_url("sig", "devnet"),
+            "https://solscan.io/tx/sig?cluster=devnet"
+        );
+        assert_eq!(
+            Explorer::Xray.account_url("addr", "devnet"),
+            "https://xray.helius.xyz/account/addr?network=devnet"
+        );
+    }
+}
@@ -0,0 +1,539 @@
+//! gRPC control API for managing the engine from orchestration tooling.
+
+use crate::{
+    alerts::Alert,
+    engine::{EngineError, MonitoringEngine, ProgramPause, RuleStatus},
+    rules::{AlertSeverity, RateOfChangeConfig, RateOfChangeRule, Rule, RuleError},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+use tonic::service::Interceptor;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+/// Generated protobuf types and service definitions.
+pub mod proto {
+    tonic::include_proto!("watchtower.control.v1");
+}
+
+use proto::watchtower_control_server::{WatchtowerControl, WatchtowerControlServer};
+
+/// Builds a rule from its JSON configuration.
+pub type RuleFactory =
+    Arc<dyn Fn(serde_json::Value) -> Result<Box<dyn Rule>, RuleError> + Send + Sync>;
+
+/// Configuration for the gRPC control server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcConfig {
+    /// Whether to start the control server
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Host to bind to
+    #[serde(default = "default_host")]
+    pub host: String,
+
+    /// Port to bind to
+    #[serde(default = "default_port")]
+    pub port: u16,
+
+    /// Token clients must send as `authorization: Bearer <token>`. Required
+    /// unless the server only listens on loopback.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Errors that can occur in the gRPC control server.
+#[derive(Error, Debug)]
+pub enum GrpcError {
+    #[error("Invalid bind address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Refusing to serve the control API on {0} without a token")]
+    MissingToken(SocketAddr),
+
+    #[error("Transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+}
+
+pub type GrpcResult<T> = Result<T, GrpcError>;
+
+impl GrpcConfig {
+    /// Socket address to bind to.
+    pub fn bind_address(&self) -> GrpcResult<SocketAddr> {
+        format!("{}:{}", self.host, self.port)
+            .parse()
+            .map_err(|_| GrpcError::InvalidAddress(format!("{}:{}", self.host, self.port)))
+    }
+
+    /// Check the bind address, and that a token is set unless it is loopback.
+    pub fn validate(&self) -> GrpcResult<SocketAddr> {
+        let addr = self.bind_address()?;
+        if self.token().is_none() && !addr.ip().is_loopback() {
+            return Err(GrpcError::MissingToken(addr));
+        }
+        Ok(addr)
+    }
+
+    /// Configured token, if set and non-empty.
+    fn token(&self) -> Option<&str> {
+        self.token.as_deref().filter(|token| !token.is_empty())
+    }
+}
+
+/// Rejects requests without the configured bearer token.
+#[derive(Debug, Clone)]
+struct TokenAuth {
+    /// Digest of the expected `authorization` value; `None` admits every request
+    expected: Option<[u8; 32]>,
+}
+
+impl TokenAuth {
+    fn new(token: Option<&str>) -> Self {
+        Self {
+            expected: token
+                .map(|token| authorization_digest(format!("Bearer {}", token).as_bytes())),
+        }
+    }
+}
+
+/// Digest of an `authorization` value. Digests are compared instead of the
+/// values so the comparison time does not reveal the token.
+fn authorization_digest(value: &[u8]) -> [u8; 32] {
+    Sha256::digest(value).into()
+}
+
+impl Interceptor for TokenAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(expected) = &self.expected else {
+            return Ok(request);
+        };
+
+        match request.metadata().get("authorization") {
+            Some(value) if authorization_digest(value.as_bytes()) == *expected => Ok(request),
+            Some(_) => Err(Status::unauthenticated("Invalid token")),
+            None => Err(Status::unauthenticated("Missing bearer token")),
+        }
+    }
+}
+
+/// gRPC service exposing rule management, engine state and the alert stream.
+pub struct ControlService {
+    engine: Arc<MonitoringEngine>,
+    factories: HashMap<String, RuleFactory>,
+}
+
+impl ControlService {
+    /// Create a control service with the built-in rule factories.
+    pub fn new(engine: Arc<MonitoringEngine>) -> Self {
+        let mut service = Self {
+            engine,
+            factories: HashMap::new(),
+        };
+        service.register_factory(
+            "rate_of_change",
+            Arc::new(|config| {
+                let config: RateOfChangeConfig = serde_json::from_value(config)
+                    .map_err(|e| RuleError::Configuration(e.to_string()))?;
+                config.validate()?;
+                Ok(Box::new(RateOfChangeRule::from_config(&config)) as Box<dyn Rule>)
+            }),
+        );
+        service
+    }
+
+    /// Register a factory for rules added through `AddRule` with `kind`.
+    pub fn register_factory(&mut self, kind: impl Into<String>, factory: RuleFactory) {
+        self.factories.insert(kind.into(), factory);
+    }
+
+    /// Serve the control API as configured until the task is cancelled.
+    ///
+    /// Fails without binding when `config` does not validate, so the API is
+    /// never reachable from other hosts without a token.
+    pub async fn serve(self, config: &GrpcConfig) -> GrpcResult<()> {
+        let addr = config.validate()?;
+        let auth = TokenAuth::new(config.token());
+        if auth.expected.is_none() {
+            warn!(
+                "gRPC control server on {} accepts requests without a token",
+                addr
+            );
+        }
+
+        info!("Starting gRPC control server on {}", addr);
+        tonic::transport::Server::builder()
+            .add_service(WatchtowerControlServer::with_interceptor(self, auth))
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+}
+
+type AlertStream = Pin<Box<dyn Stream<Item = Result<proto::AlertMessage, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl WatchtowerControl for ControlService {
+    async fn list_rules(
+        &self,
+        _request: Request<proto::ListRulesRequest>,
+    ) -> Result<Response<proto::ListRulesResponse>, Status> {
+        let rules = self
+            .engine
+            .rule_statuses()
+            .await
+            .into_iter()
+            .map(rule_status_message)
+            .collect();
+
+        Ok(Response::new(proto::ListRulesResponse { rules }))
+    }
+
+    async fn add_rule(
+        &self,
+        request: Request<proto::AddRuleRequest>,
+    ) -> Result<Response<proto::AddRuleResponse>, Status> {
+        let request = request.into_inner();
+        let factory = self.factories.get(&request.kind).ok_or_else(|| {
+            Status::invalid_argument(format!("Unknown rule kind: {}", request.kind))
+        })?;
+
+        let config: serde_json::Value = serde_json::from_str(&request.config_json)
+            .map_err(|e| Status::invalid_argument(format!("Invalid rule config: {}", e)))?;
+        let rule = factory(config).map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let name = rule.name().to_string();
+        if self.engine.list_rules().await.contains(&name) {
+            return Err(Status::already_exists(format!(
+                "Rule already registered: {}",
+                name
+            )));
+        }
+
+        self.engine.add_rule(rule).await;
+        Ok(Response::new(proto::AddRuleResponse { name }))
+    }
+
+    async fn remove_rule(
+        &self,
+        request: Request<proto::RemoveRuleRequest>,
+    ) -> Result<Response<proto::RemoveRuleResponse>, Status> {
+        let removed = self.engine.remove_rule(&request.into_inner().name).await;
+        Ok(Response::new(proto::RemoveRuleResponse { removed }))
+    }
+
+    async fn set_rule_enabled(
+        &self,
+        request: Request<proto::SetRuleEnabledRequest>,
+    ) -> Result<Response<proto::SetRuleEnabledResponse>, Status> {
+        let request = request.into_inner();
+        self.engine
+            .set_rule_enabled(&request.name, request.enabled)
+            .await
+            .map_err(engine_status)?;
+        Ok(Response::new(proto::SetRuleEnabledResponse {}))
+    }
+
+    async fn pause_program(
+        &self,
+        request: Request<proto::PauseProgramRequest>,
+    ) -> Result<Response<proto::PauseProgramResponse>, Status> {
+        let request = request.into_inner();
+        if request.program_name.is_empty() {
+            return Err(Status::invalid_argument("Program name is required"));
+        }
+
+        let duration =
+            (request.duration_seconds > 0).then(|| Duration::from_secs(request.duration_seconds));
+        let reason = (!request.reason.is_empty()).then_some(request.reason);
+        self.engine
+            .pause_program(&request.program_name, duration, reason);
+        Ok(Response::new(proto::PauseProgramResponse {}))
+    }
+
+    async fn resume_program(
+        &self,
+        request: Request<proto::ResumeProgramRequest>,
+    ) -> Result<Response<proto::ResumeProgramResponse>, Status> {
+        let resumed = self
+            .engine
+            .resume_program(&request.into_inner().program_name);
+        Ok(Response::new(proto::ResumeProgramResponse { resumed }))
+    }
+
+    async fn list_paused_programs(
+        &self,
+        _request: Request<proto::ListPausedProgramsRequest>,
+    ) -> Result<Response<proto::ListPausedProgramsResponse>, Status> {
+        let programs = self
+            .engine
+            .paused_programs()
+            .into_iter()
+            .map(paused_program_message)
+            .collect();
+        Ok(Response::new(proto::ListPausedProgramsResponse {
+            programs,
+        }))
+    }
+
+    async fn get_state(
+        &self,
+        _request: Request<proto::GetStateRequest>,
+    ) -> Result<Response<proto::GetStateResponse>, Status> {
+        let state = self.engine.state().await;
+        let statistics = self.engine.statistics().await;
+
+        Ok(Response::new(proto::GetStateResponse {
+            running: state.running,
+            start_time_unix: state.start_time.timestamp(),
+            uptime_seconds: statistics.uptime.as_secs(),
+            events_processed: statistics.events_processed,
+            rules_evaluated: statistics.rules_evaluated,
+            alerts_generated: statistics.alerts_generated,
+            rules_registered: statistics.rules_registered as u64,
+            programs_monitored: statistics.programs_monitored as u64,
+            events_per_second: statistics.performance.current_events_per_second,
+        }))
+    }
+
+    type StreamAlertsStream = AlertStream;
+
+    // Stream items must be `Result<_, Status>` per the tonic service trait
+    #[allow(clippy::result_large_err)]
+    async fn stream_alerts(
+        &self,
+        request: Request<proto::StreamAlertsRequest>,
+    ) -> Result<Response<Self::StreamAlertsStream>, Status> {
+        let request = request.into_inner();
+        let min_severity = if request.min_severity.is_empty() {
+            None
+        } else {
+            Some(AlertSeverity::parse(&request.min_severity).ok_or_else(|| {
+                Status::invalid_argument(format!("Unknown severity: {}", request.min_severity))
+            })?)
+        };
+        let tags = request.tags;
+
+        let stream =
+            BroadcastStream::new(self.engine.subscribe_to_alerts()).filter_map(move |result| {
+                match result {
+                    Ok(alert) => {
+                        let severity_ok = min_severity.map_or(true, |min| alert.severity >= min);
+                        let tags_ok =
+                            tags.is_empty() || alert.tags.iter().any(|tag| tags.contains(tag));
+                        (severity_ok && tags_ok).then(|| Ok(alert_message(&alert)))
+                    }
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        warn!("gRPC alert stream lagged, skipped {} alerts", skipped);
+                        None
+                    }
+                }
+            });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Map an engine error to a gRPC status.
+fn engine_status(error: EngineError) -> Status {
+    match error {
+        EngineError::RuleNotFound(name) => Status::not_found(format!("Rule not found: {}", name)),
+        other => Status::internal(other.to_string()),
+    }
+}
+
+/// Parse a lowercase severity name.
+fn rule_status_message(status: RuleStatus) -> proto::RuleStatusMessage {
+    proto::RuleStatusMessage {
+        name: status.name,
+        description: status.description,
+        severity: status.severity.as_str().to_string(),
+        priority: status.priority as u32,
+        enabled: status.enabled,
+        tags: status.tags,
+        evaluations: status.stats.evaluations,
+        triggers: status.stats.triggers,
+    }
+}
+
+fn paused_program_message(pause: ProgramPause) -> proto::PausedProgram {
+    proto::PausedProgram {
+        program_name: pause.program_name,
+        paused_at_unix: pause.paused_at.timestamp(),
+        until_unix: pause.until.map_or(0, |until| until.timestamp()),
+        reason: pause.reason.unwrap_or_default(),
+    }
+}
+
+fn alert_message(alert: &Alert) -> proto::AlertMessage {
+    proto::AlertMessage {
+        id: alert.id.clone(),
+        rule_name: alert.rule_name.clone(),
+        message: alert.message.clone(),
+        severity: alert.severity.as_str().to_string(),
+        program_id: alert.program_id.to_string(),
+        program_name: alert.program_name.clone(),
+        confidence: alert.confidence,
+        timestamp_unix_ms: alert.timestamp.timestamp_millis(),
+        tags: alert.tags.clone(),
+        metadata_json: serde_json::to_string(&alert.metadata).unwrap_or_default(),
+    }
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_host(),
+            port: default_port(),
+            token: None,
+        }
+    }
+}
+
+// Default value functions
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_port() -> u16 {
+    50051
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alerts::AlertManager, engine::EngineConfig, metrics::MetricsCollector};
+
+    fn service() -> ControlService {
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        let engine = MonitoringEngine::new(
+            metrics,
+            Arc::new(AlertManager::new()),
+            EngineConfig::default(),
+        );
+        ControlService::new(Arc::new(engine))
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_rule() {
+        let service = service();
+        let config = serde_json::json!({
+            "name": "tvl_drop",
+            "metric": "{program}_tvl",
+            "threshold_pct": 20.0,
+            "window_seconds": 300
+        });
+
+        let added = service
+            .add_rule(Request::new(proto::AddRuleRequest {
+                kind: "rate_of_change".to_string(),
+                config_json: config.to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(added.name, "tvl_drop");
+
+        let duplicate = service
+            .add_rule(Request::new(proto::AddRuleRequest {
+                kind: "rate_of_change".to_string(),
+                config_json: config.to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(duplicate.code(), tonic::Code::AlreadyExists);
+
+        let rules = service
+            .list_rules(Request::new(proto::ListRulesRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .rules;
+        assert_eq!(rules.len(), 1);
+
+        let removed = service
+            .remove_rule(Request::new(proto::RemoveRuleRequest {
+                name: "tvl_drop".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(removed.removed);
+    }
+
+    #[test]
+    fn test_non_loopback_bind_requires_token() {
+        let mut config = GrpcConfig {
+            enabled: true,
+            ..GrpcConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.host = "0.0.0.0".to_string();
+        assert!(matches!(config.validate(), Err(GrpcError::MissingToken(_))));
+        config.token = Some(String::new());
+        assert!(matches!(config.validate(), Err(GrpcError::MissingToken(_))));
+
+        config.token = Some("secret".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_token_auth() {
+        let request = |authorization: Option<&str>| {
+            let mut request = Request::new(());
+            if let Some(value) = authorization {
+                request
+                    .metadata_mut()
+                    .insert("authorization", value.parse().unwrap());
+            }
+            request
+        };
+
+        let mut auth = TokenAuth::new(Some("secret"));
+        assert!(auth.call(request(Some("Bearer secret"))).is_ok());
+        assert_eq!(
+            auth.call(request(Some("Bearer wrong"))).unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+        assert_eq!(
+            auth.call(request(None)).unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+
+        let mut open = TokenAuth::new(None);
+        assert!(open.call(request(None)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_rule_kind_and_rule() {
+        let service = service();
+
+        let unknown_kind = service
+            .add_rule(Request::new(proto::AddRuleRequest {
+                kind: "custom".to_string(),
+                config_json: "{}".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(unknown_kind.code(), tonic::Code::InvalidArgument);
+
+        let unknown_rule = service
+            .set_rule_enabled(Request::new(proto::SetRuleEnabledRequest {
+                name: "missing".to_string(),
+                enabled: false,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(unknown_rule.code(), tonic::Code::NotFound);
+    }
+}
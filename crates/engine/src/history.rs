@@ -0,0 +1,365 @@
+//! Per-program event history with count, age, and memory limits.
+
+use chrono::Utc;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use watchtower_subscriber::{EventData, ProgramEvent};
+
+/// Events of a program in arrival order.
+///
+/// A persistent deque: clones share structure, and pushing to or trimming a
+/// history only copies the chunks it touches, never the whole window.
+pub type EventWindow = im::Vector<ProgramEvent>;
+
+/// Event history shared by all monitored programs.
+///
+/// Each program keeps at most `max_events` events no older than `max_age`.
+/// When a global memory budget is set, events are evicted from the least
+/// recently updated programs first.
+///
+/// Histories are persistent: `get_shared` hands out the current snapshot
+/// without copying, and later pushes leave it untouched while sharing all
+/// but the chunks they change with it.
+#[derive(Debug)]
+pub struct EventHistory {
+    /// History per program key
+    programs: DashMap<String, ProgramHistory>,
+
+    /// Maximum events per program
+    max_events: usize,
+
+    /// Maximum event age
+    max_age: Duration,
+
+    /// Global memory budget in bytes
+    max_bytes: Option<usize>,
+
+    /// Estimated bytes across all programs
+    total_bytes: AtomicUsize,
+
+    /// Events across all programs
+    total_events: AtomicUsize,
+}
+
+/// History of a single program.
+#[derive(Debug)]
+struct ProgramHistory {
+    events: EventWindow,
+    bytes: usize,
+    last_access: Instant,
+}
+
+/// Current history usage.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryUsage {
+    /// Programs with history
+    pub programs: usize,
+
+    /// Events held
+    pub events: usize,
+
+    /// Estimated memory held in bytes
+    pub bytes: usize,
+}
+
+impl EventHistory {
+    /// Create an empty history.
+    pub fn new(max_events: usize, max_age: Duration, max_bytes: Option<usize>) -> Self {
+        Self {
+            programs: DashMap::new(),
+            max_events,
+            max_age,
+            max_bytes,
+            total_bytes: AtomicUsize::new(0),
+            total_events: AtomicUsize::new(0),
+        }
+    }
+
+    /// Add an event and enforce limits, returning the number of events evicted
+    /// by the memory budget.
+    pub fn push(&self, key: &str, event: ProgramEvent) -> usize {
+        {
+            let mut history =
+                self.programs
+                    .entry(key.to_string())
+                    .or_insert_with(|| ProgramHistory {
+                        events: EventWindow::new(),
+                        bytes: 0,
+                        last_access: Instant::now(),
+                    });
+
+            let size = estimate_event_size(&event);
+            history.events.push_back(event);
+            history.bytes += size;
+            history.last_access = Instant::now();
+            self.total_bytes.fetch_add(size, Ordering::Relaxed);
+            self.total_events.fetch_add(1, Ordering::Relaxed);
+
+            // Trim history to configured limits. Events arrive in about
+            // timestamp order, so expired ones are at the front.
+            let cutoff_time =
+                Utc::now() - chrono::Duration::from_std(self.max_age).unwrap_or_default();
+            let expired = history
+                .events
+                .iter()
+                .take_while(|e| e.timestamp < cutoff_time)
+                .count();
+            self.remove_oldest(&mut history, expired);
+
+            let excess = history.events.len().saturating_sub(self.max_events);
+            self.remove_oldest(&mut history, excess);
+        }
+
+        self.enforce_budget(key)
+    }
+
+    /// Get a copy of a program's history.
+    pub fn get(&self, key: &str) -> Vec<ProgramEvent> {
+        self.programs
+            .get(key)
+            .map(|history| history.events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get a shared snapshot of a program's history without copying it.
+    pub fn get_shared(&self, key: &str) -> EventWindow {
+        self.programs
+            .get(key)
+            .map(|history| history.events.clone())
+            .unwrap_or_default()
+    }
+
+    /// Event of a program with the given ID, if it is still held.
+    pub fn find(&self, key: &str, id: &str) -> Option<ProgramEvent> {
+        self.programs
+            .get(key)?
+            .events
+            .iter()
+            .rev()
+            .find(|event| event.id == id)
+            .cloned()
+    }
+
+    /// Most recent events across all programs matching `filter`, newest first.
+    pub fn latest(
+        &self,
+        limit: usize,
+        filter: impl Fn(&ProgramEvent) -> bool,
+    ) -> Vec<ProgramEvent> {
+        let mut events: Vec<ProgramEvent> = self
+            .programs
+            .iter()
+            .flat_map(|history| {
+                history
+                    .events
+                    .iter()
+                    .rev()
+                    .filter(|event| filter(event))
+                    .take(limit)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        events.sort_by_key(|event| std::cmp::Reverse(event.timestamp));
+        events.truncate(limit);
+        events
+    }
+
+    /// Remove all history.
+    pub fn clear(&self) {
+        self.programs.clear();
+        self.total_bytes.store(0, Ordering::Relaxed);
+        self.total_events.store(0, Ordering::Relaxed);
+    }
+
+    /// Number of programs with history.
+    pub fn program_count(&self) -> usize {
+        self.programs.len()
+    }
+
+    /// Current usage totals.
+    pub fn usage(&self) -> HistoryUsage {
+        HistoryUsage {
+            programs: self.programs.len(),
+            events: self.total_events.load(Ordering::Relaxed),
+            bytes: self.total_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Evict events from least recently updated programs until under budget.
+    fn enforce_budget(&self, current_key: &str) -> usize {
+        let Some(max_bytes) = self.max_bytes else {
+            return 0;
+        };
+
+        let mut evicted = 0;
+        while self.total_bytes.load(Ordering::Relaxed) > max_bytes {
+            // Prefer other programs; fall back to trimming the current one
+            let victim = self
+                .programs
+                .iter()
+                .filter(|entry| entry.key() != current_key)
+                .min_by_key(|entry| entry.last_access)
+                .map(|entry| entry.key().clone())
+                .unwrap_or_else(|| current_key.to_string());
+
+            let Some(mut history) = self.programs.get_mut(&victim) else {
+                break;
+            };
+
+            if history.events.is_empty() {
+                drop(history);
+                self.programs.remove(&victim);
+                continue;
+            }
+
+            // Drop the older half of the victim's history at a time
+            let count = (history.events.len() + 1) / 2;
+            self.remove_oldest(&mut history, count);
+            evicted += count;
+
+            if history.events.is_empty() {
+                drop(history);
+                self.programs.remove(&victim);
+            }
+        }
+
+        evicted
+    }
+
+    /// Remove the oldest `count` events from a program's history.
+    fn remove_oldest(&self, history: &mut ProgramHistory, count: usize) {
+        let count = count.min(history.events.len());
+        if count == 0 {
+            return;
+        }
+
+        let freed: usize = history
+            .events
+            .slice(..count)
+            .iter()
+            .map(estimate_event_size)
+            .sum();
+        self.release(history, count, freed);
+    }
+
+    /// Update accounting after events were removed from a program's history.
+    fn release(&self, history: &mut ProgramHistory, count: usize, freed: usize) {
+        history.bytes = history.bytes.saturating_sub(freed);
+        self.total_bytes.fetch_sub(freed, Ordering::Relaxed);
+        self.total_events.fetch_sub(count, Ordering::Relaxed);
+    }
+}
+
+/// Approximate heap and inline size of an event.
+pub fn estimate_event_size(event: &ProgramEvent) -> usize {
+    let data_size = match &event.data {
+        EventData::LogEntry { message, .. } => message.len(),
+        EventData::Instruction { data, accounts, .. } => {
+            data.len() + accounts.len() * std::mem::size_of::<solana_sdk::pubkey::Pubkey>()
+        }
+        EventData::Custom { name, data } => name.len() + estimate_json_size(data),
+        _ => 0,
+    };
+
+    let metadata_size: usize = event
+        .metadata
+        .iter()
+        .map(|(key, value)| key.len() + estimate_json_size(value))
+        .sum();
+
+    std::mem::size_of::<ProgramEvent>()
+        + event.id.len()
+        + event.program_name.len()
+        + data_size
+        + metadata_size
+}
+
+fn estimate_json_size(value: &serde_json::Value) -> usize {
+    use serde_json::Value;
+
+    std::mem::size_of::<Value>()
+        + match value {
+            Value::String(s) => s.len(),
+            Value::Array(items) => items.iter().map(estimate_json_size).sum(),
+            Value::Object(map) => map
+                .iter()
+                .map(|(k, v)| k.len() + estimate_json_size(v))
+                .sum(),
+            _ => 0,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use watchtower_subscriber::EventType;
+
+    fn log_event(program: &str) -> ProgramEvent {
+        ProgramEvent::new(
+            Pubkey::new_unique(),
+            program.to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: "x".repeat(100),
+                level: None,
+                instruction_index: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_count_limit() {
+        let history = EventHistory::new(3, Duration::from_secs(3600), None);
+        for _ in 0..5 {
+            history.push("a", log_event("a"));
+        }
+
+        assert_eq!(history.get("a").len(), 3);
+        assert_eq!(history.usage().events, 3);
+
+        // Evicted events can no longer be found
+        let held = history.get("a");
+        assert!(history.find("a", &held[0].id).is_some());
+        assert!(history.find("b", &held[0].id).is_none());
+        let evicted = log_event("a");
+        let evicted_id = evicted.id.clone();
+        history.push("a", evicted);
+        for _ in 0..3 {
+            history.push("a", log_event("a"));
+        }
+        assert!(history.find("a", &evicted_id).is_none());
+    }
+
+    #[test]
+    fn test_shared_snapshot_is_not_mutated() {
+        let history = EventHistory::new(10, Duration::from_secs(3600), None);
+        history.push("a", log_event("a"));
+
+        let snapshot = history.get_shared("a");
+        assert!(snapshot.ptr_eq(&history.get_shared("a")));
+
+        history.push("a", log_event("a"));
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(history.get_shared("a").len(), 2);
+    }
+
+    #[test]
+    fn test_memory_budget_evicts_least_recent_program() {
+        let event_size = estimate_event_size(&log_event("a"));
+        let history = EventHistory::new(1000, Duration::from_secs(3600), Some(event_size * 4));
+
+        for _ in 0..3 {
+            history.push("a", log_event("a"));
+        }
+        let evicted = history.push("b", log_event("b")) + history.push("b", log_event("b"));
+
+        assert!(evicted > 0);
+        assert_eq!(history.get("b").len(), 2);
+        assert!(history.get("a").len() < 3);
+        assert!(history.usage().bytes <= event_size * 4);
+    }
+}
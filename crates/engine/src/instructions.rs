@@ -0,0 +1,422 @@
+//! Per-instruction call frequency baselines and spike detection.
+
+use crate::rules::{AlertSeverity, Rule, RuleContext, RuleError, RuleResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use watchtower_subscriber::{EventData, ProgramEvent};
+
+/// Event metadata key carrying a decoded instruction name.
+pub const INSTRUCTION_METADATA_KEY: &str = "instruction";
+
+/// Prefix of the instruction log line emitted by Anchor programs.
+const ANCHOR_INSTRUCTION_LOG: &str = "Instruction: ";
+
+/// Configuration for an `InstructionSpikeRule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstructionSpikeConfig {
+    /// Instructions to watch (e.g. `withdraw`); every instruction when empty
+    #[serde(default)]
+    pub instructions: Vec<String>,
+
+    /// Window in which spikes are counted, in seconds
+    #[serde(default = "default_window")]
+    pub window_seconds: u64,
+
+    /// Baseline window preceding the spike window, in seconds
+    #[serde(default = "default_baseline")]
+    pub baseline_seconds: u64,
+
+    /// Multiple of the baseline rate that triggers an alert
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+
+    /// Minimum calls in the spike window before alerting
+    #[serde(default = "default_min_calls")]
+    pub min_calls: u64,
+
+    /// Instruction names keyed by hex-encoded discriminator (leading data bytes)
+    #[serde(default)]
+    pub discriminators: HashMap<String, String>,
+
+    /// Severity of generated alerts
+    #[serde(default = "default_severity")]
+    pub severity: AlertSeverity,
+}
+
+impl InstructionSpikeConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> Result<(), RuleError> {
+        if self.window_seconds == 0 || self.baseline_seconds <= self.window_seconds {
+            return Err(RuleError::Configuration(
+                "Instruction spike baseline must be longer than a non-zero window".to_string(),
+            ));
+        }
+
+        if self.multiplier <= 1.0 {
+            return Err(RuleError::Configuration(
+                "Instruction spike multiplier must be greater than 1".to_string(),
+            ));
+        }
+
+        for discriminator in self.discriminators.keys() {
+            if decode_hex(discriminator).is_none() {
+                return Err(RuleError::Configuration(format!(
+                    "Invalid instruction discriminator: {}",
+                    discriminator
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Per-second call counts of one instruction.
+type CallBuckets = VecDeque<(i64, u64)>;
+
+/// Observed calls, keyed by program and normalized instruction name.
+#[derive(Debug, Default)]
+struct CallHistory {
+    calls: HashMap<(String, String), CallBuckets>,
+    /// First instruction seen per program, bounding how much baseline exists
+    first_seen: HashMap<String, DateTime<Utc>>,
+}
+
+/// Rule that alerts when an instruction is called far more often than its baseline.
+///
+/// Instruction names come from the `instruction` event metadata key, Anchor's
+/// `Instruction: <Name>` log line, or configured discriminators for raw
+/// instruction events. Names are compared ignoring case and underscores, so
+/// `withdraw_all` matches Anchor's `WithdrawAll`.
+#[derive(Debug)]
+pub struct InstructionSpikeRule {
+    config: InstructionSpikeConfig,
+    /// Watched instruction names, normalized
+    watched: Vec<String>,
+    /// Decoded discriminators and their instruction names
+    discriminators: Vec<(Vec<u8>, String)>,
+    history: Mutex<CallHistory>,
+}
+
+impl InstructionSpikeRule {
+    pub fn new(config: &InstructionSpikeConfig) -> Self {
+        Self {
+            watched: config.instructions.iter().map(|i| normalize(i)).collect(),
+            discriminators: config
+                .discriminators
+                .iter()
+                .filter_map(|(hex, name)| Some((decode_hex(hex)?, name.clone())))
+                .collect(),
+            config: config.clone(),
+            history: Mutex::new(CallHistory::default()),
+        }
+    }
+
+    /// Instruction invoked by an event, if it can be identified.
+    fn instruction_name(&self, event: &ProgramEvent) -> Option<String> {
+        if let Some(name) = event
+            .metadata
+            .get(INSTRUCTION_METADATA_KEY)
+            .and_then(|v| v.as_str())
+        {
+            return Some(name.to_string());
+        }
+
+        match &event.data {
+            EventData::LogEntry { message, .. } => message
+                .split_once(ANCHOR_INSTRUCTION_LOG)
+                .map(|(_, name)| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            EventData::Instruction { data, .. } => self
+                .discriminators
+                .iter()
+                .find(|(discriminator, _)| data.starts_with(discriminator))
+                .map(|(_, name)| name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Record a call and return `(calls in window, expected calls from baseline)`,
+    /// or `None` while less than one window of baseline has been observed.
+    fn record_call(
+        &self,
+        program: &str,
+        instruction: &str,
+        timestamp: DateTime<Utc>,
+    ) -> Option<(u64, f64)> {
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        let first_seen = *history
+            .first_seen
+            .entry(program.to_string())
+            .or_insert(timestamp);
+
+        let now = timestamp.timestamp();
+        let window = self.config.window_seconds as i64;
+        let baseline = self.config.baseline_seconds as i64;
+
+        let buckets = history
+            .calls
+            .entry((program.to_string(), instruction.to_string()))
+            .or_default();
+        match buckets.back_mut() {
+            Some((second, count)) if *second == now => *count += 1,
+            _ => buckets.push_back((now, 1)),
+        }
+        while buckets
+            .front()
+            .is_some_and(|(second, _)| *second <= now - window - baseline)
+        {
+            buckets.pop_front();
+        }
+
+        let (recent, older) = buckets
+            .iter()
+            .fold((0, 0), |(recent, older), (second, count)| {
+                if *second > now - window {
+                    (recent + count, older)
+                } else {
+                    (recent, older + count)
+                }
+            });
+
+        let observed = (now - window - first_seen.timestamp()).min(baseline);
+        if observed < window {
+            return None;
+        }
+
+        let expected = older as f64 * window as f64 / observed as f64;
+        Some((recent, expected))
+    }
+}
+
+#[async_trait]
+impl Rule for InstructionSpikeRule {
+    fn name(&self) -> &str {
+        "instruction_spike"
+    }
+
+    fn description(&self) -> &str {
+        "Detects instructions called far more often than their baseline"
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        self.config.severity
+    }
+
+    fn fresh_instance(&self) -> Option<Box<dyn Rule>> {
+        Some(Box::new(Self::new(&self.config)))
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        let Some(instruction) = self.instruction_name(event) else {
+            return result;
+        };
+        let key = normalize(&instruction);
+        if !self.watched.is_empty() && !self.watched.contains(&key) {
+            return result;
+        }
+
+        let Some((calls, expected)) = self.record_call(&event.program_name, &key, event.timestamp)
+        else {
+            return result;
+        };
+
+        // An idle instruction still counts as one call per window
+        let ratio = calls as f64 / expected.max(1.0);
+        if calls >= self.config.min_calls && ratio >= self.config.multiplier {
+            result.triggered = true;
+            result.message = Some(format!(
+                "Instruction {} called {} times in {}s, {:.1}x its baseline of {:.1}",
+                instruction, calls, self.config.window_seconds, ratio, expected
+            ));
+            result.confidence = (ratio / (self.config.multiplier * 2.0)).clamp(0.5, 1.0);
+            result
+                .metadata
+                .insert("instruction".to_string(), instruction.into());
+            result
+                .metadata
+                .insert("window_calls".to_string(), calls.into());
+            result
+                .metadata
+                .insert("baseline_calls".to_string(), expected.into());
+            result
+                .metadata
+                .insert("spike_ratio".to_string(), ratio.into());
+            result
+                .suggested_actions
+                .push("Check who is calling the instruction and why".to_string());
+            result
+                .suggested_actions
+                .push("Consider pausing the program if the calls are unexpected".to_string());
+        }
+
+        result
+    }
+}
+
+/// Lowercase an instruction name and drop underscores.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Decode a non-empty hex string.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim_start_matches("0x");
+    if hex.is_empty() || hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+impl Default for InstructionSpikeConfig {
+    fn default() -> Self {
+        Self {
+            instructions: Vec::new(),
+            window_seconds: default_window(),
+            baseline_seconds: default_baseline(),
+            multiplier: default_multiplier(),
+            min_calls: default_min_calls(),
+            discriminators: HashMap::new(),
+            severity: default_severity(),
+        }
+    }
+}
+
+// Default value functions
+fn default_window() -> u64 {
+    60
+}
+
+fn default_baseline() -> u64 {
+    3600 // 1 hour
+}
+
+fn default_multiplier() -> f64 {
+    5.0
+}
+
+fn default_min_calls() -> u64 {
+    10
+}
+
+fn default_severity() -> AlertSeverity {
+    AlertSeverity::High
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use watchtower_subscriber::EventType;
+
+    fn anchor_log(name: &str, timestamp: DateTime<Utc>) -> ProgramEvent {
+        let mut event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Vault".to_string(),
+            EventType::LogEntry,
+            EventData::LogEntry {
+                message: format!("Program log: Instruction: {}", name),
+                level: None,
+                instruction_index: None,
+            },
+        );
+        event.timestamp = timestamp;
+        event
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_spike_over_baseline() {
+        let rule = InstructionSpikeRule::new(&InstructionSpikeConfig {
+            instructions: vec!["withdraw".to_string()],
+            window_seconds: 60,
+            baseline_seconds: 600,
+            multiplier: 5.0,
+            min_calls: 10,
+            ..Default::default()
+        });
+        let context = RuleContext::default();
+        let start = Utc::now() - chrono::Duration::seconds(1200);
+
+        // Steady baseline of one withdraw per minute, plus unwatched deposits
+        for minute in 0..15 {
+            let at = start + chrono::Duration::seconds(minute * 60);
+            assert!(
+                !rule
+                    .evaluate(&anchor_log("Withdraw", at), &context)
+                    .await
+                    .triggered
+            );
+            assert!(
+                !rule
+                    .evaluate(&anchor_log("Deposit", at), &context)
+                    .await
+                    .triggered
+            );
+        }
+
+        // Twelve withdraws within one window
+        let burst_start = start + chrono::Duration::seconds(15 * 60);
+        let mut triggered = Vec::new();
+        for second in 0..12 {
+            let at = burst_start + chrono::Duration::seconds(second);
+            triggered.push(rule.evaluate(&anchor_log("Withdraw", at), &context).await);
+        }
+
+        assert!(!triggered[8].triggered);
+        let last = triggered.last().unwrap();
+        assert!(last.triggered);
+        assert_eq!(last.metadata["instruction"], "Withdraw");
+        assert_eq!(last.metadata["window_calls"], 12);
+    }
+
+    #[test]
+    fn test_discriminator_names() {
+        let mut discriminators = HashMap::new();
+        discriminators.insert("b712469c946da122".to_string(), "withdraw".to_string());
+        let config = InstructionSpikeConfig {
+            discriminators,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let rule = InstructionSpikeRule::new(&config);
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Vault".to_string(),
+            EventType::Instruction,
+            EventData::Instruction {
+                index: 0,
+                data: vec![0xb7, 0x12, 0x46, 0x9c, 0x94, 0x6d, 0xa1, 0x22, 1, 2, 3],
+                accounts: Vec::new(),
+                success: true,
+            },
+        );
+        assert_eq!(rule.instruction_name(&event).as_deref(), Some("withdraw"));
+
+        let invalid = InstructionSpikeConfig {
+            discriminators: [("xyz".to_string(), "bad".to_string())].into(),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -6,14 +6,71 @@
 //! - Rule trait and built-in security rules
 //! - Metrics collection and aggregation
 //! - Alert generation based on rule violations
+//! - Confidence calibration from true/false positive feedback
 //! - Sliding window analysis for time-based rules
+//! - Memory-bounded per-program event history
+//! - Pyth oracle price feeds
+//! - TVL computation from vault accounts
+//! - Persistent runtime rule enable/disable state
+//! - Priority scheduling of rule evaluations across events
+//! - Per-address activity aggregation
+//! - Denylisted address lists with hot reload
+//! - Program upgrade and authority change detection
+//! - Instruction call frequency spike detection
+//! - Compute-unit exhaustion detection
+//! - Token supply inflation detection from mint account updates
+//! - Alert enrichment with explorer links, token symbols and USD values
+//! - Dead-letter queue for failed rule evaluations and alert dispatches
+//! - Silences suppressing alerts by program, rule and severity
+//! - Notification delivery history
+//! - Audit log of configuration changes
+//! - gRPC control API for orchestration tooling
+//! - Alert sinks exporting to JSONL files and webhooks
 
+pub mod aggregates;
 pub mod alerts;
+pub mod authority;
+pub mod calibration;
+pub mod compute;
+pub mod config_audit;
+pub mod dead_letter;
+pub mod deliveries;
+pub mod denylist;
 pub mod engine;
+pub mod enrichment;
+pub mod grpc;
+pub mod history;
+pub mod instructions;
 pub mod metrics;
+pub mod oracle;
+pub mod rule_state;
 pub mod rules;
+pub mod scheduler;
+pub mod silences;
+pub mod sinks;
+pub mod supply;
+pub mod tvl;
 
+pub use aggregates::*;
 pub use alerts::*;
+pub use authority::*;
+pub use calibration::*;
+pub use compute::*;
+pub use config_audit::*;
+pub use dead_letter::*;
+pub use deliveries::*;
+pub use denylist::*;
 pub use engine::*;
+pub use enrichment::*;
+pub use grpc::*;
+pub use history::*;
+pub use instructions::*;
 pub use metrics::*;
+pub use oracle::*;
+pub use rule_state::*;
 pub use rules::*;
+pub use scheduler::*;
+pub use silences::*;
+pub use sinks::*;
+pub use supply::*;
+pub use tvl::*;
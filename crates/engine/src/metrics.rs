@@ -3,14 +3,23 @@
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use prometheus::{
-    GaugeVec, Histogram, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Registry,
+    CounterVec, GaugeVec, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Registry,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tdigest::TDigest;
 use thiserror::Error;
 
+/// Number of time buckets a t-digest window is split into for expiry.
+const DIGEST_BUCKETS: u32 = 60;
+
+/// Pending values per bucket before they are compressed into a digest.
+const DIGEST_PENDING_LIMIT: usize = 1000;
+
 /// Metrics collector for program monitoring.
 #[derive(Debug, Clone)]
 pub struct MetricsCollector {
@@ -31,6 +40,111 @@ pub struct MetricsCollector {
 
     /// Sliding window metrics
     windows: Arc<DashMap<String, SlidingWindow>>,
+
+    /// Transaction outcome windows per program
+    outcomes: Arc<DashMap<String, OutcomeWindow>>,
+
+    /// Last registry gather handed to rule contexts
+    registry_values: Arc<Mutex<Option<RegistryGather>>>,
+
+    /// Histogram and sliding window settings
+    config: MetricsConfig,
+}
+
+/// Tunable histogram buckets and sliding window sizes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Bucket boundaries for transaction amounts
+    #[serde(default = "default_transaction_amount_buckets")]
+    pub transaction_amount_buckets: Vec<f64>,
+
+    /// Bucket boundaries for rule evaluation duration, in seconds
+    #[serde(default = "default_rule_evaluation_buckets")]
+    pub rule_evaluation_buckets: Vec<f64>,
+
+    /// Bucket boundaries for event processing latency, in seconds
+    #[serde(default = "default_event_processing_buckets")]
+    pub event_processing_buckets: Vec<f64>,
+
+    /// Sliding window duration in seconds
+    #[serde(default = "default_window_duration")]
+    pub window_duration_seconds: u64,
+
+    /// Maximum data points kept per sliding window
+    #[serde(default = "default_window_max_points")]
+    pub window_max_points: usize,
+
+    /// Percentile backend for sliding windows
+    #[serde(default)]
+    pub percentile_backend: PercentileBackend,
+
+    /// Window over which per-program transaction failure rates are tracked, in seconds
+    #[serde(default = "default_failure_rate_window")]
+    pub failure_rate_window_seconds: u64,
+
+    /// How long rule contexts reuse a gather of the built-in Prometheus
+    /// metrics, in milliseconds (0 gathers for every event)
+    #[serde(default = "default_rule_snapshot_interval")]
+    pub rule_snapshot_interval_ms: u64,
+}
+
+/// Metric values seen by rules.
+///
+/// The gathered built-in metrics are shared between the events of a
+/// snapshot interval; only the live values layered over them are per event.
+#[derive(Debug, Clone, Default)]
+pub struct RuleMetrics {
+    /// Built-in metrics of the last registry gather
+    gathered: Arc<HashMap<String, f64>>,
+
+    /// Custom metrics, outcome windows and values set by callers
+    live: HashMap<String, f64>,
+}
+
+impl RuleMetrics {
+    /// Value of a metric.
+    pub fn get(&self, key: &str) -> Option<&f64> {
+        self.live.get(key).or_else(|| self.gathered.get(key))
+    }
+
+    /// Whether a metric has a value.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Set a metric, overriding any gathered value.
+    pub fn insert(&mut self, key: String, value: f64) -> Option<f64> {
+        self.live.insert(key, value)
+    }
+}
+
+impl From<HashMap<String, f64>> for RuleMetrics {
+    fn from(live: HashMap<String, f64>) -> Self {
+        Self {
+            gathered: Arc::default(),
+            live,
+        }
+    }
+}
+
+/// Flattened built-in metrics and when they were gathered.
+#[derive(Debug)]
+struct RegistryGather {
+    taken: Instant,
+    values: Arc<HashMap<String, f64>>,
+}
+
+/// Transaction outcomes of one program in one-second buckets.
+#[derive(Debug, Default)]
+struct OutcomeWindow {
+    /// (unix second, transactions, failures), oldest first
+    buckets: VecDeque<(i64, u64, u64)>,
+
+    /// Transactions across all buckets
+    total: u64,
+
+    /// Failures across all buckets
+    failed: u64,
 }
 
 /// Built-in counter metrics.
@@ -50,6 +164,21 @@ pub struct MetricsCounters {
 
     /// Rule evaluations
     pub rule_evaluations_total: IntCounterVec,
+
+    /// Time spent polling rule evaluations
+    pub rule_cpu_seconds_total: CounterVec,
+
+    /// Rule evaluations that exceeded their timeout
+    pub rule_timeouts_total: IntCounterVec,
+
+    /// Events evicted from history by the memory budget
+    pub history_evictions_total: IntCounter,
+
+    /// Events dropped by the events per minute limit
+    pub events_dropped_total: IntCounter,
+
+    /// Failures captured in the dead-letter queue
+    pub dead_letters_total: IntCounterVec,
 }
 
 /// Built-in gauge metrics.
@@ -69,6 +198,15 @@ pub struct MetricsGauges {
 
     /// Recent failure rate
     pub failure_rate: GaugeVec,
+
+    /// Programs with event history
+    pub history_programs: IntGauge,
+
+    /// Events held in history
+    pub history_events: IntGauge,
+
+    /// Estimated memory held by event history
+    pub history_bytes: IntGauge,
 }
 
 /// Built-in histogram metrics.
@@ -96,6 +234,22 @@ pub enum MetricValue {
     },
 }
 
+/// How sliding windows compute percentiles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PercentileBackend {
+    /// Interpolate between the retained data points
+    #[default]
+    Exact,
+
+    /// Estimate from a t-digest covering every value in the window, including
+    /// values dropped by the point limit
+    TDigest {
+        /// Maximum number of centroids per digest
+        compression: usize,
+    },
+}
+
 /// Sliding window for time-based metrics.
 #[derive(Debug)]
 pub struct SlidingWindow {
@@ -107,6 +261,41 @@ pub struct SlidingWindow {
 
     /// Maximum number of data points to keep
     max_points: usize,
+
+    /// Optional t-digest used for percentiles
+    digest: Option<WindowDigest>,
+}
+
+/// Time-bucketed t-digests so old values can expire with the window.
+#[derive(Debug)]
+struct WindowDigest {
+    /// Maximum number of centroids per digest
+    compression: usize,
+
+    /// Time span covered by each bucket
+    bucket_width: Duration,
+
+    /// Compressed buckets keyed by their start time
+    buckets: VecDeque<(Instant, TDigest)>,
+
+    /// Values of the open bucket not yet compressed
+    pending: Vec<f64>,
+
+    /// Start time of the open bucket
+    pending_start: Instant,
+}
+
+/// Serialized form of a sliding window with wall-clock timestamps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedWindow {
+    /// Window duration in seconds
+    pub duration_seconds: u64,
+
+    /// Maximum number of data points to keep
+    pub max_points: usize,
+
+    /// Data points with timestamps
+    pub points: Vec<(DateTime<Utc>, f64)>,
 }
 
 /// Metrics snapshot for rule evaluation.
@@ -161,6 +350,9 @@ pub enum MetricsError {
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub type MetricsResult<T> = Result<T, MetricsError>;
@@ -168,11 +360,18 @@ pub type MetricsResult<T> = Result<T, MetricsError>;
 impl MetricsCollector {
     /// Create a new metrics collector.
     pub fn new() -> MetricsResult<Self> {
+        Self::with_config(MetricsConfig::default())
+    }
+
+    /// Create a metrics collector with custom buckets and window sizes.
+    pub fn with_config(config: MetricsConfig) -> MetricsResult<Self> {
+        config.validate()?;
+
         let registry = Arc::new(Registry::new());
 
         let counters = MetricsCounters::new(&registry)?;
         let gauges = MetricsGauges::new(&registry)?;
-        let histograms = MetricsHistograms::new(&registry)?;
+        let histograms = MetricsHistograms::new(&registry, &config)?;
 
         Ok(Self {
             registry,
@@ -181,9 +380,17 @@ impl MetricsCollector {
             gauges,
             histograms,
             windows: Arc::new(DashMap::new()),
+            outcomes: Arc::new(DashMap::new()),
+            registry_values: Arc::new(Mutex::new(None)),
+            config,
         })
     }
 
+    /// Histogram and sliding window settings in use.
+    pub fn config(&self) -> &MetricsConfig {
+        &self.config
+    }
+
     /// Record an event being processed.
     pub fn record_event(&self, program_name: &str, event_type: &str) {
         self.counters
@@ -233,6 +440,47 @@ impl MetricsCollector {
             .observe(duration.as_secs_f64());
     }
 
+    /// Record time a rule spent executing (excluding time spent waiting).
+    pub fn record_rule_cpu_time(&self, rule_name: &str, busy: Duration) {
+        self.counters
+            .rule_cpu_seconds_total
+            .with_label_values(&[rule_name])
+            .inc_by(busy.as_secs_f64());
+    }
+
+    /// Record a rule evaluation timeout.
+    pub fn record_rule_timeout(&self, rule_name: &str) {
+        self.counters
+            .rule_timeouts_total
+            .with_label_values(&[rule_name])
+            .inc();
+    }
+
+    /// Record a failure captured in the dead-letter queue.
+    pub fn record_dead_letter(&self, rule_name: &str, reason: &str) {
+        self.counters
+            .dead_letters_total
+            .with_label_values(&[rule_name, reason])
+            .inc();
+    }
+
+    /// Update event history usage gauges.
+    pub fn update_history_usage(&self, programs: usize, events: usize, bytes: usize) {
+        self.gauges.history_programs.set(programs as i64);
+        self.gauges.history_events.set(events as i64);
+        self.gauges.history_bytes.set(bytes as i64);
+    }
+
+    /// Record events evicted from history by the memory budget.
+    pub fn record_history_evictions(&self, count: u64) {
+        self.counters.history_evictions_total.inc_by(count);
+    }
+
+    /// Record events dropped by the events per minute limit.
+    pub fn record_dropped_events(&self, count: u64) {
+        self.counters.events_dropped_total.inc_by(count);
+    }
+
     /// Update total value locked for a program.
     pub fn update_tvl(&self, program_name: &str, tvl: f64) {
         self.gauges
@@ -242,6 +490,12 @@ impl MetricsCollector {
 
         // Also add to sliding window
         self.add_to_window(&format!("{}_tvl", program_name), tvl);
+
+        // Expose to rules through the snapshot
+        self.set_custom_metric(
+            &crate::tvl::tvl_metric_key(program_name),
+            MetricValue::Gauge(tvl),
+        );
     }
 
     /// Update token price.
@@ -255,6 +509,13 @@ impl MetricsCollector {
         self.add_to_window(&format!("{}_price", token_symbol), price);
     }
 
+    /// Get the most recent price recorded for a token.
+    pub fn token_price(&self, token_symbol: &str) -> Option<f64> {
+        self.windows
+            .get(&format!("{}_price", token_symbol))
+            .and_then(|window| window.latest())
+    }
+
     /// Update failure rate for a program.
     pub fn update_failure_rate(&self, program_name: &str, rate: f64) {
         self.gauges
@@ -265,6 +526,25 @@ impl MetricsCollector {
         self.add_to_window(&format!("{}_failure_rate", program_name), rate);
     }
 
+    /// Record a transaction outcome in the program's failure rate window.
+    ///
+    /// Updates the failure rate gauge (as a percentage) and returns the
+    /// transaction and failure counts currently in the window.
+    pub fn record_outcome(&self, program_name: &str, success: bool) -> (u64, u64) {
+        let (total, failed) = {
+            let mut window = self.outcomes.entry(program_name.to_string()).or_default();
+            window.record(
+                Utc::now().timestamp(),
+                success,
+                self.config.failure_rate_window_seconds,
+            );
+            (window.total, window.failed)
+        };
+
+        self.update_failure_rate(program_name, failed as f64 / total as f64 * 100.0);
+        (total, failed)
+    }
+
     /// Record event processing time.
     pub fn record_event_processing_time(&self, duration_seconds: f64) {
         self.histograms
@@ -277,33 +557,89 @@ impl MetricsCollector {
         let mut window = self
             .windows
             .entry(metric_name.to_string())
-            .or_insert_with(|| SlidingWindow::new(Duration::from_secs(3600), 1000)); // 1 hour window
+            .or_insert_with(|| {
+                SlidingWindow::new(
+                    Duration::from_secs(self.config.window_duration_seconds),
+                    self.config.window_max_points,
+                )
+                .with_backend(self.config.percentile_backend)
+            });
 
         window.add(value);
     }
 
+    /// Names of the sliding windows holding data.
+    pub fn window_names(&self) -> Vec<String> {
+        self.windows
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+
+    /// Points of a sliding window with wall-clock timestamps, oldest first.
+    pub fn window_points(&self, metric_name: &str) -> Option<Vec<(DateTime<Utc>, f64)>> {
+        self.windows
+            .get(metric_name)
+            .map(|window| window.to_persisted().points)
+    }
+
     /// Set a custom metric value.
     pub fn set_custom_metric(&self, name: &str, value: MetricValue) {
         self.custom_metrics.insert(name.to_string(), value);
     }
 
-    /// Get a metrics snapshot for rule evaluation.
-    pub fn snapshot(&self) -> MetricsSnapshot {
-        let mut values = HashMap::new();
-        let mut windows = HashMap::new();
+    /// Save all sliding windows to `path` so they survive a restart.
+    pub fn save_windows(&self, path: &Path) -> MetricsResult<usize> {
+        let windows: HashMap<String, PersistedWindow> = self
+            .windows
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().to_persisted()))
+            .collect();
 
-        // For now, collect basic metrics from our known metric types
-        // This is a simplified approach that avoids accessing private prometheus fields
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
 
-        // Collect custom metrics
-        for entry in self.custom_metrics.iter() {
-            let value = match entry.value() {
-                MetricValue::Counter(v) | MetricValue::Gauge(v) => *v,
-                MetricValue::Histogram { sum, .. } => *sum,
-            };
-            values.insert(entry.key().clone(), value);
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(&windows)?)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(windows.len())
+    }
+
+    /// Restore sliding windows saved by `save_windows`, dropping expired points.
+    ///
+    /// Returns the number of windows restored; a missing file restores nothing.
+    pub fn load_windows(&self, path: &Path) -> MetricsResult<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        let contents = std::fs::read(path)?;
+        let windows: HashMap<String, PersistedWindow> = serde_json::from_slice(&contents)?;
+
+        let mut restored = 0;
+        for (name, persisted) in windows {
+            let window = SlidingWindow::from_persisted(&persisted)
+                .with_backend(self.config.percentile_backend);
+            if window.data.is_empty() {
+                continue;
+            }
+
+            self.windows.insert(name, window);
+            restored += 1;
         }
 
+        Ok(restored)
+    }
+
+    /// Get a full metrics snapshot, gathering every metric and window.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let mut values = self.gather_registry();
+        let mut windows = HashMap::new();
+
+        self.collect_live_values(&mut values);
+
         // Collect sliding window statistics
         for entry in self.windows.iter() {
             if let Some(stats) = entry.value().stats() {
@@ -318,6 +654,68 @@ impl MetricsCollector {
         }
     }
 
+    /// Get metric values for rule evaluation.
+    ///
+    /// Gathering the Prometheus registry walks every label set, so the built-in
+    /// metrics are gathered at most once per `rule_snapshot_interval_ms` and
+    /// shared by the events in between. Custom metrics and transaction outcome
+    /// windows are always current.
+    pub fn rule_values(&self) -> RuleMetrics {
+        let interval = Duration::from_millis(self.config.rule_snapshot_interval_ms);
+        let gathered = {
+            let mut cached = self
+                .registry_values
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            match cached.as_ref() {
+                Some(gather) if gather.taken.elapsed() < interval => gather.values.clone(),
+                _ => {
+                    let values = Arc::new(self.gather_registry());
+                    *cached = Some(RegistryGather {
+                        taken: Instant::now(),
+                        values: values.clone(),
+                    });
+                    values
+                }
+            }
+        };
+
+        let mut live = HashMap::new();
+        self.collect_live_values(&mut live);
+        RuleMetrics { gathered, live }
+    }
+
+    /// Flatten the built-in Prometheus metrics.
+    fn gather_registry(&self) -> HashMap<String, f64> {
+        let mut values = HashMap::new();
+        for family in self.registry.gather() {
+            flatten_metric_family(&family, &mut values);
+        }
+        values
+    }
+
+    /// Add custom metrics and transaction outcome windows to `values`.
+    fn collect_live_values(&self, values: &mut HashMap<String, f64>) {
+        // Collect custom metrics
+        for entry in self.custom_metrics.iter() {
+            let value = match entry.value() {
+                MetricValue::Counter(v) | MetricValue::Gauge(v) => *v,
+                MetricValue::Histogram { sum, .. } => *sum,
+            };
+            values.insert(entry.key().clone(), value);
+        }
+
+        // Collect transaction outcome windows
+        let now = Utc::now().timestamp();
+        for entry in self.outcomes.iter() {
+            let (total, failed) = entry
+                .value()
+                .counts_since(now - self.config.failure_rate_window_seconds as i64);
+            values.insert(window_transactions_key(entry.key()), total as f64);
+            values.insert(window_failures_key(entry.key()), failed as f64);
+        }
+    }
+
     /// Get Prometheus registry for HTTP endpoint.
     pub fn registry(&self) -> Arc<Registry> {
         self.registry.clone()
@@ -372,12 +770,56 @@ impl MetricsCounters {
         )?;
         registry.register(Box::new(rule_evaluations_total.clone()))?;
 
+        let rule_cpu_seconds_total = CounterVec::new(
+            prometheus::Opts::new(
+                "watchtower_rule_cpu_seconds_total",
+                "Time spent executing rule evaluations",
+            ),
+            &["rule"],
+        )?;
+        registry.register(Box::new(rule_cpu_seconds_total.clone()))?;
+
+        let rule_timeouts_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "watchtower_rule_timeouts_total",
+                "Total rule evaluation timeouts",
+            ),
+            &["rule"],
+        )?;
+        registry.register(Box::new(rule_timeouts_total.clone()))?;
+
+        let history_evictions_total = IntCounter::new(
+            "watchtower_event_history_evictions_total",
+            "Events evicted from history by the memory budget",
+        )?;
+        registry.register(Box::new(history_evictions_total.clone()))?;
+
+        let events_dropped_total = IntCounter::new(
+            "watchtower_events_dropped_total",
+            "Events dropped by the events per minute limit",
+        )?;
+        registry.register(Box::new(events_dropped_total.clone()))?;
+
+        let dead_letters_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "watchtower_dead_letters_total",
+                "Failed rule evaluations and alert dispatches captured for retry",
+            ),
+            &["rule", "reason"],
+        )?;
+        registry.register(Box::new(dead_letters_total.clone()))?;
+
         Ok(Self {
             events_total,
             alerts_total,
             transactions_total,
             failed_transactions_total,
             rule_evaluations_total,
+            rule_cpu_seconds_total,
+            rule_timeouts_total,
+            history_evictions_total,
+            events_dropped_total,
+            dead_letters_total,
         })
     }
 }
@@ -417,23 +859,40 @@ impl MetricsGauges {
         )?;
         registry.register(Box::new(failure_rate.clone()))?;
 
+        let history_programs = IntGauge::new(
+            "watchtower_event_history_programs",
+            "Programs with event history",
+        )?;
+        registry.register(Box::new(history_programs.clone()))?;
+
+        let history_events =
+            IntGauge::new("watchtower_event_history_events", "Events held in history")?;
+        registry.register(Box::new(history_events.clone()))?;
+
+        let history_bytes = IntGauge::new(
+            "watchtower_event_history_bytes",
+            "Estimated memory held by event history",
+        )?;
+        registry.register(Box::new(history_bytes.clone()))?;
+
         Ok(Self {
             active_connections,
             total_value_locked,
             token_prices,
             program_accounts,
             failure_rate,
+            history_programs,
+            history_events,
+            history_bytes,
         })
     }
 }
 
 impl MetricsHistograms {
-    fn new(registry: &Registry) -> MetricsResult<Self> {
+    fn new(registry: &Registry, config: &MetricsConfig) -> MetricsResult<Self> {
         let transaction_amounts = HistogramVec::new(
             prometheus::HistogramOpts::new("watchtower_transaction_amounts", "Transaction amounts")
-                .buckets(vec![
-                    100.0, 1000.0, 10000.0, 100000.0, 1000000.0, 10000000.0,
-                ]),
+                .buckets(config.transaction_amount_buckets.clone()),
             &["program"],
         )?;
         registry.register(Box::new(transaction_amounts.clone()))?;
@@ -443,7 +902,7 @@ impl MetricsHistograms {
                 "watchtower_rule_evaluation_duration_seconds",
                 "Rule evaluation duration",
             )
-            .buckets(prometheus::DEFAULT_BUCKETS.to_vec()),
+            .buckets(config.rule_evaluation_buckets.clone()),
             &["rule"],
         )?;
         registry.register(Box::new(rule_evaluation_duration.clone()))?;
@@ -453,7 +912,7 @@ impl MetricsHistograms {
                 "watchtower_event_processing_latency_seconds",
                 "Event processing latency",
             )
-            .buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+            .buckets(config.event_processing_buckets.clone()),
         )?;
         registry.register(Box::new(event_processing_latency.clone()))?;
 
@@ -465,19 +924,160 @@ impl MetricsHistograms {
     }
 }
 
+impl OutcomeWindow {
+    /// Add an outcome at `now` and drop buckets older than `window_seconds`.
+    fn record(&mut self, now: i64, success: bool, window_seconds: u64) {
+        let failed = u64::from(!success);
+        match self.buckets.back_mut() {
+            Some((second, total, failures)) if *second == now => {
+                *total += 1;
+                *failures += failed;
+            }
+            _ => self.buckets.push_back((now, 1, failed)),
+        }
+        self.total += 1;
+        self.failed += failed;
+
+        let cutoff = now - window_seconds as i64;
+        while let Some(&(second, total, failures)) = self.buckets.front() {
+            if second > cutoff {
+                break;
+            }
+            self.buckets.pop_front();
+            self.total -= total;
+            self.failed -= failures;
+        }
+    }
+
+    /// Transaction and failure counts in buckets newer than `cutoff`.
+    fn counts_since(&self, cutoff: i64) -> (u64, u64) {
+        self.buckets
+            .iter()
+            .rev()
+            .take_while(|(second, _, _)| *second > cutoff)
+            .fold((0, 0), |(total, failed), (_, t, f)| (total + t, failed + f))
+    }
+}
+
+/// Snapshot key holding a program's transaction count in the failure rate window.
+pub fn window_transactions_key(program_name: &str) -> String {
+    format!("{}_window_transactions", program_name)
+}
+
+/// Snapshot key holding a program's failed transaction count in the failure rate window.
+pub fn window_failures_key(program_name: &str) -> String {
+    format!("{}_window_failures", program_name)
+}
+
+impl MetricsConfig {
+    /// Validate bucket boundaries and window sizes.
+    pub fn validate(&self) -> MetricsResult<()> {
+        for (name, buckets) in [
+            (
+                "transaction_amount_buckets",
+                &self.transaction_amount_buckets,
+            ),
+            ("rule_evaluation_buckets", &self.rule_evaluation_buckets),
+            ("event_processing_buckets", &self.event_processing_buckets),
+        ] {
+            if buckets.is_empty() || buckets.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(MetricsError::InvalidValue(format!(
+                    "{} must be non-empty and strictly increasing",
+                    name
+                )));
+            }
+        }
+
+        if self.window_duration_seconds == 0
+            || self.window_max_points == 0
+            || self.failure_rate_window_seconds == 0
+        {
+            return Err(MetricsError::InvalidValue(
+                "Window durations and max points must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            transaction_amount_buckets: default_transaction_amount_buckets(),
+            rule_evaluation_buckets: default_rule_evaluation_buckets(),
+            event_processing_buckets: default_event_processing_buckets(),
+            window_duration_seconds: default_window_duration(),
+            window_max_points: default_window_max_points(),
+            percentile_backend: PercentileBackend::default(),
+            failure_rate_window_seconds: default_failure_rate_window(),
+            rule_snapshot_interval_ms: default_rule_snapshot_interval(),
+        }
+    }
+}
+
+// Default value functions
+fn default_transaction_amount_buckets() -> Vec<f64> {
+    vec![100.0, 1000.0, 10000.0, 100000.0, 1000000.0, 10000000.0]
+}
+
+fn default_rule_evaluation_buckets() -> Vec<f64> {
+    prometheus::DEFAULT_BUCKETS.to_vec()
+}
+
+fn default_event_processing_buckets() -> Vec<f64> {
+    vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]
+}
+
+fn default_window_duration() -> u64 {
+    3600 // 1 hour
+}
+
+fn default_window_max_points() -> usize {
+    1000
+}
+
+fn default_failure_rate_window() -> u64 {
+    300 // 5 minutes
+}
+
+fn default_rule_snapshot_interval() -> u64 {
+    1000 // 1 second
+}
+
 impl SlidingWindow {
     pub fn new(duration: Duration, max_points: usize) -> Self {
         Self {
             duration,
             data: Vec::new(),
             max_points,
+            digest: None,
         }
     }
 
+    /// Switch the percentile backend, seeding a digest from the retained points.
+    pub fn with_backend(mut self, backend: PercentileBackend) -> Self {
+        self.digest = match backend {
+            PercentileBackend::Exact => None,
+            PercentileBackend::TDigest { compression } => {
+                let mut digest = WindowDigest::new(compression, self.duration);
+                for (timestamp, value) in &self.data {
+                    digest.add(*timestamp, *value, self.duration);
+                }
+                Some(digest)
+            }
+        };
+        self
+    }
+
     pub fn add(&mut self, value: f64) {
         let now = Instant::now();
         self.data.push((now, value));
 
+        if let Some(digest) = &mut self.digest {
+            digest.add(now, value, self.duration);
+        }
+
         // Remove old data points
         let cutoff = now - self.duration;
         self.data.retain(|(timestamp, _)| *timestamp > cutoff);
@@ -489,32 +1089,101 @@ impl SlidingWindow {
         }
     }
 
+    /// Convert to a serializable form using wall-clock timestamps.
+    pub fn to_persisted(&self) -> PersistedWindow {
+        let now = Instant::now();
+        let wall_now = Utc::now();
+
+        PersistedWindow {
+            duration_seconds: self.duration.as_secs(),
+            max_points: self.max_points,
+            points: self
+                .data
+                .iter()
+                .map(|(timestamp, value)| {
+                    let age = chrono::Duration::from_std(now.saturating_duration_since(*timestamp))
+                        .unwrap_or_default();
+                    (wall_now - age, *value)
+                })
+                .collect(),
+        }
+    }
+
+    /// Rebuild a window from its persisted form, skipping points outside the window.
+    pub fn from_persisted(persisted: &PersistedWindow) -> Self {
+        let now = Instant::now();
+        let wall_now = Utc::now();
+        let duration = Duration::from_secs(persisted.duration_seconds);
+
+        let data = persisted
+            .points
+            .iter()
+            .filter_map(|(timestamp, value)| {
+                let age = (wall_now - *timestamp).to_std().unwrap_or_default();
+                if age >= duration {
+                    return None;
+                }
+                now.checked_sub(age).map(|instant| (instant, *value))
+            })
+            .collect();
+
+        Self {
+            duration,
+            data,
+            max_points: persisted.max_points,
+            digest: None,
+        }
+    }
+
+    /// Most recently added value.
+    pub fn latest(&self) -> Option<f64> {
+        self.data.last().map(|(_, v)| *v)
+    }
+
     pub fn stats(&self) -> Option<WindowStats> {
         if self.data.is_empty() {
             return None;
         }
 
-        let values: Vec<f64> = self.data.iter().map(|(_, v)| *v).collect();
-        let count = values.len();
-        let sum: f64 = values.iter().sum();
+        let count = self.data.len();
+        let sum: f64 = self.data.iter().map(|(_, v)| v).sum();
         let avg = sum / count as f64;
 
-        let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-        let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let (min, max) = self
+            .data
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+                (min.min(*v), max.max(*v))
+            });
 
         // Calculate standard deviation
-        let variance: f64 = values.iter().map(|v| (v - avg).powi(2)).sum::<f64>() / count as f64;
+        let variance: f64 = self
+            .data
+            .iter()
+            .map(|(_, v)| (v - avg).powi(2))
+            .sum::<f64>()
+            / count as f64;
         let std_dev = variance.sqrt();
 
         // Calculate percentiles
-        let mut sorted_values = values.clone();
-        sorted_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
-        let mut percentiles = HashMap::new();
-        percentiles.insert("50th".to_string(), percentile(&sorted_values, 0.5));
-        percentiles.insert("90th".to_string(), percentile(&sorted_values, 0.9));
-        percentiles.insert("95th".to_string(), percentile(&sorted_values, 0.95));
-        percentiles.insert("99th".to_string(), percentile(&sorted_values, 0.99));
+        const QUANTILES: [(&str, f64); 4] =
+            [("50th", 0.5), ("90th", 0.9), ("95th", 0.95), ("99th", 0.99)];
+
+        let percentiles = match self.digest.as_ref().and_then(|d| d.merged(self.duration)) {
+            Some(digest) => QUANTILES
+                .iter()
+                .map(|(name, q)| (name.to_string(), digest.estimate_quantile(*q)))
+                .collect(),
+            None => {
+                let mut sorted_values: Vec<f64> = self.data.iter().map(|(_, v)| *v).collect();
+                sorted_values.sort_unstable_by(f64::total_cmp);
+
+                QUANTILES
+                    .iter()
+                    .map(|(name, q)| (name.to_string(), percentile(&sorted_values, *q)))
+                    .collect()
+            }
+        };
 
         Some(WindowStats {
             avg,
@@ -528,13 +1197,136 @@ impl SlidingWindow {
     }
 }
 
+impl WindowDigest {
+    fn new(compression: usize, duration: Duration) -> Self {
+        Self {
+            compression: compression.max(1),
+            bucket_width: (duration / DIGEST_BUCKETS).max(Duration::from_millis(1)),
+            buckets: VecDeque::new(),
+            pending: Vec::new(),
+            pending_start: Instant::now(),
+        }
+    }
+
+    /// Add a value observed at `timestamp`, expiring buckets older than `duration`.
+    fn add(&mut self, timestamp: Instant, value: f64, duration: Duration) {
+        if timestamp.saturating_duration_since(self.pending_start) >= self.bucket_width
+            || self.pending.len() >= DIGEST_PENDING_LIMIT
+        {
+            self.flush();
+            self.pending_start = timestamp;
+        }
+        self.pending.push(value);
+
+        // A bucket expires once its newest possible value has left the window
+        while let Some((start, _)) = self.buckets.front() {
+            if timestamp.saturating_duration_since(*start) < duration + self.bucket_width {
+                break;
+            }
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Compress the open bucket's pending values into a digest.
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let values = std::mem::take(&mut self.pending);
+        let digest = TDigest::new_with_size(self.compression).merge_unsorted(values);
+        self.buckets.push_back((self.pending_start, digest));
+    }
+
+    /// Merge all live buckets into a single digest.
+    fn merged(&self, duration: Duration) -> Option<TDigest> {
+        let now = Instant::now();
+        let mut digests: Vec<TDigest> = self
+            .buckets
+            .iter()
+            .filter(|(start, _)| {
+                now.saturating_duration_since(*start) < duration + self.bucket_width
+            })
+            .map(|(_, digest)| digest.clone())
+            .collect();
+
+        if !self.pending.is_empty() {
+            digests.push(
+                TDigest::new_with_size(self.compression).merge_unsorted(self.pending.clone()),
+            );
+        }
+
+        if digests.is_empty() {
+            return None;
+        }
+
+        Some(TDigest::merge_digests(digests))
+    }
+}
+
+/// Flatten a gathered metric family into snapshot values.
+///
+/// Labelled series are keyed like the Prometheus text format, e.g.
+/// `watchtower_events_total{program="Orca",event_type="swap"}`. Histograms and
+/// summaries contribute `_sum` and `_count` series.
+fn flatten_metric_family(
+    family: &prometheus::proto::MetricFamily,
+    values: &mut HashMap<String, f64>,
+) {
+    use prometheus::proto::MetricType;
+
+    let name = family.name();
+    for metric in family.get_metric() {
+        let labels = metric
+            .get_label()
+            .iter()
+            .map(|label| format!("{}=\"{}\"", label.name(), label.value()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let key = |suffix: &str| {
+            if labels.is_empty() {
+                format!("{}{}", name, suffix)
+            } else {
+                format!("{}{}{{{}}}", name, suffix, labels)
+            }
+        };
+
+        match family.get_field_type() {
+            MetricType::COUNTER => {
+                values.insert(key(""), metric.counter.value());
+            }
+            MetricType::GAUGE => {
+                values.insert(key(""), metric.gauge.value());
+            }
+            MetricType::UNTYPED => {
+                values.insert(key(""), metric.untyped.value());
+            }
+            MetricType::HISTOGRAM => {
+                let histogram = &metric.histogram;
+                values.insert(key("_sum"), histogram.sample_sum());
+                values.insert(key("_count"), histogram.sample_count() as f64);
+            }
+            MetricType::SUMMARY => {
+                let summary = &metric.summary;
+                values.insert(key("_sum"), summary.sample_sum());
+                values.insert(key("_count"), summary.sample_count() as f64);
+            }
+        }
+    }
+}
+
+/// Percentile of sorted values using linear interpolation between closest ranks.
 fn percentile(sorted_values: &[f64], p: f64) -> f64 {
     if sorted_values.is_empty() {
         return 0.0;
     }
 
-    let index = (p * (sorted_values.len() - 1) as f64) as usize;
-    sorted_values[index.min(sorted_values.len() - 1)]
+    let rank = p.clamp(0.0, 1.0) * (sorted_values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+
+    sorted_values[lower] + (sorted_values[upper] - sorted_values[lower]) * weight
 }
 
 impl Default for MetricsCollector {
@@ -554,6 +1346,96 @@ mod tests {
         assert!(collector.is_ok());
     }
 
+    #[test]
+    fn test_snapshot_includes_prometheus_metrics() {
+        let collector = MetricsCollector::new().unwrap();
+        collector.record_event("Orca", "swap");
+        collector.record_event("Orca", "swap");
+        collector.record_event_processing_time(0.5);
+
+        let values = collector.snapshot().values;
+        assert_eq!(
+            values.get("watchtower_events_total{event_type=\"swap\",program=\"Orca\"}"),
+            Some(&2.0)
+        );
+        assert_eq!(
+            values.get("watchtower_event_processing_latency_seconds_count"),
+            Some(&1.0)
+        );
+    }
+
+    #[test]
+    fn test_rule_values_reuse_registry_gather() {
+        let collector = MetricsCollector::new().unwrap();
+        let key = "watchtower_events_total{event_type=\"swap\",program=\"Orca\"}";
+        collector.record_event("Orca", "swap");
+        assert_eq!(collector.rule_values().get(key), Some(&1.0));
+
+        // Built-in metrics come from the cached gather, custom ones are live
+        collector.record_event("Orca", "swap");
+        collector.set_custom_metric("Orca_tvl", MetricValue::Gauge(5.0));
+        let values = collector.rule_values();
+        assert_eq!(values.get(key), Some(&1.0));
+        assert_eq!(values.get("Orca_tvl"), Some(&5.0));
+        assert_eq!(collector.snapshot().values.get(key), Some(&2.0));
+
+        // Events between gathers share the gathered values rather than copies
+        assert!(Arc::ptr_eq(
+            &values.gathered,
+            &collector.rule_values().gathered
+        ));
+
+        let uncached = MetricsCollector::with_config(MetricsConfig {
+            rule_snapshot_interval_ms: 0,
+            ..MetricsConfig::default()
+        })
+        .unwrap();
+        uncached.record_event("Orca", "swap");
+        uncached.rule_values();
+        uncached.record_event("Orca", "swap");
+        assert_eq!(uncached.rule_values().get(key), Some(&2.0));
+    }
+
+    #[test]
+    fn test_custom_metrics_config() {
+        let collector = MetricsCollector::with_config(MetricsConfig {
+            window_max_points: 5,
+            ..MetricsConfig::default()
+        })
+        .unwrap();
+
+        for i in 0..10 {
+            collector.add_to_window("custom", i as f64);
+        }
+        assert_eq!(collector.snapshot().windows["custom"].count, 5);
+
+        let invalid = MetricsConfig {
+            event_processing_buckets: vec![1.0, 0.5],
+            ..MetricsConfig::default()
+        };
+        assert!(MetricsCollector::with_config(invalid).is_err());
+    }
+
+    #[test]
+    fn test_outcome_window() {
+        let mut window = OutcomeWindow::default();
+        window.record(100, true, 60);
+        window.record(100, false, 60);
+        window.record(130, false, 60);
+        assert_eq!((window.total, window.failed), (3, 2));
+        assert_eq!(window.counts_since(110), (1, 1));
+
+        // Buckets from second 100 fall out of the window
+        window.record(161, true, 60);
+        assert_eq!((window.total, window.failed), (2, 1));
+
+        let collector = MetricsCollector::new().unwrap();
+        collector.record_outcome("Orca", false);
+        let values = collector.snapshot().values;
+        assert_eq!(values[&window_transactions_key("Orca")], 1.0);
+        assert_eq!(values[&window_failures_key("Orca")], 1.0);
+    }
+
     #[test]
     fn test_sliding_window() {
         let mut window = SlidingWindow::new(Duration::from_secs(60), 100);
@@ -569,10 +1451,73 @@ mod tests {
         assert_eq!(stats.max, 30.0);
     }
 
+    #[test]
+    fn test_window_persistence_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("watchtower-windows-{}.json", uuid::Uuid::new_v4()));
+
+        let collector = MetricsCollector::new().unwrap();
+        collector.update_tvl("Test Program", 1000.0);
+        collector.update_tvl("Test Program", 1200.0);
+        assert_eq!(collector.save_windows(&path).unwrap(), 1);
+
+        let points = collector.window_points("Test Program_tvl").unwrap();
+        assert_eq!(points.len(), 2);
+        assert!(points[0].0 <= points[1].0);
+        assert_eq!(
+            collector.window_names(),
+            vec!["Test Program_tvl".to_string()]
+        );
+
+        let restored = MetricsCollector::new().unwrap();
+        assert_eq!(restored.load_windows(&path).unwrap(), 1);
+        let stats = restored.snapshot().windows["Test Program_tvl"].clone();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.max, 1200.0);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_expired_points_not_restored() {
+        let persisted = PersistedWindow {
+            duration_seconds: 60,
+            max_points: 10,
+            points: vec![
+                (Utc::now() - chrono::Duration::seconds(120), 1.0),
+                (Utc::now() - chrono::Duration::seconds(10), 2.0),
+            ],
+        };
+
+        let window = SlidingWindow::from_persisted(&persisted);
+        assert_eq!(window.latest(), Some(2.0));
+        assert_eq!(window.stats().unwrap().count, 1);
+    }
+
     #[test]
     fn test_percentile_calculation() {
         let values = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
-        assert_eq!(percentile(&values, 0.5), 5.0);
-        assert_eq!(percentile(&values, 0.9), 9.0);
+        assert!((percentile(&values, 0.5) - 5.5).abs() < 1e-9);
+        assert!((percentile(&values, 0.9) - 9.1).abs() < 1e-9);
+        assert_eq!(percentile(&values, 0.0), 1.0);
+        assert_eq!(percentile(&values, 1.0), 10.0);
+        assert_eq!(percentile(&[42.0], 0.99), 42.0);
+    }
+
+    #[test]
+    fn test_tdigest_covers_points_beyond_limit() {
+        let mut window = SlidingWindow::new(Duration::from_secs(60), 100)
+            .with_backend(PercentileBackend::TDigest { compression: 100 });
+
+        for i in 1..=10_000 {
+            window.add(i as f64);
+        }
+
+        let stats = window.stats().unwrap();
+        assert_eq!(stats.count, 100);
+
+        // The digest still sees all 10,000 values, not just the retained 100
+        let median = stats.percentiles["50th"];
+        assert!((median - 5000.0).abs() < 100.0, "median was {}", median);
     }
 }
@@ -0,0 +1,381 @@
+//! Pyth price feed integration for oracle-based rules.
+
+use crate::metrics::MetricsCollector;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::{debug, error, info, warn};
+
+/// Configuration for the Pyth price feed poller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythConfig {
+    /// Hermes API base URL
+    #[serde(default = "default_hermes_url")]
+    pub hermes_url: String,
+
+    /// Price feeds to poll
+    pub feeds: Vec<PythFeedConfig>,
+
+    /// Polling interval in seconds
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_seconds: u64,
+
+    /// Prices older than this are ignored (in seconds)
+    #[serde(default = "default_max_staleness")]
+    pub max_staleness_seconds: u64,
+
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_request_timeout")]
+    pub request_timeout_seconds: u64,
+
+    /// Symbol used as the reference by `OracleDeviationRule` (defaults to the first feed)
+    #[serde(default)]
+    pub reference_symbol: Option<String>,
+}
+
+/// A single Pyth price feed to track.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythFeedConfig {
+    /// Token symbol used for metric names (e.g. "SOL")
+    pub symbol: String,
+
+    /// Pyth price feed ID (hex, with or without 0x prefix)
+    pub feed_id: String,
+}
+
+/// A price observation decoded from Pyth.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythPrice {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Price feed ID
+    pub feed_id: String,
+
+    /// Price as a floating point value
+    pub price: f64,
+
+    /// Confidence interval as a floating point value
+    pub confidence: f64,
+
+    /// Publish time reported by Pyth
+    pub publish_time: DateTime<Utc>,
+}
+
+/// Errors that can occur while fetching oracle prices.
+#[derive(Error, Debug)]
+pub enum OracleError {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Invalid price data: {0}")]
+    InvalidData(String),
+
+    #[error("Oracle configuration error: {0}")]
+    Configuration(String),
+}
+
+pub type OracleResult<T> = Result<T, OracleError>;
+
+/// Hermes `/v2/updates/price/latest` response body.
+#[derive(Debug, Deserialize)]
+struct HermesResponse {
+    #[serde(default)]
+    parsed: Vec<HermesParsedUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesParsedUpdate {
+    id: String,
+    price: HermesPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesPrice {
+    price: String,
+    conf: String,
+    expo: i32,
+    publish_time: i64,
+}
+
+/// Metric key under which the Pyth reference price for a symbol is published.
+///
+/// Rules read this key from `RuleContext::metrics`.
+pub fn pyth_metric_key(symbol: &str) -> String {
+    format!("pyth_{}_price", symbol.to_lowercase())
+}
+
+/// Poller that pulls prices from Pyth Hermes and feeds them into the metrics collector.
+pub struct PythPriceFeed {
+    config: PythConfig,
+    client: reqwest::Client,
+    metrics: Arc<MetricsCollector>,
+    price_sender: broadcast::Sender<PythPrice>,
+}
+
+impl PythPriceFeed {
+    /// Create a new Pyth price feed poller.
+    pub fn new(config: PythConfig, metrics: Arc<MetricsCollector>) -> OracleResult<Self> {
+        config.validate()?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .build()?;
+        let (price_sender, _) = broadcast::channel(100);
+
+        Ok(Self {
+            config,
+            client,
+            metrics,
+            price_sender,
+        })
+    }
+
+    /// Fetch the latest prices for all configured feeds.
+    pub async fn fetch_prices(&self) -> OracleResult<Vec<PythPrice>> {
+        let url = format!(
+            "{}/v2/updates/price/latest",
+            self.config.hermes_url.trim_end_matches('/')
+        );
+
+        let mut query: Vec<(&str, String)> = self
+            .config
+            .feeds
+            .iter()
+            .map(|feed| ("ids[]", normalize_feed_id(&feed.feed_id)))
+            .collect();
+        query.push(("parsed", "true".to_string()));
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: HermesResponse = response.json().await?;
+        self.decode_prices(body)
+    }
+
+    /// Fetch prices once and publish them to the metrics collector.
+    pub async fn refresh(&self) -> OracleResult<usize> {
+        let prices = self.fetch_prices().await?;
+        let max_age = chrono::Duration::seconds(self.config.max_staleness_seconds as i64);
+        let now = Utc::now();
+        let mut published = 0;
+
+        for price in prices {
+            if now - price.publish_time > max_age {
+                warn!(
+                    "Ignoring stale Pyth price for {} (published {})",
+                    price.symbol, price.publish_time
+                );
+                continue;
+            }
+
+            self.metrics.update_token_price(&price.symbol, price.price);
+            self.metrics.set_custom_metric(
+                &pyth_metric_key(&price.symbol),
+                crate::metrics::MetricValue::Gauge(price.price),
+            );
+
+            // No subscribers is not an error
+            let _ = self.price_sender.send(price);
+            published += 1;
+        }
+
+        debug!("Published {} Pyth prices", published);
+        Ok(published)
+    }
+
+    /// Start polling Hermes in the background.
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        info!(
+            "Starting Pyth price feed for {} feeds (every {}s)",
+            self.config.feeds.len(),
+            self.config.poll_interval_seconds
+        );
+
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.config.poll_interval_seconds));
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.refresh().await {
+                    error!("Failed to refresh Pyth prices: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Subscribe to price updates.
+    pub fn subscribe(&self) -> broadcast::Receiver<PythPrice> {
+        self.price_sender.subscribe()
+    }
+
+    /// Convert a Hermes response into prices for configured feeds.
+    fn decode_prices(&self, body: HermesResponse) -> OracleResult<Vec<PythPrice>> {
+        let mut prices = Vec::with_capacity(body.parsed.len());
+
+        for update in body.parsed {
+            let feed_id = normalize_feed_id(&update.id);
+            let Some(feed) = self
+                .config
+                .feeds
+                .iter()
+                .find(|f| normalize_feed_id(&f.feed_id) == feed_id)
+            else {
+                debug!("Ignoring unconfigured Pyth feed {}", feed_id);
+                continue;
+            };
+
+            let raw_price: i64 = update.price.price.parse().map_err(|_| {
+                OracleError::InvalidData(format!("invalid price for feed {}", feed_id))
+            })?;
+            let raw_conf: u64 = update.price.conf.parse().map_err(|_| {
+                OracleError::InvalidData(format!("invalid confidence for feed {}", feed_id))
+            })?;
+            let publish_time = Utc
+                .timestamp_opt(update.price.publish_time, 0)
+                .single()
+                .ok_or_else(|| {
+                    OracleError::InvalidData(format!("invalid publish time for feed {}", feed_id))
+                })?;
+
+            let scale = 10f64.powi(update.price.expo);
+            prices.push(PythPrice {
+                symbol: feed.symbol.clone(),
+                feed_id,
+                price: raw_price as f64 * scale,
+                confidence: raw_conf as f64 * scale,
+                publish_time,
+            });
+        }
+
+        Ok(prices)
+    }
+}
+
+impl PythConfig {
+    /// Symbol that oracle rules should compare against.
+    pub fn reference_symbol(&self) -> Option<&str> {
+        self.reference_symbol
+            .as_deref()
+            .or_else(|| self.feeds.first().map(|f| f.symbol.as_str()))
+    }
+
+    /// Validate the configuration.
+    pub fn validate(&self) -> OracleResult<()> {
+        if self.feeds.is_empty() {
+            return Err(OracleError::Configuration(
+                "At least one Pyth feed must be configured".to_string(),
+            ));
+        }
+
+        if self.poll_interval_seconds == 0 {
+            return Err(OracleError::Configuration(
+                "Poll interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some(symbol) = &self.reference_symbol {
+            if !self.feeds.iter().any(|f| &f.symbol == symbol) {
+                return Err(OracleError::Configuration(format!(
+                    "Reference symbol {} has no configured feed",
+                    symbol
+                )));
+            }
+        }
+
+        for feed in &self.feeds {
+            let id = normalize_feed_id(&feed.feed_id);
+            if id.len() != 64 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(OracleError::Configuration(format!(
+                    "Invalid Pyth feed ID for {}: {}",
+                    feed.symbol, feed.feed_id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn normalize_feed_id(id: &str) -> String {
+    id.trim_start_matches("0x").to_lowercase()
+}
+
+// Default value functions
+fn default_hermes_url() -> String {
+    "https://hermes.pyth.network".to_string()
+}
+
+fn default_poll_interval() -> u64 {
+    10
+}
+
+fn default_max_staleness() -> u64 {
+    60
+}
+
+fn default_request_timeout() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL_FEED: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+    fn test_config() -> PythConfig {
+        PythConfig {
+            hermes_url: default_hermes_url(),
+            feeds: vec![PythFeedConfig {
+                symbol: "SOL".to_string(),
+                feed_id: format!("0x{}", SOL_FEED),
+            }],
+            poll_interval_seconds: default_poll_interval(),
+            max_staleness_seconds: default_max_staleness(),
+            request_timeout_seconds: default_request_timeout(),
+            reference_symbol: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_hermes_response() {
+        let feed =
+            PythPriceFeed::new(test_config(), Arc::new(MetricsCollector::new().unwrap())).unwrap();
+
+        let body: HermesResponse = serde_json::from_value(serde_json::json!({
+            "parsed": [{
+                "id": SOL_FEED,
+                "price": {
+                    "price": "14523000000",
+                    "conf": "7500000",
+                    "expo": -8,
+                    "publish_time": 1700000000
+                }
+            }]
+        }))
+        .unwrap();
+
+        let prices = feed.decode_prices(body).unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].symbol, "SOL");
+        assert!((prices[0].price - 145.23).abs() < 1e-9);
+        assert!((prices[0].confidence - 0.075).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_feed_id_rejected() {
+        let mut config = test_config();
+        config.feeds[0].feed_id = "not-a-feed".to_string();
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Persistent runtime enable/disable state for rules, and the declarative
+//! rules created, modified or removed at runtime.
+
+use crate::rules::RateOfChangeConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::debug;
+
+/// Stores rule enable/disable overrides and runtime rule definitions,
+/// optionally persisted to a JSON file.
+#[derive(Debug, Default)]
+pub struct RuleStateStore {
+    /// File the overrides are persisted to
+    path: Option<PathBuf>,
+
+    /// Enabled flag overrides keyed by rule name
+    overrides: HashMap<String, bool>,
+
+    /// Declarative rules created or modified at runtime, keyed by rule name
+    definitions: BTreeMap<String, RateOfChangeConfig>,
+
+    /// Declarative rules removed at runtime, skipped when the configuration
+    /// file still defines them
+    deleted: BTreeSet<String>,
+}
+
+/// On-disk representation of the rule state file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RuleStateFile {
+    #[serde(default)]
+    rules: HashMap<String, bool>,
+
+    #[serde(default)]
+    definitions: BTreeMap<String, RateOfChangeConfig>,
+
+    #[serde(default)]
+    deleted: BTreeSet<String>,
+}
+
+/// Errors that can occur while loading or saving rule state.
+#[derive(Error, Debug)]
+pub enum RuleStateError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type RuleStateResult<T> = Result<T, RuleStateError>;
+
+impl RuleStateStore {
+    /// Create an in-memory store that is not persisted.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open a store backed by `path`, loading existing overrides if the file exists.
+    pub fn open(path: impl Into<PathBuf>) -> RuleStateResult<Self> {
+        let path = path.into();
+        let file = if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            serde_json::from_str::<RuleStateFile>(&contents)?
+        } else {
+            RuleStateFile::default()
+        };
+
+        debug!(
+            "Loaded {} rule state overrides and {} rule definitions from {}",
+            file.rules.len(),
+            file.definitions.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path: Some(path),
+            overrides: file.rules,
+            definitions: file.definitions,
+            deleted: file.deleted,
+        })
+    }
+
+    /// Path of the backing file, if persisted.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Get the enabled override for a rule.
+    pub fn get(&self, rule_name: &str) -> Option<bool> {
+        self.overrides.get(rule_name).copied()
+    }
+
+    /// Set the enabled override for a rule and persist it.
+    pub fn set(&mut self, rule_name: &str, enabled: bool) -> RuleStateResult<()> {
+        self.overrides.insert(rule_name.to_string(), enabled);
+        self.save()
+    }
+
+    /// Declarative rules created or modified at runtime.
+    pub fn definitions(&self) -> impl Iterator<Item = &RateOfChangeConfig> {
+        self.definitions.values()
+    }
+
+    /// Runtime definition of a declarative rule.
+    pub fn definition(&self, rule_name: &str) -> Option<&RateOfChangeConfig> {
+        self.definitions.get(rule_name)
+    }
+
+    /// Whether a declarative rule was removed at runtime.
+    pub fn is_deleted(&self, rule_name: &str) -> bool {
+        self.deleted.contains(rule_name)
+    }
+
+    /// Store the definition of a declarative rule and persist it.
+    pub fn define(&mut self, config: RateOfChangeConfig) -> RuleStateResult<()> {
+        self.deleted.remove(&config.name);
+        self.definitions.insert(config.name.clone(), config);
+        self.save()
+    }
+
+    /// Record the removal of a declarative rule, dropping its definition
+    /// and enabled override, and persist it.
+    pub fn delete(&mut self, rule_name: &str) -> RuleStateResult<()> {
+        self.definitions.remove(rule_name);
+        self.overrides.remove(rule_name);
+        self.deleted.insert(rule_name.to_string());
+        self.save()
+    }
+
+    /// Write the overrides to the backing file.
+    pub fn save(&self) -> RuleStateResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = RuleStateFile {
+            rules: self.overrides.clone(),
+            definitions: self.definitions.clone(),
+            deleted: self.deleted.clone(),
+        };
+
+        // Write to a temporary file first so a crash never leaves a truncated state file
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(&file)?)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_state_roundtrip() {
+        let dir =
+            std::env::temp_dir().join(format!("watchtower-rule-state-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("rules.json");
+
+        let mut store = RuleStateStore::open(&path).unwrap();
+        assert_eq!(store.get("large_transaction"), None);
+        store.set("large_transaction", false).unwrap();
+
+        let reopened = RuleStateStore::open(&path).unwrap();
+        assert_eq!(reopened.get("large_transaction"), Some(false));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rule_definitions_roundtrip() {
+        let dir =
+            std::env::temp_dir().join(format!("watchtower-rule-defs-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("rules.json");
+
+        let config: RateOfChangeConfig = serde_json::from_value(serde_json::json!({
+            "name": "tvl_drop",
+            "metric": "{program}.tvl",
+            "threshold_pct": 20.0,
+            "window_seconds": 300,
+            "direction": "decrease"
+        }))
+        .unwrap();
+
+        let mut store = RuleStateStore::open(&path).unwrap();
+        store.define(config).unwrap();
+        store.set("tvl_drop", false).unwrap();
+        store.delete("volume_spike").unwrap();
+
+        let mut reopened = RuleStateStore::open(&path).unwrap();
+        assert_eq!(reopened.definition("tvl_drop").unwrap().threshold_pct, 20.0);
+        assert!(reopened.is_deleted("volume_spike"));
+
+        reopened.delete("tvl_drop").unwrap();
+        assert!(reopened.definition("tvl_drop").is_none());
+        assert_eq!(reopened.get("tvl_drop"), None);
+        assert_eq!(reopened.definitions().count(), 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
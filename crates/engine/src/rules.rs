@@ -1,9 +1,13 @@
 //! Rule system for monitoring Solana program events and detecting anomalies.
 
+use crate::aggregates::AddressAggregator;
+use crate::history::EventWindow;
+use crate::metrics::RuleMetrics;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
 /// Trait for implementing monitoring rules.
@@ -26,26 +30,46 @@ pub trait Rule: Send + Sync {
         true
     }
 
+    /// Evaluation priority; higher priority rules are evaluated and dispatched first.
+    ///
+    /// Defaults to the priority of the rule's severity.
+    fn priority(&self) -> u8 {
+        self.severity().priority()
+    }
+
     /// Rule-specific configuration.
     fn config(&self) -> &dyn std::any::Any {
         &()
     }
+
+    /// New instance with the same configuration and none of the state
+    /// accumulated from past events, for evaluating samples without touching
+    /// the live rule. `None` for rules that keep no such state.
+    fn fresh_instance(&self) -> Option<Box<dyn Rule>> {
+        None
+    }
 }
 
 /// Context provided to rules during evaluation.
+///
+/// History and metrics are shared snapshots, so cloning a context for each
+/// rule does not copy them.
 #[derive(Debug, Clone)]
 pub struct RuleContext {
     /// Historical events for analysis
-    pub recent_events: Vec<ProgramEvent>,
+    pub recent_events: EventWindow,
 
     /// Current metrics snapshot
-    pub metrics: HashMap<String, f64>,
+    pub metrics: Arc<RuleMetrics>,
 
     /// Rule configuration
     pub config: HashMap<String, serde_json::Value>,
 
     /// Timestamp of evaluation
     pub timestamp: DateTime<Utc>,
+
+    /// Per-address activity aggregates
+    pub addresses: Arc<AddressAggregator>,
 }
 
 /// Result of rule evaluation.
@@ -276,8 +300,14 @@ impl Rule for LargeTransactionRule {
                     .push("Verify transaction legitimacy".to_string());
             }
 
-            // Check TVL percentage if available
-            if let Some(tvl) = context.metrics.get("total_value_locked") {
+            // Check TVL percentage if available, preferring the program's own TVL
+            let tvl = context
+                .metrics
+                .get(&crate::tvl::tvl_metric_key(&event.program_name))
+                .or_else(|| context.metrics.get("total_value_locked"))
+                .filter(|tvl| **tvl > 0.0);
+
+            if let Some(tvl) = tvl {
                 let pct_of_tvl = (*amount as f64 / tvl) * 100.0;
                 if pct_of_tvl >= self.tvl_threshold_pct {
                     result.triggered = true;
@@ -309,6 +339,9 @@ pub struct OracleDeviationRule {
     pub reference_oracle: String,
 }
 
+/// Event metadata keys that carry an observed AMM/oracle price.
+const OBSERVED_PRICE_KEYS: [&str; 2] = ["oracle_price", "amm_price"];
+
 impl OracleDeviationRule {
     pub fn new(max_deviation_pct: f64, reference_oracle: String) -> Self {
         Self {
@@ -316,6 +349,11 @@ impl OracleDeviationRule {
             reference_oracle,
         }
     }
+
+    /// Create a rule that compares observed prices against the Pyth price for `symbol`.
+    pub fn with_pyth_reference(max_deviation_pct: f64, symbol: &str) -> Self {
+        Self::new(max_deviation_pct, crate::oracle::pyth_metric_key(symbol))
+    }
 }
 
 #[async_trait]
@@ -344,10 +382,17 @@ impl Rule for OracleDeviationRule {
             timestamp: Utc::now(),
         };
 
-        // Check for oracle price updates in the event
-        if let Some(serde_json::Value::Number(price)) = event.metadata.get("oracle_price") {
-            if let Some(reference_price) = context.metrics.get(&self.reference_oracle) {
-                let price_val = price.as_f64().unwrap_or(0.0);
+        // Check for oracle or AMM price updates in the event
+        let observed_price = OBSERVED_PRICE_KEYS
+            .iter()
+            .find_map(|key| event.metadata.get(*key).and_then(|v| v.as_f64()));
+
+        if let Some(price_val) = observed_price {
+            if let Some(reference_price) = context
+                .metrics
+                .get(&self.reference_oracle)
+                .filter(|price| **price > 0.0)
+            {
                 let deviation_pct = ((price_val - reference_price).abs() / reference_price) * 100.0;
 
                 if deviation_pct >= self.max_deviation_pct {
@@ -366,6 +411,10 @@ impl Rule for OracleDeviationRule {
                     result
                         .metadata
                         .insert("reference_price".to_string(), (*reference_price).into());
+                    result.metadata.insert(
+                        "reference_oracle".to_string(),
+                        self.reference_oracle.clone().into(),
+                    );
                     result
                         .suggested_actions
                         .push("Halt trading immediately".to_string());
@@ -406,6 +455,37 @@ impl FailureRateRule {
             window_seconds,
         }
     }
+
+    /// Transaction and failure counts in the window.
+    ///
+    /// Reads the per-program outcome window maintained by the metrics collector
+    /// (sized by `failure_rate_window_seconds`), falling back to scanning the
+    /// event history when the context carries no window counts.
+    fn window_counts(&self, event: &ProgramEvent, context: &RuleContext) -> (usize, usize) {
+        let total = context
+            .metrics
+            .get(&crate::metrics::window_transactions_key(
+                &event.program_name,
+            ));
+        let failed = context
+            .metrics
+            .get(&crate::metrics::window_failures_key(&event.program_name));
+        if let (Some(total), Some(failed)) = (total, failed) {
+            return (*total as usize, *failed as usize);
+        }
+
+        let window_start = event.timestamp - chrono::Duration::seconds(self.window_seconds as i64);
+        context
+            .recent_events
+            .iter()
+            .filter(|e| e.timestamp >= window_start && e.is_transaction())
+            .fold((0, 0), |(total, failed), e| {
+                (
+                    total + 1,
+                    failed + usize::from(e.is_successful() == Some(false)),
+                )
+            })
+    }
 }
 
 #[async_trait]
@@ -436,29 +516,16 @@ impl Rule for FailureRateRule {
 
         // Only evaluate on transaction events
         if event.is_transaction() {
-            let window_start =
-                event.timestamp - chrono::Duration::seconds(self.window_seconds as i64);
-            let recent_transactions: Vec<_> = context
-                .recent_events
-                .iter()
-                .filter(|e| e.timestamp >= window_start && e.is_transaction())
-                .collect();
+            let (total_count, failed_count) = self.window_counts(event, context);
 
-            if recent_transactions.len() >= self.min_transaction_count {
-                let failed_count = recent_transactions
-                    .iter()
-                    .filter(|e| e.is_successful() == Some(false))
-                    .count();
-
-                let failure_rate = (failed_count as f64 / recent_transactions.len() as f64) * 100.0;
+            if total_count >= self.min_transaction_count {
+                let failure_rate = (failed_count as f64 / total_count as f64) * 100.0;
 
                 if failure_rate >= self.max_failure_rate_pct {
                     result.triggered = true;
                     result.message = Some(format!(
                         "High failure rate detected: {:.2}% ({}/{} transactions)",
-                        failure_rate,
-                        failed_count,
-                        recent_transactions.len()
+                        failure_rate, failed_count, total_count
                     ));
                     result.confidence = (failure_rate / 100.0).min(1.0);
                     result
@@ -469,7 +536,7 @@ impl Rule for FailureRateRule {
                         .insert("failed_count".to_string(), failed_count.into());
                     result
                         .metadata
-                        .insert("total_count".to_string(), recent_transactions.len().into());
+                        .insert("total_count".to_string(), total_count.into());
                     result
                         .suggested_actions
                         .push("Check program logic for errors".to_string());
@@ -484,7 +551,305 @@ impl Rule for FailureRateRule {
     }
 }
 
+/// Direction of change that a `RateOfChangeRule` reacts to.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeDirection {
+    /// Only sudden increases (spikes)
+    Increase,
+    /// Only sudden decreases (drops)
+    Decrease,
+    /// Changes in either direction
+    #[default]
+    Either,
+}
+
+/// Configuration for a `RateOfChangeRule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateOfChangeConfig {
+    /// Unique rule name
+    pub name: String,
+
+    /// Metric key to watch; `{program}` is replaced with the event's program name
+    pub metric: String,
+
+    /// Change percentage that triggers an alert
+    pub threshold_pct: f64,
+
+    /// Time window in seconds
+    pub window_seconds: u64,
+
+    /// Direction of change to alert on
+    #[serde(default)]
+    pub direction: ChangeDirection,
+
+    /// Severity of generated alerts
+    #[serde(default = "default_rate_of_change_severity")]
+    pub severity: AlertSeverity,
+
+    /// Evaluation priority (defaults to the severity's priority)
+    #[serde(default)]
+    pub priority: Option<u8>,
+}
+
+impl RateOfChangeConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> Result<(), RuleError> {
+        if self.name.is_empty() || self.metric.is_empty() {
+            return Err(RuleError::Configuration(
+                "Rate-of-change rule requires a name and a metric".to_string(),
+            ));
+        }
+
+        if self.threshold_pct <= 0.0 || self.window_seconds == 0 {
+            return Err(RuleError::Configuration(format!(
+                "Rate-of-change rule {} requires a positive threshold and window",
+                self.name
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Maximum samples kept per metric by a `RateOfChangeRule`.
+const MAX_RATE_OF_CHANGE_SAMPLES: usize = 10_000;
+
+/// Maximum number of points in the `series` metadata of rate-of-change alerts.
+const MAX_SERIES_POINTS: usize = 200;
+
+/// Generic rule that detects a metric changing by more than a percentage within a time window.
+#[derive(Debug)]
+pub struct RateOfChangeRule {
+    /// Rule name
+    pub name: String,
+    /// Rule description
+    pub description: String,
+    /// Metric key template
+    pub metric: String,
+    /// Change percentage that triggers an alert
+    pub threshold_pct: f64,
+    /// Time window in seconds
+    pub window_seconds: u64,
+    /// Direction of change to alert on
+    pub direction: ChangeDirection,
+    /// Severity of generated alerts
+    pub severity: AlertSeverity,
+    /// Evaluation priority override
+    pub priority: Option<u8>,
+    /// Observed samples per resolved metric key
+    samples: Mutex<HashMap<String, SampleWindow>>,
+}
+
+/// Timestamped metric samples within a rate-of-change window.
+type SampleWindow = VecDeque<(DateTime<Utc>, f64)>;
+
+impl RateOfChangeRule {
+    pub fn new(
+        name: impl Into<String>,
+        metric: impl Into<String>,
+        threshold_pct: f64,
+        window_seconds: u64,
+        direction: ChangeDirection,
+    ) -> Self {
+        let metric = metric.into();
+        Self {
+            name: name.into(),
+            description: format!("Detects rapid changes in {}", metric),
+            metric,
+            threshold_pct,
+            window_seconds,
+            direction,
+            severity: default_rate_of_change_severity(),
+            priority: None,
+            samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a rule from its configuration.
+    pub fn from_config(config: &RateOfChangeConfig) -> Self {
+        Self::new(
+            config.name.clone(),
+            config.metric.clone(),
+            config.threshold_pct,
+            config.window_seconds,
+            config.direction,
+        )
+        .with_severity(config.severity)
+        .with_priority(config.priority)
+    }
+
+    /// Set the severity of generated alerts.
+    pub fn with_severity(mut self, severity: AlertSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Override the evaluation priority.
+    pub fn with_priority(mut self, priority: Option<u8>) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Record a sample and return the signed change percentage against the window baseline.
+    fn record_sample(&self, key: &str, timestamp: DateTime<Utc>, value: f64) -> Option<f64> {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let window = samples.entry(key.to_string()).or_default();
+
+        let window_start = timestamp - chrono::Duration::seconds(self.window_seconds as i64);
+        while window.front().is_some_and(|(ts, _)| *ts < window_start) {
+            window.pop_front();
+        }
+
+        let values = window.iter().map(|(_, v)| *v).filter(|v| *v != 0.0);
+        let baseline = match self.direction {
+            ChangeDirection::Increase => values.reduce(f64::min),
+            ChangeDirection::Decrease => values.reduce(f64::max),
+            ChangeDirection::Either => values.max_by(|a, b| {
+                (value - a)
+                    .abs()
+                    .partial_cmp(&(value - b).abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
+        };
+
+        window.push_back((timestamp, value));
+        if window.len() > MAX_RATE_OF_CHANGE_SAMPLES {
+            window.pop_front();
+        }
+
+        baseline.map(|baseline| (value - baseline) / baseline.abs() * 100.0)
+    }
+
+    /// Samples in the window of `key` as `(unix seconds, value)` pairs, thinned
+    /// out evenly to at most `MAX_SERIES_POINTS` for alert metadata.
+    fn series(&self, key: &str) -> Vec<(i64, f64)> {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(window) = samples.get(key) else {
+            return Vec::new();
+        };
+
+        let step = (window.len() + MAX_SERIES_POINTS - 1) / MAX_SERIES_POINTS;
+        window
+            .iter()
+            .step_by(step.max(1))
+            .map(|(ts, value)| (ts.timestamp(), *value))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Rule for RateOfChangeRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        self.severity
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority.unwrap_or_else(|| self.severity.priority())
+    }
+
+    fn fresh_instance(&self) -> Option<Box<dyn Rule>> {
+        Some(Box::new(Self {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            metric: self.metric.clone(),
+            threshold_pct: self.threshold_pct,
+            window_seconds: self.window_seconds,
+            direction: self.direction,
+            severity: self.severity,
+            priority: self.priority,
+            samples: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        let key = self.metric.replace("{program}", &event.program_name);
+        let Some(value) = context.metrics.get(&key).copied() else {
+            return result;
+        };
+
+        if let Some(change_pct) = self.record_sample(&key, context.timestamp, value) {
+            let exceeded = match self.direction {
+                ChangeDirection::Increase => change_pct >= self.threshold_pct,
+                ChangeDirection::Decrease => -change_pct >= self.threshold_pct,
+                ChangeDirection::Either => change_pct.abs() >= self.threshold_pct,
+            };
+
+            if exceeded {
+                let direction = if change_pct >= 0.0 {
+                    "increased"
+                } else {
+                    "decreased"
+                };
+                result.triggered = true;
+                result.message = Some(format!(
+                    "{} {} by {:.2}% within {} seconds (threshold: {:.2}%)",
+                    key,
+                    direction,
+                    change_pct.abs(),
+                    self.window_seconds,
+                    self.threshold_pct
+                ));
+                result.confidence = (change_pct.abs() / 100.0).min(1.0);
+                result
+                    .metadata
+                    .insert("metric".to_string(), key.clone().into());
+                result
+                    .metadata
+                    .insert("change_percentage".to_string(), change_pct.into());
+                result
+                    .metadata
+                    .insert("current_value".to_string(), value.into());
+                // Lets notifiers chart the window leading up to the alert
+                result
+                    .metadata
+                    .insert("series".to_string(), serde_json::json!(self.series(&key)));
+                result
+                    .suggested_actions
+                    .push("Investigate the cause of the sudden change".to_string());
+            }
+        }
+
+        result
+    }
+}
+
+fn default_rate_of_change_severity() -> AlertSeverity {
+    AlertSeverity::High
+}
+
 impl AlertSeverity {
+    /// Parse a severity name, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "info" => Some(AlertSeverity::Info),
+            "low" => Some(AlertSeverity::Low),
+            "medium" => Some(AlertSeverity::Medium),
+            "high" => Some(AlertSeverity::High),
+            "critical" => Some(AlertSeverity::Critical),
+            _ => None,
+        }
+    }
+
     /// Get the string representation of the severity level.
     pub fn as_str(&self) -> &str {
         match self {
@@ -517,15 +882,26 @@ impl AlertSeverity {
             AlertSeverity::Critical => 5,
         }
     }
+
+    /// The next severity level up, capped at critical.
+    pub fn escalated(&self) -> AlertSeverity {
+        match self {
+            AlertSeverity::Info => AlertSeverity::Low,
+            AlertSeverity::Low => AlertSeverity::Medium,
+            AlertSeverity::Medium => AlertSeverity::High,
+            AlertSeverity::High | AlertSeverity::Critical => AlertSeverity::Critical,
+        }
+    }
 }
 
 impl Default for RuleContext {
     fn default() -> Self {
         Self {
-            recent_events: Vec::new(),
-            metrics: HashMap::new(),
+            recent_events: EventWindow::new(),
+            metrics: Arc::new(RuleMetrics::default()),
             config: HashMap::new(),
             timestamp: Utc::now(),
+            addresses: Arc::new(AddressAggregator::default()),
         }
     }
 }
@@ -585,4 +961,118 @@ mod tests {
         assert!(result.triggered);
         assert!(result.message.is_some());
     }
+
+    #[tokio::test]
+    async fn test_large_transaction_rule_uses_program_tvl() {
+        let rule = LargeTransactionRule::new(1.0, u64::MAX);
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::TokenTransfer,
+            EventData::TokenTransfer {
+                from: Pubkey::new_unique(),
+                to: Pubkey::new_unique(),
+                amount: 50_000,
+                mint: Pubkey::new_unique(),
+                decimals: 6,
+            },
+        );
+
+        let mut context = RuleContext::default();
+        Arc::make_mut(&mut context.metrics)
+            .insert(crate::tvl::tvl_metric_key("Test Program"), 1_000_000.0);
+        let result = rule.evaluate(&event, &context).await;
+
+        assert!(result.triggered);
+        assert!(result.metadata.contains_key("tvl_percentage"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_of_change_rule() {
+        let rule = RateOfChangeRule::new(
+            "tvl_drop",
+            "{program}_tvl",
+            20.0,
+            60,
+            ChangeDirection::Decrease,
+        );
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test Program".to_string(),
+            EventType::TokenTransfer,
+            EventData::TokenTransfer {
+                from: Pubkey::new_unique(),
+                to: Pubkey::new_unique(),
+                amount: 1,
+                mint: Pubkey::new_unique(),
+                decimals: 6,
+            },
+        );
+
+        let start = Utc::now();
+        let context_at = |offset_secs: i64, tvl: f64| {
+            let mut context = RuleContext {
+                timestamp: start + chrono::Duration::seconds(offset_secs),
+                ..RuleContext::default()
+            };
+            Arc::make_mut(&mut context.metrics).insert("Test Program_tvl".to_string(), tvl);
+            context
+        };
+
+        // First sample only establishes the baseline
+        let result = rule.evaluate(&event, &context_at(0, 1000.0)).await;
+        assert!(!result.triggered);
+
+        // A rise is ignored for a decrease rule
+        let result = rule.evaluate(&event, &context_at(10, 1500.0)).await;
+        assert!(!result.triggered);
+
+        // 40% drop from the window peak
+        let result = rule.evaluate(&event, &context_at(20, 900.0)).await;
+        assert!(result.triggered);
+        assert_eq!(result.rule_name, "tvl_drop");
+        assert_eq!(result.metadata["series"].as_array().unwrap().len(), 3);
+
+        // A fresh instance has no baseline yet
+        let fresh = rule.fresh_instance().unwrap();
+        assert_eq!(fresh.name(), "tvl_drop");
+        assert!(
+            !fresh
+                .evaluate(&event, &context_at(20, 900.0))
+                .await
+                .triggered
+        );
+
+        // Older samples fall out of the window
+        let result = rule.evaluate(&event, &context_at(200, 800.0)).await;
+        assert!(!result.triggered);
+    }
+
+    #[tokio::test]
+    async fn test_oracle_deviation_rule_with_pyth_reference() {
+        let rule = OracleDeviationRule::with_pyth_reference(5.0, "SOL");
+
+        let event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "Test AMM".to_string(),
+            EventType::AccountChange,
+            EventData::AccountChange {
+                account: Pubkey::new_unique(),
+                balance_before: None,
+                balance_after: None,
+                data_size_change: 0,
+                owner: Pubkey::new_unique(),
+            },
+        )
+        .with_metadata("amm_price".to_string(), 110.0.into());
+
+        let mut context = RuleContext::default();
+        Arc::make_mut(&mut context.metrics).insert(crate::oracle::pyth_metric_key("SOL"), 100.0);
+
+        let result = rule.evaluate(&event, &context).await;
+        assert!(result.triggered);
+        assert_eq!(result.metadata["reference_price"], 100.0);
+    }
 }
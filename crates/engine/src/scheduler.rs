@@ -0,0 +1,197 @@
+//! Priority scheduling of rule evaluations across events.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::oneshot;
+
+/// Limits concurrent rule evaluations, handing each free slot to the highest
+/// priority waiter.
+///
+/// A FIFO semaphore makes a critical rule of a new event wait behind every
+/// low priority rule of earlier events. Here waiters are ordered by rule
+/// priority first and arrival second, so equal priorities still run in order.
+#[derive(Debug, Clone)]
+pub struct EvaluationScheduler {
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+/// Free slots and queued waiters.
+#[derive(Debug)]
+struct SchedulerState {
+    /// Slots not held by a permit; only non-zero while no one is waiting
+    available: usize,
+
+    /// Waiters, highest priority and then earliest arrival first
+    waiters: BinaryHeap<Waiter>,
+
+    /// Arrival counter
+    next_sequence: u64,
+}
+
+/// A queued request for a slot.
+#[derive(Debug)]
+struct Waiter {
+    priority: u8,
+    sequence: u64,
+    sender: oneshot::Sender<EvaluationPermit>,
+}
+
+/// A held evaluation slot, passed on to the next waiter when dropped.
+#[derive(Debug)]
+pub struct EvaluationPermit {
+    scheduler: Option<EvaluationScheduler>,
+}
+
+impl EvaluationScheduler {
+    /// Create a scheduler allowing `slots` concurrent evaluations.
+    pub fn new(slots: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(SchedulerState {
+                available: slots,
+                waiters: BinaryHeap::new(),
+                next_sequence: 0,
+            })),
+        }
+    }
+
+    /// Wait for a slot for a rule of the given priority.
+    pub async fn acquire(&self, priority: u8) -> EvaluationPermit {
+        let receiver = {
+            let mut state = self.lock();
+            if state.available > 0 {
+                state.available -= 1;
+                return self.permit();
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let sequence = state.next_sequence;
+            state.next_sequence += 1;
+            state.waiters.push(Waiter {
+                priority,
+                sequence,
+                sender,
+            });
+            receiver
+        };
+
+        // Waiters are only dropped after their receiver has gone
+        receiver
+            .await
+            .expect("queued waiters are handed a permit before being dropped")
+    }
+
+    /// Number of evaluations waiting for a slot.
+    pub fn waiting(&self) -> usize {
+        self.lock().waiters.len()
+    }
+
+    /// Hand a freed slot to the best waiter still listening, or return it.
+    fn release(&self) {
+        let mut state = self.lock();
+        while let Some(waiter) = state.waiters.pop() {
+            match waiter.sender.send(self.permit()) {
+                Ok(()) => return,
+                // The waiter gave up; its permit must not release again
+                Err(mut permit) => permit.scheduler = None,
+            }
+        }
+        state.available += 1;
+    }
+
+    fn permit(&self) -> EvaluationPermit {
+        EvaluationPermit {
+            scheduler: Some(self.clone()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SchedulerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for EvaluationPermit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.scheduler.take() {
+            scheduler.release();
+        }
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_highest_priority_waiter_first() {
+        let scheduler = EvaluationScheduler::new(1);
+        let held = scheduler.acquire(0).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for (name, priority) in [("low", 10), ("critical", 255), ("low2", 10), ("high", 100)] {
+            let waiter = scheduler.clone();
+            let order = order.clone();
+            tasks.push(tokio::spawn(async move {
+                let _permit = waiter.acquire(priority).await;
+                order.lock().unwrap().push(name);
+            }));
+            // Queue in a known arrival order
+            while scheduler.waiting() < tasks.len() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["critical", "high", "low", "low2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_waiter_passes_slot_on() {
+        let scheduler = EvaluationScheduler::new(1);
+        let held = scheduler.acquire(0).await;
+
+        let abandoned = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.acquire(255).await }
+        });
+        while scheduler.waiting() == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        abandoned.abort();
+        let _ = abandoned.await;
+
+        drop(held);
+        let permit = tokio::time::timeout(Duration::from_secs(1), scheduler.acquire(0)).await;
+        assert!(permit.is_ok());
+    }
+}
@@ -0,0 +1,245 @@
+//! Silences suppressing alerts of a program, rule or severity for a while.
+
+use crate::{alerts::Alert, rules::AlertSeverity};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::info;
+
+/// Longest a silence may last.
+pub const MAX_SILENCE_DURATION: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Alerts a silence applies to; unset fields match any alert.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilenceScope {
+    /// Program name
+    #[serde(default)]
+    pub program: Option<String>,
+
+    /// Rule name
+    #[serde(default)]
+    pub rule: Option<String>,
+
+    /// Alert severity
+    #[serde(default)]
+    pub severity: Option<AlertSeverity>,
+}
+
+/// A window during which matching alerts are not dispatched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Silence {
+    /// Silence identifier
+    pub id: String,
+
+    /// Alerts the silence applies to
+    #[serde(flatten)]
+    pub scope: SilenceScope,
+
+    /// When the silence was created
+    pub starts_at: DateTime<Utc>,
+
+    /// When the silence expires
+    pub ends_at: DateTime<Utc>,
+
+    /// Operator-supplied reason
+    pub comment: Option<String>,
+
+    /// Operator who created the silence
+    pub created_by: Option<String>,
+
+    /// Alerts suppressed so far
+    pub suppressed: u64,
+}
+
+/// Errors that can occur when creating silences.
+#[derive(Error, Debug)]
+pub enum SilenceError {
+    #[error("A silence needs a program, rule or severity")]
+    EmptyScope,
+
+    #[error("Silence duration must be between 1 second and {} days", MAX_SILENCE_DURATION.as_secs() / 86400)]
+    InvalidDuration,
+}
+
+impl SilenceScope {
+    /// Whether the scope covers an alert.
+    pub fn matches(&self, alert: &Alert) -> bool {
+        self.program
+            .as_ref()
+            .map_or(true, |program| *program == alert.program_name)
+            && self
+                .rule
+                .as_ref()
+                .map_or(true, |rule| *rule == alert.rule_name)
+            && self
+                .severity
+                .map_or(true, |severity| severity == alert.severity)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.program.is_none() && self.rule.is_none() && self.severity.is_none()
+    }
+}
+
+/// Active silences; expired ones are dropped as they are looked at.
+#[derive(Debug, Default)]
+pub struct SilenceList {
+    silences: Mutex<Vec<Silence>>,
+}
+
+impl SilenceList {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Silence alerts in `scope` for `duration`.
+    pub fn create(
+        &self,
+        scope: SilenceScope,
+        duration: Duration,
+        comment: Option<String>,
+        created_by: Option<String>,
+    ) -> Result<Silence, SilenceError> {
+        if scope.is_empty() {
+            return Err(SilenceError::EmptyScope);
+        }
+        let length = chrono::Duration::from_std(duration)
+            .ok()
+            .filter(|_| !duration.is_zero() && duration <= MAX_SILENCE_DURATION)
+            .ok_or(SilenceError::InvalidDuration)?;
+
+        let starts_at = Utc::now();
+        let silence = Silence {
+            id: uuid::Uuid::new_v4().to_string(),
+            scope,
+            starts_at,
+            ends_at: starts_at + length,
+            comment: comment.filter(|c| !c.trim().is_empty()),
+            created_by,
+            suppressed: 0,
+        };
+
+        info!(
+            "Created silence {} until {} ({:?})",
+            silence.id, silence.ends_at, silence.scope
+        );
+        self.lock().push(silence.clone());
+        Ok(silence)
+    }
+
+    /// Active silences, oldest first.
+    pub fn list(&self) -> Vec<Silence> {
+        self.active().clone()
+    }
+
+    /// Expire a silence now, returning whether it was active.
+    pub fn expire(&self, id: &str) -> bool {
+        let mut silences = self.active();
+        let initial_len = silences.len();
+        silences.retain(|silence| silence.id != id);
+        let expired = silences.len() != initial_len;
+
+        if expired {
+            info!("Expired silence {}", id);
+        }
+        expired
+    }
+
+    /// Identifier of the first active silence covering an alert, counting
+    /// the alert as suppressed by it.
+    pub fn suppress(&self, alert: &Alert) -> Option<String> {
+        let mut silences = self.active();
+        let silence = silences
+            .iter_mut()
+            .find(|silence| silence.scope.matches(alert))?;
+        silence.suppressed += 1;
+        Some(silence.id.clone())
+    }
+
+    /// Locked list with expired silences removed.
+    fn active(&self) -> std::sync::MutexGuard<'_, Vec<Silence>> {
+        let now = Utc::now();
+        let mut silences = self.lock();
+        silences.retain(|silence| silence.ends_at > now);
+        silences
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Silence>> {
+        self.silences.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn alert(program_name: &str, rule_name: &str, severity: AlertSeverity) -> Alert {
+        Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            rule_name: rule_name.to_string(),
+            message: "Large transaction detected".to_string(),
+            severity,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: program_name.to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_silences_scope_and_expiry() {
+        let silences = SilenceList::new();
+        let hour = Duration::from_secs(3600);
+
+        assert!(matches!(
+            silences.create(SilenceScope::default(), hour, None, None),
+            Err(SilenceError::EmptyScope)
+        ));
+        let orca = SilenceScope {
+            program: Some("Orca".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            silences.create(orca.clone(), Duration::ZERO, None, None),
+            Err(SilenceError::InvalidDuration)
+        ));
+
+        let silence = silences
+            .create(
+                SilenceScope {
+                    severity: Some(AlertSeverity::Low),
+                    ..orca
+                },
+                hour,
+                Some("Planned migration".to_string()),
+                Some("alice".to_string()),
+            )
+            .unwrap();
+
+        let low = alert("Orca", "large_transaction", AlertSeverity::Low);
+        assert_eq!(silences.suppress(&low), Some(silence.id.clone()));
+        assert_eq!(
+            silences.suppress(&alert("Orca", "large_transaction", AlertSeverity::High)),
+            None
+        );
+        assert_eq!(
+            silences.suppress(&alert("Solend", "large_transaction", AlertSeverity::Low)),
+            None
+        );
+        assert_eq!(silences.list()[0].suppressed, 1);
+
+        assert!(silences.expire(&silence.id));
+        assert!(!silences.expire(&silence.id));
+        assert_eq!(silences.suppress(&low), None);
+    }
+}
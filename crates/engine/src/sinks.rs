@@ -0,0 +1,327 @@
+//! Alert sinks that export every alert independently of notifications.
+
+use crate::alerts::Alert;
+use crate::rules::AlertSeverity;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Destination that receives every alert raised by the alert manager.
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    /// Sink name used in logs.
+    fn name(&self) -> &str;
+
+    /// Write one alert.
+    async fn write(&self, alert: &Alert) -> SinkResult<()>;
+}
+
+/// Configuration for a single alert sink.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSinkConfig {
+    /// Sink name used in logs
+    pub name: String,
+
+    /// Only export alerts at or above this severity
+    #[serde(default)]
+    pub min_severity: Option<AlertSeverity>,
+
+    /// Where alerts are written
+    #[serde(flatten)]
+    pub kind: AlertSinkKind,
+}
+
+/// Supported sink destinations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertSinkKind {
+    /// Append one JSON object per line to a file
+    Jsonl { path: PathBuf },
+
+    /// POST each alert as JSON to a URL
+    Webhook {
+        url: String,
+
+        /// Extra request headers, e.g. for authentication
+        #[serde(default)]
+        headers: HashMap<String, String>,
+
+        /// Token sent as `Authorization: Bearer <token>`
+        #[serde(default)]
+        bearer_token: Option<String>,
+
+        /// Request timeout in seconds
+        #[serde(default = "default_timeout")]
+        timeout_seconds: u64,
+    },
+}
+
+/// Errors that can occur while writing to a sink.
+#[derive(Error, Debug)]
+pub enum SinkError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Sink configuration error: {0}")]
+    Configuration(String),
+}
+
+pub type SinkResult<T> = Result<T, SinkError>;
+
+impl AlertSinkConfig {
+    /// Validate configuration.
+    pub fn validate(&self) -> SinkResult<()> {
+        if self.name.is_empty() {
+            return Err(SinkError::Configuration(
+                "Sink name cannot be empty".to_string(),
+            ));
+        }
+
+        match &self.kind {
+            AlertSinkKind::Jsonl { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(SinkError::Configuration(format!(
+                        "Sink {} requires a path",
+                        self.name
+                    )));
+                }
+            }
+            AlertSinkKind::Webhook {
+                url,
+                timeout_seconds,
+                ..
+            } => {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(SinkError::Configuration(format!(
+                        "Sink {} URL must start with http:// or https://",
+                        self.name
+                    )));
+                }
+                if *timeout_seconds == 0 {
+                    return Err(SinkError::Configuration(format!(
+                        "Sink {} timeout must be greater than 0",
+                        self.name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the configured sink, wrapped in a severity filter if set.
+    pub fn build(&self) -> SinkResult<Arc<dyn AlertSink>> {
+        self.validate()?;
+
+        let sink: Arc<dyn AlertSink> = match &self.kind {
+            AlertSinkKind::Jsonl { path } => Arc::new(JsonlSink::new(&self.name, path.clone())),
+            AlertSinkKind::Webhook {
+                url,
+                headers,
+                bearer_token,
+                timeout_seconds,
+            } => Arc::new(
+                WebhookSink::new(
+                    &self.name,
+                    url.clone(),
+                    headers.clone(),
+                    Duration::from_secs(*timeout_seconds),
+                )?
+                .with_bearer_token(bearer_token.clone()),
+            ),
+        };
+
+        Ok(match self.min_severity {
+            Some(min_severity) => Arc::new(SeverityFilter {
+                inner: sink,
+                min_severity,
+            }),
+            None => sink,
+        })
+    }
+}
+
+/// Appends alerts to a JSON Lines file, creating it if needed.
+pub struct JsonlSink {
+    name: String,
+    path: PathBuf,
+    /// Serializes appends so lines never interleave
+    lock: Mutex<()>,
+}
+
+impl JsonlSink {
+    /// Create a sink appending to `path`.
+    pub fn new(name: &str, path: PathBuf) -> Self {
+        Self {
+            name: name.to_string(),
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for JsonlSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn write(&self, alert: &Alert) -> SinkResult<()> {
+        let mut line = serde_json::to_vec(alert)?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+/// POSTs alerts as JSON to a URL.
+pub struct WebhookSink {
+    name: String,
+    url: String,
+    headers: HashMap<String, String>,
+    bearer_token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    /// Create a sink posting to `url`.
+    pub fn new(
+        name: &str,
+        url: String,
+        headers: HashMap<String, String>,
+        timeout: Duration,
+    ) -> SinkResult<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self {
+            name: name.to_string(),
+            url,
+            headers,
+            bearer_token: None,
+            client,
+        })
+    }
+
+    /// Authenticate requests with a bearer token.
+    pub fn with_bearer_token(mut self, bearer_token: Option<String>) -> Self {
+        self.bearer_token = bearer_token;
+        self
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn write(&self, alert: &Alert) -> SinkResult<()> {
+        let mut request = self.client.post(&self.url).json(alert);
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Forwards only alerts at or above a minimum severity.
+struct SeverityFilter {
+    inner: Arc<dyn AlertSink>,
+    min_severity: AlertSeverity,
+}
+
+#[async_trait]
+impl AlertSink for SeverityFilter {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn write(&self, alert: &Alert) -> SinkResult<()> {
+        if alert.severity < self.min_severity {
+            return Ok(());
+        }
+        self.inner.write(alert).await
+    }
+}
+
+// Default value functions
+fn default_timeout() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn alert(severity: AlertSeverity) -> Alert {
+        Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test alert".to_string(),
+            severity,
+            program_id: Pubkey::new_unique(),
+            program_name: "Test".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_sink_with_severity_filter() {
+        let path =
+            std::env::temp_dir().join(format!("watchtower-alerts-{}.jsonl", uuid::Uuid::new_v4()));
+        let sink = AlertSinkConfig {
+            name: "archive".to_string(),
+            min_severity: Some(AlertSeverity::Medium),
+            kind: AlertSinkKind::Jsonl { path: path.clone() },
+        }
+        .build()
+        .unwrap();
+
+        sink.write(&alert(AlertSeverity::High)).await.unwrap();
+        sink.write(&alert(AlertSeverity::Low)).await.unwrap();
+        sink.write(&alert(AlertSeverity::Critical)).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<Alert> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].severity, AlertSeverity::High);
+        assert_eq!(lines[1].severity, AlertSeverity::Critical);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
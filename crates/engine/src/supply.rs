@@ -0,0 +1,273 @@
+//! Token supply inflation detection.
+
+use crate::authority::{MintAuthorityConfig, WatchedMint};
+use crate::rules::{AlertSeverity, Rule, RuleContext, RuleResult};
+use async_trait::async_trait;
+use base64::Engine as _;
+use chrono::{DateTime, Duration, Utc};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Mutex;
+use watchtower_subscriber::{EventData, ProgramEvent, ACCOUNT_DATA_METADATA_KEY};
+
+/// Byte range of the `supply` field in an SPL Token mint account.
+const MINT_SUPPLY_RANGE: std::ops::Range<usize> = 36..44;
+
+/// Supply samples of one mint as `(observed at, supply)`, oldest first.
+type SupplySamples = VecDeque<(DateTime<Utc>, u64)>;
+
+/// Rule that tracks the total supply of watched mints and alerts when it
+/// exceeds an absolute cap or grows faster than expected, catching
+/// unauthorized mints.
+///
+/// Supply is decoded from the mint account data carried by the subscriber's
+/// account subscriptions (see `MintAuthorityConfig::supply_accounts`).
+#[derive(Debug)]
+pub struct SupplyInflationRule {
+    /// Watched mints with supply limits, keyed by address
+    mints: HashMap<Pubkey, WatchedMint>,
+
+    /// Window over which growth is measured
+    window: Duration,
+
+    /// Observed supply per mint
+    samples: Mutex<HashMap<Pubkey, SupplySamples>>,
+}
+
+impl SupplyInflationRule {
+    /// Create a rule for the mints with supply limits, skipping invalid addresses.
+    pub fn new(config: &MintAuthorityConfig) -> Self {
+        let mints = config
+            .mints
+            .iter()
+            .filter(|watched| watched.tracks_supply())
+            .filter_map(|watched| {
+                Pubkey::from_str(&watched.mint)
+                    .ok()
+                    .map(|mint| (mint, watched.clone()))
+            })
+            .collect();
+
+        Self {
+            mints,
+            window: Duration::seconds(config.supply_window_seconds as i64),
+            samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a supply sample and return the lowest supply seen in the window.
+    fn record(&self, mint: Pubkey, timestamp: DateTime<Utc>, supply: u64) -> u64 {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let history = samples.entry(mint).or_default();
+        history.push_back((timestamp, supply));
+
+        let window_start = timestamp - self.window;
+        while history.front().is_some_and(|(at, _)| *at < window_start) {
+            history.pop_front();
+        }
+
+        history
+            .iter()
+            .map(|(_, supply)| *supply)
+            .min()
+            .unwrap_or(supply)
+    }
+}
+
+#[async_trait]
+impl Rule for SupplyInflationRule {
+    fn name(&self) -> &str {
+        "supply_inflation"
+    }
+
+    fn description(&self) -> &str {
+        "Detects token supply growing beyond a cap or faster than expected"
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        AlertSeverity::High
+    }
+
+    fn fresh_instance(&self) -> Option<Box<dyn Rule>> {
+        Some(Box::new(Self {
+            mints: self.mints.clone(),
+            window: self.window,
+            samples: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    async fn evaluate(&self, event: &ProgramEvent, _context: &RuleContext) -> RuleResult {
+        let mut result = RuleResult {
+            rule_name: self.name().to_string(),
+            triggered: false,
+            message: None,
+            severity: self.severity(),
+            metadata: HashMap::new(),
+            confidence: 0.0,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+        };
+
+        let EventData::AccountChange { account, .. } = &event.data else {
+            return result;
+        };
+        let Some(watched) = self.mints.get(account) else {
+            return result;
+        };
+        let Some(supply) = mint_supply(event) else {
+            return result;
+        };
+
+        let baseline = self.record(*account, event.timestamp, supply);
+        let growth_pct = if baseline > 0 {
+            (supply - baseline) as f64 / baseline as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let label = watched.label.clone().unwrap_or_else(|| account.to_string());
+        if let Some(cap) = watched.max_supply.filter(|cap| supply > *cap) {
+            result.triggered = true;
+            result.severity = AlertSeverity::Critical;
+            result.confidence = 1.0;
+            result.message = Some(format!(
+                "Supply of {} is {}, above its cap of {}",
+                label, supply, cap
+            ));
+            result.metadata.insert("max_supply".to_string(), cap.into());
+        } else if let Some(limit) = watched
+            .max_supply_growth_pct
+            .filter(|limit| growth_pct > *limit)
+        {
+            result.triggered = true;
+            result.confidence = (growth_pct / limit / 2.0).clamp(0.6, 1.0);
+            result.message = Some(format!(
+                "Supply of {} grew {:.2}% in {}s (max expected: {:.2}%)",
+                label,
+                growth_pct,
+                self.window.num_seconds(),
+                limit
+            ));
+            result
+                .metadata
+                .insert("max_supply_growth_pct".to_string(), limit.into());
+        } else {
+            return result;
+        }
+
+        result
+            .metadata
+            .insert("mint".to_string(), account.to_string().into());
+        result.metadata.insert("supply".to_string(), supply.into());
+        result
+            .metadata
+            .insert("baseline_supply".to_string(), baseline.into());
+        result
+            .metadata
+            .insert("growth_pct".to_string(), growth_pct.into());
+        result
+            .suggested_actions
+            .push("Verify the new supply was minted by the issuer".to_string());
+        result
+            .suggested_actions
+            .push("Check for newly minted tokens being sold into pools".to_string());
+
+        result
+    }
+}
+
+/// Decode the total supply from a mint account event's raw data.
+fn mint_supply(event: &ProgramEvent) -> Option<u64> {
+    let encoded = event.metadata.get(ACCOUNT_DATA_METADATA_KEY)?.as_str()?;
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()?;
+    Some(u64::from_le_bytes(
+        data.get(MINT_SUPPLY_RANGE)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_subscriber::EventType;
+
+    fn mint_update(mint: Pubkey, supply: u64, seconds_ago: i64) -> ProgramEvent {
+        let mut data = vec![0u8; 82];
+        data[MINT_SUPPLY_RANGE].copy_from_slice(&supply.to_le_bytes());
+        let mut event = ProgramEvent::new(
+            Pubkey::new_unique(),
+            "TEST Mint".to_string(),
+            EventType::AccountChange,
+            EventData::AccountChange {
+                account: mint,
+                balance_before: None,
+                balance_after: Some(1_461_600),
+                data_size_change: 0,
+                owner: Pubkey::new_unique(),
+            },
+        )
+        .with_metadata(
+            ACCOUNT_DATA_METADATA_KEY.to_string(),
+            base64::engine::general_purpose::STANDARD
+                .encode(&data)
+                .into(),
+        );
+        event.timestamp = Utc::now() - Duration::seconds(seconds_ago);
+        event
+    }
+
+    #[tokio::test]
+    async fn test_supply_growth_and_cap() {
+        let mint = Pubkey::new_unique();
+        let rule = SupplyInflationRule::new(&MintAuthorityConfig {
+            mints: vec![WatchedMint {
+                mint: mint.to_string(),
+                label: Some("TEST".to_string()),
+                max_mint_amount: None,
+                max_supply: Some(2_000_000),
+                max_supply_growth_pct: Some(10.0),
+            }],
+            ..Default::default()
+        });
+        let context = RuleContext::default();
+
+        // Samples older than the window don't count towards growth
+        assert!(
+            !rule
+                .evaluate(&mint_update(mint, 500_000, 7200), &context)
+                .await
+                .triggered
+        );
+        assert!(
+            !rule
+                .evaluate(&mint_update(mint, 1_000_000, 600), &context)
+                .await
+                .triggered
+        );
+        assert!(
+            !rule
+                .evaluate(&mint_update(mint, 1_050_000, 300), &context)
+                .await
+                .triggered
+        );
+
+        let result = rule
+            .evaluate(&mint_update(mint, 1_200_000, 0), &context)
+            .await;
+        assert!(result.triggered);
+        assert_eq!(result.severity, AlertSeverity::High);
+        assert_eq!(result.metadata["baseline_supply"], 1_000_000);
+
+        let result = rule
+            .evaluate(&mint_update(mint, 2_500_000, 0), &context)
+            .await;
+        assert!(result.triggered);
+        assert_eq!(result.severity, AlertSeverity::Critical);
+
+        // Unwatched mints are ignored
+        let other = mint_update(Pubkey::new_unique(), u64::MAX, 0);
+        assert!(!rule.evaluate(&other, &context).await.triggered);
+    }
+}
@@ -0,0 +1,416 @@
+//! Total value locked computation from on-chain vault accounts.
+
+use crate::metrics::MetricsCollector;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+/// SPL Token program ID.
+pub(crate) const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// SPL Token-2022 program ID.
+pub(crate) const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Byte range of the `amount` field in an SPL token account.
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+/// Maximum accounts per `getMultipleAccounts` request.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Configuration for TVL computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TvlConfig {
+    /// Refresh interval in seconds
+    #[serde(default = "default_refresh_interval")]
+    pub refresh_interval_seconds: u64,
+
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_request_timeout")]
+    pub request_timeout_seconds: u64,
+
+    /// Programs and the vault accounts that hold their liquidity
+    pub programs: Vec<ProgramTvlConfig>,
+}
+
+/// Vault accounts making up the TVL of one program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramTvlConfig {
+    /// Program name (must match the subscriber program name)
+    pub program_name: String,
+
+    /// Vault or pool accounts owned by the program
+    pub vaults: Vec<VaultConfig>,
+}
+
+/// A single vault account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultConfig {
+    /// Vault account public key (SPL token account or native SOL account)
+    pub account: String,
+
+    /// Token symbol used to look up the price
+    pub symbol: String,
+
+    /// Token decimals
+    pub decimals: u8,
+
+    /// Fixed price to use instead of the price feed (e.g. 1.0 for stablecoins)
+    #[serde(default)]
+    pub price_override: Option<f64>,
+}
+
+/// Decoded balance of a vault account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VaultBalance {
+    /// Raw token amount (or lamports for native accounts)
+    pub raw_amount: u64,
+
+    /// Whether the account was decoded as an SPL token account
+    pub is_token_account: bool,
+}
+
+/// Errors that can occur during TVL computation.
+#[derive(Error, Debug)]
+pub enum TvlError {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    #[error("Invalid account data: {0}")]
+    InvalidAccount(String),
+
+    #[error("TVL configuration error: {0}")]
+    Configuration(String),
+}
+
+pub type TvlResult<T> = Result<T, TvlError>;
+
+/// Metric key under which a program's TVL is published for rule evaluation.
+pub fn tvl_metric_key(program_name: &str) -> String {
+    format!("{}_tvl", program_name)
+}
+
+/// Periodically computes TVL per program and publishes it to the metrics collector.
+pub struct TvlTracker {
+    config: TvlConfig,
+    rpc_url: String,
+    client: reqwest::Client,
+    metrics: Arc<MetricsCollector>,
+}
+
+impl TvlTracker {
+    /// Create a new TVL tracker using the given Solana RPC endpoint.
+    pub fn new(
+        config: TvlConfig,
+        rpc_url: impl Into<String>,
+        metrics: Arc<MetricsCollector>,
+    ) -> TvlResult<Self> {
+        config.validate()?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .build()?;
+
+        Ok(Self {
+            config,
+            rpc_url: rpc_url.into(),
+            client,
+            metrics,
+        })
+    }
+
+    /// Recompute TVL for every configured program.
+    pub async fn refresh(&self) -> TvlResult<HashMap<String, f64>> {
+        let accounts: Vec<String> = self
+            .config
+            .programs
+            .iter()
+            .flat_map(|p| p.vaults.iter().map(|v| v.account.clone()))
+            .collect();
+
+        let mut balances = HashMap::new();
+        for chunk in accounts.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            balances.extend(self.fetch_balances(chunk).await?);
+        }
+
+        let mut tvls = HashMap::new();
+        for program in &self.config.programs {
+            match self.compute_program_tvl(program, &balances) {
+                Some(tvl) => {
+                    self.metrics.update_tvl(&program.program_name, tvl);
+                    tvls.insert(program.program_name.clone(), tvl);
+                }
+                None => {
+                    warn!(
+                        "Skipping TVL update for {}: missing balances or prices",
+                        program.program_name
+                    );
+                }
+            }
+        }
+
+        debug!("Updated TVL for {} programs", tvls.len());
+        Ok(tvls)
+    }
+
+    /// Start refreshing TVL in the background.
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        info!(
+            "Starting TVL tracker for {} programs (every {}s)",
+            self.config.programs.len(),
+            self.config.refresh_interval_seconds
+        );
+
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.config.refresh_interval_seconds));
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.refresh().await {
+                    error!("Failed to refresh TVL: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Sum the USD value of all vaults of a program.
+    ///
+    /// Returns `None` if any vault balance or price is unavailable, so a partial
+    /// sum is never published as the program's TVL.
+    fn compute_program_tvl(
+        &self,
+        program: &ProgramTvlConfig,
+        balances: &HashMap<String, VaultBalance>,
+    ) -> Option<f64> {
+        let mut tvl = 0.0;
+
+        for vault in &program.vaults {
+            let balance = balances.get(&vault.account)?;
+            let price = vault
+                .price_override
+                .or_else(|| self.metrics.token_price(&vault.symbol))?;
+
+            let decimals = if balance.is_token_account {
+                vault.decimals
+            } else {
+                9
+            };
+            let amount = balance.raw_amount as f64 / 10f64.powi(decimals as i32);
+            tvl += amount * price;
+        }
+
+        Some(tvl)
+    }
+
+    /// Fetch and decode balances for a set of accounts via `getMultipleAccounts`.
+    async fn fetch_balances(
+        &self,
+        accounts: &[String],
+    ) -> TvlResult<HashMap<String, VaultBalance>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [
+                accounts,
+                { "encoding": "base64", "commitment": "confirmed" }
+            ]
+        });
+
+        let response: Value = self
+            .client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(TvlError::Rpc(error.to_string()));
+        }
+
+        let values = response
+            .pointer("/result/value")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| TvlError::Rpc("missing result.value".to_string()))?;
+
+        let mut balances = HashMap::new();
+        for (account, value) in accounts.iter().zip(values) {
+            if value.is_null() {
+                warn!("Vault account {} not found", account);
+                continue;
+            }
+
+            match decode_vault_account(value) {
+                Ok(balance) => {
+                    balances.insert(account.clone(), balance);
+                }
+                Err(e) => warn!("Failed to decode vault account {}: {}", account, e),
+            }
+        }
+
+        Ok(balances)
+    }
+}
+
+/// Decode an RPC account object into a vault balance.
+pub fn decode_vault_account(account: &Value) -> TvlResult<VaultBalance> {
+    let owner = account
+        .get("owner")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| TvlError::InvalidAccount("missing owner".to_string()))?;
+
+    if owner == TOKEN_PROGRAM_ID || owner == TOKEN_2022_PROGRAM_ID {
+        let encoded = account
+            .pointer("/data/0")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| TvlError::InvalidAccount("missing data".to_string()))?;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| TvlError::InvalidAccount(e.to_string()))?;
+
+        let amount_bytes: [u8; 8] = data
+            .get(TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| TvlError::InvalidAccount("token account too short".to_string()))?;
+
+        Ok(VaultBalance {
+            raw_amount: u64::from_le_bytes(amount_bytes),
+            is_token_account: true,
+        })
+    } else {
+        let lamports = account
+            .get("lamports")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| TvlError::InvalidAccount("missing lamports".to_string()))?;
+
+        Ok(VaultBalance {
+            raw_amount: lamports,
+            is_token_account: false,
+        })
+    }
+}
+
+impl TvlConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> TvlResult<()> {
+        if self.refresh_interval_seconds == 0 {
+            return Err(TvlError::Configuration(
+                "Refresh interval must be greater than 0".to_string(),
+            ));
+        }
+
+        for program in &self.programs {
+            if program.vaults.is_empty() {
+                return Err(TvlError::Configuration(format!(
+                    "Program {} has no vault accounts",
+                    program.program_name
+                )));
+            }
+
+            for vault in &program.vaults {
+                Pubkey::from_str(&vault.account).map_err(|_| {
+                    TvlError::Configuration(format!("Invalid vault account: {}", vault.account))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Default value functions
+fn default_refresh_interval() -> u64 {
+    60
+}
+
+fn default_request_timeout() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_account_json(amount: u64) -> Value {
+        let mut data = vec![0u8; 165];
+        data[TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8].copy_from_slice(&amount.to_le_bytes());
+        json!({
+            "owner": TOKEN_PROGRAM_ID,
+            "lamports": 2039280,
+            "data": [base64::engine::general_purpose::STANDARD.encode(&data), "base64"]
+        })
+    }
+
+    #[test]
+    fn test_decode_token_account() {
+        let balance = decode_vault_account(&token_account_json(1_500_000)).unwrap();
+        assert!(balance.is_token_account);
+        assert_eq!(balance.raw_amount, 1_500_000);
+    }
+
+    #[test]
+    fn test_compute_program_tvl() {
+        let usdc_vault = Pubkey::new_unique().to_string();
+        let sol_vault = Pubkey::new_unique().to_string();
+        let program = ProgramTvlConfig {
+            program_name: "Test AMM".to_string(),
+            vaults: vec![
+                VaultConfig {
+                    account: usdc_vault.clone(),
+                    symbol: "USDC".to_string(),
+                    decimals: 6,
+                    price_override: Some(1.0),
+                },
+                VaultConfig {
+                    account: sol_vault.clone(),
+                    symbol: "SOL".to_string(),
+                    decimals: 9,
+                    price_override: None,
+                },
+            ],
+        };
+
+        let metrics = Arc::new(MetricsCollector::new().unwrap());
+        metrics.update_token_price("SOL", 100.0);
+
+        let tracker = TvlTracker::new(
+            TvlConfig {
+                refresh_interval_seconds: default_refresh_interval(),
+                request_timeout_seconds: default_request_timeout(),
+                programs: vec![program.clone()],
+            },
+            "http://localhost:8899",
+            metrics,
+        )
+        .unwrap();
+
+        let mut balances = HashMap::new();
+        balances.insert(
+            usdc_vault,
+            decode_vault_account(&token_account_json(2_000_000)).unwrap(),
+        );
+        balances.insert(
+            sol_vault,
+            VaultBalance {
+                raw_amount: 3_000_000_000,
+                is_token_account: false,
+            },
+        );
+
+        let tvl = tracker.compute_program_tvl(&program, &balances).unwrap();
+        assert!((tvl - 302.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,326 @@
+//! Capture channel recording rendered notifications instead of sending them.
+//!
+//! Configured on its own it is one more channel named `capture`; in dry-run
+//! mode it stands in for every configured channel, so templates, routing and
+//! filters can be checked in CI or staging without notifying anyone.
+
+use crate::{
+    channels::NotificationChannel,
+    config::NotifierConfig,
+    error::NotifierResult,
+    templates::{html_to_text, TemplateEngine},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use watchtower_engine::Alert;
+
+/// Capture channel configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureConfig {
+    /// Capture instead of sending through every configured channel
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// File captured notifications are appended to, one JSON object per line
+    pub path: Option<PathBuf>,
+
+    /// Maximum number of captured notifications kept in memory
+    #[serde(default = "default_max_captured")]
+    pub max_captured: usize,
+}
+
+/// A notification as it would have been sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedNotification {
+    /// Channel the notification was addressed to
+    pub channel: String,
+
+    /// Alert ID
+    pub alert_id: String,
+
+    /// Rule that raised the alert
+    pub rule_name: String,
+
+    /// Alert severity
+    pub severity: String,
+
+    /// Rendered message (the plain-text part for email)
+    pub message: String,
+
+    /// Rendered HTML body, for email
+    pub html: Option<String>,
+
+    /// When the notification was captured
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Captured notifications shared by the capture channels.
+#[derive(Debug, Clone)]
+pub struct CaptureSink {
+    captured: Arc<Mutex<VecDeque<CapturedNotification>>>,
+    path: Option<PathBuf>,
+    max_captured: usize,
+}
+
+/// Channel that records rendered notifications in a capture sink.
+pub struct CaptureChannel {
+    name: String,
+    message_template: Option<String>,
+    template_engine: TemplateEngine,
+    sink: CaptureSink,
+}
+
+impl CaptureSink {
+    /// Create a sink from the capture configuration.
+    pub fn new(config: &CaptureConfig) -> Self {
+        Self {
+            captured: Arc::new(Mutex::new(VecDeque::new())),
+            path: config.path.clone(),
+            max_captured: config.max_captured,
+        }
+    }
+
+    /// Record a notification, appending it to the capture file if configured.
+    pub fn record(&self, notification: CapturedNotification) -> NotifierResult<()> {
+        if let Some(path) = &self.path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(&notification)?)?;
+        }
+
+        let mut captured = self.captured.lock().unwrap();
+        captured.push_back(notification);
+        while captured.len() > self.max_captured {
+            captured.pop_front();
+        }
+
+        Ok(())
+    }
+
+    /// Notifications captured so far, oldest first.
+    pub fn captured(&self) -> Vec<CapturedNotification> {
+        self.captured.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Forget the notifications captured so far.
+    pub fn clear(&self) {
+        self.captured.lock().unwrap().clear();
+    }
+}
+
+impl CaptureChannel {
+    /// Create a capture channel named after the channel it stands in for.
+    pub fn new(name: impl Into<String>, sink: CaptureSink) -> Self {
+        Self {
+            name: name.into(),
+            message_template: None,
+            template_engine: TemplateEngine::new(),
+            sink,
+        }
+    }
+
+    /// Stand in for a configured channel, rendering its message template.
+    pub fn for_channel(config: &NotifierConfig, name: &str, sink: CaptureSink) -> Self {
+        let message_template = match name {
+            "email" => config.email.as_ref().and_then(|c| c.body_template.clone()),
+            "telegram" => config
+                .telegram
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "slack" => config
+                .slack
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "discord" => config
+                .discord
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "mattermost" => config
+                .mattermost
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "grafana_oncall" => config
+                .grafana_oncall
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "pushover" => config
+                .pushover
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "ntfy" => config
+                .ntfy
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            "sms" => config.sms.as_ref().and_then(|c| c.message_template.clone()),
+            "signal" => config
+                .signal
+                .as_ref()
+                .and_then(|c| c.message_template.clone()),
+            _ => None,
+        };
+
+        Self {
+            message_template,
+            ..Self::new(name, sink)
+        }
+    }
+
+    /// Use a shared template engine instead of the built-in templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Render the message the channel would send, and its HTML for email.
+    fn render(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<(String, Option<String>)> {
+        let engine = &self.template_engine;
+
+        Ok(match (&self.message_template, self.name.as_str()) {
+            (Some(template), "email") => {
+                let html = engine.render_template(template, template_data)?;
+                (html_to_text(&html), Some(html))
+            }
+            (Some(template), _) => (engine.render_template(template, template_data)?, None),
+            (None, "email") => (
+                engine.render_default_email_text_template(alert)?,
+                Some(engine.render_default_email_template(alert)?),
+            ),
+            (None, "telegram") => (engine.render_default_telegram_template(alert)?, None),
+            (None, "slack") => (engine.render_default_slack_template(alert)?, None),
+            (None, "discord") => (engine.render_default_discord_template(alert)?, None),
+            (None, _) => (engine.render_default_email_text_template(alert)?, None),
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for CaptureChannel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let (message, html) = self.render(alert, template_data)?;
+
+        self.sink.record(CapturedNotification {
+            channel: self.name.clone(),
+            alert_id: alert.id.clone(),
+            rule_name: alert.rule_name.clone(),
+            severity: alert.severity.as_str().to_string(),
+            message,
+            html,
+            captured_at: Utc::now(),
+        })?;
+
+        Ok(None)
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        Ok(())
+    }
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            path: None,
+            max_captured: default_max_captured(),
+        }
+    }
+}
+
+// Default value functions
+fn default_max_captured() -> usize {
+    1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_engine::AlertSeverity;
+
+    #[tokio::test]
+    async fn test_capture_records_rendered_payloads() {
+        let path =
+            std::env::temp_dir().join(format!("watchtower-capture-{}.jsonl", std::process::id()));
+        let sink = CaptureSink::new(&CaptureConfig {
+            dry_run: true,
+            path: Some(path.clone()),
+            max_captured: 1,
+        });
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transaction detected".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let email = CaptureChannel::new("email", sink.clone());
+        email.send(&alert, &HashMap::new()).await.unwrap();
+
+        let mut telegram = CaptureChannel::new("telegram", sink.clone());
+        telegram.message_template = Some("{{ rule_name }}: {{ message }}".to_string());
+        let template_data = HashMap::from([
+            ("rule_name".to_string(), Value::from("large_transaction")),
+            (
+                "message".to_string(),
+                Value::from("Large transaction detected"),
+            ),
+        ]);
+        telegram.send(&alert, &template_data).await.unwrap();
+
+        // Only the latest notification is kept in memory
+        let captured = sink.captured();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].channel, "telegram");
+        assert_eq!(
+            captured[0].message,
+            "large_transaction: Large transaction detected"
+        );
+        assert!(captured[0].html.is_none());
+
+        // The file has every notification
+        let lines: Vec<CapturedNotification> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].channel, "email");
+        assert!(lines[0]
+            .html
+            .as_deref()
+            .unwrap()
+            .contains("Large transaction detected"));
+        std::fs::remove_file(&path).unwrap();
+
+        sink.clear();
+        assert!(sink.captured().is_empty());
+    }
+}
@@ -1,21 +1,35 @@
 //! Notification channel implementations.
 
 use crate::{
-    config::{DiscordConfig, EmailConfig, SlackConfig, TelegramConfig},
+    charts::{self, CHART_FILE_NAME},
+    config::{
+        parse_severity, program_route, DiscordConfig, EmailConfig, GrafanaOnCallConfig,
+        MattermostConfig, NtfyConfig, PagerDutyConfig, ProgramRoute, PushoverConfig, SignalConfig,
+        SlackConfig, SmsConfig, SnsConfig, TelegramConfig,
+    },
+    dkim::DkimSigner,
     error::{NotifierError, NotifierResult},
-    templates::TemplateEngine,
+    oauth::OAuth2TokenSource,
+    templates::{escape_telegram_links, escape_telegram_value, html_to_text, TemplateEngine},
 };
 use async_trait::async_trait;
+use aws_sdk_sns::{error::DisplayErrorContext, types::MessageAttributeValue};
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
 use lettre::{
-    message::{header::ContentType, Mailbox, Message},
-    transport::smtp::{authentication::Credentials, PoolConfig},
+    message::{header::ContentType, Attachment, Mailbox, Message, MultiPart, SinglePart},
+    transport::smtp::{
+        authentication::{Credentials, Mechanism},
+        PoolConfig,
+    },
     AsyncSmtpTransport, AsyncTransport, Tokio1Executor,
 };
 use reqwest::Client;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use tracing::{error, info};
-use watchtower_engine::Alert;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use watchtower_engine::{Alert, AlertSeverity};
 
 /// Trait for notification channels.
 #[async_trait]
@@ -23,16 +37,28 @@ pub trait NotificationChannel: Send + Sync {
     /// Channel name (e.g., "email", "telegram", "slack")
     fn name(&self) -> &str;
 
-    /// Send a notification through this channel
+    /// Send a notification through this channel, returning the provider's
+    /// message identifier when it reports one
     async fn send(
         &self,
         alert: &Alert,
         template_data: &HashMap<String, Value>,
-    ) -> NotifierResult<()>;
+    ) -> NotifierResult<Option<String>>;
 
     /// Test the channel configuration
     async fn test(&self) -> NotifierResult<()>;
 
+    /// Destination an alert is delivered to (chat, webhook, ...), used to
+    /// rate limit each destination separately
+    fn rate_limit_key(&self, _alert: &Alert) -> String {
+        self.name().to_string()
+    }
+
+    /// Whether this channel wants to receive the alert at all
+    fn accepts(&self, _alert: &Alert) -> bool {
+        true
+    }
+
     /// Whether this channel supports batching
     fn supports_batching(&self) -> bool {
         false
@@ -48,13 +74,23 @@ pub trait NotificationChannel: Send + Sync {
             "Batching not supported for this channel".to_string(),
         ))
     }
+
+    /// Notify that a previously sent alert was resolved (no-op by default)
+    async fn resolve(&self, _alert: &Alert) -> NotifierResult<()> {
+        Ok(())
+    }
 }
 
 /// Email notification channel.
 pub struct EmailChannel {
     config: EmailConfig,
-    transport: AsyncSmtpTransport<Tokio1Executor>,
+    /// Transport and the OAuth2 access token it authenticates with, if any
+    transport: tokio::sync::Mutex<(AsyncSmtpTransport<Tokio1Executor>, Option<String>)>,
     template_engine: TemplateEngine,
+    dkim: Option<DkimSigner>,
+    oauth2: Option<OAuth2TokenSource>,
+    /// Recipient overrides per program
+    program_routes: Vec<ProgramRoute>,
 }
 
 /// Telegram notification channel.
@@ -62,6 +98,8 @@ pub struct TelegramChannel {
     config: TelegramConfig,
     client: Client,
     template_engine: TemplateEngine,
+    /// Chat overrides per program
+    program_routes: Vec<ProgramRoute>,
 }
 
 /// Slack notification channel.
@@ -69,6 +107,10 @@ pub struct SlackChannel {
     config: SlackConfig,
     client: Client,
     template_engine: TemplateEngine,
+    /// Thread (message `ts` and when it was started) per alert fingerprint
+    threads: Mutex<HashMap<String, (String, Instant)>>,
+    /// Channel overrides per program
+    program_routes: Vec<ProgramRoute>,
 }
 
 /// Discord notification channel.
@@ -76,32 +118,240 @@ pub struct DiscordChannel {
     config: DiscordConfig,
     client: Client,
     template_engine: TemplateEngine,
+    /// Thread (channel ID and when it was opened) per alert fingerprint
+    threads: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+/// Mattermost notification channel.
+pub struct MattermostChannel {
+    config: MattermostConfig,
+    client: Client,
+    template_engine: TemplateEngine,
+}
+
+/// PagerDuty Events API v2 notification channel.
+pub struct PagerDutyChannel {
+    config: PagerDutyConfig,
+    client: Client,
+}
+
+/// Grafana OnCall notification channel (formatted webhook integration).
+pub struct GrafanaOnCallChannel {
+    config: GrafanaOnCallConfig,
+    client: Client,
+    template_engine: TemplateEngine,
+}
+
+/// Pushover notification channel.
+pub struct PushoverChannel {
+    config: PushoverConfig,
+    client: Client,
+    template_engine: TemplateEngine,
+}
+
+/// ntfy push notification channel.
+pub struct NtfyChannel {
+    config: NtfyConfig,
+    client: Client,
+    template_engine: TemplateEngine,
+}
+
+/// AWS SNS notification channel publishing alerts as JSON.
+pub struct SnsChannel {
+    config: SnsConfig,
+    client: aws_sdk_sns::Client,
+}
+
+/// SMS notification channel backed by the Twilio Messaging API.
+pub struct SmsChannel {
+    config: SmsConfig,
+    client: Client,
+    template_engine: TemplateEngine,
+    min_severity: AlertSeverity,
+    number_limiter: DefaultKeyedRateLimiter<String>,
+}
+
+/// Signal notification channel backed by a signal-cli REST API gateway.
+pub struct SignalChannel {
+    config: SignalConfig,
+    client: Client,
+    template_engine: TemplateEngine,
+    min_severity: AlertSeverity,
 }
 
 impl EmailChannel {
     /// Create a new email channel.
     pub fn new(config: EmailConfig) -> NotifierResult<Self> {
-        let creds = Credentials::new(config.username.clone(), config.password.clone());
-
-        let transport = if config.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_server)
-                .map_err(|e| NotifierError::SmtpTransportBuild(e.to_string()))?
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_server)
-        }
-        .port(config.smtp_port)
-        .credentials(creds)
-        .pool_config(PoolConfig::new().max_size(10))
-        .build();
+        // With OAuth2 the transport is rebuilt once an access token is fetched
+        let transport = build_smtp_transport(&config, &config.password, None)?;
+        let dkim = config
+            .dkim
+            .as_ref()
+            .map(DkimSigner::from_config)
+            .transpose()?;
+        let oauth2 = config.oauth2.clone().map(OAuth2TokenSource::new);
 
         Ok(Self {
             config,
-            transport,
+            transport: tokio::sync::Mutex::new((transport, None)),
             template_engine: TemplateEngine::new(),
+            dkim,
+            oauth2,
+            program_routes: Vec::new(),
+        })
+    }
+
+    /// Refresh OAuth2 tokens with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.oauth2 = self.oauth2.map(|oauth2| oauth2.with_client(client));
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Send the alerts of routed programs to their own recipients.
+    pub fn with_program_routes(mut self, program_routes: Vec<ProgramRoute>) -> Self {
+        self.program_routes = program_routes;
+        self
+    }
+
+    /// Recipients of an alert: those of its program's route, or the defaults.
+    fn recipients(&self, alert: &Alert) -> &[String] {
+        program_route(&self.program_routes, alert)
+            .map(|route| route.email_to.as_slice())
+            .filter(|recipients| !recipients.is_empty())
+            .unwrap_or(&self.config.to_addresses)
+    }
+
+    /// Transport authenticated with the current credentials, rebuilt with
+    /// XOAUTH2 whenever the OAuth2 access token is refreshed.
+    async fn transport(&self) -> NotifierResult<AsyncSmtpTransport<Tokio1Executor>> {
+        let mut transport = self.transport.lock().await;
+
+        if let Some(oauth2) = &self.oauth2 {
+            let access_token = oauth2.access_token().await?;
+            if transport.1.as_ref() != Some(&access_token) {
+                let rebuilt =
+                    build_smtp_transport(&self.config, &access_token, Some(Mechanism::Xoauth2))?;
+                *transport = (rebuilt, Some(access_token));
+            }
+        }
+
+        Ok(transport.0.clone())
+    }
+
+    /// Build an email with a plain-text alternative to HTML bodies, signed
+    /// with DKIM when configured. A chart is embedded inline in HTML bodies.
+    fn build_message(
+        &self,
+        from: &Mailbox,
+        to_address: &str,
+        subject: &str,
+        body: &EmailBody,
+        chart: Option<&[u8]>,
+    ) -> NotifierResult<Message> {
+        let builder = Message::builder()
+            .from(from.clone())
+            .to(to_address.parse()?)
+            .subject(subject);
+
+        let mut email = match (body, chart) {
+            (EmailBody::Text(text), _) => builder.singlepart(SinglePart::plain(text.clone()))?,
+            (EmailBody::Alternative { text, html }, None) => builder.multipart(
+                MultiPart::alternative_plain_html(text.clone(), html.clone()),
+            )?,
+            (EmailBody::Alternative { text, html }, Some(png)) => {
+                let content_type = ContentType::parse("image/png")
+                    .map_err(|e| NotifierError::MessageFormat(e.to_string()))?;
+                let image = Attachment::new_inline(CHART_CONTENT_ID.to_string())
+                    .body(png.to_vec(), content_type);
+                builder.multipart(
+                    MultiPart::alternative()
+                        .singlepart(SinglePart::plain(text.clone()))
+                        .multipart(
+                            MultiPart::related()
+                                .singlepart(SinglePart::html(with_inline_chart(html)))
+                                .singlepart(image),
+                        ),
+                )?
+            }
+        };
+
+        if let Some(dkim) = &self.dkim {
+            dkim.sign(&mut email)?;
+        }
+
+        Ok(email)
+    }
+
+    /// Sender mailbox with the configured display name.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_mailbox(&self) -> NotifierResult<Mailbox> {
+        Ok(if let Some(from_name) = &self.config.from_name {
+            Mailbox::new(Some(from_name.clone()), self.config.from_address.parse()?)
+        } else {
+            self.config.from_address.parse()?
         })
     }
 }
 
+/// SMTP transport for the configured server, authenticating with `secret`
+/// (a password, or an access token for XOAUTH2).
+fn build_smtp_transport(
+    config: &EmailConfig,
+    secret: &str,
+    mechanism: Option<Mechanism>,
+) -> NotifierResult<AsyncSmtpTransport<Tokio1Executor>> {
+    let creds = Credentials::new(config.username.clone(), secret.to_string());
+
+    let mut builder = if config.use_tls {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_server)
+            .map_err(|e| NotifierError::SmtpTransportBuild(e.to_string()))?
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_server)
+    }
+    .port(config.smtp_port)
+    .credentials(creds)
+    .pool_config(PoolConfig::new().max_size(10));
+
+    if let Some(mechanism) = mechanism {
+        builder = builder.authentication(vec![mechanism]);
+    }
+
+    Ok(builder.build())
+}
+
+/// Content ID of charts embedded in emails.
+const CHART_CONTENT_ID: &str = "watchtower-chart";
+
+/// HTML with an image referencing the inline chart, placed at the end of the body.
+fn with_inline_chart(html: &str) -> String {
+    let image = format!(
+        r#"<p><img src="cid:{}" alt="Metric chart" width="{}" height="{}"></p>"#,
+        CHART_CONTENT_ID,
+        charts::CHART_WIDTH,
+        charts::CHART_HEIGHT
+    );
+
+    match html.rfind("</body>") {
+        Some(index) => format!("{}{}{}", &html[..index], image, &html[index..]),
+        None => format!("{}{}", html, image),
+    }
+}
+
+/// Body of an email.
+enum EmailBody {
+    /// Plain text only
+    Text(String),
+
+    /// HTML with a plain-text alternative
+    Alternative { text: String, html: String },
+}
+
 #[async_trait]
 impl NotificationChannel for EmailChannel {
     fn name(&self) -> &str {
@@ -112,7 +362,7 @@ impl NotificationChannel for EmailChannel {
         &self,
         alert: &Alert,
         template_data: &HashMap<String, Value>,
-    ) -> NotifierResult<()> {
+    ) -> NotifierResult<Option<String>> {
         let subject = if let Some(template) = &self.config.subject_template {
             self.template_engine
                 .render_template(template, template_data)?
@@ -125,27 +375,47 @@ impl NotificationChannel for EmailChannel {
         };
 
         let body = if let Some(template) = &self.config.body_template {
-            self.template_engine
-                .render_template(template, template_data)?
+            let body = self
+                .template_engine
+                .render_template(template, template_data)?;
+
+            if let Some(text_template) = &self.config.text_template {
+                EmailBody::Alternative {
+                    text: self
+                        .template_engine
+                        .render_template(text_template, template_data)?,
+                    html: body,
+                }
+            } else if body.contains("</") {
+                EmailBody::Alternative {
+                    text: html_to_text(&body),
+                    html: body,
+                }
+            } else {
+                EmailBody::Text(body)
+            }
         } else {
-            self.template_engine.render_default_email_template(alert)?
+            EmailBody::Alternative {
+                text: self
+                    .template_engine
+                    .render_default_email_text_template(alert)?,
+                html: self.template_engine.render_default_email_template(alert)?,
+            }
         };
 
-        let from_mailbox = if let Some(from_name) = &self.config.from_name {
-            Mailbox::new(Some(from_name.clone()), self.config.from_address.parse()?)
+        let chart = if self.config.attach_chart {
+            charts::alert_chart(alert)
         } else {
-            self.config.from_address.parse()?
+            None
         };
+        let from_mailbox = self.from_mailbox()?;
+        let transport = self.transport().await?;
 
-        for to_address in &self.config.to_addresses {
-            let email = Message::builder()
-                .from(from_mailbox.clone())
-                .to(to_address.parse()?)
-                .subject(&subject)
-                .header(ContentType::TEXT_HTML)
-                .body(body.clone())?;
+        for to_address in self.recipients(alert) {
+            let email =
+                self.build_message(&from_mailbox, to_address, &subject, &body, chart.as_deref())?;
 
-            match self.transport.send(email).await {
+            match transport.send(email).await {
                 Ok(_) => {
                     info!("Email sent successfully to {}", to_address);
                 }
@@ -156,7 +426,7 @@ impl NotificationChannel for EmailChannel {
             }
         }
 
-        Ok(())
+        Ok(None)
     }
 
     async fn test(&self) -> NotifierResult<()> {
@@ -176,9 +446,10 @@ impl NotificationChannel for EmailChannel {
             timestamp: chrono::Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
-        self.send(&test_alert, &test_data).await
+        self.send(&test_alert, &test_data).await.map(|_| ())
     }
 
     fn supports_batching(&self) -> bool {
@@ -190,27 +461,34 @@ impl NotificationChannel for EmailChannel {
         alerts: &[Alert],
         _template_data: &HashMap<String, Value>,
     ) -> NotifierResult<()> {
-        let subject = format!("[Watchtower] {} Alerts", alerts.len());
-        let body = self.template_engine.render_batch_email_template(alerts)?;
+        // Alerts routed to different recipients are sent as separate batches
+        let mut groups: Vec<(&[String], Vec<Alert>)> = Vec::new();
+        for alert in alerts {
+            let recipients = self.recipients(alert);
+            match groups.iter_mut().find(|(r, _)| *r == recipients) {
+                Some((_, group)) => group.push(alert.clone()),
+                None => groups.push((recipients, vec![alert.clone()])),
+            }
+        }
 
-        let from_mailbox = if let Some(from_name) = &self.config.from_name {
-            Mailbox::new(Some(from_name.clone()), self.config.from_address.parse()?)
-        } else {
-            self.config.from_address.parse()?
-        };
+        let from_mailbox = self.from_mailbox()?;
+        let transport = self.transport().await?;
 
-        for to_address in &self.config.to_addresses {
-            let email = Message::builder()
-                .from(from_mailbox.clone())
-                .to(to_address.parse()?)
-                .subject(&subject)
-                .header(ContentType::TEXT_HTML)
-                .body(body.clone())?;
+        for (recipients, group) in &groups {
+            let subject = format!("[Watchtower] {} Alerts", group.len());
+            let body = EmailBody::Alternative {
+                text: self.template_engine.render_batch_email_text_template(group),
+                html: self.template_engine.render_batch_email_template(group)?,
+            };
+
+            for to_address in recipients.iter() {
+                let email = self.build_message(&from_mailbox, to_address, &subject, &body, None)?;
 
-            self.transport
-                .send(email)
-                .await
-                .map_err(NotifierError::SmtpTransport)?;
+                transport
+                    .send(email)
+                    .await
+                    .map_err(NotifierError::SmtpTransport)?;
+            }
         }
 
         info!("Batch email sent with {} alerts", alerts.len());
@@ -225,6 +503,47 @@ impl TelegramChannel {
             config,
             client: Client::new(),
             template_engine: TemplateEngine::new(),
+            program_routes: Vec::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Send the alerts of routed programs to their own recipients.
+    pub fn with_program_routes(mut self, program_routes: Vec<ProgramRoute>) -> Self {
+        self.program_routes = program_routes;
+        self
+    }
+
+    /// Chats and forum topics an alert is posted to: every entry of `chats`
+    /// (or of its program's route) receiving its severity, or the default
+    /// chat if none does.
+    fn destinations(&self, alert: &Alert) -> Vec<(i64, Option<i64>)> {
+        let chats = program_route(&self.program_routes, alert)
+            .map(|route| &route.telegram_chats)
+            .filter(|chats| !chats.is_empty())
+            .unwrap_or(&self.config.chats);
+
+        let destinations: Vec<(i64, Option<i64>)> = chats
+            .iter()
+            .filter(|chat| chat.receives(&alert.severity))
+            .map(|chat| (chat.chat_id, chat.message_thread_id))
+            .collect();
+
+        if destinations.is_empty() {
+            vec![(self.config.chat_id, None)]
+        } else {
+            destinations
         }
     }
 }
@@ -235,47 +554,99 @@ impl NotificationChannel for TelegramChannel {
         "telegram"
     }
 
+    fn rate_limit_key(&self, alert: &Alert) -> String {
+        self.destinations(alert)
+            .iter()
+            .map(|(chat_id, thread_id)| match thread_id {
+                Some(thread_id) => format!("{}/{}", chat_id, thread_id),
+                None => chat_id.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     async fn send(
         &self,
         alert: &Alert,
         template_data: &HashMap<String, Value>,
-    ) -> NotifierResult<()> {
+    ) -> NotifierResult<Option<String>> {
+        // Alert values are escaped so they can't break the parse mode's markup
         let message = if let Some(template) = &self.config.message_template {
+            let template_data: HashMap<String, Value> = template_data
+                .iter()
+                .map(|(key, value)| {
+                    let value = match key.as_str() {
+                        "links" => escape_telegram_links(value.clone(), &self.config.parse_mode),
+                        _ => escape_telegram_value(value.clone(), &self.config.parse_mode),
+                    };
+                    (key.clone(), value)
+                })
+                .collect();
             self.template_engine
-                .render_template(template, template_data)?
+                .render_template(template, &template_data)?
         } else {
             self.template_engine
-                .render_default_telegram_template(alert)?
+                .render_telegram_template(alert, &self.config.parse_mode)?
         };
 
+        let parts = fit_message(
+            &message,
+            TELEGRAM_MESSAGE_MAX_LENGTH,
+            self.config.split_long_messages,
+        );
+
         let url = format!(
             "https://api.telegram.org/bot{}/sendMessage",
             self.config.bot_token
         );
 
-        let mut payload = json!({
-            "chat_id": self.config.chat_id,
-            "text": message,
-            "disable_web_page_preview": self.config.disable_web_page_preview,
-            "disable_notification": self.config.disable_notification,
-        });
+        // Every destination is attempted; the last error is returned
+        let mut message_id = None;
+        let mut last_error = None;
+        for (chat_id, thread_id) in self.destinations(alert) {
+            for part in &parts {
+                let mut payload = json!({
+                    "chat_id": chat_id,
+                    "text": part,
+                    "disable_web_page_preview": self.config.disable_web_page_preview,
+                    "disable_notification": self.config.disable_notification,
+                });
+
+                if let Some(thread_id) = thread_id {
+                    payload["message_thread_id"] = json!(thread_id);
+                }
 
-        if !self.config.parse_mode.is_empty() {
-            payload["parse_mode"] = json!(self.config.parse_mode);
-        }
+                if !self.config.parse_mode.is_empty() {
+                    payload["parse_mode"] = json!(self.config.parse_mode);
+                }
 
-        let response = self.client.post(&url).json(&payload).send().await?;
+                let response = self.client.post(&url).json(&payload).send().await?;
+
+                if !response.status().is_success() {
+                    let error_text = response.text().await?;
+                    error!(
+                        "Telegram message to chat {} failed: {}",
+                        chat_id, error_text
+                    );
+                    last_error = Some(NotifierError::Generic(format!(
+                        "Telegram API error: {}",
+                        error_text
+                    )));
+                    break;
+                }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(NotifierError::Generic(format!(
-                "Telegram API error: {}",
-                error_text
-            )));
+                let body: Value = response.json().await.unwrap_or_default();
+                message_id =
+                    message_id.or_else(|| provider_message_id(&body, "/result/message_id"));
+            }
+        }
+
+        if let Some(e) = last_error {
+            return Err(e);
         }
 
         info!("Telegram message sent successfully");
-        Ok(())
+        Ok(message_id)
     }
 
     async fn test(&self) -> NotifierResult<()> {
@@ -294,9 +665,10 @@ impl NotificationChannel for TelegramChannel {
             timestamp: chrono::Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
-        self.send(&test_alert, &test_data).await
+        self.send(&test_alert, &test_data).await.map(|_| ())
     }
 }
 
@@ -307,8 +679,166 @@ impl SlackChannel {
             config,
             client: Client::new(),
             template_engine: TemplateEngine::new(),
+            threads: Mutex::new(HashMap::new()),
+            program_routes: Vec::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Send the alerts of routed programs to their own recipients.
+    pub fn with_program_routes(mut self, program_routes: Vec<ProgramRoute>) -> Self {
+        self.program_routes = program_routes;
+        self
+    }
+
+    /// Channel an alert is posted to: its program's route, or the default.
+    fn channel(&self, alert: &Alert) -> Option<&String> {
+        program_route(&self.program_routes, alert)
+            .and_then(|route| route.slack_channel.as_ref())
+            .or(self.config.channel.as_ref())
+    }
+
+    /// Thread an alert with this fingerprint should be posted into, if any.
+    fn thread_ts(&self, fingerprint: &str) -> Option<String> {
+        if self.config.bot_token.is_none() || !self.config.thread_related_alerts {
+            return None;
+        }
+
+        let window = Duration::from_secs(self.config.thread_window_seconds);
+        let mut threads = self.threads.lock().unwrap_or_else(|e| e.into_inner());
+        threads.retain(|_, (_, started)| started.elapsed() < window);
+        threads.get(fingerprint).map(|(ts, _)| ts.clone())
+    }
+
+    /// Remember the message starting the thread of a fingerprint.
+    fn remember_thread(&self, fingerprint: &str, ts: String) {
+        if self.config.thread_related_alerts {
+            self.threads
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(fingerprint.to_string(), (ts, Instant::now()));
+        }
+    }
+
+    /// Post a message through the Web API when a bot token is configured,
+    /// otherwise through the webhook. Returns the Web API response.
+    async fn post(&self, payload: &Value) -> NotifierResult<Option<Value>> {
+        let Some(bot_token) = &self.config.bot_token else {
+            let response = self
+                .config
+                .auth
+                .apply(self.client.post(&self.config.webhook_url))
+                .json(payload)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let error_text = response.text().await?;
+                return Err(NotifierError::Generic(format!(
+                    "Slack webhook failed: {}",
+                    error_text
+                )));
+            }
+
+            return Ok(None);
+        };
+
+        let body: Value = self
+            .client
+            .post("https://slack.com/api/chat.postMessage")
+            .bearer_auth(bot_token)
+            .json(payload)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        check_slack_response(&body)?;
+        Ok(Some(body))
+    }
+
+    /// Upload a chart into a message's thread (Web API only).
+    async fn upload_chart(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        alert: &Alert,
+        png: Vec<u8>,
+    ) -> NotifierResult<()> {
+        let Some(bot_token) = &self.config.bot_token else {
+            return Ok(());
+        };
+
+        let upload: Value = self
+            .client
+            .post("https://slack.com/api/files.getUploadURLExternal")
+            .bearer_auth(bot_token)
+            .form(&[
+                ("filename", CHART_FILE_NAME.to_string()),
+                ("length", png.len().to_string()),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+        check_slack_response(&upload)?;
+
+        let (Some(upload_url), Some(file_id)) =
+            (upload["upload_url"].as_str(), upload["file_id"].as_str())
+        else {
+            return Err(NotifierError::Generic(
+                "Slack API error: upload URL missing".to_string(),
+            ));
+        };
+
+        let response = self.client.post(upload_url).body(png).send().await?;
+        if !response.status().is_success() {
+            return Err(NotifierError::Generic(format!(
+                "Slack file upload failed with status {}",
+                response.status()
+            )));
         }
+
+        let title = format!("{} ({})", alert.rule_name, alert.program_name);
+        let completed: Value = self
+            .client
+            .post("https://slack.com/api/files.completeUploadExternal")
+            .bearer_auth(bot_token)
+            .json(&json!({
+                "files": [{ "id": file_id, "title": title }],
+                "channel_id": channel_id,
+                "thread_ts": thread_ts,
+            }))
+            .send()
+            .await?
+            .json()
+            .await?;
+        check_slack_response(&completed)
+    }
+}
+
+/// Fail on Web API responses with `ok: false`; the Web API reports errors in
+/// the body of 200 responses.
+fn check_slack_response(body: &Value) -> NotifierResult<()> {
+    if body["ok"] != json!(true) {
+        return Err(NotifierError::Generic(format!(
+            "Slack API error: {}",
+            body["error"].as_str().unwrap_or("unknown error")
+        )));
     }
+
+    Ok(())
 }
 
 #[async_trait]
@@ -317,23 +847,56 @@ impl NotificationChannel for SlackChannel {
         "slack"
     }
 
+    fn rate_limit_key(&self, alert: &Alert) -> String {
+        match (&self.config.bot_token, self.channel(alert)) {
+            (Some(_), Some(channel)) => channel.clone(),
+            _ => self.config.webhook_url.clone(),
+        }
+    }
+
+    async fn resolve(&self, alert: &Alert) -> NotifierResult<()> {
+        let fingerprint = alert.fingerprint();
+        let Some(thread_ts) = self.thread_ts(&fingerprint) else {
+            return Ok(());
+        };
+
+        // Close the thread so the next occurrence starts a new one
+        self.threads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&fingerprint);
+
+        let payload = json!({
+            "channel": self.channel(alert),
+            "thread_ts": thread_ts,
+            "text": format!(":white_check_mark: Resolved: {}", alert.rule_name),
+        });
+        self.post(&payload).await?;
+
+        info!("Slack thread resolved for {}", fingerprint);
+        Ok(())
+    }
+
     async fn send(
         &self,
         alert: &Alert,
         template_data: &HashMap<String, Value>,
-    ) -> NotifierResult<()> {
+    ) -> NotifierResult<Option<String>> {
         let text = if let Some(template) = &self.config.message_template {
             self.template_engine
                 .render_template(template, template_data)?
         } else {
             self.template_engine.render_default_slack_template(alert)?
         };
+        let text = truncate_at_boundary(&text, SLACK_TEXT_MAX_LENGTH);
+        let fingerprint = alert.fingerprint();
+        let thread_ts = self.thread_ts(&fingerprint);
 
         let mut payload = json!({
             "text": text,
         });
 
-        if let Some(channel) = &self.config.channel {
+        if let Some(channel) = self.channel(alert) {
             payload["channel"] = json!(channel);
         }
 
@@ -349,54 +912,40 @@ impl NotificationChannel for SlackChannel {
             }
         }
 
-        // Add alert severity color
-        let color = match alert.severity {
-            watchtower_engine::AlertSeverity::Critical => "#ff0000",
-            watchtower_engine::AlertSeverity::High => "#ff8c00",
-            watchtower_engine::AlertSeverity::Medium => "#ffd700",
-            watchtower_engine::AlertSeverity::Low => "#32cd32",
-            watchtower_engine::AlertSeverity::Info => "#87ceeb",
-        };
+        payload["attachments"] = json!([slack_attachment(alert)]);
 
-        payload["attachments"] = json!([{
-            "color": color,
-            "fields": [
-                {
-                    "title": "Program",
-                    "value": alert.program_name,
-                    "short": true
-                },
-                {
-                    "title": "Severity",
-                    "value": alert.severity.as_str(),
-                    "short": true
-                },
-                {
-                    "title": "Confidence",
-                    "value": format!("{:.1}%", alert.confidence * 100.0),
-                    "short": true
-                }
-            ],
-            "ts": alert.timestamp.timestamp()
-        }]);
+        if self.config.interactive {
+            payload["blocks"] = slack_blocks(alert, &text);
+        }
 
-        let response = self
-            .client
-            .post(&self.config.webhook_url)
-            .json(&payload)
-            .send()
-            .await?;
+        if let Some(thread_ts) = &thread_ts {
+            payload["thread_ts"] = json!(thread_ts);
+        }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(NotifierError::Generic(format!(
-                "Slack webhook failed: {}",
-                error_text
-            )));
+        let response = self.post(&payload).await?;
+        let ts = response
+            .as_ref()
+            .and_then(|body| provider_message_id(body, "/ts"));
+        if let (None, Some(ts)) = (&thread_ts, &ts) {
+            self.remember_thread(&fingerprint, ts.clone());
+        }
+
+        // Charts are uploaded as files into the alert's thread
+        if self.config.attach_chart {
+            let channel_id = response.as_ref().and_then(|body| body["channel"].as_str());
+            if let (Some(channel_id), Some(thread), Some(png)) = (
+                channel_id,
+                thread_ts.as_ref().or(ts.as_ref()),
+                charts::alert_chart(alert),
+            ) {
+                if let Err(e) = self.upload_chart(channel_id, thread, alert, png).await {
+                    warn!("Failed to upload chart to Slack: {}", e);
+                }
+            }
         }
 
         info!("Slack message sent successfully");
-        Ok(())
+        Ok(ts)
     }
 
     async fn test(&self) -> NotifierResult<()> {
@@ -415,12 +964,85 @@ impl NotificationChannel for SlackChannel {
             timestamp: chrono::Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
-        self.send(&test_alert, &test_data).await
+        self.send(&test_alert, &test_data).await.map(|_| ())
     }
 }
 
+/// Slack-style message attachment, also understood by Mattermost.
+fn slack_attachment(alert: &Alert) -> Value {
+    // Add alert severity color
+    let color = match alert.severity {
+        watchtower_engine::AlertSeverity::Critical => "#ff0000",
+        watchtower_engine::AlertSeverity::High => "#ff8c00",
+        watchtower_engine::AlertSeverity::Medium => "#ffd700",
+        watchtower_engine::AlertSeverity::Low => "#32cd32",
+        watchtower_engine::AlertSeverity::Info => "#87ceeb",
+    };
+
+    json!({
+        "color": color,
+        "fields": [
+            {
+                "title": "Program",
+                "value": alert.program_name,
+                "short": true
+            },
+            {
+                "title": "Severity",
+                "value": alert.severity.as_str(),
+                "short": true
+            },
+            {
+                "title": "Confidence",
+                "value": format!("{:.1}%", alert.confidence * 100.0),
+                "short": true
+            }
+        ],
+        "ts": alert.timestamp.timestamp()
+    })
+}
+
+/// Maximum length of a Block Kit section text.
+const SLACK_SECTION_MAX_LENGTH: usize = 3000;
+
+/// Block Kit blocks with Acknowledge/Resolve buttons carrying the alert ID.
+fn slack_blocks(alert: &Alert, text: &str) -> Value {
+    json!([
+        {
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": truncate_message(text, SLACK_SECTION_MAX_LENGTH),
+            }
+        },
+        {
+            "type": "actions",
+            "block_id": "watchtower_alert_actions",
+            "elements": [
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Acknowledge" },
+                    "action_id": "acknowledge",
+                    "value": alert.id,
+                },
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Resolve" },
+                    "style": "primary",
+                    "action_id": "resolve",
+                    "value": alert.id,
+                }
+            ]
+        }
+    ])
+}
+
+/// Maximum length of a Discord thread name.
+const DISCORD_THREAD_NAME_MAX_LENGTH: usize = 100;
+
 impl DiscordChannel {
     /// Create a new Discord channel.
     pub fn new(config: DiscordConfig) -> Self {
@@ -428,40 +1050,195 @@ impl DiscordChannel {
             config,
             client: Client::new(),
             template_engine: TemplateEngine::new(),
+            threads: Mutex::new(HashMap::new()),
         }
     }
-}
 
-#[async_trait]
-impl NotificationChannel for DiscordChannel {
-    fn name(&self) -> &str {
-        "discord"
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
     }
 
-    async fn send(
-        &self,
-        alert: &Alert,
-        template_data: &HashMap<String, Value>,
-    ) -> NotifierResult<()> {
-        let content = if let Some(template) = &self.config.message_template {
-            self.template_engine
-                .render_template(template, template_data)?
-        } else {
-            self.template_engine
-                .render_default_discord_template(alert)?
-        };
-
-        let mut payload = json!({
-            "content": content,
-        });
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
 
-        if let Some(username) = &self.config.username {
-            payload["username"] = json!(username);
+    /// Thread an alert with this fingerprint should be posted into, if any.
+    fn thread_id(&self, fingerprint: &str) -> Option<String> {
+        if !self.config.thread_per_incident {
+            return None;
         }
 
-        if let Some(avatar_url) = &self.config.avatar_url {
-            payload["avatar_url"] = json!(avatar_url);
-        }
+        let window = Duration::from_secs(self.config.thread_window_seconds);
+        let mut threads = self.threads.lock().unwrap_or_else(|e| e.into_inner());
+        threads.retain(|_, (_, opened)| opened.elapsed() < window);
+        threads.get(fingerprint).map(|(id, _)| id.clone())
+    }
+
+    /// Remember the thread opened for a fingerprint.
+    fn remember_thread(&self, fingerprint: &str, thread_id: String) {
+        self.threads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(fingerprint.to_string(), (thread_id, Instant::now()));
+    }
+
+    /// Role mentions and the `allowed_mentions` restricting pings to them.
+    ///
+    /// Only the configured roles of critical alerts are pinged; mentions in
+    /// alert text (e.g. `@everyone`) never are.
+    fn mentions(&self, alert: &Alert) -> (Option<String>, Value) {
+        if alert.severity != AlertSeverity::Critical || self.config.critical_role_ids.is_empty() {
+            return (None, json!({ "parse": [] }));
+        }
+
+        let mentions = self
+            .config
+            .critical_role_ids
+            .iter()
+            .map(|id| format!("<@&{}>", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        (
+            Some(mentions),
+            json!({ "parse": [], "roles": self.config.critical_role_ids }),
+        )
+    }
+
+    /// Execute the webhook, optionally in a thread and with a chart file,
+    /// returning the created message.
+    async fn execute(
+        &self,
+        payload: &Value,
+        thread_id: Option<&str>,
+        chart: Option<Vec<u8>>,
+    ) -> NotifierResult<Value> {
+        let mut query = vec![("wait", "true")];
+        if let Some(thread_id) = thread_id {
+            query.push(("thread_id", thread_id));
+        }
+
+        let request = self
+            .config
+            .auth
+            .apply(self.client.post(&self.config.webhook_url))
+            .query(&query);
+        let request = match chart {
+            // Files are sent as multipart form data next to the JSON payload
+            Some(png) => {
+                let file = reqwest::multipart::Part::bytes(png)
+                    .file_name(CHART_FILE_NAME)
+                    .mime_str("image/png")?;
+                request.multipart(
+                    reqwest::multipart::Form::new()
+                        .text("payload_json", payload.to_string())
+                        .part("files[0]", file),
+                )
+            }
+            None => request.json(payload),
+        };
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "Discord webhook failed: {}",
+                error_text
+            )));
+        }
+
+        Ok(response.json().await.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for DiscordChannel {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    fn rate_limit_key(&self, _alert: &Alert) -> String {
+        self.config.webhook_url.clone()
+    }
+
+    async fn resolve(&self, alert: &Alert) -> NotifierResult<()> {
+        let fingerprint = alert.fingerprint();
+        let Some(thread_id) = self.thread_id(&fingerprint) else {
+            return Ok(());
+        };
+
+        // Close the thread so the next occurrence opens a new one
+        self.threads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&fingerprint);
+
+        let payload = json!({
+            "content": format!(":white_check_mark: Resolved: {}", alert.rule_name),
+            "allowed_mentions": { "parse": [] },
+        });
+        self.execute(&payload, Some(&thread_id), None).await?;
+
+        info!("Discord thread resolved for {}", fingerprint);
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let content = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            self.template_engine
+                .render_default_discord_template(alert)?
+        };
+        let fingerprint = alert.fingerprint();
+        let thread_id = self.thread_id(&fingerprint);
+
+        let (mentions, allowed_mentions) = self.mentions(alert);
+        let content = match mentions {
+            Some(mentions) => format!("{}\n{}", mentions, content),
+            None => content,
+        };
+        let mut parts = fit_message(
+            &content,
+            DISCORD_CONTENT_MAX_LENGTH,
+            self.config.split_long_messages,
+        )
+        .into_iter();
+
+        let mut payload = json!({
+            "content": parts.next().unwrap_or_default(),
+            "allowed_mentions": allowed_mentions,
+        });
+
+        // Forum webhooks open a new thread when given a name
+        if self.config.thread_per_incident && thread_id.is_none() {
+            let thread_name: String = format!(
+                "{} {} on {}",
+                alert.severity.as_str().to_uppercase(),
+                alert.rule_name,
+                alert.program_name
+            )
+            .chars()
+            .take(DISCORD_THREAD_NAME_MAX_LENGTH)
+            .collect();
+            payload["thread_name"] = json!(thread_name);
+        }
+
+        if let Some(username) = &self.config.username {
+            payload["username"] = json!(username);
+        }
+
+        if let Some(avatar_url) = &self.config.avatar_url {
+            payload["avatar_url"] = json!(avatar_url);
+        }
 
         if self.config.use_embeds {
             let color = match alert.severity {
@@ -474,7 +1251,7 @@ impl NotificationChannel for DiscordChannel {
 
             payload["embeds"] = json!([{
                 "title": format!("{} Alert", alert.severity.as_str().to_uppercase()),
-                "description": alert.message,
+                "description": truncate_at_boundary(&alert.message, DISCORD_EMBED_DESCRIPTION_MAX_LENGTH),
                 "color": color,
                 "fields": [
                     {
@@ -497,9 +1274,135 @@ impl NotificationChannel for DiscordChannel {
             }]);
         }
 
+        let chart = if self.config.attach_chart {
+            charts::alert_chart(alert)
+        } else {
+            None
+        };
+        if chart.is_some() && self.config.use_embeds {
+            payload["embeds"][0]["image"] =
+                json!({ "url": format!("attachment://{}", CHART_FILE_NAME) });
+        }
+
+        let message = self.execute(&payload, thread_id.as_deref(), chart).await?;
+
+        // Messages opening a thread are posted in it, so their channel is the thread
+        let mut thread_id = thread_id;
+        if thread_id.is_none() && self.config.thread_per_incident {
+            if let Some(channel_id) = message["channel_id"].as_str() {
+                self.remember_thread(&fingerprint, channel_id.to_string());
+                thread_id = Some(channel_id.to_string());
+            }
+        }
+
+        // The rest of a split message follows without mentions
+        for part in parts {
+            let mut payload = json!({
+                "content": part,
+                "allowed_mentions": { "parse": [] },
+            });
+            if let Some(username) = &self.config.username {
+                payload["username"] = json!(username);
+            }
+            if let Some(avatar_url) = &self.config.avatar_url {
+                payload["avatar_url"] = json!(avatar_url);
+            }
+            self.execute(&payload, thread_id.as_deref(), None).await?;
+        }
+
+        info!("Discord message sent successfully");
+        Ok(provider_message_id(&message, "/id"))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await.map(|_| ())
+    }
+}
+
+impl MattermostChannel {
+    /// Create a new Mattermost channel.
+    pub fn new(config: MattermostConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            template_engine: TemplateEngine::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for MattermostChannel {
+    fn name(&self) -> &str {
+        "mattermost"
+    }
+
+    fn rate_limit_key(&self, _alert: &Alert) -> String {
+        self.config.webhook_url.clone()
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let text = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            self.template_engine.render_default_slack_template(alert)?
+        };
+
+        let mut payload = json!({
+            "text": truncate_at_boundary(&text, MATTERMOST_TEXT_MAX_LENGTH),
+            "attachments": [slack_attachment(alert)],
+        });
+
+        if let Some(channel) = &self.config.channel {
+            payload["channel"] = json!(channel);
+        }
+
+        if let Some(username) = &self.config.username {
+            payload["username"] = json!(username);
+        }
+
+        if let Some(icon_url) = &self.config.icon_url {
+            payload["icon_url"] = json!(icon_url);
+        }
+
         let response = self
-            .client
-            .post(&self.config.webhook_url)
+            .config
+            .auth
+            .apply(self.client.post(&self.config.webhook_url))
             .json(&payload)
             .send()
             .await?;
@@ -507,13 +1410,140 @@ impl NotificationChannel for DiscordChannel {
         if !response.status().is_success() {
             let error_text = response.text().await?;
             return Err(NotifierError::Generic(format!(
-                "Discord webhook failed: {}",
+                "Mattermost webhook failed: {}",
                 error_text
             )));
         }
 
-        info!("Discord message sent successfully");
-        Ok(())
+        info!("Mattermost message sent successfully");
+        Ok(None)
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await.map(|_| ())
+    }
+}
+
+impl PagerDutyChannel {
+    /// Create a new PagerDuty channel.
+    pub fn new(config: PagerDutyConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// PagerDuty severity for an alert, from the configured mapping or the default one.
+    fn severity(&self, alert: &Alert) -> String {
+        if let Some(severity) = self.config.severity_mapping.get(alert.severity.as_str()) {
+            return severity.clone();
+        }
+
+        match alert.severity {
+            watchtower_engine::AlertSeverity::Critical => "critical",
+            watchtower_engine::AlertSeverity::High => "error",
+            watchtower_engine::AlertSeverity::Medium => "warning",
+            watchtower_engine::AlertSeverity::Low | watchtower_engine::AlertSeverity::Info => {
+                "info"
+            }
+        }
+        .to_string()
+    }
+
+    /// Build an Events API v2 payload. Alerts are deduplicated into one
+    /// incident per fingerprint, which is also the key resolve events target.
+    fn event_payload(&self, alert: &Alert, action: &str) -> Value {
+        let mut payload = json!({
+            "routing_key": self.config.routing_key,
+            "event_action": action,
+            "dedup_key": alert.fingerprint(),
+        });
+
+        if action == "trigger" {
+            payload["payload"] = json!({
+                "summary": format!("[{}] {}", alert.rule_name, alert.message),
+                "source": self.config.source,
+                "severity": self.severity(alert),
+                "timestamp": alert.timestamp.to_rfc3339(),
+                "component": alert.program_name,
+                "group": alert.rule_name,
+                "class": alert.severity.as_str(),
+                "custom_details": {
+                    "alert_id": alert.id,
+                    "program_id": alert.program_id.to_string(),
+                    "confidence": alert.confidence,
+                    "tags": alert.tags,
+                    "suggested_actions": alert.suggested_actions,
+                    "metadata": alert.metadata,
+                },
+            });
+        }
+
+        payload
+    }
+
+    /// Post an event to the Events API, returning the response body.
+    async fn send_event(&self, payload: &Value) -> NotifierResult<Value> {
+        let response = self
+            .client
+            .post(&self.config.events_url)
+            .json(payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "PagerDuty API error: {}",
+                error_text
+            )));
+        }
+
+        Ok(response.json().await.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for PagerDutyChannel {
+    fn name(&self) -> &str {
+        "pagerduty"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        _template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let body = self
+            .send_event(&self.event_payload(alert, "trigger"))
+            .await?;
+
+        info!("PagerDuty event sent successfully");
+        Ok(provider_message_id(&body, "/dedup_key"))
     }
 
     async fn test(&self) -> NotifierResult<()> {
@@ -532,8 +1562,1446 @@ impl NotificationChannel for DiscordChannel {
             timestamp: chrono::Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
-        self.send(&test_alert, &test_data).await
+        self.send(&test_alert, &test_data).await?;
+        self.resolve(&test_alert).await
+    }
+
+    async fn resolve(&self, alert: &Alert) -> NotifierResult<()> {
+        self.send_event(&self.event_payload(alert, "resolve"))
+            .await?;
+
+        info!("PagerDuty incident resolved for {}", alert.fingerprint());
+        Ok(())
+    }
+}
+
+impl GrafanaOnCallChannel {
+    /// Create a new Grafana OnCall channel.
+    pub fn new(config: GrafanaOnCallConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            template_engine: TemplateEngine::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Build a formatted webhook payload. OnCall groups alerts into one alert
+    /// group per `alert_uid` (the fingerprint) and resolves the group when a
+    /// payload with the `ok` state arrives.
+    fn payload(&self, alert: &Alert, message: &str, state: &str) -> Value {
+        let links = self.template_engine.explorer_links(alert);
+        let mut payload = json!({
+            "alert_uid": alert.fingerprint(),
+            "title": format!(
+                "[{}] {}: {}",
+                alert.severity.as_str().to_uppercase(),
+                alert.rule_name,
+                alert.program_name
+            ),
+            "state": state,
+            "message": message,
+            "severity": alert.severity.as_str(),
+            "alert_id": alert.id,
+            "program_id": alert.program_id.to_string(),
+            "tags": alert.tags,
+        });
+
+        if let Some(link) = links.get("transaction").or(links.get("program")) {
+            payload["link_to_upstream_details"] = link.clone();
+        }
+
+        payload
+    }
+
+    /// Post a payload to the integration.
+    async fn post(&self, payload: &Value) -> NotifierResult<()> {
+        let response = self
+            .client
+            .post(&self.config.webhook_url)
+            .json(payload)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "Grafana OnCall API error: {}",
+                error_text
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for GrafanaOnCallChannel {
+    fn name(&self) -> &str {
+        "grafana_oncall"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let message = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            self.template_engine
+                .render_default_email_text_template(alert)?
+        };
+
+        self.post(&self.payload(alert, &message, "alerting"))
+            .await?;
+
+        info!("Grafana OnCall alert sent successfully");
+        Ok(Some(alert.fingerprint()))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await?;
+        self.resolve(&test_alert).await
+    }
+
+    async fn resolve(&self, alert: &Alert) -> NotifierResult<()> {
+        let message = format!("Resolved: {}", alert.message);
+        self.post(&self.payload(alert, &message, "ok")).await?;
+
+        info!(
+            "Grafana OnCall alert group resolved for {}",
+            alert.fingerprint()
+        );
+        Ok(())
+    }
+}
+
+/// Emergency priority, repeated until acknowledged.
+const PUSHOVER_EMERGENCY_PRIORITY: i8 = 2;
+
+/// Maximum message length accepted by the Pushover API.
+const PUSHOVER_MAX_MESSAGE_LENGTH: usize = 1024;
+
+impl PushoverChannel {
+    /// Create a new Pushover channel.
+    pub fn new(config: PushoverConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            template_engine: TemplateEngine::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Pushover priority for an alert, from the configured mapping or the default one.
+    fn priority(&self, alert: &Alert) -> i8 {
+        if let Some(priority) = self.config.priorities.get(alert.severity.as_str()) {
+            return *priority;
+        }
+
+        match alert.severity {
+            AlertSeverity::Critical => PUSHOVER_EMERGENCY_PRIORITY,
+            AlertSeverity::High => 1,
+            AlertSeverity::Medium => 0,
+            AlertSeverity::Low => -1,
+            AlertSeverity::Info => -2,
+        }
+    }
+
+    /// Form parameters of a message request.
+    fn params(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Vec<(&'static str, String)>> {
+        let message = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            format!("{} ({})", alert.message, alert.program_name)
+        };
+
+        let priority = self.priority(alert);
+        let mut params = vec![
+            ("token", self.config.app_token.clone()),
+            ("user", self.config.user_key.clone()),
+            (
+                "title",
+                format!(
+                    "[{}] {}",
+                    alert.severity.as_str().to_uppercase(),
+                    alert.rule_name
+                ),
+            ),
+            (
+                "message",
+                truncate_message(&message, PUSHOVER_MAX_MESSAGE_LENGTH),
+            ),
+            ("priority", priority.to_string()),
+            ("timestamp", alert.timestamp.timestamp().to_string()),
+        ];
+
+        if priority == PUSHOVER_EMERGENCY_PRIORITY {
+            params.push(("retry", self.config.emergency_retry_seconds.to_string()));
+            params.push(("expire", self.config.emergency_expire_seconds.to_string()));
+        }
+
+        if let Some(sound) = self.config.sounds.get(alert.severity.as_str()) {
+            params.push(("sound", sound.clone()));
+        }
+
+        if !self.config.devices.is_empty() {
+            params.push(("device", self.config.devices.join(",")));
+        }
+
+        Ok(params)
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for PushoverChannel {
+    fn name(&self) -> &str {
+        "pushover"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let params = self.params(alert, template_data)?;
+
+        let response = self
+            .client
+            .post("https://api.pushover.net/1/messages.json")
+            .form(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "Pushover API error: {}",
+                error_text
+            )));
+        }
+
+        let body: Value = response.json().await.unwrap_or_default();
+
+        info!("Pushover notification sent successfully");
+        Ok(provider_message_id(&body, "/request"))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await.map(|_| ())
+    }
+}
+
+impl NtfyChannel {
+    /// Create a new ntfy channel.
+    pub fn new(config: NtfyConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            template_engine: TemplateEngine::new(),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// ntfy priority (1 = min to 5 = urgent) for an alert.
+    fn priority(alert: &Alert) -> u8 {
+        match alert.severity {
+            AlertSeverity::Critical => 5,
+            AlertSeverity::High => 4,
+            AlertSeverity::Medium => 3,
+            AlertSeverity::Low => 2,
+            AlertSeverity::Info => 1,
+        }
+    }
+
+    /// ntfy tags for an alert: a severity emoji followed by the alert's own tags.
+    fn tags(alert: &Alert) -> String {
+        let emoji = match alert.severity {
+            AlertSeverity::Critical => "rotating_light",
+            AlertSeverity::High => "warning",
+            AlertSeverity::Medium => "large_orange_diamond",
+            AlertSeverity::Low => "information_source",
+            AlertSeverity::Info => "speech_balloon",
+        };
+
+        std::iter::once(emoji)
+            .chain(alert.tags.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for NtfyChannel {
+    fn name(&self) -> &str {
+        "ntfy"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let message = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            format!(
+                "{}\nProgram: {}\nConfidence: {:.1}%",
+                alert.message,
+                alert.program_name,
+                alert.confidence * 100.0
+            )
+        };
+
+        let mut request = self
+            .client
+            .post(&self.config.topic_url)
+            .header(
+                "Title",
+                format!(
+                    "[{}] {}",
+                    alert.severity.as_str().to_uppercase(),
+                    alert.rule_name
+                ),
+            )
+            .header("Priority", Self::priority(alert).to_string())
+            .header("Tags", Self::tags(alert))
+            .body(message);
+
+        if let Some(token) = &self.config.access_token {
+            request = request.bearer_auth(token);
+        } else if let Some(username) = &self.config.username {
+            request = request.basic_auth(username, self.config.password.as_ref());
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "ntfy publish failed: {}",
+                error_text
+            )));
+        }
+
+        let body: Value = response.json().await.unwrap_or_default();
+
+        info!("ntfy notification sent successfully");
+        Ok(provider_message_id(&body, "/id"))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await.map(|_| ())
+    }
+}
+
+impl SnsChannel {
+    /// Create a new SNS channel, resolving credentials from the AWS provider chain.
+    pub async fn new(config: SnsConfig) -> Self {
+        let mut loader = aws_config::from_env();
+        if let Some(region) = &config.region {
+            loader = loader.region(aws_config::Region::new(region.clone()));
+        }
+        if let Some(profile) = &config.profile {
+            loader = loader.profile_name(profile);
+        }
+
+        let client = aws_sdk_sns::Client::new(&loader.load().await);
+
+        Self { config, client }
+    }
+
+    /// String message attribute, usable in SNS subscription filter policies.
+    fn attribute(value: &str) -> NotifierResult<MessageAttributeValue> {
+        MessageAttributeValue::builder()
+            .data_type("String")
+            .string_value(value)
+            .build()
+            .map_err(|e| NotifierError::MessageFormat(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for SnsChannel {
+    fn name(&self) -> &str {
+        "sns"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        _template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        // SNS subjects are limited to 100 characters
+        let subject = truncate_message(
+            &format!(
+                "[Watchtower] {} Alert: {}",
+                alert.severity.as_str().to_uppercase(),
+                alert.rule_name
+            ),
+            100,
+        );
+
+        let mut request = self
+            .client
+            .publish()
+            .topic_arn(&self.config.topic_arn)
+            .subject(subject)
+            .message(serde_json::to_string(alert)?)
+            .message_attributes("severity", Self::attribute(alert.severity.as_str())?)
+            .message_attributes("rule_name", Self::attribute(&alert.rule_name)?)
+            .message_attributes(
+                "program_id",
+                Self::attribute(&alert.program_id.to_string())?,
+            );
+
+        // FIFO topics order and deduplicate alerts per fingerprint
+        if self.config.topic_arn.ends_with(".fifo") {
+            request = request
+                .message_group_id(alert.fingerprint())
+                .message_deduplication_id(&alert.id);
+        }
+
+        let output = request.send().await.map_err(|e| {
+            NotifierError::Generic(format!("SNS publish failed: {}", DisplayErrorContext(e)))
+        })?;
+
+        info!("SNS message published successfully");
+        Ok(output.message_id().map(str::to_string))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await.map(|_| ())
+    }
+}
+
+impl SmsChannel {
+    /// Create a new SMS channel.
+    pub fn new(config: SmsConfig) -> Self {
+        let min_severity = parse_severity(&config.min_severity).unwrap_or(AlertSeverity::Critical);
+        let per_hour = std::num::NonZeroU32::new(config.max_messages_per_number_per_hour)
+            .unwrap_or(std::num::NonZeroU32::new(10).unwrap());
+
+        Self {
+            config,
+            client: Client::new(),
+            template_engine: TemplateEngine::new(),
+            min_severity,
+            number_limiter: RateLimiter::keyed(Quota::per_hour(per_hour)),
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Render the message text, truncated to the configured length.
+    fn message(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<String> {
+        let text = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            format!(
+                "[{}] {} on {}: {}",
+                alert.severity.as_str().to_uppercase(),
+                alert.rule_name,
+                alert.program_name,
+                alert.message
+            )
+        };
+
+        Ok(truncate_message(&text, self.config.max_length))
+    }
+}
+
+impl SignalChannel {
+    /// Create a new Signal channel.
+    pub fn new(config: SignalConfig) -> Self {
+        let min_severity = parse_severity(&config.min_severity).unwrap_or(AlertSeverity::Critical);
+
+        Self {
+            config,
+            client: Client::new(),
+            template_engine: TemplateEngine::new(),
+            min_severity,
+        }
+    }
+
+    /// Send requests with a configured HTTP client (proxy, CA certificates, timeouts).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Render messages with a shared template engine, e.g. one with user templates.
+    pub fn with_template_engine(mut self, template_engine: TemplateEngine) -> Self {
+        self.template_engine = template_engine;
+        self
+    }
+
+    /// Build a `/v2/send` request body.
+    fn payload(&self, message: &str) -> Value {
+        json!({
+            "message": message,
+            "number": self.config.number,
+            "recipients": self.config.recipients,
+        })
+    }
+}
+
+/// Truncate a message to at most `max_chars` characters, marking the cut.
+fn truncate_message(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[async_trait]
+impl NotificationChannel for SignalChannel {
+    fn name(&self) -> &str {
+        "signal"
+    }
+
+    fn accepts(&self, alert: &Alert) -> bool {
+        alert.severity >= self.min_severity
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let message = if let Some(template) = &self.config.message_template {
+            self.template_engine
+                .render_template(template, template_data)?
+        } else {
+            self.template_engine
+                .render_default_email_text_template(alert)?
+        };
+
+        let url = format!("{}/v2/send", self.config.api_url.trim_end_matches('/'));
+        let response = self
+            .config
+            .auth
+            .apply(self.client.post(&url))
+            .json(&self.payload(&message))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "Signal API error: {}",
+                error_text
+            )));
+        }
+
+        let body: Value = response.json().await.unwrap_or_default();
+
+        info!("Signal message sent successfully");
+        Ok(provider_message_id(&body, "/timestamp"))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            // Critical so the test passes the default severity filter
+            severity: watchtower_engine::AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await?;
+        Ok(())
+    }
+}
+
+/// Maximum length of a Telegram message.
+const TELEGRAM_MESSAGE_MAX_LENGTH: usize = 4096;
+
+/// Maximum length of a Slack message text.
+const SLACK_TEXT_MAX_LENGTH: usize = 40000;
+
+/// Maximum length of a Mattermost post.
+const MATTERMOST_TEXT_MAX_LENGTH: usize = 16383;
+
+/// Maximum length of a Discord message.
+const DISCORD_CONTENT_MAX_LENGTH: usize = 2000;
+
+/// Maximum length of a Discord embed description.
+const DISCORD_EMBED_DESCRIPTION_MAX_LENGTH: usize = 4096;
+
+/// Fit a message into `max_chars` characters, either split into several
+/// messages at line breaks (or spaces) or truncated to the first of them.
+fn fit_message(text: &str, max_chars: usize, split: bool) -> Vec<String> {
+    if !split {
+        return vec![truncate_at_boundary(text, max_chars)];
+    }
+
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > max_chars {
+        let cut = split_point(rest, max_chars);
+        parts.push(rest[..cut].trim_end().to_string());
+        rest = rest[cut..].trim_start_matches(['\n', ' ']);
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(rest.to_string());
+    }
+
+    parts
+}
+
+/// Truncate a message to at most `max_chars` characters at a line break or
+/// space where possible, marking the cut.
+fn truncate_at_boundary(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let cut = split_point(text, max_chars.saturating_sub(1));
+    format!("{}…", text[..cut].trim_end())
+}
+
+/// Byte offset at which to cut `text` to at most `max_chars` characters,
+/// preferring the last line break and then the last space in the second
+/// half of that range.
+fn split_point(text: &str, max_chars: usize) -> usize {
+    let limit = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(i, _)| i);
+    let head = &text[..limit];
+
+    head.rfind('\n')
+        .filter(|&i| i >= limit / 2)
+        .or_else(|| head.rfind(' ').filter(|&i| i >= limit / 2))
+        .unwrap_or(limit)
+}
+
+/// Message identifier at `pointer` in a provider response, if present.
+fn provider_message_id(body: &Value, pointer: &str) -> Option<String> {
+    match body.pointer(pointer)? {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for SmsChannel {
+    fn name(&self) -> &str {
+        "sms"
+    }
+
+    fn accepts(&self, alert: &Alert) -> bool {
+        alert.severity >= self.min_severity
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let body = self.message(alert, template_data)?;
+        let url = format!(
+            "{}/2010-04-01/Accounts/{}/Messages.json",
+            self.config.api_url.trim_end_matches('/'),
+            self.config.account_sid
+        );
+
+        let mut message_sids = Vec::new();
+        for to_number in &self.config.to_numbers {
+            if self.number_limiter.check_key(to_number).is_err() {
+                warn!("SMS rate limit exceeded for {}", to_number);
+                continue;
+            }
+
+            let params = [
+                ("To", to_number.as_str()),
+                ("From", self.config.from_number.as_str()),
+                ("Body", body.as_str()),
+            ];
+
+            let response = self
+                .client
+                .post(&url)
+                .basic_auth(&self.config.account_sid, Some(&self.config.auth_token))
+                .form(&params)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let error_text = response.text().await?;
+                return Err(NotifierError::Generic(format!(
+                    "Twilio API error: {}",
+                    error_text
+                )));
+            }
+
+            let body: Value = response.json().await.unwrap_or_default();
+            message_sids.extend(provider_message_id(&body, "/sid"));
+
+            info!("SMS sent successfully to {}", to_number);
+        }
+
+        Ok(Some(message_sids.join(",")).filter(|sids| !sids.is_empty()))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        let test_data = HashMap::new();
+        let test_alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "This is a test alert".to_string(),
+            severity: watchtower_engine::AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: vec!["This is a test".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        self.send(&test_alert, &test_data).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramChat;
+    use crate::http::WebhookAuthConfig;
+
+    #[tokio::test]
+    async fn test_email_signed_with_dkim() {
+        let channel = EmailChannel::new(EmailConfig {
+            smtp_server: "smtp.example.com".to_string(),
+            smtp_port: 587,
+            username: "alerts@example.com".to_string(),
+            password: "password".to_string(),
+            oauth2: None,
+            from_address: "alerts@example.com".to_string(),
+            from_name: Some("Watchtower".to_string()),
+            to_addresses: vec!["ops@example.com".to_string()],
+            use_tls: true,
+            subject_template: None,
+            body_template: None,
+            text_template: None,
+            dkim: Some(crate::config::DkimConfig {
+                domain: "example.com".to_string(),
+                selector: "watchtower".to_string(),
+                private_key: None,
+                private_key_path: Some(
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/dkim_rsa.pem").into(),
+                ),
+                algorithm: "rsa".to_string(),
+            }),
+            attach_chart: false,
+        })
+        .unwrap();
+
+        let body = EmailBody::Alternative {
+            text: "Large transaction".to_string(),
+            html: "<p>Large transaction</p>".to_string(),
+        };
+        let email = channel
+            .build_message(
+                &channel.from_mailbox().unwrap(),
+                "ops@example.com",
+                "Alert",
+                &body,
+                None,
+            )
+            .unwrap();
+        let formatted = String::from_utf8(email.formatted()).unwrap();
+        assert!(formatted.contains("DKIM-Signature: v=1; a=rsa-sha256;"));
+        assert!(formatted.contains("multipart/alternative"));
+    }
+
+    #[test]
+    fn test_pagerduty_payload() {
+        let channel = PagerDutyChannel::new(PagerDutyConfig {
+            routing_key: "routing-key".to_string(),
+            events_url: "https://events.pagerduty.com/v2/enqueue".to_string(),
+            source: "watchtower".to_string(),
+            severity_mapping: HashMap::from([("high".to_string(), "critical".to_string())]),
+        });
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "liquidity_drop".to_string(),
+            message: "Liquidity dropped 40%".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let trigger = channel.event_payload(&alert, "trigger");
+        assert_eq!(trigger["dedup_key"], alert.fingerprint());
+        assert_eq!(trigger["payload"]["severity"], "critical");
+        assert_eq!(trigger["payload"]["component"], "Orca");
+
+        let medium = Alert {
+            severity: AlertSeverity::Medium,
+            ..alert.clone()
+        };
+        assert_eq!(channel.severity(&medium), "warning");
+
+        // Resolve events only carry the dedup key
+        let resolve = channel.event_payload(&alert, "resolve");
+        assert_eq!(resolve["event_action"], "resolve");
+        assert_eq!(resolve["dedup_key"], trigger["dedup_key"]);
+        assert!(resolve.get("payload").is_none());
+    }
+
+    #[test]
+    fn test_grafana_oncall_payload() {
+        let channel = GrafanaOnCallChannel::new(GrafanaOnCallConfig {
+            webhook_url: "https://oncall.example.com/integrations/v1/formatted_webhook/abc/"
+                .to_string(),
+            message_template: None,
+        });
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "liquidity_drop".to_string(),
+            message: "Liquidity dropped 40%".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: vec!["defi".to_string()],
+        };
+
+        let firing = channel.payload(&alert, "Liquidity dropped 40%", "alerting");
+        assert_eq!(firing["alert_uid"], alert.fingerprint());
+        assert_eq!(firing["title"], "[HIGH] liquidity_drop: Orca");
+        assert_eq!(
+            firing["link_to_upstream_details"],
+            format!("https://solscan.io/account/{}", alert.program_id)
+        );
+
+        // Repeats and the resolution land in the same alert group
+        let resolved = channel.payload(&alert, "Resolved", "ok");
+        assert_eq!(resolved["state"], "ok");
+        assert_eq!(resolved["alert_uid"], firing["alert_uid"]);
+    }
+
+    #[test]
+    fn test_sms_severity_and_truncation() {
+        let channel = SmsChannel::new(SmsConfig {
+            account_sid: "AC123".to_string(),
+            auth_token: "token".to_string(),
+            from_number: "+14155550100".to_string(),
+            to_numbers: vec!["+14155550101".to_string()],
+            min_severity: "critical".to_string(),
+            max_messages_per_number_per_hour: 1,
+            max_length: 40,
+            message_template: None,
+            api_url: "https://api.twilio.com".to_string(),
+        });
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "oracle_deviation".to_string(),
+            message: "Price deviated 25% from the reference oracle".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Pyth".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        assert!(channel.accepts(&alert));
+        assert!(!channel.accepts(&Alert {
+            severity: AlertSeverity::High,
+            ..alert.clone()
+        }));
+
+        let message = channel.message(&alert, &HashMap::new()).unwrap();
+        assert_eq!(message.chars().count(), 40);
+        assert!(message.starts_with("[CRITICAL] oracle_deviation on Pyth"));
+        assert!(message.ends_with('…'));
+
+        // The per-number quota is shared across sends
+        let number = "+14155550101".to_string();
+        assert!(channel.number_limiter.check_key(&number).is_ok());
+        assert!(channel.number_limiter.check_key(&number).is_err());
+    }
+
+    #[test]
+    fn test_signal_severity_and_payload() {
+        let channel = SignalChannel::new(SignalConfig {
+            api_url: "http://signal-api:8080".to_string(),
+            number: "+14155550100".to_string(),
+            recipients: vec!["+14155550101".to_string(), "group.b25jYWxs".to_string()],
+            min_severity: "critical".to_string(),
+            message_template: None,
+            auth: WebhookAuthConfig::default(),
+        });
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "oracle_deviation".to_string(),
+            message: "Price deviated 25% from the reference oracle".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Pyth".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        // Only critical alerts by default
+        assert!(!channel.accepts(&alert));
+        assert!(channel.accepts(&Alert {
+            severity: AlertSeverity::Critical,
+            ..alert.clone()
+        }));
+
+        let payload = channel.payload("Price deviated");
+        assert_eq!(payload["number"], "+14155550100");
+        assert_eq!(payload["recipients"][1], "group.b25jYWxs");
+        assert_eq!(payload["message"], "Price deviated");
+    }
+
+    #[test]
+    fn test_long_messages_split_or_truncated() {
+        let text = format!("{}\n{}\n{}", "a".repeat(30), "b".repeat(30), "c".repeat(30));
+
+        // Truncated at the last line break that fits, keeping whole lines
+        let truncated = fit_message(&text, 70, false);
+        assert_eq!(
+            truncated,
+            vec![format!("{}\n{}…", "a".repeat(30), "b".repeat(30))]
+        );
+
+        // Split at line breaks, every part within the limit
+        let parts = fit_message(&text, 70, true);
+        assert_eq!(
+            parts,
+            vec![
+                format!("{}\n{}", "a".repeat(30), "b".repeat(30)),
+                "c".repeat(30)
+            ]
+        );
+
+        // Lines longer than the limit are split at spaces, then anywhere
+        let words = "word ".repeat(10);
+        assert!(fit_message(&words, 12, true)
+            .iter()
+            .all(|part| part.chars().count() <= 12 && !part.starts_with(' ')));
+        let parts = fit_message(&"é".repeat(25), 10, true);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[2], "é".repeat(5));
+
+        assert_eq!(fit_message("short", 70, true), vec!["short".to_string()]);
+    }
+
+    #[test]
+    fn test_pushover_priority_mapping() {
+        let channel = PushoverChannel::new(PushoverConfig {
+            app_token: "app-token".to_string(),
+            user_key: "user-key".to_string(),
+            devices: vec!["oncall-phone".to_string(), "tablet".to_string()],
+            priorities: HashMap::from([("high".to_string(), 0)]),
+            sounds: HashMap::from([("critical".to_string(), "siren".to_string())]),
+            emergency_retry_seconds: 60,
+            emergency_expire_seconds: 3600,
+            message_template: None,
+        });
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_withdrawal".to_string(),
+            message: "Withdrawal of 1M USDC".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Vault".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        // Critical alerts are emergencies retried until acknowledged
+        let params: HashMap<_, _> = channel
+            .params(&alert, &HashMap::new())
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(params["priority"], "2");
+        assert_eq!(params["retry"], "60");
+        assert_eq!(params["expire"], "3600");
+        assert_eq!(params["sound"], "siren");
+        assert_eq!(params["device"], "oncall-phone,tablet");
+
+        // Configured priorities override the default mapping
+        let high: HashMap<_, _> = channel
+            .params(
+                &Alert {
+                    severity: AlertSeverity::High,
+                    ..alert.clone()
+                },
+                &HashMap::new(),
+            )
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(high["priority"], "0");
+        assert!(!high.contains_key("retry"));
+        assert!(!high.contains_key("sound"));
+    }
+
+    #[test]
+    fn test_ntfy_priority_and_tags() {
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "authority_change".to_string(),
+            message: "Mint authority changed".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Token".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 1.0,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: vec!["team:treasury".to_string()],
+        };
+
+        assert_eq!(NtfyChannel::priority(&alert), 5);
+        assert_eq!(NtfyChannel::tags(&alert), "rotating_light,team:treasury");
+
+        let info = Alert {
+            severity: AlertSeverity::Info,
+            tags: Vec::new(),
+            ..alert
+        };
+        assert_eq!(NtfyChannel::priority(&info), 1);
+        assert_eq!(NtfyChannel::tags(&info), "speech_balloon");
+    }
+
+    #[test]
+    fn test_slack_blocks_carry_alert_id() {
+        let alert = Alert {
+            id: "alert-42".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Transfer of 5M USDC".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Vault".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let blocks = slack_blocks(&alert, &"x".repeat(4000));
+        assert_eq!(
+            blocks[0]["text"]["text"].as_str().unwrap().chars().count(),
+            SLACK_SECTION_MAX_LENGTH
+        );
+        let buttons = blocks[1]["elements"].as_array().unwrap();
+        assert_eq!(buttons[0]["action_id"], "acknowledge");
+        assert_eq!(buttons[1]["action_id"], "resolve");
+        assert!(buttons.iter().all(|button| button["value"] == "alert-42"));
+    }
+
+    #[test]
+    fn test_telegram_destinations_by_severity() {
+        let channel = TelegramChannel::new(TelegramConfig {
+            bot_token: "token".to_string(),
+            chat_id: -100,
+            chats: vec![
+                TelegramChat {
+                    chat_id: -200,
+                    message_thread_id: None,
+                    severities: Some(vec!["critical".to_string(), "high".to_string()]),
+                },
+                TelegramChat {
+                    chat_id: -300,
+                    message_thread_id: Some(7),
+                    severities: Some(vec!["info".to_string()]),
+                },
+            ],
+            message_template: None,
+            parse_mode: "Markdown".to_string(),
+            disable_web_page_preview: false,
+            disable_notification: false,
+            split_long_messages: false,
+            commands: Default::default(),
+        });
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "liquidity_drop".to_string(),
+            message: "Liquidity dropped 40%".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        assert_eq!(channel.destinations(&alert), vec![(-200, None)]);
+
+        alert.severity = AlertSeverity::Info;
+        assert_eq!(channel.destinations(&alert), vec![(-300, Some(7))]);
+        assert_eq!(channel.rate_limit_key(&alert), "-300/7");
+
+        // Severities no chat takes go to the default chat
+        alert.severity = AlertSeverity::Medium;
+        assert_eq!(channel.destinations(&alert), vec![(-100, None)]);
+    }
+
+    #[test]
+    fn test_program_routes_override_recipients() {
+        let routes = vec![ProgramRoute {
+            programs: vec!["Orca".to_string()],
+            email_to: Vec::new(),
+            slack_channel: Some("#amm-squad".to_string()),
+            telegram_chats: vec![TelegramChat {
+                chat_id: -400,
+                message_thread_id: None,
+                severities: None,
+            }],
+        }];
+        let telegram = TelegramChannel::new(TelegramConfig {
+            bot_token: "token".to_string(),
+            chat_id: -100,
+            chats: Vec::new(),
+            message_template: None,
+            parse_mode: "Markdown".to_string(),
+            disable_web_page_preview: false,
+            disable_notification: false,
+            split_long_messages: false,
+            commands: Default::default(),
+        })
+        .with_program_routes(routes.clone());
+        let slack = SlackChannel::new(SlackConfig {
+            webhook_url: String::new(),
+            auth: WebhookAuthConfig::default(),
+            bot_token: Some("xoxb-token".to_string()),
+            channel: Some("#alerts".to_string()),
+            username: None,
+            icon: None,
+            message_template: None,
+            custom_fields: None,
+            interactive: false,
+            thread_related_alerts: true,
+            thread_window_seconds: 3600,
+            attach_chart: false,
+        })
+        .with_program_routes(routes);
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "liquidity_drop".to_string(),
+            message: "Liquidity dropped 40%".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        assert_eq!(telegram.destinations(&alert), vec![(-400, None)]);
+        assert_eq!(slack.rate_limit_key(&alert), "#amm-squad");
+
+        // Other programs keep the defaults
+        alert.program_name = "Raydium".to_string();
+        assert_eq!(telegram.destinations(&alert), vec![(-100, None)]);
+        assert_eq!(slack.rate_limit_key(&alert), "#alerts");
+    }
+
+    #[test]
+    fn test_discord_role_mentions_and_threads() {
+        let channel = DiscordChannel::new(DiscordConfig {
+            webhook_url: "https://discord.com/api/webhooks/1/token".to_string(),
+            auth: WebhookAuthConfig::default(),
+            username: None,
+            avatar_url: None,
+            message_template: None,
+            use_embeds: true,
+            thread_per_incident: true,
+            thread_window_seconds: 3600,
+            critical_role_ids: vec!["123".to_string(), "456".to_string()],
+            split_long_messages: false,
+            attach_chart: false,
+        });
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "liquidity_drop".to_string(),
+            message: "Liquidity dropped 40% @everyone".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let (mentions, allowed_mentions) = channel.mentions(&alert);
+        assert_eq!(mentions.as_deref(), Some("<@&123> <@&456>"));
+        assert_eq!(
+            allowed_mentions,
+            json!({ "parse": [], "roles": ["123", "456"] })
+        );
+
+        // Other severities ping nobody
+        alert.severity = AlertSeverity::High;
+        assert_eq!(channel.mentions(&alert), (None, json!({ "parse": [] })));
+
+        assert_eq!(channel.thread_id(&alert.fingerprint()), None);
+        channel.remember_thread(&alert.fingerprint(), "999".to_string());
+        assert_eq!(
+            channel.thread_id(&alert.fingerprint()).as_deref(),
+            Some("999")
+        );
+    }
+
+    #[test]
+    fn test_slack_threads_per_fingerprint() {
+        let config = SlackConfig {
+            webhook_url: String::new(),
+            auth: WebhookAuthConfig::default(),
+            bot_token: Some("xoxb-token".to_string()),
+            channel: Some("#alerts".to_string()),
+            username: None,
+            icon: None,
+            message_template: None,
+            custom_fields: None,
+            interactive: false,
+            thread_related_alerts: true,
+            thread_window_seconds: 3600,
+            attach_chart: false,
+        };
+        let channel = SlackChannel::new(config.clone());
+
+        assert_eq!(channel.thread_ts("rule:program"), None);
+        channel.remember_thread("rule:program", "1700000000.000100".to_string());
+        assert_eq!(
+            channel.thread_ts("rule:program").as_deref(),
+            Some("1700000000.000100")
+        );
+        assert_eq!(channel.thread_ts("other:program"), None);
+
+        // Threads expire after the window
+        let expired = SlackChannel::new(SlackConfig {
+            thread_window_seconds: 0,
+            ..config
+        });
+        expired.remember_thread("rule:program", "1700000000.000100".to_string());
+        assert_eq!(expired.thread_ts("rule:program"), None);
+    }
+
+    #[test]
+    fn test_provider_message_id() {
+        let telegram = json!({"ok": true, "result": {"message_id": 4711}});
+        assert_eq!(
+            provider_message_id(&telegram, "/result/message_id").as_deref(),
+            Some("4711")
+        );
+
+        let twilio = json!({"sid": "SM123", "status": "queued"});
+        assert_eq!(
+            provider_message_id(&twilio, "/sid").as_deref(),
+            Some("SM123")
+        );
+        assert_eq!(provider_message_id(&Value::Null, "/sid"), None);
     }
 }
@@ -0,0 +1,203 @@
+//! Sparkline charts of the metric window behind an alert.
+
+use crate::error::{NotifierError, NotifierResult};
+use watchtower_engine::Alert;
+
+/// Chart width in pixels.
+pub const CHART_WIDTH: u32 = 400;
+
+/// Chart height in pixels.
+pub const CHART_HEIGHT: u32 = 100;
+
+/// File name of chart attachments.
+pub const CHART_FILE_NAME: &str = "chart.png";
+
+/// Blank margin around the plotted line.
+const CHART_PADDING: u32 = 6;
+
+/// Points of the `series` metadata (`[[unix seconds, value], ...]`) of an
+/// alert, if it carries at least two.
+pub fn alert_series(alert: &Alert) -> Option<Vec<(i64, f64)>> {
+    let points: Vec<(i64, f64)> = alert
+        .metadata
+        .get("series")?
+        .as_array()?
+        .iter()
+        .filter_map(|point| Some((point.get(0)?.as_i64()?, point.get(1)?.as_f64()?)))
+        .filter(|(_, value)| value.is_finite())
+        .collect();
+
+    (points.len() >= 2).then_some(points)
+}
+
+/// PNG sparkline of an alert's metric window in its severity color, if it has one.
+pub fn alert_chart(alert: &Alert) -> Option<Vec<u8>> {
+    let points = alert_series(alert)?;
+    let color = parse_hex_color(alert.severity.color()).unwrap_or([0, 0, 0]);
+
+    match render_sparkline(&points, CHART_WIDTH, CHART_HEIGHT, color) {
+        Ok(png) => Some(png),
+        Err(e) => {
+            tracing::warn!("Failed to render chart for alert {}: {}", alert.id, e);
+            None
+        }
+    }
+}
+
+/// Render points as a line over a lightly tinted area on a white PNG.
+pub fn render_sparkline(
+    points: &[(i64, f64)],
+    width: u32,
+    height: u32,
+    color: [u8; 3],
+) -> NotifierResult<Vec<u8>> {
+    let mut canvas = Canvas::new(width, height);
+
+    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+        let (min, max) = points
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+                (min.min(*v), max.max(*v))
+            });
+        let time_span = (last.0 - first.0).max(1) as f64;
+        let plot_width = width.saturating_sub(2 * CHART_PADDING).max(1) as f64;
+        let plot_height = height.saturating_sub(2 * CHART_PADDING).max(1) as f64;
+
+        let to_pixel = |(ts, value): (i64, f64)| {
+            let x = CHART_PADDING as f64 + (ts - first.0) as f64 / time_span * plot_width;
+            // Flat series are drawn through the middle
+            let y_fraction = if max > min {
+                (value - min) / (max - min)
+            } else {
+                0.5
+            };
+            let y = CHART_PADDING as f64 + (1.0 - y_fraction) * plot_height;
+            (x.round() as i64, y.round() as i64)
+        };
+
+        let pixels: Vec<(i64, i64)> = points.iter().map(|p| to_pixel(*p)).collect();
+        let tint = color.map(|c| 255 - (255 - c) / 6);
+
+        for pair in pixels.windows(2) {
+            canvas.fill_below(pair[0], pair[1], tint);
+        }
+        for pair in pixels.windows(2) {
+            canvas.line(pair[0], pair[1], color);
+        }
+    }
+
+    canvas.to_png()
+}
+
+/// RGB pixel buffer.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![255; (width * height * 3) as usize],
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, color: [u8; 3]) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let offset = ((y as u32 * self.width + x as u32) * 3) as usize;
+        self.pixels[offset..offset + 3].copy_from_slice(&color);
+    }
+
+    /// Two pixel thick line between two points.
+    fn line(&mut self, from: (i64, i64), to: (i64, i64), color: [u8; 3]) {
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).max(1);
+        for step in 0..=steps {
+            let x = from.0 + (to.0 - from.0) * step / steps;
+            let y = from.1 + (to.1 - from.1) * step / steps;
+            self.set(x, y, color);
+            self.set(x, y + 1, color);
+        }
+    }
+
+    /// Fill the area between a segment and the bottom of the canvas.
+    fn fill_below(&mut self, from: (i64, i64), to: (i64, i64), color: [u8; 3]) {
+        let span = (to.0 - from.0).max(1);
+        for x in from.0..=to.0 {
+            let y = from.1 + (to.1 - from.1) * (x - from.0) / span;
+            for fill_y in y..self.height as i64 {
+                self.set(x, fill_y, color);
+            }
+        }
+    }
+
+    fn to_png(&self) -> NotifierResult<Vec<u8>> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| NotifierError::MessageFormat(format!("PNG encoding failed: {}", e)))?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(|e| NotifierError::MessageFormat(format!("PNG encoding failed: {}", e)))?;
+        writer
+            .finish()
+            .map_err(|e| NotifierError::MessageFormat(format!("PNG encoding failed: {}", e)))?;
+
+        Ok(png)
+    }
+}
+
+/// RGB components of a `#rrggbb` color.
+fn parse_hex_color(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+    let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([component(0)?, component(2)?, component(4)?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use watchtower_engine::AlertSeverity;
+
+    #[test]
+    fn test_alert_chart_from_series() {
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "tvl_drop".to_string(),
+            message: "TVL decreased by 40%".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.4,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        assert!(alert_chart(&alert).is_none());
+
+        alert.metadata.insert(
+            "series".to_string(),
+            serde_json::json!([[0, 1000.0], [10, 1500.0], [20, 900.0]]),
+        );
+        assert_eq!(alert_series(&alert).unwrap().len(), 3);
+
+        let png = alert_chart(&alert).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        assert_eq!(parse_hex_color("#fd7e14"), Some([0xfd, 0x7e, 0x14]));
+        assert_eq!(parse_hex_color("red"), None);
+    }
+}
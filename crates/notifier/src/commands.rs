@@ -0,0 +1,297 @@
+//! Telegram bot commands for operating the watchtower from chat.
+
+use crate::error::{NotifierError, NotifierResult};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Telegram bot command configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramCommandsConfig {
+    /// Whether the bot long-polls for commands
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Telegram user IDs allowed to issue commands; everyone else is ignored
+    #[serde(default)]
+    pub allowed_user_ids: Vec<i64>,
+
+    /// Seconds a getUpdates long poll waits for new messages
+    #[serde(default = "default_poll_timeout_seconds")]
+    pub poll_timeout_seconds: u64,
+}
+
+/// A command sent to the bot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BotCommand {
+    /// Summary of alerts, notifications and channel health
+    Status,
+
+    /// Unacknowledged active alerts
+    Alerts,
+
+    /// Acknowledge an alert
+    Ack { alert_id: String },
+
+    /// Stop notifying about a rule for a while
+    Mute {
+        rule: String,
+        duration: chrono::Duration,
+    },
+
+    /// Notify about a muted rule again
+    Unmute { rule: String },
+
+    /// List the commands
+    Help,
+}
+
+/// Text listing the supported commands.
+pub const BOT_COMMANDS_HELP: &str = "/status - alert and channel summary\n\
+/alerts - unacknowledged alerts\n\
+/ack <id> - acknowledge an alert\n\
+/mute <rule> <duration> - mute a rule, e.g. /mute large_transaction 1h\n\
+/unmute <rule> - unmute a rule";
+
+impl BotCommand {
+    /// Parse a message starting with `/`, returning a usage hint on errors.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts = text.split_whitespace();
+        // Commands in groups may be addressed as /command@bot_name
+        let command = parts
+            .next()
+            .unwrap_or_default()
+            .split('@')
+            .next()
+            .unwrap_or_default();
+        let args: Vec<&str> = parts.collect();
+
+        match (command, args.as_slice()) {
+            ("/status", []) => Ok(BotCommand::Status),
+            ("/alerts", []) => Ok(BotCommand::Alerts),
+            ("/ack", [alert_id]) => Ok(BotCommand::Ack {
+                alert_id: alert_id.to_string(),
+            }),
+            ("/ack", _) => Err("Usage: /ack <id>".to_string()),
+            ("/mute", [rule, duration]) => match parse_duration(duration) {
+                Some(duration) => Ok(BotCommand::Mute {
+                    rule: rule.to_string(),
+                    duration,
+                }),
+                None => Err(format!(
+                    "Invalid duration '{}', use e.g. 30m, 1h or 2d",
+                    duration
+                )),
+            },
+            ("/mute", _) => Err("Usage: /mute <rule> <duration>".to_string()),
+            ("/unmute", [rule]) => Ok(BotCommand::Unmute {
+                rule: rule.to_string(),
+            }),
+            ("/unmute", _) => Err("Usage: /unmute <rule>".to_string()),
+            ("/help" | "/start", _) => Ok(BotCommand::Help),
+            _ => Err(format!("Unknown command\n\n{}", BOT_COMMANDS_HELP)),
+        }
+    }
+}
+
+/// Parse a duration like `90s`, `30m`, `1h` or `2d`.
+pub fn parse_duration(text: &str) -> Option<chrono::Duration> {
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = text.split_at(split);
+    let amount: i64 = amount.parse().ok().filter(|amount| *amount > 0)?;
+
+    match unit {
+        "s" => Some(chrono::Duration::seconds(amount)),
+        "m" => Some(chrono::Duration::minutes(amount)),
+        "h" => Some(chrono::Duration::hours(amount)),
+        "d" => Some(chrono::Duration::days(amount)),
+        _ => None,
+    }
+}
+
+/// A text message received by the bot.
+#[derive(Debug, Clone)]
+pub struct BotMessage {
+    /// Chat the message was sent in
+    pub chat_id: i64,
+
+    /// Sender's user ID
+    pub user_id: i64,
+
+    /// Sender's username, if set
+    pub username: Option<String>,
+
+    /// Message text
+    pub text: String,
+}
+
+/// Long-polling client for the Telegram Bot API.
+pub struct TelegramBot {
+    client: Client,
+    bot_token: String,
+    poll_timeout: Duration,
+
+    /// Identifier of the next update to fetch
+    offset: i64,
+}
+
+impl TelegramBot {
+    /// Create a bot polling with the given long-poll timeout.
+    pub fn new(bot_token: String, poll_timeout: Duration) -> Self {
+        Self {
+            client: Client::new(),
+            bot_token,
+            poll_timeout,
+            offset: 0,
+        }
+    }
+
+    /// Send requests with a configured HTTP client.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Wait for new text messages, confirming all updates fetched before.
+    pub async fn poll(&mut self) -> NotifierResult<Vec<BotMessage>> {
+        let body: Value = self
+            .client
+            .get(self.url("getUpdates"))
+            .query(&[
+                ("offset", self.offset.to_string()),
+                ("timeout", self.poll_timeout.as_secs().to_string()),
+                ("allowed_updates", "[\"message\"]".to_string()),
+            ])
+            // Leave the request time to outlast the long poll
+            .timeout(self.poll_timeout + Duration::from_secs(10))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if body["ok"] != json!(true) {
+            return Err(NotifierError::Generic(format!(
+                "Telegram getUpdates failed: {}",
+                body["description"].as_str().unwrap_or("unknown error")
+            )));
+        }
+
+        let updates = body["result"].as_array().cloned().unwrap_or_default();
+        if let Some(last) = updates.iter().filter_map(|u| u["update_id"].as_i64()).max() {
+            self.offset = last + 1;
+        }
+
+        Ok(updates.iter().filter_map(parse_message).collect())
+    }
+
+    /// Reply in a chat.
+    pub async fn reply(&self, chat_id: i64, text: &str) -> NotifierResult<()> {
+        let response = self
+            .client
+            .post(self.url("sendMessage"))
+            .json(&json!({
+                "chat_id": chat_id,
+                "text": text,
+                "disable_web_page_preview": true,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Generic(format!(
+                "Telegram API error: {}",
+                error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn url(&self, method: &str) -> String {
+        format!("https://api.telegram.org/bot{}/{}", self.bot_token, method)
+    }
+}
+
+/// Text message carried by an update, if any.
+fn parse_message(update: &Value) -> Option<BotMessage> {
+    let message = &update["message"];
+    Some(BotMessage {
+        chat_id: message["chat"]["id"].as_i64()?,
+        user_id: message["from"]["id"].as_i64()?,
+        username: message["from"]["username"].as_str().map(str::to_string),
+        text: message["text"].as_str()?.to_string(),
+    })
+}
+
+impl Default for TelegramCommandsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_user_ids: Vec::new(),
+            poll_timeout_seconds: default_poll_timeout_seconds(),
+        }
+    }
+}
+
+// Default value functions
+fn default_poll_timeout_seconds() -> u64 {
+    30
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(BotCommand::parse("/status"), Ok(BotCommand::Status));
+        assert_eq!(
+            BotCommand::parse("/ack@watchtower_bot alert-1"),
+            Ok(BotCommand::Ack {
+                alert_id: "alert-1".to_string()
+            })
+        );
+        assert_eq!(
+            BotCommand::parse("/mute large_transaction 1h"),
+            Ok(BotCommand::Mute {
+                rule: "large_transaction".to_string(),
+                duration: chrono::Duration::hours(1),
+            })
+        );
+        assert!(BotCommand::parse("/mute large_transaction soon").is_err());
+        assert!(BotCommand::parse("/ack").is_err());
+        assert!(BotCommand::parse("/reboot").is_err());
+
+        assert_eq!(parse_duration("30m"), Some(chrono::Duration::minutes(30)));
+        assert_eq!(parse_duration("2d"), Some(chrono::Duration::days(2)));
+        assert_eq!(parse_duration("0h"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("5w"), None);
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let update = json!({
+            "update_id": 10,
+            "message": {
+                "chat": { "id": -100 },
+                "from": { "id": 42, "username": "operator" },
+                "text": "/status",
+            }
+        });
+
+        let message = parse_message(&update).unwrap();
+        assert_eq!(message.chat_id, -100);
+        assert_eq!(message.user_id, 42);
+        assert_eq!(message.username.as_deref(), Some("operator"));
+        assert_eq!(message.text, "/status");
+
+        // Updates without text (stickers, joins, ...) are skipped
+        assert!(
+            parse_message(&json!({ "update_id": 11, "message": { "chat": { "id": 1 } } }))
+                .is_none()
+        );
+    }
+}
@@ -1,7 +1,21 @@
 //! Configuration structures for notification channels.
 
+use crate::capture::CaptureConfig;
+use crate::commands::TelegramCommandsConfig;
+use crate::digest::DigestConfig;
+use crate::escalation::EscalationConfig;
+use crate::health::HealthCheckConfig;
+use crate::http::{HttpConfig, WebhookAuthConfig};
+use crate::oauth::SmtpOAuth2Config;
+use crate::schedule::{QuietHours, QuietHoursConfig};
+use crate::stream::{KafkaConfig, NatsConfig};
+use crate::templates::TemplatesConfig;
+use governor::Quota;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Main configuration for the notification system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,13 +32,95 @@ pub struct NotifierConfig {
     /// Discord notification configuration
     pub discord: Option<DiscordConfig>,
 
+    /// PagerDuty notification configuration
+    #[serde(default)]
+    pub pagerduty: Option<PagerDutyConfig>,
+
+    /// Mattermost notification configuration
+    #[serde(default)]
+    pub mattermost: Option<MattermostConfig>,
+
+    /// Grafana OnCall notification configuration
+    #[serde(default)]
+    pub grafana_oncall: Option<GrafanaOnCallConfig>,
+
+    /// Pushover notification configuration
+    #[serde(default)]
+    pub pushover: Option<PushoverConfig>,
+
+    /// ntfy notification configuration
+    #[serde(default)]
+    pub ntfy: Option<NtfyConfig>,
+
+    /// AWS SNS notification configuration
+    #[serde(default)]
+    pub sns: Option<SnsConfig>,
+
+    /// Kafka channel configuration
+    #[serde(default)]
+    pub kafka: Option<KafkaConfig>,
+
+    /// NATS channel configuration
+    #[serde(default)]
+    pub nats: Option<NatsConfig>,
+
+    /// SMS (Twilio) notification configuration
+    #[serde(default)]
+    pub sms: Option<SmsConfig>,
+
+    /// Signal (signal-cli REST API) notification configuration
+    #[serde(default)]
+    pub signal: Option<SignalConfig>,
+
+    /// Capture channel recording notifications instead of sending them
+    #[serde(default)]
+    pub capture: Option<CaptureConfig>,
+
     /// Rate limiting configuration
     #[serde(default)]
     pub rate_limiting: RateLimitConfig,
 
+    /// Outbound HTTP proxies, CA certificates and timeouts
+    #[serde(default)]
+    pub http: HttpConfig,
+
     /// Global notification settings
     #[serde(default)]
     pub global: GlobalNotificationConfig,
+
+    /// Delivery retry and outbox settings
+    #[serde(default)]
+    pub retry: RetryConfig,
+
+    /// Channels per alert severity (e.g. `critical = ["pagerduty", "sms"]`),
+    /// applied before filters; severities without a route go to every channel
+    #[serde(default)]
+    pub routing: HashMap<String, Vec<String>>,
+
+    /// Recipients per program; the first route matching an alert's program
+    /// replaces the default recipients of the channels it configures
+    #[serde(default)]
+    pub program_routes: Vec<ProgramRoute>,
+
+    /// Escalation chains across channels for unacknowledged alerts
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+
+    /// Quiet hours per channel name; non-critical alerts are held until they end
+    #[serde(default)]
+    pub quiet_hours: HashMap<String, QuietHoursConfig>,
+
+    /// Periodic digests of low-severity alerts
+    #[serde(default)]
+    pub digest: DigestConfig,
+
+    /// Channel health checks and circuit breakers
+    #[serde(default)]
+    pub health: HealthCheckConfig,
+
+    /// User templates overriding the built-in message formats
+    #[serde(default)]
+    pub templates: TemplatesConfig,
 }
 
 /// Email notification configuration.
@@ -40,9 +136,14 @@ pub struct EmailConfig {
     /// Username for SMTP authentication
     pub username: String,
 
-    /// Password for SMTP authentication
+    /// Password for SMTP authentication (unused with OAuth2)
+    #[serde(default)]
     pub password: String,
 
+    /// OAuth2 credentials for XOAUTH2 authentication instead of a password
+    #[serde(default)]
+    pub oauth2: Option<SmtpOAuth2Config>,
+
     /// From email address
     pub from_address: String,
 
@@ -61,6 +162,39 @@ pub struct EmailConfig {
 
     /// Email body template (HTML or plain text)
     pub body_template: Option<String>,
+
+    /// Plain-text alternative of an HTML `body_template` (derived from the
+    /// HTML if unset)
+    #[serde(default)]
+    pub text_template: Option<String>,
+
+    /// DKIM signing of outgoing emails
+    #[serde(default)]
+    pub dkim: Option<DkimConfig>,
+
+    /// Embed a chart of the metric window in HTML emails of metric-driven alerts
+    #[serde(default)]
+    pub attach_chart: bool,
+}
+
+/// DKIM signing configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkimConfig {
+    /// Signing domain (`d=`), usually the domain of the from address
+    pub domain: String,
+
+    /// Selector (`s=`) under which the public key is published in DNS
+    pub selector: String,
+
+    /// Private key in PEM format
+    pub private_key: Option<String>,
+
+    /// File containing the private key in PEM format
+    pub private_key_path: Option<PathBuf>,
+
+    /// Signing algorithm ("rsa" or "ed25519")
+    #[serde(default = "default_dkim_algorithm")]
+    pub algorithm: String,
 }
 
 /// Telegram notification configuration.
@@ -69,13 +203,17 @@ pub struct TelegramConfig {
     /// Telegram Bot API token
     pub bot_token: String,
 
-    /// Chat ID to send messages to
+    /// Chat ID to send messages to when no entry of `chats` matches
     pub chat_id: i64,
 
+    /// Additional chats (or forum topics) receiving alerts by severity
+    #[serde(default)]
+    pub chats: Vec<TelegramChat>,
+
     /// Message template
     pub message_template: Option<String>,
 
-    /// Parse mode (Markdown, HTML, or None)
+    /// Parse mode (Markdown, MarkdownV2, HTML, or empty for plain text)
     #[serde(default = "default_parse_mode")]
     pub parse_mode: String,
 
@@ -86,15 +224,136 @@ pub struct TelegramConfig {
     /// Send messages silently
     #[serde(default)]
     pub disable_notification: bool,
+
+    /// Split messages over Telegram's 4096 character limit into several
+    /// messages instead of truncating them
+    #[serde(default)]
+    pub split_long_messages: bool,
+
+    /// Bot commands (`/status`, `/ack`, `/mute`, ...) received by long polling
+    #[serde(default)]
+    pub commands: TelegramCommandsConfig,
+}
+
+/// A Telegram chat, or a topic of a forum chat, receiving some severities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramChat {
+    /// Chat ID
+    pub chat_id: i64,
+
+    /// Forum topic to post into
+    pub message_thread_id: Option<i64>,
+
+    /// Severities sent to this chat (all if unset)
+    pub severities: Option<Vec<String>>,
+}
+
+impl TelegramChat {
+    /// Whether this chat receives alerts of `severity`.
+    pub fn receives(&self, severity: &watchtower_engine::AlertSeverity) -> bool {
+        self.severities.as_ref().map_or(true, |severities| {
+            severities.iter().any(|s| s == severity.as_str())
+        })
+    }
+
+    /// Validate the chat's severities.
+    pub fn validate(&self) -> crate::NotifierResult<()> {
+        if let Some(severity) = self
+            .severities
+            .iter()
+            .flatten()
+            .find(|severity| parse_severity(severity).is_none())
+        {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid severity '{}' for Telegram chat {}",
+                severity, self.chat_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Recipients of the alerts of some programs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramRoute {
+    /// Program names or IDs the route applies to
+    pub programs: Vec<String>,
+
+    /// Email recipients
+    #[serde(default)]
+    pub email_to: Vec<String>,
+
+    /// Slack channel (bot token mode, or webhooks that allow overriding the channel)
+    pub slack_channel: Option<String>,
+
+    /// Telegram chats and forum topics
+    #[serde(default)]
+    pub telegram_chats: Vec<TelegramChat>,
+}
+
+impl ProgramRoute {
+    /// Whether the route applies to an alert's program.
+    pub fn matches(&self, alert: &watchtower_engine::Alert) -> bool {
+        let program_id = alert.program_id.to_string();
+        self.programs
+            .iter()
+            .any(|program| *program == alert.program_name || *program == program_id)
+    }
+
+    /// Validate the route.
+    pub fn validate(&self) -> crate::NotifierResult<()> {
+        if self.programs.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "Program routes must list at least one program".to_string(),
+            ));
+        }
+
+        if self
+            .email_to
+            .iter()
+            .any(|address| address.trim().is_empty())
+        {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Empty email recipient in program route for {}",
+                self.programs.join(", ")
+            )));
+        }
+
+        for chat in &self.telegram_chats {
+            chat.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// First route applying to an alert's program.
+pub fn program_route<'a>(
+    routes: &'a [ProgramRoute],
+    alert: &watchtower_engine::Alert,
+) -> Option<&'a ProgramRoute> {
+    routes.iter().find(|route| route.matches(alert))
 }
 
 /// Slack notification configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlackConfig {
-    /// Slack webhook URL
+    /// Slack webhook URL (unused when a bot token is set); with webhook
+    /// authentication any Slack-compatible endpoint is accepted
+    #[serde(default)]
     pub webhook_url: String,
 
-    /// Channel to send messages to (optional, webhook may have default)
+    /// Headers and bearer token for a webhook behind an authenticating gateway
+    #[serde(flatten)]
+    pub auth: WebhookAuthConfig,
+
+    /// Bot token (`xoxb-...`) to post through `chat.postMessage` instead of
+    /// the webhook; required for threading related alerts
+    #[serde(default)]
+    pub bot_token: Option<String>,
+
+    /// Channel to send messages to (optional, webhook may have default;
+    /// required with a bot token)
     pub channel: Option<String>,
 
     /// Username to send messages as
@@ -108,14 +367,39 @@ pub struct SlackConfig {
 
     /// Custom fields to include in messages
     pub custom_fields: Option<HashMap<String, String>>,
+
+    /// Send Block Kit messages with Acknowledge/Resolve buttons; requires a
+    /// Slack app whose interactivity request URL points at the dashboard's
+    /// `/api/slack/actions` endpoint
+    #[serde(default)]
+    pub interactive: bool,
+
+    /// Post alerts with the same fingerprint into the first alert's thread
+    /// (bot token only)
+    #[serde(default = "default_true")]
+    pub thread_related_alerts: bool,
+
+    /// Seconds after which a follow-up alert starts a new thread
+    #[serde(default = "default_thread_window_seconds")]
+    pub thread_window_seconds: u64,
+
+    /// Upload a chart of the metric window of metric-driven alerts into
+    /// their thread (bot token with `files:write` only)
+    #[serde(default)]
+    pub attach_chart: bool,
 }
 
 /// Discord notification configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscordConfig {
-    /// Discord webhook URL
+    /// Discord webhook URL; with webhook authentication any
+    /// Discord-compatible endpoint is accepted
     pub webhook_url: String,
 
+    /// Headers and bearer token for a webhook behind an authenticating gateway
+    #[serde(flatten)]
+    pub auth: WebhookAuthConfig,
+
     /// Username to send messages as
     pub username: Option<String>,
 
@@ -128,6 +412,210 @@ pub struct DiscordConfig {
     /// Whether to use Discord embeds for rich formatting
     #[serde(default = "default_true")]
     pub use_embeds: bool,
+
+    /// Open a thread per incident and post follow-up alerts with the same
+    /// fingerprint into it (the webhook must belong to a forum channel)
+    #[serde(default)]
+    pub thread_per_incident: bool,
+
+    /// Seconds after which a follow-up alert opens a new thread
+    #[serde(default = "default_thread_window_seconds")]
+    pub thread_window_seconds: u64,
+
+    /// Role IDs mentioned in critical alerts
+    #[serde(default)]
+    pub critical_role_ids: Vec<String>,
+
+    /// Split messages over Discord's 2000 character limit into several
+    /// messages instead of truncating them
+    #[serde(default)]
+    pub split_long_messages: bool,
+
+    /// Attach a chart of the metric window of metric-driven alerts
+    #[serde(default)]
+    pub attach_chart: bool,
+}
+
+/// Mattermost notification configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostConfig {
+    /// Mattermost incoming webhook URL
+    pub webhook_url: String,
+
+    /// Headers and bearer token for a webhook behind an authenticating gateway
+    #[serde(flatten)]
+    pub auth: WebhookAuthConfig,
+
+    /// Channel to post to (optional, webhook may have default)
+    pub channel: Option<String>,
+
+    /// Username to post as
+    pub username: Option<String>,
+
+    /// Icon URL for the bot
+    pub icon_url: Option<String>,
+
+    /// Message template
+    pub message_template: Option<String>,
+}
+
+/// PagerDuty Events API v2 configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagerDutyConfig {
+    /// Integration routing key of the PagerDuty service
+    pub routing_key: String,
+
+    /// Events API endpoint
+    #[serde(default = "default_pagerduty_events_url")]
+    pub events_url: String,
+
+    /// Source reported on incidents
+    #[serde(default = "default_pagerduty_source")]
+    pub source: String,
+
+    /// PagerDuty severity per alert severity (e.g. `high = "critical"`),
+    /// overriding the default mapping
+    #[serde(default)]
+    pub severity_mapping: HashMap<String, String>,
+}
+
+/// Grafana OnCall formatted webhook integration configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrafanaOnCallConfig {
+    /// URL of the integration, e.g.
+    /// `https://oncall-prod-us-central-0.grafana.net/integrations/v1/formatted_webhook/<token>/`
+    pub webhook_url: String,
+
+    /// Message template
+    pub message_template: Option<String>,
+}
+
+/// Pushover notification configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushoverConfig {
+    /// Application API token
+    pub app_token: String,
+
+    /// User or group key to notify
+    pub user_key: String,
+
+    /// Devices to target (all of the user's devices if empty)
+    #[serde(default)]
+    pub devices: Vec<String>,
+
+    /// Pushover priority (-2 to 2) per alert severity, overriding the default
+    /// mapping in which critical alerts are sent as emergencies
+    #[serde(default)]
+    pub priorities: HashMap<String, i8>,
+
+    /// Notification sound per alert severity (e.g. `critical = "siren"`)
+    #[serde(default)]
+    pub sounds: HashMap<String, String>,
+
+    /// How often emergency notifications are retried until acknowledged
+    #[serde(default = "default_pushover_retry")]
+    pub emergency_retry_seconds: u32,
+
+    /// How long emergency notifications keep being retried
+    #[serde(default = "default_pushover_expire")]
+    pub emergency_expire_seconds: u32,
+
+    /// Message template
+    pub message_template: Option<String>,
+}
+
+/// ntfy notification configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NtfyConfig {
+    /// Topic URL, e.g. `https://ntfy.sh/my-watchtower-alerts`
+    pub topic_url: String,
+
+    /// Access token for protected topics
+    pub access_token: Option<String>,
+
+    /// Username for protected topics (basic auth)
+    pub username: Option<String>,
+
+    /// Password for protected topics (basic auth)
+    pub password: Option<String>,
+
+    /// Message template
+    pub message_template: Option<String>,
+}
+
+/// AWS SNS notification configuration.
+///
+/// Credentials are resolved through the standard AWS provider chain
+/// (environment, shared profile, web identity, ECS/EC2 instance roles).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnsConfig {
+    /// ARN of the topic alerts are published to
+    pub topic_arn: String,
+
+    /// AWS region (defaults to the region of the provider chain)
+    pub region: Option<String>,
+
+    /// Named profile from the shared AWS config files
+    pub profile: Option<String>,
+}
+
+/// SMS notification configuration (Twilio Messaging API).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsConfig {
+    /// Twilio account SID
+    pub account_sid: String,
+
+    /// Twilio auth token
+    pub auth_token: String,
+
+    /// Sending phone number in E.164 format
+    pub from_number: String,
+
+    /// Recipient phone numbers in E.164 format
+    pub to_numbers: Vec<String>,
+
+    /// Minimum alert severity sent by SMS
+    #[serde(default = "default_sms_min_severity")]
+    pub min_severity: String,
+
+    /// Maximum messages per hour to each recipient number
+    #[serde(default = "default_sms_max_messages_per_hour")]
+    pub max_messages_per_number_per_hour: u32,
+
+    /// Messages longer than this many characters are truncated
+    #[serde(default = "default_sms_max_length")]
+    pub max_length: usize,
+
+    /// Message template
+    pub message_template: Option<String>,
+
+    /// Twilio API base URL
+    #[serde(default = "default_twilio_api_url")]
+    pub api_url: String,
+}
+
+/// Signal notification configuration (signal-cli REST API gateway).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalConfig {
+    /// Base URL of the signal-cli-rest-api gateway, e.g. `http://signal-api:8080`
+    pub api_url: String,
+
+    /// Registered sending number in E.164 format
+    pub number: String,
+
+    /// Recipient numbers in E.164 format or group IDs (`group.…`)
+    pub recipients: Vec<String>,
+
+    /// Minimum alert severity sent over Signal
+    #[serde(default = "default_signal_min_severity")]
+    pub min_severity: String,
+
+    /// Message template
+    pub message_template: Option<String>,
+
+    /// Headers and bearer token for gateways behind an authenticating proxy
+    #[serde(flatten)]
+    pub auth: WebhookAuthConfig,
 }
 
 /// Rate limiting configuration.
@@ -144,6 +632,55 @@ pub struct RateLimitConfig {
     /// Whether to enable rate limiting
     #[serde(default = "default_true")]
     pub enabled: bool,
+
+    /// Per-channel overrides of the quota and burst size, keyed by channel name
+    #[serde(default)]
+    pub channels: HashMap<String, ChannelRateLimit>,
+}
+
+/// Rate limit override for a single channel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelRateLimit {
+    /// Maximum messages per minute per destination
+    pub max_messages_per_minute: Option<u32>,
+
+    /// Maximum burst size per destination
+    pub burst_size: Option<u32>,
+}
+
+/// Delivery retry configuration.
+///
+/// Failed deliveries are retried in place with exponential backoff; if all
+/// attempts fail the notification moves to the outbox, which keeps retrying
+/// it in the background (across restarts when `outbox_path` is set).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Delivery attempts per channel before moving a notification to the outbox
+    #[serde(default = "default_retry_attempts")]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound for the delay between retries
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// File the outbox is persisted to (kept in memory if unset)
+    pub outbox_path: Option<PathBuf>,
+
+    /// Maximum number of notifications held in the outbox
+    #[serde(default = "default_outbox_capacity")]
+    pub outbox_capacity: usize,
+
+    /// Total attempts after which an outbox notification is dropped
+    #[serde(default = "default_outbox_max_attempts")]
+    pub outbox_max_attempts: u32,
+
+    /// How often the outbox is checked for due notifications
+    #[serde(default = "default_outbox_poll_interval")]
+    pub outbox_poll_interval_seconds: u64,
 }
 
 /// Global notification settings.
@@ -167,6 +704,14 @@ pub struct GlobalNotificationConfig {
 
     /// Custom notification filters
     pub filters: Option<Vec<NotificationFilter>>,
+
+    /// File notification statistics are persisted to (reset on restart if unset)
+    pub stats_path: Option<PathBuf>,
+
+    /// Seconds during which an alert with the same fingerprint is not sent to
+    /// the same channel again (0 disables)
+    #[serde(default)]
+    pub dedup_window_seconds: u64,
 }
 
 /// Notification filter configuration.
@@ -184,6 +729,10 @@ pub struct NotificationFilter {
     /// Severity levels to include/exclude
     pub severities: Option<Vec<String>>,
 
+    /// Alert tags to include/exclude (matches alerts carrying any of them)
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+
     /// Whether this is an include filter (true) or exclude filter (false)
     #[serde(default = "default_true")]
     pub include: bool,
@@ -215,11 +764,143 @@ impl NotifierConfig {
             discord.validate()?;
         }
 
+        // Validate Mattermost config
+        if let Some(mattermost) = &self.mattermost {
+            mattermost.validate()?;
+        }
+
+        // Validate PagerDuty config
+        if let Some(pagerduty) = &self.pagerduty {
+            pagerduty.validate()?;
+        }
+
+        // Validate Grafana OnCall config
+        if let Some(grafana_oncall) = &self.grafana_oncall {
+            grafana_oncall.validate()?;
+        }
+
+        // Validate Pushover config
+        if let Some(pushover) = &self.pushover {
+            pushover.validate()?;
+        }
+
+        // Validate ntfy config
+        if let Some(ntfy) = &self.ntfy {
+            ntfy.validate()?;
+        }
+
+        // Validate SNS config
+        if let Some(sns) = &self.sns {
+            sns.validate()?;
+        }
+
+        // Validate Kafka config
+        if let Some(kafka) = &self.kafka {
+            kafka.validate()?;
+        }
+
+        // Validate NATS config
+        if let Some(nats) = &self.nats {
+            nats.validate()?;
+        }
+
+        // Validate SMS config
+        if let Some(sms) = &self.sms {
+            sms.validate()?;
+        }
+
+        // Validate Signal config
+        if let Some(signal) = &self.signal {
+            signal.validate()?;
+        }
+
+        // Validate routing table
+        let enabled_channels = self.enabled_channels();
+        for (severity, channels) in &self.routing {
+            if parse_severity(severity).is_none() {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Unknown alert severity in routing table: {}",
+                    severity
+                )));
+            }
+            if let Some(channel) = channels.iter().find(|c| !enabled_channels.contains(c)) {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Routing for {} alerts references unconfigured channel: {}",
+                    severity, channel
+                )));
+            }
+        }
+
+        // Validate program routes
+        for route in &self.program_routes {
+            route.validate()?;
+        }
+
+        self.escalation.validate(&enabled_channels)?;
+
+        // Validate quiet hours
+        for (channel, quiet_hours) in &self.quiet_hours {
+            if !enabled_channels.contains(channel) {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Quiet hours configured for unconfigured channel: {}",
+                    channel
+                )));
+            }
+            QuietHours::from_config(quiet_hours)?;
+        }
+
+        self.templates.validate()?;
+        self.http.validate()?;
+
+        // Validate digest config
+        if parse_severity(&self.digest.max_severity).is_none() {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid digest maximum severity: {}",
+                self.digest.max_severity
+            )));
+        }
+        if let Some(channel) = self
+            .digest
+            .channels
+            .iter()
+            .flatten()
+            .find(|c| !enabled_channels.contains(c))
+        {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Digest configured for unconfigured channel: {}",
+                channel
+            )));
+        }
+
+        // Validate health check fallbacks
+        for (channel, fallbacks) in &self.health.fallbacks {
+            if let Some(unknown) = std::iter::once(channel)
+                .chain(fallbacks)
+                .find(|c| !enabled_channels.contains(c))
+            {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Fallback for {} references unconfigured channel: {}",
+                    channel, unknown
+                )));
+            }
+        }
+
         // Check that at least one notification channel is configured
         if self.email.is_none()
             && self.telegram.is_none()
             && self.slack.is_none()
             && self.discord.is_none()
+            && self.mattermost.is_none()
+            && self.pagerduty.is_none()
+            && self.grafana_oncall.is_none()
+            && self.pushover.is_none()
+            && self.ntfy.is_none()
+            && self.sns.is_none()
+            && self.kafka.is_none()
+            && self.nats.is_none()
+            && self.sms.is_none()
+            && self.signal.is_none()
+            && self.capture.is_none()
         {
             return Err(crate::NotifierError::Configuration(
                 "At least one notification channel must be configured".to_string(),
@@ -245,6 +926,39 @@ impl NotifierConfig {
         if self.discord.is_some() {
             channels.push("discord".to_string());
         }
+        if self.mattermost.is_some() {
+            channels.push("mattermost".to_string());
+        }
+        if self.pagerduty.is_some() {
+            channels.push("pagerduty".to_string());
+        }
+        if self.grafana_oncall.is_some() {
+            channels.push("grafana_oncall".to_string());
+        }
+        if self.pushover.is_some() {
+            channels.push("pushover".to_string());
+        }
+        if self.ntfy.is_some() {
+            channels.push("ntfy".to_string());
+        }
+        if self.sns.is_some() {
+            channels.push("sns".to_string());
+        }
+        if self.kafka.is_some() {
+            channels.push("kafka".to_string());
+        }
+        if self.nats.is_some() {
+            channels.push("nats".to_string());
+        }
+        if self.sms.is_some() {
+            channels.push("sms".to_string());
+        }
+        if self.signal.is_some() {
+            channels.push("signal".to_string());
+        }
+        if self.capture.is_some() {
+            channels.push("capture".to_string());
+        }
 
         channels
     }
@@ -264,7 +978,17 @@ impl EmailConfig {
             ));
         }
 
-        if self.password.is_empty() {
+        if let Some(oauth2) = &self.oauth2 {
+            if oauth2.client_id.is_empty()
+                || oauth2.client_secret.is_empty()
+                || oauth2.refresh_token.is_empty()
+            {
+                return Err(crate::NotifierError::Configuration(
+                    "SMTP OAuth2 client ID, client secret and refresh token cannot be empty"
+                        .to_string(),
+                ));
+            }
+        } else if self.password.is_empty() {
             return Err(crate::NotifierError::Configuration(
                 "SMTP password cannot be empty".to_string(),
             ));
@@ -282,6 +1006,34 @@ impl EmailConfig {
             ));
         }
 
+        if let Some(dkim) = &self.dkim {
+            dkim.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DkimConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if self.domain.is_empty() || self.selector.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "DKIM domain and selector cannot be empty".to_string(),
+            ));
+        }
+
+        if self.private_key.is_some() == self.private_key_path.is_some() {
+            return Err(crate::NotifierError::Configuration(
+                "Exactly one of DKIM private_key and private_key_path must be set".to_string(),
+            ));
+        }
+
+        if !["rsa", "ed25519"].contains(&self.algorithm.as_str()) {
+            return Err(crate::NotifierError::Configuration(
+                "Invalid DKIM algorithm. Must be 'rsa' or 'ed25519'".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -294,9 +1046,20 @@ impl TelegramConfig {
             ));
         }
 
-        if !["Markdown", "HTML", ""].contains(&self.parse_mode.as_str()) {
+        if !["Markdown", "MarkdownV2", "HTML", ""].contains(&self.parse_mode.as_str()) {
+            return Err(crate::NotifierError::Configuration(
+                "Invalid Telegram parse mode. Must be 'Markdown', 'MarkdownV2', 'HTML', or empty"
+                    .to_string(),
+            ));
+        }
+
+        for chat in &self.chats {
+            chat.validate()?;
+        }
+
+        if self.commands.enabled && self.commands.allowed_user_ids.is_empty() {
             return Err(crate::NotifierError::Configuration(
-                "Invalid Telegram parse mode. Must be 'Markdown', 'HTML', or empty".to_string(),
+                "Telegram commands require at least one allowed user ID".to_string(),
             ));
         }
 
@@ -306,13 +1069,32 @@ impl TelegramConfig {
 
 impl SlackConfig {
     fn validate(&self) -> crate::NotifierResult<()> {
+        if let Some(bot_token) = &self.bot_token {
+            if bot_token.is_empty() {
+                return Err(crate::NotifierError::Configuration(
+                    "Slack bot token cannot be empty".to_string(),
+                ));
+            }
+
+            if self.channel.as_deref().map_or(true, str::is_empty) {
+                return Err(crate::NotifierError::Configuration(
+                    "Slack channel is required when posting with a bot token".to_string(),
+                ));
+            }
+
+            return Ok(());
+        }
+
         if self.webhook_url.is_empty() {
             return Err(crate::NotifierError::Configuration(
                 "Slack webhook URL cannot be empty".to_string(),
             ));
         }
 
-        if !self.webhook_url.starts_with("https://hooks.slack.com/") {
+        self.auth.validate("Slack")?;
+
+        // Gateways in front of Slack-compatible endpoints use their own URLs
+        if self.auth.is_empty() && !self.webhook_url.starts_with("https://hooks.slack.com/") {
             return Err(crate::NotifierError::Configuration(
                 "Invalid Slack webhook URL format".to_string(),
             ));
@@ -330,19 +1112,326 @@ impl DiscordConfig {
             ));
         }
 
-        if !self
-            .webhook_url
-            .starts_with("https://discord.com/api/webhooks/")
+        self.auth.validate("Discord")?;
+
+        // Gateways in front of Discord-compatible endpoints use their own URLs
+        if self.auth.is_empty()
+            && !self
+                .webhook_url
+                .starts_with("https://discord.com/api/webhooks/")
         {
             return Err(crate::NotifierError::Configuration(
                 "Invalid Discord webhook URL format".to_string(),
             ));
         }
 
+        if let Some(role_id) = self
+            .critical_role_ids
+            .iter()
+            .find(|id| id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid Discord role ID '{}'",
+                role_id
+            )));
+        }
+
         Ok(())
     }
 }
 
+impl MattermostConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if self.webhook_url.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "Mattermost webhook URL cannot be empty".to_string(),
+            ));
+        }
+
+        // Mattermost is self-hosted, so only the incoming webhook path is fixed
+        if !(self.webhook_url.starts_with("https://") || self.webhook_url.starts_with("http://"))
+            || !self.webhook_url.contains("/hooks/")
+        {
+            return Err(crate::NotifierError::Configuration(
+                "Invalid Mattermost webhook URL format".to_string(),
+            ));
+        }
+
+        self.auth.validate("Mattermost")
+    }
+}
+
+impl PagerDutyConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if self.routing_key.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "PagerDuty routing key cannot be empty".to_string(),
+            ));
+        }
+
+        if !self.events_url.starts_with("https://") {
+            return Err(crate::NotifierError::Configuration(
+                "PagerDuty events URL must use https://".to_string(),
+            ));
+        }
+
+        for (severity, pagerduty_severity) in &self.severity_mapping {
+            if !["critical", "high", "medium", "low", "info"].contains(&severity.as_str()) {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Unknown alert severity in PagerDuty mapping: {}",
+                    severity
+                )));
+            }
+            if !PAGERDUTY_SEVERITIES.contains(&pagerduty_severity.as_str()) {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Invalid PagerDuty severity '{}'. Must be one of: {}",
+                    pagerduty_severity,
+                    PAGERDUTY_SEVERITIES.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl GrafanaOnCallConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if !self.webhook_url.starts_with("https://") && !self.webhook_url.starts_with("http://") {
+            return Err(crate::NotifierError::Configuration(
+                "Grafana OnCall webhook URL must be an http(s) URL".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl PushoverConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if self.app_token.is_empty() || self.user_key.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "Pushover app token and user key cannot be empty".to_string(),
+            ));
+        }
+
+        for (severity, priority) in &self.priorities {
+            if parse_severity(severity).is_none() {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Unknown alert severity in Pushover priorities: {}",
+                    severity
+                )));
+            }
+            if !(-2..=2).contains(priority) {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Invalid Pushover priority {}. Must be between -2 and 2",
+                    priority
+                )));
+            }
+        }
+
+        if let Some(severity) = self.sounds.keys().find(|s| parse_severity(s).is_none()) {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Unknown alert severity in Pushover sounds: {}",
+                severity
+            )));
+        }
+
+        // Limits imposed by the Pushover API on emergency notifications
+        if self.emergency_retry_seconds < 30 {
+            return Err(crate::NotifierError::Configuration(
+                "Pushover emergency retry must be at least 30 seconds".to_string(),
+            ));
+        }
+
+        if self.emergency_expire_seconds > 10800 {
+            return Err(crate::NotifierError::Configuration(
+                "Pushover emergency expire cannot exceed 10800 seconds".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl NtfyConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if !(self.topic_url.starts_with("https://") || self.topic_url.starts_with("http://")) {
+            return Err(crate::NotifierError::Configuration(
+                "ntfy topic URL must be an http(s) URL".to_string(),
+            ));
+        }
+
+        if self.access_token.is_some() && self.username.is_some() {
+            return Err(crate::NotifierError::Configuration(
+                "ntfy access token and username/password are mutually exclusive".to_string(),
+            ));
+        }
+
+        if self.username.is_some() != self.password.is_some() {
+            return Err(crate::NotifierError::Configuration(
+                "ntfy username and password must be set together".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl SnsConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        // arn:partition:sns:region:account-id:topic-name
+        let parts: Vec<&str> = self.topic_arn.split(':').collect();
+        if parts.len() != 6 || parts[0] != "arn" || parts[2] != "sns" || parts[5].is_empty() {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid SNS topic ARN: {}",
+                self.topic_arn
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl SmsConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if self.account_sid.is_empty() || self.auth_token.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "Twilio account SID and auth token cannot be empty".to_string(),
+            ));
+        }
+
+        if self.to_numbers.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "At least one SMS recipient number must be specified".to_string(),
+            ));
+        }
+
+        for number in std::iter::once(&self.from_number).chain(&self.to_numbers) {
+            if !is_e164(number) {
+                return Err(crate::NotifierError::Configuration(format!(
+                    "Invalid phone number '{}'. Must be in E.164 format (e.g. +14155550100)",
+                    number
+                )));
+            }
+        }
+
+        if parse_severity(&self.min_severity).is_none() {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid SMS minimum severity: {}",
+                self.min_severity
+            )));
+        }
+
+        if self.max_messages_per_number_per_hour == 0 {
+            return Err(crate::NotifierError::Configuration(
+                "SMS per-number rate limit must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_length < 20 {
+            return Err(crate::NotifierError::Configuration(
+                "SMS max length must be at least 20 characters".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a lowercase severity name as used in notifier configuration.
+pub fn parse_severity(severity: &str) -> Option<watchtower_engine::AlertSeverity> {
+    use watchtower_engine::AlertSeverity;
+
+    match severity {
+        "critical" => Some(AlertSeverity::Critical),
+        "high" => Some(AlertSeverity::High),
+        "medium" => Some(AlertSeverity::Medium),
+        "low" => Some(AlertSeverity::Low),
+        "info" => Some(AlertSeverity::Info),
+        _ => None,
+    }
+}
+
+/// Whether a phone number is in E.164 format.
+impl SignalConfig {
+    fn validate(&self) -> crate::NotifierResult<()> {
+        if !self.api_url.starts_with("https://") && !self.api_url.starts_with("http://") {
+            return Err(crate::NotifierError::Configuration(
+                "Signal API URL must be an http(s) URL".to_string(),
+            ));
+        }
+
+        if !is_e164(&self.number) {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid Signal number '{}'. Must be in E.164 format (e.g. +14155550100)",
+                self.number
+            )));
+        }
+
+        if self.recipients.is_empty() {
+            return Err(crate::NotifierError::Configuration(
+                "At least one Signal recipient must be specified".to_string(),
+            ));
+        }
+
+        if let Some(recipient) = self
+            .recipients
+            .iter()
+            .find(|r| !is_e164(r) && !r.starts_with("group."))
+        {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid Signal recipient '{}'. Must be an E.164 number or a group ID",
+                recipient
+            )));
+        }
+
+        if parse_severity(&self.min_severity).is_none() {
+            return Err(crate::NotifierError::Configuration(format!(
+                "Invalid Signal minimum severity: {}",
+                self.min_severity
+            )));
+        }
+
+        self.auth.validate("Signal")
+    }
+}
+
+fn is_e164(number: &str) -> bool {
+    number.strip_prefix('+').is_some_and(|digits| {
+        (8..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+/// Severities accepted by the PagerDuty Events API.
+pub const PAGERDUTY_SEVERITIES: [&str; 4] = ["critical", "error", "warning", "info"];
+
+impl RateLimitConfig {
+    /// Quota applied to each destination of a channel.
+    pub fn quota(&self, channel: &str) -> Quota {
+        let overrides = self.channels.get(channel);
+        let per_minute = overrides
+            .and_then(|o| o.max_messages_per_minute)
+            .unwrap_or(self.max_messages_per_minute);
+        let burst = overrides
+            .and_then(|o| o.burst_size)
+            .unwrap_or(self.burst_size);
+
+        let per_minute = NonZeroU32::new(per_minute).unwrap_or(NonZeroU32::new(60).unwrap());
+        let burst = NonZeroU32::new(burst).unwrap_or(per_minute);
+        Quota::per_minute(per_minute).allow_burst(burst)
+    }
+}
+
+impl RetryConfig {
+    /// Delay before the retry following `attempts` failed attempts.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        let delay = self.initial_backoff_ms.saturating_mul(1 << exponent);
+        Duration::from_millis(delay.min(self.max_backoff_ms))
+    }
+}
+
 // Default value functions
 fn default_smtp_port() -> u16 {
     587
@@ -352,10 +1441,78 @@ fn default_true() -> bool {
     true
 }
 
+fn default_dkim_algorithm() -> String {
+    "rsa".to_string()
+}
+
+fn default_thread_window_seconds() -> u64 {
+    86400 // 24 hours
+}
+
 fn default_parse_mode() -> String {
     "Markdown".to_string()
 }
 
+fn default_pagerduty_events_url() -> String {
+    "https://events.pagerduty.com/v2/enqueue".to_string()
+}
+
+fn default_pagerduty_source() -> String {
+    "solana-watchtower".to_string()
+}
+
+fn default_pushover_retry() -> u32 {
+    60
+}
+
+fn default_pushover_expire() -> u32 {
+    3600
+}
+
+fn default_sms_min_severity() -> String {
+    "critical".to_string()
+}
+
+fn default_sms_max_messages_per_hour() -> u32 {
+    10
+}
+
+fn default_sms_max_length() -> usize {
+    160
+}
+
+fn default_twilio_api_url() -> String {
+    "https://api.twilio.com".to_string()
+}
+
+fn default_signal_min_severity() -> String {
+    "critical".to_string()
+}
+
+fn default_retry_attempts() -> u32 {
+    3
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_max_backoff_ms() -> u64 {
+    300_000
+}
+
+fn default_outbox_capacity() -> usize {
+    1000
+}
+
+fn default_outbox_max_attempts() -> u32 {
+    20
+}
+
+fn default_outbox_poll_interval() -> u64 {
+    10
+}
+
 fn default_max_messages_per_minute() -> u32 {
     10
 }
@@ -382,6 +1539,7 @@ impl Default for RateLimitConfig {
             max_messages_per_minute: default_max_messages_per_minute(),
             burst_size: default_burst_size(),
             enabled: default_true(),
+            channels: HashMap::new(),
         }
     }
 }
@@ -394,6 +1552,22 @@ impl Default for GlobalNotificationConfig {
             batch_timeout_seconds: default_batch_timeout(),
             enable_batching: false,
             filters: None,
+            stats_path: None,
+            dedup_window_seconds: 0,
+        }
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            outbox_path: None,
+            outbox_capacity: default_outbox_capacity(),
+            outbox_max_attempts: default_outbox_max_attempts(),
+            outbox_poll_interval_seconds: default_outbox_poll_interval(),
         }
     }
 }
@@ -0,0 +1,228 @@
+//! Periodic digests summarizing low-severity alerts.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use watchtower_engine::{Alert, AlertSeverity};
+
+/// Digest mode configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    /// Whether low-severity alerts are collected into digests
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// How often digests are sent
+    #[serde(default)]
+    pub interval: DigestInterval,
+
+    /// Highest severity collected into the digest instead of sent right away
+    #[serde(default = "default_max_severity")]
+    pub max_severity: String,
+
+    /// Channels receiving digests (all channels if unset)
+    pub channels: Option<Vec<String>>,
+}
+
+/// Digest frequency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestInterval {
+    /// Every hour
+    #[default]
+    Hourly,
+    /// Every day
+    Daily,
+}
+
+impl DigestInterval {
+    /// Interval length.
+    pub fn duration(&self) -> Duration {
+        match self {
+            DigestInterval::Hourly => Duration::from_secs(3600),
+            DigestInterval::Daily => Duration::from_secs(86400),
+        }
+    }
+}
+
+/// Alerts collected for one channel since its last digest.
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    /// Start of the digest period
+    pub period_start: DateTime<Utc>,
+
+    /// Number of alerts collected
+    pub total: usize,
+
+    /// Alert counts per severity
+    pub by_severity: BTreeMap<String, usize>,
+
+    /// Alert counts per rule
+    pub by_rule: BTreeMap<String, usize>,
+
+    /// Alert counts per program
+    pub by_program: BTreeMap<String, usize>,
+}
+
+impl Digest {
+    /// Create an empty digest starting now.
+    pub fn new() -> Self {
+        Self {
+            period_start: Utc::now(),
+            total: 0,
+            by_severity: BTreeMap::new(),
+            by_rule: BTreeMap::new(),
+            by_program: BTreeMap::new(),
+        }
+    }
+
+    /// Count an alert.
+    pub fn add(&mut self, alert: &Alert) {
+        self.total += 1;
+        *self
+            .by_severity
+            .entry(alert.severity.as_str().to_string())
+            .or_insert(0) += 1;
+        *self.by_rule.entry(alert.rule_name.clone()).or_insert(0) += 1;
+        *self
+            .by_program
+            .entry(alert.program_name.clone())
+            .or_insert(0) += 1;
+    }
+
+    /// Whether no alerts were collected.
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Plain-text summary with counts per rule and program.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} alert(s) since {}",
+            self.total,
+            self.period_start.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        for (title, counts) in [("By rule", &self.by_rule), ("By program", &self.by_program)] {
+            summary.push_str(&format!("\n\n{}:", title));
+            for (name, count) in sorted_by_count(counts) {
+                summary.push_str(&format!("\n• {}: {}", name, count));
+            }
+        }
+
+        summary
+    }
+
+    /// Informational alert carrying the summary, delivered like any other alert.
+    pub fn to_alert(&self, channel: &str) -> Alert {
+        let now = Utc::now();
+        let mut metadata = HashMap::new();
+        metadata.insert("digest_total".to_string(), serde_json::json!(self.total));
+        metadata.insert(
+            "digest_by_severity".to_string(),
+            serde_json::json!(self.by_severity),
+        );
+        metadata.insert(
+            "digest_by_rule".to_string(),
+            serde_json::json!(self.by_rule),
+        );
+        metadata.insert(
+            "digest_by_program".to_string(),
+            serde_json::json!(self.by_program),
+        );
+
+        Alert {
+            id: format!("digest-{}-{}", channel, now.timestamp()),
+            rule_name: "digest".to_string(),
+            message: self.summary(),
+            severity: AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::default(),
+            program_name: format!("{} program(s)", self.by_program.len()),
+            event_id: None,
+            metadata,
+            confidence: 1.0,
+            suggested_actions: Vec::new(),
+            timestamp: now,
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl Default for Digest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: DigestInterval::default(),
+            max_severity: default_max_severity(),
+            channels: None,
+        }
+    }
+}
+
+/// Entries ordered by descending count, then name.
+fn sorted_by_count(counts: &BTreeMap<String, usize>) -> Vec<(&String, &usize)> {
+    let mut entries: Vec<_> = counts.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    entries
+}
+
+fn default_max_severity() -> String {
+    "low".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(rule_name: &str, program_name: &str) -> Alert {
+        Alert {
+            id: "test".to_string(),
+            rule_name: rule_name.to_string(),
+            message: "Test message".to_string(),
+            severity: AlertSeverity::Low,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: program_name.to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_digest_counts_and_summary() {
+        let mut digest = Digest::new();
+        assert!(digest.is_empty());
+
+        digest.add(&alert("high_failure_rate", "Orca"));
+        digest.add(&alert("high_failure_rate", "Raydium"));
+        digest.add(&alert("large_transaction", "Orca"));
+
+        assert_eq!(digest.total, 3);
+        assert_eq!(digest.by_rule["high_failure_rate"], 2);
+        assert_eq!(digest.by_program["Orca"], 2);
+        assert_eq!(digest.by_severity["low"], 3);
+
+        let summary = digest.summary();
+        assert!(summary.starts_with("3 alert(s) since"));
+        assert!(summary.contains("By rule:\n• high_failure_rate: 2\n• large_transaction: 1"));
+
+        let alert = digest.to_alert("slack");
+        assert_eq!(alert.rule_name, "digest");
+        assert_eq!(alert.severity, AlertSeverity::Info);
+        assert_eq!(alert.metadata["digest_total"], 3);
+    }
+}
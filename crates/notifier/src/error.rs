@@ -33,10 +33,18 @@ pub enum NotifierError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Configuration error
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    /// Metrics registration error
+    #[error("Metrics registration failed: {0}")]
+    Metrics(#[from] prometheus::Error),
+
     /// Rate limit exceeded
     #[error("Rate limit exceeded for channel: {channel}")]
     RateLimit { channel: String },
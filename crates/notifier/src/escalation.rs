@@ -0,0 +1,142 @@
+//! Escalation chains: unacknowledged alerts move on to further channels.
+
+use crate::config::parse_severity;
+use crate::error::{NotifierError, NotifierResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use watchtower_engine::Alert;
+
+/// Escalation chain configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationConfig {
+    /// Chains; an alert follows the first one applying to it
+    #[serde(default)]
+    pub chains: Vec<EscalationChain>,
+
+    /// Seconds between checks for escalation steps that are due
+    #[serde(default = "default_check_interval_seconds")]
+    pub check_interval_seconds: u64,
+}
+
+/// Channels notified one step after another until an alert is acknowledged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationChain {
+    /// Chain name, used in logs
+    pub name: String,
+
+    /// Severities the chain applies to; all when empty
+    #[serde(default)]
+    pub severities: Vec<String>,
+
+    /// Rules the chain applies to; all when empty
+    #[serde(default)]
+    pub rules: Vec<String>,
+
+    /// Steps, the first of which is taken right away
+    pub steps: Vec<EscalationStep>,
+}
+
+/// One step of an escalation chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationStep {
+    /// Minutes the alert stays unacknowledged after the previous step before
+    /// this one is taken (ignored for the first step)
+    #[serde(default)]
+    pub after_minutes: u64,
+
+    /// Channels notified by this step
+    pub channels: Vec<String>,
+}
+
+/// An alert waiting for the next step of its chain.
+#[derive(Debug, Clone)]
+pub struct Escalation {
+    /// Escalated alert
+    pub alert: Alert,
+
+    /// Index of the chain in the configuration
+    pub chain: usize,
+
+    /// Index of the next step
+    pub next_step: usize,
+
+    /// When the next step is taken
+    pub due: DateTime<Utc>,
+}
+
+impl EscalationConfig {
+    /// Index of the first chain applying to an alert.
+    pub fn chain_for(&self, alert: &Alert) -> Option<usize> {
+        self.chains.iter().position(|chain| chain.applies_to(alert))
+    }
+
+    /// Validate the chains against the configured channels.
+    pub fn validate(&self, enabled_channels: &[String]) -> NotifierResult<()> {
+        for chain in &self.chains {
+            if chain.steps.is_empty() {
+                return Err(NotifierError::Configuration(format!(
+                    "Escalation chain {} has no steps",
+                    chain.name
+                )));
+            }
+
+            if let Some(severity) = chain
+                .severities
+                .iter()
+                .find(|severity| parse_severity(severity).is_none())
+            {
+                return Err(NotifierError::Configuration(format!(
+                    "Invalid severity '{}' in escalation chain {}",
+                    severity, chain.name
+                )));
+            }
+
+            for step in &chain.steps {
+                if step.channels.is_empty() {
+                    return Err(NotifierError::Configuration(format!(
+                        "Escalation chain {} has a step without channels",
+                        chain.name
+                    )));
+                }
+                if let Some(channel) = step
+                    .channels
+                    .iter()
+                    .find(|channel| !enabled_channels.contains(channel))
+                {
+                    return Err(NotifierError::Configuration(format!(
+                        "Escalation chain {} references unconfigured channel: {}",
+                        chain.name, channel
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl EscalationChain {
+    /// Whether the chain applies to an alert's severity and rule.
+    pub fn applies_to(&self, alert: &Alert) -> bool {
+        (self.severities.is_empty()
+            || self
+                .severities
+                .iter()
+                .any(|severity| severity == alert.severity.as_str()))
+            && (self.rules.is_empty() || self.rules.contains(&alert.rule_name))
+    }
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            chains: Vec::new(),
+            check_interval_seconds: default_check_interval_seconds(),
+        }
+    }
+}
+
+// Default value functions
+fn default_check_interval_seconds() -> u64 {
+    30
+}
@@ -0,0 +1,221 @@
+//! Block explorer deep links included in the default templates.
+
+use crate::error::{NotifierError, NotifierResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use watchtower_engine::{Alert, Explorer, ACCOUNT_KEY, SIGNATURE_KEY};
+
+/// Explorer the links in notifications point to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplorerProvider {
+    Solscan,
+    SolanaFm,
+    Xray,
+    /// URL templates of `ExplorerConfig`
+    Custom,
+    /// No links
+    None,
+}
+
+/// Explorer link configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerConfig {
+    /// Explorer linked to (solscan, solana_fm, xray, custom or none)
+    #[serde(default = "default_provider")]
+    pub provider: ExplorerProvider,
+
+    /// Cluster of the linked accounts and transactions (mainnet-beta, devnet, testnet)
+    #[serde(default = "default_cluster")]
+    pub cluster: String,
+
+    /// Account and program link of the custom provider, with `{address}`
+    /// and `{cluster}` placeholders
+    pub account_url: Option<String>,
+
+    /// Transaction link of the custom provider, with `{signature}` and
+    /// `{cluster}` placeholders
+    pub transaction_url: Option<String>,
+}
+
+impl ExplorerConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> NotifierResult<()> {
+        if !matches!(self.cluster.as_str(), "mainnet-beta" | "devnet" | "testnet") {
+            return Err(NotifierError::Configuration(format!(
+                "Unknown explorer cluster: {}",
+                self.cluster
+            )));
+        }
+
+        if self.provider == ExplorerProvider::Custom {
+            match (&self.account_url, &self.transaction_url) {
+                (Some(account_url), Some(transaction_url))
+                    if account_url.contains("{address}")
+                        && transaction_url.contains("{signature}") => {}
+                _ => {
+                    return Err(NotifierError::Configuration(
+                        "Custom explorer needs an account_url with {address} and a \
+                         transaction_url with {signature}"
+                            .to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Link to an account or program page.
+    pub fn account_url(&self, address: &str) -> Option<String> {
+        match self.provider {
+            ExplorerProvider::Custom => self.account_url.as_ref().map(|url| {
+                url.replace("{address}", address)
+                    .replace("{cluster}", &self.cluster)
+            }),
+            _ => self
+                .explorer()
+                .map(|explorer| explorer.account_url(address, &self.cluster)),
+        }
+    }
+
+    /// Link to a transaction page.
+    pub fn transaction_url(&self, signature: &str) -> Option<String> {
+        match self.provider {
+            ExplorerProvider::Custom => self.transaction_url.as_ref().map(|url| {
+                url.replace("{signature}", signature)
+                    .replace("{cluster}", &self.cluster)
+            }),
+            _ => self
+                .explorer()
+                .map(|explorer| explorer.transaction_url(signature, &self.cluster)),
+        }
+    }
+
+    /// Links of an alert, available to templates as `links`: `program`, and
+    /// `transaction` and `account` when the alert has a signature and account.
+    pub fn links(&self, alert: &Alert) -> Value {
+        let mut links = Map::new();
+        let reference = |key: &str| alert.metadata.get(key).and_then(Value::as_str);
+
+        if let Some(url) = self.account_url(&alert.program_id.to_string()) {
+            links.insert("program".to_string(), url.into());
+        }
+        if let Some(url) = reference(SIGNATURE_KEY).and_then(|s| self.transaction_url(s)) {
+            links.insert("transaction".to_string(), url.into());
+        }
+        if let Some(url) = reference(ACCOUNT_KEY).and_then(|a| self.account_url(a)) {
+            links.insert("account".to_string(), url.into());
+        }
+
+        links.into()
+    }
+
+    /// Built-in explorer of the provider.
+    fn explorer(&self) -> Option<Explorer> {
+        match self.provider {
+            ExplorerProvider::Solscan => Some(Explorer::Solscan),
+            ExplorerProvider::SolanaFm => Some(Explorer::SolanaFm),
+            ExplorerProvider::Xray => Some(Explorer::Xray),
+            ExplorerProvider::Custom | ExplorerProvider::None => None,
+        }
+    }
+}
+
+impl Default for ExplorerConfig {
+    fn default() -> Self {
+        Self {
+            provider: default_provider(),
+            cluster: default_cluster(),
+            account_url: None,
+            transaction_url: None,
+        }
+    }
+}
+
+// Default value functions
+fn default_provider() -> ExplorerProvider {
+    ExplorerProvider::Solscan
+}
+
+fn default_cluster() -> String {
+    "mainnet-beta".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use watchtower_engine::AlertSeverity;
+
+    #[test]
+    fn test_explorer_links() {
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transaction detected".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        // Without a signature or account only the program is linked
+        let links = ExplorerConfig::default().links(&alert);
+        assert_eq!(
+            links["program"],
+            format!("https://solscan.io/account/{}", alert.program_id)
+        );
+        assert!(links.get("transaction").is_none());
+
+        alert
+            .metadata
+            .insert(SIGNATURE_KEY.to_string(), "5sig".into());
+        alert
+            .metadata
+            .insert(ACCOUNT_KEY.to_string(), "Wallet111".into());
+
+        let xray = ExplorerConfig {
+            provider: ExplorerProvider::Xray,
+            ..Default::default()
+        };
+        assert_eq!(
+            xray.links(&alert)["transaction"],
+            "https://xray.helius.xyz/tx/5sig"
+        );
+
+        let mut custom = ExplorerConfig {
+            provider: ExplorerProvider::Custom,
+            cluster: "devnet".to_string(),
+            account_url: Some("https://explorer.internal/{cluster}/address/{address}".to_string()),
+            transaction_url: None,
+        };
+        assert!(custom.validate().is_err());
+        custom.transaction_url =
+            Some("https://explorer.internal/{cluster}/tx/{signature}".to_string());
+        assert!(custom.validate().is_ok());
+
+        let links = custom.links(&alert);
+        assert_eq!(
+            links["transaction"],
+            "https://explorer.internal/devnet/tx/5sig"
+        );
+        assert_eq!(
+            links["account"],
+            "https://explorer.internal/devnet/address/Wallet111"
+        );
+
+        let none = ExplorerConfig {
+            provider: ExplorerProvider::None,
+            ..Default::default()
+        };
+        assert_eq!(none.links(&alert), Value::Object(Map::new()));
+    }
+}
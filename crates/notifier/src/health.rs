@@ -0,0 +1,174 @@
+//! Channel health checks and circuit breakers.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use watchtower_engine::{Alert, AlertSeverity};
+
+/// Health check and circuit breaker configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckConfig {
+    /// Whether channels are tested in the background and broken ones bypassed
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds between health checks
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+
+    /// Consecutive failures (deliveries or health checks) that open a channel's circuit
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// Channels receiving alerts in place of a channel whose circuit is open
+    #[serde(default)]
+    pub fallbacks: HashMap<String, Vec<String>>,
+}
+
+/// Circuit state of a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum CircuitState {
+    /// The channel is used normally
+    Closed,
+
+    /// The channel is bypassed until a health check succeeds
+    Open { since: DateTime<Utc> },
+}
+
+/// Counts consecutive failures of a channel and opens once the threshold is reached.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// Consecutive failures that open the circuit
+    threshold: u32,
+
+    /// Current state and consecutive failures
+    inner: Mutex<(CircuitState, u32)>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker.
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            inner: Mutex::new((CircuitState::Closed, 0)),
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> CircuitState {
+        self.lock().0
+    }
+
+    /// Whether the channel is bypassed.
+    pub fn is_open(&self) -> bool {
+        matches!(self.state(), CircuitState::Open { .. })
+    }
+
+    /// Record a success, returning whether this closed the circuit.
+    pub fn record_success(&self) -> bool {
+        let mut inner = self.lock();
+        let was_open = matches!(inner.0, CircuitState::Open { .. });
+        *inner = (CircuitState::Closed, 0);
+        was_open
+    }
+
+    /// Record a failure, returning whether this opened the circuit.
+    pub fn record_failure(&self) -> bool {
+        let mut inner = self.lock();
+        inner.1 += 1;
+        if inner.0 == CircuitState::Closed && inner.1 >= self.threshold {
+            inner.0 = CircuitState::Open { since: Utc::now() };
+            return true;
+        }
+        false
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (CircuitState, u32)> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Meta-alert about a channel whose circuit opened.
+pub fn channel_down_alert(channel: &str, error: &str, fallbacks: &[String]) -> Alert {
+    let now = Utc::now();
+    let mut metadata = HashMap::new();
+    metadata.insert("channel".to_string(), serde_json::json!(channel));
+    metadata.insert("error".to_string(), serde_json::json!(error));
+    metadata.insert("fallbacks".to_string(), serde_json::json!(fallbacks));
+
+    let routing = if fallbacks.is_empty() {
+        "its alerts are not delivered".to_string()
+    } else {
+        format!("its alerts are routed to {}", fallbacks.join(", "))
+    };
+
+    Alert {
+        id: format!("channel-down-{}-{}", channel, now.timestamp()),
+        // One rule per channel so outages of different channels are not deduplicated
+        rule_name: format!("{}_channel_down", channel),
+        message: format!(
+            "Notification channel {} is failing and {} until it recovers: {}",
+            channel, routing, error
+        ),
+        severity: AlertSeverity::High,
+        program_id: solana_sdk::pubkey::Pubkey::default(),
+        program_name: "watchtower".to_string(),
+        event_id: None,
+        metadata,
+        confidence: 1.0,
+        suggested_actions: vec![format!(
+            "Check the {} channel configuration and the provider's status",
+            channel
+        )],
+        timestamp: now,
+        acknowledged: false,
+        resolved: false,
+        tags: Vec::new(),
+    }
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: default_interval_seconds(),
+            failure_threshold: default_failure_threshold(),
+            fallbacks: HashMap::new(),
+        }
+    }
+}
+
+// Default value functions
+fn default_interval_seconds() -> u64 {
+    300
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(2);
+
+        // A success resets the failure count
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_success());
+        assert!(!breaker.record_failure());
+        assert!(!breaker.is_open());
+
+        // Only the failure reaching the threshold reports the transition
+        assert!(breaker.record_failure());
+        assert!(breaker.is_open());
+        assert!(!breaker.record_failure());
+
+        assert!(breaker.record_success());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
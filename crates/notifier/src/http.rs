@@ -0,0 +1,231 @@
+//! Outbound HTTP settings: proxies, CA certificates, timeouts and webhook
+//! authentication.
+
+use crate::error::{NotifierError, NotifierResult};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, Proxy, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// HTTP client configuration shared by the channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Proxy for all requests, e.g. `http://proxy.internal:3128`; without one
+    /// the `HTTPS_PROXY`/`HTTP_PROXY` environment variables apply
+    pub proxy: Option<String>,
+
+    /// PEM files of additional trusted CA certificates (e.g. of a TLS-inspecting proxy)
+    #[serde(default)]
+    pub ca_certificates: Vec<PathBuf>,
+
+    /// Request timeout in seconds
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+
+    /// Connection timeout in seconds
+    #[serde(default = "default_connect_timeout_seconds")]
+    pub connect_timeout_seconds: u64,
+
+    /// Per-channel overrides, keyed by channel name
+    #[serde(default)]
+    pub channels: HashMap<String, ChannelHttpConfig>,
+}
+
+/// HTTP settings overridden for one channel.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelHttpConfig {
+    /// Proxy for this channel; an empty string connects directly
+    pub proxy: Option<String>,
+
+    /// Request timeout in seconds
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Authentication for webhooks behind a gateway or proxy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookAuthConfig {
+    /// Extra request headers, e.g. `{ "X-Api-Key" = "..." }`
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Token sent as `Authorization: Bearer <token>`
+    pub bearer_token: Option<String>,
+}
+
+impl HttpConfig {
+    /// Validate the proxies and certificates by building every client.
+    pub fn validate(&self) -> NotifierResult<()> {
+        self.client("")?;
+        for channel in self.channels.keys() {
+            self.client(channel)?;
+        }
+        Ok(())
+    }
+
+    /// HTTP client of a channel.
+    pub fn client(&self, channel: &str) -> NotifierResult<Client> {
+        let overrides = self.channels.get(channel);
+        let timeout = overrides
+            .and_then(|o| o.timeout_seconds)
+            .unwrap_or(self.timeout_seconds);
+
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .connect_timeout(Duration::from_secs(self.connect_timeout_seconds));
+
+        match overrides
+            .and_then(|o| o.proxy.as_deref())
+            .or(self.proxy.as_deref())
+        {
+            Some("") => builder = builder.no_proxy(),
+            Some(proxy) => {
+                let proxy = Proxy::all(proxy).map_err(|e| {
+                    NotifierError::Configuration(format!("Invalid proxy '{}': {}", proxy, e))
+                })?;
+                builder = builder.proxy(proxy);
+            }
+            None => {}
+        }
+
+        for path in &self.ca_certificates {
+            let pem = std::fs::read(path).map_err(|e| {
+                NotifierError::Configuration(format!(
+                    "Failed to read CA certificate {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            let certificate = Certificate::from_pem(&pem).map_err(|e| {
+                NotifierError::Configuration(format!(
+                    "Invalid CA certificate {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        builder.build().map_err(|e| {
+            NotifierError::Configuration(format!(
+                "Failed to build HTTP client for {}: {}",
+                channel, e
+            ))
+        })
+    }
+}
+
+impl WebhookAuthConfig {
+    /// Whether neither headers nor a bearer token are configured.
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.bearer_token.is_none()
+    }
+
+    /// Validate the header names and values.
+    pub fn validate(&self, channel: &str) -> NotifierResult<()> {
+        for (name, value) in &self.headers {
+            if HeaderName::from_bytes(name.as_bytes()).is_err()
+                || HeaderValue::from_str(value).is_err()
+            {
+                return Err(NotifierError::Configuration(format!(
+                    "Invalid {} webhook header: {}",
+                    channel, name
+                )));
+            }
+        }
+
+        if self.bearer_token.as_deref().is_some_and(str::is_empty) {
+            return Err(NotifierError::Configuration(format!(
+                "{} webhook bearer token cannot be empty",
+                channel
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Add the headers and bearer token to a webhook request.
+    pub fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        request
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            ca_certificates: Vec::new(),
+            timeout_seconds: default_timeout_seconds(),
+            connect_timeout_seconds: default_connect_timeout_seconds(),
+            channels: HashMap::new(),
+        }
+    }
+}
+
+// Default value functions
+fn default_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_connect_timeout_seconds() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_clients_per_channel() {
+        let mut config = HttpConfig {
+            proxy: Some("http://proxy.internal:3128".to_string()),
+            channels: HashMap::from([(
+                "slack".to_string(),
+                ChannelHttpConfig {
+                    proxy: Some(String::new()),
+                    timeout_seconds: Some(5),
+                },
+            )]),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert!(config.client("telegram").is_ok());
+
+        config.proxy = Some("not a proxy url".to_string());
+        assert!(config.client("telegram").is_err());
+        // Channels connecting directly ignore the global proxy
+        assert!(config.client("slack").is_ok());
+
+        config.proxy = None;
+        config.ca_certificates = vec![PathBuf::from("/nonexistent/ca.pem")];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_webhook_auth_headers() {
+        let mut auth = WebhookAuthConfig {
+            headers: HashMap::from([("X-Api-Key".to_string(), "secret".to_string())]),
+            bearer_token: Some("token".to_string()),
+        };
+        assert!(auth.validate("slack").is_ok());
+
+        let request = auth
+            .apply(Client::new().post("https://gateway.internal/hooks/slack"))
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["x-api-key"], "secret");
+        assert_eq!(request.headers()["authorization"], "Bearer token");
+
+        auth.headers
+            .insert("Bad Header".to_string(), "value".to_string());
+        assert!(auth.validate("slack").is_err());
+        assert!(WebhookAuthConfig::default().is_empty());
+    }
+}
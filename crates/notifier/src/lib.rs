@@ -4,19 +4,69 @@
 //!
 //! This module provides:
 //! - Abstract notifier trait for multiple channels
-//! - Email notifications via SMTP
-//! - Telegram bot notifications
-//! - Slack and Discord webhook support
+//! - Secrets read from environment variables, files or Vault
+//! - Email notifications via SMTP, with password or OAuth2 authentication and DKIM signing
+//! - Telegram bot notifications and chat commands
+//! - Slack, Mattermost and Discord webhook support
+//! - PagerDuty incidents and Grafana OnCall alert groups resolved together with their alerts
+//! - Pushover and ntfy push notifications
+//! - SMS via Twilio and Signal via signal-cli for critical alerts
+//! - AWS SNS publishing for existing incident pipelines
+//! - Kafka and NATS publishing for stream-processing pipelines
+//! - A capture channel and dry-run mode recording notifications instead of sending
+//! - Charts of the metric window behind metric-driven alerts
+//! - User templates that are reloaded when they change
+//! - Translated messages with localized dates and numbers
+//! - Explorer links to the program, transaction and account behind alerts
+//! - Outbound HTTP proxies, custom CA certificates and timeouts
 //! - Rate limiting and alert batching
+//! - Notification statistics persisted across restarts and exported to Prometheus
+//! - Delivery retries with a persistent outbox
+//! - Escalation chains across channels until alerts are acknowledged
+//! - Per-channel quiet hours
+//! - Hourly or daily digests of low-severity alerts
+//! - Channel health checks with circuit breakers and fallback channels
 
+pub mod capture;
 pub mod channels;
+pub mod charts;
+pub mod commands;
 pub mod config;
+pub mod digest;
+pub mod dkim;
 pub mod error;
+pub mod escalation;
+pub mod explorer;
+pub mod health;
+pub mod http;
+pub mod locale;
 pub mod manager;
+pub mod oauth;
+pub mod outbox;
+pub mod schedule;
+pub mod secrets;
+pub mod stats;
+pub mod stream;
 pub mod templates;
 
+pub use capture::*;
 pub use channels::*;
+pub use charts::*;
+pub use commands::*;
 pub use config::*;
+pub use digest::*;
+pub use dkim::*;
 pub use error::*;
+pub use escalation::*;
+pub use explorer::*;
+pub use health::*;
+pub use http::*;
+pub use locale::*;
 pub use manager::*;
+pub use oauth::*;
+pub use outbox::*;
+pub use schedule::*;
+pub use secrets::*;
+pub use stats::*;
+pub use stream::*;
 pub use templates::*;
@@ -0,0 +1,218 @@
+//! Locales for translated built-in templates and localized dates and numbers.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use watchtower_engine::AlertSeverity;
+
+/// Language of rendered notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+
+    /// German
+    De,
+
+    /// Spanish
+    Es,
+
+    /// French
+    Fr,
+}
+
+/// Template labels in English, German, Spanish and French.
+const LABELS: &[(&str, [&str; 4])] = &[
+    (
+        "title",
+        [
+            "Solana Watchtower Alert",
+            "Solana Watchtower Alarm",
+            "Alerta de Solana Watchtower",
+            "Alerte Solana Watchtower",
+        ],
+    ),
+    (
+        "subtitle",
+        [
+            "Security Alert Notification",
+            "Sicherheitsbenachrichtigung",
+            "Notificación de alerta de seguridad",
+            "Notification d'alerte de sécurité",
+        ],
+    ),
+    ("alert", ["Alert", "Alarm", "Alerta", "Alerte"]),
+    (
+        "severity",
+        ["Severity", "Schweregrad", "Gravedad", "Gravité"],
+    ),
+    ("rule", ["Rule", "Regel", "Regla", "Règle"]),
+    ("program", ["Program", "Programm", "Programa", "Programme"]),
+    ("message", ["Message", "Nachricht", "Mensaje", "Message"]),
+    (
+        "confidence",
+        ["Confidence", "Konfidenz", "Confianza", "Confiance"],
+    ),
+    ("time", ["Time", "Zeit", "Hora", "Heure"]),
+    (
+        "suggested_actions",
+        [
+            "Suggested Actions",
+            "Empfohlene Maßnahmen",
+            "Acciones sugeridas",
+            "Actions suggérées",
+        ],
+    ),
+    (
+        "details",
+        [
+            "Additional Details",
+            "Weitere Details",
+            "Detalles adicionales",
+            "Détails supplémentaires",
+        ],
+    ),
+    ("links", ["Links", "Links", "Enlaces", "Liens"]),
+    (
+        "transaction",
+        ["Transaction", "Transaktion", "Transacción", "Transaction"],
+    ),
+    ("account", ["Account", "Konto", "Cuenta", "Compte"]),
+    (
+        "alert_id",
+        ["Alert ID", "Alarm-ID", "ID de alerta", "ID de l'alerte"],
+    ),
+    (
+        "generated_by",
+        [
+            "This alert was generated by Solana Watchtower",
+            "Dieser Alarm wurde von Solana Watchtower erzeugt",
+            "Esta alerta fue generada por Solana Watchtower",
+            "Cette alerte a été générée par Solana Watchtower",
+        ],
+    ),
+];
+
+impl Locale {
+    /// Parse a locale code such as `de`, `de-DE` or `es_ES` by its language.
+    pub fn parse(code: &str) -> Option<Self> {
+        let language = code
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            "es" => Some(Locale::Es),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    /// Language code of the locale.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+        }
+    }
+
+    /// Template labels keyed by name, available to templates as `t`.
+    pub fn labels(&self) -> HashMap<&'static str, &'static str> {
+        LABELS
+            .iter()
+            .map(|(key, translations)| (*key, translations[self.index()]))
+            .collect()
+    }
+
+    /// Name of a severity.
+    pub fn severity(&self, severity: &AlertSeverity) -> &'static str {
+        let names = match severity {
+            AlertSeverity::Critical => ["critical", "kritisch", "crítica", "critique"],
+            AlertSeverity::High => ["high", "hoch", "alta", "élevée"],
+            AlertSeverity::Medium => ["medium", "mittel", "media", "moyenne"],
+            AlertSeverity::Low => ["low", "niedrig", "baja", "faible"],
+            AlertSeverity::Info => ["info", "info", "info", "info"],
+        };
+        names[self.index()]
+    }
+
+    /// Date and time in the locale's usual order.
+    pub fn format_datetime(&self, timestamp: &DateTime<Utc>) -> String {
+        let format = match self {
+            Locale::En => "%Y-%m-%d %H:%M:%S UTC",
+            Locale::De => "%d.%m.%Y %H:%M:%S UTC",
+            Locale::Es | Locale::Fr => "%d/%m/%Y %H:%M:%S UTC",
+        };
+        timestamp.format(format).to_string()
+    }
+
+    /// Number with the locale's decimal and thousands separators.
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        let (thousands, decimal) = match self {
+            Locale::En => (",", "."),
+            Locale::De | Locale::Es => (".", ","),
+            // Narrow no-break space
+            Locale::Fr => ("\u{202f}", ","),
+        };
+
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = formatted
+            .split_once('.')
+            .map_or((formatted.as_str(), None), |(i, f)| (i, Some(f)));
+
+        let mut grouped = String::new();
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push_str(thousands);
+            }
+            grouped.push(digit);
+        }
+
+        let sign = if value < 0.0 { "-" } else { "" };
+        match fraction {
+            Some(fraction) => format!("{}{}{}{}", sign, grouped, decimal, fraction),
+            None => format!("{}{}", sign, grouped),
+        }
+    }
+
+    /// Column of the locale in the translation tables.
+    fn index(&self) -> usize {
+        match self {
+            Locale::En => 0,
+            Locale::De => 1,
+            Locale::Es => 2,
+            Locale::Fr => 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_locale_formatting() {
+        assert_eq!(Locale::parse("de-DE"), Some(Locale::De));
+        assert_eq!(Locale::parse("es_ES"), Some(Locale::Es));
+        assert_eq!(Locale::parse("xx"), None);
+
+        assert_eq!(Locale::En.format_number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(Locale::De.format_number(1234.5, 1), "1.234,5");
+        assert_eq!(Locale::Fr.format_number(-98.26, 1), "-98,3");
+        assert_eq!(Locale::Es.format_number(999.0, 0), "999");
+
+        let timestamp = Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap();
+        assert_eq!(
+            Locale::De.format_datetime(&timestamp),
+            "09.03.2024 14:05:00 UTC"
+        );
+        assert_eq!(Locale::De.severity(&AlertSeverity::High), "hoch");
+        assert_eq!(Locale::Fr.labels()["time"], "Heure");
+    }
+}
@@ -1,34 +1,43 @@
 //! Notification manager that coordinates all channels with rate limiting and batching.
 
 use crate::{
-    channels::{DiscordChannel, EmailChannel, NotificationChannel, SlackChannel, TelegramChannel},
-    config::{NotificationFilter, NotifierConfig},
-    error::NotifierResult,
+    capture::{CaptureChannel, CaptureSink, CapturedNotification},
+    channels::{
+        DiscordChannel, EmailChannel, GrafanaOnCallChannel, MattermostChannel, NotificationChannel,
+        NtfyChannel, PagerDutyChannel, PushoverChannel, SignalChannel, SlackChannel, SmsChannel,
+        SnsChannel, TelegramChannel,
+    },
+    commands::{BotCommand, TelegramBot, BOT_COMMANDS_HELP},
+    config::{parse_severity, NotificationFilter, NotifierConfig},
+    digest::Digest,
+    error::{NotifierError, NotifierResult},
+    escalation::Escalation,
+    health::{channel_down_alert, CircuitBreaker, CircuitState},
+    outbox::Outbox,
+    schedule::QuietHours,
+    stats::NotificationMetrics,
+    stream::{KafkaChannel, NatsChannel},
+    templates::TemplateEngine,
 };
-use governor::{Quota, RateLimiter};
+use governor::{DefaultKeyedRateLimiter, RateLimiter};
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
-use tokio::time::interval;
+use tokio::time::{interval_at, Instant};
 use tracing::{debug, error, info, warn};
-use watchtower_engine::Alert;
+use watchtower_engine::{Alert, AlertFilter, AlertManager, AlertTransition, DeliveryOutcome};
 
 /// Notification manager that handles all notification channels.
 pub struct NotificationManager {
     /// Configured notification channels
     channels: HashMap<String, Box<dyn NotificationChannel>>,
 
-    /// Rate limiters per channel
-    rate_limiters: HashMap<
-        String,
-        RateLimiter<
-            governor::state::NotKeyed,
-            governor::state::InMemoryState,
-            governor::clock::DefaultClock,
-        >,
-    >,
+    /// Rate limiters per channel, keyed by destination (chat, webhook, ...)
+    rate_limiters: HashMap<String, DefaultKeyedRateLimiter<String>>,
 
     /// Configuration
     config: NotifierConfig,
@@ -36,13 +45,58 @@ pub struct NotificationManager {
     /// Alert batching system
     batch_manager: Option<BatchManager>,
 
+    /// Batches ready for delivery, taken by the dispatcher task
+    batch_receiver: Mutex<Option<mpsc::UnboundedReceiver<Batch>>>,
+
     /// Notification filters
     filters: Vec<NotificationFilter>,
 
+    /// Notifications awaiting redelivery
+    outbox: Outbox,
+
+    /// Quiet hours per channel
+    quiet_hours: HashMap<String, QuietHours>,
+
+    /// Alerts held per channel until its quiet hours end
+    held_alerts: RwLock<HashMap<String, Vec<Alert>>>,
+
+    /// Digests collected per channel since they were last sent
+    digests: RwLock<HashMap<String, Digest>>,
+
+    /// Circuit breakers per channel
+    breakers: HashMap<String, CircuitBreaker>,
+
+    /// Muted rules and when their mute ends
+    muted_rules: RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>,
+
+    /// Alerts waiting for their next escalation step, keyed by alert ID
+    escalations: RwLock<HashMap<String, Escalation>>,
+
+    /// When an alert fingerprint was last sent, keyed by channel and fingerprint
+    recently_sent: Mutex<HashMap<(String, String), Instant>>,
+
+    /// Channels switched off at runtime, e.g. from the dashboard
+    disabled_channels: std::sync::RwLock<HashSet<String>>,
+
+    /// Templates shared by the channels
+    templates: TemplateEngine,
+
+    /// Notifications recorded by the capture channels
+    capture: Option<CaptureSink>,
+
     /// Statistics
     stats: Arc<RwLock<NotificationStats>>,
+
+    /// Prometheus counters mirroring the statistics
+    metrics: Option<NotificationMetrics>,
+
+    /// Alert manager recording delivery attempts
+    alert_manager: Option<Arc<AlertManager>>,
 }
 
+/// Alerts collected for one channel.
+type Batch = (String, Vec<Alert>);
+
 /// Batch manager for collecting and sending batched notifications.
 struct BatchManager {
     /// Pending alerts per channel
@@ -55,12 +109,32 @@ struct BatchManager {
     /// Maximum batch size
     max_batch_size: usize,
 
+    /// Sender of batches that are full or timed out
+    ready_tx: mpsc::UnboundedSender<Batch>,
+
     /// Shutdown sender
     shutdown_tx: mpsc::Sender<()>,
 }
 
+/// Notifications waiting to be delivered.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct NotificationQueues {
+    /// Alerts collected for batches not yet sent
+    pub batched: usize,
+
+    /// Alerts held until their channel's quiet hours end
+    pub held: usize,
+
+    /// Failed notifications awaiting redelivery
+    pub outbox: usize,
+
+    /// Alerts waiting for their next escalation step
+    pub escalations: usize,
+}
+
 /// Notification statistics.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NotificationStats {
     /// Total notifications sent
     pub total_sent: u64,
@@ -71,12 +145,24 @@ pub struct NotificationStats {
     /// Failed notifications
     pub total_failed: u64,
 
+    /// Failed notifications per channel
+    pub failed_per_channel: HashMap<String, u64>,
+
     /// Rate limited notifications
     pub rate_limited: u64,
 
+    /// Rate limited notifications per channel
+    pub rate_limited_per_channel: HashMap<String, u64>,
+
     /// Batched notifications
     pub batched: u64,
 
+    /// Notifications delivered from the outbox after failing
+    pub redelivered: u64,
+
+    /// Notifications suppressed as repeats within the dedup window
+    pub deduplicated: u64,
+
     /// Last notification time
     pub last_notification: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -86,72 +172,195 @@ impl NotificationManager {
     pub async fn new(config: NotifierConfig) -> NotifierResult<Self> {
         config.validate()?;
 
+        let templates = match &config.templates.directory {
+            Some(directory) => TemplateEngine::with_directory(directory)?,
+            None => TemplateEngine::new(),
+        }
+        .with_explorer(config.templates.explorer.clone());
+        // Channels share the templates, each rendering in its own locale
+        let channel_templates =
+            |channel: &str| templates.with_locale(config.templates.locale_for(channel));
+
         let mut channels: HashMap<String, Box<dyn NotificationChannel>> = HashMap::new();
-        let mut rate_limiters = HashMap::new();
 
         // Initialize email channel
         if let Some(email_config) = &config.email {
-            let channel = EmailChannel::new(email_config.clone())?;
+            let channel = EmailChannel::new(email_config.clone())?
+                .with_template_engine(channel_templates("email"))
+                .with_http_client(config.http.client("email")?)
+                .with_program_routes(config.program_routes.clone());
             channels.insert("email".to_string(), Box::new(channel));
-
-            let rate_limiter = RateLimiter::direct(Quota::per_minute(
-                std::num::NonZeroU32::new(config.rate_limiting.max_messages_per_minute)
-                    .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-            ));
-            rate_limiters.insert("email".to_string(), rate_limiter);
         }
 
         // Initialize Telegram channel
         if let Some(telegram_config) = &config.telegram {
-            let channel = TelegramChannel::new(telegram_config.clone());
+            let channel = TelegramChannel::new(telegram_config.clone())
+                .with_template_engine(channel_templates("telegram"))
+                .with_http_client(config.http.client("telegram")?)
+                .with_program_routes(config.program_routes.clone());
             channels.insert("telegram".to_string(), Box::new(channel));
-
-            let rate_limiter = RateLimiter::direct(Quota::per_minute(
-                std::num::NonZeroU32::new(config.rate_limiting.max_messages_per_minute)
-                    .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-            ));
-            rate_limiters.insert("telegram".to_string(), rate_limiter);
         }
 
         // Initialize Slack channel
         if let Some(slack_config) = &config.slack {
-            let channel = SlackChannel::new(slack_config.clone());
+            let channel = SlackChannel::new(slack_config.clone())
+                .with_template_engine(channel_templates("slack"))
+                .with_http_client(config.http.client("slack")?)
+                .with_program_routes(config.program_routes.clone());
             channels.insert("slack".to_string(), Box::new(channel));
-
-            let rate_limiter = RateLimiter::direct(Quota::per_minute(
-                std::num::NonZeroU32::new(config.rate_limiting.max_messages_per_minute)
-                    .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-            ));
-            rate_limiters.insert("slack".to_string(), rate_limiter);
         }
 
         // Initialize Discord channel
         if let Some(discord_config) = &config.discord {
-            let channel = DiscordChannel::new(discord_config.clone());
+            let channel = DiscordChannel::new(discord_config.clone())
+                .with_template_engine(channel_templates("discord"))
+                .with_http_client(config.http.client("discord")?);
             channels.insert("discord".to_string(), Box::new(channel));
+        }
 
-            let rate_limiter = RateLimiter::direct(Quota::per_minute(
-                std::num::NonZeroU32::new(config.rate_limiting.max_messages_per_minute)
-                    .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-            ));
-            rate_limiters.insert("discord".to_string(), rate_limiter);
+        // Initialize Mattermost channel
+        if let Some(mattermost_config) = &config.mattermost {
+            let channel = MattermostChannel::new(mattermost_config.clone())
+                .with_template_engine(channel_templates("mattermost"))
+                .with_http_client(config.http.client("mattermost")?);
+            channels.insert("mattermost".to_string(), Box::new(channel));
         }
 
-        // Initialize batch manager if batching is enabled
-        let batch_manager = if config.global.enable_batching {
-            Some(
-                BatchManager::new(
-                    Duration::from_secs(config.global.batch_timeout_seconds),
-                    config.global.batch_size,
+        // Initialize PagerDuty channel
+        if let Some(pagerduty_config) = &config.pagerduty {
+            let channel = PagerDutyChannel::new(pagerduty_config.clone())
+                .with_http_client(config.http.client("pagerduty")?);
+            channels.insert("pagerduty".to_string(), Box::new(channel));
+        }
+
+        // Initialize Grafana OnCall channel
+        if let Some(grafana_oncall_config) = &config.grafana_oncall {
+            let channel = GrafanaOnCallChannel::new(grafana_oncall_config.clone())
+                .with_template_engine(channel_templates("grafana_oncall"))
+                .with_http_client(config.http.client("grafana_oncall")?);
+            channels.insert("grafana_oncall".to_string(), Box::new(channel));
+        }
+
+        // Initialize Pushover channel
+        if let Some(pushover_config) = &config.pushover {
+            let channel = PushoverChannel::new(pushover_config.clone())
+                .with_template_engine(channel_templates("pushover"))
+                .with_http_client(config.http.client("pushover")?);
+            channels.insert("pushover".to_string(), Box::new(channel));
+        }
+
+        // Initialize ntfy channel
+        if let Some(ntfy_config) = &config.ntfy {
+            let channel = NtfyChannel::new(ntfy_config.clone())
+                .with_template_engine(channel_templates("ntfy"))
+                .with_http_client(config.http.client("ntfy")?);
+            channels.insert("ntfy".to_string(), Box::new(channel));
+        }
+
+        // Initialize SNS channel
+        if let Some(sns_config) = &config.sns {
+            let channel = SnsChannel::new(sns_config.clone()).await;
+            channels.insert("sns".to_string(), Box::new(channel));
+        }
+
+        // Initialize Kafka and NATS channels
+        if let Some(kafka_config) = &config.kafka {
+            let channel = KafkaChannel::new(kafka_config.clone());
+            channels.insert("kafka".to_string(), Box::new(channel));
+        }
+        if let Some(nats_config) = &config.nats {
+            let channel = NatsChannel::new(nats_config.clone());
+            channels.insert("nats".to_string(), Box::new(channel));
+        }
+
+        // Initialize SMS channel (also rate limited per recipient number)
+        if let Some(sms_config) = &config.sms {
+            let channel = SmsChannel::new(sms_config.clone())
+                .with_template_engine(channel_templates("sms"))
+                .with_http_client(config.http.client("sms")?);
+            channels.insert("sms".to_string(), Box::new(channel));
+        }
+
+        // Initialize Signal channel (critical alerts only by default)
+        if let Some(signal_config) = &config.signal {
+            let channel = SignalChannel::new(signal_config.clone())
+                .with_template_engine(channel_templates("signal"))
+                .with_http_client(config.http.client("signal")?);
+            channels.insert("signal".to_string(), Box::new(channel));
+        }
+
+        // Initialize capture channel, standing in for every channel in a dry run
+        let capture = config.capture.as_ref().map(CaptureSink::new);
+        if let (Some(capture_config), Some(sink)) = (&config.capture, &capture) {
+            if capture_config.dry_run {
+                info!("Dry run: notifications are captured instead of sent");
+                channels.clear();
+            }
+            for name in config.enabled_channels() {
+                if capture_config.dry_run || name == "capture" {
+                    let channel = CaptureChannel::for_channel(&config, &name, sink.clone())
+                        .with_template_engine(channel_templates(&name));
+                    channels.insert(name, Box::new(channel));
+                }
+            }
+        }
+
+        let breakers = channels
+            .keys()
+            .map(|name| {
+                (
+                    name.clone(),
+                    CircuitBreaker::new(config.health.failure_threshold),
                 )
-                .await?,
+            })
+            .collect();
+
+        let rate_limiters = channels
+            .keys()
+            .map(|name| {
+                let quota = config.rate_limiting.quota(name);
+                (name.clone(), RateLimiter::keyed(quota))
+            })
+            .collect();
+
+        // Initialize batch manager if batching is enabled
+        let (batch_manager, batch_receiver) = if config.global.enable_batching {
+            let (ready_tx, ready_rx) = mpsc::unbounded_channel();
+            let batch_manager = BatchManager::new(
+                Duration::from_secs(config.global.batch_timeout_seconds),
+                config.global.batch_size,
+                ready_tx,
             )
+            .await?;
+            (Some(batch_manager), Some(ready_rx))
         } else {
-            None
+            (None, None)
         };
 
         let filters = config.global.filters.clone().unwrap_or_default();
 
+        let quiet_hours = config
+            .quiet_hours
+            .iter()
+            .map(|(channel, schedule)| Ok((channel.clone(), QuietHours::from_config(schedule)?)))
+            .collect::<NotifierResult<HashMap<_, _>>>()?;
+
+        let outbox = match &config.retry.outbox_path {
+            Some(path) => Outbox::open(path, config.retry.outbox_capacity)?,
+            None => Outbox::in_memory(config.retry.outbox_capacity),
+        };
+        if !outbox.is_empty() {
+            info!(
+                "{} undelivered notification(s) pending in the outbox",
+                outbox.len()
+            );
+        }
+
+        let stats = match &config.global.stats_path {
+            Some(path) => NotificationStats::load(path)?,
+            None => NotificationStats::default(),
+        };
+
         info!(
             "Notification manager initialized with {} channels",
             channels.len()
@@ -162,215 +371,1358 @@ impl NotificationManager {
             rate_limiters,
             config,
             batch_manager,
+            batch_receiver: Mutex::new(batch_receiver),
             filters,
-            stats: Arc::new(RwLock::new(NotificationStats::default())),
+            outbox,
+            quiet_hours,
+            held_alerts: RwLock::new(HashMap::new()),
+            digests: RwLock::new(HashMap::new()),
+            muted_rules: RwLock::new(HashMap::new()),
+            escalations: RwLock::new(HashMap::new()),
+            recently_sent: Mutex::new(HashMap::new()),
+            disabled_channels: std::sync::RwLock::new(HashSet::new()),
+            templates,
+            capture,
+            breakers,
+            stats: Arc::new(RwLock::new(stats)),
+            metrics: None,
+            alert_manager: None,
         })
     }
 
-    /// Send a notification for an alert.
-    pub async fn send_notification(&self, alert: Alert) -> NotifierResult<()> {
-        debug!("Processing notification for alert: {}", alert.id);
+    /// Record delivery attempts in the history and audit trail of `alert_manager`.
+    pub fn with_alert_manager(mut self, alert_manager: Arc<AlertManager>) -> Self {
+        self.alert_manager = Some(alert_manager);
+        self
+    }
 
-        // Check minimum severity
-        if !self.meets_minimum_severity(&alert) {
-            debug!("Alert {} below minimum severity threshold", alert.id);
-            return Ok(());
+    /// Export the statistics as counters on a Prometheus registry.
+    pub fn with_metrics_registry(mut self, registry: &Registry) -> NotifierResult<Self> {
+        let metrics = NotificationMetrics::register(registry)?;
+        if let Ok(stats) = self.stats.try_read() {
+            metrics.update(&stats);
         }
+        self.metrics = Some(metrics);
+        Ok(self)
+    }
 
-        // Apply filters
-        let channels_to_notify = self.apply_filters(&alert).await;
+    /// Start the background tasks: batch dispatch, outbox retries, the
+    /// release of alerts held during quiet hours, periodic digests,
+    /// channel health checks, Telegram bot commands, template reloads and
+    /// escalation steps.
+    pub fn start_background_tasks(self: &Arc<Self>) {
+        self.start_batch_dispatch();
+        self.start_outbox_retry();
+        self.start_quiet_hours_release();
+        self.start_digests();
+        self.start_health_checks();
+        self.start_telegram_commands();
+        self.start_template_reload();
+        self.start_escalations();
+    }
 
-        if channels_to_notify.is_empty() {
-            debug!("No channels to notify for alert {}", alert.id);
-            return Ok(());
-        }
+    /// Start delivering batches when batching is enabled.
+    ///
+    /// Batches are handed over when they reach `batch_size` or when the
+    /// batch timeout fires; without the dispatcher they are never sent.
+    pub fn start_batch_dispatch(self: &Arc<Self>) {
+        let Some(mut receiver) = self.batch_receiver.lock().unwrap().take() else {
+            return;
+        };
 
-        // Handle batching vs immediate sending
-        if self.config.global.enable_batching {
-            self.add_to_batch(alert, channels_to_notify).await?;
-        } else {
-            self.send_immediate(alert, channels_to_notify).await?;
-        }
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while let Some((channel_name, alerts)) = receiver.recv().await {
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
 
-        Ok(())
+                if let Err(e) = manager.send_batch(alerts, &channel_name).await {
+                    error!("Failed to dispatch batch via {}: {}", channel_name, e);
+                }
+            }
+            debug!("Batch dispatcher stopped");
+        });
     }
 
-    /// Send notification immediately to specified channels.
-    async fn send_immediate(&self, alert: Alert, channels: Vec<String>) -> NotifierResult<()> {
-        let template_data = self.create_template_data(&alert);
+    /// Start retrying notifications from the outbox in the background.
+    pub fn start_outbox_retry(self: &Arc<Self>) {
+        let poll_interval =
+            Duration::from_secs(self.config.retry.outbox_poll_interval_seconds.max(1));
+        let manager = Arc::downgrade(self);
 
-        for channel_name in channels {
-            if let Some(channel) = self.channels.get(&channel_name) {
-                // Check rate limit
-                if self.config.rate_limiting.enabled {
-                    if let Some(rate_limiter) = self.rate_limiters.get(&channel_name) {
-                        if rate_limiter.check().is_err() {
-                            warn!("Rate limit exceeded for channel: {}", channel_name);
-                            self.update_stats(|stats| stats.rate_limited += 1).await;
-                            continue;
-                        }
-                    }
-                }
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + poll_interval, poll_interval);
 
-                // Send notification
-                match channel.send(&alert, &template_data).await {
-                    Ok(_) => {
-                        info!("Notification sent successfully via {}", channel_name);
-                        self.update_stats(|stats| {
-                            stats.total_sent += 1;
-                            *stats
-                                .sent_per_channel
-                                .entry(channel_name.clone())
-                                .or_insert(0) += 1;
-                            stats.last_notification = Some(chrono::Utc::now());
-                        })
-                        .await;
-                    }
-                    Err(e) => {
-                        error!("Failed to send notification via {}: {}", channel_name, e);
-                        self.update_stats(|stats| stats.total_failed += 1).await;
-                        return Err(e);
-                    }
-                }
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.retry_outbox().await;
             }
+        });
+    }
+
+    /// Start releasing held alerts once their channel's quiet hours end.
+    pub fn start_quiet_hours_release(self: &Arc<Self>) {
+        if self.quiet_hours.is_empty() {
+            return;
         }
 
-        Ok(())
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = interval_at(
+                Instant::now() + Duration::from_secs(60),
+                Duration::from_secs(60),
+            );
+
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.release_held_alerts().await;
+            }
+        });
     }
 
-    /// Add alert to batch for later sending.
-    async fn add_to_batch(&self, alert: Alert, channels: Vec<String>) -> NotifierResult<()> {
-        if let Some(batch_manager) = &self.batch_manager {
-            batch_manager.add_alert(alert, channels).await;
+    /// Start sending digests at the configured interval.
+    pub fn start_digests(self: &Arc<Self>) {
+        if !self.config.digest.enabled {
+            return;
         }
-        Ok(())
+
+        let period = self.config.digest.interval.duration();
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + period, period);
+
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.send_digests().await;
+            }
+        });
     }
 
-    /// Send batched notifications.
-    pub async fn send_batch(&self, alerts: Vec<Alert>, channel_name: &str) -> NotifierResult<()> {
-        if alerts.is_empty() {
-            return Ok(());
+    /// Start testing channels at the configured interval.
+    pub fn start_health_checks(self: &Arc<Self>) {
+        if !self.config.health.enabled {
+            return;
         }
 
-        if let Some(channel) = self.channels.get(channel_name) {
-            if channel.supports_batching() {
-                let template_data = self.create_batch_template_data(&alerts);
+        let period = Duration::from_secs(self.config.health.interval_seconds.max(1));
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + period, period);
 
-                // Check rate limit
-                if self.config.rate_limiting.enabled {
-                    if let Some(rate_limiter) = self.rate_limiters.get(channel_name) {
-                        if rate_limiter.check().is_err() {
-                            warn!("Rate limit exceeded for batch on channel: {}", channel_name);
-                            self.update_stats(|stats| stats.rate_limited += 1).await;
-                            return Ok(());
-                        }
-                    }
-                }
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.run_health_checks().await;
+            }
+        });
+    }
 
-                match channel.send_batch(&alerts, &template_data).await {
-                    Ok(_) => {
-                        info!(
-                            "Batch notification sent successfully via {} ({} alerts)",
-                            channel_name,
-                            alerts.len()
-                        );
-                        self.update_stats(|stats| {
-                            stats.total_sent += 1;
-                            stats.batched += alerts.len() as u64;
-                            *stats
-                                .sent_per_channel
-                                .entry(channel_name.to_string())
-                                .or_insert(0) += 1;
-                            stats.last_notification = Some(chrono::Utc::now());
-                        })
-                        .await;
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to send batch notification via {}: {}",
-                            channel_name, e
-                        );
-                        self.update_stats(|stats| stats.total_failed += 1).await;
+    /// Start answering Telegram bot commands from allowed users.
+    pub fn start_telegram_commands(self: &Arc<Self>) {
+        let Some(telegram) = self.config.telegram.as_ref().filter(|t| t.commands.enabled) else {
+            return;
+        };
+        if self.config.capture.as_ref().is_some_and(|c| c.dry_run) {
+            return;
+        }
 
-                        // Fallback to individual notifications
+        let mut bot = TelegramBot::new(
+            telegram.bot_token.clone(),
+            Duration::from_secs(telegram.commands.poll_timeout_seconds),
+        );
+        // The HTTP settings were validated when the manager was created
+        if let Ok(client) = self.config.http.client("telegram") {
+            bot = bot.with_client(client);
+        }
+        let allowed_user_ids = telegram.commands.allowed_user_ids.clone();
+        let manager = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            loop {
+                let messages = match bot.poll().await {
+                    Ok(messages) => messages,
+                    Err(e) => {
+                        warn!("Failed to poll Telegram commands: {}", e);
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+
+                for message in messages {
+                    if !message.text.starts_with('/') {
+                        continue;
+                    }
+                    if !allowed_user_ids.contains(&message.user_id) {
                         warn!(
-                            "Falling back to individual notifications for {} alerts",
-                            alerts.len()
+                            "Ignoring Telegram command from unauthorized user {}",
+                            message.user_id
                         );
-                        for alert in alerts {
-                            if let Err(e) = self
-                                .send_immediate(alert, vec![channel_name.to_string()])
-                                .await
-                            {
-                                error!("Fallback notification failed: {}", e);
-                            }
-                        }
+                        continue;
                     }
-                }
-            } else {
-                // Channel doesn't support batching, send individually
-                for alert in alerts {
-                    if let Err(e) = self
-                        .send_immediate(alert, vec![channel_name.to_string()])
-                        .await
-                    {
-                        error!("Individual notification failed: {}", e);
+
+                    let operator = format!(
+                        "telegram:{}",
+                        message
+                            .username
+                            .clone()
+                            .unwrap_or_else(|| message.user_id.to_string())
+                    );
+                    let reply = match BotCommand::parse(&message.text) {
+                        Ok(command) => manager.handle_command(command, &operator).await,
+                        Err(usage) => usage,
+                    };
+
+                    if let Err(e) = bot.reply(message.chat_id, &reply).await {
+                        warn!("Failed to reply to Telegram command: {}", e);
                     }
                 }
             }
-        }
-
-        Ok(())
+            debug!("Telegram command polling stopped");
+        });
     }
 
-    /// Test all configured notification channels.
-    pub async fn test_channels(&self) -> HashMap<String, NotifierResult<()>> {
-        let mut results = HashMap::new();
-
-        for (channel_name, channel) in &self.channels {
-            info!("Testing channel: {}", channel_name);
-            let result = channel.test().await;
-            results.insert(channel_name.clone(), result);
+    /// Start reloading user templates when their files change.
+    pub fn start_template_reload(self: &Arc<Self>) {
+        if self.config.templates.directory.is_none() {
+            return;
         }
 
-        results
-    }
+        let reload_interval =
+            Duration::from_secs(self.config.templates.reload_interval_seconds.max(1));
+        let manager = Arc::downgrade(self);
 
-    /// Get notification statistics.
-    pub async fn statistics(&self) -> NotificationStats {
-        self.stats.read().await.clone()
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + reload_interval, reload_interval);
+
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.templates.reload_if_changed();
+            }
+        });
     }
 
-    /// Shutdown the notification manager.
-    pub async fn shutdown(&self) -> NotifierResult<()> {
-        if let Some(batch_manager) = &self.batch_manager {
-            batch_manager.shutdown().await?;
+    /// Start taking escalation steps that are due.
+    pub fn start_escalations(self: &Arc<Self>) {
+        if self.config.escalation.chains.is_empty() {
+            return;
         }
 
-        info!("Notification manager shut down");
-        Ok(())
-    }
+        let check_interval =
+            Duration::from_secs(self.config.escalation.check_interval_seconds.max(1));
+        let manager = Arc::downgrade(self);
 
-    /// Check if alert meets minimum severity requirement.
-    fn meets_minimum_severity(&self, alert: &Alert) -> bool {
-        let min_severity = match self.config.global.min_severity.as_str() {
-            "critical" => watchtower_engine::AlertSeverity::Critical,
-            "high" => watchtower_engine::AlertSeverity::High,
-            "medium" => watchtower_engine::AlertSeverity::Medium,
-            "low" => watchtower_engine::AlertSeverity::Low,
-            _ => watchtower_engine::AlertSeverity::Info,
-        };
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + check_interval, check_interval);
 
-        alert.severity >= min_severity
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.escalate_due().await;
+            }
+        });
     }
 
-    /// Apply filters and return channels that should receive the notification.
-    async fn apply_filters(&self, alert: &Alert) -> Vec<String> {
-        let mut eligible_channels = self.config.enabled_channels();
+    /// Execute a bot command on behalf of `operator`, returning the reply.
+    pub async fn handle_command(&self, command: BotCommand, operator: &str) -> String {
+        match command {
+            BotCommand::Status => self.status_summary().await,
+            BotCommand::Alerts => {
+                let Some(alert_manager) = &self.alert_manager else {
+                    return "Alert history is not available".to_string();
+                };
+
+                let mut alerts = alert_manager
+                    .list_alerts(Some(AlertFilter {
+                        acknowledged: Some(false),
+                        resolved: Some(false),
+                        ..Default::default()
+                    }))
+                    .await;
+                if alerts.is_empty() {
+                    return "No unacknowledged alerts".to_string();
+                }
 
-        // Apply each filter
-        for filter in &self.filters {
-            let matches = self.filter_matches(filter, alert);
+                alerts.sort_by_key(|alert| std::cmp::Reverse(alert.timestamp));
+                let mut reply = format!("{} unacknowledged alert(s):", alerts.len());
+                for alert in alerts.iter().take(10) {
+                    reply.push_str(&format!(
+                        "\n[{}] {} on {} ({})",
+                        alert.severity.as_str(),
+                        alert.rule_name,
+                        alert.program_name,
+                        alert.id
+                    ));
+                }
+                reply
+            }
+            BotCommand::Ack { alert_id } => {
+                let Some(alert_manager) = &self.alert_manager else {
+                    return "Alert history is not available".to_string();
+                };
+
+                match alert_manager
+                    .acknowledge_alert_by(&alert_id, Some(operator))
+                    .await
+                {
+                    Ok(()) => {
+                        self.cancel_escalation(&alert_id).await;
+                        format!("Alert {} acknowledged", alert_id)
+                    }
+                    Err(e) => format!("Could not acknowledge alert {}: {}", alert_id, e),
+                }
+            }
+            BotCommand::Mute { rule, duration } => {
+                let until = self.mute_rule(&rule, duration).await;
+                info!("Rule {} muted until {} by {}", rule, until, operator);
+                format!(
+                    "Rule {} muted until {}",
+                    rule,
+                    until.format("%Y-%m-%d %H:%M UTC")
+                )
+            }
+            BotCommand::Unmute { rule } => {
+                if self.unmute_rule(&rule).await {
+                    info!("Rule {} unmuted by {}", rule, operator);
+                    format!("Rule {} unmuted", rule)
+                } else {
+                    format!("Rule {} is not muted", rule)
+                }
+            }
+            BotCommand::Help => BOT_COMMANDS_HELP.to_string(),
+        }
+    }
+
+    /// Take the escalation steps that are due, dropping escalations of
+    /// alerts that were acknowledged or resolved in the meantime.
+    pub async fn escalate_due(&self) {
+        let now = chrono::Utc::now();
+        let due: Vec<Escalation> = {
+            let mut escalations = self.escalations.write().await;
+            let ids: Vec<String> = escalations
+                .iter()
+                .filter(|(_, escalation)| escalation.due <= now)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| escalations.remove(id)).collect()
+        };
+
+        for escalation in due {
+            if self.is_acknowledged(&escalation.alert.id) {
+                debug!(
+                    "Alert {} was acknowledged or resolved, escalation stopped",
+                    escalation.alert.id
+                );
+                continue;
+            }
+
+            if let Err(e) = self
+                .escalate(escalation.alert, escalation.chain, escalation.next_step)
+                .await
+            {
+                error!("Failed to deliver escalation step: {}", e);
+            }
+        }
+    }
+
+    /// Stop escalating an alert, returning whether it was escalating.
+    pub async fn cancel_escalation(&self, alert_id: &str) -> bool {
+        self.escalations.write().await.remove(alert_id).is_some()
+    }
+
+    /// Alerts waiting for their next escalation step.
+    pub async fn escalations(&self) -> Vec<Escalation> {
+        self.escalations.read().await.values().cloned().collect()
+    }
+
+    /// Take a step of an escalation chain and schedule the next one.
+    async fn escalate(&self, alert: Alert, chain: usize, step: usize) -> NotifierResult<()> {
+        let escalation_chain = &self.config.escalation.chains[chain];
+        info!(
+            "Escalating alert {} to step {} of chain {}",
+            alert.id,
+            step + 1,
+            escalation_chain.name
+        );
+
+        if let Some(next) = escalation_chain.steps.get(step + 1) {
+            let due = chrono::Utc::now() + chrono::Duration::minutes(next.after_minutes as i64);
+            self.escalations.write().await.insert(
+                alert.id.clone(),
+                Escalation {
+                    alert: alert.clone(),
+                    chain,
+                    next_step: step + 1,
+                    due,
+                },
+            );
+        }
+
+        self.send_immediate(alert, escalation_chain.steps[step].channels.clone())
+            .await
+    }
+
+    /// Whether an alert was acknowledged or resolved in the alert manager.
+    ///
+    /// Resolved alerts are no longer open, so their resolution is looked up
+    /// in the audit trail.
+    fn is_acknowledged(&self, alert_id: &str) -> bool {
+        let Some(alert_manager) = &self.alert_manager else {
+            return false;
+        };
+
+        match alert_manager.get_alert(alert_id) {
+            Some(alert) => alert.acknowledged,
+            None => alert_manager
+                .audit_trail(alert_id)
+                .iter()
+                .any(|entry| matches!(entry.transition, AlertTransition::Resolved { .. })),
+        }
+    }
+
+    /// Stop notifying about a rule for `duration`, returning when the mute ends.
+    pub async fn mute_rule(
+        &self,
+        rule: &str,
+        duration: chrono::Duration,
+    ) -> chrono::DateTime<chrono::Utc> {
+        let until = chrono::Utc::now() + duration;
+        self.muted_rules
+            .write()
+            .await
+            .insert(rule.to_string(), until);
+        until
+    }
+
+    /// Notify about a rule again, returning whether it was muted.
+    pub async fn unmute_rule(&self, rule: &str) -> bool {
+        self.muted_rules.write().await.remove(rule).is_some()
+    }
+
+    /// Muted rules and when their mutes end.
+    pub async fn muted_rules(&self) -> HashMap<String, chrono::DateTime<chrono::Utc>> {
+        let now = chrono::Utc::now();
+        let mut muted_rules = self.muted_rules.write().await;
+        muted_rules.retain(|_, until| *until > now);
+        muted_rules.clone()
+    }
+
+    /// Summary of alerts, notifications and channel health for `/status`.
+    async fn status_summary(&self) -> String {
+        let stats = self.statistics().await;
+        let mut summary = format!(
+            "Notifications: {} sent, {} failed, {} pending redelivery",
+            stats.total_sent,
+            stats.total_failed,
+            self.outbox.len()
+        );
+
+        if let Some(alert_manager) = &self.alert_manager {
+            let alert_stats = alert_manager.statistics().await;
+            summary.push_str(&format!(
+                "\nAlerts: {} total, {} acknowledged, {} resolved",
+                alert_stats.total_alerts,
+                alert_stats.acknowledged_count,
+                alert_stats.resolved_count
+            ));
+        }
+
+        let mut open_circuits: Vec<&str> = self
+            .breakers
+            .iter()
+            .filter(|(_, breaker)| breaker.is_open())
+            .map(|(name, _)| name.as_str())
+            .collect();
+        open_circuits.sort();
+        if open_circuits.is_empty() {
+            summary.push_str("\nChannels: all healthy");
+        } else {
+            summary.push_str(&format!("\nChannels down: {}", open_circuits.join(", ")));
+        }
+
+        let mut muted_rules: Vec<String> = self.muted_rules().await.into_keys().collect();
+        muted_rules.sort();
+        if !muted_rules.is_empty() {
+            summary.push_str(&format!("\nMuted rules: {}", muted_rules.join(", ")));
+        }
+
+        summary
+    }
+
+    /// Whether a rule is currently muted.
+    async fn is_muted(&self, rule: &str) -> bool {
+        self.muted_rules
+            .read()
+            .await
+            .get(rule)
+            .is_some_and(|until| *until > chrono::Utc::now())
+    }
+
+    /// Test every channel once, updating its circuit breaker.
+    pub async fn run_health_checks(&self) {
+        for (channel_name, channel) in &self.channels {
+            let result = channel.test().await;
+            if let Err(e) = &result {
+                warn!("Health check of {} failed: {}", channel_name, e);
+            }
+            self.record_channel_result(channel_name, result.as_ref().err())
+                .await;
+        }
+    }
+
+    /// Circuit state of every channel.
+    pub fn circuit_states(&self) -> HashMap<String, CircuitState> {
+        self.breakers
+            .iter()
+            .map(|(name, breaker)| (name.clone(), breaker.state()))
+            .collect()
+    }
+
+    /// Notifications waiting in each queue.
+    pub async fn queue_depths(&self) -> NotificationQueues {
+        let batched = match &self.batch_manager {
+            Some(batch_manager) => batch_manager
+                .pending_alerts
+                .read()
+                .await
+                .values()
+                .map(Vec::len)
+                .sum(),
+            None => 0,
+        };
+
+        NotificationQueues {
+            batched,
+            held: self.held_alerts.read().await.values().map(Vec::len).sum(),
+            outbox: self.outbox.len(),
+            escalations: self.escalations.read().await.len(),
+        }
+    }
+
+    /// Send the collected digests, except to channels in quiet hours.
+    pub async fn send_digests(&self) {
+        let now = chrono::Utc::now();
+        let ready: Vec<(String, Digest)> = {
+            let mut digests = self.digests.write().await;
+            digests
+                .iter_mut()
+                .filter(|(channel, digest)| !digest.is_empty() && !self.is_quiet(channel, now))
+                .map(|(channel, digest)| (channel.clone(), std::mem::take(digest)))
+                .collect()
+        };
+
+        for (channel_name, digest) in ready {
+            debug!(
+                "Sending digest of {} alert(s) via {}",
+                digest.total, channel_name
+            );
+            let alert = digest.to_alert(&channel_name);
+            if let Err(e) = self.send_immediate(alert, vec![channel_name.clone()]).await {
+                error!("Failed to send digest via {}: {}", channel_name, e);
+            }
+        }
+    }
+
+    /// Deliver held alerts of channels whose quiet hours have ended.
+    pub async fn release_held_alerts(&self) {
+        let now = chrono::Utc::now();
+        let released: Vec<(String, Vec<Alert>)> = {
+            let mut held = self.held_alerts.write().await;
+            held.iter_mut()
+                .filter(|(channel, alerts)| !alerts.is_empty() && !self.is_quiet(channel, now))
+                .map(|(channel, alerts)| (channel.clone(), std::mem::take(alerts)))
+                .collect()
+        };
+
+        for (channel_name, alerts) in released {
+            info!(
+                "Quiet hours ended for {}, releasing {} held alert(s)",
+                channel_name,
+                alerts.len()
+            );
+            if let Err(e) = self.send_batch(alerts, &channel_name).await {
+                error!("Failed to release held alerts via {}: {}", channel_name, e);
+            }
+        }
+    }
+
+    /// Retry the outbox notifications that are due, once.
+    pub async fn retry_outbox(&self) {
+        for entry in self.outbox.due(chrono::Utc::now()) {
+            let Some(channel) = self.channels.get(&entry.channel) else {
+                warn!(
+                    "Dropping outbox notification for unconfigured channel {}",
+                    entry.channel
+                );
+                self.remove_from_outbox(entry.id);
+                continue;
+            };
+
+            // Broken channels keep their notifications until a health check succeeds
+            if self.circuit_open(&entry.channel)
+                || self.rate_limited(&entry.channel, &channel.rate_limit_key(&entry.alert))
+            {
+                continue;
+            }
+
+            let template_data = self.create_template_data(&entry.alert);
+            let result = channel.send(&entry.alert, &template_data).await;
+            self.record_attempt(&entry.alert, &entry.channel, &result);
+            self.record_channel_result(&entry.channel, result.as_ref().err())
+                .await;
+            match result {
+                Ok(_) => {
+                    info!(
+                        "Notification for alert {} redelivered via {} after {} failed attempts",
+                        entry.alert.id, entry.channel, entry.attempts
+                    );
+                    self.remove_from_outbox(entry.id);
+                    self.remember_sent(&entry.channel, &entry.alert);
+                    self.update_stats(|stats| {
+                        stats.total_sent += 1;
+                        stats.redelivered += 1;
+                        *stats
+                            .sent_per_channel
+                            .entry(entry.channel.clone())
+                            .or_insert(0) += 1;
+                        stats.last_notification = Some(chrono::Utc::now());
+                    })
+                    .await;
+                }
+                Err(e) => {
+                    let attempts = entry.attempts + 1;
+                    if attempts >= self.config.retry.outbox_max_attempts {
+                        error!(
+                            "Giving up on notification via {} for alert {} after {} attempts: {}",
+                            entry.channel, entry.alert.id, attempts, e
+                        );
+                        self.remove_from_outbox(entry.id);
+                    } else if let Err(e) = self.outbox.reschedule(
+                        entry.id,
+                        e.to_string(),
+                        self.next_attempt_at(attempts),
+                    ) {
+                        error!("Failed to update notification outbox: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Notifications awaiting redelivery.
+    pub fn outbox(&self) -> &Outbox {
+        &self.outbox
+    }
+
+    /// Notifications recorded by the capture channels, oldest first.
+    pub fn captured_notifications(&self) -> Vec<CapturedNotification> {
+        self.capture
+            .as_ref()
+            .map(CaptureSink::captured)
+            .unwrap_or_default()
+    }
+
+    /// Send a notification for an alert.
+    pub async fn send_notification(&self, alert: Alert) -> NotifierResult<()> {
+        debug!("Processing notification for alert: {}", alert.id);
+
+        // Check minimum severity
+        if !self.meets_minimum_severity(&alert) {
+            debug!("Alert {} below minimum severity threshold", alert.id);
+            return Ok(());
+        }
+
+        // Skip rules muted from chat
+        if self.is_muted(&alert.rule_name).await {
+            debug!(
+                "Rule {} is muted, skipping alert {}",
+                alert.rule_name, alert.id
+            );
+            return Ok(());
+        }
+
+        // Alerts with an escalation chain follow its steps instead of the routing
+        if let Some(chain) = self.config.escalation.chain_for(&alert) {
+            return self.escalate(alert, chain, 0).await;
+        }
+
+        // Apply filters
+        let channels_to_notify = self.apply_filters(&alert).await;
+
+        // Hold non-critical alerts for channels in quiet hours
+        let channels_to_notify = self
+            .hold_during_quiet_hours(&alert, channels_to_notify)
+            .await;
+
+        // Collect low-severity alerts into digests
+        let channels_to_notify = self.collect_into_digests(&alert, channels_to_notify).await;
+
+        // Route around channels whose circuit is open
+        let channels_to_notify = self.route_around_open_circuits(channels_to_notify);
+
+        if channels_to_notify.is_empty() {
+            debug!("No channels to notify for alert {}", alert.id);
+            return Ok(());
+        }
+
+        // Handle batching vs immediate sending
+        if self.config.global.enable_batching {
+            self.add_to_batch(alert, channels_to_notify).await?;
+        } else {
+            self.send_immediate(alert, channels_to_notify).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Notify channels that an alert was resolved, e.g. to close incidents.
+    ///
+    /// Only channels that would have received the alert are notified; rate
+    /// limits are not applied so incidents are never left open.
+    pub async fn send_resolution(&self, alert: &Alert) -> NotifierResult<()> {
+        if !self.meets_minimum_severity(alert) {
+            return Ok(());
+        }
+
+        self.cancel_escalation(&alert.id).await;
+
+        for channel_name in self.apply_filters(alert).await {
+            if let Some(channel) = self.channels.get(&channel_name) {
+                if let Err(e) = channel.resolve(alert).await {
+                    error!(
+                        "Failed to send resolution via {} for alert {}: {}",
+                        channel_name, alert.id, e
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send notification immediately to specified channels.
+    ///
+    /// Every channel is attempted; notifications that still fail after
+    /// retrying are moved to the outbox and the last error is returned.
+    async fn send_immediate(&self, alert: Alert, channels: Vec<String>) -> NotifierResult<()> {
+        let template_data = self.create_template_data(&alert);
+        let mut result = Ok(());
+
+        for channel_name in self.route_around_open_circuits(channels) {
+            if let Some(channel) = self.channels.get(&channel_name) {
+                if self.recently_sent(&channel_name, &alert) {
+                    debug!(
+                        "Suppressing repeat of {} via {} within the dedup window",
+                        alert.fingerprint(),
+                        channel_name
+                    );
+                    self.update_stats(|stats| stats.deduplicated += 1).await;
+                    continue;
+                }
+
+                // Check rate limit
+                if self.rate_limited(&channel_name, &channel.rate_limit_key(&alert)) {
+                    warn!("Rate limit exceeded for channel: {}", channel_name);
+                    self.record_delivery(
+                        &alert,
+                        &channel_name,
+                        DeliveryOutcome::RateLimited,
+                        None,
+                        None,
+                    );
+                    self.update_stats(|stats| {
+                        stats.rate_limited += 1;
+                        *stats
+                            .rate_limited_per_channel
+                            .entry(channel_name.clone())
+                            .or_insert(0) += 1;
+                    })
+                    .await;
+                    continue;
+                }
+
+                // Send notification
+                match self.deliver(channel.as_ref(), &alert, &template_data).await {
+                    Ok(_) => {
+                        info!("Notification sent successfully via {}", channel_name);
+                        self.record_channel_result(&channel_name, None).await;
+                        self.remember_sent(&channel_name, &alert);
+                        self.update_stats(|stats| {
+                            stats.total_sent += 1;
+                            *stats
+                                .sent_per_channel
+                                .entry(channel_name.clone())
+                                .or_insert(0) += 1;
+                            stats.last_notification = Some(chrono::Utc::now());
+                        })
+                        .await;
+                    }
+                    Err(e) => {
+                        error!("Failed to send notification via {}: {}", channel_name, e);
+                        self.record_channel_result(&channel_name, Some(&e)).await;
+                        self.update_stats(|stats| {
+                            stats.total_failed += 1;
+                            *stats
+                                .failed_per_channel
+                                .entry(channel_name.clone())
+                                .or_insert(0) += 1;
+                        })
+                        .await;
+                        self.enqueue(&channel_name, &alert, &e);
+                        result = Err(e);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Send through a channel, retrying with exponential backoff.
+    ///
+    /// Every attempt is recorded in the delivery history.
+    async fn deliver(
+        &self,
+        channel: &dyn NotificationChannel,
+        alert: &Alert,
+        template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let max_attempts = self.config.retry.max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            let result = channel.send(alert, template_data).await;
+            self.record_attempt(alert, channel.name(), &result);
+            match result {
+                Ok(message_id) => return Ok(message_id),
+                Err(e) if attempt >= max_attempts => return Err(e),
+                Err(e) => {
+                    let delay = self.config.retry.backoff(attempt);
+                    warn!(
+                        "Attempt {} via {} failed, retrying in {:?}: {}",
+                        attempt,
+                        channel.name(),
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Move a notification that could not be delivered to the outbox.
+    fn enqueue(&self, channel_name: &str, alert: &Alert, error: &NotifierError) {
+        let attempts = self.config.retry.max_attempts.max(1);
+        if let Err(e) = self.outbox.push(
+            channel_name,
+            alert.clone(),
+            attempts,
+            error.to_string(),
+            self.next_attempt_at(attempts),
+        ) {
+            error!(
+                "Failed to queue notification via {} for alert {}: {}",
+                channel_name, alert.id, e
+            );
+        }
+    }
+
+    /// When to retry after `attempts` failed attempts.
+    fn next_attempt_at(&self, attempts: u32) -> chrono::DateTime<chrono::Utc> {
+        let delay = chrono::Duration::from_std(self.config.retry.backoff(attempts))
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        chrono::Utc::now() + delay
+    }
+
+    /// Remove a delivered or abandoned notification from the outbox.
+    fn remove_from_outbox(&self, id: u64) {
+        if let Err(e) = self.outbox.remove(id) {
+            error!("Failed to update notification outbox: {}", e);
+        }
+    }
+
+    /// Whether the channel's rate limit for a destination is exhausted.
+    fn rate_limited(&self, channel_name: &str, key: &str) -> bool {
+        self.config.rate_limiting.enabled
+            && self
+                .rate_limiters
+                .get(channel_name)
+                .is_some_and(|rate_limiter| rate_limiter.check_key(&key.to_string()).is_err())
+    }
+
+    /// Whether the channel was sent an alert with the same fingerprint within
+    /// the dedup window.
+    fn recently_sent(&self, channel_name: &str, alert: &Alert) -> bool {
+        let window = self.config.global.dedup_window_seconds;
+        if window == 0 {
+            return false;
+        }
+
+        let mut recently_sent = self.recently_sent.lock().unwrap();
+        recently_sent.retain(|_, sent_at| sent_at.elapsed() < Duration::from_secs(window));
+        recently_sent.contains_key(&(channel_name.to_string(), alert.fingerprint()))
+    }
+
+    /// Start the dedup window of an alert sent through a channel.
+    fn remember_sent(&self, channel_name: &str, alert: &Alert) {
+        if self.config.global.dedup_window_seconds > 0 {
+            self.recently_sent.lock().unwrap().insert(
+                (channel_name.to_string(), alert.fingerprint()),
+                Instant::now(),
+            );
+        }
+    }
+
+    /// Add alert to batch for later sending.
+    async fn add_to_batch(&self, alert: Alert, channels: Vec<String>) -> NotifierResult<()> {
+        if let Some(batch_manager) = &self.batch_manager {
+            batch_manager.add_alert(alert, channels).await;
+        }
+        Ok(())
+    }
+
+    /// Send batched notifications.
+    pub async fn send_batch(&self, alerts: Vec<Alert>, channel_name: &str) -> NotifierResult<()> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(channel) = self.channels.get(channel_name) {
+            if channel.supports_batching() {
+                let total = alerts.len();
+                let alerts: Vec<Alert> = alerts
+                    .into_iter()
+                    .filter(|alert| !self.recently_sent(channel_name, alert))
+                    .collect();
+                if alerts.len() < total {
+                    debug!(
+                        "Suppressing {} repeated alerts in batch via {}",
+                        total - alerts.len(),
+                        channel_name
+                    );
+                    self.update_stats(|stats| stats.deduplicated += (total - alerts.len()) as u64)
+                        .await;
+                }
+                if alerts.is_empty() {
+                    return Ok(());
+                }
+
+                let template_data = self.create_batch_template_data(&alerts);
+
+                // Check rate limit
+                if self.rate_limited(channel_name, &channel.rate_limit_key(&alerts[0])) {
+                    warn!("Rate limit exceeded for batch on channel: {}", channel_name);
+                    for alert in &alerts {
+                        self.record_delivery(
+                            alert,
+                            channel_name,
+                            DeliveryOutcome::RateLimited,
+                            None,
+                            None,
+                        );
+                    }
+                    self.update_stats(|stats| {
+                        stats.rate_limited += 1;
+                        *stats
+                            .rate_limited_per_channel
+                            .entry(channel_name.to_string())
+                            .or_insert(0) += 1;
+                    })
+                    .await;
+                    return Ok(());
+                }
+
+                match channel.send_batch(&alerts, &template_data).await {
+                    Ok(_) => {
+                        info!(
+                            "Batch notification sent successfully via {} ({} alerts)",
+                            channel_name,
+                            alerts.len()
+                        );
+                        self.record_channel_result(channel_name, None).await;
+                        for alert in &alerts {
+                            self.remember_sent(channel_name, alert);
+                            self.record_delivery(
+                                alert,
+                                channel_name,
+                                DeliveryOutcome::Delivered,
+                                None,
+                                None,
+                            );
+                        }
+                        self.update_stats(|stats| {
+                            stats.total_sent += 1;
+                            stats.batched += alerts.len() as u64;
+                            *stats
+                                .sent_per_channel
+                                .entry(channel_name.to_string())
+                                .or_insert(0) += 1;
+                            stats.last_notification = Some(chrono::Utc::now());
+                        })
+                        .await;
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to send batch notification via {}: {}",
+                            channel_name, e
+                        );
+                        self.record_channel_result(channel_name, Some(&e)).await;
+                        for alert in &alerts {
+                            self.record_delivery(
+                                alert,
+                                channel_name,
+                                DeliveryOutcome::Failed,
+                                None,
+                                Some(e.to_string()),
+                            );
+                        }
+                        self.update_stats(|stats| {
+                            stats.total_failed += 1;
+                            *stats
+                                .failed_per_channel
+                                .entry(channel_name.to_string())
+                                .or_insert(0) += 1;
+                        })
+                        .await;
+
+                        // Fallback to individual notifications
+                        warn!(
+                            "Falling back to individual notifications for {} alerts",
+                            alerts.len()
+                        );
+                        for alert in alerts {
+                            if let Err(e) = self
+                                .send_immediate(alert, vec![channel_name.to_string()])
+                                .await
+                            {
+                                error!("Fallback notification failed: {}", e);
+                            }
+                        }
+                    }
+                }
+            } else {
+                // Channel doesn't support batching, send individually
+                for alert in alerts {
+                    if let Err(e) = self
+                        .send_immediate(alert, vec![channel_name.to_string()])
+                        .await
+                    {
+                        error!("Individual notification failed: {}", e);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Test all configured notification channels.
+    pub async fn test_channels(&self) -> HashMap<String, NotifierResult<()>> {
+        let mut results = HashMap::new();
+
+        for (channel_name, channel) in &self.channels {
+            info!("Testing channel: {}", channel_name);
+            let result = channel.test().await;
+            results.insert(channel_name.clone(), result);
+        }
+
+        results
+    }
+
+    /// Test a single channel, or `None` if no channel has that name.
+    pub async fn test_channel(&self, channel_name: &str) -> Option<NotifierResult<()>> {
+        let channel = self.channels.get(channel_name)?;
+        info!("Testing channel: {}", channel_name);
+        Some(channel.test().await)
+    }
+
+    /// Names of the configured channels, sorted.
+    pub fn channel_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.channels.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Whether a channel receives alerts, i.e. it was not switched off at runtime.
+    pub fn is_channel_enabled(&self, channel_name: &str) -> bool {
+        !self
+            .disabled_channels
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(channel_name)
+    }
+
+    /// Switch a configured channel off or back on without restarting.
+    pub fn set_channel_enabled(&self, channel_name: &str, enabled: bool) -> NotifierResult<()> {
+        if !self.channels.contains_key(channel_name) {
+            return Err(NotifierError::Configuration(format!(
+                "Unknown notification channel: {}",
+                channel_name
+            )));
+        }
+
+        let mut disabled = self
+            .disabled_channels
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let changed = if enabled {
+            disabled.remove(channel_name)
+        } else {
+            disabled.insert(channel_name.to_string())
+        };
+        if changed {
+            info!(
+                "Notification channel {} {}",
+                channel_name,
+                if enabled { "enabled" } else { "disabled" }
+            );
+        }
+        Ok(())
+    }
+
+    /// Get notification statistics.
+    pub async fn statistics(&self) -> NotificationStats {
+        self.stats.read().await.clone()
+    }
+
+    /// Shutdown the notification manager.
+    pub async fn shutdown(&self) -> NotifierResult<()> {
+        if let Some(batch_manager) = &self.batch_manager {
+            // Deliver pending batches before stopping
+            for (channel_name, alerts) in batch_manager.shutdown().await? {
+                self.send_batch(alerts, &channel_name).await?;
+            }
+        }
+
+        info!("Notification manager shut down");
+        Ok(())
+    }
+
+    /// Hold the alert for channels in quiet hours, returning the channels to notify now.
+    async fn hold_during_quiet_hours(&self, alert: &Alert, channels: Vec<String>) -> Vec<String> {
+        if alert.severity == watchtower_engine::AlertSeverity::Critical {
+            return channels;
+        }
+
+        let now = chrono::Utc::now();
+        let (quiet, active): (Vec<_>, Vec<_>) = channels
+            .into_iter()
+            .partition(|channel| self.is_quiet(channel, now));
+
+        if !quiet.is_empty() {
+            let mut held = self.held_alerts.write().await;
+            let mut digests = self.digests.write().await;
+            for channel in quiet {
+                // Channels with digests get held alerts in their next digest
+                if self.uses_digest(&channel) {
+                    debug!(
+                        "Adding alert {} to the {} digest during quiet hours",
+                        alert.id, channel
+                    );
+                    digests.entry(channel).or_default().add(alert);
+                } else {
+                    debug!(
+                        "Holding alert {} for {} during quiet hours",
+                        alert.id, channel
+                    );
+                    held.entry(channel).or_default().push(alert.clone());
+                }
+            }
+        }
+
+        active
+    }
+
+    /// Add a low-severity alert to channel digests, returning the channels to notify now.
+    async fn collect_into_digests(&self, alert: &Alert, channels: Vec<String>) -> Vec<String> {
+        let max_severity = parse_severity(&self.config.digest.max_severity)
+            .unwrap_or(watchtower_engine::AlertSeverity::Low);
+        if alert.severity > max_severity {
+            return channels;
+        }
+
+        let (digested, immediate): (Vec<_>, Vec<_>) = channels
+            .into_iter()
+            .partition(|channel| self.uses_digest(channel));
+
+        if !digested.is_empty() {
+            let mut digests = self.digests.write().await;
+            for channel in digested {
+                digests.entry(channel).or_default().add(alert);
+            }
+        }
+
+        immediate
+    }
+
+    /// Replace channels whose circuit is open by their healthy fallbacks.
+    fn route_around_open_circuits(&self, channels: Vec<String>) -> Vec<String> {
+        if !self.config.health.enabled {
+            return channels;
+        }
+
+        let mut routed: Vec<String> = Vec::with_capacity(channels.len());
+        for channel in channels {
+            let candidates = if self.circuit_open(&channel) {
+                self.config
+                    .health
+                    .fallbacks
+                    .get(&channel)
+                    .cloned()
+                    .unwrap_or_default()
+            } else {
+                vec![channel]
+            };
+
+            for candidate in candidates {
+                if !self.circuit_open(&candidate)
+                    && self.is_channel_enabled(&candidate)
+                    && !routed.contains(&candidate)
+                {
+                    routed.push(candidate);
+                }
+            }
+        }
+
+        routed
+    }
+
+    /// Whether a channel is bypassed because its circuit is open.
+    fn circuit_open(&self, channel_name: &str) -> bool {
+        self.config.health.enabled
+            && self
+                .breakers
+                .get(channel_name)
+                .is_some_and(|breaker| breaker.is_open())
+    }
+
+    /// Update a channel's circuit breaker after a delivery or health check,
+    /// raising a meta-alert when the circuit opens.
+    async fn record_channel_result(&self, channel_name: &str, error: Option<&NotifierError>) {
+        if !self.config.health.enabled {
+            return;
+        }
+        let Some(breaker) = self.breakers.get(channel_name) else {
+            return;
+        };
+
+        let Some(error) = error else {
+            if breaker.record_success() {
+                info!("Channel {} recovered, closing its circuit", channel_name);
+            }
+            return;
+        };
+
+        if !breaker.record_failure() {
+            return;
+        }
+
+        let fallbacks = self
+            .config
+            .health
+            .fallbacks
+            .get(channel_name)
+            .cloned()
+            .unwrap_or_default();
+        error!(
+            "Channel {} failed {} consecutive times, opening its circuit",
+            channel_name, self.config.health.failure_threshold
+        );
+
+        let alert = channel_down_alert(channel_name, &error.to_string(), &fallbacks);
+        match &self.alert_manager {
+            // Alerts raised through the alert manager reach the notifier like any other
+            Some(alert_manager) => {
+                if let Err(e) = alert_manager.send_alert(alert).await {
+                    error!(
+                        "Failed to raise alert for broken channel {}: {}",
+                        channel_name, e
+                    );
+                }
+            }
+            None => {
+                let template_data = self.create_template_data(&alert);
+                for (name, channel) in &self.channels {
+                    if self.circuit_open(name) {
+                        continue;
+                    }
+                    if let Err(e) = channel.send(&alert, &template_data).await {
+                        warn!(
+                            "Failed to report broken channel {} via {}: {}",
+                            channel_name, name, e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether a channel receives digests.
+    fn uses_digest(&self, channel_name: &str) -> bool {
+        let digest = &self.config.digest;
+        digest.enabled
+            && digest
+                .channels
+                .as_ref()
+                .map_or(true, |channels| channels.iter().any(|c| c == channel_name))
+    }
+
+    /// Whether a channel is in its quiet hours.
+    fn is_quiet(&self, channel_name: &str, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.quiet_hours
+            .get(channel_name)
+            .is_some_and(|quiet_hours| quiet_hours.is_quiet(now))
+    }
+
+    /// Check if alert meets minimum severity requirement.
+    fn meets_minimum_severity(&self, alert: &Alert) -> bool {
+        let min_severity = parse_severity(&self.config.global.min_severity)
+            .unwrap_or(watchtower_engine::AlertSeverity::Info);
+
+        alert.severity >= min_severity
+    }
+
+    /// Apply filters and return channels that should receive the notification.
+    async fn apply_filters(&self, alert: &Alert) -> Vec<String> {
+        let mut eligible_channels = self.config.enabled_channels();
+        eligible_channels.retain(|c| self.is_channel_enabled(c));
+
+        // Route by severity before applying filters
+        if let Some(routed_channels) = self.config.routing.get(alert.severity.as_str()) {
+            eligible_channels.retain(|c| routed_channels.contains(c));
+        }
+
+        // Drop channels that restrict which alerts they take, e.g. by severity
+        eligible_channels.retain(|name| {
+            self.channels
+                .get(name)
+                .map_or(true, |channel| channel.accepts(alert))
+        });
+
+        // Apply each filter
+        for filter in &self.filters {
+            let matches = self.filter_matches(filter, alert);
 
             if filter.include && matches {
                 // Include filter matches - keep only specified channels
@@ -414,6 +1766,13 @@ impl NotificationManager {
             }
         }
 
+        // Check tags
+        if let Some(tags) = &filter.tags {
+            if !alert.tags.iter().any(|tag| tags.contains(tag)) {
+                return false;
+            }
+        }
+
         true
     }
 
@@ -425,6 +1784,7 @@ impl NotificationManager {
             "alert".to_string(),
             serde_json::to_value(alert).unwrap_or_default(),
         );
+        data.insert("links".to_string(), self.templates.explorer_links(alert));
         data.insert(
             "timestamp".to_string(),
             serde_json::to_value(chrono::Utc::now()).unwrap_or_default(),
@@ -453,6 +1813,51 @@ impl NotificationManager {
         data
     }
 
+    /// Record the result of a send in the delivery history.
+    fn record_attempt(
+        &self,
+        alert: &Alert,
+        channel_name: &str,
+        result: &NotifierResult<Option<String>>,
+    ) {
+        match result {
+            Ok(message_id) => self.record_delivery(
+                alert,
+                channel_name,
+                DeliveryOutcome::Delivered,
+                message_id.clone(),
+                None,
+            ),
+            Err(e) => self.record_delivery(
+                alert,
+                channel_name,
+                DeliveryOutcome::Failed,
+                None,
+                Some(e.to_string()),
+            ),
+        }
+    }
+
+    /// Record a delivery attempt in the alert manager's delivery history.
+    fn record_delivery(
+        &self,
+        alert: &Alert,
+        channel_name: &str,
+        outcome: DeliveryOutcome,
+        provider_message_id: Option<String>,
+        error: Option<String>,
+    ) {
+        if let Some(alert_manager) = &self.alert_manager {
+            alert_manager.record_delivery(
+                &alert.id,
+                channel_name,
+                outcome,
+                provider_message_id,
+                error,
+            );
+        }
+    }
+
     /// Update statistics with a closure.
     async fn update_stats<F>(&self, f: F)
     where
@@ -460,12 +1865,25 @@ impl NotificationManager {
     {
         let mut stats = self.stats.write().await;
         f(&mut stats);
+
+        if let Some(metrics) = &self.metrics {
+            metrics.update(&stats);
+        }
+        if let Some(path) = &self.config.global.stats_path {
+            if let Err(e) = stats.save(path) {
+                error!("Failed to save notification statistics: {}", e);
+            }
+        }
     }
 }
 
 impl BatchManager {
     /// Create a new batch manager.
-    async fn new(batch_timeout: Duration, max_batch_size: usize) -> NotifierResult<Self> {
+    async fn new(
+        batch_timeout: Duration,
+        max_batch_size: usize,
+        ready_tx: mpsc::UnboundedSender<Batch>,
+    ) -> NotifierResult<Self> {
         let pending_alerts = Arc::new(RwLock::new(HashMap::new()));
         let (shutdown_tx, mut shutdown_rx) = mpsc::channel(1);
 
@@ -473,19 +1891,25 @@ impl BatchManager {
             pending_alerts: pending_alerts.clone(),
             batch_timeout,
             max_batch_size,
+            ready_tx: ready_tx.clone(),
             shutdown_tx,
         };
 
         // Start batch processing task
         let pending_alerts_clone = pending_alerts.clone();
         tokio::spawn(async move {
-            let mut interval = interval(batch_timeout);
+            // The first batch is due one timeout after startup
+            let mut interval = interval_at(Instant::now() + batch_timeout, batch_timeout);
 
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
                         // Process batches on timeout
-                        Self::process_batches(pending_alerts_clone.clone(), max_batch_size).await;
+                        for batch in Self::take_batches(&pending_alerts_clone).await {
+                            if ready_tx.send(batch).is_err() {
+                                warn!("Batch dispatcher is gone, dropping batch");
+                            }
+                        }
                     }
                     _ = shutdown_rx.recv() => {
                         // Shutdown signal received
@@ -507,89 +1931,297 @@ impl BatchManager {
             let alerts = pending.entry(channel.clone()).or_insert_with(Vec::new);
             alerts.push(alert.clone());
 
-            // Check if batch is full
+            // Hand full batches over immediately
             if alerts.len() >= self.max_batch_size {
                 debug!("Batch full for channel {}, processing immediately", channel);
-                // Process this batch immediately
-                let _batch = std::mem::take(alerts);
-                drop(pending); // Release lock before async operation
-
-                // TODO: Send batch notification
-                // This would require access to the NotificationManager
-                // For now, we'll rely on the timer-based processing
-
-                return;
+                let batch = std::mem::take(alerts);
+                if self.ready_tx.send((channel, batch)).is_err() {
+                    warn!("Batch dispatcher is gone, dropping batch");
+                }
             }
         }
     }
 
-    /// Process all pending batches.
-    async fn process_batches(
-        pending_alerts: Arc<RwLock<HashMap<String, Vec<Alert>>>>,
-        _max_batch_size: usize,
-    ) {
+    /// Take all pending batches, leaving the queues empty.
+    async fn take_batches(pending_alerts: &RwLock<HashMap<String, Vec<Alert>>>) -> Vec<Batch> {
         let mut pending = pending_alerts.write().await;
 
-        for (channel, alerts) in pending.iter_mut() {
-            if !alerts.is_empty() {
+        pending
+            .iter_mut()
+            .filter(|(_, alerts)| !alerts.is_empty())
+            .map(|(channel, alerts)| {
                 debug!(
                     "Processing batch for channel {} with {} alerts",
                     channel,
                     alerts.len()
                 );
-
-                // TODO: Actually send the batch
-                // This would require access to the NotificationManager
-                // For now, we'll just clear the batch
-                alerts.clear();
-            }
-        }
+                (channel.clone(), std::mem::take(alerts))
+            })
+            .collect()
     }
 
-    /// Shutdown the batch manager.
-    async fn shutdown(&self) -> NotifierResult<()> {
-        // Process any pending batches before shutdown
-        Self::process_batches(self.pending_alerts.clone(), self.max_batch_size).await;
-
+    /// Shutdown the batch manager, returning the batches still pending.
+    async fn shutdown(&self) -> NotifierResult<Vec<Batch>> {
         // Send shutdown signal
         if let Err(e) = self.shutdown_tx.send(()).await {
             warn!("Failed to send shutdown signal to batch manager: {}", e);
         }
 
-        Ok(())
+        Ok(Self::take_batches(&self.pending_alerts).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::CaptureConfig;
+    use crate::config::{
+        ChannelRateLimit, EmailConfig, GlobalNotificationConfig, NotifierConfig, PagerDutyConfig,
+        RateLimitConfig, RetryConfig, SlackConfig,
+    };
+    use crate::digest::DigestConfig;
+    use crate::escalation::{EscalationChain, EscalationConfig, EscalationStep};
+    use crate::health::HealthCheckConfig;
+    use crate::http::{HttpConfig, WebhookAuthConfig};
+    use crate::templates::TemplatesConfig;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use watchtower_engine::AlertSeverity;
+
+    #[tokio::test]
+    async fn test_notification_manager_creation() {
+        let config = NotifierConfig {
+            email: Some(EmailConfig {
+                smtp_server: "smtp.example.com".to_string(),
+                smtp_port: 587,
+                username: "test@example.com".to_string(),
+                password: "password".to_string(),
+                oauth2: None,
+                from_address: "test@example.com".to_string(),
+                from_name: Some("Test".to_string()),
+                to_addresses: vec!["recipient@example.com".to_string()],
+                use_tls: true,
+                subject_template: None,
+                body_template: None,
+                text_template: None,
+                dkim: None,
+                attach_chart: false,
+            }),
+            telegram: None,
+            slack: None,
+            discord: None,
+            mattermost: None,
+            pagerduty: None,
+            grafana_oncall: None,
+            pushover: None,
+            ntfy: None,
+            sns: None,
+            kafka: None,
+            nats: None,
+            sms: None,
+            signal: None,
+            capture: None,
+            rate_limiting: RateLimitConfig::default(),
+            http: HttpConfig::default(),
+            global: GlobalNotificationConfig::default(),
+            retry: RetryConfig::default(),
+            routing: HashMap::new(),
+            program_routes: Vec::new(),
+            escalation: EscalationConfig::default(),
+            quiet_hours: HashMap::new(),
+            digest: DigestConfig::default(),
+            health: HealthCheckConfig::default(),
+            templates: TemplatesConfig::default(),
+        };
+
+        let result = NotificationManager::new(config).await;
+        assert!(result.is_ok());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::{EmailConfig, GlobalNotificationConfig, NotifierConfig, RateLimitConfig};
-    use watchtower_engine::AlertSeverity;
+    #[tokio::test]
+    async fn test_dry_run_captures_instead_of_sending() {
+        let config = NotifierConfig {
+            email: Some(EmailConfig {
+                smtp_server: "smtp.invalid".to_string(),
+                smtp_port: 587,
+                username: "test@example.com".to_string(),
+                password: "password".to_string(),
+                oauth2: None,
+                from_address: "test@example.com".to_string(),
+                from_name: None,
+                to_addresses: vec!["recipient@example.com".to_string()],
+                use_tls: true,
+                subject_template: None,
+                body_template: None,
+                text_template: None,
+                dkim: None,
+                attach_chart: false,
+            }),
+            telegram: None,
+            slack: None,
+            discord: None,
+            mattermost: None,
+            pagerduty: None,
+            grafana_oncall: None,
+            pushover: None,
+            ntfy: None,
+            sns: None,
+            kafka: None,
+            nats: None,
+            sms: None,
+            signal: None,
+            capture: Some(CaptureConfig {
+                dry_run: true,
+                ..Default::default()
+            }),
+            rate_limiting: RateLimitConfig::default(),
+            http: HttpConfig::default(),
+            global: GlobalNotificationConfig::default(),
+            retry: RetryConfig::default(),
+            routing: HashMap::new(),
+            program_routes: Vec::new(),
+            escalation: EscalationConfig::default(),
+            quiet_hours: HashMap::new(),
+            digest: DigestConfig::default(),
+            health: HealthCheckConfig::default(),
+            templates: TemplatesConfig::default(),
+        };
+        let manager = NotificationManager::new(config).await.unwrap();
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transaction detected".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        manager.send_notification(alert).await.unwrap();
+
+        // The email is rendered but never reaches the SMTP server
+        let mut captured = manager.captured_notifications();
+        captured.sort_by(|a, b| a.channel.cmp(&b.channel));
+        assert_eq!(captured.len(), 2);
+        assert_eq!(captured[0].channel, "capture");
+        assert_eq!(captured[1].channel, "email");
+        assert!(captured[1].html.is_some());
+        assert!(captured[1].message.contains("Large transaction detected"));
+        assert_eq!(manager.statistics().await.total_failed, 0);
+    }
 
     #[tokio::test]
-    async fn test_notification_manager_creation() {
+    async fn test_bot_commands_mute_and_acknowledge() {
         let config = NotifierConfig {
             email: Some(EmailConfig {
                 smtp_server: "smtp.example.com".to_string(),
                 smtp_port: 587,
                 username: "test@example.com".to_string(),
                 password: "password".to_string(),
+                oauth2: None,
                 from_address: "test@example.com".to_string(),
-                from_name: Some("Test".to_string()),
+                from_name: None,
                 to_addresses: vec!["recipient@example.com".to_string()],
                 use_tls: true,
                 subject_template: None,
                 body_template: None,
+                text_template: None,
+                dkim: None,
+                attach_chart: false,
             }),
             telegram: None,
             slack: None,
             discord: None,
+            mattermost: None,
+            pagerduty: None,
+            grafana_oncall: None,
+            pushover: None,
+            ntfy: None,
+            sns: None,
+            kafka: None,
+            nats: None,
+            sms: None,
+            signal: None,
+            capture: None,
             rate_limiting: RateLimitConfig::default(),
+            http: HttpConfig::default(),
             global: GlobalNotificationConfig::default(),
+            retry: RetryConfig::default(),
+            routing: HashMap::new(),
+            program_routes: Vec::new(),
+            escalation: EscalationConfig::default(),
+            quiet_hours: HashMap::new(),
+            digest: DigestConfig::default(),
+            health: HealthCheckConfig::default(),
+            templates: TemplatesConfig::default(),
+        };
+        let alert_manager = Arc::new(AlertManager::new());
+        let manager = NotificationManager::new(config)
+            .await
+            .unwrap()
+            .with_alert_manager(alert_manager.clone());
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transfer".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
         };
+        alert_manager.send_alert(alert.clone()).await.unwrap();
+
+        let reply = manager
+            .handle_command(BotCommand::Alerts, "telegram:operator")
+            .await;
+        assert!(reply.contains("alert-1"));
+
+        // Muted rules are not delivered
+        manager
+            .handle_command(
+                BotCommand::Mute {
+                    rule: "large_transaction".to_string(),
+                    duration: chrono::Duration::hours(1),
+                },
+                "telegram:operator",
+            )
+            .await;
+        assert!(manager
+            .muted_rules()
+            .await
+            .contains_key("large_transaction"));
+        manager.send_notification(alert).await.unwrap();
+        assert_eq!(manager.statistics().await.total_sent, 0);
+        assert!(manager.outbox().is_empty());
+
+        let reply = manager
+            .handle_command(
+                BotCommand::Ack {
+                    alert_id: "alert-1".to_string(),
+                },
+                "telegram:operator",
+            )
+            .await;
+        assert_eq!(reply, "Alert alert-1 acknowledged");
+        assert!(alert_manager.get_alert("alert-1").unwrap().acknowledged);
 
-        let result = NotificationManager::new(config).await;
-        assert!(result.is_ok());
+        assert!(manager.unmute_rule("large_transaction").await);
+        assert!(!manager.unmute_rule("large_transaction").await);
     }
 
     #[test]
@@ -599,11 +2231,31 @@ mod tests {
             telegram: None,
             slack: None,
             discord: None,
+            mattermost: None,
+            pagerduty: None,
+            grafana_oncall: None,
+            pushover: None,
+            ntfy: None,
+            sns: None,
+            kafka: None,
+            nats: None,
+            sms: None,
+            signal: None,
+            capture: None,
             rate_limiting: RateLimitConfig::default(),
+            http: HttpConfig::default(),
             global: GlobalNotificationConfig {
                 min_severity: "high".to_string(),
                 ..Default::default()
             },
+            retry: RetryConfig::default(),
+            routing: HashMap::new(),
+            program_routes: Vec::new(),
+            escalation: EscalationConfig::default(),
+            quiet_hours: HashMap::new(),
+            digest: DigestConfig::default(),
+            health: HealthCheckConfig::default(),
+            templates: TemplatesConfig::default(),
         };
 
         // This would fail validation due to no channels, but we're testing the logic
@@ -612,8 +2264,22 @@ mod tests {
             rate_limiters: HashMap::new(),
             config,
             batch_manager: None,
+            batch_receiver: Mutex::new(None),
             filters: Vec::new(),
+            outbox: Outbox::in_memory(10),
+            quiet_hours: HashMap::new(),
+            held_alerts: RwLock::new(HashMap::new()),
+            digests: RwLock::new(HashMap::new()),
+            muted_rules: RwLock::new(HashMap::new()),
+            escalations: RwLock::new(HashMap::new()),
+            recently_sent: Mutex::new(HashMap::new()),
+            disabled_channels: std::sync::RwLock::new(HashSet::new()),
+            templates: TemplateEngine::new(),
+            capture: None,
+            breakers: HashMap::new(),
             stats: Arc::new(RwLock::new(NotificationStats::default())),
+            metrics: None,
+            alert_manager: None,
         };
 
         let high_alert = Alert {
@@ -630,6 +2296,7 @@ mod tests {
             timestamp: chrono::Utc::now(),
             acknowledged: false,
             resolved: false,
+            tags: Vec::new(),
         };
 
         let low_alert = Alert {
@@ -640,4 +2307,762 @@ mod tests {
         assert!(manager.meets_minimum_severity(&high_alert));
         assert!(!manager.meets_minimum_severity(&low_alert));
     }
+
+    #[tokio::test]
+    async fn test_batch_manager_hands_over_batches() {
+        let (ready_tx, mut ready_rx) = mpsc::unbounded_channel();
+        let batch_manager = BatchManager::new(Duration::from_secs(3600), 2, ready_tx)
+            .await
+            .unwrap();
+
+        let alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test message".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        let channels = vec!["email".to_string(), "slack".to_string()];
+
+        // Full batches are handed over for every channel
+        batch_manager
+            .add_alert(alert.clone(), channels.clone())
+            .await;
+        batch_manager
+            .add_alert(alert.clone(), channels.clone())
+            .await;
+        let mut handed_over = [ready_rx.try_recv().unwrap(), ready_rx.try_recv().unwrap()];
+        handed_over.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(handed_over[0].0, "email");
+        assert_eq!(handed_over[0].1.len(), 2);
+        assert_eq!(handed_over[1].0, "slack");
+
+        // Partial batches are returned on shutdown
+        batch_manager
+            .add_alert(alert, vec!["email".to_string()])
+            .await;
+        let pending = batch_manager.shutdown().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].1.len(), 1);
+        assert!(ready_rx.try_recv().is_err());
+    }
+
+    /// Channel whose deliveries fail while `failing` is set.
+    struct FlakyChannel {
+        name: String,
+        failing: Arc<AtomicBool>,
+        attempts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl NotificationChannel for FlakyChannel {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn send(
+            &self,
+            _alert: &Alert,
+            _template_data: &HashMap<String, Value>,
+        ) -> NotifierResult<Option<String>> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.failing.load(Ordering::SeqCst) {
+                Err(NotifierError::Generic("connection reset".to_string()))
+            } else {
+                Ok(Some(format!("{}-{}", self.name, attempt)))
+            }
+        }
+
+        async fn test(&self) -> NotifierResult<()> {
+            if self.failing.load(Ordering::SeqCst) {
+                Err(NotifierError::Generic("connection reset".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_from_outbox() {
+        let failing = Arc::new(AtomicBool::new(true));
+        let flaky_attempts = Arc::new(AtomicU32::new(0));
+        let healthy_attempts = Arc::new(AtomicU32::new(0));
+
+        let mut channels: HashMap<String, Box<dyn NotificationChannel>> = HashMap::new();
+        channels.insert(
+            "flaky".to_string(),
+            Box::new(FlakyChannel {
+                name: "flaky".to_string(),
+                failing: failing.clone(),
+                attempts: flaky_attempts.clone(),
+            }),
+        );
+        channels.insert(
+            "healthy".to_string(),
+            Box::new(FlakyChannel {
+                name: "healthy".to_string(),
+                failing: Arc::new(AtomicBool::new(false)),
+                attempts: healthy_attempts.clone(),
+            }),
+        );
+
+        let alert_manager = Arc::new(AlertManager::new());
+        let manager = NotificationManager {
+            channels,
+            rate_limiters: HashMap::new(),
+            config: NotifierConfig {
+                email: None,
+                telegram: None,
+                slack: None,
+                discord: None,
+                mattermost: None,
+                pagerduty: None,
+                grafana_oncall: None,
+                pushover: None,
+                ntfy: None,
+                sns: None,
+                kafka: None,
+                nats: None,
+                sms: None,
+                signal: None,
+                capture: None,
+                rate_limiting: RateLimitConfig::default(),
+                http: HttpConfig::default(),
+                global: GlobalNotificationConfig::default(),
+                retry: RetryConfig {
+                    max_attempts: 2,
+                    initial_backoff_ms: 0,
+                    ..Default::default()
+                },
+                routing: HashMap::new(),
+                program_routes: Vec::new(),
+                escalation: EscalationConfig::default(),
+                quiet_hours: HashMap::new(),
+                digest: DigestConfig::default(),
+                health: HealthCheckConfig::default(),
+                templates: TemplatesConfig::default(),
+            },
+            batch_manager: None,
+            batch_receiver: Mutex::new(None),
+            filters: Vec::new(),
+            outbox: Outbox::in_memory(10),
+            quiet_hours: HashMap::new(),
+            held_alerts: RwLock::new(HashMap::new()),
+            digests: RwLock::new(HashMap::new()),
+            muted_rules: RwLock::new(HashMap::new()),
+            escalations: RwLock::new(HashMap::new()),
+            recently_sent: Mutex::new(HashMap::new()),
+            disabled_channels: std::sync::RwLock::new(HashSet::new()),
+            templates: TemplateEngine::new(),
+            capture: None,
+            breakers: HashMap::new(),
+            stats: Arc::new(RwLock::new(NotificationStats::default())),
+            metrics: None,
+            alert_manager: Some(alert_manager.clone()),
+        };
+
+        let alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test message".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        // A failing channel is retried and does not stop delivery to the others
+        let result = manager
+            .send_immediate(alert, vec!["flaky".to_string(), "healthy".to_string()])
+            .await;
+        assert!(result.is_err());
+        assert_eq!(flaky_attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(healthy_attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(manager.outbox().len(), 1);
+        assert_eq!(manager.queue_depths().await.outbox, 1);
+
+        // Once the channel recovers the outbox delivers the notification
+        failing.store(false, Ordering::SeqCst);
+        manager.retry_outbox().await;
+        assert!(manager.outbox().is_empty());
+        assert_eq!(manager.statistics().await.redelivered, 1);
+
+        // Every attempt is in the delivery history, with the provider's message ID
+        let history = alert_manager.delivery_history("test");
+        let outcomes: Vec<(&str, DeliveryOutcome)> = history
+            .iter()
+            .map(|record| (record.channel.as_str(), record.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("flaky", DeliveryOutcome::Failed),
+                ("flaky", DeliveryOutcome::Failed),
+                ("healthy", DeliveryOutcome::Delivered),
+                ("flaky", DeliveryOutcome::Delivered),
+            ]
+        );
+        assert_eq!(history[3].provider_message_id.as_deref(), Some("flaky-3"));
+        assert_eq!(
+            history[0].error.as_deref(),
+            Some("Notifier error: connection reset")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeats_suppressed_within_dedup_window() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let mut channels: HashMap<String, Box<dyn NotificationChannel>> = HashMap::new();
+        channels.insert(
+            "webhook".to_string(),
+            Box::new(FlakyChannel {
+                name: "webhook".to_string(),
+                failing: Arc::new(AtomicBool::new(false)),
+                attempts: attempts.clone(),
+            }),
+        );
+
+        let manager = NotificationManager {
+            channels,
+            rate_limiters: HashMap::new(),
+            config: NotifierConfig {
+                email: None,
+                telegram: None,
+                slack: None,
+                discord: None,
+                mattermost: None,
+                pagerduty: None,
+                grafana_oncall: None,
+                pushover: None,
+                ntfy: None,
+                sns: None,
+                kafka: None,
+                nats: None,
+                sms: None,
+                signal: None,
+                capture: None,
+                rate_limiting: RateLimitConfig::default(),
+                http: HttpConfig::default(),
+                global: GlobalNotificationConfig {
+                    dedup_window_seconds: 300,
+                    ..Default::default()
+                },
+                retry: RetryConfig::default(),
+                routing: HashMap::new(),
+                program_routes: Vec::new(),
+                escalation: EscalationConfig::default(),
+                quiet_hours: HashMap::new(),
+                digest: DigestConfig::default(),
+                health: HealthCheckConfig::default(),
+                templates: TemplatesConfig::default(),
+            },
+            batch_manager: None,
+            batch_receiver: Mutex::new(None),
+            filters: Vec::new(),
+            outbox: Outbox::in_memory(10),
+            quiet_hours: HashMap::new(),
+            held_alerts: RwLock::new(HashMap::new()),
+            digests: RwLock::new(HashMap::new()),
+            muted_rules: RwLock::new(HashMap::new()),
+            escalations: RwLock::new(HashMap::new()),
+            recently_sent: Mutex::new(HashMap::new()),
+            disabled_channels: std::sync::RwLock::new(HashSet::new()),
+            templates: TemplateEngine::new(),
+            capture: None,
+            breakers: HashMap::new(),
+            stats: Arc::new(RwLock::new(NotificationStats::default())),
+            metrics: None,
+            alert_manager: None,
+        };
+
+        let alert = Alert {
+            id: "first".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transaction detected".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        let repeat = Alert {
+            id: "second".to_string(),
+            ..alert.clone()
+        };
+        let other_rule = Alert {
+            id: "third".to_string(),
+            rule_name: "liquidity_drop".to_string(),
+            ..alert.clone()
+        };
+
+        let channels = vec!["webhook".to_string()];
+        manager
+            .send_immediate(alert, channels.clone())
+            .await
+            .unwrap();
+        manager
+            .send_immediate(repeat, channels.clone())
+            .await
+            .unwrap();
+        manager
+            .send_immediate(other_rule, channels.clone())
+            .await
+            .unwrap();
+
+        // Only the repeat of the same fingerprint is suppressed
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        let stats = manager.statistics().await;
+        assert_eq!(stats.total_sent, 2);
+        assert_eq!(stats.deduplicated, 1);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_routes_to_fallback() {
+        let failing = Arc::new(AtomicBool::new(true));
+        let flaky_attempts = Arc::new(AtomicU32::new(0));
+        let fallback_attempts = Arc::new(AtomicU32::new(0));
+
+        let mut channels: HashMap<String, Box<dyn NotificationChannel>> = HashMap::new();
+        channels.insert(
+            "flaky".to_string(),
+            Box::new(FlakyChannel {
+                name: "flaky".to_string(),
+                failing: failing.clone(),
+                attempts: flaky_attempts.clone(),
+            }),
+        );
+        channels.insert(
+            "fallback".to_string(),
+            Box::new(FlakyChannel {
+                name: "fallback".to_string(),
+                failing: Arc::new(AtomicBool::new(false)),
+                attempts: fallback_attempts.clone(),
+            }),
+        );
+        let breakers = channels
+            .keys()
+            .map(|name| (name.clone(), CircuitBreaker::new(2)))
+            .collect();
+
+        let alert_manager = Arc::new(AlertManager::new());
+        let manager = NotificationManager {
+            channels,
+            rate_limiters: HashMap::new(),
+            config: NotifierConfig {
+                email: None,
+                telegram: None,
+                slack: None,
+                discord: None,
+                mattermost: None,
+                pagerduty: None,
+                grafana_oncall: None,
+                pushover: None,
+                ntfy: None,
+                sns: None,
+                kafka: None,
+                nats: None,
+                sms: None,
+                signal: None,
+                capture: None,
+                rate_limiting: RateLimitConfig::default(),
+                http: HttpConfig::default(),
+                global: GlobalNotificationConfig::default(),
+                retry: RetryConfig {
+                    max_attempts: 1,
+                    ..Default::default()
+                },
+                routing: HashMap::new(),
+                program_routes: Vec::new(),
+                escalation: EscalationConfig::default(),
+                quiet_hours: HashMap::new(),
+                digest: DigestConfig::default(),
+                health: HealthCheckConfig {
+                    enabled: true,
+                    failure_threshold: 2,
+                    fallbacks: HashMap::from([("flaky".to_string(), vec!["fallback".to_string()])]),
+                    ..Default::default()
+                },
+                templates: TemplatesConfig::default(),
+            },
+            batch_manager: None,
+            batch_receiver: Mutex::new(None),
+            filters: Vec::new(),
+            outbox: Outbox::in_memory(10),
+            quiet_hours: HashMap::new(),
+            held_alerts: RwLock::new(HashMap::new()),
+            digests: RwLock::new(HashMap::new()),
+            muted_rules: RwLock::new(HashMap::new()),
+            escalations: RwLock::new(HashMap::new()),
+            recently_sent: Mutex::new(HashMap::new()),
+            disabled_channels: std::sync::RwLock::new(HashSet::new()),
+            templates: TemplateEngine::new(),
+            capture: None,
+            breakers,
+            stats: Arc::new(RwLock::new(NotificationStats::default())),
+            metrics: None,
+            alert_manager: Some(alert_manager.clone()),
+        };
+
+        let alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test message".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        let mut meta_alerts = alert_manager.subscribe();
+
+        // The circuit opens after two failed health checks and raises a meta-alert
+        manager.run_health_checks().await;
+        assert!(meta_alerts.try_recv().is_err());
+        manager.run_health_checks().await;
+        assert!(matches!(
+            manager.circuit_states()["flaky"],
+            CircuitState::Open { .. }
+        ));
+        let meta_alert = meta_alerts.try_recv().unwrap();
+        assert_eq!(meta_alert.rule_name, "flaky_channel_down");
+        assert_eq!(
+            meta_alert.metadata["fallbacks"],
+            serde_json::json!(["fallback"])
+        );
+
+        // Alerts for the broken channel go to its fallback instead
+        manager
+            .send_immediate(alert, vec!["flaky".to_string(), "fallback".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(flaky_attempts.load(Ordering::SeqCst), 0);
+        assert_eq!(fallback_attempts.load(Ordering::SeqCst), 1);
+
+        // A successful health check closes the circuit again
+        failing.store(false, Ordering::SeqCst);
+        manager.run_health_checks().await;
+        assert_eq!(manager.circuit_states()["flaky"], CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_escalation_until_acknowledged() {
+        let first_attempts = Arc::new(AtomicU32::new(0));
+        let second_attempts = Arc::new(AtomicU32::new(0));
+
+        let mut channels: HashMap<String, Box<dyn NotificationChannel>> = HashMap::new();
+        channels.insert(
+            "first".to_string(),
+            Box::new(FlakyChannel {
+                name: "first".to_string(),
+                failing: Arc::new(AtomicBool::new(false)),
+                attempts: first_attempts.clone(),
+            }),
+        );
+        channels.insert(
+            "second".to_string(),
+            Box::new(FlakyChannel {
+                name: "second".to_string(),
+                failing: Arc::new(AtomicBool::new(false)),
+                attempts: second_attempts.clone(),
+            }),
+        );
+
+        let alert_manager = Arc::new(AlertManager::new());
+        let manager = NotificationManager {
+            channels,
+            rate_limiters: HashMap::new(),
+            config: NotifierConfig {
+                email: None,
+                telegram: None,
+                slack: None,
+                discord: None,
+                mattermost: None,
+                pagerduty: None,
+                grafana_oncall: None,
+                pushover: None,
+                ntfy: None,
+                sns: None,
+                kafka: None,
+                nats: None,
+                sms: None,
+                signal: None,
+                capture: None,
+                rate_limiting: RateLimitConfig::default(),
+                http: HttpConfig::default(),
+                global: GlobalNotificationConfig::default(),
+                retry: RetryConfig::default(),
+                routing: HashMap::new(),
+                program_routes: Vec::new(),
+                escalation: EscalationConfig {
+                    chains: vec![EscalationChain {
+                        name: "on-call".to_string(),
+                        severities: vec!["critical".to_string()],
+                        rules: Vec::new(),
+                        steps: vec![
+                            EscalationStep {
+                                after_minutes: 0,
+                                channels: vec!["first".to_string()],
+                            },
+                            EscalationStep {
+                                after_minutes: 0,
+                                channels: vec!["second".to_string()],
+                            },
+                        ],
+                    }],
+                    ..Default::default()
+                },
+                quiet_hours: HashMap::new(),
+                digest: DigestConfig::default(),
+                health: HealthCheckConfig::default(),
+                templates: TemplatesConfig::default(),
+            },
+            batch_manager: None,
+            batch_receiver: Mutex::new(None),
+            filters: Vec::new(),
+            outbox: Outbox::in_memory(10),
+            quiet_hours: HashMap::new(),
+            held_alerts: RwLock::new(HashMap::new()),
+            digests: RwLock::new(HashMap::new()),
+            muted_rules: RwLock::new(HashMap::new()),
+            escalations: RwLock::new(HashMap::new()),
+            recently_sent: Mutex::new(HashMap::new()),
+            disabled_channels: std::sync::RwLock::new(HashSet::new()),
+            templates: TemplateEngine::new(),
+            capture: None,
+            breakers: HashMap::new(),
+            stats: Arc::new(RwLock::new(NotificationStats::default())),
+            metrics: None,
+            alert_manager: Some(alert_manager.clone()),
+        };
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "drain".to_string(),
+            message: "Vault drained".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        // The first step is taken right away, the next once it is due
+        manager.send_notification(alert.clone()).await.unwrap();
+        assert_eq!(first_attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(second_attempts.load(Ordering::SeqCst), 0);
+        assert_eq!(manager.escalations().await.len(), 1);
+
+        manager.escalate_due().await;
+        assert_eq!(second_attempts.load(Ordering::SeqCst), 1);
+        assert!(manager.escalations().await.is_empty());
+
+        // Acknowledged alerts are not escalated further
+        let mut acknowledged = alert.clone();
+        acknowledged.id = "alert-2".to_string();
+        acknowledged.rule_name = "mint".to_string();
+        alert_manager
+            .send_alert(acknowledged.clone())
+            .await
+            .unwrap();
+        manager.send_notification(acknowledged).await.unwrap();
+        alert_manager.acknowledge_alert("alert-2").await.unwrap();
+        manager.escalate_due().await;
+        assert_eq!(second_attempts.load(Ordering::SeqCst), 1);
+        assert!(manager.escalations().await.is_empty());
+
+        // Acknowledging from chat cancels the escalation
+        let mut acked_from_chat = alert.clone();
+        acked_from_chat.id = "alert-3".to_string();
+        acked_from_chat.rule_name = "freeze".to_string();
+        alert_manager
+            .send_alert(acked_from_chat.clone())
+            .await
+            .unwrap();
+        manager.send_notification(acked_from_chat).await.unwrap();
+        assert_eq!(manager.escalations().await.len(), 1);
+        manager
+            .handle_command(
+                BotCommand::Ack {
+                    alert_id: "alert-3".to_string(),
+                },
+                "telegram:operator",
+            )
+            .await;
+        assert!(manager.escalations().await.is_empty());
+
+        // Resolved alerts are not escalated further either
+        let mut resolved = alert.clone();
+        resolved.id = "alert-5".to_string();
+        resolved.rule_name = "withdraw".to_string();
+        alert_manager.send_alert(resolved.clone()).await.unwrap();
+        manager.send_notification(resolved).await.unwrap();
+        alert_manager.resolve_alert("alert-5").await.unwrap();
+        manager.escalate_due().await;
+        assert_eq!(second_attempts.load(Ordering::SeqCst), 1);
+        assert!(manager.escalations().await.is_empty());
+
+        // Alerts no chain applies to use the regular routing
+        let mut high = alert;
+        high.id = "alert-4".to_string();
+        high.severity = AlertSeverity::High;
+        manager.send_notification(high).await.unwrap();
+        assert!(manager.escalations().await.is_empty());
+        assert_eq!(first_attempts.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_per_channel_keyed_rate_limits() {
+        let mut rate_limiting = RateLimitConfig::default();
+        rate_limiting.channels.insert(
+            "telegram".to_string(),
+            ChannelRateLimit {
+                max_messages_per_minute: Some(20),
+                burst_size: Some(1),
+            },
+        );
+
+        assert_eq!(rate_limiting.quota("telegram").burst_size().get(), 1);
+        assert_eq!(rate_limiting.quota("discord").burst_size().get(), 5);
+
+        // Each destination has its own budget
+        let limiter: DefaultKeyedRateLimiter<String> =
+            RateLimiter::keyed(rate_limiting.quota("telegram"));
+        assert!(limiter.check_key(&"chat-a".to_string()).is_ok());
+        assert!(limiter.check_key(&"chat-a".to_string()).is_err());
+        assert!(limiter.check_key(&"chat-b".to_string()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_severity_routing_before_filters() {
+        let config = NotifierConfig {
+            email: None,
+            telegram: None,
+            slack: Some(SlackConfig {
+                webhook_url: "https://hooks.slack.com/services/T/B/X".to_string(),
+                auth: WebhookAuthConfig::default(),
+                bot_token: None,
+                channel: None,
+                username: None,
+                icon: None,
+                message_template: None,
+                custom_fields: None,
+                interactive: false,
+                thread_related_alerts: true,
+                thread_window_seconds: 86400,
+                attach_chart: false,
+            }),
+            discord: None,
+            mattermost: None,
+            pagerduty: Some(PagerDutyConfig {
+                routing_key: "routing-key".to_string(),
+                events_url: "https://events.pagerduty.com/v2/enqueue".to_string(),
+                source: "watchtower".to_string(),
+                severity_mapping: HashMap::new(),
+            }),
+            grafana_oncall: None,
+            pushover: None,
+            ntfy: None,
+            sns: None,
+            kafka: None,
+            nats: None,
+            sms: None,
+            signal: None,
+            capture: None,
+            rate_limiting: RateLimitConfig::default(),
+            http: HttpConfig::default(),
+            global: GlobalNotificationConfig::default(),
+            retry: RetryConfig::default(),
+            routing: HashMap::from([
+                ("info".to_string(), vec!["slack".to_string()]),
+                (
+                    "critical".to_string(),
+                    vec!["pagerduty".to_string(), "slack".to_string()],
+                ),
+            ]),
+            program_routes: Vec::new(),
+            escalation: EscalationConfig::default(),
+            quiet_hours: HashMap::new(),
+            digest: DigestConfig::default(),
+            health: HealthCheckConfig::default(),
+            templates: TemplatesConfig::default(),
+        };
+        let manager = NotificationManager::new(config).await.unwrap();
+
+        let alert = Alert {
+            id: "test".to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test message".to_string(),
+            severity: AlertSeverity::Info,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+        assert_eq!(manager.apply_filters(&alert).await, vec!["slack"]);
+
+        let critical = Alert {
+            severity: AlertSeverity::Critical,
+            ..alert.clone()
+        };
+        assert_eq!(
+            manager.apply_filters(&critical).await,
+            vec!["slack", "pagerduty"]
+        );
+
+        // Severities without a route go to every channel
+        let medium = Alert {
+            severity: AlertSeverity::Medium,
+            ..alert
+        };
+        assert_eq!(manager.apply_filters(&medium).await.len(), 2);
+
+        // Channels switched off at runtime are skipped until switched back on
+        assert_eq!(manager.channel_names(), vec!["pagerduty", "slack"]);
+        manager.set_channel_enabled("slack", false).unwrap();
+        assert!(!manager.is_channel_enabled("slack"));
+        assert_eq!(manager.apply_filters(&critical).await, vec!["pagerduty"]);
+        manager.set_channel_enabled("slack", true).unwrap();
+        assert_eq!(manager.apply_filters(&critical).await.len(), 2);
+        assert!(manager.set_channel_enabled("discord", false).is_err());
+    }
 }
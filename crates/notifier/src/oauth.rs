@@ -0,0 +1,146 @@
+//! OAuth2 access tokens for XOAUTH2 SMTP authentication.
+
+use crate::error::{NotifierError, NotifierResult};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::debug;
+
+/// Tokens are refreshed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// OAuth2 refresh-token configuration for SMTP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpOAuth2Config {
+    /// OAuth2 client ID
+    pub client_id: String,
+
+    /// OAuth2 client secret
+    pub client_secret: String,
+
+    /// Long-lived refresh token authorized for mail sending
+    pub refresh_token: String,
+
+    /// Token endpoint (Google by default)
+    #[serde(default = "default_token_url")]
+    pub token_url: String,
+
+    /// Scope requested with the refresh (some providers, e.g. Microsoft, require it)
+    pub scope: Option<String>,
+}
+
+/// Token endpoint response.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+/// Caches an access token and refreshes it shortly before it expires.
+pub struct OAuth2TokenSource {
+    config: SmtpOAuth2Config,
+    client: Client,
+
+    /// Current token and when it expires
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl OAuth2TokenSource {
+    /// Create a token source; no token is fetched until one is needed.
+    pub fn new(config: SmtpOAuth2Config) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Request tokens with a configured HTTP client.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Valid access token, refreshed if it is missing or about to expire.
+    pub async fn access_token(&self) -> NotifierResult<String> {
+        let mut token = self.token.lock().await;
+
+        if let Some((access_token, expires_at)) = token.as_ref() {
+            if Instant::now() + TOKEN_REFRESH_MARGIN < *expires_at {
+                return Ok(access_token.clone());
+            }
+        }
+
+        let (access_token, expires_at) = self.refresh().await?;
+        *token = Some((access_token.clone(), expires_at));
+        Ok(access_token)
+    }
+
+    /// Exchange the refresh token for a new access token.
+    async fn refresh(&self) -> NotifierResult<(String, Instant)> {
+        let mut form = vec![
+            ("grant_type", "refresh_token"),
+            ("client_id", self.config.client_id.as_str()),
+            ("client_secret", self.config.client_secret.as_str()),
+            ("refresh_token", self.config.refresh_token.as_str()),
+        ];
+        if let Some(scope) = &self.config.scope {
+            form.push(("scope", scope.as_str()));
+        }
+
+        let response = self
+            .client
+            .post(&self.config.token_url)
+            .form(&form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(NotifierError::Authentication {
+                channel: "email".to_string(),
+                reason: format!("OAuth2 token refresh failed: {}", error_text),
+            });
+        }
+
+        let token: TokenResponse = response.json().await?;
+        // Tokens without a lifetime are treated as valid for an hour
+        let expires_in = Duration::from_secs(token.expires_in.unwrap_or(3600));
+        debug!("Refreshed SMTP OAuth2 access token");
+
+        Ok((token.access_token, Instant::now() + expires_in))
+    }
+}
+
+// Default value functions
+fn default_token_url() -> String {
+    "https://oauth2.googleapis.com/token".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cached_token_is_reused_until_expiry() {
+        let source = OAuth2TokenSource::new(SmtpOAuth2Config {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            refresh_token: "refresh".to_string(),
+            // Unreachable, so any refresh fails
+            token_url: "http://127.0.0.1:9/token".to_string(),
+            scope: None,
+        });
+
+        *source.token.lock().await = Some((
+            "cached".to_string(),
+            Instant::now() + Duration::from_secs(3600),
+        ));
+        assert_eq!(source.access_token().await.unwrap(), "cached");
+
+        // Tokens about to expire are refreshed
+        *source.token.lock().await = Some(("stale".to_string(), Instant::now()));
+        assert!(source.access_token().await.is_err());
+    }
+}
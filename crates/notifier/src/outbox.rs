@@ -0,0 +1,277 @@
+//! Persistent outbox of notifications awaiting redelivery.
+
+use crate::error::NotifierResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+use watchtower_engine::Alert;
+
+/// A notification that could not be delivered to a channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// Outbox-assigned identifier
+    pub id: u64,
+
+    /// Channel the notification is for
+    pub channel: String,
+
+    /// Alert to deliver
+    pub alert: Alert,
+
+    /// Number of failed delivery attempts so far
+    pub attempts: u32,
+
+    /// Last delivery error
+    pub last_error: String,
+
+    /// When the first delivery attempt failed
+    pub first_failed_at: DateTime<Utc>,
+
+    /// When the next delivery attempt is due
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+/// Bounded outbox of undelivered notifications, optionally persisted to a
+/// JSON file so they survive restarts.
+#[derive(Debug)]
+pub struct Outbox {
+    /// File the entries are persisted to
+    path: Option<PathBuf>,
+
+    /// Entries in arrival order
+    entries: Mutex<Vec<OutboxEntry>>,
+
+    /// Maximum number of entries
+    capacity: usize,
+
+    /// Next entry identifier
+    next_id: AtomicU64,
+}
+
+impl Outbox {
+    /// Create an in-memory outbox that is not persisted.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self {
+            path: None,
+            entries: Mutex::new(Vec::new()),
+            capacity,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Open an outbox backed by `path`, loading entries left by a previous run.
+    pub fn open(path: impl Into<PathBuf>, capacity: usize) -> NotifierResult<Self> {
+        let path = path.into();
+        let entries: Vec<OutboxEntry> = if path.exists() {
+            let contents = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&contents)?
+        } else {
+            Vec::new()
+        };
+
+        debug!(
+            "Loaded {} pending notifications from {}",
+            entries.len(),
+            path.display()
+        );
+
+        let next_id = entries.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+        Ok(Self {
+            path: Some(path),
+            entries: Mutex::new(entries),
+            capacity,
+            next_id: AtomicU64::new(next_id),
+        })
+    }
+
+    /// Path of the backing file, if persisted.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Queue a notification whose delivery failed, returning the new entry's identifier.
+    pub fn push(
+        &self,
+        channel: &str,
+        alert: Alert,
+        attempts: u32,
+        error: String,
+        next_attempt_at: DateTime<Utc>,
+    ) -> NotifierResult<u64> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.lock();
+
+        while entries.len() >= self.capacity.max(1) {
+            let oldest = entries.remove(0);
+            warn!(
+                "Notification outbox full, dropping {} notification for alert {}",
+                oldest.channel, oldest.alert.id
+            );
+        }
+
+        entries.push(OutboxEntry {
+            id,
+            channel: channel.to_string(),
+            alert,
+            attempts,
+            last_error: error,
+            first_failed_at: Utc::now(),
+            next_attempt_at,
+        });
+        self.save(&entries)?;
+
+        Ok(id)
+    }
+
+    /// Entries whose next attempt is due at `now`.
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<OutboxEntry> {
+        self.lock()
+            .iter()
+            .filter(|entry| entry.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// Record another failed attempt and schedule the next one.
+    pub fn reschedule(
+        &self,
+        id: u64,
+        error: String,
+        next_attempt_at: DateTime<Utc>,
+    ) -> NotifierResult<()> {
+        let mut entries = self.lock();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) {
+            entry.attempts += 1;
+            entry.last_error = error;
+            entry.next_attempt_at = next_attempt_at;
+        }
+        self.save(&entries)
+    }
+
+    /// Remove an entry, e.g. once delivered or given up on.
+    pub fn remove(&self, id: u64) -> NotifierResult<Option<OutboxEntry>> {
+        let mut entries = self.lock();
+        let removed = entries
+            .iter()
+            .position(|entry| entry.id == id)
+            .map(|position| entries.remove(position));
+        self.save(&entries)?;
+        Ok(removed)
+    }
+
+    /// All entries, oldest first.
+    pub fn list(&self) -> Vec<OutboxEntry> {
+        self.lock().clone()
+    }
+
+    /// Number of entries held.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the outbox is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Write the entries to the backing file.
+    fn save(&self, entries: &[OutboxEntry]) -> NotifierResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first so a crash never leaves a truncated outbox
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_string(entries)?)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<OutboxEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use watchtower_engine::AlertSeverity;
+
+    fn alert(id: &str) -> Alert {
+        Alert {
+            id: id.to_string(),
+            rule_name: "test_rule".to_string(),
+            message: "Test message".to_string(),
+            severity: AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_outbox_survives_reopen() {
+        let dir = std::env::temp_dir().join(format!(
+            "watchtower-outbox-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let path = dir.join("outbox.json");
+        let now = Utc::now();
+
+        let outbox = Outbox::open(&path, 2).unwrap();
+        outbox
+            .push("email", alert("a"), 3, "timeout".to_string(), now)
+            .unwrap();
+        let later = outbox
+            .push(
+                "slack",
+                alert("b"),
+                3,
+                "502".to_string(),
+                now + chrono::Duration::minutes(5),
+            )
+            .unwrap();
+
+        // Only due entries are returned
+        let due = outbox.due(now);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].channel, "email");
+
+        let reopened = Outbox::open(&path, 2).unwrap();
+        assert_eq!(reopened.len(), 2);
+        reopened.reschedule(later, "503".to_string(), now).unwrap();
+        assert_eq!(reopened.due(now).len(), 2);
+
+        // Identifiers keep increasing and the oldest entry is dropped when full
+        let newest = reopened
+            .push("email", alert("c"), 3, "timeout".to_string(), now)
+            .unwrap();
+        assert!(newest > later);
+        let ids: Vec<u64> = reopened.list().iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![later, newest]);
+        assert_eq!(reopened.list()[0].attempts, 4);
+
+        reopened.remove(later).unwrap();
+        reopened.remove(newest).unwrap();
+        assert!(Outbox::open(&path, 2).unwrap().is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
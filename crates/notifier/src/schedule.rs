@@ -0,0 +1,146 @@
+//! Quiet-hour schedules that hold non-critical notifications per channel.
+
+use crate::error::{NotifierError, NotifierResult};
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Quiet hours of a channel, during which only critical alerts are delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHoursConfig {
+    /// IANA timezone the periods are expressed in (e.g. `Europe/Berlin`)
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
+    /// Quiet periods
+    pub periods: Vec<QuietPeriodConfig>,
+}
+
+/// A recurring quiet period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietPeriodConfig {
+    /// Start time (`HH:MM`)
+    pub start: String,
+
+    /// End time (`HH:MM`); periods ending before they start span midnight
+    pub end: String,
+
+    /// Days the period starts on (e.g. `["sat", "sun"]`); every day if empty
+    #[serde(default)]
+    pub days: Vec<String>,
+}
+
+/// Parsed quiet-hour schedule.
+#[derive(Debug, Clone)]
+pub struct QuietHours {
+    timezone: Tz,
+    periods: Vec<QuietPeriod>,
+}
+
+#[derive(Debug, Clone)]
+struct QuietPeriod {
+    start: NaiveTime,
+    end: NaiveTime,
+    days: Vec<Weekday>,
+}
+
+impl QuietHours {
+    /// Parse a schedule, rejecting unknown timezones, times and days.
+    pub fn from_config(config: &QuietHoursConfig) -> NotifierResult<Self> {
+        let timezone: Tz = config.timezone.parse().map_err(|_| {
+            NotifierError::Configuration(format!("Unknown timezone: {}", config.timezone))
+        })?;
+
+        let periods = config
+            .periods
+            .iter()
+            .map(|period| {
+                let days = period
+                    .days
+                    .iter()
+                    .map(|day| {
+                        day.parse::<Weekday>().map_err(|_| {
+                            NotifierError::Configuration(format!("Invalid day: {}", day))
+                        })
+                    })
+                    .collect::<NotifierResult<Vec<_>>>()?;
+
+                Ok(QuietPeriod {
+                    start: parse_time(&period.start)?,
+                    end: parse_time(&period.end)?,
+                    days,
+                })
+            })
+            .collect::<NotifierResult<Vec<_>>>()?;
+
+        Ok(Self { timezone, periods })
+    }
+
+    /// Whether `now` falls into any quiet period.
+    pub fn is_quiet(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.timezone);
+        let time = local.time();
+        let today = local.weekday();
+
+        self.periods.iter().any(|period| {
+            if period.start <= period.end {
+                period.starts_on(today) && time >= period.start && time < period.end
+            } else {
+                // Spans midnight: the evening part starts today, the morning part yesterday
+                (period.starts_on(today) && time >= period.start)
+                    || (period.starts_on(today.pred()) && time < period.end)
+            }
+        })
+    }
+}
+
+impl QuietPeriod {
+    fn starts_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+fn parse_time(time: &str) -> NotifierResult<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| {
+        NotifierError::Configuration(format!("Invalid time (expected HH:MM): {}", time))
+    })
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_overnight_weekday_quiet_hours() {
+        let quiet_hours = QuietHours::from_config(&QuietHoursConfig {
+            timezone: "Europe/Berlin".to_string(),
+            periods: vec![QuietPeriodConfig {
+                start: "22:00".to_string(),
+                end: "07:00".to_string(),
+                days: vec!["fri".to_string()],
+            }],
+        })
+        .unwrap();
+
+        // Friday 23:30 and Saturday 06:30 in Berlin (UTC+2 in summer)
+        let friday_night = Utc.with_ymd_and_hms(2026, 6, 5, 21, 30, 0).unwrap();
+        let saturday_morning = Utc.with_ymd_and_hms(2026, 6, 6, 4, 30, 0).unwrap();
+        assert!(quiet_hours.is_quiet(friday_night));
+        assert!(quiet_hours.is_quiet(saturday_morning));
+
+        // Saturday 23:30 starts on a day without quiet hours
+        let saturday_night = Utc.with_ymd_and_hms(2026, 6, 6, 21, 30, 0).unwrap();
+        assert!(!quiet_hours.is_quiet(saturday_night));
+
+        assert!(QuietHours::from_config(&QuietHoursConfig {
+            timezone: "Mars/Olympus".to_string(),
+            periods: Vec::new(),
+        })
+        .is_err());
+    }
+}
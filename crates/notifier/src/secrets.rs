@@ -0,0 +1,277 @@
+//! Secret references in the notifier configuration.
+//!
+//! Secret fields may hold a reference instead of the secret itself:
+//! - `${VAR}` is replaced by an environment variable (also inside a value,
+//!   e.g. `https://hooks.slack.com/services/${SLACK_WEBHOOK_PATH}`)
+//! - `file:/path` reads a file, without its trailing newline
+//! - `vault:<path>#<field>` reads a field of a Vault KV secret, using the
+//!   `VAULT_ADDR` and `VAULT_TOKEN` environment variables
+
+use crate::config::NotifierConfig;
+use crate::error::{NotifierError, NotifierResult};
+use crate::http::WebhookAuthConfig;
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Resolves secret references, caching Vault secrets by path.
+pub struct SecretResolver {
+    client: Client,
+
+    /// Vault address and token
+    vault: Option<(String, String)>,
+
+    /// Vault secrets read so far
+    vault_cache: HashMap<String, Value>,
+}
+
+impl SecretResolver {
+    /// Create a resolver reading Vault settings from the environment.
+    pub fn from_env() -> Self {
+        let vault = match (std::env::var("VAULT_ADDR"), std::env::var("VAULT_TOKEN")) {
+            (Ok(address), Ok(token)) => Some((address, token)),
+            _ => None,
+        };
+
+        Self {
+            client: Client::new(),
+            vault,
+            vault_cache: HashMap::new(),
+        }
+    }
+
+    /// Value of a secret field, with references resolved.
+    pub async fn resolve(&mut self, value: &str) -> NotifierResult<String> {
+        if let Some(path) = value.strip_prefix("file:") {
+            let secret = std::fs::read_to_string(path).map_err(|e| {
+                NotifierError::Configuration(format!("Failed to read secret file {}: {}", path, e))
+            })?;
+            return Ok(secret.trim_end_matches(['\r', '\n']).to_string());
+        }
+
+        if let Some(reference) = value.strip_prefix("vault:") {
+            return self.read_vault(reference).await;
+        }
+
+        interpolate_env(value)
+    }
+
+    /// Read `<path>#<field>` from Vault (KV version 1 or 2).
+    async fn read_vault(&mut self, reference: &str) -> NotifierResult<String> {
+        let (path, field) = reference.split_once('#').ok_or_else(|| {
+            NotifierError::Configuration(format!(
+                "Vault secret reference '{}' must have the form vault:<path>#<field>",
+                reference
+            ))
+        })?;
+        let Some((address, token)) = &self.vault else {
+            return Err(NotifierError::Configuration(format!(
+                "VAULT_ADDR and VAULT_TOKEN must be set to read vault:{}",
+                reference
+            )));
+        };
+
+        if !self.vault_cache.contains_key(path) {
+            let response = self
+                .client
+                .get(format!(
+                    "{}/v1/{}",
+                    address.trim_end_matches('/'),
+                    path.trim_start_matches('/')
+                ))
+                .header("X-Vault-Token", token)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(NotifierError::Configuration(format!(
+                    "Failed to read Vault secret {}: HTTP {}",
+                    path,
+                    response.status()
+                )));
+            }
+
+            let body: Value = response.json().await?;
+            self.vault_cache.insert(path.to_string(), body);
+        }
+
+        let body = &self.vault_cache[path];
+        // KV version 2 nests the secret in data.data
+        body["data"]["data"][field]
+            .as_str()
+            .or_else(|| body["data"][field].as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                NotifierError::Configuration(format!(
+                    "Vault secret {} has no field '{}'",
+                    path, field
+                ))
+            })
+    }
+}
+
+/// Replace `${VAR}` references with environment variables.
+fn interpolate_env(value: &str) -> NotifierResult<String> {
+    let mut resolved = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + end];
+        let variable = std::env::var(name).map_err(|_| {
+            NotifierError::Configuration(format!("Environment variable {} is not set", name))
+        })?;
+
+        resolved.push_str(&rest[..start]);
+        resolved.push_str(&variable);
+        rest = &rest[start + end + 1..];
+    }
+    resolved.push_str(rest);
+
+    Ok(resolved)
+}
+
+impl NotifierConfig {
+    /// Resolve `${ENV_VAR}`, `file:` and `vault:` references in all secret fields.
+    pub async fn resolve_secrets(&mut self) -> NotifierResult<()> {
+        let mut resolver = SecretResolver::from_env();
+
+        for field in self.secret_fields() {
+            *field = resolver.resolve(field).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Fields holding credentials, tokens or secret URLs.
+    fn secret_fields(&mut self) -> Vec<&mut String> {
+        let mut fields = Vec::new();
+
+        if let Some(email) = &mut self.email {
+            fields.push(&mut email.password);
+            if let Some(oauth2) = &mut email.oauth2 {
+                fields.push(&mut oauth2.client_secret);
+                fields.push(&mut oauth2.refresh_token);
+            }
+            if let Some(dkim) = &mut email.dkim {
+                fields.extend(dkim.private_key.as_mut());
+            }
+        }
+        if let Some(telegram) = &mut self.telegram {
+            fields.push(&mut telegram.bot_token);
+        }
+        if let Some(slack) = &mut self.slack {
+            fields.push(&mut slack.webhook_url);
+            fields.extend(slack.bot_token.as_mut());
+            fields.extend(slack.auth.secret_fields());
+        }
+        if let Some(discord) = &mut self.discord {
+            fields.push(&mut discord.webhook_url);
+            fields.extend(discord.auth.secret_fields());
+        }
+        if let Some(mattermost) = &mut self.mattermost {
+            fields.push(&mut mattermost.webhook_url);
+            fields.extend(mattermost.auth.secret_fields());
+        }
+        if let Some(pagerduty) = &mut self.pagerduty {
+            fields.push(&mut pagerduty.routing_key);
+        }
+        if let Some(grafana_oncall) = &mut self.grafana_oncall {
+            fields.push(&mut grafana_oncall.webhook_url);
+        }
+        if let Some(pushover) = &mut self.pushover {
+            fields.push(&mut pushover.app_token);
+            fields.push(&mut pushover.user_key);
+        }
+        if let Some(ntfy) = &mut self.ntfy {
+            fields.extend(ntfy.access_token.as_mut());
+            fields.extend(ntfy.password.as_mut());
+        }
+        if let Some(nats) = &mut self.nats {
+            fields.extend(nats.token.as_mut());
+        }
+        if let Some(sms) = &mut self.sms {
+            fields.push(&mut sms.account_sid);
+            fields.push(&mut sms.auth_token);
+        }
+        if let Some(signal) = &mut self.signal {
+            fields.extend(signal.auth.secret_fields());
+        }
+
+        fields
+    }
+}
+
+impl WebhookAuthConfig {
+    /// Header values and bearer token, which usually hold credentials.
+    fn secret_fields(&mut self) -> impl Iterator<Item = &mut String> {
+        self.headers.values_mut().chain(self.bearer_token.as_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_secret_references() {
+        let mut resolver = SecretResolver {
+            client: Client::new(),
+            vault: None,
+            vault_cache: HashMap::new(),
+        };
+
+        assert_eq!(resolver.resolve("plain").await.unwrap(), "plain");
+
+        std::env::set_var("WATCHTOWER_TEST_SLACK_PATH", "T000/B000/XXX");
+        assert_eq!(
+            resolver
+                .resolve("https://hooks.slack.com/services/${WATCHTOWER_TEST_SLACK_PATH}")
+                .await
+                .unwrap(),
+            "https://hooks.slack.com/services/T000/B000/XXX"
+        );
+        assert!(resolver
+            .resolve("${WATCHTOWER_TEST_UNSET_SECRET}")
+            .await
+            .is_err());
+
+        let path = std::env::temp_dir().join(format!("watchtower-secret-{}", std::process::id()));
+        std::fs::write(&path, "smtp-password\n").unwrap();
+        assert_eq!(
+            resolver
+                .resolve(&format!("file:{}", path.display()))
+                .await
+                .unwrap(),
+            "smtp-password"
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        // Vault references need VAULT_ADDR and VAULT_TOKEN
+        assert!(resolver
+            .resolve("vault:secret/data/watchtower#smtp_password")
+            .await
+            .is_err());
+
+        resolver.vault_cache.insert(
+            "secret/data/watchtower".to_string(),
+            serde_json::json!({
+                "data": { "data": { "smtp_password": "from-vault" } }
+            }),
+        );
+        resolver.vault = Some(("http://127.0.0.1:9".to_string(), "token".to_string()));
+        assert_eq!(
+            resolver
+                .resolve("vault:secret/data/watchtower#smtp_password")
+                .await
+                .unwrap(),
+            "from-vault"
+        );
+        assert!(resolver
+            .resolve("vault:secret/data/watchtower")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Persistence and Prometheus export of notification statistics.
+
+use crate::error::NotifierResult;
+use crate::manager::NotificationStats;
+use prometheus::{IntCounterVec, Registry};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::debug;
+
+/// Prometheus counters mirroring the notification statistics.
+#[derive(Debug, Clone)]
+pub struct NotificationMetrics {
+    sent_total: IntCounterVec,
+    failed_total: IntCounterVec,
+    rate_limited_total: IntCounterVec,
+}
+
+impl NotificationStats {
+    /// Load statistics saved by a previous run, starting empty without a file.
+    pub fn load(path: &Path) -> NotifierResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let stats: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        debug!(
+            "Loaded notification statistics ({} sent) from {}",
+            stats.total_sent,
+            path.display()
+        );
+        Ok(stats)
+    }
+
+    /// Write the statistics to `path`.
+    pub fn save(&self, path: &Path) -> NotifierResult<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first so a crash never leaves truncated statistics
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_string(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
+impl NotificationMetrics {
+    /// Register the notification counters on a registry.
+    pub fn register(registry: &Registry) -> NotifierResult<Self> {
+        let sent_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "watchtower_notifications_sent_total",
+                "Total notifications sent",
+            ),
+            &["channel"],
+        )?;
+        registry.register(Box::new(sent_total.clone()))?;
+
+        let failed_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "watchtower_notifications_failed_total",
+                "Total notifications that failed after retrying",
+            ),
+            &["channel"],
+        )?;
+        registry.register(Box::new(failed_total.clone()))?;
+
+        let rate_limited_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "watchtower_notifications_rate_limited_total",
+                "Total notifications dropped by rate limits",
+            ),
+            &["channel"],
+        )?;
+        registry.register(Box::new(rate_limited_total.clone()))?;
+
+        Ok(Self {
+            sent_total,
+            failed_total,
+            rate_limited_total,
+        })
+    }
+
+    /// Bring the counters up to the statistics, which only ever grow.
+    pub fn update(&self, stats: &NotificationStats) {
+        sync_counters(&self.sent_total, &stats.sent_per_channel);
+        sync_counters(&self.failed_total, &stats.failed_per_channel);
+        sync_counters(&self.rate_limited_total, &stats.rate_limited_per_channel);
+    }
+}
+
+/// Increment each channel's counter to its count.
+fn sync_counters(counters: &IntCounterVec, counts: &HashMap<String, u64>) {
+    for (channel, count) in counts {
+        let counter = counters.with_label_values(&[channel.as_str()]);
+        counter.inc_by(count.saturating_sub(counter.get()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_persist_and_export() {
+        let path =
+            std::env::temp_dir().join(format!("watchtower-stats-{}.json", std::process::id()));
+        assert_eq!(NotificationStats::load(&path).unwrap().total_sent, 0);
+
+        let mut stats = NotificationStats {
+            total_sent: 3,
+            sent_per_channel: HashMap::from([("slack".to_string(), 3)]),
+            total_failed: 1,
+            failed_per_channel: HashMap::from([("email".to_string(), 1)]),
+            ..Default::default()
+        };
+        stats.save(&path).unwrap();
+        let loaded = NotificationStats::load(&path).unwrap();
+        assert_eq!(loaded.sent_per_channel["slack"], 3);
+        assert_eq!(loaded.failed_per_channel["email"], 1);
+        std::fs::remove_file(&path).unwrap();
+
+        // Counters start from the restored statistics and follow them
+        let registry = Registry::new();
+        let metrics = NotificationMetrics::register(&registry).unwrap();
+        metrics.update(&loaded);
+        stats.sent_per_channel.insert("slack".to_string(), 5);
+        metrics.update(&stats);
+        metrics.update(&stats);
+
+        let exported = prometheus::TextEncoder::new()
+            .encode_to_string(&registry.gather())
+            .unwrap();
+        assert!(exported.contains("watchtower_notifications_sent_total{channel=\"slack\"} 5"));
+        assert!(exported.contains("watchtower_notifications_failed_total{channel=\"email\"} 1"));
+    }
+}
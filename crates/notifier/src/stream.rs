@@ -0,0 +1,473 @@
+//! Kafka and NATS channels publishing alerts as JSON for stream processing
+//! and data-warehouse pipelines.
+
+use crate::{
+    channels::NotificationChannel,
+    config::parse_severity,
+    error::{NotifierError, NotifierResult},
+};
+use async_trait::async_trait;
+use rskafka::client::{
+    partition::{Compression, UnknownTopicHandling},
+    Client as KafkaClient, ClientBuilder,
+};
+use rskafka::record::Record;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::OnceCell;
+use tracing::info;
+use watchtower_engine::Alert;
+
+/// Kafka channel configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KafkaConfig {
+    /// Bootstrap brokers, e.g. `kafka-1.internal:9092`
+    pub brokers: Vec<String>,
+
+    /// Topic alerts are published to
+    pub topic: String,
+
+    /// Topics overriding `topic` per severity
+    #[serde(default)]
+    pub severity_topics: HashMap<String, String>,
+
+    /// Partition alerts are written to
+    #[serde(default)]
+    pub partition: i32,
+}
+
+/// NATS channel configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsConfig {
+    /// Server URL, e.g. `nats://nats.internal:4222`; `tls://` upgrades the
+    /// connection to TLS
+    pub url: String,
+
+    /// Subject alerts are published to
+    pub subject: String,
+
+    /// Subjects overriding `subject` per severity
+    #[serde(default)]
+    pub severity_subjects: HashMap<String, String>,
+
+    /// Authentication token
+    pub token: Option<String>,
+}
+
+/// Channel producing alerts to a Kafka topic.
+pub struct KafkaChannel {
+    config: KafkaConfig,
+    client: OnceCell<KafkaClient>,
+}
+
+/// Channel publishing alerts to a NATS subject.
+///
+/// Speaks the NATS core protocol directly over a connection opened per send,
+/// which is plenty for alert volumes and keeps the channel free of client
+/// library dependencies.
+pub struct NatsChannel {
+    config: NatsConfig,
+}
+
+impl KafkaConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> NotifierResult<()> {
+        if self.brokers.is_empty() {
+            return Err(NotifierError::Configuration(
+                "At least one Kafka broker must be specified".to_string(),
+            ));
+        }
+
+        validate_destinations("Kafka topic", &self.topic, &self.severity_topics)
+    }
+}
+
+impl NatsConfig {
+    /// Validate the configuration.
+    pub fn validate(&self) -> NotifierResult<()> {
+        if nats_address(&self.url).is_none() {
+            return Err(NotifierError::Configuration(format!(
+                "Invalid NATS server URL: {}",
+                self.url
+            )));
+        }
+
+        validate_destinations("NATS subject", &self.subject, &self.severity_subjects)
+    }
+}
+
+/// Whether a NATS URL uses TLS, and its `host:port` address.
+fn nats_address(url: &str) -> Option<(bool, String)> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("nats://") {
+        (false, rest)
+    } else {
+        (true, url.strip_prefix("tls://")?)
+    };
+
+    let host = rest.trim_end_matches('/');
+    if host.is_empty() || host.contains(['/', '@', '?']) {
+        return None;
+    }
+
+    if host
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+    {
+        Some((tls, host.to_string()))
+    } else {
+        Some((tls, format!("{}:4222", host)))
+    }
+}
+
+/// Check a default topic or subject and its per-severity overrides.
+fn validate_destinations(
+    kind: &str,
+    default: &str,
+    per_severity: &HashMap<String, String>,
+) -> NotifierResult<()> {
+    let check = |destination: &str| {
+        if destination.is_empty() || destination.contains(char::is_whitespace) {
+            return Err(NotifierError::Configuration(format!(
+                "Invalid {}: '{}'",
+                kind, destination
+            )));
+        }
+        Ok(())
+    };
+
+    check(default)?;
+    for (severity, destination) in per_severity {
+        if parse_severity(severity).is_none() {
+            return Err(NotifierError::Configuration(format!(
+                "Invalid severity '{}' for {}",
+                severity, kind
+            )));
+        }
+        check(destination)?;
+    }
+
+    Ok(())
+}
+
+/// Topic or subject of an alert's severity.
+fn destination_for<'a>(
+    alert: &Alert,
+    default: &'a str,
+    per_severity: &'a HashMap<String, String>,
+) -> &'a str {
+    per_severity
+        .get(alert.severity.as_str())
+        .map(String::as_str)
+        .unwrap_or(default)
+}
+
+impl KafkaChannel {
+    /// Create a Kafka channel; brokers are connected to on the first send.
+    pub fn new(config: KafkaConfig) -> Self {
+        Self {
+            config,
+            client: OnceCell::new(),
+        }
+    }
+
+    /// Topic an alert is produced to.
+    pub fn topic_for(&self, alert: &Alert) -> &str {
+        destination_for(alert, &self.config.topic, &self.config.severity_topics)
+    }
+
+    async fn client(&self) -> NotifierResult<&KafkaClient> {
+        self.client
+            .get_or_try_init(|| async {
+                ClientBuilder::new(self.config.brokers.clone())
+                    .build()
+                    .await
+                    .map_err(|e| NotifierError::Generic(format!("Kafka connection failed: {}", e)))
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for KafkaChannel {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        _template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let topic = self.topic_for(alert);
+        let partition = self
+            .client()
+            .await?
+            .partition_client(topic, self.config.partition, UnknownTopicHandling::Retry)
+            .await
+            .map_err(|e| NotifierError::Generic(format!("Kafka topic {}: {}", topic, e)))?;
+
+        // Keyed by fingerprint so consumers can group repeats of an alert
+        let record = Record {
+            key: Some(alert.fingerprint().into_bytes()),
+            value: Some(serde_json::to_vec(alert)?),
+            headers: BTreeMap::from([
+                (
+                    "severity".to_string(),
+                    alert.severity.as_str().as_bytes().to_vec(),
+                ),
+                ("rule_name".to_string(), alert.rule_name.as_bytes().to_vec()),
+            ]),
+            timestamp: alert.timestamp,
+        };
+
+        let offsets = partition
+            .produce(vec![record], Compression::NoCompression)
+            .await
+            .map_err(|e| NotifierError::Generic(format!("Kafka produce failed: {}", e)))?;
+
+        info!("Alert produced to Kafka topic {}", topic);
+        Ok(offsets
+            .first()
+            .map(|offset| format!("{}/{}/{}", topic, self.config.partition, offset)))
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        self.client()
+            .await?
+            .list_topics()
+            .await
+            .map_err(|e| NotifierError::Generic(format!("Kafka test failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+impl NatsChannel {
+    /// Create a NATS channel.
+    pub fn new(config: NatsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Subject an alert is published to.
+    pub fn subject_for(&self, alert: &Alert) -> &str {
+        destination_for(alert, &self.config.subject, &self.config.severity_subjects)
+    }
+
+    /// Connect to the server and run `commands`, waiting for the PONG that
+    /// confirms the server processed them.
+    async fn exchange(&self, commands: &[u8]) -> NotifierResult<()> {
+        let (tls, address) = nats_address(&self.config.url).ok_or_else(|| {
+            NotifierError::Configuration(format!("Invalid NATS server URL: {}", self.config.url))
+        })?;
+
+        let mut stream = BufReader::new(TcpStream::connect(&address).await?);
+        // The server greets with INFO before any TLS upgrade
+        let mut line = String::new();
+        stream.read_line(&mut line).await?;
+        if !line.starts_with("INFO ") {
+            return Err(NotifierError::Generic(format!(
+                "Unexpected NATS greeting: {}",
+                line.trim_end()
+            )));
+        }
+
+        if tls {
+            let host = address
+                .rsplit_once(':')
+                .map_or(address.as_str(), |(host, _)| host);
+            let connector = tokio_native_tls::native_tls::TlsConnector::new()
+                .map(tokio_native_tls::TlsConnector::from)
+                .map_err(|e| NotifierError::Generic(format!("NATS TLS setup failed: {}", e)))?;
+            let stream = connector
+                .connect(host, stream.into_inner())
+                .await
+                .map_err(|e| NotifierError::Generic(format!("NATS TLS handshake failed: {}", e)))?;
+            self.run(BufReader::new(stream), commands).await
+        } else {
+            self.run(stream, commands).await
+        }
+    }
+
+    async fn run<S>(&self, mut stream: BufReader<S>, commands: &[u8]) -> NotifierResult<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut connect = serde_json::json!({
+            "verbose": false,
+            "pedantic": false,
+            "headers": true,
+            "name": "watchtower",
+            "lang": "rust",
+            "version": env!("CARGO_PKG_VERSION"),
+            "protocol": 1,
+        });
+        if let Some(token) = &self.config.token {
+            connect["auth_token"] = Value::String(token.clone());
+        }
+
+        let mut request = format!("CONNECT {}\r\n", connect).into_bytes();
+        request.extend_from_slice(commands);
+        request.extend_from_slice(b"PING\r\n");
+        stream.get_mut().write_all(&request).await?;
+        stream.get_mut().flush().await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if stream.read_line(&mut line).await? == 0 {
+                return Err(NotifierError::Generic(
+                    "NATS server closed the connection".to_string(),
+                ));
+            }
+
+            match line.trim_end() {
+                "PONG" => return Ok(()),
+                "PING" => stream.get_mut().write_all(b"PONG\r\n").await?,
+                error if error.starts_with("-ERR") => {
+                    return Err(NotifierError::Generic(format!("NATS error: {}", error)))
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// HPUB command publishing `payload` with `headers` to `subject`.
+fn hpub(subject: &str, headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut header_block = String::from("NATS/1.0\r\n");
+    for (name, value) in headers {
+        // Header values cannot span lines
+        let value = value.replace(['\r', '\n'], " ");
+        header_block.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    header_block.push_str("\r\n");
+
+    let mut command = format!(
+        "HPUB {} {} {}\r\n",
+        subject,
+        header_block.len(),
+        header_block.len() + payload.len()
+    )
+    .into_bytes();
+    command.extend_from_slice(header_block.as_bytes());
+    command.extend_from_slice(payload);
+    command.extend_from_slice(b"\r\n");
+    command
+}
+
+#[async_trait]
+impl NotificationChannel for NatsChannel {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn send(
+        &self,
+        alert: &Alert,
+        _template_data: &HashMap<String, Value>,
+    ) -> NotifierResult<Option<String>> {
+        let subject = self.subject_for(alert);
+        let command = hpub(
+            subject,
+            &[
+                ("Watchtower-Severity", alert.severity.as_str()),
+                ("Watchtower-Rule", alert.rule_name.as_str()),
+                // JetStream drops messages it has already stored with this ID
+                ("Nats-Msg-Id", alert.id.as_str()),
+            ],
+            &serde_json::to_vec(alert)?,
+        );
+
+        self.exchange(&command)
+            .await
+            .map_err(|e| NotifierError::Generic(format!("NATS publish failed: {}", e)))?;
+
+        info!("Alert published to NATS subject {}", subject);
+        Ok(None)
+    }
+
+    async fn test(&self) -> NotifierResult<()> {
+        self.exchange(&[])
+            .await
+            .map_err(|e| NotifierError::Generic(format!("NATS test failed: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchtower_engine::AlertSeverity;
+
+    #[test]
+    fn test_stream_destinations_per_severity() {
+        let config = KafkaConfig {
+            brokers: vec!["kafka-1.internal:9092".to_string()],
+            topic: "watchtower.alerts".to_string(),
+            severity_topics: HashMap::from([(
+                "critical".to_string(),
+                "watchtower.alerts.critical".to_string(),
+            )]),
+            partition: 0,
+        };
+        assert!(config.validate().is_ok());
+
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transaction detected".to_string(),
+            severity: AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Test Program".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.8,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let kafka = KafkaChannel::new(config.clone());
+        assert_eq!(kafka.topic_for(&alert), "watchtower.alerts.critical");
+        alert.severity = AlertSeverity::Low;
+        assert_eq!(kafka.topic_for(&alert), "watchtower.alerts");
+
+        let invalid = KafkaConfig {
+            severity_topics: HashMap::from([("urgent".to_string(), "alerts".to_string())]),
+            ..config
+        };
+        assert!(invalid.validate().is_err());
+
+        let mut nats = NatsConfig {
+            url: "nats://nats.internal:4222".to_string(),
+            subject: "watchtower.alerts".to_string(),
+            severity_subjects: HashMap::from([(
+                "low".to_string(),
+                "watchtower.alerts.low".to_string(),
+            )]),
+            token: None,
+        };
+        assert!(nats.validate().is_ok());
+        assert_eq!(
+            NatsChannel::new(nats.clone()).subject_for(&alert),
+            "watchtower.alerts.low"
+        );
+
+        nats.url = "http://nats.internal:4222".to_string();
+        assert!(nats.validate().is_err());
+
+        assert_eq!(
+            nats_address("tls://nats.internal"),
+            Some((true, "nats.internal:4222".to_string()))
+        );
+
+        let command = hpub("alerts", &[("Nats-Msg-Id", "alert-1")], b"{}");
+        assert_eq!(
+            command,
+            b"HPUB alerts 34 36\r\nNATS/1.0\r\nNats-Msg-Id: alert-1\r\n\r\n{}\r\n".to_vec()
+        );
+    }
+}
@@ -1,47 +1,232 @@
 //! Template engine for rendering notification messages.
 
-use crate::{NotifierError, NotifierResult};
+use crate::{explorer::ExplorerConfig, locale::Locale, NotifierError, NotifierResult};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
 use tera::{Context, Tera};
 use watchtower_engine::Alert;
 
+/// Built-in templates as (name, file name, source). A file with the same
+/// name in the templates directory overrides the built-in, one in
+/// `rules/<rule>/` overrides it for alerts of that rule.
+const BUILTIN_TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "email_default",
+        "email_default.html",
+        include_str!("../templates/email_default.html"),
+    ),
+    (
+        "email_batch",
+        "email_batch.html",
+        include_str!("../templates/email_batch.html"),
+    ),
+    (
+        "email_default_text",
+        "email_default.txt",
+        include_str!("../templates/email_default.txt"),
+    ),
+    (
+        "telegram_default",
+        "telegram_default.md",
+        include_str!("../templates/telegram_default.md"),
+    ),
+    (
+        "telegram_html",
+        "telegram_default.html",
+        include_str!("../templates/telegram_default.html"),
+    ),
+    (
+        "slack_default",
+        "slack_default.txt",
+        include_str!("../templates/slack_default.txt"),
+    ),
+    (
+        "discord_default",
+        "discord_default.txt",
+        include_str!("../templates/discord_default.txt"),
+    ),
+];
+
+/// Subdirectory of the templates directory holding per-rule templates.
+const RULE_TEMPLATES_DIR: &str = "rules";
+
+/// User template configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplatesConfig {
+    /// Directory whose files (e.g. `slack_default.txt`) override the built-in templates
+    pub directory: Option<PathBuf>,
+
+    /// Seconds between checks of the directory for changed files
+    #[serde(default = "default_reload_interval_seconds")]
+    pub reload_interval_seconds: u64,
+
+    /// Locale of messages (`en`, `de`, `es` or `fr`)
+    #[serde(default = "default_locale")]
+    pub locale: String,
+
+    /// Locales overriding `locale` per channel
+    #[serde(default)]
+    pub locales: HashMap<String, String>,
+
+    /// Explorer linked to from the built-in templates
+    #[serde(default)]
+    pub explorer: ExplorerConfig,
+}
+
+impl TemplatesConfig {
+    /// Validate the configured locales.
+    pub fn validate(&self) -> NotifierResult<()> {
+        for code in std::iter::once(&self.locale).chain(self.locales.values()) {
+            if Locale::parse(code).is_none() {
+                return Err(NotifierError::Configuration(format!(
+                    "Unsupported template locale: {}",
+                    code
+                )));
+            }
+        }
+        self.explorer.validate()
+    }
+
+    /// Locale of a channel's messages.
+    pub fn locale_for(&self, channel: &str) -> Locale {
+        self.locales
+            .get(channel)
+            .and_then(|code| Locale::parse(code))
+            .or_else(|| Locale::parse(&self.locale))
+            .unwrap_or_default()
+    }
+}
+
 /// Template engine for rendering notification messages.
+///
+/// Clones share their templates, so a reload is seen by every channel.
+#[derive(Clone)]
 pub struct TemplateEngine {
     /// Tera template engine
-    tera: Tera,
+    tera: Arc<RwLock<Tera>>,
+
+    /// Directory with template overrides
+    directory: Option<PathBuf>,
+
+    /// Modification times and sizes of the template files when they were last loaded
+    loaded: Arc<Mutex<Vec<(String, SystemTime, u64)>>>,
+
+    /// Locale of labels, dates and numbers
+    locale: Locale,
+
+    /// Explorer the `links` of alerts point to
+    explorer: ExplorerConfig,
 }
 
 impl TemplateEngine {
-    /// Create a new template engine.
+    /// Create a new template engine with the built-in templates.
     pub fn new() -> Self {
-        let mut tera = Tera::default();
-
-        // Add built-in templates
-        tera.add_raw_templates(vec![
-            (
-                "email_default",
-                include_str!("../templates/email_default.html"),
-            ),
-            ("email_batch", include_str!("../templates/email_batch.html")),
-            (
-                "telegram_default",
-                include_str!("../templates/telegram_default.md"),
-            ),
-            (
-                "slack_default",
-                include_str!("../templates/slack_default.txt"),
-            ),
-            (
-                "discord_default",
-                include_str!("../templates/discord_default.txt"),
-            ),
-        ])
-        .unwrap_or_else(|e| {
+        let tera = load_templates(None).unwrap_or_else(|e| {
             tracing::warn!("Failed to load built-in templates: {}", e);
+            Tera::default()
         });
 
-        Self { tera }
+        Self {
+            tera: Arc::new(RwLock::new(tera)),
+            directory: None,
+            loaded: Arc::new(Mutex::new(Vec::new())),
+            locale: Locale::default(),
+            explorer: ExplorerConfig::default(),
+        }
+    }
+
+    /// Create a template engine whose built-in templates are overridden by
+    /// the files in a directory.
+    pub fn with_directory(directory: impl Into<PathBuf>) -> NotifierResult<Self> {
+        let directory = directory.into();
+        let stamps = template_stamps(&directory);
+        let tera = load_templates(Some(&directory))?;
+
+        Ok(Self {
+            tera: Arc::new(RwLock::new(tera)),
+            directory: Some(directory),
+            loaded: Arc::new(Mutex::new(stamps)),
+            locale: Locale::default(),
+            explorer: ExplorerConfig::default(),
+        })
+    }
+
+    /// Link alerts to another explorer.
+    pub fn with_explorer(mut self, explorer: ExplorerConfig) -> Self {
+        self.explorer = explorer;
+        self
+    }
+
+    /// Explorer links of an alert.
+    pub fn explorer_links(&self, alert: &Alert) -> Value {
+        self.explorer.links(alert)
+    }
+
+    /// Engine sharing these templates that renders in another locale.
+    pub fn with_locale(&self, locale: Locale) -> Self {
+        Self {
+            locale,
+            ..self.clone()
+        }
+    }
+
+    /// Reload the templates if a template file was added, changed or
+    /// removed. Broken templates are logged and the previous ones kept.
+    ///
+    /// Returns whether the templates were reloaded.
+    pub fn reload_if_changed(&self) -> bool {
+        let Some(directory) = &self.directory else {
+            return false;
+        };
+
+        let stamps = template_stamps(directory);
+        let mut loaded = self.loaded.lock().unwrap();
+        if *loaded == stamps {
+            return false;
+        }
+        // Remember the attempt so a broken file is not reparsed until it changes
+        *loaded = stamps;
+
+        match load_templates(Some(directory)) {
+            Ok(tera) => {
+                *self.tera.write().unwrap() = tera;
+                tracing::info!("Reloaded templates from {}", directory.display());
+                true
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to reload templates from {}, keeping the previous ones: {}",
+                    directory.display(),
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// Render a named template.
+    fn render_named(&self, name: &str, context: &Context) -> tera::Result<String> {
+        self.tera.read().unwrap().render(name, context)
+    }
+
+    /// Render the template of an alert's rule if it defines one, otherwise
+    /// the named channel default.
+    fn render_for_rule(&self, rule: &str, name: &str, context: &Context) -> tera::Result<String> {
+        let tera = self.tera.read().unwrap();
+        let rule_template = format!("{}/{}", rule, name);
+
+        if tera
+            .get_template_names()
+            .any(|template| template == rule_template)
+        {
+            tera.render(&rule_template, context)
+        } else {
+            tera.render(name, context)
+        }
     }
 
     /// Render a template with the given data.
@@ -64,7 +249,7 @@ impl TemplateEngine {
     pub fn render_default_email_template(&self, alert: &Alert) -> NotifierResult<String> {
         let context = self.create_alert_context(alert)?;
 
-        match self.tera.render("email_default", &context) {
+        match self.render_for_rule(&alert.rule_name, "email_default", &context) {
             Ok(rendered) => Ok(rendered),
             Err(_) => {
                 // Fallback to simple HTML template
@@ -75,12 +260,14 @@ impl TemplateEngine {
 
     /// Render batch email template for multiple alerts.
     pub fn render_batch_email_template(&self, alerts: &[Alert]) -> NotifierResult<String> {
+        let links: Vec<Value> = alerts.iter().map(|a| self.explorer.links(a)).collect();
         let mut context = Context::new();
         context.insert("alerts", alerts);
+        context.insert("links", &links);
         context.insert("alert_count", &alerts.len());
         context.insert("timestamp", &chrono::Utc::now().to_rfc3339());
 
-        match self.tera.render("email_batch", &context) {
+        match self.render_named("email_batch", &context) {
             Ok(rendered) => Ok(rendered),
             Err(_) => {
                 // Fallback to simple HTML template
@@ -89,15 +276,77 @@ impl TemplateEngine {
         }
     }
 
+    /// Render the plain-text part of the default email for an alert.
+    pub fn render_default_email_text_template(&self, alert: &Alert) -> NotifierResult<String> {
+        let context = self.create_alert_context(alert)?;
+
+        match self.render_for_rule(&alert.rule_name, "email_default_text", &context) {
+            Ok(rendered) => Ok(rendered),
+            Err(_) => {
+                // Fallback to the text of the HTML template
+                Ok(html_to_text(&self.render_fallback_email_template(alert)))
+            }
+        }
+    }
+
+    /// Render the plain-text part of a batch email.
+    pub fn render_batch_email_text_template(&self, alerts: &[Alert]) -> String {
+        let mut text = format!("Solana Watchtower: {} new alert(s)\n", alerts.len());
+
+        for alert in alerts {
+            text.push_str(&format!(
+                "\n[{}] {} - {}\n{}\nTime: {}\nAlert ID: {}\n",
+                alert.severity.as_str().to_uppercase(),
+                alert.rule_name,
+                alert.program_name,
+                alert.message,
+                alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                alert.id
+            ));
+
+            let links = self.explorer.links(alert);
+            if let Some(url) = links
+                .get("transaction")
+                .or(links.get("program"))
+                .and_then(Value::as_str)
+            {
+                text.push_str(&format!("Explorer: {}\n", url));
+            }
+        }
+
+        text
+    }
+
     /// Render default Telegram template for an alert.
     pub fn render_default_telegram_template(&self, alert: &Alert) -> NotifierResult<String> {
+        self.render_telegram_template(alert, "Markdown")
+    }
+
+    /// Render the Telegram template for a parse mode (`Markdown`,
+    /// `MarkdownV2`, `HTML` or empty), with the alert's values escaped for it.
+    pub fn render_telegram_template(
+        &self,
+        alert: &Alert,
+        parse_mode: &str,
+    ) -> NotifierResult<String> {
         let context = self.create_alert_context(alert)?;
+        let mut context =
+            Context::from_value(escape_telegram_value(context.into_json(), parse_mode))?;
+        context.insert(
+            "links",
+            &escape_telegram_links(self.explorer.links(alert), parse_mode),
+        );
+        let name = if parse_mode == "HTML" {
+            "telegram_html"
+        } else {
+            "telegram_default"
+        };
 
-        match self.tera.render("telegram_default", &context) {
+        match self.render_for_rule(&alert.rule_name, name, &context) {
             Ok(rendered) => Ok(rendered),
             Err(_) => {
-                // Fallback to simple Markdown template
-                Ok(self.render_fallback_telegram_template(alert))
+                // Fallback to simple template
+                Ok(self.render_fallback_telegram_template(alert, parse_mode))
             }
         }
     }
@@ -106,7 +355,7 @@ impl TemplateEngine {
     pub fn render_default_slack_template(&self, alert: &Alert) -> NotifierResult<String> {
         let context = self.create_alert_context(alert)?;
 
-        match self.tera.render("slack_default", &context) {
+        match self.render_for_rule(&alert.rule_name, "slack_default", &context) {
             Ok(rendered) => Ok(rendered),
             Err(_) => {
                 // Fallback to simple text template
@@ -119,7 +368,7 @@ impl TemplateEngine {
     pub fn render_default_discord_template(&self, alert: &Alert) -> NotifierResult<String> {
         let context = self.create_alert_context(alert)?;
 
-        match self.tera.render("discord_default", &context) {
+        match self.render_for_rule(&alert.rule_name, "discord_default", &context) {
             Ok(rendered) => Ok(rendered),
             Err(_) => {
                 // Fallback to simple text template
@@ -142,12 +391,22 @@ impl TemplateEngine {
         context.insert("program_name", &alert.program_name);
         context.insert("confidence", &(alert.confidence * 100.0));
         context.insert("timestamp", &alert.timestamp.to_rfc3339());
+
+        // Localized labels and values
+        context.insert("locale", self.locale.code());
+        context.insert("t", &self.locale.labels());
+        context.insert("severity_label", self.locale.severity(&alert.severity));
+        context.insert(
+            "confidence_human",
+            &self.locale.format_number(alert.confidence * 100.0, 1),
+        );
         context.insert(
             "timestamp_human",
-            &alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            &self.locale.format_datetime(&alert.timestamp),
         );
         context.insert("suggested_actions", &alert.suggested_actions);
         context.insert("metadata", &alert.metadata);
+        context.insert("links", &self.explorer.links(alert));
 
         // Add severity-specific styling
         let severity_color = match alert.severity {
@@ -254,6 +513,22 @@ impl TemplateEngine {
     fn render_fallback_batch_email_template(&self, alerts: &[Alert]) -> String {
         let alerts_html = alerts.iter()
             .map(|alert| {
+                let links = self.explorer.links(alert);
+                let links_html = [
+                    ("program", "Program"),
+                    ("transaction", "Transaction"),
+                    ("account", "Account"),
+                ]
+                .iter()
+                .filter_map(|(kind, label)| {
+                    links
+                        .get(kind)
+                        .and_then(Value::as_str)
+                        .map(|url| format!(r#"<a href="{}">{}</a>"#, url, label))
+                })
+                .collect::<Vec<_>>()
+                .join(" · ");
+
                 format!(
                     r#"
                     <div style="border: 1px solid #ddd; border-radius: 4px; padding: 15px; margin-bottom: 15px;">
@@ -261,6 +536,7 @@ impl TemplateEngine {
                         <p><strong>Program:</strong> {}</p>
                         <p><strong>Message:</strong> {}</p>
                         <p><strong>Time:</strong> {}</p>
+                        <p>{}</p>
                     </div>
                     "#,
                     alert.severity.color(),
@@ -268,7 +544,8 @@ impl TemplateEngine {
                     alert.rule_name,
                     alert.program_name,
                     alert.message,
-                    alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+                    alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                    links_html
                 )
             })
             .collect::<Vec<_>>()
@@ -307,7 +584,7 @@ impl TemplateEngine {
     }
 
     /// Fallback Telegram template.
-    fn render_fallback_telegram_template(&self, alert: &Alert) -> String {
+    fn render_fallback_telegram_template(&self, alert: &Alert, parse_mode: &str) -> String {
         let emoji = match alert.severity {
             watchtower_engine::AlertSeverity::Critical => "🔴",
             watchtower_engine::AlertSeverity::High => "🟠",
@@ -315,29 +592,35 @@ impl TemplateEngine {
             watchtower_engine::AlertSeverity::Low => "🟢",
             watchtower_engine::AlertSeverity::Info => "🔵",
         };
+        let bold = |label: &str| match parse_mode {
+            "HTML" => format!("<b>{}</b>", label),
+            "" => label.to_string(),
+            _ => format!("*{}*", label),
+        };
+        let escape = |value: &str| escape_telegram(value, parse_mode);
 
         let mut message = format!(
-            r#"{} *Solana Watchtower Alert*
-
-*Severity:* {}
-*Rule:* `{}`
-*Program:* `{}`
-*Message:* {}
-*Confidence:* {:.1}%
-*Time:* {}"#,
+            "{} {}\n\n{} {}\n{} {}\n{} {}\n{} {}\n{} {}%\n{} {}",
             emoji,
+            bold("Solana Watchtower Alert"),
+            bold("Severity:"),
             alert.severity.as_str().to_uppercase(),
-            alert.rule_name,
-            alert.program_name,
-            alert.message,
-            alert.confidence * 100.0,
-            alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+            bold("Rule:"),
+            escape(&alert.rule_name),
+            bold("Program:"),
+            escape(&alert.program_name),
+            bold("Message:"),
+            escape(&alert.message),
+            bold("Confidence:"),
+            escape(&format!("{:.1}", alert.confidence * 100.0)),
+            bold("Time:"),
+            escape(&alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string())
         );
 
         if !alert.suggested_actions.is_empty() {
-            message.push_str("\n\n*Suggested Actions:*");
+            message.push_str(&format!("\n\n{}", bold("Suggested Actions:")));
             for action in &alert.suggested_actions {
-                message.push_str(&format!("\n• {}", action));
+                message.push_str(&format!("\n• {}", escape(action)));
             }
         }
 
@@ -385,3 +668,564 @@ impl Default for TemplateEngine {
         Self::new()
     }
 }
+
+impl Default for TemplatesConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            reload_interval_seconds: default_reload_interval_seconds(),
+            locale: default_locale(),
+            locales: HashMap::new(),
+            explorer: ExplorerConfig::default(),
+        }
+    }
+}
+
+/// Built-in templates, overridden by the files present in `directory`.
+fn load_templates(directory: Option<&Path>) -> NotifierResult<Tera> {
+    let mut templates: Vec<(String, String)> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _, builtin)| (name.to_string(), builtin.to_string()))
+        .collect();
+
+    for (name, path) in directory.map(template_files).unwrap_or_default() {
+        let source = std::fs::read_to_string(&path).map_err(|e| {
+            NotifierError::Configuration(format!(
+                "Failed to read template {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        match templates.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, existing)) => *existing = source,
+            None => templates.push((name, source)),
+        }
+    }
+
+    let mut tera = Tera::default();
+    tera.add_raw_templates(templates)?;
+    Ok(tera)
+}
+
+/// Template files in a directory with the names they are registered under:
+/// overrides of the built-ins, then `rules/<rule>/` overrides named
+/// `<rule>/<built-in name>`.
+fn template_files(directory: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, file_name, _)| (name.to_string(), directory.join(file_name)))
+        .filter(|(_, path)| path.is_file())
+        .collect();
+
+    let mut rule_dirs: Vec<PathBuf> = std::fs::read_dir(directory.join(RULE_TEMPLATES_DIR))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    rule_dirs.sort();
+
+    for rule_dir in rule_dirs {
+        let Some(rule) = rule_dir.file_name().and_then(|rule| rule.to_str()) else {
+            continue;
+        };
+        for (name, file_name, _) in BUILTIN_TEMPLATES {
+            let path = rule_dir.join(file_name);
+            if path.is_file() {
+                files.push((format!("{}/{}", rule, name), path));
+            }
+        }
+    }
+
+    files
+}
+
+/// Modification times and sizes of the template files in a directory.
+fn template_stamps(directory: &Path) -> Vec<(String, SystemTime, u64)> {
+    template_files(directory)
+        .into_iter()
+        .filter_map(|(name, path)| {
+            let metadata = std::fs::metadata(path).ok()?;
+            Some((name, metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+/// Escape text for a Telegram parse mode so it is shown literally.
+pub fn escape_telegram(text: &str, parse_mode: &str) -> String {
+    let special: &[char] = match parse_mode {
+        "Markdown" => &['_', '*', '`', '['],
+        "MarkdownV2" => &[
+            '\\', '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}',
+            '.', '!',
+        ],
+        "HTML" => {
+            return text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+        }
+        _ => return text.to_string(),
+    };
+
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape explorer links for a Telegram parse mode. Link targets follow
+/// their own rules: MarkdownV2 only escapes `)` and `\` in them.
+pub fn escape_telegram_links(links: Value, parse_mode: &str) -> Value {
+    match links {
+        Value::String(url) => Value::String(match parse_mode {
+            "MarkdownV2" => url.replace('\\', "\\\\").replace(')', "\\)"),
+            "HTML" => url.replace('&', "&amp;").replace('"', "&quot;"),
+            _ => url,
+        }),
+        Value::Object(links) => Value::Object(
+            links
+                .into_iter()
+                .map(|(kind, url)| (kind, escape_telegram_links(url, parse_mode)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Escape every string in template data for a Telegram parse mode.
+pub fn escape_telegram_value(value: Value, parse_mode: &str) -> Value {
+    match value {
+        Value::String(text) => Value::String(escape_telegram(&text, parse_mode)),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| escape_telegram_value(item, parse_mode))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, field)| (key, escape_telegram_value(field, parse_mode)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Plain-text version of an HTML document for the text part of emails:
+/// tags, styles and scripts are dropped, block elements become line breaks
+/// and common entities are decoded.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim().to_ascii_lowercase();
+        rest = &rest[start + end + 1..];
+
+        // Skip the contents of non-visible elements
+        for hidden in ["style", "script", "head"] {
+            if tag == hidden || tag.starts_with(&format!("{} ", hidden)) {
+                let closing = format!("</{}>", hidden);
+                rest = rest
+                    .to_ascii_lowercase()
+                    .find(&closing)
+                    .map_or("", |i| &rest[i + closing.len()..]);
+            }
+        }
+
+        let name = tag.trim_start_matches('/');
+        let name = name
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        if matches!(
+            name,
+            "br" | "p" | "div" | "tr" | "li" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        ) {
+            text.push('\n');
+        }
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    // Trim indentation and collapse runs of blank lines
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() && lines.last().map_or(true, |last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+// Default value functions
+fn default_reload_interval_seconds() -> u64 {
+    5
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"<!DOCTYPE html>
+            <html>
+            <head><title>Alert</title><style>body { color: red; }</style></head>
+            <body>
+                <h1>HIGH Alert</h1>
+                <p><strong>Program:</strong> Orca &amp; Raydium</p>
+
+
+                <div>Line<br/>break</div>
+            </body>
+            </html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "HIGH Alert\n\nProgram: Orca & Raydium\n\nLine\nbreak"
+        );
+    }
+
+    #[test]
+    fn test_builtin_templates_load() {
+        assert!(load_templates(None).is_ok());
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "tvl_drop".to_string(),
+            message: "TVL decreased by 40%".to_string(),
+            severity: watchtower_engine::AlertSeverity::Critical,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.4,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let batch = TemplateEngine::new()
+            .render_batch_email_template(&[alert.clone(), alert])
+            .unwrap();
+        assert!(batch.contains("Alert Summary Report"));
+        assert!(batch.contains(
+            r#"<div class="summary-number">2</div>
+                <div class="summary-label">Critical</div>"#
+        ));
+        assert!(batch.contains(">40%<"));
+    }
+
+    #[test]
+    fn test_directory_overrides_and_reload() {
+        let directory =
+            std::env::temp_dir().join(format!("watchtower-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let template = directory.join("slack_default.txt");
+        std::fs::write(&template, "Custom {{ rule_name }}").unwrap();
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "tvl_drop".to_string(),
+            message: "TVL decreased by 40%".to_string(),
+            severity: watchtower_engine::AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.4,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let engine = TemplateEngine::with_directory(&directory).unwrap();
+        let shared = engine.clone();
+        assert_eq!(
+            engine.render_default_slack_template(&alert).unwrap(),
+            "Custom tvl_drop"
+        );
+        // Templates without an override stay built in
+        assert!(engine
+            .render_default_discord_template(&alert)
+            .unwrap()
+            .contains("TVL decreased by 40%"));
+        assert!(!engine.reload_if_changed());
+
+        std::fs::write(&template, "Changed {{ program_name }}!").unwrap();
+        assert!(engine.reload_if_changed());
+        assert_eq!(
+            shared.render_default_slack_template(&alert).unwrap(),
+            "Changed Orca!"
+        );
+
+        // Broken templates keep the previous ones
+        std::fs::write(&template, "Broken {{ rule_name").unwrap();
+        assert!(!engine.reload_if_changed());
+        assert_eq!(
+            shared.render_default_slack_template(&alert).unwrap(),
+            "Changed Orca!"
+        );
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_rule_templates_fall_back_to_channel_default() {
+        let directory =
+            std::env::temp_dir().join(format!("watchtower-rule-templates-{}", std::process::id()));
+        let rule_directory = directory.join("rules").join("liquidation");
+        std::fs::create_dir_all(&rule_directory).unwrap();
+        std::fs::write(
+            rule_directory.join("slack_default.txt"),
+            "Liquidation of {{ metadata.account }} at health factor {{ metadata.health_factor }}",
+        )
+        .unwrap();
+
+        let mut alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "liquidation".to_string(),
+            message: "Position liquidated".to_string(),
+            severity: watchtower_engine::AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Solend".to_string(),
+            event_id: None,
+            metadata: HashMap::from([
+                ("account".to_string(), serde_json::json!("7xKXtg")),
+                ("health_factor".to_string(), serde_json::json!(0.98)),
+            ]),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let engine = TemplateEngine::with_directory(&directory).unwrap();
+        assert_eq!(
+            engine.render_default_slack_template(&alert).unwrap(),
+            "Liquidation of 7xKXtg at health factor 0.98"
+        );
+        // Channels without a rule template use their default
+        assert!(engine
+            .render_default_discord_template(&alert)
+            .unwrap()
+            .contains("Position liquidated"));
+
+        // Other rules use the channel default
+        alert.rule_name = "tvl_drop".to_string();
+        assert!(engine
+            .render_default_slack_template(&alert)
+            .unwrap()
+            .contains("Position liquidated"));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_localized_templates() {
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "tvl_drop".to_string(),
+            message: "TVL decreased by 40%".to_string(),
+            severity: watchtower_engine::AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.4,
+            suggested_actions: vec!["Check the pool".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let engine = TemplateEngine::new();
+        let english = engine.render_default_slack_template(&alert).unwrap();
+        assert!(english.contains("*Severity:* HIGH"));
+        assert!(english.contains("*Confidence:* 40.0%"));
+
+        let german = engine
+            .with_locale(Locale::De)
+            .render_default_slack_template(&alert)
+            .unwrap();
+        assert!(german.contains("*Schweregrad:* HOCH"));
+        assert!(german.contains("*Konfidenz:* 40,0%"));
+        assert!(german.contains("*Empfohlene Maßnahmen:*"));
+        assert!(german.contains(&alert.timestamp.format("%d.%m.%Y").to_string()));
+
+        let config = TemplatesConfig {
+            locale: "de".to_string(),
+            locales: HashMap::from([("telegram".to_string(), "es-ES".to_string())]),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.locale_for("slack"), Locale::De);
+        assert_eq!(config.locale_for("telegram"), Locale::Es);
+
+        let invalid = TemplatesConfig {
+            locale: "klingon".to_string(),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_explorer_links_in_default_templates() {
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Large transaction detected".to_string(),
+            severity: watchtower_engine::AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::from([(
+                watchtower_engine::SIGNATURE_KEY.to_string(),
+                serde_json::json!("5sig"),
+            )]),
+            confidence: 0.9,
+            suggested_actions: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let engine = TemplateEngine::new().with_explorer(ExplorerConfig {
+            provider: crate::explorer::ExplorerProvider::Xray,
+            cluster: "devnet".to_string(),
+            ..Default::default()
+        });
+        let transaction = "https://xray.helius.xyz/tx/5sig?network=devnet";
+
+        assert!(engine
+            .render_default_slack_template(&alert)
+            .unwrap()
+            .contains(&format!("<{}|Transaction>", transaction)));
+        assert!(engine
+            .render_default_discord_template(&alert)
+            .unwrap()
+            .contains(&format!("[Transaction]({})", transaction)));
+        assert!(engine
+            .render_telegram_template(&alert, "Markdown")
+            .unwrap()
+            .contains(&format!("[Transaction]({})", transaction)));
+        assert!(engine
+            .render_telegram_template(&alert, "HTML")
+            .unwrap()
+            .contains(&format!("<a href=\"{}\">Transaction</a>", transaction)));
+        assert!(engine
+            .render_default_email_text_template(&alert)
+            .unwrap()
+            .contains(&format!("Transaction: {}", transaction)));
+        assert!(engine
+            .render_default_email_template(&alert)
+            .unwrap()
+            .contains(&format!("<a href=\"{}\">Transaction</a>", transaction)));
+        assert!(engine
+            .render_batch_email_template(std::slice::from_ref(&alert))
+            .unwrap()
+            .contains(&format!("<a href=\"{}\">Transaction</a>", transaction)));
+        assert!(engine
+            .render_batch_email_text_template(std::slice::from_ref(&alert))
+            .contains(&format!("Explorer: {}", transaction)));
+
+        // Without an explorer the templates have no links
+        let engine = TemplateEngine::new().with_explorer(ExplorerConfig {
+            provider: crate::explorer::ExplorerProvider::None,
+            ..Default::default()
+        });
+        assert!(!engine
+            .render_default_slack_template(&alert)
+            .unwrap()
+            .contains("🔗"));
+    }
+
+    #[test]
+    fn test_telegram_escaping() {
+        assert_eq!(
+            escape_telegram("price_feed *stale* [x]", "Markdown"),
+            "price\\_feed \\*stale\\* \\[x]"
+        );
+        assert_eq!(
+            escape_telegram("-12.5% (oracle)!", "MarkdownV2"),
+            "\\-12\\.5% \\(oracle\\)\\!"
+        );
+        assert_eq!(
+            escape_telegram("<b>Orca & Raydium</b>", "HTML"),
+            "&lt;b&gt;Orca &amp; Raydium&lt;/b&gt;"
+        );
+        assert_eq!(escape_telegram("a_b", ""), "a_b");
+
+        let alert = Alert {
+            id: "alert-1".to_string(),
+            rule_name: "large_transaction".to_string(),
+            message: "Withdrew 1.5M from pool_a <fast>".to_string(),
+            severity: watchtower_engine::AlertSeverity::High,
+            program_id: solana_sdk::pubkey::Pubkey::new_unique(),
+            program_name: "Orca".to_string(),
+            event_id: None,
+            metadata: HashMap::new(),
+            confidence: 0.9,
+            suggested_actions: vec!["Pause deposits (if possible)".to_string()],
+            timestamp: chrono::Utc::now(),
+            acknowledged: false,
+            resolved: false,
+            tags: Vec::new(),
+        };
+
+        let engine = TemplateEngine::new();
+        let markdown = engine.render_telegram_template(&alert, "Markdown").unwrap();
+        assert!(markdown.contains("*Rule:* large\\_transaction"));
+        assert!(markdown.contains("pool\\_a"));
+
+        let v2 = engine
+            .render_telegram_template(&alert, "MarkdownV2")
+            .unwrap();
+        assert!(v2.contains("Withdrew 1\\.5M from pool\\_a <fast\\>"));
+        assert!(v2.contains("• Pause deposits \\(if possible\\)"));
+        assert!(v2.contains("*Confidence:* 90\\.0%"));
+
+        let html = engine.render_telegram_template(&alert, "HTML").unwrap();
+        assert!(html.contains("<b>Rule:</b> <code>large_transaction</code>"));
+        assert!(html.contains("pool_a &lt;fast&gt;"));
+    }
+}
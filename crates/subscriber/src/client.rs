@@ -1,19 +1,33 @@
 //! WebSocket client for real-time Solana program event monitoring.
 
 use crate::{
-    config::SubscriberConfig,
-    events::{EventData, EventType, ProgramEvent},
+    config::{AccountConfig, ProgramConfig, SubscriberConfig},
+    events::{parse_upgrade_log, EventData, EventType, ProgramEvent, ACCOUNT_DATA_METADATA_KEY},
     filters::{EventFilter, SubscriptionManager},
-    SubscriberResult,
+    SubscriberError, SubscriberResult,
 };
+use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
 use serde_json::{json, Value};
-use solana_sdk::pubkey::Pubkey;
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 
+/// Request ID of the first `accountSubscribe`; the account index is added to it
+/// so confirmations can be matched back to the configured account.
+const ACCOUNT_REQUEST_ID_BASE: u64 = 1000;
+
+/// Request ID of the first program or logs subscription; IDs from it up are
+/// matched back to the subscribed program so it can be unsubscribed later.
+const PROGRAM_REQUEST_ID_BASE: u64 = 1_000_000;
+
 /// WebSocket client for subscribing to Solana program events.
 pub struct SolanaWebSocketClient {
     /// Client configuration
@@ -32,6 +46,342 @@ pub struct SolanaWebSocketClient {
 
     /// Connection status
     is_connected: Arc<tokio::sync::RwLock<bool>>,
+
+    /// Handle for changing the monitored programs at runtime
+    handle: SubscriptionHandle,
+
+    /// Receiver of runtime subscription changes, taken by the connection task
+    commands: Option<mpsc::UnboundedReceiver<SubscriptionCommand>>,
+}
+
+/// Cloneable handle listing the monitored programs and subscribing to or
+/// unsubscribing from programs while the client runs.
+#[derive(Debug, Clone)]
+pub struct SubscriptionHandle {
+    /// Monitored programs, including those added at runtime
+    programs: Arc<RwLock<Vec<ProgramConfig>>>,
+
+    /// Event counts per program since the client started
+    activity: Arc<RwLock<HashMap<Pubkey, ProgramActivity>>>,
+
+    /// Changes applied to the live connection
+    commands: mpsc::UnboundedSender<SubscriptionCommand>,
+
+    /// State of the WebSocket connection
+    connection: Arc<RwLock<ConnectionStatus>>,
+
+    /// Sender of received events, for the number still queued
+    events: broadcast::Sender<ProgramEvent>,
+
+    /// HTTP RPC endpoint transactions are fetched from
+    rpc_url: String,
+
+    /// Commitment of the subscriptions
+    commitment: String,
+
+    /// Timeout of RPC requests
+    rpc_timeout: Duration,
+}
+
+/// State of the subscriber's WebSocket connection to the RPC node.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnectionStatus {
+    /// Whether the connection is open
+    pub connected: bool,
+
+    /// WebSocket URL of the RPC node
+    pub ws_url: String,
+
+    /// When the current connection was opened
+    pub connected_since: Option<DateTime<Utc>>,
+
+    /// When the last message was received from the node
+    pub last_message: Option<DateTime<Utc>>,
+
+    /// Failed connection attempts since the last successful session
+    pub reconnect_attempts: u32,
+
+    /// Error that closed the last connection
+    pub last_error: Option<String>,
+
+    /// Received events not yet taken by their slowest consumer
+    pub queued_events: usize,
+}
+
+/// A monitored program and its activity since the client started.
+#[derive(Debug, Clone)]
+pub struct MonitoredProgram {
+    /// Program configuration
+    pub config: ProgramConfig,
+
+    /// Events received for the program
+    pub events: u64,
+
+    /// Time of the last event received for the program
+    pub last_event: Option<DateTime<Utc>>,
+}
+
+/// Event count and last event time of a program.
+#[derive(Debug, Clone, Copy, Default)]
+struct ProgramActivity {
+    events: u64,
+    last_event: Option<DateTime<Utc>>,
+}
+
+/// Runtime change to the monitored programs, applied by the connection task.
+#[derive(Debug)]
+enum SubscriptionCommand {
+    Subscribe(ProgramConfig),
+    Unsubscribe(Pubkey),
+}
+
+/// Subscriptions of the current connection.
+#[derive(Debug)]
+struct Subscriptions {
+    /// Account index for each confirmed account subscription
+    accounts: HashMap<u64, usize>,
+
+    /// Next program request ID
+    next_request_id: u64,
+
+    /// Program and unsubscribe method of each unconfirmed program request
+    pending: HashMap<u64, (Pubkey, &'static str)>,
+
+    /// Confirmed subscription IDs and their unsubscribe method per program
+    programs: HashMap<Pubkey, Vec<(u64, &'static str)>>,
+
+    /// Unconfirmed requests of programs removed since they were sent
+    cancelled: HashSet<u64>,
+
+    /// Subscriptions confirmed after their program was removed
+    stale: Vec<(u64, &'static str)>,
+}
+
+impl SubscriptionHandle {
+    /// Monitored programs with their activity, in subscription order.
+    pub fn programs(&self) -> Vec<MonitoredProgram> {
+        let activity = self.activity.read().unwrap_or_else(|e| e.into_inner());
+        self.programs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|program| {
+                let activity = activity.get(&program.id).copied().unwrap_or_default();
+                MonitoredProgram {
+                    config: program.clone(),
+                    events: activity.events,
+                    last_event: activity.last_event,
+                }
+            })
+            .collect()
+    }
+
+    /// Start monitoring a program; it is subscribed to immediately when
+    /// connected and on every reconnect.
+    pub fn subscribe_program(&self, program: ProgramConfig) -> SubscriberResult<()> {
+        program.validate()?;
+
+        // Changes are sent while holding the lock so the connection task sees
+        // each one either in the program list or as a command, never both
+        let mut programs = self.programs.write().unwrap_or_else(|e| e.into_inner());
+        if programs.iter().any(|p| p.id == program.id) {
+            return Err(SubscriberError::InvalidConfig(format!(
+                "Program {} is already monitored",
+                program.id
+            )));
+        }
+
+        info!(
+            "Adding monitored program: {} ({})",
+            program.name, program.id
+        );
+        programs.push(program.clone());
+        // Without a running connection the change applies when it starts
+        let _ = self.commands.send(SubscriptionCommand::Subscribe(program));
+        Ok(())
+    }
+
+    /// Stop monitoring a program, returning its configuration.
+    pub fn unsubscribe_program(&self, program_id: &Pubkey) -> SubscriberResult<ProgramConfig> {
+        let mut programs = self.programs.write().unwrap_or_else(|e| e.into_inner());
+        let index = programs
+            .iter()
+            .position(|p| p.id == *program_id)
+            .ok_or_else(|| {
+                SubscriberError::InvalidConfig(format!("Program {} is not monitored", program_id))
+            })?;
+
+        let program = programs.remove(index);
+        info!(
+            "Removing monitored program: {} ({})",
+            program.name, program.id
+        );
+        let _ = self
+            .commands
+            .send(SubscriptionCommand::Unsubscribe(program.id));
+        Ok(program)
+    }
+
+    /// State of the WebSocket connection.
+    pub fn connection_status(&self) -> ConnectionStatus {
+        let mut status = self
+            .connection
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        status.queued_events = self.events.len();
+        status
+    }
+
+    /// Fetch a transaction from the RPC node as JSON, as `getTransaction`
+    /// returns it with the `json` encoding.
+    pub async fn fetch_transaction(&self, signature: &Signature) -> SubscriberResult<Value> {
+        // `getTransaction` does not accept the processed commitment
+        let commitment = match self.commitment.as_str() {
+            "finalized" => CommitmentConfig::finalized(),
+            _ => CommitmentConfig::confirmed(),
+        };
+        let rpc = RpcClient::new_with_timeout_and_commitment(
+            self.rpc_url.clone(),
+            self.rpc_timeout,
+            commitment,
+        );
+
+        let transaction = rpc
+            .get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        Ok(serde_json::to_value(transaction)?)
+    }
+
+    fn update_connection(&self, update: impl FnOnce(&mut ConnectionStatus)) {
+        update(&mut self.connection.write().unwrap_or_else(|e| e.into_inner()));
+    }
+
+    /// Count an event towards its program's activity.
+    fn record_event(&self, event: &ProgramEvent) {
+        let mut activity = self.activity.write().unwrap_or_else(|e| e.into_inner());
+        let entry = activity.entry(event.program_id).or_default();
+        entry.events += 1;
+        entry.last_event = Some(event.timestamp);
+    }
+}
+
+impl Subscriptions {
+    fn new() -> Self {
+        Self {
+            accounts: HashMap::new(),
+            next_request_id: PROGRAM_REQUEST_ID_BASE,
+            pending: HashMap::new(),
+            programs: HashMap::new(),
+            cancelled: HashSet::new(),
+            stale: Vec::new(),
+        }
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        id
+    }
+
+    /// Subscription requests of a program.
+    fn subscribe(&mut self, program: &ProgramConfig, commitment: &str) -> Vec<Value> {
+        let mut requests = Vec::new();
+
+        if program.monitor_accounts || program.monitor_transactions {
+            let id = self.next_id();
+            self.pending.insert(id, (program.id, "programUnsubscribe"));
+            requests.push(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "programSubscribe",
+                "params": [
+                    program.id.to_string(),
+                    {
+                        "commitment": commitment,
+                        "encoding": "jsonParsed"
+                    }
+                ]
+            }));
+        }
+
+        if program.monitor_logs {
+            let id = self.next_id();
+            self.pending.insert(id, (program.id, "logsUnsubscribe"));
+            requests.push(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "logsSubscribe",
+                "params": [
+                    {
+                        "mentions": [program.id.to_string()]
+                    },
+                    {
+                        "commitment": commitment
+                    }
+                ]
+            }));
+        }
+
+        requests
+    }
+
+    /// Unsubscribe requests of a program's confirmed subscriptions; pending
+    /// ones are unsubscribed once confirmed.
+    fn unsubscribe(&mut self, program_id: &Pubkey) -> Vec<Value> {
+        self.cancelled.extend(
+            self.pending
+                .iter()
+                .filter(|(_, (program, _))| program == program_id)
+                .map(|(id, _)| *id),
+        );
+
+        let subscriptions = self.programs.remove(program_id).unwrap_or_default();
+        subscriptions
+            .into_iter()
+            .map(|(subscription, method)| self.unsubscribe_request(subscription, method))
+            .collect()
+    }
+
+    fn unsubscribe_request(&mut self, subscription: u64, method: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id(),
+            "method": method,
+            "params": [subscription]
+        })
+    }
+
+    /// Record the confirmation of a program subscription request.
+    fn confirm(&mut self, request_id: u64, subscription: u64) {
+        let Some((program_id, method)) = self.pending.remove(&request_id) else {
+            return;
+        };
+
+        if self.cancelled.remove(&request_id) {
+            self.stale.push((subscription, method));
+        } else {
+            self.programs
+                .entry(program_id)
+                .or_default()
+                .push((subscription, method));
+        }
+    }
+
+    /// Unsubscribe requests of subscriptions confirmed after their program was removed.
+    fn take_stale(&mut self) -> Vec<Value> {
+        std::mem::take(&mut self.stale)
+            .into_iter()
+            .map(|(subscription, method)| self.unsubscribe_request(subscription, method))
+            .collect()
+    }
 }
 
 /// WebSocket message types from Solana RPC.
@@ -40,10 +390,7 @@ pub struct SolanaWebSocketClient {
 #[allow(dead_code)]
 enum WebSocketMessage {
     #[serde(rename = "accountNotification")]
-    AccountNotification {
-        #[allow(dead_code)]
-        params: AccountNotificationParams,
-    },
+    AccountNotification { params: AccountNotificationParams },
 
     #[serde(rename = "programNotification")]
     ProgramNotification { params: ProgramNotificationParams },
@@ -68,20 +415,14 @@ enum WebSocketMessage {
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
-#[allow(dead_code)]
 struct AccountNotificationParams {
-    #[allow(dead_code)]
     result: AccountNotificationResult,
-    #[allow(dead_code)]
     subscription: u64,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
-#[allow(dead_code)]
 struct AccountNotificationResult {
-    #[allow(dead_code)]
     context: NotificationContext,
-    #[allow(dead_code)]
     value: AccountInfo,
 }
 
@@ -152,7 +493,6 @@ struct AccountInfo {
     #[serde(rename = "rentEpoch")]
     #[allow(dead_code)]
     rent_epoch: u64,
-    #[allow(dead_code)]
     data: Vec<String>,
 }
 
@@ -200,6 +540,20 @@ impl SolanaWebSocketClient {
         );
 
         let (event_sender, _) = broadcast::channel(1000);
+        let (commands, command_receiver) = mpsc::unbounded_channel();
+        let handle = SubscriptionHandle {
+            programs: Arc::new(RwLock::new(config.programs.clone())),
+            activity: Arc::new(RwLock::new(HashMap::new())),
+            commands,
+            connection: Arc::new(RwLock::new(ConnectionStatus {
+                ws_url: config.ws_url.to_string(),
+                ..Default::default()
+            })),
+            events: event_sender.clone(),
+            rpc_url: config.rpc_url.to_string(),
+            commitment: config.filters.commitment.clone(),
+            rpc_timeout: Duration::from_secs(config.timeout_seconds),
+        };
 
         Ok(Self {
             config,
@@ -207,22 +561,47 @@ impl SolanaWebSocketClient {
             subscription_manager: SubscriptionManager::new(),
             event_sender,
             is_connected: Arc::new(tokio::sync::RwLock::new(false)),
+            handle,
+            commands: Some(command_receiver),
         })
     }
 
+    /// Handle for listing and changing the monitored programs at runtime.
+    pub fn handle(&self) -> SubscriptionHandle {
+        self.handle.clone()
+    }
+
     /// Start the WebSocket client and begin monitoring.
     pub async fn start(&mut self) -> SubscriberResult<broadcast::Receiver<ProgramEvent>> {
         info!("Starting Solana WebSocket client");
 
+        let mut commands = self
+            .commands
+            .take()
+            .ok_or_else(|| SubscriberError::Generic("Client already started".to_string()))?;
         let receiver = self.event_sender.subscribe();
 
+        // Count events per program for the monitored program listing
+        let mut activity_receiver = self.event_sender.subscribe();
+        let handle = self.handle.clone();
+        tokio::spawn(async move {
+            loop {
+                match activity_receiver.recv().await {
+                    Ok(event) => handle.record_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
         // Start connection task
-        let config = self.config.clone();
+        let mut config = self.config.clone();
         let sender = self.event_sender.clone();
         let is_connected = self.is_connected.clone();
+        let handle = self.handle.clone();
 
         tokio::spawn(async move {
-            Self::connection_task(config, sender, is_connected).await;
+            Self::connection_task(&mut config, &handle, &mut commands, sender, is_connected).await;
         });
 
         Ok(receiver)
@@ -230,17 +609,39 @@ impl SolanaWebSocketClient {
 
     /// Connection task that handles WebSocket connection and reconnection.
     async fn connection_task(
-        config: SubscriberConfig,
+        config: &mut SubscriberConfig,
+        handle: &SubscriptionHandle,
+        commands: &mut mpsc::UnboundedReceiver<SubscriptionCommand>,
         event_sender: broadcast::Sender<ProgramEvent>,
         is_connected: Arc<tokio::sync::RwLock<bool>>,
     ) {
         let mut reconnect_attempts = 0;
 
         loop {
-            match Self::connect_and_subscribe(&config, &event_sender, &is_connected).await {
+            // Every change made so far is in the program list, so queued
+            // commands are dropped rather than applied twice
+            {
+                let programs = handle.programs.read().unwrap_or_else(|e| e.into_inner());
+                while commands.try_recv().is_ok() {}
+                config.programs = programs.clone();
+            }
+
+            match Self::connect_and_subscribe(
+                config,
+                handle,
+                commands,
+                &event_sender,
+                &is_connected,
+            )
+            .await
+            {
                 Ok(_) => {
                     info!("WebSocket connection closed gracefully");
                     reconnect_attempts = 0;
+                    handle.update_connection(|status| {
+                        status.connected = false;
+                        status.reconnect_attempts = 0;
+                    });
                 }
                 Err(e) => {
                     error!("WebSocket connection error: {}", e);
@@ -248,6 +649,11 @@ impl SolanaWebSocketClient {
                     *is_connected.write().await = false;
 
                     reconnect_attempts += 1;
+                    handle.update_connection(|status| {
+                        status.connected = false;
+                        status.reconnect_attempts = reconnect_attempts;
+                        status.last_error = Some(e.to_string());
+                    });
                     if reconnect_attempts > config.max_reconnect_attempts {
                         error!("Max reconnection attempts reached, stopping client");
                         break;
@@ -268,7 +674,9 @@ impl SolanaWebSocketClient {
 
     /// Connect to WebSocket and handle subscriptions.
     async fn connect_and_subscribe(
-        config: &SubscriberConfig,
+        config: &mut SubscriberConfig,
+        handle: &SubscriptionHandle,
+        commands: &mut mpsc::UnboundedReceiver<SubscriptionCommand>,
         event_sender: &broadcast::Sender<ProgramEvent>,
         is_connected: &Arc<tokio::sync::RwLock<bool>>,
     ) -> SubscriberResult<()> {
@@ -278,72 +686,94 @@ impl SolanaWebSocketClient {
         let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
         *is_connected.write().await = true;
+        handle.update_connection(|status| {
+            status.connected = true;
+            status.connected_since = Some(Utc::now());
+        });
         info!("WebSocket connected successfully");
 
+        let mut subscriptions = Subscriptions::new();
+
         // Subscribe to programs
         for program in &config.programs {
-            if program.monitor_accounts || program.monitor_transactions {
-                let subscription_request = json!({
-                    "jsonrpc": "2.0",
-                    "id": 1,
-                    "method": "programSubscribe",
-                    "params": [
-                        program.id.to_string(),
-                        {
-                            "commitment": config.filters.commitment,
-                            "encoding": "jsonParsed"
-                        }
-                    ]
-                });
+            for request in subscriptions.subscribe(program, &config.filters.commitment) {
+                ws_sender.send(Message::Text(request.to_string())).await?;
+            }
+            info!("Subscribed to program: {} ({})", program.name, program.id);
+        }
 
-                let message = Message::Text(subscription_request.to_string());
-                ws_sender.send(message).await?;
+        // Subscribe to individual accounts
+        for (index, account) in config.accounts.iter().enumerate() {
+            let account_request = json!({
+                "jsonrpc": "2.0",
+                "id": ACCOUNT_REQUEST_ID_BASE + index as u64,
+                "method": "accountSubscribe",
+                "params": [
+                    account.id.to_string(),
+                    {
+                        "commitment": config.filters.commitment,
+                        "encoding": "base64"
+                    }
+                ]
+            });
 
-                info!("Subscribed to program: {} ({})", program.name, program.id);
-            }
+            let message = Message::Text(account_request.to_string());
+            ws_sender.send(message).await?;
 
-            if program.monitor_logs {
-                let logs_request = json!({
-                    "jsonrpc": "2.0",
-                    "id": 2,
-                    "method": "logsSubscribe",
-                    "params": [
-                        {
-                            "mentions": [program.id.to_string()]
-                        },
-                        {
-                            "commitment": config.filters.commitment
-                        }
-                    ]
-                });
+            info!("Subscribed to account: {} ({})", account.name, account.id);
+        }
 
-                let message = Message::Text(logs_request.to_string());
-                ws_sender.send(message).await?;
+        // Handle incoming messages and runtime subscription changes
+        loop {
+            let message = tokio::select! {
+                message = ws_receiver.next() => message,
+                Some(command) = commands.recv() => {
+                    let requests = match command {
+                        SubscriptionCommand::Subscribe(program) => {
+                            let requests =
+                                subscriptions.subscribe(&program, &config.filters.commitment);
+                            info!("Subscribed to program: {} ({})", program.name, program.id);
+                            config.programs.push(program);
+                            requests
+                        }
+                        SubscriptionCommand::Unsubscribe(program_id) => {
+                            config.programs.retain(|p| p.id != program_id);
+                            info!("Unsubscribed from program: {}", program_id);
+                            subscriptions.unsubscribe(&program_id)
+                        }
+                    };
+                    for request in requests {
+                        ws_sender.send(Message::Text(request.to_string())).await?;
+                    }
+                    continue;
+                }
+            };
 
-                info!(
-                    "Subscribed to logs for program: {} ({})",
-                    program.name, program.id
-                );
+            if let Some(Ok(_)) = &message {
+                handle.update_connection(|status| status.last_message = Some(Utc::now()));
             }
-        }
 
-        // Handle incoming messages
-        while let Some(message) = ws_receiver.next().await {
             match message {
-                Ok(Message::Text(text)) => {
-                    if let Err(e) = Self::handle_message(&text, config, event_sender).await {
+                Some(Ok(Message::Text(text))) => {
+                    if let Err(e) =
+                        Self::handle_message(&text, config, event_sender, &mut subscriptions).await
+                    {
                         error!("Error handling message: {}", e);
                     }
+                    for request in subscriptions.take_stale() {
+                        ws_sender.send(Message::Text(request.to_string())).await?;
+                    }
                 }
-                Ok(Message::Close(_)) => {
+                Some(Ok(Message::Close(_))) => {
                     info!("WebSocket connection closed by server");
                     break;
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     error!("WebSocket error: {}", e);
                     break;
                 }
-                _ => {}
+                Some(Ok(_)) => {}
+                None => break,
             }
         }
 
@@ -356,6 +786,7 @@ impl SolanaWebSocketClient {
         text: &str,
         config: &SubscriberConfig,
         event_sender: &broadcast::Sender<ProgramEvent>,
+        subscriptions: &mut Subscriptions,
     ) -> SubscriberResult<()> {
         debug!("Received message: {}", text);
 
@@ -363,8 +794,13 @@ impl SolanaWebSocketClient {
 
         // Handle subscription confirmations
         if let Some(result) = value.get("result") {
-            if result.is_number() {
+            if let Some(subscription) = result.as_u64() {
                 debug!("Subscription confirmed with ID: {}", result);
+                if let Some(index) = Self::account_index(&value, config) {
+                    subscriptions.accounts.insert(subscription, index);
+                } else if let Some(request_id) = value.get("id").and_then(Value::as_u64) {
+                    subscriptions.confirm(request_id, subscription);
+                }
                 return Ok(());
             }
         }
@@ -372,7 +808,13 @@ impl SolanaWebSocketClient {
         // Handle notifications
         if let Some(_method) = value.get("method") {
             if let Ok(ws_message) = serde_json::from_value::<WebSocketMessage>(value) {
-                Self::process_notification(ws_message, config, event_sender).await?;
+                Self::process_notification(
+                    ws_message,
+                    config,
+                    event_sender,
+                    &subscriptions.accounts,
+                )
+                .await?;
             }
         }
 
@@ -384,8 +826,23 @@ impl SolanaWebSocketClient {
         message: WebSocketMessage,
         config: &SubscriberConfig,
         event_sender: &broadcast::Sender<ProgramEvent>,
+        account_subscriptions: &HashMap<u64, usize>,
     ) -> SubscriberResult<()> {
         match message {
+            WebSocketMessage::AccountNotification { params } => {
+                let account = account_subscriptions
+                    .get(&params.subscription)
+                    .and_then(|index| config.accounts.get(*index));
+                if let Some(account) = account {
+                    if let Some(event) = Self::account_event(account, &params.result.value) {
+                        let event = event.with_slot(params.result.context.slot);
+                        if let Err(e) = event_sender.send(event) {
+                            error!("Failed to send account event: {}", e);
+                        }
+                    }
+                }
+            }
+
             WebSocketMessage::ProgramNotification { params } => {
                 if let Ok(account_pubkey) = params.result.value.pubkey.parse::<Pubkey>() {
                     if let Ok(owner_pubkey) = params.result.value.account.owner.parse::<Pubkey>() {
@@ -419,7 +876,9 @@ impl SolanaWebSocketClient {
                 if let Ok(signature) = params.result.value.signature.parse() {
                     for log in &params.result.value.logs {
                         // Parse program ID from logs
-                        if let Some(program_id) = Self::extract_program_id_from_log(log) {
+                        if let Some(program_id) = Self::extract_program_id_from_log(log)
+                            .or_else(|| Self::extract_program_id_from_compute_log(log))
+                        {
                             if let Some(program_config) =
                                 config.programs.iter().find(|p| p.id == program_id)
                             {
@@ -442,6 +901,16 @@ impl SolanaWebSocketClient {
                             }
                         }
                     }
+
+                    for event in Self::upgrade_events(&params.result.value.logs, config) {
+                        let event = event
+                            .with_slot(params.result.context.slot)
+                            .with_signature(Some(signature));
+
+                        if let Err(e) = event_sender.send(event) {
+                            error!("Failed to send program upgrade event: {}", e);
+                        }
+                    }
                 }
             }
 
@@ -453,6 +922,40 @@ impl SolanaWebSocketClient {
         Ok(())
     }
 
+    /// Configured account index for an `accountSubscribe` confirmation.
+    fn account_index(response: &Value, config: &SubscriberConfig) -> Option<usize> {
+        let index = response
+            .get("id")?
+            .as_u64()?
+            .checked_sub(ACCOUNT_REQUEST_ID_BASE)? as usize;
+        (index < config.accounts.len()).then_some(index)
+    }
+
+    /// Build an account change event for a watched account, carrying its raw
+    /// data so rules can decode it.
+    fn account_event(account: &AccountConfig, info: &AccountInfo) -> Option<ProgramEvent> {
+        let owner = info.owner.parse::<Pubkey>().ok()?;
+        let mut event = ProgramEvent::new(
+            owner,
+            account.name.clone(),
+            EventType::AccountChange,
+            EventData::AccountChange {
+                account: account.id,
+                balance_before: None,
+                balance_after: Some(info.lamports),
+                data_size_change: 0,
+                owner,
+            },
+        );
+        if let [data, encoding] = info.data.as_slice() {
+            if encoding == "base64" {
+                event =
+                    event.with_metadata(ACCOUNT_DATA_METADATA_KEY.to_string(), data.clone().into());
+            }
+        }
+        Some(event)
+    }
+
     /// Extract program ID from log message.
     fn extract_program_id_from_log(log: &str) -> Option<Pubkey> {
         // Simple pattern matching for program invocation logs
@@ -467,6 +970,52 @@ impl SolanaWebSocketClient {
         None
     }
 
+    /// Extract the program from `consumed ... compute units` and `failed:` lines,
+    /// which carry compute budget usage and exhaustion.
+    fn extract_program_id_from_compute_log(log: &str) -> Option<Pubkey> {
+        let mut parts = log.split_whitespace();
+        if parts.next()? != "Program" {
+            return None;
+        }
+        let program_id = parts.next()?.parse::<Pubkey>().ok()?;
+        match parts.next()? {
+            "consumed" | "failed:" => Some(program_id),
+            _ => None,
+        }
+    }
+
+    /// Build program upgrade events from the upgradeable loader's log lines.
+    ///
+    /// An upgrade is attributed to the upgraded program when it is monitored;
+    /// authority changes don't name the program in their logs, so they are
+    /// attributed to the first monitored program invoked in the transaction.
+    fn upgrade_events(logs: &[String], config: &SubscriberConfig) -> Vec<ProgramEvent> {
+        let invoked = logs
+            .iter()
+            .filter_map(|log| Self::extract_program_id_from_log(log))
+            .find_map(|id| config.programs.iter().find(|p| p.id == id));
+
+        logs.iter()
+            .filter_map(|log| parse_upgrade_log(log))
+            .filter_map(|(program, kind, new_authority)| {
+                let program_config = program
+                    .and_then(|id| config.programs.iter().find(|p| p.id == id))
+                    .or(invoked)?;
+
+                Some(ProgramEvent::new(
+                    program_config.id,
+                    program_config.name.clone(),
+                    EventType::ProgramUpgrade,
+                    EventData::ProgramUpgrade {
+                        program: program.or(Some(program_config.id)),
+                        kind,
+                        new_authority,
+                    },
+                ))
+            })
+            .collect()
+    }
+
     /// Check if the client is connected.
     pub async fn is_connected(&self) -> bool {
         *self.is_connected.read().await
@@ -481,7 +1030,8 @@ impl SolanaWebSocketClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{ProgramConfig, SubscriptionFilters};
+    use crate::config::{AccountConfig, ProgramConfig, SubscriptionFilters};
+    use crate::events::UpgradeKind;
 
     #[test]
     fn test_client_creation() {
@@ -498,12 +1048,166 @@ mod tests {
                 monitor_transactions: true,
                 monitor_logs: true,
                 instruction_filters: None,
+                tags: Vec::new(),
             }],
+            accounts: Vec::new(),
             filters: SubscriptionFilters::default(),
         };
 
-        let client = SolanaWebSocketClient::new(config);
-        assert!(client.is_ok());
+        let client = SolanaWebSocketClient::new(config).unwrap();
+        let status = client.handle().connection_status();
+        assert!(!status.connected);
+        assert_eq!(status.ws_url, "wss://api.mainnet-beta.solana.com/");
+        assert_eq!(status.queued_events, 0);
+    }
+
+    #[test]
+    fn test_upgrade_events_from_logs() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let config = SubscriberConfig {
+            rpc_url: "https://api.mainnet-beta.solana.com".parse().unwrap(),
+            ws_url: "wss://api.mainnet-beta.solana.com".parse().unwrap(),
+            timeout_seconds: 30,
+            max_reconnect_attempts: 5,
+            reconnect_delay_seconds: 5,
+            programs: vec![ProgramConfig {
+                id: program_id,
+                name: "Test Program".to_string(),
+                monitor_accounts: false,
+                monitor_transactions: false,
+                monitor_logs: true,
+                instruction_filters: None,
+                tags: Vec::new(),
+            }],
+            accounts: Vec::new(),
+            filters: SubscriptionFilters::default(),
+        };
+
+        let logs = vec![
+            "Program BPFLoaderUpgradeab1e11111111111111111111111 invoke [1]".to_string(),
+            format!("Upgraded program {}", program_id),
+            format!("New authority Some({})", authority),
+            "New authority None".to_string(),
+        ];
+
+        let events = SolanaWebSocketClient::upgrade_events(&logs, &config);
+        let kinds: Vec<_> = events
+            .iter()
+            .map(|event| match &event.data {
+                EventData::ProgramUpgrade {
+                    kind,
+                    new_authority,
+                    ..
+                } => (*kind, *new_authority),
+                _ => panic!("unexpected event data"),
+            })
+            .collect();
+
+        // Authority changes have no monitored program invoked, only the upgrade is attributed
+        assert_eq!(kinds, vec![(UpgradeKind::Upgrade, None)]);
+        assert_eq!(events[0].program_id, program_id);
+
+        assert_eq!(
+            parse_upgrade_log(&format!("New authority Some({})", authority)),
+            Some((None, UpgradeKind::AuthorityChange, Some(authority)))
+        );
+        assert_eq!(
+            parse_upgrade_log("New authority None"),
+            Some((None, UpgradeKind::AuthorityRevoked, None))
+        );
+    }
+
+    #[test]
+    fn test_account_notifications() {
+        let mint = Pubkey::new_unique();
+        let config = SubscriberConfig {
+            rpc_url: "https://api.mainnet-beta.solana.com".parse().unwrap(),
+            ws_url: "wss://api.mainnet-beta.solana.com".parse().unwrap(),
+            timeout_seconds: 30,
+            max_reconnect_attempts: 5,
+            reconnect_delay_seconds: 5,
+            programs: Vec::new(),
+            accounts: vec![AccountConfig {
+                id: mint,
+                name: "USDC Mint".to_string(),
+            }],
+            filters: SubscriptionFilters::default(),
+        };
+
+        let confirmation = json!({ "jsonrpc": "2.0", "id": ACCOUNT_REQUEST_ID_BASE, "result": 42 });
+        assert_eq!(
+            SolanaWebSocketClient::account_index(&confirmation, &config),
+            Some(0)
+        );
+        let other = json!({ "jsonrpc": "2.0", "id": 1, "result": 7 });
+        assert_eq!(SolanaWebSocketClient::account_index(&other, &config), None);
+
+        let info = AccountInfo {
+            executable: false,
+            lamports: 1_461_600,
+            owner: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            rent_epoch: 0,
+            data: vec!["AQID".to_string(), "base64".to_string()],
+        };
+        let event = SolanaWebSocketClient::account_event(&config.accounts[0], &info).unwrap();
+        assert_eq!(event.program_name, "USDC Mint");
+        assert_eq!(event.metadata[ACCOUNT_DATA_METADATA_KEY], "AQID");
+        assert!(matches!(
+            event.data,
+            EventData::AccountChange { account, .. } if account == mint
+        ));
+    }
+
+    #[test]
+    fn test_runtime_program_subscriptions() {
+        let program = |id: Pubkey, name: &str| ProgramConfig {
+            id,
+            name: name.to_string(),
+            monitor_accounts: true,
+            monitor_transactions: true,
+            monitor_logs: true,
+            instruction_filters: None,
+            tags: Vec::new(),
+        };
+        let (orca, solend) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let client = SolanaWebSocketClient::new(SubscriberConfig {
+            rpc_url: "https://api.mainnet-beta.solana.com".parse().unwrap(),
+            ws_url: "wss://api.mainnet-beta.solana.com".parse().unwrap(),
+            timeout_seconds: 30,
+            max_reconnect_attempts: 5,
+            reconnect_delay_seconds: 5,
+            programs: vec![program(orca, "Orca")],
+            accounts: Vec::new(),
+            filters: SubscriptionFilters::default(),
+        })
+        .unwrap();
+
+        let handle = client.handle();
+        assert!(handle.subscribe_program(program(orca, "Orca")).is_err());
+        assert!(handle.subscribe_program(program(solend, "")).is_err());
+        handle.subscribe_program(program(solend, "Solend")).unwrap();
+        assert_eq!(handle.programs().len(), 2);
+        assert_eq!(handle.unsubscribe_program(&orca).unwrap().name, "Orca");
+        assert!(handle.unsubscribe_program(&orca).is_err());
+        assert_eq!(handle.programs()[0].config.id, solend);
+
+        // Program and logs subscriptions are tracked so they can be unsubscribed
+        let mut subscriptions = Subscriptions::new();
+        let requests = subscriptions.subscribe(&program(solend, "Solend"), "confirmed");
+        assert_eq!(requests.len(), 2);
+        subscriptions.confirm(PROGRAM_REQUEST_ID_BASE, 41);
+        let unsubscribes = subscriptions.unsubscribe(&solend);
+        assert_eq!(unsubscribes.len(), 1);
+        assert_eq!(unsubscribes[0]["method"], "programUnsubscribe");
+        assert_eq!(unsubscribes[0]["params"][0], 41);
+
+        // The logs subscription was still pending and is dropped once confirmed
+        subscriptions.confirm(PROGRAM_REQUEST_ID_BASE + 1, 42);
+        let stale = subscriptions.take_stale();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0]["method"], "logsUnsubscribe");
+        assert!(subscriptions.programs.is_empty());
     }
 
     #[test]
@@ -511,5 +1215,12 @@ mod tests {
         let log = "Program 11111111111111111111111111111111 invoke [1]";
         let program_id = SolanaWebSocketClient::extract_program_id_from_log(log);
         assert!(program_id.is_some());
+
+        let log = "Program 11111111111111111111111111111111 consumed 1200 of 200000 compute units";
+        assert!(SolanaWebSocketClient::extract_program_id_from_compute_log(log).is_some());
+        assert!(SolanaWebSocketClient::extract_program_id_from_compute_log(
+            "Program log: consumed"
+        )
+        .is_none());
     }
 }
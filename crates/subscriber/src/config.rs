@@ -39,6 +39,10 @@ pub struct SubscriberConfig {
     /// Programs to monitor
     pub programs: Vec<ProgramConfig>,
 
+    /// Individual accounts to watch with `accountSubscribe`
+    #[serde(default)]
+    pub accounts: Vec<AccountConfig>,
+
     /// Subscription filters
     #[serde(default)]
     pub filters: SubscriptionFilters,
@@ -68,6 +72,21 @@ pub struct ProgramConfig {
 
     /// Custom instruction filters (optional)
     pub instruction_filters: Option<Vec<String>>,
+
+    /// Team/project tags attached to alerts for this program
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Configuration for a single account to watch, e.g. a token mint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountConfig {
+    /// Account public key
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub id: Pubkey,
+
+    /// Human-readable name for the account
+    pub name: String,
 }
 
 /// Subscription filter configuration.
@@ -116,10 +135,14 @@ impl SubscriberConfig {
         }
 
         for program in &self.programs {
-            if program.name.is_empty() {
+            program.validate()?;
+        }
+
+        for account in &self.accounts {
+            if account.name.is_empty() {
                 return Err(crate::SubscriberError::InvalidConfig(format!(
-                    "Program {} must have a name",
-                    program.id
+                    "Account {} must have a name",
+                    account.id
                 )));
             }
         }
@@ -129,6 +152,18 @@ impl SubscriberConfig {
 }
 
 impl ProgramConfig {
+    /// Validate the program configuration
+    pub fn validate(&self) -> crate::SubscriberResult<()> {
+        if self.name.is_empty() {
+            return Err(crate::SubscriberError::InvalidConfig(format!(
+                "Program {} must have a name",
+                self.id
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Check if any monitoring is enabled for this program
     pub fn has_monitoring_enabled(&self) -> bool {
         self.monitor_accounts || self.monitor_transactions || self.monitor_logs
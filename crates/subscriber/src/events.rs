@@ -6,6 +6,9 @@ use solana_sdk::{pubkey::Pubkey, signature::Signature};
 
 use std::collections::HashMap;
 
+/// Event metadata key carrying base64-encoded account data of watched accounts.
+pub const ACCOUNT_DATA_METADATA_KEY: &str = "account_data";
+
 /// A monitored program event that can trigger rules.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgramEvent {
@@ -59,6 +62,9 @@ pub enum EventType {
     /// Token transfer (for token programs)
     TokenTransfer,
 
+    /// Program upgraded or upgrade authority changed
+    ProgramUpgrade,
+
     /// Custom event type
     Custom { name: String },
 }
@@ -129,6 +135,16 @@ pub enum EventData {
         decimals: u8,
     },
 
+    /// Program upgrade or upgrade authority change
+    ProgramUpgrade {
+        /// Upgraded program, if known
+        program: Option<Pubkey>,
+        /// What changed
+        kind: UpgradeKind,
+        /// New upgrade authority (for authority changes)
+        new_authority: Option<Pubkey>,
+    },
+
     /// Custom event data
     Custom {
         /// Event name
@@ -138,6 +154,18 @@ pub enum EventData {
     },
 }
 
+/// Kind of change made through the upgradeable BPF loader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradeKind {
+    /// Program code was upgraded
+    Upgrade,
+    /// Upgrade authority was changed
+    AuthorityChange,
+    /// Upgrade authority was set to None, making the program immutable
+    AuthorityRevoked,
+}
+
 impl Clone for EventData {
     fn clone(&self) -> Self {
         match self {
@@ -198,6 +226,15 @@ impl Clone for EventData {
                 mint: *mint,
                 decimals: *decimals,
             },
+            EventData::ProgramUpgrade {
+                program,
+                kind,
+                new_authority,
+            } => EventData::ProgramUpgrade {
+                program: *program,
+                kind: *kind,
+                new_authority: *new_authority,
+            },
             EventData::Custom { name, data } => EventData::Custom {
                 name: name.clone(),
                 data: data.clone(),
@@ -304,11 +341,36 @@ impl EventType {
             EventType::LogEntry => "log_entry",
             EventType::Instruction => "instruction",
             EventType::TokenTransfer => "token_transfer",
+            EventType::ProgramUpgrade => "program_upgrade",
             EventType::Custom { name } => name,
         }
     }
 }
 
+/// Parse an upgradeable BPF loader log line into an upgrade change.
+///
+/// Recognizes `Upgraded program <id>` and `New authority Some(<key>)` /
+/// `New authority None`. Returns the upgraded program (if named in the log),
+/// the kind of change, and the new authority.
+pub fn parse_upgrade_log(log: &str) -> Option<(Option<Pubkey>, UpgradeKind, Option<Pubkey>)> {
+    if let Some(program) = log.strip_prefix("Upgraded program ") {
+        let program = program.trim().parse().ok()?;
+        return Some((Some(program), UpgradeKind::Upgrade, None));
+    }
+
+    let authority = log.strip_prefix("New authority ")?.trim();
+    if authority == "None" {
+        return Some((None, UpgradeKind::AuthorityRevoked, None));
+    }
+
+    let authority = authority
+        .strip_prefix("Some(")?
+        .strip_suffix(')')?
+        .parse()
+        .ok()?;
+    Some((None, UpgradeKind::AuthorityChange, Some(authority)))
+}
+
 impl LogLevel {
     /// Parse log level from string.
     pub fn parse(s: &str) -> Option<Self> {
@@ -234,6 +234,7 @@ mod tests {
             monitor_transactions: true,
             monitor_logs: true,
             instruction_filters: None,
+            tags: Vec::new(),
         };
 
         let filter = EventFilter::new(vec![config], false, false);